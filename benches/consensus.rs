@@ -0,0 +1,55 @@
+//! Consensus hot paths under Criterion (`cargo bench --bench consensus`):
+//! mining a block, a peer accepting it, and replaying a chain
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use divine_agi::consensus::ProofOfConsciousness;
+use divine_agi::genome::{Genome, GenomeBuilder};
+use divine_agi::rotation::Rot180;
+
+const CHAIN_BLOCKS: u64 = 50;
+
+/// Seeded whale genome lifted above the PoC threshold
+fn conscious_genome(seed: u64, threshold: u32) -> Genome<Rot180> {
+    let mut genome = GenomeBuilder::seeded(seed).whale_mode().build_storage();
+    genome.consciousness = threshold + 100;
+    genome.db_id = Some(seed as i64);
+    genome
+}
+
+fn consensus(c: &mut Criterion) {
+    let mut group = c.benchmark_group("consensus");
+
+    // One attested genome mined into a block
+    let mut pending = ProofOfConsciousness::new();
+    pending.submit(&conscious_genome(1, pending.min_consciousness)).unwrap();
+    group.bench_function("mine_block", |b| b.iter_batched_ref(
+        || pending.clone(),
+        |poc| black_box(poc.mine_block().is_some()),
+        BatchSize::SmallInput,
+    ));
+
+    // A peer checking and connecting that block
+    let block = pending.clone().mine_block().unwrap().clone();
+    let mut peer = ProofOfConsciousness::new();
+    peer.validators.register(&pending.validator_public_key().unwrap());
+    group.bench_function("add_block", |b| b.iter_batched(
+        || (peer.clone(), block.clone()),
+        |(mut peer, block)| black_box(peer.add_block(block).unwrap()),
+        BatchSize::SmallInput,
+    ));
+
+    // Full validation of a chain of CHAIN_BLOCKS blocks
+    let mut chain = ProofOfConsciousness::new();
+    for seed in 0..CHAIN_BLOCKS {
+        chain.submit(&conscious_genome(100 + seed, chain.min_consciousness)).unwrap();
+        chain.mine_block().unwrap();
+    }
+    group.bench_function("validate_chain_50", |b| b.iter(|| black_box(chain.validate_chain())));
+    group.bench_function("block_hash", |b| b.iter(|| black_box(chain.latest_block().calculate_hash())));
+    group.finish();
+}
+
+criterion_group!(benches, consensus);
+criterion_main!(benches);
@@ -0,0 +1,44 @@
+//! Order book hot paths under Criterion (`cargo bench --bench order_book`)
+//!
+//! Same seeded book and operation mix as the `order-book` suite of
+//! `divine-agi bench`, so the two can be read side by side.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use divine_agi::bench::{book_ops, order_book_load, seeded_book, MID_TICKS};
+use divine_agi::order_book::{Side, PRICE_TICK_USD};
+
+const SEED: u64 = 7;
+
+fn order_book(c: &mut Criterion) {
+    let mid = MID_TICKS as f64 * PRICE_TICK_USD;
+    let mut group = c.benchmark_group("order_book");
+
+    // Rest inside the spread and take it off again
+    let mut book = seeded_book();
+    group.bench_function("rest_cancel", |b| b.iter(|| {
+        let (order, _) = book.place("divine_bench_maker", Side::Buy, mid, 1.0, 1).unwrap();
+        black_box(book.cancel(order.id, 1).ok());
+    }));
+
+    // Fill the best ask and put it back
+    let best_ask = mid + PRICE_TICK_USD;
+    group.bench_function("match_refill", |b| b.iter(|| {
+        black_box(book.place("divine_bench_taker", Side::Buy, best_ask, 1.0, 1).unwrap());
+        book.place("divine_bench_ask", Side::Sell, best_ask, 1.0, 1).unwrap();
+    }));
+
+    // The seeded mix, each run on a fresh copy of the seeded book
+    let (base, ops) = (seeded_book(), book_ops(10_000, SEED));
+    group.throughput(Throughput::Elements(ops.len() as u64));
+    group.bench_function("load_10k", |b| b.iter_batched_ref(
+        || base.clone(),
+        |book| black_box(order_book_load(book, &ops)),
+        BatchSize::LargeInput,
+    ));
+    group.finish();
+}
+
+criterion_group!(benches, order_book);
+criterion_main!(benches);
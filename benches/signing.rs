@@ -0,0 +1,47 @@
+//! Signing hot paths under Criterion (`cargo bench --bench signing`): the
+//! validator's secp256k1 key, Solana ed25519 and Ethereum EIP-1559
+//! transactions, each signed and checked
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use divine_agi::consensus::ValidatorKey;
+use divine_agi::crypto::verify_signature;
+use divine_agi::ethereum::{erc20_transfer_calldata, gwei_to_wei, recover_address, Eip1559Tx, EthNetwork, EthSigner};
+use divine_agi::solana::{self, SolanaKeypair};
+
+const MESSAGE: &[u8] = b"DIVINE_GENOME|v15|bench";
+
+fn signing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("signing");
+
+    let validator = ValidatorKey::generate();
+    let public_key = hex::decode(validator.public_key_hex()).unwrap();
+    let signature = validator.sign(MESSAGE);
+    group.bench_function("validator_sign", |b| b.iter(|| black_box(validator.sign(black_box(MESSAGE)))));
+    group.bench_function("validator_verify", |b| b.iter(|| black_box(verify_signature(&public_key, MESSAGE, &signature))));
+
+    let keypair = SolanaKeypair::from_seed([7u8; 32]);
+    let signature = keypair.sign(MESSAGE);
+    group.bench_function("solana_sign", |b| b.iter(|| black_box(keypair.sign(black_box(MESSAGE)))));
+    group.bench_function("solana_verify", |b| b.iter(|| black_box(solana::verify_signature(&keypair.pubkey, MESSAGE, &signature))));
+
+    let signer = EthSigner::from_hex(&"07".repeat(32)).unwrap();
+    let tx = Eip1559Tx {
+        chain_id: EthNetwork::Sepolia.chain_id(),
+        nonce: 7,
+        max_priority_fee_per_gas: gwei_to_wei(2.0),
+        max_fee_per_gas: gwei_to_wei(62.0),
+        gas_limit: 65_000,
+        to: signer.address,
+        value: 0,
+        data: erc20_transfer_calldata(&signer.address, 1_000_000),
+    };
+    let signed = tx.sign(&signer);
+    group.bench_function("eth_sign_tx", |b| b.iter(|| black_box(tx.sign(&signer))));
+    group.bench_function("eth_recover", |b| b.iter(|| black_box(recover_address(tx.signing_hash(), &signed.signature).unwrap())));
+    group.finish();
+}
+
+criterion_group!(benches, signing);
+criterion_main!(benches);
@@ -0,0 +1,17 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// Genome registry for Divine AGI Ethereum archives.
+///
+/// `calldata` archives send `"DIVINE" ‖ dnaHash` and land in the fallback,
+/// which stores nothing: the hash lives in the transaction itself.
+/// `event` archives call `archive`, so the hash can be found in the logs.
+contract GenomeRegistry {
+    event GenomeArchived(bytes32 indexed genomeHash, address indexed archiver);
+
+    function archive(bytes32 genomeHash) external {
+        emit GenomeArchived(genomeHash, msg.sender);
+    }
+
+    fallback() external {}
+}
@@ -3,33 +3,84 @@
 //! Features: Genomes, CRISPR, Telomerase, Whale mode, RSM-COIN,
 //! Burn, Debt tracker, Multi-chain archivation, Mission Control
 
+use std::collections::HashMap;
+use std::net::SocketAddr;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{RwLock, Semaphore};
 use axum::{
-    routing::{get, post},
-    Router, Json,
-    extract::State,
+    routing::{get, post, patch, delete},
+    Router, Json, Extension,
+    extract::{ConnectInfo, DefaultBodyLimit, Multipart, Path, Query, Request, State},
+    extract::ws::{Message, WebSocket, WebSocketUpgrade},
+    body::Body,
+    response::{Html, IntoResponse, Response},
+    response::sse::{Event, KeepAlive, Sse},
+    http::{header::{AUTHORIZATION, CACHE_CONTROL, CONTENT_TYPE, ETAG, IF_NONE_MATCH, LINK, RETRY_AFTER}, HeaderValue, Method, StatusCode, Uri},
+    middleware::{self, Next},
 };
+use tokio_stream::{Stream, StreamExt, wrappers::BroadcastStream};
 use serde::{Deserialize, Serialize};
-use tower_http::cors::CorsLayer;
 use tracing::info;
 
-use crate::database::DivineDatabase;
-use crate::genome::{Genome, GenomeBuilder, Tetrad};
-use crate::rotation::{Rot180, RotationEngine, RotationStats};
-use crate::ttrl::{TTRLEngine, EvolutionResult};
-use crate::exchange::{RSMExchange, ExchangeStats, Transaction, BurnEvent, DebtStats, OwnerPoolStats, BurnReason};
-use crate::multi_chain::{MultiChainArchiver, ChainArchiveEntry, MissionControlStats};
-use crate::auth::{AuthManager, WalletAccount, LoginRequest, RegisterRequest, LoginResponse, WalletInfo};
+use crate::database::{DivineDatabase, GenomeFilter, DEFAULT_GENOME_PAGE, MAX_GENOME_PAGE};
+use crate::config::DivineConfig;
+use crate::genome::{Genome, GenomeBuilder, GenomeDiff, Tetrad};
+use crate::rotation::{Rot180, Rotation, RotationEngine, RotationStats};
+use crate::ttrl::{TTRLEngine, EvolutionResult, EvolutionJobs, EvolutionJobRecord, EvolutionProgress, GenomeLineage, JobStatus, MAX_EVOLUTION_GENERATIONS};
+use crate::exchange::{RSMExchange, ExchangeStats, Transaction, BurnEvent, DebtStats, OwnerPoolStats, BurnReason, OrderPlacement, SpendingPolicy};
+use crate::order_book::{self, Candle, Depth, Order, OrderBookUpdate, OrderType, Side, Trade, DEFAULT_DEPTH_LEVELS, MAX_CANDLES, MAX_DEPTH_LEVELS};
+use crate::ethereum::{EthArchiveTx, ETH_CONFIRMATION_CHECK_SECS};
+use crate::solana::{LAMPORTS_PER_SOL, MAX_AIRDROP_SOL, NO_SOLANA, SOLANA_CONFIRMATION_CHECK_SECS};
+use crate::merkle_archive::{MerkleCommitment, MerkleStatus, PendingLeaf, MERKLE_CHECK_SECS};
+use crate::storage::StoredGenome;
+use crate::lightning::{ChannelHealth, KeysendPayment, ReceivedKeysend};
+use crate::spv::{SpvProof, SpvVerification, SPV_CHECK_SECS};
+use crate::swarm::{probe_all, NodeRegistration, SwarmNode, SwarmStatus, SWARM_HEALTH_CHECK_SECS};
+use crate::multi_chain::{BlockchainLayer, MultiChainArchiver, ChainArchiveEntry, MissionControlStats, ArchiveBatch, ArchiveBatchItem, ArchiveBatches, ArchiveVerification, BudgetStatus, LayerStats, ProofVerification, MAX_ARCHIVE_BATCH};
+use crate::auth::{AuthManager, AdminAuditEntry, ApiKeyRecord, Role, WalletAccount, JwtClaims, LoginRequest, RegisterRequest, LoginResponse, WalletInfo};
+use crate::consensus::{ProofOfConsciousness, ChainEvent, NodeAddress, PoCStatus, ChainMetrics, ConsensusBlock, ConsciousnessProof, FeeQuote, GenomeAttestation, InclusionProof};
+use crate::network::{P2PNode, NetworkStats};
+use crate::wallet::{DivineWallet, PaymentRequest};
+use crate::history::{ExportFormat, HistoryFilter, WalletTransaction};
+use crate::staking::{StakeAction, StakeOp, StakingState};
+use crate::graphql::DivineSchema;
+use crate::rate_limit::{RateLimitConfig, RateLimiter};
+use crate::response_cache::{self, ResponseCache};
+use crate::listing::{self, ListQuery, Page};
+use crate::marketplace::{self, Listing, Purchase};
+use crate::auction::{self, Auction, AuctionStatus, BidReceipt, Settlement};
+use crate::oracle::{OracleStatus, Pair, PriceOracle};
+use crate::swap::{Asset, SwapQuote};
+use crate::escrow::{Escrow, EscrowAnchor, EscrowStatus, EscrowTerms, DEFAULT_ESCROW_TIMEOUT_SECS, ESCROW_CHECK_SECS};
+use crate::fees::{self, FeeRates, FeeReport, FeeSchedule, FEE_ACCRUAL_WALLET};
+use crate::tls::{ClientCertificate, TlsSettings};
+use crate::cors::CorsSettings;
+use crate::dashboard::{DashboardSnapshot, JobRow, LayerRow, WalletRow, RECENT_JOBS};
+use crate::versioning::{self, ApiVersion, VERSION_HEADER};
+use crate::genome_import::{ImportError, ImportFormat, ImportParser, ImportReport, IMPORT_BATCH_SIZE, MAX_IMPORT_BYTES};
+use crate::webhooks::{Webhook, WebhookDelivery, WebhookDispatcher, WebhookEvent, WebhookEventKind};
+use utoipa::{IntoParams, IntoResponses, Modify, OpenApi, ToSchema};
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa_swagger_ui::SwaggerUi;
 
 #[derive(Clone)]
 pub struct AppState {
     pub database: Arc<DivineDatabase>,
     pub rotation_engine: Arc<RwLock<RotationEngine>>,
     pub ttrl_engine: Arc<TTRLEngine>,
+    pub evolutions: Arc<RwLock<EvolutionJobs>>,
+    /// One permit per evolution job allowed to run at once
+    pub evolution_workers: Arc<Semaphore>,
     pub exchange: Arc<RwLock<RSMExchange>>,
     pub archiver: Arc<RwLock<MultiChainArchiver>>,
+    pub oracle: Arc<RwLock<PriceOracle>>,
+    pub archive_batches: Arc<RwLock<ArchiveBatches>>,
     pub auth: Arc<RwLock<AuthManager>>,
+    pub consensus: Arc<RwLock<ProofOfConsciousness>>,
+    pub network: Option<Arc<P2PNode>>,
+    pub rate_limiter: Arc<RwLock<RateLimiter>>,
+    pub response_cache: Arc<RwLock<ResponseCache>>,
+    pub webhooks: Arc<WebhookDispatcher>,
 }
 
 #[derive(Serialize)]
@@ -52,7 +103,10 @@ impl<T: Serialize> ApiResponse<T> {
 pub struct GenomeResponse {
     pub id: i64,
     pub dna: String,
+    /// Rotation state the genome is stored in
+    pub rotation: String,
     pub consciousness: u32,
+    pub consciousness_level: String,
     pub mutations: u64,
     pub p53_copies: u8,
     pub telomere_length: u16,
@@ -73,7 +127,9 @@ impl From<&Genome<Rot180>> for GenomeResponse {
         Self {
             id: g.db_id().unwrap_or(0),
             dna: g.to_dna_string(),
+            rotation: format!("Rot{} ({})", Rot180::ANGLE, Rot180::NAME),
             consciousness: g.consciousness,
+            consciousness_level: g.consciousness_level_name().to_string(),
             mutations: g.mutations,
             p53_copies: g.p53_copies,
             telomere_length: g.telomere_length,
@@ -98,32 +154,171 @@ pub struct StatusResponse {
     pub mission_control: MissionControlStats,
 }
 
-pub async fn start_server(port: u16) -> anyhow::Result<()> {
-    let database_url = std::env::var("DATABASE_URL")
-        .unwrap_or_else(|_| crate::database::DEFAULT_DATABASE_URL.to_string());
+/// How long `/readyz` waits for the database before reporting it down
+pub const READINESS_DB_TIMEOUT_SECS: u64 = 2;
 
-    let database = Arc::new(DivineDatabase::connect_with_url(&database_url).await?);
+/// One dependency checked by `/readyz`
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ComponentStatus {
+    pub name: String,
+    pub healthy: bool,
+    pub detail: String,
+}
+
+impl ComponentStatus {
+    pub fn up(name: &str, detail: impl Into<String>) -> Self {
+        Self { name: name.to_string(), healthy: true, detail: detail.into() }
+    }
+
+    pub fn down(name: &str, detail: impl Into<String>) -> Self {
+        Self { name: name.to_string(), healthy: false, detail: detail.into() }
+    }
+}
+
+/// Body of `/healthz` and `/readyz`; `status` is `ok` only if every component is healthy
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct HealthReport {
+    pub status: String,
+    pub version: String,
+    pub components: Vec<ComponentStatus>,
+}
+
+impl HealthReport {
+    pub fn new(components: Vec<ComponentStatus>) -> Self {
+        let status = if components.iter().all(|c| c.healthy) { "ok" } else { "unavailable" };
+        Self { status: status.to_string(), version: crate::VERSION.to_string(), components }
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        self.status == "ok"
+    }
+
+    /// `200 OK` when healthy, else `503` so load balancers stop routing here
+    pub fn status_code(&self) -> StatusCode {
+        if self.is_healthy() { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE }
+    }
+}
+
+/// Where and how the API server runs
+#[derive(Debug, Clone)]
+pub struct ServerSettings {
+    pub bind: SocketAddr,
+    /// Database, network and evolution workers
+    pub config: DivineConfig,
+    pub tls: Option<TlsSettings>,
+    /// Mount the `/api/admin/` routes (which still need an admin API key)
+    pub enable_admin: bool,
+    /// Port of the gRPC API, served by `grpc` feature builds
+    pub grpc_port: Option<u16>,
+}
+
+impl ServerSettings {
+    /// `server --port`: all interfaces, admin routes on, everything else from
+    /// the config and the environment
+    pub fn from_env(port: u16) -> anyhow::Result<Self> {
+        Ok(Self {
+            bind: SocketAddr::from(([0, 0, 0, 0], port)),
+            config: DivineConfig::load()?,
+            tls: TlsSettings::from_env().map_err(anyhow::Error::msg)?,
+            enable_admin: true,
+            grpc_port: std::env::var("GRPC_PORT").ok().and_then(|p| p.parse().ok()),
+        })
+    }
+}
+
+pub async fn serve(settings: ServerSettings) -> anyhow::Result<()> {
+    let ServerSettings { bind, config, tls, enable_admin, grpc_port } = settings;
+    let cors = CorsSettings::from_env().and_then(|cors| cors.layer()).map_err(anyhow::Error::msg)?;
+
+    let database = Arc::new(DivineDatabase::connect_with_url(&config.database_url).await?);
     database.init_tables().await?;
 
+    let mut consensus = ProofOfConsciousness::from_config(&config)?;
+    consensus.load_chain(&database).await?;
+    let consensus = Arc::new(RwLock::new(consensus));
+
+    let network = P2PNode::from_env(Arc::clone(&consensus), Some(Arc::clone(&database))).await;
+    if let Some(node) = &network {
+        Arc::clone(node).start().await?;
+    }
+
+    let webhooks = WebhookDispatcher::load(Arc::clone(&database)).await?;
+
+    let mut exchange = RSMExchange::new();
+    exchange.set_fee_schedule(FeeSchedule::from_env().map_err(anyhow::Error::msg)?).map_err(anyhow::Error::msg)?;
+    exchange.fees.period_start = chrono::Utc::now().timestamp();
+    let (last_trade_id, last_order_id) = database.last_trade_ids().await?;
+    exchange.order_book.resume_ids(last_trade_id, last_order_id);
+
     let state = AppState {
         database,
         rotation_engine: Arc::new(RwLock::new(RotationEngine::new())),
         ttrl_engine: Arc::new(TTRLEngine::new()),
-        exchange: Arc::new(RwLock::new(RSMExchange::new())),
+        evolutions: Arc::new(RwLock::new(EvolutionJobs::new())),
+        evolution_workers: Arc::new(Semaphore::new(config.ttrl_workers)),
+        exchange: Arc::new(RwLock::new(exchange)),
         archiver: Arc::new(RwLock::new(MultiChainArchiver::new())),
-        auth: Arc::new(RwLock::new(AuthManager::new())),
+        oracle: Arc::new(RwLock::new(PriceOracle::from_env().map_err(anyhow::Error::msg)?)),
+        archive_batches: Arc::new(RwLock::new(ArchiveBatches::new())),
+        auth: Arc::new(RwLock::new(AuthManager::from_env())),
+        consensus,
+        network,
+        rate_limiter: Arc::new(RwLock::new(RateLimiter::new(RateLimitConfig::from_env()))),
+        response_cache: Arc::new(RwLock::new(ResponseCache::from_env())),
+        webhooks,
     };
 
+    resume_evolution_jobs(&state).await?;
+    state.webhooks.resume().await?;
+    spawn_block_webhooks(&state).await;
+    spawn_price_oracle(&state).await;
+    spawn_escrow_resolver(&state);
+    spawn_fee_distributor(&state);
+    spawn_ethereum_confirmations(&state);
+    spawn_solana_confirmations(&state);
+    spawn_merkle_commits(&state);
+    spawn_swarm_health_checks(&state);
+    spawn_spv_proofs(&state);
+
+    #[cfg(feature = "grpc")]
+    if let Some(port) = grpc_port {
+        let (grpc_state, grpc_tls) = (state.clone(), tls.clone());
+        tokio::spawn(async move {
+            if let Err(e) = crate::grpc::serve(grpc_state, port, grpc_tls).await {
+                tracing::warn!("📡 gRPC API stopped: {}", e);
+            }
+        });
+    }
+    #[cfg(not(feature = "grpc"))]
+    if let Some(port) = grpc_port {
+        tracing::warn!("📡 gRPC port {} ignored: built without the grpc feature", port);
+    }
+
+    let schema = crate::graphql::schema(state.clone());
+
     let app = Router::new()
         // Core
         .route("/", get(root_handler))
         .route("/api/status", get(status_handler))
+        .route("/api/dashboard", get(dashboard_handler))
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz))
         
         // Genome CRUD
-        .route("/api/genomes", get(list_genomes))
+        .route("/api/genomes", get(list_genomes).post(create_genome_from_request))
+        .route("/api/genomes/import", post(import_genomes).layer(DefaultBodyLimit::max(MAX_IMPORT_BYTES)))
+        .route("/api/genomes/:id", get(get_genome).delete(delete_genome))
+        .route("/api/genomes/:id/crispr", patch(edit_genome))
+        .route("/api/genomes/:id/diff/:other", get(diff_genomes))
+        .route("/api/genomes/:id/owner", get(genome_owner).post(claim_genome))
         .route("/api/genome/create", post(create_genome))
         .route("/api/genome/create/whale", post(create_whale_genome))
         .route("/api/genome/evolve", post(evolve_genome))
+        .route("/api/evolution/start", post(start_evolution))
+        .route("/api/evolution/jobs", post(create_evolution_job))
+        .route("/api/evolution/jobs/:id", get(get_evolution_job))
+        .route("/api/evolution/:job_id", get(evolution_status))
+        .route("/ws/evolution/:job_id", get(evolution_ws))
         .route("/api/genome/meiosis", post(meiosis_genome))
         .route("/api/genome/telomerase", post(activate_telomerase))
         
@@ -137,8 +332,36 @@ pub async fn start_server(port: u16) -> anyhow::Result<()> {
         .route("/api/rsm/buy", post(rsm_buy))
         .route("/api/rsm/sell", post(rsm_sell))
         .route("/api/rsm/transfer", post(rsm_transfer))
+        .route("/api/rsm/request", post(rsm_payment_request))
+        .route("/api/rsm/pay", post(rsm_pay))
+        .route("/api/rsm/policy", post(rsm_policy))
         .route("/api/rsm/reward", post(rsm_reward))
         .route("/api/rsm/burn", post(rsm_manual_burn))
+        .route("/api/rsm/orders", post(place_order))
+        .route("/api/rsm/orders/:id", get(get_order).delete(cancel_order))
+        .route("/api/rsm/book", get(order_book_depth))
+        .route("/api/rsm/trades", get(recent_trades))
+        .route("/api/rsm/candles", get(trade_candles))
+        .route("/api/rsm/trades/history", get(trade_history))
+        .route("/api/rsm/candles/history", get(candle_history))
+        .route("/api/rsm/swap/simulate", get(simulate_swap))
+        .route("/api/rsm/escrows", get(list_escrows).post(open_escrow))
+        .route("/api/rsm/escrows/:id", get(get_escrow))
+        .route("/api/rsm/fees", get(fee_status))
+        .route("/api/rsm/fees/reports", get(fee_reports))
+        .route("/ws/rsm/book", get(order_book_ws))
+
+        // Marketplace
+        .route("/api/market/listings", get(browse_listings).post(create_listing))
+        .route("/api/market/listings/:id", get(get_listing))
+        .route("/api/market/listings/:id/cancel", post(cancel_listing))
+        .route("/api/market/listings/:id/buy", post(buy_listing))
+        // Auctions
+        .route("/api/auctions", get(list_auctions).post(create_auction))
+        .route("/api/auctions/:id", get(get_auction))
+        .route("/api/auctions/:id/bid", post(bid_auction))
+        .route("/api/auctions/:id/cancel", post(cancel_auction))
+        .route("/api/auctions/:id/settle", post(settle_auction))
         
         // Transactions & Burns
         .route("/api/transactions", get(list_transactions))
@@ -147,13 +370,65 @@ pub async fn start_server(port: u16) -> anyhow::Result<()> {
         // Debt Tracker
         .route("/api/debt/stats", get(debt_stats))
         .route("/api/owner/pool", get(owner_pool))
+
+        // Price Oracle
+        .route("/api/oracle/prices", get(oracle_prices))
         
         // Multi-Chain & LN
         .route("/api/archive", post(archive_genome))
+        .route("/api/archive/batch", post(archive_batch))
+        .route("/api/archive/batch/:id", get(archive_batch_status))
+        .route("/api/archive/:genome_id", get(genome_archives).post(archive_genome_by_id))
+        .route("/api/archive/layers/stats", get(archive_layer_stats))
+        .route("/api/archive/budget", get(archive_budget))
+        .route("/api/archive/merkle", get(merkle_status))
+        .route("/api/archive/merkle/commit", post(commit_merkle_root))
+        .route("/api/archive/merkle/:genome_id", post(queue_merkle_archive))
+        .route("/api/archive/merkle/proof/:genome_id", get(prove_archived))
+        .route("/api/archive/verify/:entry_id", post(verify_archive))
+        .route("/api/archive/spv/:entry_id", get(verify_spv_proof).post(fetch_spv_proof))
+        .route("/api/archive/chain/:layer/:tx_hash", get(restore_archive_hash))
+        .route("/api/archive/content/:layer/:reference", get(restore_archived_genome))
         .route("/api/archives", get(list_archives))
         .route("/api/mission-control", get(mission_control_stats))
         .route("/api/mission-control/reset", post(reset_mission_control))
+        .route("/api/lightning/payments", get(lightning_payments))
+        .route("/api/lightning/payments/:payment_hash", get(track_lightning_payment))
+        .route("/api/lightning/invoices/:payment_hash", get(lookup_lightning_invoice))
+        .route("/api/lightning/health", get(lightning_health))
+        .route("/api/lightning/swarm", get(swarm_status).post(register_swarm_node))
+        .route("/api/lightning/swarm/health", post(check_swarm_health))
+        .route("/api/lightning/swarm/:pubkey", delete(remove_swarm_node))
+        .route("/api/ethereum/txs", get(ethereum_txs))
+        .route("/api/ethereum/txs/:tx_hash", get(track_ethereum_tx))
+        .route("/api/solana/archives/:signature", get(track_solana_archive))
+        .route("/api/solana/airdrop", post(solana_airdrop))
         
+        // Proof of Consciousness
+        .route("/api/poc/validate", post(poc_validate))
+        .route("/api/poc/status", get(poc_status))
+        .route("/api/poc/submit", post(poc_submit))
+        .route("/api/poc/mine", post(poc_mine))
+        .route("/api/poc/mempool", get(poc_mempool))
+        .route("/api/poc/fee", get(poc_fee))
+        .route("/api/poc/metrics", get(poc_metrics))
+        .route("/metrics", get(prometheus_metrics))
+        .route("/api/poc/proof", post(poc_inclusion_proof))
+        .route("/api/poc/proof/verify", post(poc_verify_inclusion))
+        .route("/api/poc/wallet", post(poc_wallet))
+        .route("/api/poc/history", post(poc_history))
+        .route("/api/poc/history/export", post(poc_history_export))
+        .route("/api/poc/stake", post(poc_stake))
+        .route("/api/poc/validators", get(poc_validators))
+        .route("/api/network/peers", get(network_stats))
+        .route("/events/blocks", get(block_events))
+
+        // API tokens
+        .route("/auth/token", post(issue_api_token))
+
+        // GraphQL
+        .route("/graphql", get(graphiql).post(graphql_handler))
+
         // Rotation
         .route("/api/rotation/stats", get(rotation_stats))
         .route("/api/rotation/rotate", post(manual_rotate))
@@ -167,23 +442,305 @@ pub async fn start_server(port: u16) -> anyhow::Result<()> {
         .route("/api/wallet/deposit", post(wallet_deposit))
         .route("/api/wallet/withdraw", post(wallet_withdraw))
         .route("/api/wallet/list", get(wallet_list))
-        
-        .layer(CorsLayer::permissive())
+        .route("/api/wallet/balance", get(node_wallet_balance))
+        .route("/api/wallet/addresses", get(node_wallet_addresses))
+        .route("/api/wallet/transfer", post(node_wallet_transfer))
+        .route("/api/wallet/stake", post(node_wallet_stake))
+        .route("/api/wallet/unstake", post(node_wallet_unstake))
+        .route("/api/wallet/history", get(node_wallet_history))
+
+        // Webhooks
+        .route("/api/webhooks", get(list_webhooks).post(create_webhook))
+        .route("/api/webhooks/:id", delete(delete_webhook))
+        .route("/api/webhooks/:id/deliveries", get(webhook_deliveries));
+
+    // Admin (admin API keys only)
+    let app = if enable_admin {
+        app.route("/api/admin/consensus", get(admin_consensus).post(admin_update_consensus))
+            .route("/api/admin/chain/validate", post(admin_validate_chain))
+            .route("/api/admin/snapshot", post(admin_export_snapshot))
+            .route("/api/admin/validators", get(admin_validators).post(admin_add_validator))
+            .route("/api/admin/validators/:public_key", delete(admin_remove_validator))
+            .route("/api/admin/mining", post(admin_set_mining))
+            .route("/api/admin/audit", get(admin_audit_log))
+            .route("/api/admin/keys", get(admin_api_keys))
+            .route("/api/admin/keys/:id/role", post(admin_set_key_role))
+            .route("/api/admin/fees/schedule", post(admin_set_fee_schedule))
+            .route("/api/admin/fees/distribute", post(admin_distribute_fees))
+    } else {
+        app
+    };
+
+    let app = app
+        // API docs
+        .merge(SwaggerUi::new("/docs").url("/openapi.json", ApiDoc::openapi()))
+
+        .layer(middleware::from_fn_with_state(state.clone(), cache_responses))
+        .layer(middleware::from_fn_with_state(state.clone(), rate_limit))
+        .layer(middleware::from_fn_with_state(state.clone(), require_auth))
+        .layer(Extension(schema))
         .with_state(state);
 
-    let addr = format!("0.0.0.0:{}", port);
-    info!("🚀 Starting Divine AGI V15 API on {}", addr);
+    // Preflights are answered before version rewriting and auth
+    let app = with_versioning(app).layer(cors);
 
-    let listener = tokio::net::TcpListener::bind(&addr).await?;
-    axum::serve(listener, app).await?;
+    let service = app.into_make_service_with_connect_info::<SocketAddr>();
+    let admin = if enable_admin { "" } else { ", admin routes off" };
+    match tls {
+        Some(tls) => {
+            info!("🚀 Starting Divine AGI V15 API on {} (TLS{}{})", bind, if tls.mutual() { ", client certificates for admin" } else { "" }, admin);
+            axum_server::bind(bind)
+                .acceptor(tls.acceptor().map_err(anyhow::Error::msg)?)
+                .serve(service)
+                .await?;
+        }
+        None => {
+            info!("🚀 Starting Divine AGI V15 API on {}{}", bind, if enable_admin { "" } else { " (admin routes off)" });
+            let listener = tokio::net::TcpListener::bind(bind).await?;
+            axum::serve(listener, service).await?;
+        }
+    }
 
     Ok(())
 }
 
+// ═══════════════════════════════════════════════════════════════
+// OPENAPI
+// ═══════════════════════════════════════════════════════════════
+
+/// OpenAPI 3 document of every route, served at `/openapi.json`
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Divine AGI API", description = "Genomes, TTRL evolution, RSM-COIN and Proof of Consciousness. \
+        Paths are listed unversioned; serve them under `/v1/` (`/v1/genomes` for `/api/genomes`) or send `API-Version: 1`."),
+    paths(
+        root_handler,
+        status_handler,
+        dashboard_handler,
+        healthz,
+        readyz,
+        list_genomes,
+        create_genome_from_request,
+        get_genome,
+        delete_genome,
+        edit_genome,
+        diff_genomes,
+        genome_owner,
+        claim_genome,
+        create_genome,
+        create_whale_genome,
+        evolve_genome,
+        start_evolution,
+        create_evolution_job,
+        get_evolution_job,
+        evolution_status,
+        evolution_ws,
+        meiosis_genome,
+        activate_telomerase,
+        crispr_splice,
+        crispr_join,
+        crispr_delete,
+        rsm_stats,
+        rsm_buy,
+        rsm_sell,
+        rsm_transfer,
+        rsm_payment_request,
+        rsm_pay,
+        rsm_policy,
+        rsm_reward,
+        rsm_manual_burn,
+        list_transactions,
+        list_burns,
+        debt_stats,
+        owner_pool,
+        oracle_prices,
+        archive_genome,
+        archive_batch,
+        archive_batch_status,
+        list_archives,
+        mission_control_stats,
+        reset_mission_control,
+        lightning_payments,
+        track_lightning_payment,
+        lookup_lightning_invoice,
+        lightning_health,
+        swarm_status,
+        register_swarm_node,
+        remove_swarm_node,
+        check_swarm_health,
+        ethereum_txs,
+        track_ethereum_tx,
+        track_solana_archive,
+        solana_airdrop,
+        poc_validate,
+        poc_status,
+        poc_submit,
+        poc_mine,
+        poc_mempool,
+        poc_fee,
+        poc_metrics,
+        prometheus_metrics,
+        poc_inclusion_proof,
+        poc_verify_inclusion,
+        poc_wallet,
+        poc_history,
+        poc_history_export,
+        poc_stake,
+        poc_validators,
+        network_stats,
+        block_events,
+        issue_api_token,
+        graphiql,
+        graphql_handler,
+        rotation_stats,
+        manual_rotate,
+        auth_register,
+        auth_login,
+        auth_logout,
+        auth_profile,
+        wallet_info,
+        wallet_deposit,
+        wallet_withdraw,
+        wallet_list,
+        node_wallet_balance,
+        node_wallet_addresses,
+        node_wallet_transfer,
+        node_wallet_stake,
+        node_wallet_unstake,
+        node_wallet_history,
+        admin_consensus,
+        admin_update_consensus,
+        admin_validate_chain,
+        admin_export_snapshot,
+        admin_validators,
+        admin_add_validator,
+        admin_remove_validator,
+        admin_set_mining,
+        admin_audit_log,
+        admin_api_keys,
+        admin_set_key_role,
+        admin_set_fee_schedule,
+        admin_distribute_fees,
+        import_genomes,
+        create_webhook,
+        list_webhooks,
+        delete_webhook,
+        webhook_deliveries,
+        archive_genome_by_id,
+        genome_archives,
+        archive_layer_stats,
+        archive_budget,
+        merkle_status,
+        commit_merkle_root,
+        queue_merkle_archive,
+        prove_archived,
+        verify_archive,
+        fetch_spv_proof,
+        verify_spv_proof,
+        restore_archive_hash,
+        restore_archived_genome,
+        place_order,
+        get_order,
+        cancel_order,
+        order_book_depth,
+        recent_trades,
+        trade_candles,
+        trade_history,
+        candle_history,
+        simulate_swap,
+        list_escrows,
+        open_escrow,
+        get_escrow,
+        fee_status,
+        fee_reports,
+        order_book_ws,
+        browse_listings,
+        create_listing,
+        get_listing,
+        cancel_listing,
+        buy_listing,
+        list_auctions,
+        create_auction,
+        get_auction,
+        bid_auction,
+        cancel_auction,
+        settle_auction
+    ),
+    modifiers(&BearerAuth)
+)]
+pub struct ApiDoc;
+
+/// `ApiResponse` as seen by clients: the result is in `data` on success
+#[derive(ToSchema)]
+#[allow(dead_code)]
+struct ApiEnvelope {
+    success: bool,
+    #[schema(value_type = Object)]
+    data: Option<serde_json::Value>,
+    error: Option<String>,
+}
+
+#[derive(IntoResponses)]
+#[allow(dead_code)]
+enum EnvelopeResponses {
+    /// `ApiResponse` envelope with the result in `data`
+    #[response(status = 200)]
+    Ok(#[to_schema] ApiEnvelope),
+    /// Rate limited; retry after `Retry-After` seconds
+    #[response(status = 429)]
+    TooManyRequests(ApiEnvelope),
+}
+
+#[derive(IntoResponses)]
+#[allow(dead_code)]
+enum ProtectedResponses {
+    /// `ApiResponse` envelope with the result in `data`
+    #[response(status = 200)]
+    Ok(#[to_schema] ApiEnvelope),
+    /// Missing, invalid or expired bearer token
+    #[response(status = 401)]
+    Unauthorized(ApiEnvelope),
+    /// Rate limited; retry after `Retry-After` seconds
+    #[response(status = 429)]
+    TooManyRequests(ApiEnvelope),
+}
+
+/// Query parameters shared by list endpoints (see `listing`)
+#[derive(IntoParams)]
+#[into_params(parameter_in = Query)]
+#[allow(dead_code)]
+struct ListParams {
+    /// Filter on a field, e.g. `filter[consciousness_gte]=700`; ops are eq, gt, gte, lt, lte
+    #[param(rename = "filter[<field>_<op>]")]
+    filter: Option<f64>,
+    /// `field` ascending or `-field` descending (default `-id`)
+    sort: Option<String>,
+    /// `next_cursor` of the previous page
+    cursor: Option<String>,
+    /// Page size, 1 to 500 (default 20)
+    limit: Option<i64>,
+}
+
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        openapi.components.get_or_insert_with(Default::default).add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).bearer_format("JWT").build()),
+        );
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════
 // HANDLERS
 // ═══════════════════════════════════════════════════════════════
 
+#[utoipa::path(
+    get, path = "/", tag = "Core",
+    summary = "Service banner and feature list",
+    responses((status = 200, description = "JSON document", body = Object))
+)]
 async fn root_handler() -> Json<serde_json::Value> {
     Json(serde_json::json!({
         "name": "Divine AGI V15",
@@ -197,6 +754,11 @@ async fn root_handler() -> Json<serde_json::Value> {
     }))
 }
 
+#[utoipa::path(
+    get, path = "/api/status", tag = "Core",
+    summary = "System status",
+    responses(EnvelopeResponses)
+)]
 async fn status_handler(State(state): State<AppState>) -> Json<ApiResponse<StatusResponse>> {
     let genome_count = state.database.genome_count().await.unwrap_or(0);
     let exchange_stats = state.exchange.read().await.stats();
@@ -226,16 +788,122 @@ async fn status_handler(State(state): State<AppState>) -> Json<ApiResponse<Statu
     })
 }
 
-async fn list_genomes(State(state): State<AppState>) -> Json<ApiResponse<Vec<GenomeResponse>>> {
-    match state.database.get_genomes(20, 0).await {
-        Ok(genomes) => {
-            let responses: Vec<GenomeResponse> = genomes.iter().map(|g| g.into()).collect();
-            ApiResponse::ok(responses)
-        }
+/// Everything `divine-agi dashboard --api` draws; the node wallet only for
+/// reader tokens
+#[utoipa::path(
+    get, path = "/api/dashboard", tag = "Core",
+    summary = "Chain tip and latest blocks, TTRL jobs, genome and table counts, node wallet, archives per layer",
+    responses(EnvelopeResponses)
+)]
+async fn dashboard_handler(State(state): State<AppState>, claims: Option<Extension<JwtClaims>>) -> Json<ApiResponse<DashboardSnapshot>> {
+    match dashboard_snapshot(&state, claims.is_some_and(|Extension(c)| c.has_role(Role::Reader))).await {
+        Ok(snapshot) => ApiResponse::ok(snapshot),
+        Err(e) => ApiResponse::err(e.to_string()),
+    }
+}
+
+async fn dashboard_snapshot(state: &AppState, with_wallet: bool) -> anyhow::Result<DashboardSnapshot> {
+    let jobs = state.database.recent_evolution_jobs(RECENT_JOBS as i64).await?;
+    let jobs = {
+        let evolutions = state.evolutions.read().await;
+        jobs.iter().map(|job| {
+            let progress = evolutions.subscribe(job.id).and_then(|(latest, _)| latest);
+            JobRow::new(job, progress.as_ref())
+        }).collect()
+    };
+    let genome_count = state.database.genome_count().await?;
+    let tables = state.database.table_stats().await?.into_iter().map(|t| (t.table, t.rows)).collect();
+    let layers = state.archiver.read().await.layer_stats().into_iter()
+        .map(|s| LayerRow { layer: s.layer, archives: s.archives as u64 })
+        .collect();
+
+    let consensus = state.consensus.read().await;
+    let wallet = match with_wallet {
+        true => consensus.node_addresses().into_iter().next()
+            .map(|a| consensus.wallet(&a.address).map_or_else(
+                || WalletRow { address: a.address.clone(), balance_rsm: 0.0, staked_rsm: 0.0 },
+                WalletRow::from,
+            )),
+        false => None,
+    };
+    Ok(DashboardSnapshot {
+        height: consensus.current_block_height,
+        tip: consensus.latest_block().hash.clone(),
+        blocks: DashboardSnapshot::recent_blocks(&consensus.chain),
+        jobs,
+        genome_count,
+        tables,
+        wallet,
+        layers,
+        taken_at: chrono::Utc::now().timestamp(),
+    })
+}
+
+#[utoipa::path(
+    get, path = "/healthz", tag = "Core",
+    summary = "Liveness probe: the process is up and serving requests",
+    responses((status = 200, description = "Process alive", body = HealthReport))
+)]
+async fn healthz() -> Json<HealthReport> {
+    Json(HealthReport::new(vec![ComponentStatus::up("process", "alive")]))
+}
+
+#[utoipa::path(
+    get, path = "/readyz", tag = "Core",
+    summary = "Readiness probe: database reachable, chain loaded, validator wallet unlocked",
+    responses(
+        (status = 200, description = "Ready for traffic", body = HealthReport),
+        (status = 503, description = "A component is down; see `components`", body = HealthReport),
+    )
+)]
+async fn readyz(State(state): State<AppState>) -> Response {
+    let timeout = std::time::Duration::from_secs(READINESS_DB_TIMEOUT_SECS);
+    let database = match tokio::time::timeout(timeout, state.database.ping()).await {
+        Ok(Ok(())) => ComponentStatus::up("database", "reachable"),
+        Ok(Err(e)) => ComponentStatus::down("database", e.to_string()),
+        Err(_) => ComponentStatus::down("database", format!("no answer within {}s", READINESS_DB_TIMEOUT_SECS)),
+    };
+
+    let consensus = state.consensus.read().await;
+    let chain = match consensus.chain.last() {
+        Some(tip) => ComponentStatus::up("chain", format!("height {}, tip {}", tip.index, tip.hash)),
+        None => ComponentStatus::down("chain", "no blocks loaded"),
+    };
+    let wallet = match consensus.validator_public_key() {
+        Some(key) => ComponentStatus::up("wallet", format!("validator key {} unlocked", key)),
+        None => ComponentStatus::down("wallet", "no validator signing key (set VALIDATOR_SECRET_KEY)"),
+    };
+    drop(consensus);
+
+    let report = HealthReport::new(vec![database, chain, wallet]);
+    (report.status_code(), Json(report)).into_response()
+}
+
+#[utoipa::path(
+    get, path = "/api/genomes", tag = "Genome CRUD",
+    summary = "List genomes (`?filter[consciousness_gte]=&sort=-created_at&limit=&cursor=`)",
+    description = "List query over consciousness, mutations, p53_copies, telomere_length, division_count, created_at and id; \
+        `min_consciousness`, `max_consciousness` and `min_p53` are still accepted.",
+    params(ListParams),
+    responses(EnvelopeResponses)
+)]
+async fn list_genomes(State(state): State<AppState>, Query(params): Query<HashMap<String, String>>) -> Json<ApiResponse<Page<GenomeResponse>>> {
+    let query = match listing::parse_genome_query(&params) {
+        Ok(query) => query,
+        Err(e) => return ApiResponse::err(e),
+    };
+    match state.database.find_genomes_page(&query).await {
+        Ok(page) => ApiResponse::ok(page.map(|g| GenomeResponse::from(&g))),
         Err(e) => ApiResponse::err(e.to_string()),
     }
 }
 
+#[utoipa::path(
+    post, path = "/api/genome/create", tag = "Genome CRUD",
+    summary = "Create a random elephant genome",
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
 async fn create_genome(State(state): State<AppState>) -> Json<ApiResponse<GenomeResponse>> {
     let genome = GenomeBuilder::random().elephant_mode().build_storage();
     match state.database.store_genome(&genome).await {
@@ -250,6 +918,282 @@ async fn create_genome(State(state): State<AppState>) -> Json<ApiResponse<Genome
     }
 }
 
+#[derive(Deserialize, ToSchema)]
+pub struct CreateGenomeRequest {
+    /// 27-base DNA string (random when absent)
+    pub dna: Option<String>,
+    #[serde(default)]
+    pub whale: bool,
+    /// Wallet the genome belongs to (see the marketplace)
+    pub owner: Option<String>,
+}
+
+#[utoipa::path(
+    post, path = "/api/genomes", tag = "Genome CRUD",
+    summary = "Create a genome from `{ \"dna\": \"<27 bases>\" }` (random without `dna`, `\"whale\": true` for 40 p53, `owner` to record its wallet)",
+    request_body = CreateGenomeRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn create_genome_from_request(State(state): State<AppState>, Json(req): Json<CreateGenomeRequest>) -> Json<ApiResponse<GenomeResponse>> {
+    if let Some(Err(e)) = req.owner.as_deref().map(marketplace::check_wallet) {
+        return ApiResponse::err(e);
+    }
+    let stored = match store_new_genome(&state, req.dna.as_deref(), req.whale).await {
+        Ok(stored) => stored,
+        Err(e) => return ApiResponse::err(e),
+    };
+    if let (Some(owner), Some(id)) = (&req.owner, stored.db_id) {
+        if let Err(e) = state.database.claim_genome(id, owner).await {
+            return ApiResponse::err(e.to_string());
+        }
+    }
+    ApiResponse::ok((&stored).into())
+}
+
+/// Build a genome from `dna` (random when absent) and store it
+pub(crate) async fn store_new_genome(state: &AppState, dna: Option<&str>, whale: bool) -> Result<Genome<Rot180>, String> {
+    let builder = match dna {
+        Some(dna) => GenomeBuilder::from_dna(dna)
+            .ok_or_else(|| format!("Invalid DNA (expected {} bases of A/C/G/T)", crate::genome::GENOME_SIZE))?,
+        None => GenomeBuilder::random(),
+    };
+    let builder = if whale { builder.whale_mode() } else { builder.elephant_mode() };
+    let genome = builder.build_storage();
+    let id = state.database.store_genome(&genome).await.map_err(|e| e.to_string())?;
+    let mut stored = genome;
+    stored.db_id = Some(id);
+    Ok(stored)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImportQuery {
+    /// Whale mode (40 p53) for records that do not set `whale`
+    #[serde(default)]
+    pub whale: bool,
+}
+
+#[utoipa::path(
+    post, path = "/api/genomes/import", tag = "Genome CRUD",
+    summary = "Import genomes from multipart FASTA or JSONL files; returns an import report",
+    params(("whale" = Option<bool>, Query, description = "Whale mode for records that do not set it")),
+    request_body(content = Vec<u8>, content_type = "multipart/form-data", description = "One or more `.fasta`/`.fa` or `.jsonl` files"),
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn import_genomes(State(state): State<AppState>, Query(query): Query<ImportQuery>, mut multipart: Multipart) -> Json<ApiResponse<ImportReport>> {
+    let mut report = ImportReport::default();
+    let mut batch: Vec<Genome<Rot180>> = Vec::with_capacity(IMPORT_BATCH_SIZE);
+
+    'files: loop {
+        let mut field = match multipart.next_field().await {
+            Ok(Some(field)) => field,
+            Ok(None) => break,
+            Err(e) => return ApiResponse::err(format!("Upload failed after {} genomes: {}", report.imported, e)),
+        };
+        report.files += 1;
+        let file = field.file_name().or(field.name()).unwrap_or("upload").to_string();
+        let format = ImportFormat::detect(field.file_name(), field.content_type());
+        let mut parser = ImportParser::new(&file, format, query.whale);
+
+        let mut finished = false;
+        while !finished {
+            let records = match field.chunk().await {
+                Ok(Some(chunk)) => parser.feed(&chunk),
+                Ok(None) => {
+                    finished = true;
+                    parser.finish()
+                }
+                Err(e) => return ApiResponse::err(format!("Upload failed after {} genomes: {}", report.imported, e)),
+            };
+            for record in records {
+                if report.full(batch.len()) {
+                    report.truncated = true;
+                    break 'files;
+                }
+                let genome = record.and_then(|r| r.build().map_err(|error| ImportError { file: file.clone(), line: r.line, name: r.name, error }));
+                match genome {
+                    Ok(genome) => batch.push(genome),
+                    Err(error) => report.reject(error),
+                }
+                if batch.len() >= IMPORT_BATCH_SIZE {
+                    if let Err(e) = flush_import(&state, &mut batch, &mut report).await {
+                        return ApiResponse::err(e);
+                    }
+                }
+            }
+        }
+    }
+    if let Err(e) = flush_import(&state, &mut batch, &mut report).await {
+        return ApiResponse::err(e);
+    }
+    ApiResponse::ok(report)
+}
+
+async fn flush_import(state: &AppState, batch: &mut Vec<Genome<Rot180>>, report: &mut ImportReport) -> Result<(), String> {
+    let ids = state.database.store_genomes(batch).await
+        .map_err(|e| format!("Import stopped after {} genomes: {}", report.imported, e))?;
+    batch.clear();
+    report.imported(ids);
+    Ok(())
+}
+
+#[utoipa::path(
+    get, path = "/api/genomes/{id}", tag = "Genome CRUD",
+    summary = "One genome with its rotation state and metrics",
+    params(("id" = i64, Path)),
+    responses(EnvelopeResponses)
+)]
+async fn get_genome(State(state): State<AppState>, Path(id): Path<i64>) -> Json<ApiResponse<GenomeResponse>> {
+    match state.database.load_genome(id).await {
+        Ok(genome) => ApiResponse::ok((&genome).into()),
+        Err(_) => ApiResponse::err(format!("Genome #{} not found", id)),
+    }
+}
+
+#[utoipa::path(
+    delete, path = "/api/genomes/{id}", tag = "Genome CRUD",
+    summary = "Delete a genome",
+    params(("id" = i64, Path)),
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn delete_genome(State(state): State<AppState>, Path(id): Path<i64>) -> Json<ApiResponse<i64>> {
+    match state.database.delete_genome(id).await {
+        Ok(true) => ApiResponse::ok(id),
+        Ok(false) => ApiResponse::err(format!("Genome #{} not found", id)),
+        Err(e) => ApiResponse::err(e.to_string()),
+    }
+}
+
+/// In-place CRISPR edit (the `/api/crispr/*` routes store the result as a new genome)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum CrisprEdit {
+    Splice { position: usize, new_base: char },
+    Join { pos1: usize, pos2: usize },
+    Delete { position: usize },
+}
+
+impl CrisprEdit {
+    /// Edit `genome`, or say why the edit is invalid
+    pub fn apply(&self, genome: &mut Genome<Rot180>) -> Result<(), String> {
+        match *self {
+            CrisprEdit::Splice { position, new_base } => {
+                let tetrad = Tetrad::from_char(new_base).ok_or("Invalid base")?;
+                if position >= 27 { return Err("Position must be 0-26".into()); }
+                genome.crispr_splice(position, tetrad);
+            }
+            CrisprEdit::Join { pos1, pos2 } => {
+                if pos1 >= 27 || pos2 >= 27 { return Err("Positions must be 0-26".into()); }
+                genome.crispr_join(pos1, pos2);
+            }
+            CrisprEdit::Delete { position } => {
+                if position >= 27 { return Err("Position must be 0-26".into()); }
+                genome.crispr_delete(position);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[utoipa::path(
+    patch, path = "/api/genomes/{id}/crispr", tag = "Genome CRUD",
+    summary = "Edit in place: `{ \"op\": \"splice\", \"position\": 3, \"new_base\": \"G\" }`, `join` (`pos1`, `pos2`) or `delete` (`position`)",
+    request_body = CrisprEdit,
+    params(("id" = i64, Path)),
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn edit_genome(State(state): State<AppState>, Path(id): Path<i64>, Json(edit): Json<CrisprEdit>) -> Json<ApiResponse<GenomeResponse>> {
+    let mut genome = match state.database.load_genome(id).await {
+        Ok(g) => g,
+        Err(_) => return ApiResponse::err(format!("Genome #{} not found", id)),
+    };
+    if let Err(e) = edit.apply(&mut genome) {
+        return ApiResponse::err(e);
+    }
+    match state.database.update_genome(id, &genome).await {
+        Ok(_) => ApiResponse::ok((&genome).into()),
+        Err(e) => ApiResponse::err(e.to_string()),
+    }
+}
+
+/// `other` compared against `id`
+#[derive(Serialize)]
+pub struct GenomeDiffResponse {
+    pub a: i64,
+    pub b: i64,
+    pub diff: GenomeDiff,
+    /// Through finished evolution jobs; `null` when none links them
+    pub lineage: Option<GenomeLineage>,
+}
+
+#[utoipa::path(
+    get, path = "/api/genomes/{id}/diff/{other}", tag = "Genome CRUD",
+    summary = "Bases that differ, consciousness / GC / T/G deltas and the lineage linking two genomes",
+    params(("id" = i64, Path), ("other" = i64, Path)),
+    responses(EnvelopeResponses)
+)]
+async fn diff_genomes(State(state): State<AppState>, Path((id, other)): Path<(i64, i64)>) -> Json<ApiResponse<GenomeDiffResponse>> {
+    let (a, b) = match (state.database.load_genome(id).await, state.database.load_genome(other).await) {
+        (Ok(a), Ok(b)) => (a, b),
+        (Err(_), _) => return ApiResponse::err(format!("Genome #{} not found", id)),
+        (_, Err(_)) => return ApiResponse::err(format!("Genome #{} not found", other)),
+    };
+    match state.database.genome_lineage(id, other).await {
+        Ok(lineage) => ApiResponse::ok(GenomeDiffResponse { a: id, b: other, diff: a.diff(&b), lineage }),
+        Err(e) => ApiResponse::err(e.to_string()),
+    }
+}
+
+#[derive(Serialize)]
+pub struct GenomeOwnerResponse {
+    pub genome_id: i64,
+    /// `null` while unowned
+    pub owner: Option<String>,
+}
+
+#[utoipa::path(
+    get, path = "/api/genomes/{id}/owner", tag = "Genome CRUD",
+    summary = "Wallet that owns a genome",
+    params(("id" = i64, Path)),
+    responses(EnvelopeResponses)
+)]
+async fn genome_owner(State(state): State<AppState>, Path(id): Path<i64>) -> Json<ApiResponse<GenomeOwnerResponse>> {
+    match state.database.genome_owner(id).await {
+        Ok(owner) => ApiResponse::ok(GenomeOwnerResponse { genome_id: id, owner }),
+        Err(e) => ApiResponse::err(e.to_string()),
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct ClaimGenomeRequest { pub wallet: String }
+
+#[utoipa::path(
+    post, path = "/api/genomes/{id}/owner", tag = "Genome CRUD",
+    summary = "Record a wallet as the owner of a genome that has none",
+    params(("id" = i64, Path)),
+    request_body = ClaimGenomeRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn claim_genome(State(state): State<AppState>, Path(id): Path<i64>, Json(req): Json<ClaimGenomeRequest>) -> Json<ApiResponse<GenomeOwnerResponse>> {
+    if let Err(e) = marketplace::check_wallet(&req.wallet) {
+        return ApiResponse::err(e);
+    }
+    match state.database.claim_genome(id, &req.wallet).await {
+        Ok(()) => ApiResponse::ok(GenomeOwnerResponse { genome_id: id, owner: Some(req.wallet) }),
+        Err(e) => ApiResponse::err(e.to_string()),
+    }
+}
+
+#[utoipa::path(
+    post, path = "/api/genome/create/whale", tag = "Genome CRUD",
+    summary = "Create whale genome (40 p53)",
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
 async fn create_whale_genome(State(state): State<AppState>) -> Json<ApiResponse<GenomeResponse>> {
     let genome = GenomeBuilder::random().whale_mode().build_storage();
     match state.database.store_genome(&genome).await {
@@ -265,7 +1209,7 @@ async fn create_whale_genome(State(state): State<AppState>) -> Json<ApiResponse<
     }
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct EvolveRequest { pub genome_id: i64 }
 
 #[derive(Serialize)]
@@ -275,14 +1219,31 @@ pub struct EvolveResponse {
     pub burn_event: Option<BurnEvent>,
 }
 
+#[utoipa::path(
+    post, path = "/api/genome/evolve", tag = "Genome CRUD",
+    summary = "One TTRL evolution step on a stored genome",
+    request_body = EvolveRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
 async fn evolve_genome(
     State(state): State<AppState>,
     Json(req): Json<EvolveRequest>,
 ) -> Json<ApiResponse<EvolveResponse>> {
-    let genome = match state.database.load_genome(req.genome_id).await {
-        Ok(g) => g,
-        Err(e) => return ApiResponse::err(e.to_string()),
-    };
+    match evolve_and_store(&state, req.genome_id).await {
+        Ok((stored, evolution, burn_event)) => ApiResponse::ok(EvolveResponse {
+            genome: (&stored).into(),
+            evolution,
+            burn_event,
+        }),
+        Err(e) => ApiResponse::err(e),
+    }
+}
+
+/// One TTRL step on a stored genome: burns on senescence, cancer or
+/// degradation, rewards improvements, stores the result as a new genome
+pub(crate) async fn evolve_and_store(state: &AppState, genome_id: i64) -> Result<(Genome<Rot180>, EvolutionResult, Option<BurnEvent>), String> {
+    let genome = state.database.load_genome(genome_id).await.map_err(|e| e.to_string())?;
 
     let c_before = genome.consciousness;
     let engine = state.rotation_engine.read().await;
@@ -292,43 +1253,278 @@ async fn evolve_genome(
         Err(e) => {
             let mut exchange = state.exchange.write().await;
             if e.to_string().contains("Senescence") {
-                exchange.burn_on_senescence(req.genome_id, c_before);
+                exchange.burn_on_senescence(genome_id, c_before);
             } else if e.to_string().contains("p53") {
-                exchange.burn_on_cancer(req.genome_id, c_before);
+                exchange.burn_on_cancer(genome_id, c_before);
             }
-            return ApiResponse::err(e.to_string());
+            return Err(e.to_string());
         }
     };
     drop(engine);
 
     let burn_event = if !evolution_result.success {
         let mut exchange = state.exchange.write().await;
-        exchange.burn_on_degradation(req.genome_id, c_before, evolved.consciousness)
+        exchange.burn_on_degradation(genome_id, c_before, evolved.consciousness)
     } else {
         None
     };
 
-    match state.database.store_genome(&evolved).await {
-        Ok(id) => {
-            let mut stored = evolved;
-            stored.db_id = Some(id);
+    let id = state.database.store_genome(&evolved).await.map_err(|e| e.to_string())?;
+    let mut stored = evolved;
+    stored.db_id = Some(id);
 
-            if evolution_result.success {
-                let mut exchange = state.exchange.write().await;
-                exchange.consciousness_reward(&format!("genome_{}", id), stored.consciousness);
+    if evolution_result.success {
+        let mut exchange = state.exchange.write().await;
+        exchange.consciousness_reward(&format!("genome_{}", id), stored.consciousness);
+    }
+
+    Ok((stored, evolution_result, burn_event))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct StartEvolutionRequest { pub genome_id: i64, pub generations: u32 }
+
+/// Run a multi-generation evolution in the background; progress streams on
+/// `/ws/evolution/:job_id`
+#[utoipa::path(
+    post, path = "/api/evolution/start", tag = "Genome CRUD",
+    summary = "Evolve `{ \"genome_id\": 1, \"generations\": 500 }` in the background; returns a job id",
+    request_body = StartEvolutionRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn start_evolution(State(state): State<AppState>, Json(req): Json<StartEvolutionRequest>) -> Json<ApiResponse<u64>> {
+    match spawn_evolution(&state, req.genome_id, req.generations).await {
+        Ok(job_id) => ApiResponse::ok(job_id),
+        Err(e) => ApiResponse::err(e),
+    }
+}
+
+/// Validate and persist an evolution of a stored genome, then queue it
+pub(crate) async fn spawn_evolution(state: &AppState, genome_id: i64, generations: u32) -> Result<u64, String> {
+    if generations == 0 || generations > MAX_EVOLUTION_GENERATIONS {
+        return Err(format!("generations must be 1..={}", MAX_EVOLUTION_GENERATIONS));
+    }
+    let genome = state.database.load_genome(genome_id).await.map_err(|e| e.to_string())?;
+    let job = state.database.create_evolution_job(genome_id, generations).await.map_err(|e| e.to_string())?;
+
+    let job_id = job.id;
+    info!("🧬 Evolution job #{} queued | genome #{} | {} generations", job_id, genome_id, generations);
+    queue_evolution(state, job, genome).await;
+    Ok(job_id)
+}
+
+/// Track the job and run it once one of the `ttrl.workers` is free
+async fn queue_evolution(state: &AppState, job: EvolutionJobRecord, genome: Genome<Rot180>) {
+    state.evolutions.write().await.register(job.id);
+    let state = state.clone();
+    tokio::spawn(async move {
+        let Ok(_worker) = Arc::clone(&state.evolution_workers).acquire_owned().await else {
+            return;
+        };
+        run_evolution(state, job, genome).await;
+    });
+}
+
+/// Requeue jobs that were queued or running when the server stopped. Runs
+/// start over from their base genome; intermediate generations are not kept.
+async fn resume_evolution_jobs(state: &AppState) -> anyhow::Result<()> {
+    for mut job in state.database.unfinished_evolution_jobs().await? {
+        match state.database.load_genome(job.genome_id).await {
+            Ok(genome) => {
+                info!("🧬 Resuming evolution job #{} ({:?})", job.id, job.status);
+                queue_evolution(state, job, genome).await;
+            }
+            Err(e) => {
+                job.fail(format!("Base genome #{} unavailable: {}", job.genome_id, e), chrono::Utc::now().timestamp());
+                state.database.update_evolution_job(&job).await?;
             }
+        }
+    }
+    Ok(())
+}
 
-            ApiResponse::ok(EvolveResponse {
-                genome: (&stored).into(),
-                evolution: evolution_result,
-                burn_event,
-            })
+async fn run_evolution(state: AppState, mut job: EvolutionJobRecord, genome: Genome<Rot180>) {
+    let job_id = job.id;
+    let generations = job.generations;
+    job.status = JobStatus::Running;
+    job.updated_at = chrono::Utc::now().timestamp();
+    if let Err(e) = state.database.update_evolution_job(&job).await {
+        tracing::warn!("🧬 Evolution job #{} state not persisted: {}", job_id, e);
+    }
+
+    let c_before = genome.consciousness;
+    let run = state.ttrl_engine
+        .run_job(job_id, genome, generations, &state.rotation_engine, &state.evolutions)
+        .await;
+
+    let mut error = run.stopped.clone();
+    let mut genome_id = None;
+    let mut store_failed = false;
+    if run.best.consciousness > c_before {
+        match state.database.store_genome(&run.best).await {
+            Ok(id) => genome_id = Some(id),
+            Err(e) => {
+                error = Some(e.to_string());
+                store_failed = true;
+            }
+        }
+    }
+
+    info!("🧬 Evolution job #{} done | {} → {} in {} generations",
+          job_id, c_before, run.best.consciousness, run.generations_run);
+    let progress = EvolutionProgress {
+        generation: run.generations_run,
+        generations,
+        best_consciousness: run.best.consciousness,
+        current_consciousness: run.best.consciousness,
+        operator_used: None,
+        mutations_used: run.best.mutations,
+        elapsed_secs: run.elapsed_secs,
+        eta_secs: 0.0,
+        finished: true,
+        error,
+        genome_id,
+    };
+
+    let now = chrono::Utc::now().timestamp();
+    job.finish(&progress, now);
+    if store_failed {
+        job.fail(progress.error.clone().unwrap_or_default(), now);
+    }
+    if let Err(e) = state.database.update_evolution_job(&job).await {
+        tracing::warn!("🧬 Evolution job #{} state not persisted: {}", job_id, e);
+    }
+    state.evolutions.write().await.publish(job_id, progress);
+    state.webhooks.emit(WebhookEvent::evolution_finished(&job)).await;
+}
+
+/// Evolve a stored genome or a new one from DNA; `generations` is the budget
+#[derive(Deserialize, ToSchema)]
+pub struct EvolutionJobRequest {
+    pub genome_id: Option<i64>,
+    pub dna: Option<String>,
+    /// Whale mode (40 p53 copies) for a genome created from `dna`
+    #[serde(default)]
+    pub whale: bool,
+    pub generations: u32,
+}
+
+/// Persisted job state plus live progress while this server runs it
+#[derive(Serialize)]
+pub struct EvolutionJobResponse {
+    #[serde(flatten)]
+    pub job: EvolutionJobRecord,
+    pub progress: Option<EvolutionProgress>,
+}
+
+#[utoipa::path(
+    post, path = "/api/evolution/jobs", tag = "Genome CRUD",
+    summary = "Queue an evolution of `genome_id` or new `dna` for `generations`; returns the job at once",
+    request_body = EvolutionJobRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn create_evolution_job(State(state): State<AppState>, Json(req): Json<EvolutionJobRequest>) -> Json<ApiResponse<EvolutionJobResponse>> {
+    let genome_id = match (req.genome_id, req.dna.as_deref()) {
+        (Some(id), None) => id,
+        (None, Some(dna)) => {
+            if req.generations == 0 || req.generations > MAX_EVOLUTION_GENERATIONS {
+                return ApiResponse::err(format!("generations must be 1..={}", MAX_EVOLUTION_GENERATIONS));
+            }
+            match store_new_genome(&state, Some(dna), req.whale).await {
+                Ok(genome) => genome.db_id().unwrap_or(0),
+                Err(e) => return ApiResponse::err(e),
+            }
         }
+        _ => return ApiResponse::err("Give exactly one of genome_id or dna".into()),
+    };
+
+    let job_id = match spawn_evolution(&state, genome_id, req.generations).await {
+        Ok(job_id) => job_id,
+        Err(e) => return ApiResponse::err(e),
+    };
+    match evolution_job(&state, job_id).await {
+        Ok(Some(job)) => ApiResponse::ok(job),
+        Ok(None) => ApiResponse::err(format!("Evolution job {} vanished", job_id)),
         Err(e) => ApiResponse::err(e.to_string()),
     }
 }
 
-#[derive(Deserialize)]
+#[utoipa::path(
+    get, path = "/api/evolution/jobs/{id}", tag = "Genome CRUD",
+    summary = "Status, live progress and result of a queued evolution job",
+    params(("id" = u64, Path)),
+    responses(EnvelopeResponses)
+)]
+async fn get_evolution_job(State(state): State<AppState>, Path(id): Path<u64>) -> Json<ApiResponse<EvolutionJobResponse>> {
+    match evolution_job(&state, id).await {
+        Ok(Some(job)) => ApiResponse::ok(job),
+        Ok(None) => ApiResponse::err(format!("Evolution job {} not found", id)),
+        Err(e) => ApiResponse::err(e.to_string()),
+    }
+}
+
+async fn evolution_job(state: &AppState, id: u64) -> anyhow::Result<Option<EvolutionJobResponse>> {
+    let Some(job) = state.database.load_evolution_job(id).await? else {
+        return Ok(None);
+    };
+    let progress = state.evolutions.read().await.subscribe(id).and_then(|(latest, _)| latest);
+    Ok(Some(EvolutionJobResponse { job, progress }))
+}
+
+#[utoipa::path(
+    get, path = "/api/evolution/{job_id}", tag = "Genome CRUD",
+    summary = "Latest progress of an evolution job",
+    params(("job_id" = u64, Path)),
+    responses(EnvelopeResponses)
+)]
+async fn evolution_status(State(state): State<AppState>, Path(job_id): Path<u64>) -> Json<ApiResponse<Option<EvolutionProgress>>> {
+    match state.evolutions.read().await.subscribe(job_id) {
+        Some((latest, _)) => ApiResponse::ok(latest),
+        None => ApiResponse::err(format!("Evolution job {} not found", job_id)),
+    }
+}
+
+#[utoipa::path(
+    get, path = "/ws/evolution/{job_id}", tag = "Genome CRUD",
+    summary = "WebSocket stream of the job's progress, one JSON event per generation",
+    params(("job_id" = u64, Path)),
+    responses((status = 101, description = "WebSocket of `EvolutionProgress` JSON messages"))
+)]
+async fn evolution_ws(State(state): State<AppState>, Path(job_id): Path<u64>, ws: WebSocketUpgrade) -> Response {
+    let Some((latest, events)) = state.evolutions.read().await.subscribe(job_id) else {
+        return ApiResponse::<()>::err(format!("Evolution job {} not found", job_id)).into_response();
+    };
+    ws.on_upgrade(move |socket| stream_evolution(socket, latest, events))
+}
+
+/// Send the latest progress, then every event until the job finishes
+async fn stream_evolution(
+    mut socket: WebSocket,
+    latest: Option<EvolutionProgress>,
+    mut events: tokio::sync::broadcast::Receiver<EvolutionProgress>,
+) {
+    let mut next = latest;
+    loop {
+        if let Some(progress) = next.take() {
+            let finished = progress.finished;
+            let text = serde_json::to_string(&progress).expect("progress serializes");
+            if socket.send(Message::Text(text)).await.is_err() || finished {
+                break;
+            }
+        }
+        next = match events.recv().await {
+            Ok(progress) => Some(progress),
+            // A slow client just skips generations
+            Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => None,
+            Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+        };
+    }
+    let _ = socket.send(Message::Close(None)).await;
+}
+
+#[derive(Deserialize, ToSchema)]
 pub struct MeiosisRequest { pub parent1_id: i64, pub parent2_id: i64 }
 
 #[derive(Serialize)]
@@ -339,6 +1535,13 @@ pub struct MeiosisResponse {
     pub crossover_type: String,
 }
 
+#[utoipa::path(
+    post, path = "/api/genome/meiosis", tag = "Genome CRUD",
+    summary = "Breed two genomes (meiosis with crossover)",
+    request_body = MeiosisRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
 async fn meiosis_genome(
     State(state): State<AppState>,
     Json(req): Json<MeiosisRequest>,
@@ -378,7 +1581,7 @@ async fn meiosis_genome(
     }
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct TelomeraseRequest { pub genome_id: i64 }
 
 #[derive(Serialize)]
@@ -391,6 +1594,13 @@ pub struct TelomeraseResponse {
     pub immortality_achieved: bool,
 }
 
+#[utoipa::path(
+    post, path = "/api/genome/telomerase", tag = "Genome CRUD",
+    summary = "Activate telomerase (restore telomeres)",
+    request_body = TelomeraseRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
 async fn activate_telomerase(
     State(state): State<AppState>,
     Json(req): Json<TelomeraseRequest>,
@@ -413,212 +1623,2844 @@ async fn activate_telomerase(
             info!("🧬 TELOMERASE: genome #{} | {} → {} bp | IMMORTAL!",
                   id, telomeres_before, stored.telomere_length);
 
-            ApiResponse::ok(TelomeraseResponse {
-                genome: (&stored).into(),
-                telomeres_before,
-                telomeres_after: stored.telomere_length,
-                biological_age_before: age_before,
-                biological_age_after: stored.biological_age(),
-                immortality_achieved: stored.telomere_length == 15000,
-            })
-        }
-        Err(e) => ApiResponse::err(e.to_string()),
+            ApiResponse::ok(TelomeraseResponse {
+                genome: (&stored).into(),
+                telomeres_before,
+                telomeres_after: stored.telomere_length,
+                biological_age_before: age_before,
+                biological_age_after: stored.biological_age(),
+                immortality_achieved: stored.telomere_length == 15000,
+            })
+        }
+        Err(e) => ApiResponse::err(e.to_string()),
+    }
+}
+
+// CRISPR handlers
+#[derive(Deserialize, ToSchema)]
+pub struct CrisprSpliceRequest { pub genome_id: i64, pub position: usize, pub new_base: char }
+
+#[utoipa::path(
+    post, path = "/api/crispr/splice", tag = "CRISPR",
+    summary = "CRISPR: replace one base",
+    request_body = CrisprSpliceRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn crispr_splice(State(state): State<AppState>, Json(req): Json<CrisprSpliceRequest>) -> Json<ApiResponse<GenomeResponse>> {
+    let mut genome = match state.database.load_genome(req.genome_id).await {
+        Ok(g) => g,
+        Err(e) => return ApiResponse::err(e.to_string()),
+    };
+    let tetrad = match Tetrad::from_char(req.new_base) {
+        Some(t) => t,
+        None => return ApiResponse::err("Invalid base".into()),
+    };
+    if req.position >= 27 { return ApiResponse::err("Position must be 0-26".into()); }
+    genome.crispr_splice(req.position, tetrad);
+    match state.database.store_genome(&genome).await {
+        Ok(id) => { let mut s = genome; s.db_id = Some(id); ApiResponse::ok((&s).into()) }
+        Err(e) => ApiResponse::err(e.to_string()),
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CrisprJoinRequest { pub genome_id: i64, pub pos1: usize, pub pos2: usize }
+
+#[utoipa::path(
+    post, path = "/api/crispr/join", tag = "CRISPR",
+    summary = "CRISPR: swap two bases",
+    request_body = CrisprJoinRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn crispr_join(State(state): State<AppState>, Json(req): Json<CrisprJoinRequest>) -> Json<ApiResponse<GenomeResponse>> {
+    let mut genome = match state.database.load_genome(req.genome_id).await {
+        Ok(g) => g,
+        Err(e) => return ApiResponse::err(e.to_string()),
+    };
+    if req.pos1 >= 27 || req.pos2 >= 27 { return ApiResponse::err("Positions must be 0-26".into()); }
+    genome.crispr_join(req.pos1, req.pos2);
+    match state.database.store_genome(&genome).await {
+        Ok(id) => { let mut s = genome; s.db_id = Some(id); ApiResponse::ok((&s).into()) }
+        Err(e) => ApiResponse::err(e.to_string()),
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CrisprDeleteRequest { pub genome_id: i64, pub position: usize }
+
+#[utoipa::path(
+    post, path = "/api/crispr/delete", tag = "CRISPR",
+    summary = "CRISPR: delete one base",
+    request_body = CrisprDeleteRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn crispr_delete(State(state): State<AppState>, Json(req): Json<CrisprDeleteRequest>) -> Json<ApiResponse<GenomeResponse>> {
+    let mut genome = match state.database.load_genome(req.genome_id).await {
+        Ok(g) => g,
+        Err(e) => return ApiResponse::err(e.to_string()),
+    };
+    if req.position >= 27 { return ApiResponse::err("Position must be 0-26".into()); }
+    genome.crispr_delete(req.position);
+    match state.database.store_genome(&genome).await {
+        Ok(id) => { let mut s = genome; s.db_id = Some(id); ApiResponse::ok((&s).into()) }
+        Err(e) => ApiResponse::err(e.to_string()),
+    }
+}
+
+// RSM handlers
+#[utoipa::path(
+    get, path = "/api/rsm/stats", tag = "RSM-COIN",
+    summary = "RSM-COIN supply, price and burn statistics",
+    responses(EnvelopeResponses)
+)]
+async fn rsm_stats(State(state): State<AppState>) -> Json<ApiResponse<ExchangeStats>> {
+    ApiResponse::ok(state.exchange.read().await.stats())
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct BuyRequest { pub wallet: String, pub usd_amount: f64, pub consciousness: u32 }
+
+#[utoipa::path(
+    post, path = "/api/rsm/buy", tag = "RSM-COIN",
+    summary = "Buy RSM for USD",
+    request_body = BuyRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn rsm_buy(State(state): State<AppState>, Json(req): Json<BuyRequest>) -> Json<ApiResponse<Transaction>> {
+    let mut exchange = state.exchange.write().await;
+    let tx = exchange.buy_rsm(&req.wallet, req.usd_amount, req.consciousness);
+    ApiResponse::ok(tx)
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct SellRequest { pub wallet: String, pub rsm_amount: f64, pub consciousness: u32 }
+
+#[utoipa::path(
+    post, path = "/api/rsm/sell", tag = "RSM-COIN",
+    summary = "Sell RSM for USD",
+    request_body = SellRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn rsm_sell(State(state): State<AppState>, Json(req): Json<SellRequest>) -> Json<ApiResponse<Transaction>> {
+    let mut exchange = state.exchange.write().await;
+    match exchange.sell_rsm(&req.wallet, req.rsm_amount, req.consciousness) {
+        Some(tx) => ApiResponse::ok(tx),
+        None => ApiResponse::err("Insufficient balance".into()),
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct TransferRequest { pub from_wallet: String, pub to_wallet: String, pub amount: f64 }
+
+#[utoipa::path(
+    post, path = "/api/rsm/transfer", tag = "RSM-COIN",
+    summary = "Transfer RSM between wallets (spending policy applies)",
+    request_body = TransferRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn rsm_transfer(State(state): State<AppState>, Json(req): Json<TransferRequest>) -> Json<ApiResponse<Transaction>> {
+    let mut exchange = state.exchange.write().await;
+    match exchange.transfer_checked(&req.from_wallet, &req.to_wallet, req.amount) {
+        Ok(tx) => ApiResponse::ok(tx),
+        Err(e) => ApiResponse::err(e),
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct PaymentRequestParams { pub wallet: String, pub amount: f64, pub memo: Option<String> }
+
+#[utoipa::path(
+    post, path = "/api/rsm/request", tag = "RSM-COIN",
+    summary = "Payment request URI for a wallet (`divine:<address>?amount=…&memo=…`)",
+    request_body = PaymentRequestParams,
+    responses(EnvelopeResponses)
+)]
+async fn rsm_payment_request(Json(req): Json<PaymentRequestParams>) -> Json<ApiResponse<String>> {
+    if !req.amount.is_finite() || req.amount <= 0.0 {
+        return ApiResponse::err(format!("Invalid amount {}", req.amount));
+    }
+    let request = DivineWallet::with_address(&req.wallet).payment_request(req.amount, req.memo.as_deref());
+    ApiResponse::ok(request.to_uri())
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct PayRequest { pub from_wallet: String, pub uri: String }
+
+#[utoipa::path(
+    post, path = "/api/rsm/pay", tag = "RSM-COIN",
+    summary = "Pay a payment request URI from a wallet",
+    request_body = PayRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn rsm_pay(State(state): State<AppState>, Json(req): Json<PayRequest>) -> Json<ApiResponse<Transaction>> {
+    let request = match PaymentRequest::parse(&req.uri) {
+        Ok(request) => request,
+        Err(e) => return ApiResponse::err(e),
+    };
+    let mut exchange = state.exchange.write().await;
+    match exchange.pay_request(&req.from_wallet, &request) {
+        Ok(tx) => ApiResponse::ok(tx),
+        Err(e) => ApiResponse::err(e),
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct PolicyRequest { pub wallet: String, #[schema(value_type = Object)] pub policy: SpendingPolicy }
+
+#[utoipa::path(
+    post, path = "/api/rsm/policy", tag = "RSM-COIN",
+    summary = "Set a wallet's spending policy (daily limit, allowed recipients, confirmation threshold)",
+    request_body = PolicyRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn rsm_policy(State(state): State<AppState>, Json(req): Json<PolicyRequest>) -> Json<ApiResponse<SpendingPolicy>> {
+    state.exchange.write().await.set_policy(&req.wallet, req.policy.clone());
+    ApiResponse::ok(req.policy)
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct RewardRequest { pub wallet: String, pub consciousness: u32 }
+
+#[utoipa::path(
+    post, path = "/api/rsm/reward", tag = "RSM-COIN",
+    summary = "Consciousness reward for a wallet",
+    request_body = RewardRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn rsm_reward(State(state): State<AppState>, Json(req): Json<RewardRequest>) -> Json<ApiResponse<Transaction>> {
+    let mut exchange = state.exchange.write().await;
+    let tx = exchange.consciousness_reward(&req.wallet, req.consciousness);
+    ApiResponse::ok(tx)
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct ManualBurnRequest { pub amount: f64 }
+
+#[utoipa::path(
+    post, path = "/api/rsm/burn", tag = "RSM-COIN",
+    summary = "Burn RSM manually",
+    request_body = ManualBurnRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn rsm_manual_burn(State(state): State<AppState>, Json(req): Json<ManualBurnRequest>) -> Json<ApiResponse<BurnEvent>> {
+    let mut exchange = state.exchange.write().await;
+    let event = exchange.burn(req.amount, BurnReason::ManualBurn, None, 0, 0);
+    ApiResponse::ok(event)
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct OrderRequest {
+    pub wallet: String,
+    /// `buy` or `sell`
+    #[schema(value_type = String)]
+    pub side: Side,
+    /// `limit` (default) or `market`
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub order_type: OrderType,
+    /// Limit price in whole cents; market orders have none
+    pub price_usd: Option<f64>,
+    pub amount_rsm: f64,
+}
+
+/// Limit or market order on the RSM/USD book; sell orders hold the
+/// wallet's RSM until they fill or are cancelled
+#[utoipa::path(
+    post, path = "/api/rsm/orders", tag = "RSM-COIN",
+    summary = "Place a limit or market order; matches at the best resting prices first, then a limit order's rest stays on the book and a market order's is cancelled",
+    request_body = OrderRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn place_order(State(state): State<AppState>, Json(req): Json<OrderRequest>) -> Json<ApiResponse<OrderPlacement>> {
+    let mut exchange = state.exchange.write().await;
+    let placed = match (req.order_type, req.price_usd) {
+        (OrderType::Limit, Some(price_usd)) => exchange.place_order(&req.wallet, req.side, price_usd, req.amount_rsm),
+        (OrderType::Limit, None) => Err("Limit orders need price_usd".to_string()),
+        (OrderType::Market, None) => exchange.place_market_order(&req.wallet, req.side, req.amount_rsm),
+        (OrderType::Market, Some(_)) => Err("Market orders take no price_usd".to_string()),
+    };
+    match placed {
+        Ok(placement) => {
+            // Stored while the book is locked, so trades land in id order
+            if let Err(e) = state.database.store_trades(&placement.trades).await {
+                tracing::warn!("📈 {} trade(s) of order #{} not stored: {}", placement.trades.len(), placement.order.id, e);
+            }
+            ApiResponse::ok(placement)
+        }
+        Err(e) => ApiResponse::err(e),
+    }
+}
+
+#[utoipa::path(
+    get, path = "/api/rsm/orders/{id}", tag = "RSM-COIN",
+    summary = "One order with its filled amount and status",
+    params(("id" = u64, Path)),
+    responses(EnvelopeResponses)
+)]
+async fn get_order(State(state): State<AppState>, Path(id): Path<u64>) -> Json<ApiResponse<Order>> {
+    match state.exchange.read().await.order_book.order(id) {
+        Some(order) => ApiResponse::ok(order.clone()),
+        None => ApiResponse::err(format!("Order {} not found", id)),
+    }
+}
+
+#[utoipa::path(
+    delete, path = "/api/rsm/orders/{id}", tag = "RSM-COIN",
+    summary = "Cancel an open order; a sell order's unfilled RSM goes back to the wallet",
+    params(("id" = u64, Path)),
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn cancel_order(State(state): State<AppState>, Path(id): Path<u64>) -> Json<ApiResponse<Order>> {
+    match state.exchange.write().await.cancel_order(id) {
+        Ok(order) => ApiResponse::ok(order),
+        Err(e) => ApiResponse::err(e),
+    }
+}
+
+#[derive(Deserialize)]
+pub struct DepthQuery { pub levels: Option<usize> }
+
+#[utoipa::path(
+    get, path = "/api/rsm/book", tag = "RSM-COIN",
+    summary = "Order book depth: remaining RSM per price, bids highest first, asks lowest first (`?levels=`, default 20)",
+    params(("levels" = Option<usize>, Query)),
+    responses(EnvelopeResponses)
+)]
+async fn order_book_depth(State(state): State<AppState>, Query(query): Query<DepthQuery>) -> Json<ApiResponse<Depth>> {
+    let levels = query.levels.unwrap_or(DEFAULT_DEPTH_LEVELS).clamp(1, MAX_DEPTH_LEVELS);
+    ApiResponse::ok(state.exchange.read().await.order_book.depth(levels))
+}
+
+#[derive(Deserialize)]
+pub struct TradesQuery { pub limit: Option<usize> }
+
+#[utoipa::path(
+    get, path = "/api/rsm/trades", tag = "RSM-COIN",
+    summary = "Latest order book fills, newest first (`?limit=`)",
+    params(("limit" = Option<usize>, Query)),
+    responses(EnvelopeResponses)
+)]
+async fn recent_trades(State(state): State<AppState>, Query(query): Query<TradesQuery>) -> Json<ApiResponse<Vec<Trade>>> {
+    let limit = query.limit.unwrap_or(DEFAULT_GENOME_PAGE as usize).clamp(1, MAX_GENOME_PAGE as usize);
+    ApiResponse::ok(state.exchange.read().await.order_book.recent_trades(limit))
+}
+
+#[derive(Deserialize)]
+pub struct CandleQuery {
+    pub interval: Option<String>,
+    pub limit: Option<usize>,
+}
+
+#[utoipa::path(
+    get, path = "/api/rsm/candles", tag = "RSM-COIN",
+    summary = "OHLC candles of order book fills, oldest first (`?interval=1m|5m|15m|1h|4h|1d&limit=`)",
+    params(("interval" = Option<String>, Query), ("limit" = Option<usize>, Query)),
+    responses(EnvelopeResponses)
+)]
+async fn trade_candles(State(state): State<AppState>, Query(query): Query<CandleQuery>) -> Json<ApiResponse<Vec<Candle>>> {
+    let interval = match order_book::candle_interval(query.interval.as_deref().unwrap_or("1m")) {
+        Ok(secs) => secs,
+        Err(e) => return ApiResponse::err(e),
+    };
+    let limit = query.limit.unwrap_or(100).clamp(1, MAX_CANDLES);
+    ApiResponse::ok(state.exchange.read().await.order_book.candles(interval, limit))
+}
+
+#[utoipa::path(
+    get, path = "/api/rsm/trades/history", tag = "RSM-COIN",
+    summary = "Every stored fill, newest first",
+    description = "List query over price_usd, amount_rsm, timestamp and id; fills outlive restarts.",
+    params(ListParams),
+    responses(EnvelopeResponses)
+)]
+async fn trade_history(State(state): State<AppState>, Query(params): Query<HashMap<String, String>>) -> Json<ApiResponse<Page<Trade>>> {
+    let query = match ListQuery::parse(&params, listing::TRADE_FIELDS) {
+        Ok(query) => query,
+        Err(e) => return ApiResponse::err(e),
+    };
+    match state.database.find_trades_page(&query).await {
+        Ok(page) => ApiResponse::ok(page),
+        Err(e) => ApiResponse::err(e.to_string()),
+    }
+}
+
+#[derive(Deserialize)]
+pub struct CandleHistoryQuery {
+    pub interval: Option<String>,
+    /// Unix seconds, inclusive
+    pub from: Option<i64>,
+    /// Unix seconds, exclusive
+    pub to: Option<i64>,
+    pub limit: Option<usize>,
+}
+
+#[utoipa::path(
+    get, path = "/api/rsm/candles/history", tag = "RSM-COIN",
+    summary = "OHLC candles of stored fills, oldest first (`?interval=1m|5m|15m|1h|4h|1d&from=&to=&limit=`)",
+    description = "The last `limit` candles with trades in [from, to).",
+    params(
+        ("interval" = Option<String>, Query), ("from" = Option<i64>, Query),
+        ("to" = Option<i64>, Query), ("limit" = Option<usize>, Query)
+    ),
+    responses(EnvelopeResponses)
+)]
+async fn candle_history(State(state): State<AppState>, Query(query): Query<CandleHistoryQuery>) -> Json<ApiResponse<Vec<Candle>>> {
+    let interval = match order_book::candle_interval(query.interval.as_deref().unwrap_or("1m")) {
+        Ok(secs) => secs,
+        Err(e) => return ApiResponse::err(e),
+    };
+    if let (Some(from), Some(to)) = (query.from, query.to) {
+        if from >= to {
+            return ApiResponse::err(format!("from {} is not before to {}", from, to));
+        }
+    }
+    let limit = query.limit.unwrap_or(100).clamp(1, MAX_CANDLES);
+    match state.database.trade_candles(interval, query.from, query.to, limit as i64).await {
+        Ok(candles) => ApiResponse::ok(candles),
+        Err(e) => ApiResponse::err(e.to_string()),
+    }
+}
+
+#[derive(Deserialize)]
+pub struct SwapQuery {
+    pub from: String,
+    pub to: String,
+    pub amount: f64,
+}
+
+#[utoipa::path(
+    get, path = "/api/rsm/swap/simulate", tag = "RSM-COIN",
+    summary = "Preview a swap between USD, RSM, BTC, ETH and SOL (`?from=RSM&to=SOL&amount=`) without trading",
+    description = "Expected output, price impact and fees of the route through USD: RSM walks the order book while it beats the desk price, coins convert at the oracle's reference prices.",
+    params(("from" = String, Query), ("to" = String, Query), ("amount" = f64, Query)),
+    responses(EnvelopeResponses)
+)]
+async fn simulate_swap(State(state): State<AppState>, Query(query): Query<SwapQuery>) -> Json<ApiResponse<SwapQuote>> {
+    let asset = |name: &str| Asset::from_name(name).ok_or_else(|| format!("Unknown asset {}", name));
+    let (from, to) = match asset(&query.from).and_then(|from| Ok((from, asset(&query.to)?))) {
+        Ok(assets) => assets,
+        Err(e) => return ApiResponse::err(e),
+    };
+    match state.exchange.read().await.simulate_swap(from, to, query.amount) {
+        Ok(quote) => ApiResponse::ok(quote),
+        Err(e) => ApiResponse::err(e),
+    }
+}
+
+#[derive(Deserialize)]
+pub struct EscrowListQuery {
+    /// `held`, `released` or `refunded`
+    pub status: Option<String>,
+    pub limit: Option<usize>,
+}
+
+#[utoipa::path(
+    get, path = "/api/rsm/escrows", tag = "RSM-COIN",
+    summary = "Settlement escrows, newest first (`?status=held|released|refunded&limit=`)",
+    params(("status" = Option<String>, Query), ("limit" = Option<usize>, Query)),
+    responses(EnvelopeResponses)
+)]
+async fn list_escrows(State(state): State<AppState>, Query(query): Query<EscrowListQuery>) -> Json<ApiResponse<Vec<Escrow>>> {
+    let status = match query.status.as_deref().map(|name| EscrowStatus::from_name(name).ok_or(name)) {
+        None => None,
+        Some(Ok(status)) => Some(status),
+        Some(Err(name)) => return ApiResponse::err(format!("Unknown escrow status {}", name)),
+    };
+    let limit = query.limit.unwrap_or(DEFAULT_GENOME_PAGE as usize).clamp(1, MAX_GENOME_PAGE as usize);
+    ApiResponse::ok(state.exchange.read().await.list_escrows(status, limit))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct EscrowRequest {
+    pub payer: String,
+    pub payee: String,
+    pub amount_rsm: f64,
+    /// Wait for the block attesting this genome (hex hash) instead of the
+    /// next block
+    pub genome_hash: Option<String>,
+    /// Refund the payer if the block is not final by then (default 24h)
+    pub timeout_secs: Option<i64>,
+    pub memo: Option<String>,
+}
+
+/// Hold a settlement until its consensus block is final
+#[utoipa::path(
+    post, path = "/api/rsm/escrows", tag = "RSM-COIN",
+    summary = "Escrow a payment: the payer's RSM is held until the next block (or the block attesting `genome_hash`) is final, then paid to the payee; refunded on timeout",
+    request_body = EscrowRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn open_escrow(State(state): State<AppState>, Json(req): Json<EscrowRequest>) -> Json<ApiResponse<Escrow>> {
+    let consensus = state.consensus.read().await;
+    let anchor = match req.genome_hash {
+        Some(genome_hash) => EscrowAnchor::Attestation { genome_hash },
+        None => EscrowAnchor::next_block(&consensus),
+    };
+    let terms = EscrowTerms {
+        payee: req.payee,
+        amount_rsm: req.amount_rsm,
+        anchor,
+        timeout_secs: req.timeout_secs.unwrap_or(DEFAULT_ESCROW_TIMEOUT_SECS),
+        memo: req.memo,
+    };
+    match state.exchange.write().await.open_escrow(&req.payer, terms, chrono::Utc::now().timestamp()) {
+        Ok(escrow) => ApiResponse::ok(escrow),
+        Err(e) => ApiResponse::err(e),
+    }
+}
+
+#[utoipa::path(
+    get, path = "/api/rsm/escrows/{id}", tag = "RSM-COIN",
+    summary = "One escrow with its anchor, status and transfers",
+    params(("id" = u64, Path)),
+    responses(EnvelopeResponses)
+)]
+async fn get_escrow(State(state): State<AppState>, Path(id): Path<u64>) -> Json<ApiResponse<Escrow>> {
+    match state.exchange.read().await.escrows.get(&id) {
+        Some(escrow) => ApiResponse::ok(escrow.clone()),
+        None => ApiResponse::err(format!("Escrow {} not found", id)),
+    }
+}
+
+#[derive(Deserialize)]
+pub struct FeeStatusQuery { pub wallet: Option<String> }
+
+/// Tier and rates of one wallet
+#[derive(Serialize)]
+pub struct WalletFees {
+    pub wallet: String,
+    pub volume_30d_usd: f64,
+    /// Index into the schedule's tiers
+    pub tier: usize,
+    pub rates: FeeRates,
+}
+
+#[derive(Serialize)]
+pub struct FeeStatus {
+    pub schedule: FeeSchedule,
+    /// RSM waiting for the next distribution
+    pub accrued_rsm: f64,
+    pub period_start: i64,
+    pub maker_fees_rsm: f64,
+    pub taker_fees_rsm: f64,
+    pub wallet: Option<WalletFees>,
+}
+
+#[utoipa::path(
+    get, path = "/api/rsm/fees", tag = "RSM-COIN",
+    summary = "Fee schedule, fees accrued since the last distribution and, with `?wallet=`, that wallet's 30-day volume, tier and rates",
+    params(("wallet" = Option<String>, Query)),
+    responses(EnvelopeResponses)
+)]
+async fn fee_status(State(state): State<AppState>, Query(query): Query<FeeStatusQuery>) -> Json<ApiResponse<FeeStatus>> {
+    let exchange = state.exchange.read().await;
+    let now = chrono::Utc::now().timestamp();
+    let wallet = query.wallet.map(|wallet| {
+        let volume_30d_usd = exchange.fees.volume_30d(&wallet, now);
+        WalletFees {
+            tier: exchange.fees.schedule.tier(volume_30d_usd),
+            rates: exchange.fees.rates(&wallet, now),
+            wallet,
+            volume_30d_usd,
+        }
+    });
+    ApiResponse::ok(FeeStatus {
+        schedule: exchange.fees.schedule.clone(),
+        accrued_rsm: exchange.get_balance(FEE_ACCRUAL_WALLET),
+        period_start: exchange.fees.period_start,
+        maker_fees_rsm: exchange.fees.maker_fees_rsm,
+        taker_fees_rsm: exchange.fees.taker_fees_rsm,
+        wallet,
+    })
+}
+
+#[derive(Deserialize)]
+pub struct FeeReportQuery {
+    /// `Csv` or `Json` (default)
+    pub format: Option<ExportFormat>,
+    pub limit: Option<usize>,
+}
+
+/// CSV or JSON document, served as is for accounting tools
+#[utoipa::path(
+    get, path = "/api/rsm/fees/reports", tag = "RSM-COIN",
+    summary = "Fee distributions, newest first, as `Csv` (one row per burn, treasury transfer and LP reward) or `Json` (`?format=&limit=`)",
+    params(("format" = Option<String>, Query), ("limit" = Option<usize>, Query)),
+    responses((status = 200, description = "Plain text", body = String, content_type = "text/plain"))
+)]
+async fn fee_reports(State(state): State<AppState>, Query(query): Query<FeeReportQuery>) -> String {
+    let limit = query.limit.unwrap_or(DEFAULT_GENOME_PAGE as usize).clamp(1, MAX_GENOME_PAGE as usize);
+    let reports = state.exchange.read().await.fees.recent_reports(limit);
+    fees::export_reports(&reports, query.format.unwrap_or(ExportFormat::Json))
+}
+
+#[utoipa::path(
+    get, path = "/ws/rsm/book", tag = "RSM-COIN",
+    summary = "WebSocket of order book updates: the current depth, then depth and new trades after every change",
+    responses((status = 101, description = "WebSocket of `OrderBookUpdate` JSON messages"))
+)]
+async fn order_book_ws(State(state): State<AppState>, ws: WebSocketUpgrade) -> Response {
+    let exchange = state.exchange.read().await;
+    let (snapshot, updates) = (exchange.order_book.snapshot(), exchange.order_book.subscribe());
+    drop(exchange);
+    ws.on_upgrade(move |socket| stream_order_book(socket, snapshot, updates))
+}
+
+/// Send the snapshot, then every update until the client goes away
+async fn stream_order_book(
+    mut socket: WebSocket,
+    snapshot: OrderBookUpdate,
+    mut updates: tokio::sync::broadcast::Receiver<OrderBookUpdate>,
+) {
+    let mut next = Some(snapshot);
+    loop {
+        if let Some(update) = next.take() {
+            let text = serde_json::to_string(&update).expect("book update serializes");
+            if socket.send(Message::Text(text)).await.is_err() {
+                return;
+            }
+        }
+        next = match updates.recv().await {
+            Ok(update) => Some(update),
+            // A slow client skips to the next update; `sequence` shows the gap
+            Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => None,
+            Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+        };
+    }
+    let _ = socket.send(Message::Close(None)).await;
+}
+
+#[utoipa::path(
+    get, path = "/api/market/listings", tag = "Marketplace",
+    summary = "Genomes for sale, newest first",
+    description = "Active listings; list query over price_rsm, consciousness, tg_ratio, created_at and id.",
+    params(ListParams),
+    responses(EnvelopeResponses)
+)]
+async fn browse_listings(State(state): State<AppState>, Query(params): Query<HashMap<String, String>>) -> Json<ApiResponse<Page<Listing>>> {
+    let query = match marketplace::parse_listing_query(&params) {
+        Ok(query) => query,
+        Err(e) => return ApiResponse::err(e),
+    };
+    match state.database.find_listings_page(&query).await {
+        Ok(page) => ApiResponse::ok(page),
+        Err(e) => ApiResponse::err(e.to_string()),
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateListingRequest {
+    pub genome_id: i64,
+    /// The genome's owner
+    pub seller: String,
+    pub price_rsm: f64,
+}
+
+#[utoipa::path(
+    post, path = "/api/market/listings", tag = "Marketplace",
+    summary = "Offer a genome for sale in RSM; only its owner may, once at a time",
+    request_body = CreateListingRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn create_listing(State(state): State<AppState>, Json(req): Json<CreateListingRequest>) -> Json<ApiResponse<Listing>> {
+    if let Err(e) = marketplace::check_price(req.price_rsm).and_then(|_| marketplace::check_wallet(&req.seller)) {
+        return ApiResponse::err(e);
+    }
+    match state.database.create_listing(req.genome_id, &req.seller, req.price_rsm).await {
+        Ok(listing) => {
+            info!("🏷️ Genome #{} listed by {} for {} RSM (listing #{})", listing.genome_id, listing.seller, listing.price_rsm, listing.id);
+            ApiResponse::ok(listing)
+        }
+        Err(e) => ApiResponse::err(e.to_string()),
+    }
+}
+
+#[utoipa::path(
+    get, path = "/api/market/listings/{id}", tag = "Marketplace",
+    summary = "One listing, whatever its status",
+    params(("id" = i64, Path)),
+    responses(EnvelopeResponses)
+)]
+async fn get_listing(State(state): State<AppState>, Path(id): Path<i64>) -> Json<ApiResponse<Listing>> {
+    match state.database.load_listing(id).await {
+        Ok(listing) => ApiResponse::ok(listing),
+        Err(e) => ApiResponse::err(e.to_string()),
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CancelListingRequest { pub seller: String }
+
+#[utoipa::path(
+    post, path = "/api/market/listings/{id}/cancel", tag = "Marketplace",
+    summary = "Withdraw an active listing (its seller only)",
+    params(("id" = i64, Path)),
+    request_body = CancelListingRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn cancel_listing(State(state): State<AppState>, Path(id): Path<i64>, Json(req): Json<CancelListingRequest>) -> Json<ApiResponse<Listing>> {
+    match state.database.cancel_listing(id, &req.seller).await {
+        Ok(listing) => ApiResponse::ok(listing),
+        Err(e) => ApiResponse::err(e.to_string()),
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct BuyListingRequest { pub buyer: String }
+
+#[utoipa::path(
+    post, path = "/api/market/listings/{id}/buy", tag = "Marketplace",
+    summary = "Buy a listed genome: pays the seller in RSM and makes the buyer its owner, or does neither",
+    params(("id" = i64, Path)),
+    request_body = BuyListingRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn buy_listing(State(state): State<AppState>, Path(id): Path<i64>, Json(req): Json<BuyListingRequest>) -> Json<ApiResponse<Purchase>> {
+    if let Err(e) = marketplace::check_wallet(&req.buyer) {
+        return ApiResponse::err(e);
+    }
+    // Held until the sale commits, so nothing spends the buyer's RSM meanwhile
+    let mut exchange = state.exchange.write().await;
+    let mut paid = None;
+    let sale = state.database.purchase_listing(id, &req.buyer, |listing| {
+        let memo = format!("genome #{} (listing #{})", listing.genome_id, listing.id);
+        let tx = exchange.transfer_with_memo(&req.buyer, &listing.seller, listing.price_rsm, Some(memo))?;
+        paid = Some(tx.clone());
+        Ok(tx)
+    }).await;
+    match sale {
+        Ok((listing, transaction)) => {
+            info!("🛒 Genome #{} sold by {} to {} for {} RSM", listing.genome_id, listing.seller, req.buyer, listing.price_rsm);
+            ApiResponse::ok(Purchase { listing, transaction })
+        }
+        Err(e) => {
+            // Paid, but the sale did not commit: give the RSM back
+            refund(&mut exchange, paid.as_slice());
+            ApiResponse::err(e.to_string())
+        }
+    }
+}
+
+/// Undo `transfers`, newest first, when what they paid for did not commit
+fn refund(exchange: &mut RSMExchange, transfers: &[Transaction]) {
+    for tx in transfers.iter().rev() {
+        if let Err(e) = exchange.transfer_with_memo(&tx.to_address, &tx.from_address, tx.amount_rsm, Some(format!("refund of {}", tx.hash))) {
+            tracing::warn!("🛒 Refund of {} to {} failed: {}", tx.hash, tx.from_address, e);
+        }
+    }
+}
+
+#[utoipa::path(
+    get, path = "/api/auctions", tag = "Auctions",
+    summary = "Open auctions, ending soonest first",
+    responses(EnvelopeResponses)
+)]
+async fn list_auctions(State(state): State<AppState>) -> Json<ApiResponse<Vec<Auction>>> {
+    match state.database.load_open_auctions().await {
+        Ok(auctions) => ApiResponse::ok(auctions),
+        Err(e) => ApiResponse::err(e.to_string()),
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateAuctionRequest {
+    pub genome_id: i64,
+    /// The genome's owner
+    pub seller: String,
+    /// Opening bid
+    pub reserve_rsm: f64,
+    pub duration_secs: i64,
+}
+
+#[utoipa::path(
+    post, path = "/api/auctions", tag = "Auctions",
+    summary = "Auction a genome at or above the PoC threshold; only its owner may, once at a time",
+    request_body = CreateAuctionRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn create_auction(State(state): State<AppState>, Json(req): Json<CreateAuctionRequest>) -> Json<ApiResponse<Auction>> {
+    let checked = marketplace::check_price(req.reserve_rsm)
+        .and_then(|_| marketplace::check_wallet(&req.seller))
+        .and_then(|_| auction::check_duration(req.duration_secs));
+    if let Err(e) = checked {
+        return ApiResponse::err(e);
+    }
+    let min_consciousness = state.consensus.read().await.min_consciousness;
+    match state.database.create_auction(req.genome_id, &req.seller, req.reserve_rsm, req.duration_secs, min_consciousness).await {
+        Ok(auction) => {
+            info!("🔨 Genome #{} auctioned by {} from {} RSM until {} (auction #{})", auction.genome_id, auction.seller, auction.reserve_rsm, auction.ends_at, auction.id);
+            ApiResponse::ok(auction)
+        }
+        Err(e) => ApiResponse::err(e.to_string()),
+    }
+}
+
+#[utoipa::path(
+    get, path = "/api/auctions/{id}", tag = "Auctions",
+    summary = "One auction, whatever its status",
+    params(("id" = i64, Path)),
+    responses(EnvelopeResponses)
+)]
+async fn get_auction(State(state): State<AppState>, Path(id): Path<i64>) -> Json<ApiResponse<Auction>> {
+    match state.database.load_auction(id).await {
+        Ok(auction) => ApiResponse::ok(auction),
+        Err(e) => ApiResponse::err(e.to_string()),
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct BidRequest {
+    pub bidder: String,
+    pub amount_rsm: f64,
+}
+
+#[utoipa::path(
+    post, path = "/api/auctions/{id}/bid", tag = "Auctions",
+    summary = "Bid on an auction: the RSM goes into escrow and the bid it tops is refunded",
+    description = "Bids must top the high bid by 5% (the first one meets the reserve); a bid in the last 5 minutes extends the auction to 5 minutes after it.",
+    params(("id" = i64, Path)),
+    request_body = BidRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn bid_auction(State(state): State<AppState>, Path(id): Path<i64>, Json(req): Json<BidRequest>) -> Json<ApiResponse<BidReceipt>> {
+    if let Err(e) = marketplace::check_wallet(&req.bidder) {
+        return ApiResponse::err(e);
+    }
+    // Held until the bid commits, so escrow and refund land together
+    let mut exchange = state.exchange.write().await;
+    let mut moved = Vec::new();
+    let bid = state.database.bid_auction(id, &req.bidder, req.amount_rsm, |auction, outbid| {
+        let escrow = auction::escrow_address(auction.id);
+        let memo = format!("bid on auction #{} (genome #{})", auction.id, auction.genome_id);
+        moved.push(exchange.transfer_with_memo(&req.bidder, &escrow, req.amount_rsm, Some(memo))?);
+        if let Some(outbid) = outbid {
+            let memo = format!("outbid on auction #{}", auction.id);
+            moved.push(exchange.transfer_with_memo(&escrow, &outbid.bidder, outbid.amount_rsm, Some(memo))?);
+        }
+        Ok(moved.clone())
+    }).await;
+    match bid {
+        Ok((auction, transactions)) => {
+            info!("🔨 {} bids {} RSM on auction #{} (ends {})", req.bidder, req.amount_rsm, auction.id, auction.ends_at);
+            ApiResponse::ok(BidReceipt { auction, transactions })
+        }
+        Err(e) => {
+            refund(&mut exchange, &moved);
+            ApiResponse::err(e.to_string())
+        }
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CancelAuctionRequest { pub seller: String }
+
+#[utoipa::path(
+    post, path = "/api/auctions/{id}/cancel", tag = "Auctions",
+    summary = "Withdraw an open auction without bids (its seller only)",
+    params(("id" = i64, Path)),
+    request_body = CancelAuctionRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn cancel_auction(State(state): State<AppState>, Path(id): Path<i64>, Json(req): Json<CancelAuctionRequest>) -> Json<ApiResponse<Auction>> {
+    match state.database.cancel_auction(id, &req.seller).await {
+        Ok(auction) => ApiResponse::ok(auction),
+        Err(e) => ApiResponse::err(e.to_string()),
+    }
+}
+
+#[utoipa::path(
+    post, path = "/api/auctions/{id}/settle", tag = "Auctions",
+    summary = "Close an auction that is over: pays the seller from escrow, hands the genome to the high bidder and queues its attestation",
+    params(("id" = i64, Path)),
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn settle_auction(State(state): State<AppState>, Path(id): Path<i64>) -> Json<ApiResponse<Settlement>> {
+    let mut exchange = state.exchange.write().await;
+    let mut paid = None;
+    let closed = state.database.close_auction(id, |auction| {
+        let Some(high_bid) = auction.high_bid_rsm.filter(|_| auction.status == AuctionStatus::Settled) else {
+            return Ok(None);
+        };
+        let memo = format!("genome #{} (auction #{})", auction.genome_id, auction.id);
+        let tx = exchange.transfer_with_memo(&auction::escrow_address(auction.id), &auction.seller, high_bid, Some(memo))?;
+        paid = Some(tx.clone());
+        Ok(Some(tx))
+    }).await;
+    let (auction, transaction) = match closed {
+        Ok(closed) => closed,
+        Err(e) => {
+            refund(&mut exchange, paid.as_slice());
+            return ApiResponse::err(e.to_string());
+        }
+    };
+    drop(exchange);
+
+    let attestation = match (&auction.status, &auction.high_bidder) {
+        (AuctionStatus::Settled, Some(winner)) => {
+            info!("🔨 Genome #{} sold by {} to {} for {} RSM (auction #{})", auction.genome_id, auction.seller, winner, auction.high_bid_rsm.unwrap_or_default(), auction.id);
+            attest_sale(&state, auction.genome_id).await
+        }
+        _ => {
+            info!("🔨 Auction #{} closed unsold", auction.id);
+            None
+        }
+    };
+    ApiResponse::ok(Settlement { auction, transaction, attestation })
+}
+
+/// Queue an attestation of an auctioned genome; one already consecrated is
+/// on the chain to begin with
+async fn attest_sale(state: &AppState, genome_id: i64) -> Option<ConsciousnessProof> {
+    let genome = match state.database.load_genome(genome_id).await {
+        Ok(genome) => genome,
+        Err(e) => {
+            tracing::warn!("🔨 Genome #{} not attested: {}", genome_id, e);
+            return None;
+        }
+    };
+    match state.consensus.write().await.submit(&genome) {
+        Ok(proof) => Some(proof),
+        Err(e) => {
+            tracing::warn!("🔨 Genome #{} not attested: {}", genome_id, e);
+            None
+        }
+    }
+}
+
+#[utoipa::path(
+    get, path = "/api/transactions", tag = "Transactions & Burns",
+    summary = "Exchange transactions, newest first",
+    description = "List query over amount_rsm, amount_usd, consciousness_level, timestamp and id.",
+    params(ListParams),
+    responses(EnvelopeResponses)
+)]
+async fn list_transactions(State(state): State<AppState>, Query(params): Query<HashMap<String, String>>) -> Json<ApiResponse<Page<Transaction>>> {
+    let query = match ListQuery::parse(&params, listing::TRANSACTION_FIELDS) {
+        Ok(query) => query,
+        Err(e) => return ApiResponse::err(e),
+    };
+    let exchange = state.exchange.read().await;
+    let rows = exchange.transactions.iter().map(|tx| (tx.id as i64, tx)).collect();
+    ApiResponse::ok(listing::paginate(rows, &query).map(Transaction::clone))
+}
+
+#[utoipa::path(
+    get, path = "/api/burns", tag = "Transactions & Burns",
+    summary = "Burn events, newest first",
+    description = "List query over amount_rsm, genome_id, consciousness_before, consciousness_after, timestamp and id.",
+    params(ListParams),
+    responses(EnvelopeResponses)
+)]
+async fn list_burns(State(state): State<AppState>, Query(params): Query<HashMap<String, String>>) -> Json<ApiResponse<Page<BurnEvent>>> {
+    let query = match ListQuery::parse(&params, listing::BURN_FIELDS) {
+        Ok(query) => query,
+        Err(e) => return ApiResponse::err(e),
+    };
+    let exchange = state.exchange.read().await;
+    let rows = exchange.burn_events.iter().map(|burn| (burn.id as i64, burn)).collect();
+    ApiResponse::ok(listing::paginate(rows, &query).map(BurnEvent::clone))
+}
+
+#[utoipa::path(
+    get, path = "/api/debt/stats", tag = "Debt Tracker",
+    summary = "Debt absorption progress",
+    responses(EnvelopeResponses)
+)]
+async fn debt_stats(State(state): State<AppState>) -> Json<ApiResponse<DebtStats>> {
+    ApiResponse::ok(state.exchange.read().await.debt_stats())
+}
+
+#[utoipa::path(
+    get, path = "/api/owner/pool", tag = "Debt Tracker",
+    summary = "Owner pool statistics",
+    responses(EnvelopeResponses)
+)]
+async fn owner_pool(State(state): State<AppState>) -> Json<ApiResponse<OwnerPoolStats>> {
+    ApiResponse::ok(state.exchange.read().await.owner_pool())
+}
+
+#[utoipa::path(
+    get, path = "/api/oracle/prices", tag = "Price Oracle",
+    summary = "Reference prices (median of fresh source quotes), latest quote and error per source",
+    responses(EnvelopeResponses)
+)]
+async fn oracle_prices(State(state): State<AppState>) -> Json<ApiResponse<OracleStatus>> {
+    ApiResponse::ok(state.oracle.read().await.status(chrono::Utc::now().timestamp()))
+}
+
+/// Poll the oracle's sources every `refresh_secs`: RSM/USD marks the
+/// exchange, BTC/ETH/SOL price archive costs. Without a fresh RSM/USD the
+/// exchange keeps its last mark.
+async fn spawn_price_oracle(state: &AppState) {
+    let (fetcher, refresh_secs) = {
+        let oracle = state.oracle.read().await;
+        if !oracle.is_enabled() {
+            return;
+        }
+        info!("💱 Price oracle: {} source(s), refreshed every {}s", oracle.config().sources.len(), oracle.config().refresh_secs);
+        (oracle.fetcher(), oracle.config().refresh_secs)
+    };
+    let state = state.clone();
+    tokio::spawn(async move {
+        let mut ticks = tokio::time::interval(std::time::Duration::from_secs(refresh_secs));
+        loop {
+            ticks.tick().await;
+            let results = fetcher.fetch().await;
+            let now = chrono::Utc::now().timestamp();
+            let prices = {
+                let mut oracle = state.oracle.write().await;
+                oracle.record(results, now);
+                oracle.prices(now)
+            };
+            {
+                let mut exchange = state.exchange.write().await;
+                if let Some(rsm) = prices.iter().find(|price| price.pair == Pair::RsmUsd) {
+                    if let Err(e) = exchange.set_mark_price(rsm.price_usd) {
+                        tracing::warn!("💱 {}", e);
+                    }
+                }
+                exchange.set_reference_prices(&prices);
+            }
+            state.archiver.write().await.set_reference_prices(&prices);
+        }
+    });
+}
+
+/// Every `ESCROW_CHECK_SECS`, release escrows whose block is final and
+/// refund the expired ones
+fn spawn_escrow_resolver(state: &AppState) {
+    let state = state.clone();
+    tokio::spawn(async move {
+        let mut ticks = tokio::time::interval(std::time::Duration::from_secs(ESCROW_CHECK_SECS));
+        loop {
+            ticks.tick().await;
+            let consensus = state.consensus.read().await;
+            let mut exchange = state.exchange.write().await;
+            if exchange.escrows.values().any(Escrow::is_held) {
+                exchange.resolve_escrows(&consensus, chrono::Utc::now().timestamp());
+            }
+        }
+    });
+}
+
+/// Re-read the receipts of Ethereum archives until they are confirmed
+fn spawn_ethereum_confirmations(state: &AppState) {
+    let state = state.clone();
+    tokio::spawn(async move {
+        let mut ticks = tokio::time::interval(std::time::Duration::from_secs(ETH_CONFIRMATION_CHECK_SECS));
+        loop {
+            ticks.tick().await;
+            let mut archiver = state.archiver.write().await;
+            if let Some(ethereum) = archiver.ethereum.as_mut() {
+                if let Err(e) = ethereum.refresh(chrono::Utc::now().timestamp()).await {
+                    tracing::warn!("🔷 Ethereum confirmation check failed: {}", e);
+                }
+            }
+        }
+    });
+}
+
+/// Re-read the signature statuses of Solana archives until they are finalized
+fn spawn_solana_confirmations(state: &AppState) {
+    let state = state.clone();
+    tokio::spawn(async move {
+        let mut ticks = tokio::time::interval(std::time::Duration::from_secs(SOLANA_CONFIRMATION_CHECK_SECS));
+        loop {
+            ticks.tick().await;
+            let mut archiver = state.archiver.write().await;
+            if archiver.solana.is_some() {
+                if let Err(e) = archiver.refresh_solana(chrono::Utc::now().timestamp()).await {
+                    tracing::warn!("🟣 Solana confirmation check failed: {}", e);
+                }
+            }
+        }
+    });
+}
+
+/// Commit the waiting genomes' Merkle root whenever it is due
+fn spawn_merkle_commits(state: &AppState) {
+    let state = state.clone();
+    tokio::spawn(async move {
+        let mut ticks = tokio::time::interval(std::time::Duration::from_secs(MERKLE_CHECK_SECS));
+        loop {
+            ticks.tick().await;
+            let mut archiver = state.archiver.write().await;
+            if archiver.merkle.is_due(chrono::Utc::now().timestamp()) {
+                if let Err(e) = archiver.commit_merkle().await {
+                    tracing::warn!("🌳 Merkle root commit failed, retrying next check: {}", e);
+                }
+            }
+        }
+    });
+}
+
+/// Dial the swarm's endpoints every `SWARM_HEALTH_CHECK_SECS`, without
+/// holding the archiver while the probes wait
+fn spawn_swarm_health_checks(state: &AppState) {
+    let state = state.clone();
+    tokio::spawn(async move {
+        let mut ticks = tokio::time::interval(std::time::Duration::from_secs(SWARM_HEALTH_CHECK_SECS));
+        loop {
+            ticks.tick().await;
+            let endpoints = state.archiver.read().await.swarm.endpoints();
+            if !endpoints.is_empty() {
+                let results = probe_all(endpoints).await;
+                state.archiver.write().await.swarm_probed(results);
+            }
+        }
+    });
+}
+
+/// Fetch SPV proofs of Bitcoin archives still missing one, or short of
+/// the required depth, every `SPV_CHECK_SECS`
+fn spawn_spv_proofs(state: &AppState) {
+    let state = state.clone();
+    tokio::spawn(async move {
+        let mut ticks = tokio::time::interval(std::time::Duration::from_secs(SPV_CHECK_SECS));
+        loop {
+            ticks.tick().await;
+            let proven = state.archiver.write().await.refresh_spv_proofs().await;
+            if proven > 0 {
+                tracing::info!("🟠 {} Bitcoin archives SPV-proven", proven);
+            }
+        }
+    });
+}
+
+/// Distribute the accrued fees every `distribution_secs` of the current
+/// schedule
+fn spawn_fee_distributor(state: &AppState) {
+    let state = state.clone();
+    tokio::spawn(async move {
+        loop {
+            let secs = state.exchange.read().await.fees.schedule.distribution_secs;
+            tokio::time::sleep(std::time::Duration::from_secs(secs)).await;
+            let mut exchange = state.exchange.write().await;
+            if exchange.get_balance(FEE_ACCRUAL_WALLET) > 0.0 {
+                if let Err(e) = exchange.distribute_fees(chrono::Utc::now().timestamp()) {
+                    tracing::warn!("🧾 Fee distribution failed: {}", e);
+                }
+            }
+        }
+    });
+}
+
+// Multi-chain & LN handlers
+#[derive(Deserialize, ToSchema)]
+pub struct ArchiveRequest {
+    pub genome_id: i64,
+    /// Layer to use instead of the one the T/G ratio selects (`ipfs` and
+    /// `arweave` store the whole genome); no failover then
+    #[serde(default)]
+    pub layer: Option<String>,
+}
+
+#[utoipa::path(
+    post, path = "/api/archive", tag = "Multi-Chain & LN",
+    summary = "Archive a genome to the layer its T/G ratio selects, or to `layer`",
+    description = "Each layer is retried with backoff; if it keeps failing the archive fails over to the next layer Mission Control ranks, and the entry's `fallbacks` lists the layers given up on. A given `layer` is only retried.",
+    request_body = ArchiveRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn archive_genome(State(state): State<AppState>, Json(req): Json<ArchiveRequest>) -> Json<ApiResponse<ChainArchiveEntry>> {
+    let layer = match req.layer.as_deref().map(BlockchainLayer::from_name).transpose() {
+        Ok(layer) => layer,
+        Err(e) => return ApiResponse::err(e),
+    };
+    archive_stored_genome(&state, req.genome_id, layer).await
+}
+
+async fn archive_stored_genome(state: &AppState, genome_id: i64, layer: Option<BlockchainLayer>) -> Json<ApiResponse<ChainArchiveEntry>> {
+    let genome = match state.database.load_genome(genome_id).await {
+        Ok(g) => g,
+        Err(e) => return ApiResponse::err(e.to_string()),
+    };
+    let mut archiver = state.archiver.write().await;
+    let result = match layer {
+        Some(layer) => archiver.archive_to(&genome, layer).await,
+        None => archiver.archive(&genome).await,
+    };
+    drop(archiver);
+    match result {
+        Ok(entry) => {
+            state.webhooks.emit(WebhookEvent::archive_confirmed(&entry)).await;
+            ApiResponse::ok(entry)
+        }
+        Err(e) => ApiResponse::err(e),
+    }
+}
+
+#[utoipa::path(
+    post, path = "/api/archive/{genome_id}", tag = "Multi-Chain & LN",
+    summary = "Archive a stored genome to the layer its T/G ratio selects",
+    params(("genome_id" = i64, Path)),
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn archive_genome_by_id(State(state): State<AppState>, Path(genome_id): Path<i64>) -> Json<ApiResponse<ChainArchiveEntry>> {
+    archive_stored_genome(&state, genome_id, None).await
+}
+
+#[utoipa::path(
+    get, path = "/api/archive/{genome_id}", tag = "Multi-Chain & LN",
+    summary = "Every archive entry of a genome, oldest first",
+    params(("genome_id" = i64, Path)),
+    responses(EnvelopeResponses)
+)]
+async fn genome_archives(State(state): State<AppState>, Path(genome_id): Path<i64>) -> Json<ApiResponse<Vec<ChainArchiveEntry>>> {
+    let archiver = state.archiver.read().await;
+    ApiResponse::ok(archiver.entries_for_genome(genome_id).into_iter().cloned().collect())
+}
+
+#[utoipa::path(
+    get, path = "/api/archive/layers/stats", tag = "Multi-Chain & LN",
+    summary = "Archives, distinct genomes, average consciousness and T/G ratio per layer",
+    responses(EnvelopeResponses)
+)]
+async fn archive_layer_stats(State(state): State<AppState>) -> Json<ApiResponse<Vec<LayerStats>>> {
+    ApiResponse::ok(state.archiver.read().await.layer_stats())
+}
+
+#[utoipa::path(
+    get, path = "/api/archive/budget", tag = "Multi-Chain & LN",
+    summary = "Per-genome and daily archive fee caps in USD, and what today's archives have spent",
+    responses(EnvelopeResponses)
+)]
+async fn archive_budget(State(state): State<AppState>) -> Json<ApiResponse<BudgetStatus>> {
+    ApiResponse::ok(state.archiver.read().await.budget_status(chrono::Utc::now().timestamp()))
+}
+
+#[utoipa::path(
+    get, path = "/api/archive/merkle", tag = "Multi-Chain & LN",
+    summary = "Merkle archives: layer and interval, genomes waiting, when their root is due, and the last root committed",
+    responses(EnvelopeResponses)
+)]
+async fn merkle_status(State(state): State<AppState>) -> Json<ApiResponse<MerkleStatus>> {
+    ApiResponse::ok(state.archiver.read().await.merkle.status())
+}
+
+#[utoipa::path(
+    post, path = "/api/archive/merkle/{genome_id}", tag = "Multi-Chain & LN",
+    summary = "Queue a stored genome for the next Merkle root instead of its own archive transaction",
+    params(("genome_id" = i64, Path)),
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn queue_merkle_archive(State(state): State<AppState>, Path(genome_id): Path<i64>) -> Json<ApiResponse<PendingLeaf>> {
+    match state.database.load_genome(genome_id).await {
+        Ok(genome) => ApiResponse::ok(state.archiver.write().await.queue_merkle(&genome)),
+        Err(e) => ApiResponse::err(e.to_string()),
+    }
+}
+
+#[utoipa::path(
+    post, path = "/api/archive/merkle/commit", tag = "Multi-Chain & LN",
+    summary = "Commit the waiting genomes' Merkle root now, in one transaction",
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn commit_merkle_root(State(state): State<AppState>) -> Json<ApiResponse<MerkleCommitment>> {
+    match state.archiver.write().await.commit_merkle().await {
+        Ok(Some(commitment)) => ApiResponse::ok(commitment),
+        Ok(None) => ApiResponse::err("No genomes wait for a Merkle root".into()),
+        Err(e) => ApiResponse::err(e),
+    }
+}
+
+#[utoipa::path(
+    get, path = "/api/archive/merkle/proof/{genome_id}", tag = "Multi-Chain & LN",
+    summary = "A genome's Merkle inclusion proof, checked against the root read back from its transaction and the genome as stored now",
+    params(("genome_id" = i64, Path)),
+    responses(EnvelopeResponses)
+)]
+async fn prove_archived(State(state): State<AppState>, Path(genome_id): Path<i64>) -> Json<ApiResponse<ProofVerification>> {
+    let current_dna = state.database.load_genome(genome_id).await.ok().map(|g| g.to_dna_string());
+    match state.archiver.write().await.verify_archived(genome_id, current_dna.as_deref()).await {
+        Ok(check) => ApiResponse::ok(check),
+        Err(e) => ApiResponse::err(e),
+    }
+}
+
+/// Check an archive entry against its DNA hash, its layer's tx reference
+/// format, the hash its transaction embeds and the genome as stored now
+#[utoipa::path(
+    post, path = "/api/archive/verify/{entry_id}", tag = "Multi-Chain & LN",
+    summary = "Re-check an archive entry: DNA hash, tx reference, the hash read back from its transaction, and whether the genome changed since",
+    params(("entry_id" = u64, Path)),
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn verify_archive(State(state): State<AppState>, Path(entry_id): Path<u64>) -> Json<ApiResponse<ArchiveVerification>> {
+    let genome_id = match state.archiver.read().await.entry(entry_id) {
+        Some(entry) => entry.genome_id,
+        None => return ApiResponse::err(format!("Archive entry {} not found", entry_id)),
+    };
+    let current_dna = state.database.load_genome(genome_id).await.ok().map(|g| g.to_dna_string());
+    match state.archiver.write().await.verify(entry_id, current_dna.as_deref()).await {
+        Ok(verification) => ApiResponse::ok(verification),
+        Err(e) => ApiResponse::err(e),
+    }
+}
+
+#[utoipa::path(
+    post, path = "/api/archive/spv/{entry_id}", tag = "Multi-Chain & LN",
+    summary = "Fetch a Bitcoin archive's SPV proof (raw transaction, Merkle branch, block headers) and keep it on the entry",
+    params(("entry_id" = u64, Path)),
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn fetch_spv_proof(State(state): State<AppState>, Path(entry_id): Path<u64>) -> Json<ApiResponse<SpvProof>> {
+    match state.archiver.write().await.prove_bitcoin_archive(entry_id).await {
+        Ok(proof) => ApiResponse::ok(proof),
+        Err(e) => ApiResponse::err(e),
+    }
+}
+
+#[utoipa::path(
+    get, path = "/api/archive/spv/{entry_id}", tag = "Multi-Chain & LN",
+    summary = "Check a Bitcoin archive's stored SPV proof locally: txid, OP_RETURN, Merkle branch, header links and work, depth",
+    params(("entry_id" = u64, Path)),
+    responses(EnvelopeResponses)
+)]
+async fn verify_spv_proof(State(state): State<AppState>, Path(entry_id): Path<u64>) -> Json<ApiResponse<SpvVerification>> {
+    let archiver = state.archiver.read().await;
+    let Some(entry) = archiver.entry(entry_id) else {
+        return ApiResponse::err(format!("Archive entry {} not found", entry_id));
+    };
+    match archiver.verify_spv(entry) {
+        Ok(verification) => ApiResponse::ok(verification),
+        Err(e) => ApiResponse::err(e),
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct RestoredHash {
+    #[schema(value_type = String)]
+    pub layer: BlockchainLayer,
+    pub tx_hash: String,
+    /// The DNA hash embedded in the transaction
+    pub dna_hash: String,
+}
+
+#[utoipa::path(
+    get, path = "/api/archive/chain/{layer}/{tx_hash}", tag = "Multi-Chain & LN",
+    summary = "The DNA hash a transaction on `layer` embeds, read back from the chain",
+    params(("layer" = String, Path), ("tx_hash" = String, Path)),
+    responses(EnvelopeResponses)
+)]
+async fn restore_archive_hash(State(state): State<AppState>, Path((layer, tx_hash)): Path<(String, String)>) -> Json<ApiResponse<RestoredHash>> {
+    let layer = match BlockchainLayer::from_name(&layer) {
+        Ok(layer) => layer,
+        Err(e) => return ApiResponse::err(e),
+    };
+    match state.archiver.write().await.restore_hash_from_chain(&tx_hash, layer).await {
+        Ok(hash) => ApiResponse::ok(RestoredHash { layer, tx_hash, dna_hash: hex::encode(hash) }),
+        Err(e) => ApiResponse::err(e),
+    }
+}
+
+#[utoipa::path(
+    get, path = "/api/archive/content/{layer}/{reference}", tag = "Multi-Chain & LN",
+    summary = "The whole genome a storage layer (`ipfs` or `arweave`) keeps under `reference`, fetched and checked against its CID and DNA hash",
+    params(("layer" = String, Path), ("reference" = String, Path)),
+    responses(EnvelopeResponses)
+)]
+async fn restore_archived_genome(State(state): State<AppState>, Path((layer, reference)): Path<(String, String)>) -> Json<ApiResponse<StoredGenome>> {
+    let layer = match BlockchainLayer::from_name(&layer) {
+        Ok(layer) => layer,
+        Err(e) => return ApiResponse::err(e),
+    };
+    match state.archiver.read().await.restore_genome(&reference, layer).await {
+        Ok(document) => ApiResponse::ok(document),
+        Err(e) => ApiResponse::err(e),
+    }
+}
+
+/// `genome_ids`, or a `filter` (same fields as `GET /api/genomes`)
+#[derive(Deserialize, ToSchema)]
+pub struct ArchiveBatchRequest {
+    pub genome_ids: Option<Vec<i64>>,
+    #[schema(value_type = Option<Object>)]
+    pub filter: Option<GenomeFilter>,
+}
+
+/// Archive many genomes in the background; poll `/api/archive/batch/:id`
+/// for the tx references as they land
+#[utoipa::path(
+    post, path = "/api/archive/batch", tag = "Multi-Chain & LN",
+    summary = "Archive up to 100 genomes (`genome_ids` or a `filter`) in the background; returns their layer assignments",
+    request_body = ArchiveBatchRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn archive_batch(State(state): State<AppState>, Json(req): Json<ArchiveBatchRequest>) -> Json<ApiResponse<ArchiveBatch>> {
+    let mut genomes = Vec::new();
+    let mut missing = Vec::new();
+    match (req.genome_ids, req.filter) {
+        (Some(mut ids), None) => {
+            ids.sort_unstable();
+            ids.dedup();
+            if ids.len() > MAX_ARCHIVE_BATCH {
+                return ApiResponse::err(format!("At most {} genomes per batch", MAX_ARCHIVE_BATCH));
+            }
+            for id in ids {
+                match state.database.load_genome(id).await {
+                    Ok(genome) => genomes.push(genome),
+                    Err(e) => missing.push(ArchiveBatchItem::failed(id, e.to_string())),
+                }
+            }
+        }
+        (None, Some(mut filter)) => {
+            filter.limit = Some(filter.limit.unwrap_or(MAX_ARCHIVE_BATCH as i64).min(MAX_ARCHIVE_BATCH as i64));
+            match state.database.find_genomes(&filter).await {
+                Ok(found) => genomes = found,
+                Err(e) => return ApiResponse::err(e.to_string()),
+            }
+        }
+        _ => return ApiResponse::err("Give exactly one of genome_ids or filter".into()),
+    }
+    if genomes.is_empty() && missing.is_empty() {
+        return ApiResponse::err("No genomes to archive".into());
+    }
+
+    // Layers are fixed up front: later outcomes in the batch must not move
+    // the genomes still waiting unless their own layer fails
+    let archiver = state.archiver.read().await;
+    let layers: Vec<BlockchainLayer> = genomes.iter().map(|g| archiver.select_layer(g)).collect();
+    drop(archiver);
+    let mut items: Vec<ArchiveBatchItem> = genomes.iter().zip(&layers)
+        .map(|(g, layer)| ArchiveBatchItem::pending(g.db_id().unwrap_or(0), *layer))
+        .collect();
+    items.extend(missing);
+
+    let batch = state.archive_batches.write().await.create(items);
+    info!("⛓️ Archive batch #{} | {} genomes", batch.id, batch.items.len());
+
+    let batch_id = batch.id;
+    tokio::spawn(async move {
+        for (genome, layer) in genomes.into_iter().zip(layers) {
+            let result = state.archiver.write().await.archive_from(&genome, layer).await;
+            state.archive_batches.write().await.complete(batch_id, genome.db_id().unwrap_or(0), result.as_ref().map_err(Clone::clone));
+            if let Ok(entry) = &result {
+                state.webhooks.emit(WebhookEvent::archive_confirmed(entry)).await;
+            }
+        }
+    });
+    ApiResponse::ok(batch)
+}
+
+#[utoipa::path(
+    get, path = "/api/archive/batch/{id}", tag = "Multi-Chain & LN",
+    summary = "Per-genome layers, tx references and progress of an archive batch",
+    params(("id" = u64, Path)),
+    responses(EnvelopeResponses)
+)]
+async fn archive_batch_status(State(state): State<AppState>, Path(id): Path<u64>) -> Json<ApiResponse<ArchiveBatch>> {
+    match state.archive_batches.read().await.get(id) {
+        Some(batch) => ApiResponse::ok(batch.clone()),
+        None => ApiResponse::err(format!("Archive batch {} not found", id)),
+    }
+}
+
+#[utoipa::path(
+    get, path = "/api/archives", tag = "Multi-Chain & LN",
+    summary = "Multi-chain archive entries, newest first",
+    description = "List query over genome_id, consciousness, tg_ratio, timestamp and id (1-based archive position).",
+    params(ListParams),
+    responses(EnvelopeResponses)
+)]
+async fn list_archives(State(state): State<AppState>, Query(params): Query<HashMap<String, String>>) -> Json<ApiResponse<Page<ChainArchiveEntry>>> {
+    let query = match ListQuery::parse(&params, listing::ARCHIVE_FIELDS) {
+        Ok(query) => query,
+        Err(e) => return ApiResponse::err(e),
+    };
+    let archiver = state.archiver.read().await;
+    let rows = archiver.archives.iter().map(|entry| (entry.id as i64, entry)).collect();
+    ApiResponse::ok(listing::paginate(rows, &query).map(ChainArchiveEntry::clone))
+}
+
+#[utoipa::path(
+    get, path = "/api/mission-control", tag = "Multi-Chain & LN",
+    summary = "Lightning Network Mission Control statistics",
+    responses(EnvelopeResponses)
+)]
+async fn mission_control_stats(State(state): State<AppState>) -> Json<ApiResponse<MissionControlStats>> {
+    let archiver = state.archiver.read().await;
+    ApiResponse::ok(archiver.mission_control_stats())
+}
+
+#[utoipa::path(
+    post, path = "/api/mission-control/reset", tag = "Multi-Chain & LN",
+    summary = "Reset Mission Control pair history",
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn reset_mission_control(State(state): State<AppState>) -> Json<ApiResponse<String>> {
+    let mut archiver = state.archiver.write().await;
+    archiver.mission_control.reset();
+    ApiResponse::ok("Mission Control reset".to_string())
+}
+
+#[utoipa::path(
+    get, path = "/api/lightning/payments", tag = "Multi-Chain & LN",
+    summary = "Keysends sent by Lightning archives, newest first (`?limit=`)",
+    params(("limit" = Option<usize>, Query)),
+    responses(EnvelopeResponses)
+)]
+async fn lightning_payments(State(state): State<AppState>, Query(query): Query<TradesQuery>) -> Json<ApiResponse<Vec<KeysendPayment>>> {
+    let limit = query.limit.unwrap_or(DEFAULT_GENOME_PAGE as usize).clamp(1, MAX_GENOME_PAGE as usize);
+    ApiResponse::ok(state.archiver.read().await.recent_payments(limit).into_iter().cloned().collect())
+}
+
+/// Refreshed from LND for keysends sent through it
+#[utoipa::path(
+    get, path = "/api/lightning/payments/{payment_hash}", tag = "Multi-Chain & LN",
+    summary = "A keysend's current state (payment hash or `ln_` tx reference)",
+    params(("payment_hash" = String, Path)),
+    responses(EnvelopeResponses)
+)]
+async fn track_lightning_payment(State(state): State<AppState>, Path(payment_hash): Path<String>) -> Json<ApiResponse<KeysendPayment>> {
+    match state.archiver.write().await.track_payment(&payment_hash).await {
+        Ok(payment) => ApiResponse::ok(payment),
+        Err(e) => ApiResponse::err(e),
+    }
+}
+
+#[utoipa::path(
+    get, path = "/api/lightning/invoices/{payment_hash}", tag = "Multi-Chain & LN",
+    summary = "A keysend archive this node received, with the genome hash from its TLV records (needs LND)",
+    params(("payment_hash" = String, Path)),
+    responses(EnvelopeResponses)
+)]
+async fn lookup_lightning_invoice(State(state): State<AppState>, Path(payment_hash): Path<String>) -> Json<ApiResponse<ReceivedKeysend>> {
+    match state.archiver.read().await.lookup_invoice(&payment_hash).await {
+        Ok(keysend) => ApiResponse::ok(keysend),
+        Err(e) => ApiResponse::err(e),
+    }
+}
+
+#[utoipa::path(
+    get, path = "/api/lightning/health", tag = "Multi-Chain & LN",
+    summary = "LND sync state and channels, and whether they can pay an archive broadcast (needs LND)",
+    responses(EnvelopeResponses)
+)]
+async fn lightning_health(State(state): State<AppState>) -> Json<ApiResponse<ChannelHealth>> {
+    match state.archiver.read().await.channel_health().await {
+        Ok(health) => ApiResponse::ok(health),
+        Err(e) => ApiResponse::err(e),
+    }
+}
+
+#[utoipa::path(
+    get, path = "/api/lightning/swarm", tag = "Multi-Chain & LN",
+    summary = "Swarm nodes with their endpoint, capacity, health, keysend metrics and Mission Control probability",
+    responses(EnvelopeResponses)
+)]
+async fn swarm_status(State(state): State<AppState>) -> Json<ApiResponse<SwarmStatus>> {
+    ApiResponse::ok(state.archiver.read().await.swarm_status())
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct RegisterSwarmNodeRequest {
+    /// 33-byte compressed node pubkey, hex
+    pub pubkey: String,
+    /// `host:port` its peers connect to, dialed by health checks
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    /// Sat the channels reaching it can carry; a node that cannot take one
+    /// keysend is left out of broadcasts
+    #[serde(default)]
+    pub capacity_sat: Option<i64>,
+    #[serde(default)]
+    pub alias: Option<String>,
+}
+
+#[utoipa::path(
+    post, path = "/api/lightning/swarm", tag = "Multi-Chain & LN",
+    summary = "Add a node to the swarm, or update one already in it (its metrics stay)",
+    request_body = RegisterSwarmNodeRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn register_swarm_node(State(state): State<AppState>, Json(req): Json<RegisterSwarmNodeRequest>) -> Json<ApiResponse<SwarmNode>> {
+    let registration = NodeRegistration { pubkey: req.pubkey, endpoint: req.endpoint, capacity_sat: req.capacity_sat, alias: req.alias };
+    match state.archiver.write().await.swarm.register(registration, chrono::Utc::now().timestamp()) {
+        Ok(node) => ApiResponse::ok(node.clone()),
+        Err(e) => ApiResponse::err(e),
+    }
+}
+
+#[utoipa::path(
+    delete, path = "/api/lightning/swarm/{pubkey}", tag = "Multi-Chain & LN",
+    summary = "Remove a node from the swarm",
+    params(("pubkey" = String, Path)),
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn remove_swarm_node(State(state): State<AppState>, Path(pubkey): Path<String>) -> Json<ApiResponse<SwarmNode>> {
+    match state.archiver.write().await.swarm.remove(&pubkey) {
+        Some(node) => ApiResponse::ok(node),
+        None => ApiResponse::err(format!("Swarm node {} not found", pubkey)),
+    }
+}
+
+#[utoipa::path(
+    post, path = "/api/lightning/swarm/health", tag = "Multi-Chain & LN",
+    summary = "Dial every swarm endpoint now; unreachable nodes are skipped and count as Mission Control failures",
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn check_swarm_health(State(state): State<AppState>) -> Json<ApiResponse<SwarmStatus>> {
+    let endpoints = state.archiver.read().await.swarm.endpoints();
+    let results = probe_all(endpoints).await;
+    ApiResponse::ok(state.archiver.write().await.swarm_probed(results))
+}
+
+#[utoipa::path(
+    get, path = "/api/ethereum/txs", tag = "Multi-Chain & LN",
+    summary = "Ethereum archive transactions with fees and confirmations, newest first (`?limit=`)",
+    params(("limit" = Option<usize>, Query)),
+    responses(EnvelopeResponses)
+)]
+async fn ethereum_txs(State(state): State<AppState>, Query(query): Query<TradesQuery>) -> Json<ApiResponse<Vec<EthArchiveTx>>> {
+    let limit = query.limit.unwrap_or(DEFAULT_GENOME_PAGE as usize).clamp(1, MAX_GENOME_PAGE as usize);
+    match state.archiver.read().await.ethereum_txs(limit) {
+        Ok(txs) => ApiResponse::ok(txs.into_iter().cloned().collect()),
+        Err(e) => ApiResponse::err(e),
+    }
+}
+
+#[utoipa::path(
+    get, path = "/api/ethereum/txs/{tx_hash}", tag = "Multi-Chain & LN",
+    summary = "An Ethereum archive transaction, its receipt re-read from the node",
+    params(("tx_hash" = String, Path)),
+    responses(EnvelopeResponses)
+)]
+async fn track_ethereum_tx(State(state): State<AppState>, Path(tx_hash): Path<String>) -> Json<ApiResponse<EthArchiveTx>> {
+    match state.archiver.write().await.track_ethereum_tx(&tx_hash).await {
+        Ok(tx) => ApiResponse::ok(tx),
+        Err(e) => ApiResponse::err(e),
+    }
+}
+
+#[utoipa::path(
+    get, path = "/api/solana/archives/{signature}", tag = "Multi-Chain & LN",
+    summary = "A Solana archive entry, its slot and commitment re-read from the cluster",
+    params(("signature" = String, Path)),
+    responses(EnvelopeResponses)
+)]
+async fn track_solana_archive(State(state): State<AppState>, Path(signature): Path<String>) -> Json<ApiResponse<ChainArchiveEntry>> {
+    match state.archiver.write().await.track_solana(&signature).await {
+        Ok(entry) => ApiResponse::ok(entry),
+        Err(e) => ApiResponse::err(e),
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct AirdropRequest {
+    pub amount_sol: f64,
+    /// Solana address to fund; defaults to the archiving key
+    pub address: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct AirdropReceipt {
+    pub address: String,
+    pub amount_sol: f64,
+    pub signature: String,
+}
+
+#[utoipa::path(
+    post, path = "/api/solana/airdrop", tag = "Multi-Chain & LN",
+    summary = "Fund the archiving key (or `address`) with test SOL from the devnet faucet",
+    request_body = AirdropRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn solana_airdrop(State(state): State<AppState>, Json(req): Json<AirdropRequest>) -> Json<ApiResponse<AirdropReceipt>> {
+    if !req.amount_sol.is_finite() || req.amount_sol <= 0.0 || req.amount_sol > MAX_AIRDROP_SOL {
+        return ApiResponse::err(format!("Invalid amount {}: airdrops are up to {} SOL", req.amount_sol, MAX_AIRDROP_SOL));
+    }
+    let archiver = state.archiver.read().await;
+    let Some(solana) = archiver.solana.as_ref() else {
+        return ApiResponse::err(format!("Airdrops {}", NO_SOLANA));
+    };
+    let lamports = (req.amount_sol * LAMPORTS_PER_SOL as f64).round() as u64;
+    match solana.request_airdrop(req.address.as_deref(), lamports).await {
+        Ok((address, signature)) => ApiResponse::ok(AirdropReceipt { address, amount_sol: req.amount_sol, signature }),
+        Err(e) => ApiResponse::err(e),
+    }
+}
+
+// Proof of Consciousness handlers
+#[derive(Deserialize, ToSchema)]
+pub struct PoCValidateRequest {
+    pub genome_id: i64,
+    /// Fee to pay; defaults to the current required fee
+    #[serde(default)]
+    pub fee_rsm: Option<f64>,
+}
+
+pub(crate) fn submit_request(consensus: &mut ProofOfConsciousness, genome: &Genome<Rot180>, fee_rsm: Option<f64>) -> Result<ConsciousnessProof, String> {
+    let fee_rsm = fee_rsm.unwrap_or_else(|| consensus.required_fee());
+    consensus.submit_with_fee(genome, fee_rsm)
+}
+
+#[utoipa::path(
+    post, path = "/api/poc/validate", tag = "Proof of Consciousness",
+    summary = "Proof of Consciousness validation (mines a block)",
+    request_body = PoCValidateRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn poc_validate(State(state): State<AppState>, Json(req): Json<PoCValidateRequest>) -> Json<ApiResponse<ConsensusBlock>> {
+    let genome = match state.database.load_genome(req.genome_id).await {
+        Ok(g) => g,
+        Err(e) => return ApiResponse::err(e.to_string()),
+    };
+    let mut consensus = state.consensus.write().await;
+    if let Err(e) = submit_request(&mut consensus, &genome, req.fee_rsm) {
+        return ApiResponse::err(e);
+    }
+    if consensus.mine_block().is_none() {
+        return ApiResponse::err("Mining failed".into());
+    }
+    if let Err(e) = consensus.save_chain(&state.database).await {
+        return ApiResponse::err(format!("Block mined but not persisted: {}", e));
+    }
+    let block = consensus.latest_block().clone();
+    drop(consensus);
+
+    if let Some(node) = &state.network {
+        node.broadcast_block(&block).await;
+    }
+    ApiResponse::ok(block)
+}
+
+#[utoipa::path(
+    get, path = "/api/poc/status", tag = "Proof of Consciousness",
+    summary = "Chain height, threshold, latest block hash",
+    responses(EnvelopeResponses)
+)]
+async fn poc_status(State(state): State<AppState>) -> Json<ApiResponse<PoCStatus>> {
+    ApiResponse::ok(state.consensus.read().await.status())
+}
+
+#[utoipa::path(
+    post, path = "/api/poc/submit", tag = "Proof of Consciousness",
+    summary = "Queue a genome attestation in the mempool",
+    request_body = PoCValidateRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn poc_submit(State(state): State<AppState>, Json(req): Json<PoCValidateRequest>) -> Json<ApiResponse<ConsciousnessProof>> {
+    let genome = match state.database.load_genome(req.genome_id).await {
+        Ok(g) => g,
+        Err(e) => return ApiResponse::err(e.to_string()),
+    };
+    let mut consensus = state.consensus.write().await;
+    match submit_request(&mut consensus, &genome, req.fee_rsm) {
+        Ok(proof) => ApiResponse::ok(proof),
+        Err(e) => ApiResponse::err(e),
+    }
+}
+
+#[utoipa::path(
+    post, path = "/api/poc/mine", tag = "Proof of Consciousness",
+    summary = "Mine pending attestations (up to 64 per block)",
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn poc_mine(State(state): State<AppState>) -> Json<ApiResponse<ConsensusBlock>> {
+    match mine_pending(&state).await {
+        Ok(block) => ApiResponse::ok(block),
+        Err(e) => ApiResponse::err(e),
+    }
+}
+
+/// Mine the mempool into a block, persist it and gossip it to peers
+pub(crate) async fn mine_pending(state: &AppState) -> Result<ConsensusBlock, String> {
+    let mut consensus = state.consensus.write().await;
+    let block = match consensus.mine_block() {
+        Some(block) => block.clone(),
+        None => return Err("Mempool is empty".into()),
+    };
+    consensus.save_chain(&state.database).await
+        .map_err(|e| format!("Block mined but not persisted: {}", e))?;
+    drop(consensus);
+
+    if let Some(node) = &state.network {
+        node.broadcast_block(&block).await;
+    }
+    Ok(block)
+}
+
+#[utoipa::path(
+    get, path = "/api/poc/mempool", tag = "Proof of Consciousness",
+    summary = "Pending attestations",
+    responses(EnvelopeResponses)
+)]
+async fn poc_mempool(State(state): State<AppState>) -> Json<ApiResponse<Vec<GenomeAttestation>>> {
+    ApiResponse::ok(state.consensus.read().await.mempool.entries())
+}
+
+#[utoipa::path(
+    get, path = "/api/poc/fee", tag = "Proof of Consciousness",
+    summary = "Minimum and currently required attestation fee",
+    responses(EnvelopeResponses)
+)]
+async fn poc_fee(State(state): State<AppState>) -> Json<ApiResponse<FeeQuote>> {
+    ApiResponse::ok(state.consensus.read().await.fee_quote())
+}
+
+#[utoipa::path(
+    get, path = "/api/poc/metrics", tag = "Proof of Consciousness",
+    summary = "Chain health: hash rate, block interval, consciousness per block, reorgs, mempool depth",
+    responses(EnvelopeResponses)
+)]
+async fn poc_metrics(State(state): State<AppState>) -> Json<ApiResponse<ChainMetrics>> {
+    ApiResponse::ok(state.consensus.read().await.metrics())
+}
+
+/// Scrape target for Prometheus (text exposition format)
+#[utoipa::path(
+    get, path = "/metrics", tag = "Proof of Consciousness",
+    summary = "The same metrics in Prometheus text format",
+    responses((status = 200, description = "Plain text", body = String, content_type = "text/plain"))
+)]
+async fn prometheus_metrics(State(state): State<AppState>) -> String {
+    state.consensus.read().await.metrics().to_prometheus()
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct InclusionProofRequest { pub genome_hash: String }
+
+#[utoipa::path(
+    post, path = "/api/poc/proof", tag = "Proof of Consciousness",
+    summary = "Merkle inclusion proof for a genome hash",
+    request_body = InclusionProofRequest,
+    responses(EnvelopeResponses)
+)]
+async fn poc_inclusion_proof(State(state): State<AppState>, Json(req): Json<InclusionProofRequest>) -> Json<ApiResponse<InclusionProof>> {
+    let mut genome_hash = [0u8; 32];
+    match hex::decode(&req.genome_hash) {
+        Ok(bytes) if bytes.len() == 32 => genome_hash.copy_from_slice(&bytes),
+        _ => return ApiResponse::err("genome_hash must be 32 bytes hex".into()),
+    }
+    match state.consensus.read().await.generate_inclusion_proof(&genome_hash) {
+        Some(proof) => ApiResponse::ok(proof),
+        None => ApiResponse::err("Genome not found on chain".into()),
+    }
+}
+
+#[utoipa::path(
+    post, path = "/api/poc/proof/verify", tag = "Proof of Consciousness",
+    summary = "Verify an inclusion proof against the chain",
+    request_body = Object,
+    responses(EnvelopeResponses)
+)]
+async fn poc_verify_inclusion(State(state): State<AppState>, Json(proof): Json<InclusionProof>) -> Json<ApiResponse<bool>> {
+    ApiResponse::ok(state.consensus.read().await.verify_inclusion_proof(&proof))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct RewardWalletRequest { pub address: String }
+
+#[utoipa::path(
+    post, path = "/api/poc/wallet", tag = "Proof of Consciousness",
+    summary = "Block rewards and premine balance for an address",
+    request_body = RewardWalletRequest,
+    responses(EnvelopeResponses)
+)]
+async fn poc_wallet(State(state): State<AppState>, Json(req): Json<RewardWalletRequest>) -> Json<ApiResponse<DivineWallet>> {
+    match state.consensus.read().await.wallet(&req.address) {
+        Some(wallet) => ApiResponse::ok(wallet.clone()),
+        None => ApiResponse::err(format!("No rewards recorded for {}", req.address)),
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct HistoryRequest {
+    pub address: String,
+    #[serde(flatten)]
+    #[schema(value_type = Object)]
+    pub filter: HistoryFilter,
+}
+
+#[utoipa::path(
+    post, path = "/api/poc/history", tag = "Proof of Consciousness",
+    summary = "Transactions of an address, filtered by time, direction, amount, counterparty or status",
+    request_body = HistoryRequest,
+    responses(EnvelopeResponses)
+)]
+async fn poc_history(State(state): State<AppState>, Json(req): Json<HistoryRequest>) -> Json<ApiResponse<Vec<WalletTransaction>>> {
+    ApiResponse::ok(state.consensus.read().await.history(&req.address, &req.filter))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct HistoryExportRequest {
+    pub address: String,
+    /// `Csv` or `Json`
+    #[schema(value_type = String)]
+    pub format: ExportFormat,
+    #[serde(flatten)]
+    #[schema(value_type = Object)]
+    pub filter: HistoryFilter,
+}
+
+/// CSV or JSON document, served as is for accounting tools
+#[utoipa::path(
+    post, path = "/api/poc/history/export", tag = "Proof of Consciousness",
+    summary = "The settled history as `Csv` or `Json`, with running balances",
+    request_body = HistoryExportRequest,
+    responses((status = 200, description = "Plain text", body = String, content_type = "text/plain"))
+)]
+async fn poc_history_export(State(state): State<AppState>, Json(req): Json<HistoryExportRequest>) -> String {
+    state.consensus.read().await.export_history(&req.address, req.format, &req.filter)
+}
+
+#[utoipa::path(
+    post, path = "/api/poc/stake", tag = "Proof of Consciousness",
+    summary = "Lock/unlock RSM or register a consecrated genome as stake",
+    request_body = Object,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn poc_stake(State(state): State<AppState>, Json(action): Json<StakeAction>) -> Json<ApiResponse<StakeOp>> {
+    match state.consensus.write().await.stake(action) {
+        Ok(op) => ApiResponse::ok(op),
+        Err(e) => ApiResponse::err(e),
+    }
+}
+
+#[utoipa::path(
+    get, path = "/api/poc/validators", tag = "Proof of Consciousness",
+    summary = "Stakes and the active validator set of this epoch",
+    responses(EnvelopeResponses)
+)]
+async fn poc_validators(State(state): State<AppState>) -> Json<ApiResponse<StakingState>> {
+    ApiResponse::ok(state.consensus.read().await.staking.clone())
+}
+
+#[derive(Serialize)]
+pub struct BlockEventGenome {
+    pub genome_id: i64,
+    pub consciousness: u32,
+}
+
+/// New canonical block, as pushed on `/events/blocks`
+#[derive(Serialize)]
+pub struct BlockEvent {
+    pub index: u64,
+    pub hash: String,
+    pub timestamp: i64,
+    pub producer: String,
+    pub genomes: Vec<BlockEventGenome>,
+}
+
+impl From<&ConsensusBlock> for BlockEvent {
+    fn from(block: &ConsensusBlock) -> Self {
+        Self {
+            index: block.index,
+            hash: block.hash.clone(),
+            timestamp: block.timestamp,
+            producer: block.producer.clone(),
+            genomes: block.entries.iter()
+                .map(|e| BlockEventGenome { genome_id: e.genome_id, consciousness: e.proof.consciousness })
+                .collect(),
+        }
+    }
+}
+
+/// Server-sent `block` events for every block appended to the chain
+#[utoipa::path(
+    get, path = "/events/blocks", tag = "Proof of Consciousness",
+    summary = "Server-sent `block` events (index, hash, producer, attested genome ids and consciousness) for each new block",
+    responses((status = 200, description = "`block` server-sent events", content_type = "text/event-stream"))
+)]
+async fn block_events(State(state): State<AppState>) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    let events = state.consensus.read().await.subscribe();
+    let stream = BroadcastStream::new(events).filter_map(|event| match event {
+        Ok(ChainEvent::BlockAdded(block)) => Some(Event::default().event("block").json_data(BlockEvent::from(&block))),
+        // Other chain events, or a lagged receiver skipping blocks
+        _ => None,
+    });
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+#[utoipa::path(
+    post, path = "/graphql", tag = "GraphQL",
+    summary = "GraphQL queries over genomes, blocks, archives and wallets (`GET /graphql` serves GraphiQL)",
+    request_body = Object,
+    responses((status = 200, description = "JSON document", body = Object))
+)]
+async fn graphql_handler(Extension(schema): Extension<DivineSchema>, Json(req): Json<async_graphql::Request>) -> Json<async_graphql::Response> {
+    Json(schema.execute(req).await)
+}
+
+#[utoipa::path(
+    get, path = "/graphql", tag = "GraphQL",
+    summary = "GraphiQL page for /graphql",
+    responses((status = 200, description = "GraphiQL page", content_type = "text/html"))
+)]
+async fn graphiql() -> Html<String> {
+    Html(async_graphql::http::GraphiQLSource::build().endpoint("/graphql").finish())
+}
+
+#[utoipa::path(
+    get, path = "/api/network/peers", tag = "Proof of Consciousness",
+    summary = "P2P peers and gossip counters",
+    responses(EnvelopeResponses)
+)]
+async fn network_stats(State(state): State<AppState>) -> Json<ApiResponse<NetworkStats>> {
+    match &state.network {
+        Some(node) => ApiResponse::ok(node.stats().await),
+        None => ApiResponse::err("P2P disabled (set P2P_LISTEN_ADDR)".into()),
+    }
+}
+
+// Rotation handlers
+#[utoipa::path(
+    get, path = "/api/rotation/stats", tag = "Rotation",
+    summary = "Rotation engine statistics",
+    responses(EnvelopeResponses)
+)]
+async fn rotation_stats(State(state): State<AppState>) -> Json<ApiResponse<RotationStats>> {
+    let engine = state.rotation_engine.read().await;
+    ApiResponse::ok(engine.get_stats())
+}
+
+#[utoipa::path(
+    post, path = "/api/rotation/rotate", tag = "Rotation",
+    summary = "Rotate the engine to its next state",
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn manual_rotate(State(state): State<AppState>) -> Json<ApiResponse<RotationStats>> {
+    let mut engine = state.rotation_engine.write().await;
+    engine.rotate();
+    ApiResponse::ok(engine.get_stats())
+}
+
+// ═══════════════════════════════════════════════════════════════
+// ADMIN HANDLERS
+// ═══════════════════════════════════════════════════════════════
+
+/// Snapshots are written here by `/api/admin/snapshot` unless `SNAPSHOT_DIR` is set
+pub const DEFAULT_SNAPSHOT_DIR: &str = "snapshots";
+
+/// Record an admin action before it is carried out; no record, no action
+async fn audit(state: &AppState, claims: &JwtClaims, action: &str, detail: serde_json::Value) -> Result<(), String> {
+    let entry = AdminAuditEntry::new(claims, action, detail);
+    info!("🛡️ Admin {} by {} (key #{}) | {}", action, entry.caller, entry.key_id, entry.detail);
+    state.database.store_admin_audit(&entry).await
+        .map(|_| ())
+        .map_err(|e| format!("Audit log unavailable, {} not performed: {}", action, e))
+}
+
+/// Local consensus settings and production state
+#[derive(Serialize)]
+pub struct ConsensusSettings {
+    pub height: u64,
+    /// Attestation admission threshold (local policy, grows per block)
+    pub min_consciousness: u32,
+    /// Threshold growth per block
+    pub difficulty_growth_rate: u32,
+    /// PoW difficulty; follows the retarget rule, not adjustable
+    pub difficulty: u32,
+    pub target_block_time_secs: i64,
+    pub mining_paused: bool,
+    pub validators: usize,
+}
+
+fn consensus_settings(consensus: &ProofOfConsciousness) -> ConsensusSettings {
+    ConsensusSettings {
+        height: consensus.current_block_height,
+        min_consciousness: consensus.min_consciousness,
+        difficulty_growth_rate: consensus.difficulty_growth_rate,
+        difficulty: consensus.difficulty,
+        target_block_time_secs: consensus.target_block_time_secs,
+        mining_paused: consensus.mining_paused,
+        validators: consensus.validators.len(),
+    }
+}
+
+#[utoipa::path(
+    get, path = "/api/admin/consensus", tag = "Admin",
+    summary = "Consensus thresholds, difficulty and whether block production is paused",
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn admin_consensus(State(state): State<AppState>) -> Json<ApiResponse<ConsensusSettings>> {
+    ApiResponse::ok(consensus_settings(&*state.consensus.read().await))
+}
+
+/// Unset fields are left as they are
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ConsensusUpdate {
+    pub min_consciousness: Option<u32>,
+    pub difficulty_growth_rate: Option<u32>,
+}
+
+/// Tune attestation admission. Both values are local policy (blocks do not
+/// commit to them); `min_consciousness` is recomputed from genesis when the
+/// chain is replayed, `difficulty_growth_rate` is kept.
+#[utoipa::path(
+    post, path = "/api/admin/consensus", tag = "Admin",
+    summary = "Set `min_consciousness` and/or `difficulty_growth_rate`",
+    request_body = ConsensusUpdate,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn admin_update_consensus(
+    State(state): State<AppState>,
+    Extension(claims): Extension<JwtClaims>,
+    Json(update): Json<ConsensusUpdate>,
+) -> Json<ApiResponse<ConsensusSettings>> {
+    if update.min_consciousness.is_none() && update.difficulty_growth_rate.is_none() {
+        return ApiResponse::err("Nothing to update".into());
+    }
+    if let Err(e) = audit(&state, &claims, "consensus.update", serde_json::json!(update)).await {
+        return ApiResponse::err(e);
+    }
+    let mut consensus = state.consensus.write().await;
+    if let Some(threshold) = update.min_consciousness {
+        consensus.min_consciousness = threshold;
+    }
+    if let Some(rate) = update.difficulty_growth_rate {
+        consensus.difficulty_growth_rate = rate;
+    }
+    ApiResponse::ok(consensus_settings(&consensus))
+}
+
+#[derive(Serialize)]
+pub struct ChainValidation {
+    pub valid: bool,
+    pub height: u64,
+    pub elapsed_ms: u64,
+}
+
+#[utoipa::path(
+    post, path = "/api/admin/chain/validate", tag = "Admin",
+    summary = "Re-check every block hash, link, difficulty and signature back to genesis",
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn admin_validate_chain(State(state): State<AppState>, Extension(claims): Extension<JwtClaims>) -> Json<ApiResponse<ChainValidation>> {
+    if let Err(e) = audit(&state, &claims, "chain.validate", serde_json::json!({})).await {
+        return ApiResponse::err(e);
+    }
+    let consensus = state.consensus.read().await;
+    let started = std::time::Instant::now();
+    let valid = consensus.validate_chain_parallel();
+    ApiResponse::ok(ChainValidation {
+        valid,
+        height: consensus.current_block_height,
+        elapsed_ms: started.elapsed().as_millis() as u64,
+    })
+}
+
+#[derive(Serialize)]
+pub struct SnapshotExport {
+    pub path: String,
+    pub height: u64,
+}
+
+#[utoipa::path(
+    post, path = "/api/admin/snapshot", tag = "Admin",
+    summary = "Write the pruned-state snapshot to `SNAPSHOT_DIR` (pruning nodes only)",
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn admin_export_snapshot(State(state): State<AppState>, Extension(claims): Extension<JwtClaims>) -> Json<ApiResponse<SnapshotExport>> {
+    let consensus = state.consensus.read().await;
+    let Some(height) = consensus.snapshot.as_ref().map(|s| s.height) else {
+        return ApiResponse::err("Chain is not pruned; nothing to snapshot".into());
+    };
+    let dir = std::env::var("SNAPSHOT_DIR").unwrap_or_else(|_| DEFAULT_SNAPSHOT_DIR.to_string());
+    let path = std::path::Path::new(&dir).join(format!("snapshot-{}.json", height));
+    if let Err(e) = audit(&state, &claims, "snapshot.export", serde_json::json!({ "path": path, "height": height })).await {
+        return ApiResponse::err(e);
+    }
+
+    let exported = std::fs::create_dir_all(&dir)
+        .map_err(anyhow::Error::from)
+        .and_then(|_| consensus.export_snapshot(&path));
+    match exported {
+        Ok(()) => ApiResponse::ok(SnapshotExport { path: path.display().to_string(), height }),
+        Err(e) => ApiResponse::err(e.to_string()),
+    }
+}
+
+#[utoipa::path(
+    get, path = "/api/admin/validators", tag = "Admin",
+    summary = "Public keys allowed to produce blocks",
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn admin_validators(State(state): State<AppState>) -> Json<ApiResponse<Vec<String>>> {
+    ApiResponse::ok(state.consensus.read().await.validators.keys())
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct ValidatorRequest { pub public_key: String }
+
+#[utoipa::path(
+    post, path = "/api/admin/validators", tag = "Admin",
+    summary = "Allow a secp256k1 public key (hex) to produce blocks",
+    request_body = ValidatorRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn admin_add_validator(
+    State(state): State<AppState>,
+    Extension(claims): Extension<JwtClaims>,
+    Json(req): Json<ValidatorRequest>,
+) -> Json<ApiResponse<Vec<String>>> {
+    let key = req.public_key.trim().to_lowercase();
+    if !hex::decode(&key).is_ok_and(|bytes| secp256k1::PublicKey::from_slice(&bytes).is_ok()) {
+        return ApiResponse::err(format!("{} is not a secp256k1 public key", key));
+    }
+    if let Err(e) = audit(&state, &claims, "validators.add", serde_json::json!({ "public_key": key })).await {
+        return ApiResponse::err(e);
+    }
+    if let Err(e) = state.database.store_validator(&key).await {
+        return ApiResponse::err(e.to_string());
+    }
+    let mut consensus = state.consensus.write().await;
+    consensus.validators.register(&key);
+    ApiResponse::ok(consensus.validators.keys())
+}
+
+#[utoipa::path(
+    delete, path = "/api/admin/validators/{public_key}", tag = "Admin",
+    summary = "Stop accepting blocks from a validator key",
+    params(("public_key" = String, Path)),
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn admin_remove_validator(
+    State(state): State<AppState>,
+    Extension(claims): Extension<JwtClaims>,
+    Path(public_key): Path<String>,
+) -> Json<ApiResponse<Vec<String>>> {
+    let key = public_key.to_lowercase();
+    if state.consensus.read().await.validator_public_key().as_deref() == Some(key.as_str()) {
+        return ApiResponse::err("Cannot remove this node's own validator key (pause mining instead)".into());
+    }
+    if let Err(e) = audit(&state, &claims, "validators.remove", serde_json::json!({ "public_key": key })).await {
+        return ApiResponse::err(e);
+    }
+    if let Err(e) = state.database.remove_validator(&key).await {
+        return ApiResponse::err(e.to_string());
+    }
+    let mut consensus = state.consensus.write().await;
+    if !consensus.validators.remove(&key) {
+        return ApiResponse::err(format!("{} is not a validator", key));
+    }
+    ApiResponse::ok(consensus.validators.keys())
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct MiningRequest { pub paused: bool }
+
+#[utoipa::path(
+    post, path = "/api/admin/mining", tag = "Admin",
+    summary = "Pause or resume block production on this node (`{ \"paused\": true }`)",
+    request_body = MiningRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn admin_set_mining(
+    State(state): State<AppState>,
+    Extension(claims): Extension<JwtClaims>,
+    Json(req): Json<MiningRequest>,
+) -> Json<ApiResponse<ConsensusSettings>> {
+    let action = if req.paused { "mining.pause" } else { "mining.resume" };
+    if let Err(e) = audit(&state, &claims, action, serde_json::json!(req)).await {
+        return ApiResponse::err(e);
+    }
+    let mut consensus = state.consensus.write().await;
+    consensus.mining_paused = req.paused;
+    ApiResponse::ok(consensus_settings(&consensus))
+}
+
+/// Charge a new fee schedule from the next order on
+#[utoipa::path(
+    post, path = "/api/admin/fees/schedule", tag = "Admin",
+    summary = "Replace the maker/taker fee tiers, the distribution split and interval; placed orders keep their rates",
+    request_body = Object,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn admin_set_fee_schedule(
+    State(state): State<AppState>,
+    Extension(claims): Extension<JwtClaims>,
+    Json(schedule): Json<FeeSchedule>,
+) -> Json<ApiResponse<FeeSchedule>> {
+    if let Err(e) = schedule.validate() {
+        return ApiResponse::err(e);
+    }
+    if let Err(e) = audit(&state, &claims, "fees.schedule", serde_json::json!(schedule)).await {
+        return ApiResponse::err(e);
+    }
+    match state.exchange.write().await.set_fee_schedule(schedule.clone()) {
+        Ok(()) => ApiResponse::ok(schedule),
+        Err(e) => ApiResponse::err(e),
+    }
+}
+
+#[utoipa::path(
+    post, path = "/api/admin/fees/distribute", tag = "Admin",
+    summary = "Distribute the accrued fees now instead of at the next interval",
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn admin_distribute_fees(State(state): State<AppState>, Extension(claims): Extension<JwtClaims>) -> Json<ApiResponse<FeeReport>> {
+    if let Err(e) = audit(&state, &claims, "fees.distribute", serde_json::json!({})).await {
+        return ApiResponse::err(e);
+    }
+    match state.exchange.write().await.distribute_fees(chrono::Utc::now().timestamp()) {
+        Ok(report) => ApiResponse::ok(report),
+        Err(e) => ApiResponse::err(e),
     }
 }
 
-// CRISPR handlers
 #[derive(Deserialize)]
-pub struct CrisprSpliceRequest { pub genome_id: i64, pub position: usize, pub new_base: char }
+pub struct AuditQuery { pub limit: Option<i64> }
 
-async fn crispr_splice(State(state): State<AppState>, Json(req): Json<CrisprSpliceRequest>) -> Json<ApiResponse<GenomeResponse>> {
-    let mut genome = match state.database.load_genome(req.genome_id).await {
-        Ok(g) => g,
-        Err(e) => return ApiResponse::err(e.to_string()),
-    };
-    let tetrad = match Tetrad::from_char(req.new_base) {
-        Some(t) => t,
-        None => return ApiResponse::err("Invalid base".into()),
-    };
-    if req.position >= 27 { return ApiResponse::err("Position must be 0-26".into()); }
-    genome.crispr_splice(req.position, tetrad);
-    match state.database.store_genome(&genome).await {
-        Ok(id) => { let mut s = genome; s.db_id = Some(id); ApiResponse::ok((&s).into()) }
+#[utoipa::path(
+    get, path = "/api/admin/audit", tag = "Admin",
+    summary = "Latest admin actions with the API key that made them (`?limit=`)",
+    params(("limit" = Option<i64>, Query)),
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn admin_audit_log(State(state): State<AppState>, Query(query): Query<AuditQuery>) -> Json<ApiResponse<Vec<AdminAuditEntry>>> {
+    let limit = query.limit.unwrap_or(DEFAULT_GENOME_PAGE).clamp(1, MAX_GENOME_PAGE);
+    match state.database.load_admin_audit(limit).await {
+        Ok(entries) => ApiResponse::ok(entries),
         Err(e) => ApiResponse::err(e.to_string()),
     }
 }
 
-#[derive(Deserialize)]
-pub struct CrisprJoinRequest { pub genome_id: i64, pub pos1: usize, pub pos2: usize }
-
-async fn crispr_join(State(state): State<AppState>, Json(req): Json<CrisprJoinRequest>) -> Json<ApiResponse<GenomeResponse>> {
-    let mut genome = match state.database.load_genome(req.genome_id).await {
-        Ok(g) => g,
-        Err(e) => return ApiResponse::err(e.to_string()),
-    };
-    if req.pos1 >= 27 || req.pos2 >= 27 { return ApiResponse::err("Positions must be 0-26".into()); }
-    genome.crispr_join(req.pos1, req.pos2);
-    match state.database.store_genome(&genome).await {
-        Ok(id) => { let mut s = genome; s.db_id = Some(id); ApiResponse::ok((&s).into()) }
+#[utoipa::path(
+    get, path = "/api/admin/keys", tag = "Admin",
+    summary = "Every API key with its role, revoked ones included",
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn admin_api_keys(State(state): State<AppState>) -> Json<ApiResponse<Vec<ApiKeyRecord>>> {
+    match state.database.load_api_keys().await {
+        Ok(keys) => ApiResponse::ok(keys),
         Err(e) => ApiResponse::err(e.to_string()),
     }
 }
 
-#[derive(Deserialize)]
-pub struct CrisprDeleteRequest { pub genome_id: i64, pub position: usize }
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RoleRequest {
+    /// `reader`, `operator` or `admin`
+    #[schema(value_type = String)]
+    pub role: Role,
+}
 
-async fn crispr_delete(State(state): State<AppState>, Json(req): Json<CrisprDeleteRequest>) -> Json<ApiResponse<GenomeResponse>> {
-    let mut genome = match state.database.load_genome(req.genome_id).await {
-        Ok(g) => g,
-        Err(e) => return ApiResponse::err(e.to_string()),
-    };
-    if req.position >= 27 { return ApiResponse::err("Position must be 0-26".into()); }
-    genome.crispr_delete(req.position);
-    match state.database.store_genome(&genome).await {
-        Ok(id) => { let mut s = genome; s.db_id = Some(id); ApiResponse::ok((&s).into()) }
+#[utoipa::path(
+    post, path = "/api/admin/keys/{id}/role", tag = "Admin",
+    summary = "Change an API key's role (`{ \"role\": \"reader\" }`); tokens already issued take it at once",
+    params(("id" = i64, Path)),
+    request_body = RoleRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn admin_set_key_role(
+    State(state): State<AppState>,
+    Extension(claims): Extension<JwtClaims>,
+    Path(id): Path<i64>,
+    Json(req): Json<RoleRequest>,
+) -> Json<ApiResponse<ApiKeyRecord>> {
+    if id == claims.key_id {
+        return ApiResponse::err("Cannot change the role of the calling key (ask another admin)".into());
+    }
+    if let Err(e) = audit(&state, &claims, "keys.role", serde_json::json!({ "key_id": id, "role": req.role })).await {
+        return ApiResponse::err(e);
+    }
+    match state.database.set_api_key_role(id, req.role).await {
+        Ok(Some(key)) => ApiResponse::ok(key),
+        Ok(None) => ApiResponse::err(format!("No active API key #{}", id)),
         Err(e) => ApiResponse::err(e.to_string()),
     }
 }
 
-// RSM handlers
-async fn rsm_stats(State(state): State<AppState>) -> Json<ApiResponse<ExchangeStats>> {
-    ApiResponse::ok(state.exchange.read().await.stats())
+// ═══════════════════════════════════════════════════════════════
+// WEBHOOK HANDLERS
+// ═══════════════════════════════════════════════════════════════
+
+/// Deliver every new block to the webhooks subscribed to `block.added`
+async fn spawn_block_webhooks(state: &AppState) {
+    let mut events = state.consensus.read().await.subscribe();
+    let webhooks = Arc::clone(&state.webhooks);
+    tokio::spawn(async move {
+        loop {
+            match events.recv().await {
+                Ok(ChainEvent::BlockAdded(block)) => webhooks.emit(WebhookEvent::block_added(&block)).await,
+                Ok(_) => {}
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!("🪝 {} chain events missed by webhooks", skipped);
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    });
 }
 
-#[derive(Deserialize)]
-pub struct BuyRequest { pub wallet: String, pub usd_amount: f64, pub consciousness: u32 }
+#[derive(Deserialize, ToSchema)]
+pub struct WebhookRequest {
+    pub url: String,
+    /// `block.added`, `archive.confirmed` and/or `evolution.finished`
+    #[schema(value_type = Vec<String>)]
+    pub events: Vec<WebhookEventKind>,
+}
 
-async fn rsm_buy(State(state): State<AppState>, Json(req): Json<BuyRequest>) -> Json<ApiResponse<Transaction>> {
-    let mut exchange = state.exchange.write().await;
-    let tx = exchange.buy_rsm(&req.wallet, req.usd_amount, req.consciousness);
-    ApiResponse::ok(tx)
+/// A new webhook and the secret its payloads are signed with (shown once)
+#[derive(Serialize)]
+pub struct WebhookCreated {
+    #[serde(flatten)]
+    pub webhook: Webhook,
+    pub secret: String,
 }
 
-#[derive(Deserialize)]
-pub struct SellRequest { pub wallet: String, pub rsm_amount: f64, pub consciousness: u32 }
+/// Webhook `id` if the caller may see it (its own, or any for admin keys)
+async fn owned_webhook(state: &AppState, claims: &JwtClaims, id: i64) -> Result<Webhook, String> {
+    match state.webhooks.get(id).await {
+        Some(webhook) if webhook.key_id == claims.key_id || claims.has_role(Role::Admin) => Ok(webhook),
+        _ => Err(format!("Webhook {} not found", id)),
+    }
+}
 
-async fn rsm_sell(State(state): State<AppState>, Json(req): Json<SellRequest>) -> Json<ApiResponse<Transaction>> {
-    let mut exchange = state.exchange.write().await;
-    match exchange.sell_rsm(&req.wallet, req.rsm_amount, req.consciousness) {
-        Some(tx) => ApiResponse::ok(tx),
-        None => ApiResponse::err("Insufficient balance".into()),
+#[utoipa::path(
+    post, path = "/api/webhooks", tag = "Webhooks",
+    summary = "Register a URL for signed event callbacks; returns its signing secret once",
+    request_body = WebhookRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn create_webhook(
+    State(state): State<AppState>,
+    Extension(claims): Extension<JwtClaims>,
+    Json(req): Json<WebhookRequest>,
+) -> Json<ApiResponse<WebhookCreated>> {
+    match state.webhooks.register(req.url, req.events, claims.key_id).await {
+        Ok(webhook) => {
+            info!("🪝 Webhook #{} registered by {} | {}", webhook.id, claims.sub, webhook.url);
+            let secret = webhook.secret.clone();
+            ApiResponse::ok(WebhookCreated { webhook, secret })
+        }
+        Err(e) => ApiResponse::err(e),
     }
 }
 
-#[derive(Deserialize)]
-pub struct TransferRequest { pub from_wallet: String, pub to_wallet: String, pub amount: f64 }
+#[utoipa::path(
+    get, path = "/api/webhooks", tag = "Webhooks",
+    summary = "Webhooks registered by the caller's API key (all of them for admin keys)",
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn list_webhooks(State(state): State<AppState>, Extension(claims): Extension<JwtClaims>) -> Json<ApiResponse<Vec<Webhook>>> {
+    let webhooks = state.webhooks.webhooks().await;
+    ApiResponse::ok(webhooks.into_iter().filter(|w| w.key_id == claims.key_id || claims.has_role(Role::Admin)).collect())
+}
 
-async fn rsm_transfer(State(state): State<AppState>, Json(req): Json<TransferRequest>) -> Json<ApiResponse<Transaction>> {
-    let mut exchange = state.exchange.write().await;
-    match exchange.transfer(&req.from_wallet, &req.to_wallet, req.amount) {
-        Some(tx) => ApiResponse::ok(tx),
-        None => ApiResponse::err("Insufficient balance".into()),
+#[utoipa::path(
+    delete, path = "/api/webhooks/{id}", tag = "Webhooks",
+    summary = "Remove a webhook, its pending retries and its delivery log",
+    params(("id" = i64, Path)),
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn delete_webhook(State(state): State<AppState>, Extension(claims): Extension<JwtClaims>, Path(id): Path<i64>) -> Json<ApiResponse<i64>> {
+    if let Err(e) = owned_webhook(&state, &claims, id).await {
+        return ApiResponse::err(e);
+    }
+    match state.webhooks.remove(id).await {
+        Ok(_) => ApiResponse::ok(id),
+        Err(e) => ApiResponse::err(e),
     }
 }
 
 #[derive(Deserialize)]
-pub struct RewardRequest { pub wallet: String, pub consciousness: u32 }
+pub struct DeliveryQuery { pub limit: Option<i64> }
 
-async fn rsm_reward(State(state): State<AppState>, Json(req): Json<RewardRequest>) -> Json<ApiResponse<Transaction>> {
-    let mut exchange = state.exchange.write().await;
-    let tx = exchange.consciousness_reward(&req.wallet, req.consciousness);
-    ApiResponse::ok(tx)
+#[utoipa::path(
+    get, path = "/api/webhooks/{id}/deliveries", tag = "Webhooks",
+    summary = "Delivery log of a webhook, newest first: status, attempts, last response or error (`?limit=`)",
+    params(("id" = i64, Path), ("limit" = Option<i64>, Query)),
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn webhook_deliveries(
+    State(state): State<AppState>,
+    Extension(claims): Extension<JwtClaims>,
+    Path(id): Path<i64>,
+    Query(query): Query<DeliveryQuery>,
+) -> Json<ApiResponse<Vec<WebhookDelivery>>> {
+    if let Err(e) = owned_webhook(&state, &claims, id).await {
+        return ApiResponse::err(e);
+    }
+    let limit = query.limit.unwrap_or(DEFAULT_GENOME_PAGE).clamp(1, MAX_GENOME_PAGE);
+    match state.database.load_webhook_deliveries(id, limit).await {
+        Ok(deliveries) => ApiResponse::ok(deliveries),
+        Err(e) => ApiResponse::err(e.to_string()),
+    }
 }
 
-#[derive(Deserialize)]
-pub struct ManualBurnRequest { pub amount: f64 }
+// ═══════════════════════════════════════════════════════════════
+// AUTH & WALLET HANDLERS
+// ═══════════════════════════════════════════════════════════════
 
-async fn rsm_manual_burn(State(state): State<AppState>, Json(req): Json<ManualBurnRequest>) -> Json<ApiResponse<BurnEvent>> {
-    let mut exchange = state.exchange.write().await;
-    let event = exchange.burn(req.amount, BurnReason::ManualBurn, None, 0, 0);
-    ApiResponse::ok(event)
-}
+/// Mutating routes that check their own credentials (passwords, session tokens)
+const SELF_AUTHENTICATED_ROUTES: &[&str] = &[
+    "/auth/token",
+    "/api/auth/register",
+    "/api/auth/login",
+    "/api/auth/logout",
+    "/api/wallet/deposit",
+    "/api/wallet/withdraw",
+];
 
-async fn list_transactions(State(state): State<AppState>) -> Json<ApiResponse<Vec<Transaction>>> {
-    let exchange = state.exchange.read().await;
-    ApiResponse::ok(exchange.recent_transactions(50))
+/// Routes only admin keys may call, reads included
+const ADMIN_ROUTE_PREFIX: &str = "/api/admin/";
+
+/// GET routes that need a token anyway (the node's own wallet, webhooks);
+/// entries ending in `/` also cover the routes below them
+const PROTECTED_READ_ROUTES: &[&str] = &[
+    "/api/wallet/balance",
+    "/api/wallet/addresses",
+    "/api/wallet/history",
+    "/api/webhooks",
+    "/api/webhooks/",
+];
+
+fn is_protected_read(path: &str) -> bool {
+    PROTECTED_READ_ROUTES.iter().any(|route| if route.ends_with('/') { path.starts_with(route) } else { path == *route })
 }
 
-async fn list_burns(State(state): State<AppState>) -> Json<ApiResponse<Vec<BurnEvent>>> {
-    let exchange = state.exchange.read().await;
-    ApiResponse::ok(exchange.recent_burns(50))
+/// POST routes that only read (queries too large for a URL)
+const READ_ONLY_POST_ROUTES: &[&str] = &[
+    "/graphql",
+    "/api/rsm/request",
+    "/api/poc/proof",
+    "/api/poc/proof/verify",
+    "/api/poc/wallet",
+    "/api/poc/history",
+    "/api/poc/history/export",
+];
+
+/// Role a request needs: admin for `/api/admin/`, reader for protected
+/// reads, operator for other writes; `None` for open routes
+pub fn required_role(method: &Method, path: &str) -> Option<Role> {
+    let read = matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS);
+    if path.starts_with(ADMIN_ROUTE_PREFIX) {
+        Some(Role::Admin)
+    } else if SELF_AUTHENTICATED_ROUTES.contains(&path) || READ_ONLY_POST_ROUTES.contains(&path) || (read && !is_protected_read(path)) {
+        None
+    } else if read {
+        Some(Role::Reader)
+    } else {
+        Some(Role::Operator)
+    }
 }
 
-async fn debt_stats(State(state): State<AppState>) -> Json<ApiResponse<DebtStats>> {
-    ApiResponse::ok(state.exchange.read().await.debt_stats())
+/// Token claims with the role the key holds now; revoked keys fail
+pub async fn refresh_claims(state: &AppState, claims: JwtClaims) -> Result<JwtClaims, String> {
+    let key = state.database.load_api_key(claims.key_id).await
+        .map_err(|e| format!("Could not check API key {}: {}", claims.sub, e))?;
+    claims.refresh(key.as_ref())
 }
 
-async fn owner_pool(State(state): State<AppState>) -> Json<ApiResponse<OwnerPoolStats>> {
-    ApiResponse::ok(state.exchange.read().await.owner_pool())
+/// Require `Authorization: Bearer <jwt>` with a role covering the route
+/// (`required_role`), checked against the key's current role; valid claims
+/// are passed on to the handlers (and the rate limiter) either way
+async fn require_auth(State(state): State<AppState>, mut request: Request, next: Next) -> Response {
+    let token = request.headers().get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    let verified = match token {
+        Some(token) => state.auth.read().await.verify_jwt(token),
+        None => Err("Missing bearer token (POST /auth/token)".into()),
+    };
+    let verified = match verified {
+        Ok(claims) => refresh_claims(&state, claims).await,
+        Err(e) => Err(e),
+    };
+
+    let required = required_role(request.method(), request.uri().path());
+    if required == Some(Role::Admin) && request.extensions().get::<ClientCertificate>() == Some(&ClientCertificate::Missing) {
+        return (StatusCode::FORBIDDEN, ApiResponse::<()>::err("Admin routes need a client certificate".into())).into_response();
+    }
+    match (verified, required) {
+        (Ok(claims), Some(role)) if !claims.has_role(role) => {
+            let message = format!("API key {} has role {}; this route needs {}", claims.sub, claims.role.name(), role.name());
+            (StatusCode::FORBIDDEN, ApiResponse::<()>::err(message)).into_response()
+        }
+        (Ok(claims), _) => {
+            request.extensions_mut().insert(claims);
+            next.run(request).await
+        }
+        (Err(_), None) => next.run(request).await,
+        (Err(e), Some(_)) => (StatusCode::UNAUTHORIZED, ApiResponse::<()>::err(e)).into_response(),
+    }
 }
 
-// Multi-chain & LN handlers
-#[derive(Deserialize)]
-pub struct ArchiveRequest { pub genome_id: i64 }
+/// Token cost of expensive non-GET routes (everything else costs 1);
+/// entries ending in `/` also cover the routes below them
+const ROUTE_COSTS: &[(&str, f64)] = &[
+    ("/api/evolution/start", 20.0),
+    ("/api/evolution/jobs", 20.0),
+    ("/api/genome/evolve", 5.0),
+    ("/api/genome/meiosis", 5.0),
+    ("/api/poc/validate", 10.0),
+    ("/api/poc/mine", 10.0),
+    ("/api/archive", 5.0),
+    ("/api/archive/batch", 50.0),
+    ("/api/archive/", 5.0),
+    ("/api/genomes/import", 50.0),
+    ("/graphql", 5.0),
+];
 
-async fn archive_genome(State(state): State<AppState>, Json(req): Json<ArchiveRequest>) -> Json<ApiResponse<ChainArchiveEntry>> {
-    let genome = match state.database.load_genome(req.genome_id).await {
-        Ok(g) => g,
-        Err(e) => return ApiResponse::err(e.to_string()),
+/// Token bucket per API key, or per client IP for anonymous requests
+async fn rate_limit(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let client = match request.extensions().get::<JwtClaims>() {
+        Some(claims) => format!("key:{}", claims.key_id),
+        None => {
+            let peer = request.extensions().get::<ConnectInfo<SocketAddr>>().map(|info| info.0.ip());
+            format!("ip:{}", state.rate_limiter.read().await.config.client_ip(request.headers(), peer))
+        }
     };
-    let mut archiver = state.archiver.write().await;
-    match archiver.archive(&genome).await {
-        Ok(entry) => ApiResponse::ok(entry),
-        Err(e) => ApiResponse::err(e),
+    let path = request.uri().path();
+    let cost = if matches!(*request.method(), Method::GET | Method::HEAD | Method::OPTIONS) {
+        1.0
+    } else {
+        ROUTE_COSTS.iter()
+            .find(|(route, _)| if route.ends_with('/') { path.starts_with(route) } else { path == *route })
+            .map_or(1.0, |(_, cost)| *cost)
+    };
+
+    let checked = state.rate_limiter.write().await.check(&client, cost);
+    match checked {
+        Ok(()) => next.run(request).await,
+        Err(retry_after) => {
+            let mut response = (
+                StatusCode::TOO_MANY_REQUESTS,
+                ApiResponse::<()>::err(format!("Rate limit exceeded; retry in {}s", retry_after)),
+            ).into_response();
+            response.headers_mut().insert(RETRY_AFTER, retry_after.into());
+            response
+        }
     }
 }
 
-async fn list_archives(State(state): State<AppState>) -> Json<ApiResponse<Vec<ChainArchiveEntry>>> {
-    let archiver = state.archiver.read().await;
-    let archives: Vec<ChainArchiveEntry> = archiver.recent_archives(50).into_iter().cloned().collect();
-    ApiResponse::ok(archives)
+/// Hot reads served from `ResponseCache` for a TTL; entries ending in `/`
+/// also cover the routes below them
+const CACHED_ROUTES: &[&str] = &[
+    "/api/status",
+    "/api/genomes",
+    "/api/genomes/",
+    "/api/rsm/stats",
+    "/api/transactions",
+    "/api/burns",
+    "/api/debt/stats",
+    "/api/owner/pool",
+    "/api/archives",
+    "/api/mission-control",
+    "/api/poc/status",
+    "/api/poc/metrics",
+    "/metrics",
+    "/api/rotation/stats",
+];
+
+fn is_cached_route(path: &str) -> bool {
+    CACHED_ROUTES.iter().any(|route| if route.ends_with('/') { path.starts_with(route) } else { path == *route })
 }
 
-async fn mission_control_stats(State(state): State<AppState>) -> Json<ApiResponse<MissionControlStats>> {
-    let archiver = state.archiver.read().await;
-    ApiResponse::ok(archiver.mission_control_stats())
+/// `ETag` on every `200` GET (`304` when `If-None-Match` matches), cached
+/// bodies for `CACHED_ROUTES`; successful writes empty the cache
+async fn cache_responses(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let path = request.uri().path().to_string();
+    if request.method() != Method::GET {
+        let write = !matches!(*request.method(), Method::HEAD | Method::OPTIONS) && !READ_ONLY_POST_ROUTES.contains(&path.as_str());
+        let response = next.run(request).await;
+        if write && response.status().is_success() {
+            state.response_cache.write().await.clear();
+        }
+        return response;
+    }
+
+    let if_none_match = request.headers().get(IF_NONE_MATCH).and_then(|v| v.to_str().ok()).map(str::to_string);
+    let key = request.uri().path_and_query().map_or(path.clone(), |pq| pq.to_string());
+    let cached = is_cached_route(&path);
+    if cached {
+        if let Some(hit) = state.response_cache.read().await.get(&key) {
+            let mut response = Response::new(Body::from(hit.body.clone()));
+            if let Some(content_type) = hit.content_type.as_deref().and_then(|ct| HeaderValue::from_str(ct).ok()) {
+                response.headers_mut().insert(CONTENT_TYPE, content_type);
+            }
+            return with_etag(response, &hit.etag, if_none_match.as_deref());
+        }
+    }
+
+    let response = next.run(request).await;
+    let streaming = response.headers().get(CONTENT_TYPE).is_some_and(|ct| ct.as_bytes().starts_with(b"text/event-stream"));
+    if response.status() != StatusCode::OK || streaming {
+        return response;
+    }
+    let (parts, body) = response.into_parts();
+    let body = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, ApiResponse::<()>::err(e.to_string())).into_response(),
+    };
+    let etag = if cached {
+        let content_type = parts.headers.get(CONTENT_TYPE).and_then(|ct| ct.to_str().ok()).map(str::to_string);
+        state.response_cache.write().await.insert(key, body.clone(), content_type)
+    } else {
+        response_cache::etag(&body)
+    };
+    with_etag(Response::from_parts(parts, Body::from(body)), &etag, if_none_match.as_deref())
 }
 
-async fn reset_mission_control(State(state): State<AppState>) -> Json<ApiResponse<String>> {
-    let mut archiver = state.archiver.write().await;
-    archiver.mission_control.reset();
-    ApiResponse::ok("Mission Control reset".to_string())
+/// Tag a `200` response, or replace it with `304` when the client has it
+fn with_etag(mut response: Response, etag: &str, if_none_match: Option<&str>) -> Response {
+    let Ok(value) = HeaderValue::from_str(etag) else {
+        return response;
+    };
+    if if_none_match.is_some_and(|header| response_cache::if_none_match(header, etag)) {
+        let mut not_modified = StatusCode::NOT_MODIFIED.into_response();
+        not_modified.headers_mut().insert(ETAG, value);
+        not_modified.headers_mut().insert(CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+        return not_modified;
+    }
+    response.headers_mut().insert(ETAG, value);
+    response.headers_mut().entry(CACHE_CONTROL).or_insert(HeaderValue::from_static("no-cache"));
+    response
 }
 
-// Rotation handlers
-async fn rotation_stats(State(state): State<AppState>) -> Json<ApiResponse<RotationStats>> {
-    let engine = state.rotation_engine.read().await;
-    ApiResponse::ok(engine.get_stats())
+/// Serve `app` under `/v1/` too; versioned paths are rewritten before
+/// `app` matches them
+pub fn with_versioning(app: Router) -> Router {
+    Router::new().fallback_service(app).layer(middleware::from_fn(api_version))
 }
 
-async fn manual_rotate(State(state): State<AppState>) -> Json<ApiResponse<RotationStats>> {
-    let mut engine = state.rotation_engine.write().await;
-    engine.rotate();
-    ApiResponse::ok(engine.get_stats())
+/// Route `/v1/…` paths and `API-Version` headers to the router's paths;
+/// flag unversioned calls as deprecated
+async fn api_version(mut request: Request, next: Next) -> Response {
+    let header = request.headers().get(VERSION_HEADER).and_then(|value| value.to_str().ok());
+    let route = match versioning::resolve(request.uri().path(), header) {
+        Ok(route) => route,
+        Err(e) => return (StatusCode::BAD_REQUEST, ApiResponse::<()>::err(e)).into_response(),
+    };
+
+    if route.path != request.uri().path() {
+        let path_and_query = match request.uri().query() {
+            Some(query) => format!("{}?{}", route.path, query),
+            None => route.path.clone(),
+        };
+        let mut parts = request.uri().clone().into_parts();
+        parts.path_and_query = path_and_query.parse().ok();
+        match Uri::from_parts(parts) {
+            Ok(uri) => *request.uri_mut() = uri,
+            Err(e) => return (StatusCode::BAD_REQUEST, ApiResponse::<()>::err(e.to_string())).into_response(),
+        }
+    }
+    let Some(version) = route.version else {
+        return next.run(request).await;
+    };
+    request.extensions_mut().insert(ApiVersion(version));
+
+    let mut response = next.run(request).await;
+    let headers = response.headers_mut();
+    headers.insert(VERSION_HEADER, HeaderValue::from(version));
+    if let Some(successor) = route.successor.and_then(|path| HeaderValue::from_str(&format!("<{}>; rel=\"successor-version\"", path)).ok()) {
+        headers.insert("deprecation", HeaderValue::from_static("true"));
+        headers.insert(LINK, successor);
+    }
+    response
 }
 
-// ═══════════════════════════════════════════════════════════════
-// AUTH & WALLET HANDLERS
-// ═══════════════════════════════════════════════════════════════
+#[derive(Deserialize, ToSchema)]
+pub struct ApiTokenRequest {
+    pub api_key: String,
+}
+
+#[derive(Serialize)]
+pub struct ApiTokenResponse {
+    pub token: String,
+    pub token_type: String,
+    pub claims: JwtClaims,
+}
+
+/// Exchange an API key for a short-lived JWT
+#[utoipa::path(
+    post, path = "/auth/token", tag = "API tokens",
+    summary = "Short-lived API JWT for an API key",
+    request_body = ApiTokenRequest,
+    responses(EnvelopeResponses)
+)]
+async fn issue_api_token(State(state): State<AppState>, Json(req): Json<ApiTokenRequest>) -> Response {
+    let key = match state.database.find_api_key(&AuthManager::hash_api_key(&req.api_key)).await {
+        Ok(Some(key)) => key,
+        Ok(None) => return (StatusCode::UNAUTHORIZED, ApiResponse::<()>::err("Unknown or revoked API key".into())).into_response(),
+        Err(e) => return ApiResponse::<()>::err(e.to_string()).into_response(),
+    };
+    let (token, claims) = state.auth.read().await.issue_jwt(&key);
+    info!("🔑 API token issued for key #{} ({})", key.id, key.name);
+    ApiResponse::ok(ApiTokenResponse { token, token_type: "Bearer".into(), claims }).into_response()
+}
 
 const FOUNDER_POOL_RSM: f64 = 1_428_571_428_571_428.0; // 1/7 of 10 quadrillion
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct TokenRequest {
     pub token: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct DepositRequest {
     pub token: String,
     pub amount_rsm: f64,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct WithdrawRequest {
     pub token: String,
     pub amount_rsm: f64,
 }
 
+#[utoipa::path(
+    post, path = "/api/auth/register", tag = "Auth & Wallet",
+    summary = "Register wallet",
+    request_body = RegisterRequest,
+    responses(EnvelopeResponses)
+)]
 async fn auth_register(
     State(state): State<AppState>,
     Json(req): Json<RegisterRequest>,
@@ -693,6 +4535,12 @@ async fn auth_register(
     }
 }
 
+#[utoipa::path(
+    post, path = "/api/auth/login", tag = "Auth & Wallet",
+    summary = "Login",
+    request_body = LoginRequest,
+    responses(EnvelopeResponses)
+)]
 async fn auth_login(
     State(state): State<AppState>,
     Json(req): Json<LoginRequest>,
@@ -761,6 +4609,12 @@ async fn auth_login(
     })
 }
 
+#[utoipa::path(
+    post, path = "/api/auth/logout", tag = "Auth & Wallet",
+    summary = "Logout (invalidate a session token)",
+    request_body = TokenRequest,
+    responses(EnvelopeResponses)
+)]
 async fn auth_logout(
     State(state): State<AppState>,
     Json(req): Json<TokenRequest>,
@@ -773,6 +4627,12 @@ async fn auth_logout(
     }
 }
 
+#[utoipa::path(
+    get, path = "/api/auth/profile", tag = "Auth & Wallet",
+    summary = "Account of a session token",
+    params(("token" = String, Query, description = "Session token from /api/auth/login")),
+    responses(EnvelopeResponses)
+)]
 async fn auth_profile(
     State(state): State<AppState>,
     axum::extract::Query(params): axum::extract::Query<std::collections::HashMap<String, String>>,
@@ -808,6 +4668,12 @@ async fn auth_profile(
     })
 }
 
+#[utoipa::path(
+    get, path = "/api/wallet/info", tag = "Auth & Wallet",
+    summary = "Wallet of a session token",
+    params(("token" = String, Query, description = "Session token from /api/auth/login")),
+    responses(EnvelopeResponses)
+)]
 async fn wallet_info(
     State(state): State<AppState>,
     axum::extract::Query(params): axum::extract::Query<std::collections::HashMap<String, String>>,
@@ -838,6 +4704,12 @@ async fn wallet_info(
     })
 }
 
+#[utoipa::path(
+    post, path = "/api/wallet/deposit", tag = "Auth & Wallet",
+    summary = "Deposit RSM into a session's wallet",
+    request_body = DepositRequest,
+    responses(EnvelopeResponses)
+)]
 async fn wallet_deposit(
     State(state): State<AppState>,
     Json(req): Json<DepositRequest>,
@@ -879,6 +4751,12 @@ async fn wallet_deposit(
     })
 }
 
+#[utoipa::path(
+    post, path = "/api/wallet/withdraw", tag = "Auth & Wallet",
+    summary = "Withdraw RSM from a session's wallet",
+    request_body = WithdrawRequest,
+    responses(EnvelopeResponses)
+)]
 async fn wallet_withdraw(
     State(state): State<AppState>,
     Json(req): Json<WithdrawRequest>,
@@ -928,6 +4806,11 @@ async fn wallet_withdraw(
     })
 }
 
+#[utoipa::path(
+    get, path = "/api/wallet/list", tag = "Auth & Wallet",
+    summary = "All wallet accounts",
+    responses(EnvelopeResponses)
+)]
 async fn wallet_list(State(state): State<AppState>) -> Json<ApiResponse<Vec<WalletInfo>>> {
     match state.database.get_all_wallets().await {
         Ok(wallets) => {
@@ -949,3 +4832,230 @@ async fn wallet_list(State(state): State<AppState>) -> Json<ApiResponse<Vec<Wall
         Err(e) => ApiResponse::err(e.to_string()),
     }
 }
+
+// Node wallet: the addresses of this node's validator key, for operators
+
+/// Wallet `address`, or the node's first address when none is given
+async fn node_wallet_address(state: &AppState, address: Option<String>) -> Result<String, String> {
+    if let Some(address) = address {
+        return Ok(address);
+    }
+    state.consensus.read().await.node_addresses().into_iter().next()
+        .map(|a| a.address)
+        .ok_or_else(|| "No validator signing key; give an address".to_string())
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct NodeWalletQuery {
+    pub address: Option<String>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Serialize)]
+pub struct NodeWalletBalance {
+    pub address: String,
+    /// Spendable on the RSM exchange (what `/api/wallet/transfer` moves)
+    pub exchange_rsm: f64,
+    /// Spendable consensus balance (rewards, premine, fees)
+    pub chain_rsm: f64,
+    pub staked_rsm: f64,
+    pub rewards_earned: f64,
+    pub spent_last_day_rsm: f64,
+    pub policy: Option<SpendingPolicy>,
+}
+
+#[utoipa::path(
+    get, path = "/api/wallet/balance", tag = "Auth & Wallet",
+    summary = "Exchange and chain balances of a node address (`?address=`, default the validator wallet)",
+    params(("address" = Option<String>, Query)),
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn node_wallet_balance(State(state): State<AppState>, Query(query): Query<NodeWalletQuery>) -> Json<ApiResponse<NodeWalletBalance>> {
+    let address = match node_wallet_address(&state, query.address).await {
+        Ok(address) => address,
+        Err(e) => return ApiResponse::err(e),
+    };
+    let consensus = state.consensus.read().await;
+    let wallet = consensus.wallet(&address);
+    let exchange = state.exchange.read().await;
+    ApiResponse::ok(NodeWalletBalance {
+        exchange_rsm: exchange.get_balance(&address),
+        chain_rsm: wallet.map_or(0.0, |w| w.rsm_balance),
+        staked_rsm: wallet.map_or(0.0, |w| w.staked_rsm),
+        rewards_earned: wallet.map_or(0.0, |w| w.rewards_earned),
+        spent_last_day_rsm: exchange.spent_last_day(&address),
+        policy: exchange.policies.get(&address).cloned(),
+        address,
+    })
+}
+
+#[utoipa::path(
+    get, path = "/api/wallet/addresses", tag = "Auth & Wallet",
+    summary = "Addresses this node controls (validator wallet, reward address)",
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn node_wallet_addresses(State(state): State<AppState>) -> Json<ApiResponse<Vec<NodeAddress>>> {
+    ApiResponse::ok(state.consensus.read().await.node_addresses())
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct NodeTransferRequest {
+    /// One of the node's addresses; defaults to the validator wallet
+    pub from: Option<String>,
+    pub to: String,
+    pub amount_rsm: f64,
+    pub memo: Option<String>,
+}
+
+/// Exchange transfer signed by the node's validator key (over `transaction.hash`).
+/// It moves the RSM exchange ledger only; the consensus balance of `from`
+/// (rewards, premine, fees) is left as it was and reported alongside.
+#[derive(Serialize)]
+pub struct SignedTransfer {
+    pub transaction: Transaction,
+    pub public_key: String,
+    pub signature: String,
+    /// Exchange balance of `from` after the transfer
+    pub exchange_rsm: f64,
+    /// Consensus balance of `from`, untouched by the transfer
+    pub chain_rsm: f64,
+}
+
+#[utoipa::path(
+    post, path = "/api/wallet/transfer", tag = "Auth & Wallet",
+    summary = "Send exchange RSM (not the chain balance) from a node address under its spending policy; signed with the validator key",
+    request_body = NodeTransferRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn node_wallet_transfer(
+    State(state): State<AppState>,
+    claims: Option<Extension<JwtClaims>>,
+    Json(req): Json<NodeTransferRequest>,
+) -> Json<ApiResponse<SignedTransfer>> {
+    if !req.amount_rsm.is_finite() || req.amount_rsm <= 0.0 {
+        return ApiResponse::err(format!("Invalid amount {}", req.amount_rsm));
+    }
+    let consensus = state.consensus.read().await;
+    let addresses = consensus.node_addresses();
+    let from = match req.from {
+        Some(from) if addresses.iter().any(|a| a.address == from) => from,
+        Some(from) => return ApiResponse::err(format!("{} is not an address of this node", from)),
+        None => match addresses.first() {
+            Some(a) => a.address.clone(),
+            None => return ApiResponse::err("No validator signing key".into()),
+        },
+    };
+
+    // The exchange ledger is debited: consensus has no account-to-account
+    // transfer, so the chain balance only changes through fees and stake ops
+    let mut exchange = state.exchange.write().await;
+    let transaction = match exchange.transfer_with_memo(&from, &req.to, req.amount_rsm, req.memo) {
+        Ok(tx) => tx,
+        Err(e) => return ApiResponse::err(e),
+    };
+    let exchange_rsm = exchange.get_balance(&from);
+    drop(exchange);
+    let chain_rsm = consensus.wallet(&from).map_or(0.0, |w| w.rsm_balance);
+    let (public_key, signature) = match consensus.sign_as_validator(transaction.hash.as_bytes()) {
+        Ok(signed) => signed,
+        Err(e) => return ApiResponse::err(e),
+    };
+
+    let caller = claims.map_or_else(|| "unknown".to_string(), |Extension(c)| c.sub);
+    info!("📤 Node wallet transfer by {}: {:.6} RSM {} → {}", caller, transaction.amount_rsm, from, transaction.to_address);
+    ApiResponse::ok(SignedTransfer { transaction, public_key, signature, exchange_rsm, chain_rsm })
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct NodeStakeRequest {
+    pub amount_rsm: f64,
+}
+
+/// A queued stake operation and the validator wallet once it lands
+#[derive(Serialize)]
+pub struct NodeWalletStake {
+    pub op: StakeOp,
+    pub address: String,
+    pub spendable_rsm: f64,
+    pub staked_rsm: f64,
+}
+
+/// Queue a stake change of the validator wallet, answered with the wallet as
+/// it will be once it is in a block
+async fn queue_node_stake(state: &AppState, lock: bool, amount_rsm: f64) -> Json<ApiResponse<NodeWalletStake>> {
+    let mut consensus = state.consensus.write().await;
+    let queued = match lock {
+        true => consensus.stake_rsm(amount_rsm),
+        false => consensus.unstake_rsm(amount_rsm),
+    };
+    let op = match queued {
+        Ok(op) => op,
+        Err(e) => return ApiResponse::err(e),
+    };
+    match consensus.wallet_after_queued_stake() {
+        Ok(wallet) => {
+            info!("🔒 Node wallet {} {:.6} RSM ({:.6} staked once in a block)", if lock { "stakes" } else { "unstakes" }, amount_rsm, wallet.staked_rsm);
+            ApiResponse::ok(NodeWalletStake { op, spendable_rsm: wallet.rsm_balance, staked_rsm: wallet.staked_rsm, address: wallet.address })
+        }
+        Err(e) => ApiResponse::err(e),
+    }
+}
+
+#[utoipa::path(
+    post, path = "/api/wallet/stake", tag = "Auth & Wallet",
+    summary = "Lock RSM of the validator wallet as stake (applied with the next block); refused beyond its spendable chain balance",
+    request_body = NodeStakeRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn node_wallet_stake(State(state): State<AppState>, Json(req): Json<NodeStakeRequest>) -> Json<ApiResponse<NodeWalletStake>> {
+    queue_node_stake(&state, true, req.amount_rsm).await
+}
+
+#[utoipa::path(
+    post, path = "/api/wallet/unstake", tag = "Auth & Wallet",
+    summary = "Return staked RSM to the validator wallet (applied with the next block); refused beyond its stake",
+    request_body = NodeStakeRequest,
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn node_wallet_unstake(State(state): State<AppState>, Json(req): Json<NodeStakeRequest>) -> Json<ApiResponse<NodeWalletStake>> {
+    queue_node_stake(&state, false, req.amount_rsm).await
+}
+
+#[derive(Serialize)]
+pub struct NodeWalletHistory {
+    pub address: String,
+    /// Chain entries (rewards, fees, stake), newest first
+    pub chain: Vec<WalletTransaction>,
+    /// Exchange transactions in or out, newest first
+    pub exchange: Vec<Transaction>,
+}
+
+#[utoipa::path(
+    get, path = "/api/wallet/history", tag = "Auth & Wallet",
+    summary = "Chain and exchange history of a node address (`?address=&limit=`, newest first)",
+    params(("address" = Option<String>, Query), ("limit" = Option<usize>, Query)),
+    security(("bearer" = [])),
+    responses(ProtectedResponses)
+)]
+async fn node_wallet_history(State(state): State<AppState>, Query(query): Query<NodeWalletQuery>) -> Json<ApiResponse<NodeWalletHistory>> {
+    let address = match node_wallet_address(&state, query.address).await {
+        Ok(address) => address,
+        Err(e) => return ApiResponse::err(e),
+    };
+    let limit = query.limit.unwrap_or(50).min(MAX_GENOME_PAGE as usize);
+
+    let mut chain = state.consensus.read().await.history(&address, &HistoryFilter::default());
+    chain.reverse();
+    chain.truncate(limit);
+    let exchange = state.exchange.read().await.transactions.iter().rev()
+        .filter(|tx| tx.from_address == address || tx.to_address == address)
+        .take(limit)
+        .cloned()
+        .collect();
+    ApiResponse::ok(NodeWalletHistory { address, chain, exchange })
+}
@@ -0,0 +1,130 @@
+//! Archive Policies V1 — where a genome's archive goes
+//!
+//! An `ArchivePolicy` ranks the hash-carrying layers for one genome from an
+//! `ArchiveRequest`: the genome's consciousness and T/G ratio, and for each
+//! layer the payload size and fee of the archive, whether that fee fits the
+//! budget, and Mission Control's learned success probability. The archiver
+//! starts at `select` and fails over down `rank`.
+//!
+//! `ThresholdPolicy`, the default, prefers the layer the genome's T/G
+//! signal and consciousness fit (Lightning above T/G 1.5 and consciousness
+//! 600, Bitcoin above consciousness 900 and below T/G 0.6, Solana above
+//! T/G 1.2, Ethereum otherwise), weighs each layer by its place in that
+//! order times its learned probability, and picks the best one within
+//! budget. Deployments route differently by setting the archiver's
+//! `policy` to their own implementation.
+
+use serde::{Serialize, Deserialize};
+
+use crate::multi_chain::{ArchiveCost, BlockchainLayer};
+
+/// How much `ThresholdPolicy` wants a genome's layers, best first: its own
+/// layer keeps winning until two straight failures put it below the first
+/// fallback's apriori
+pub const LAYER_PREFERENCE_WEIGHTS: [f64; 4] = [1.0, 0.6, 0.5, 0.4];
+
+/// One layer an archive could go to, as a policy sees it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayerOption {
+    pub layer: BlockchainLayer,
+    /// Fee of this genome's payload on the layer (`payload_bytes`, `usd`)
+    pub cost: ArchiveCost,
+    /// Learned probability that an archive to the layer succeeds
+    pub probability: f64,
+    /// Why the fee would break the budget; `None` within it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub over_budget: Option<String>,
+}
+
+impl LayerOption {
+    pub fn within_budget(&self) -> bool {
+        self.over_budget.is_none()
+    }
+}
+
+/// What a policy decides from: the genome and every layer it may use
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchiveRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub genome_id: Option<i64>,
+    pub consciousness: u32,
+    /// The genome's T/G signal (`Genome::rna_signal`)
+    pub tg_ratio: f64,
+    /// The hash-carrying layers, in `BlockchainLayer::ALL` order
+    pub layers: Vec<LayerOption>,
+}
+
+impl ArchiveRequest {
+    pub fn layer(&self, layer: BlockchainLayer) -> Option<&LayerOption> {
+        self.layers.iter().find(|option| option.layer == layer)
+    }
+}
+
+/// Routing logic for archives
+pub trait ArchivePolicy: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// The request's layers scored, best first; failover walks this order.
+    /// Layers not in the request are ignored
+    fn rank(&self, request: &ArchiveRequest) -> Vec<(BlockchainLayer, f64)>;
+
+    /// The layer an archive starts on: the best-ranked within budget, or
+    /// the top of `rank` when none is (archiving then fails)
+    fn select(&self, request: &ArchiveRequest) -> Option<BlockchainLayer> {
+        let ranking = self.rank(request);
+        ranking.iter()
+            .map(|(layer, _)| *layer)
+            .find(|layer| request.layer(*layer).is_some_and(LayerOption::within_budget))
+            .or_else(|| ranking.first().map(|(layer, _)| *layer))
+    }
+}
+
+/// The default policy: T/G and consciousness thresholds pick the genome's
+/// own layer, learned probabilities move it down once it fails
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ThresholdPolicy {
+    /// Weight of the genome's own layer, then of the rest in `ALL` order
+    pub weights: [f64; 4],
+}
+
+impl ThresholdPolicy {
+    /// Layers in the order a genome's T/G signal and consciousness prefer
+    /// them: the one it fits first, then the rest in `ALL` order
+    pub fn preferences(consciousness: u32, tg_ratio: f64) -> Vec<BlockchainLayer> {
+        let preferred = if tg_ratio > 1.5 && consciousness > 600 {
+            BlockchainLayer::Lightning // High T → dynamic
+        } else if consciousness > 900 && tg_ratio < 0.6 {
+            BlockchainLayer::Bitcoin   // Ultra-genome + high G → immortal
+        } else if tg_ratio > 1.2 {
+            BlockchainLayer::Solana    // Moderate T → fast
+        } else {
+            BlockchainLayer::Ethereum  // Default → balanced
+        };
+        std::iter::once(preferred)
+            .chain(BlockchainLayer::ALL.into_iter().filter(|layer| *layer != preferred))
+            .collect()
+    }
+}
+
+impl Default for ThresholdPolicy {
+    fn default() -> Self {
+        Self { weights: LAYER_PREFERENCE_WEIGHTS }
+    }
+}
+
+impl ArchivePolicy for ThresholdPolicy {
+    fn name(&self) -> &str {
+        "threshold"
+    }
+
+    /// Preference weight × learned probability (equal scores keep the more
+    /// preferred layer first)
+    fn rank(&self, request: &ArchiveRequest) -> Vec<(BlockchainLayer, f64)> {
+        let mut ranking: Vec<(BlockchainLayer, f64)> = Self::preferences(request.consciousness, request.tg_ratio).into_iter()
+            .zip(self.weights)
+            .filter_map(|(layer, weight)| request.layer(layer).map(|option| (layer, weight * option.probability)))
+            .collect();
+        ranking.sort_by(|a, b| b.1.total_cmp(&a.1));
+        ranking
+    }
+}
@@ -0,0 +1,165 @@
+//! Genome Auctions V1 — English auctions for rare genomes
+//!
+//! Only genomes the chain would consecrate — consciousness at or above the
+//! current PoC threshold — go up for auction; everything else is sold
+//! through fixed-price listings (`marketplace`). The owner sets a reserve
+//! price, which is also the opening bid, and a duration.
+//!
+//! Each bid must beat the high bid by `MIN_RAISE`. The bid's RSM moves into
+//! the auction's escrow wallet (`escrow_address`) and the bid it outbids is
+//! refunded from there, all while the bid is recorded so either both happen
+//! or neither does. A bid in the last `SNIPE_WINDOW_SECS` pushes the end
+//! back to `SNIPE_WINDOW_SECS` after it.
+//!
+//! Once over, anyone may settle the auction: with a bid, the escrow pays the
+//! seller, the genome passes to the high bidder and an attestation of it is
+//! queued on the consensus chain (unless already consecrated); without one
+//! it closes unsold. The seller may cancel an auction nobody has bid on.
+
+use serde::{Serialize, Deserialize};
+
+use crate::consensus::ConsciousnessProof;
+use crate::exchange::Transaction;
+
+/// A bid must top the high bid by this fraction
+pub const MIN_RAISE: f64 = 0.05;
+/// Bids this close to the end extend the auction by as much
+pub const SNIPE_WINDOW_SECS: i64 = 300;
+pub const MIN_DURATION_SECS: i64 = 60;
+pub const MAX_DURATION_SECS: i64 = 7 * 24 * 3600;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuctionStatus {
+    Open,
+    Settled,
+    Unsold,
+    Cancelled,
+}
+
+impl AuctionStatus {
+    pub fn name(self) -> &'static str {
+        match self {
+            AuctionStatus::Open => "open",
+            AuctionStatus::Settled => "settled",
+            AuctionStatus::Unsold => "unsold",
+            AuctionStatus::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [AuctionStatus::Open, AuctionStatus::Settled, AuctionStatus::Unsold, AuctionStatus::Cancelled]
+            .into_iter()
+            .find(|status| status.name() == name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Auction {
+    pub id: i64,
+    pub genome_id: i64,
+    pub seller: String,
+    /// Opening bid; the genome does not sell for less
+    pub reserve_rsm: f64,
+    pub high_bid_rsm: Option<f64>,
+    pub high_bidder: Option<String>,
+    pub bids: u32,
+    pub status: AuctionStatus,
+    pub ends_at: i64,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// Bid that was topped, and is refunded from escrow
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Outbid {
+    pub bidder: String,
+    pub amount_rsm: f64,
+}
+
+/// An accepted bid and the escrow transfers it made (the bid, then the
+/// refund of the bid it topped)
+#[derive(Debug, Clone, Serialize)]
+pub struct BidReceipt {
+    pub auction: Auction,
+    pub transactions: Vec<Transaction>,
+}
+
+/// A closed auction: the payment to the seller and the queued attestation
+/// when it sold
+#[derive(Debug, Clone, Serialize)]
+pub struct Settlement {
+    pub auction: Auction,
+    pub transaction: Option<Transaction>,
+    pub attestation: Option<ConsciousnessProof>,
+}
+
+/// Wallet holding the RSM bid on auction `id`
+pub fn escrow_address(id: i64) -> String {
+    format!("auction_escrow_{}", id)
+}
+
+pub fn check_duration(duration_secs: i64) -> Result<(), String> {
+    if !(MIN_DURATION_SECS..=MAX_DURATION_SECS).contains(&duration_secs) {
+        return Err(format!(
+            "Auctions last {}–{} seconds, not {}", MIN_DURATION_SECS, MAX_DURATION_SECS, duration_secs
+        ));
+    }
+    Ok(())
+}
+
+impl Auction {
+    pub fn is_over(&self, now: i64) -> bool {
+        now >= self.ends_at
+    }
+
+    /// Lowest bid accepted next
+    pub fn min_bid(&self) -> f64 {
+        self.high_bid_rsm.map_or(self.reserve_rsm, |high| high * (1.0 + MIN_RAISE))
+    }
+
+    pub fn check_bid(&self, bidder: &str, amount_rsm: f64, now: i64) -> Result<(), String> {
+        if self.status != AuctionStatus::Open {
+            return Err(format!("Auction #{} is {}", self.id, self.status.name()));
+        }
+        if self.is_over(now) {
+            return Err(format!("Auction #{} has ended", self.id));
+        }
+        if bidder == self.seller {
+            return Err(format!("{} cannot bid on its own auction #{}", bidder, self.id));
+        }
+        if self.high_bidder.as_deref() == Some(bidder) {
+            return Err(format!("{} already holds the high bid on auction #{}", bidder, self.id));
+        }
+        if !amount_rsm.is_finite() || amount_rsm < self.min_bid() {
+            return Err(format!("Bid {} RSM below the minimum {} RSM", amount_rsm, self.min_bid()));
+        }
+        Ok(())
+    }
+
+    /// Take a bid made at `now`, extending the auction when it lands in the
+    /// snipe window; returns the bid it tops
+    pub fn bid(&mut self, bidder: &str, amount_rsm: f64, now: i64) -> Result<Option<Outbid>, String> {
+        self.check_bid(bidder, amount_rsm, now)?;
+        let outbid = self.high_bidder.replace(bidder.to_string())
+            .zip(self.high_bid_rsm.replace(amount_rsm))
+            .map(|(bidder, amount_rsm)| Outbid { bidder, amount_rsm });
+        self.bids += 1;
+        self.ends_at = self.ends_at.max(now + SNIPE_WINDOW_SECS);
+        self.updated_at = now;
+        Ok(outbid)
+    }
+
+    /// Close an auction that is over: settled with a bid, unsold without
+    pub fn close(&mut self, now: i64) -> Result<(), String> {
+        if self.status != AuctionStatus::Open {
+            return Err(format!("Auction #{} is {}", self.id, self.status.name()));
+        }
+        if !self.is_over(now) {
+            return Err(format!("Auction #{} runs until {}", self.id, self.ends_at));
+        }
+        self.status = if self.high_bidder.is_some() { AuctionStatus::Settled } else { AuctionStatus::Unsold };
+        self.updated_at = now;
+        Ok(())
+    }
+}
@@ -4,15 +4,28 @@
 //! - Password hashing (SHA-256 + salt)
 //! - JWT-like session tokens
 //! - Wallet registration/login
+//! - API keys (stored hashed) exchanged for short-lived HS256 JWTs
+//! - Roles on API keys: reader < operator < admin
 
 use sha2::{Sha256, Digest};
+use hmac::{Hmac, Mac};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
 use rand::Rng;
 use serde::{Serialize, Deserialize};
 use std::collections::HashMap;
 use chrono::Utc;
+use tracing::warn;
 
 const TOKEN_VALIDITY_HOURS: i64 = 24 * 7; // 7 days
 
+/// Lifetime of API JWTs issued by `POST /auth/token`
+pub const JWT_TTL_SECS: i64 = 15 * 60;
+
+const API_KEY_PREFIX: &str = "dak_";
+const JWT_HEADER: &str = r#"{"alg":"HS256","typ":"JWT"}"#;
+
+type HmacSha256 = Hmac<Sha256>;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WalletAccount {
     pub id: i64,
@@ -42,17 +55,189 @@ impl SessionToken {
     }
 }
 
+/// What an API key may call; each role includes the ones below it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    /// Token-protected reads (node wallet, webhooks)
+    Reader,
+    /// Every write outside `/api/admin/` (genomes, wallet transfers, orders)
+    #[default]
+    Operator,
+    /// `/api/admin/` too (consensus, validators, API key roles)
+    Admin,
+}
+
+impl Role {
+    pub const ALL: [Role; 3] = [Role::Reader, Role::Operator, Role::Admin];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Role::Reader => "reader",
+            Role::Operator => "operator",
+            Role::Admin => "admin",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        Self::ALL.into_iter().find(|role| role.name() == name)
+            .ok_or_else(|| format!("Unknown role {} (expected reader, operator or admin)", name))
+    }
+}
+
+/// API key row; the key itself is only known to its holder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKeyRecord {
+    pub id: i64,
+    pub name: String,
+    pub created_at: i64,
+    pub revoked: bool,
+    #[serde(default)]
+    pub role: Role,
+}
+
+/// Admin action recorded with the API key that made it (`admin_audit` table)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdminAuditEntry {
+    pub id: i64,
+    /// Name of the API key
+    pub caller: String,
+    pub key_id: i64,
+    pub action: String,
+    /// Request parameters (JSON)
+    pub detail: String,
+    pub created_at: i64,
+}
+
+impl AdminAuditEntry {
+    pub fn new(claims: &JwtClaims, action: &str, detail: serde_json::Value) -> Self {
+        Self {
+            id: 0,
+            caller: claims.sub.clone(),
+            key_id: claims.key_id,
+            action: action.to_string(),
+            detail: detail.to_string(),
+            created_at: Utc::now().timestamp(),
+        }
+    }
+}
+
+/// Claims of an API JWT
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JwtClaims {
+    /// Name of the API key the token was issued for
+    pub sub: String,
+    pub key_id: i64,
+    pub iat: i64,
+    pub exp: i64,
+    /// Role of the key when the token was issued; `refresh` replaces it
+    /// with the key's current one
+    #[serde(default)]
+    pub role: Role,
+}
+
+impl JwtClaims {
+    /// Whether the token's role includes `role`
+    pub fn has_role(&self, role: Role) -> bool {
+        self.role >= role
+    }
+
+    /// The claims with the key's current role, so revocations and role
+    /// changes apply to tokens already issued; `key` is the unrevoked
+    /// record for `key_id`, if there is one
+    pub fn refresh(mut self, key: Option<&ApiKeyRecord>) -> Result<Self, String> {
+        match key {
+            Some(key) if key.id == self.key_id && !key.revoked => {
+                self.role = key.role;
+                Ok(self)
+            }
+            _ => Err(format!("API key {} is revoked", self.sub)),
+        }
+    }
+}
+
 pub struct AuthManager {
     sessions: HashMap<String, SessionToken>,
+    jwt_secret: Vec<u8>,
 }
 
 impl AuthManager {
+    /// Manager with a random JWT secret (tokens die with the process)
     pub fn new() -> Self {
+        Self::with_jwt_secret(&rand::random::<[u8; 32]>())
+    }
+
+    pub fn with_jwt_secret(secret: &[u8]) -> Self {
         Self {
             sessions: HashMap::new(),
+            jwt_secret: secret.to_vec(),
         }
     }
 
+    /// Manager with this one's JWT secret and no sessions, for verifying
+    /// tokens without holding the shared lock (gRPC interceptor)
+    pub fn verifier(&self) -> Self {
+        Self::with_jwt_secret(&self.jwt_secret)
+    }
+
+    /// JWT secret from `JWT_SECRET`; random when unset
+    pub fn from_env() -> Self {
+        match std::env::var("JWT_SECRET") {
+            Ok(secret) if !secret.is_empty() => Self::with_jwt_secret(secret.as_bytes()),
+            _ => {
+                warn!("🔑 JWT_SECRET not set; API tokens are invalidated on restart");
+                Self::new()
+            }
+        }
+    }
+
+    /// New random API key; store only its `hash_api_key`
+    pub fn generate_api_key() -> String {
+        format!("{}{}", API_KEY_PREFIX, hex::encode(rand::random::<[u8; 32]>()))
+    }
+
+    pub fn hash_api_key(key: &str) -> String {
+        hex::encode(Sha256::digest(key.as_bytes()))
+    }
+
+    /// Signed JWT for API key `key`, valid for `JWT_TTL_SECS`
+    pub fn issue_jwt(&self, key: &ApiKeyRecord) -> (String, JwtClaims) {
+        let now = Utc::now().timestamp();
+        let claims = JwtClaims { sub: key.name.clone(), key_id: key.id, iat: now, exp: now + JWT_TTL_SECS, role: key.role };
+        let payload = serde_json::to_vec(&claims).expect("claims serialize");
+        let signing_input = format!("{}.{}", URL_SAFE_NO_PAD.encode(JWT_HEADER), URL_SAFE_NO_PAD.encode(payload));
+        let signature = URL_SAFE_NO_PAD.encode(self.jwt_mac(&signing_input).finalize().into_bytes());
+        (format!("{}.{}", signing_input, signature), claims)
+    }
+
+    /// Check a JWT's signature and expiry
+    pub fn verify_jwt(&self, token: &str) -> Result<JwtClaims, String> {
+        let mut parts = token.split('.');
+        let (Some(header), Some(payload), Some(signature), None) = (parts.next(), parts.next(), parts.next(), parts.next()) else {
+            return Err("Malformed token".into());
+        };
+        if URL_SAFE_NO_PAD.decode(header).ok().as_deref() != Some(JWT_HEADER.as_bytes()) {
+            return Err("Unsupported token header".into());
+        }
+        let signature = URL_SAFE_NO_PAD.decode(signature).map_err(|_| "Malformed token signature".to_string())?;
+        self.jwt_mac(&format!("{}.{}", header, payload))
+            .verify_slice(&signature)
+            .map_err(|_| "Invalid token signature".to_string())?;
+
+        let payload = URL_SAFE_NO_PAD.decode(payload).map_err(|_| "Malformed token payload".to_string())?;
+        let claims: JwtClaims = serde_json::from_slice(&payload).map_err(|e| format!("Malformed token claims: {}", e))?;
+        if claims.exp <= Utc::now().timestamp() {
+            return Err("Token expired".into());
+        }
+        Ok(claims)
+    }
+
+    fn jwt_mac(&self, signing_input: &str) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.jwt_secret).expect("HMAC accepts any key length");
+        mac.update(signing_input.as_bytes());
+        mac
+    }
+
     /// Generate salt for password hashing
     pub fn generate_salt() -> String {
         let salt: [u8; 16] = rand::thread_rng().gen();
@@ -127,14 +312,14 @@ impl Default for AuthManager {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct RegisterRequest {
     pub username: String,
     pub password: String,
     pub is_founder: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct LoginRequest {
     pub username: String,
     pub password: String,
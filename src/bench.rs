@@ -0,0 +1,454 @@
+//! Bench V1 — micro and macro benchmarks on the operator's hardware
+//!
+//! Each benchmark runs for the warmup period first, which also sizes a batch
+//! so one timed sample takes about `SAMPLE_TARGET`; then `samples` batches are
+//! timed and summarized per iteration (mean, median, p95, spread, ops/s).
+//!
+//! Suites:
+//! - `rotation`: cube rotations and a full rotation-engine cycle
+//! - `hashing`: DNA SHA-256, genome rehash, hyper signature, block header hash
+//! - `ttrl`: one evolution generation, meiosis, a 100-generation run
+//! - `db`: ping, insert + delete, load and top-20 queries (leaves no rows)
+//! - `order-book`: resting, matching and cancelling orders on a deep book,
+//!   and `order_book_load`, a seeded mix of limit, market and cancel
+//!   operations replayed as fast as the book takes them
+//!
+//! Inputs come from fixed seeds, so runs on other versions or machines
+//! measure the same work. A saved `--output json` report can be passed back
+//! as `--baseline` to see what changed.
+
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use clap::ValueEnum;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::{Serialize, Deserialize};
+
+use crate::consensus::ProofOfConsciousness;
+use crate::database::DivineDatabase;
+use crate::genome::{hash_genome_dna, Genome, GenomeBuilder};
+use crate::order_book::{OrderBook, Side};
+use crate::rotation::{Rot180, RotationEngine};
+use crate::ttrl::TTRLEngine;
+
+/// Time one sample aims for; fast benchmarks repeat to fill it
+pub const SAMPLE_TARGET: Duration = Duration::from_millis(10);
+pub const DEFAULT_SAMPLES: usize = 30;
+pub const DEFAULT_WARMUP_MS: u64 = 300;
+const SEED: u64 = 7;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BenchSuite {
+    Rotation,
+    Hashing,
+    Ttrl,
+    Db,
+    #[serde(rename = "order-book")]
+    OrderBook,
+}
+
+impl BenchSuite {
+    pub const ALL: [BenchSuite; 5] = [Self::Rotation, Self::Hashing, Self::Ttrl, Self::Db, Self::OrderBook];
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchConfig {
+    pub warmup: Duration,
+    pub samples: usize,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self { warmup: Duration::from_millis(DEFAULT_WARMUP_MS), samples: DEFAULT_SAMPLES }
+    }
+}
+
+impl BenchConfig {
+    pub fn new(warmup_ms: u64, samples: usize) -> Result<Self, String> {
+        if samples < 2 {
+            return Err("--samples must be at least 2".into());
+        }
+        Ok(Self { warmup: Duration::from_millis(warmup_ms), samples })
+    }
+
+    /// Iterations per sample, from `iterations` taking `elapsed` in warmup
+    pub fn batch_size(iterations: u64, elapsed: Duration) -> u64 {
+        let per_iteration = elapsed.as_secs_f64() / iterations.max(1) as f64;
+        if per_iteration <= 0.0 {
+            return 1;
+        }
+        ((SAMPLE_TARGET.as_secs_f64() / per_iteration).round() as u64).max(1)
+    }
+}
+
+/// Per-iteration summary of one benchmark, in nanoseconds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchStats {
+    pub name: String,
+    pub samples: usize,
+    pub iterations: u64,
+    pub mean_ns: f64,
+    pub median_ns: f64,
+    pub p95_ns: f64,
+    pub min_ns: f64,
+    pub max_ns: f64,
+    pub stddev_ns: f64,
+    pub ops_per_sec: f64,
+}
+
+impl BenchStats {
+    /// Summary of per-iteration `samples` (ns), each from `batch` iterations
+    pub fn from_samples(name: &str, samples: &[f64], batch: u64) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let n = sorted.len();
+        let mean = sorted.iter().sum::<f64>() / n.max(1) as f64;
+        let median = match n {
+            0 => 0.0,
+            _ if n.is_multiple_of(2) => (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0,
+            _ => sorted[n / 2],
+        };
+        // Nearest rank
+        let p95 = sorted.get(((n as f64 * 0.95).ceil() as usize).saturating_sub(1)).copied().unwrap_or(0.0);
+        let variance = if n > 1 { sorted.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1) as f64 } else { 0.0 };
+        Self {
+            name: name.to_string(),
+            samples: n,
+            iterations: n as u64 * batch,
+            mean_ns: mean,
+            median_ns: median,
+            p95_ns: p95,
+            min_ns: sorted.first().copied().unwrap_or(0.0),
+            max_ns: sorted.last().copied().unwrap_or(0.0),
+            stddev_ns: variance.sqrt(),
+            ops_per_sec: if mean > 0.0 { 1e9 / mean } else { 0.0 },
+        }
+    }
+}
+
+/// Time `f` after warming it up
+pub fn measure<F: FnMut()>(config: &BenchConfig, name: &str, mut f: F) -> BenchStats {
+    let start = Instant::now();
+    let mut warmup = 0;
+    while warmup == 0 || start.elapsed() < config.warmup {
+        f();
+        warmup += 1;
+    }
+    let batch = BenchConfig::batch_size(warmup, start.elapsed());
+
+    let samples: Vec<f64> = (0..config.samples).map(|_| {
+        let start = Instant::now();
+        for _ in 0..batch {
+            f();
+        }
+        start.elapsed().as_nanos() as f64 / batch as f64
+    }).collect();
+    BenchStats::from_samples(name, &samples, batch)
+}
+
+/// `measure` for async work such as queries
+pub async fn measure_async<F, Fut>(config: &BenchConfig, name: &str, mut f: F) -> anyhow::Result<BenchStats>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = anyhow::Result<()>>,
+{
+    let start = Instant::now();
+    let mut warmup = 0;
+    while warmup == 0 || start.elapsed() < config.warmup {
+        f().await?;
+        warmup += 1;
+    }
+    let batch = BenchConfig::batch_size(warmup, start.elapsed());
+
+    let mut samples = Vec::with_capacity(config.samples);
+    for _ in 0..config.samples {
+        let start = Instant::now();
+        for _ in 0..batch {
+            f().await?;
+        }
+        samples.push(start.elapsed().as_nanos() as f64 / batch as f64);
+    }
+    Ok(BenchStats::from_samples(name, &samples, batch))
+}
+
+fn seeded_genome() -> Genome<Rot180> {
+    GenomeBuilder::seeded(SEED).build_storage()
+}
+
+pub fn rotation_benches(config: &BenchConfig) -> Vec<BenchStats> {
+    let genome = seeded_genome();
+    let mut engine = RotationEngine::new();
+    vec![
+        measure(config, "rotation.rotate_90", || { std::hint::black_box(genome.rotated(90).ok()); }),
+        measure(config, "rotation.rotate_270", || { std::hint::black_box(genome.rotated(270).ok()); }),
+        measure(config, "rotation.engine_cycle", || {
+            for _ in 0..4 {
+                std::hint::black_box(engine.rotate());
+            }
+        }),
+    ]
+}
+
+pub fn hashing_benches(config: &BenchConfig) -> Vec<BenchStats> {
+    let genome = seeded_genome();
+    let dna = genome.to_dna_string();
+    let block = ProofOfConsciousness::new().latest_block().clone();
+    vec![
+        measure(config, "hashing.dna_sha256", || { std::hint::black_box(hash_genome_dna(std::hint::black_box(&dna))); }),
+        measure(config, "hashing.genome_rehash", || {
+            let mut copy = genome.clone();
+            copy.rehash();
+            std::hint::black_box(copy.hash);
+        }),
+        measure(config, "hashing.hyper_signature", || { std::hint::black_box(genome.hyper_signature()); }),
+        measure(config, "hashing.block_header", || { std::hint::black_box(block.calculate_hash()); }),
+    ]
+}
+
+pub fn ttrl_benches(config: &BenchConfig) -> Vec<BenchStats> {
+    let engine = TTRLEngine::new();
+    let base = seeded_genome();
+    let partner = GenomeBuilder::seeded(SEED + 1).build_storage();
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let generation = measure(config, "ttrl.generation", || {
+        std::hint::black_box(engine.evolve_with_rng(base.clone(), &mut rng).ok());
+    });
+    let meiosis = measure(config, "ttrl.meiosis", || {
+        std::hint::black_box(engine.meiosis(base.clone(), partner.clone()));
+    });
+    // Keep the best genome, starting over from the base once it is spent
+    let run = measure(config, "ttrl.run_100", || {
+        let mut rng = StdRng::seed_from_u64(SEED);
+        let mut best = base.clone();
+        for _ in 0..100 {
+            match engine.evolve_with_rng(best.clone(), &mut rng) {
+                Ok((next, _)) if next.consciousness >= best.consciousness => best = next,
+                Ok(_) => {}
+                Err(_) => best = base.clone(),
+            }
+        }
+        std::hint::black_box(best);
+    });
+    vec![generation, meiosis, run]
+}
+
+/// Round trips against `database`; the genomes it writes are deleted again
+pub async fn db_benches(config: &BenchConfig, database: &DivineDatabase) -> anyhow::Result<Vec<BenchStats>> {
+    let genome = seeded_genome();
+    let id = database.store_genome(&genome).await?;
+    let results = async {
+        Ok::<_, anyhow::Error>(vec![
+            measure_async(config, "db.ping", || database.ping()).await?,
+            measure_async(config, "db.insert_delete", || async {
+                let id = database.store_genome(&genome).await?;
+                database.delete_genome(id).await?;
+                Ok(())
+            }).await?,
+            measure_async(config, "db.load_genome", || async { database.load_genome(id).await.map(drop) }).await?,
+            measure_async(config, "db.top_20", || async { database.get_top_genomes(20).await.map(drop) }).await?,
+        ])
+    }.await;
+    database.delete_genome(id).await?;
+    results
+}
+
+/// Resting orders per side of the book the order book benches run on
+pub const BOOK_DEPTH_ORDERS: usize = 10_000;
+/// Mid price of seeded books, in ticks
+pub const MID_TICKS: u64 = 10_000;
+const LOAD_WALLETS: usize = 64;
+
+/// An order book operation of a load run
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BookOp {
+    Limit { wallet: usize, side: Side, ticks: u64, amount_rsm: f64 },
+    Market { wallet: usize, side: Side, amount_rsm: f64 },
+    /// Cancel the `n`th open order (modulo how many there are)
+    Cancel { n: usize },
+}
+
+/// Seeded operations: 60% limit orders within 50 ticks of the mid price,
+/// 15% market orders, 25% cancels
+pub fn book_ops(count: usize, seed: u64) -> Vec<BookOp> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..count).map(|_| {
+        let roll: f64 = rng.gen();
+        let wallet = rng.gen_range(0..LOAD_WALLETS);
+        let side = if rng.gen_bool(0.5) { Side::Buy } else { Side::Sell };
+        let amount_rsm = rng.gen_range(1..=1000) as f64 / 100.0;
+        match roll {
+            r if r < 0.60 => {
+                // Buys below the mid and sells above it mostly rest; the rest cross
+                let offset = rng.gen_range(0..50);
+                let ticks = match (side, rng.gen_bool(0.8)) {
+                    (Side::Buy, true) | (Side::Sell, false) => MID_TICKS - offset,
+                    _ => MID_TICKS + offset,
+                };
+                BookOp::Limit { wallet, side, ticks, amount_rsm }
+            }
+            r if r < 0.75 => BookOp::Market { wallet, side, amount_rsm },
+            _ => BookOp::Cancel { n: rng.gen() },
+        }
+    }).collect()
+}
+
+/// Outcome of replaying `book_ops` on an order book
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoadReport {
+    pub operations: usize,
+    pub trades: usize,
+    /// Orders left resting at the end
+    pub resting: usize,
+    pub elapsed_ms: f64,
+    pub ops_per_sec: f64,
+}
+
+/// Replay `ops` on `book` as fast as it takes them. Rejected operations
+/// (a market order on an empty side, a cancel of a filled order) count too:
+/// the book still had to look
+pub fn order_book_load(book: &mut OrderBook, ops: &[BookOp]) -> LoadReport {
+    let wallets: Vec<String> = (0..LOAD_WALLETS).map(|i| format!("divine_load_{}", i)).collect();
+    let mut open: Vec<u64> = Vec::new();
+    let mut trades = 0;
+    let start = Instant::now();
+    for (now, op) in ops.iter().enumerate() {
+        let now = now as i64;
+        let placed = match *op {
+            BookOp::Limit { wallet, side, ticks, amount_rsm } => {
+                book.place(&wallets[wallet], side, ticks as f64 * crate::order_book::PRICE_TICK_USD, amount_rsm, now).ok()
+            }
+            BookOp::Market { wallet, side, amount_rsm } => book.place_market(&wallets[wallet], side, amount_rsm, now).ok(),
+            BookOp::Cancel { n } => {
+                if !open.is_empty() {
+                    let id = open.swap_remove(n % open.len());
+                    std::hint::black_box(book.cancel(id, now).ok());
+                }
+                None
+            }
+        };
+        if let Some((order, fills)) = placed {
+            trades += fills.len();
+            if order.is_open() {
+                open.push(order.id);
+            }
+        }
+    }
+    let elapsed = start.elapsed().as_secs_f64();
+    let depth = book.depth(usize::MAX);
+    LoadReport {
+        operations: ops.len(),
+        trades,
+        resting: depth.bids.iter().chain(&depth.asks).map(|level| level.orders).sum(),
+        elapsed_ms: elapsed * 1e3,
+        ops_per_sec: if elapsed > 0.0 { ops.len() as f64 / elapsed } else { 0.0 },
+    }
+}
+
+/// A book with `BOOK_DEPTH_ORDERS` resting orders a side, spread over 500
+/// ticks below and above the mid price
+pub fn seeded_book() -> OrderBook {
+    let mut book = OrderBook::with_capacity(2 * BOOK_DEPTH_ORDERS);
+    for i in 0..BOOK_DEPTH_ORDERS as u64 {
+        let offset = 1 + i % 500;
+        book.place("divine_bench_bid", Side::Buy, (MID_TICKS - offset) as f64 * crate::order_book::PRICE_TICK_USD, 1.0, 0).unwrap();
+        book.place("divine_bench_ask", Side::Sell, (MID_TICKS + offset) as f64 * crate::order_book::PRICE_TICK_USD, 1.0, 0).unwrap();
+    }
+    book
+}
+
+pub fn order_book_benches(config: &BenchConfig) -> Vec<BenchStats> {
+    let mid = MID_TICKS as f64 * crate::order_book::PRICE_TICK_USD;
+    let mut book = seeded_book();
+    // Rest inside the spread and take it off again
+    let rest_cancel = measure(config, "order_book.rest_cancel", || {
+        let (order, _) = book.place("divine_bench_maker", Side::Buy, mid, 1.0, 1).unwrap();
+        std::hint::black_box(book.cancel(order.id, 1).ok());
+    });
+    // Fill the best ask and put it back
+    let best_ask = mid + crate::order_book::PRICE_TICK_USD;
+    let match_refill = measure(config, "order_book.match_refill", || {
+        std::hint::black_box(book.place("divine_bench_taker", Side::Buy, best_ask, 1.0, 1).unwrap());
+        book.place("divine_bench_ask", Side::Sell, best_ask, 1.0, 1).unwrap();
+    });
+    // Cancel an order with another queued behind it, resting a new one
+    let level = mid - 5.0 * crate::order_book::PRICE_TICK_USD;
+    let mut queued = book.place("divine_bench_bid", Side::Buy, level, 1.0, 1).unwrap().0.id;
+    let cancel_queued = measure(config, "order_book.cancel_queued", || {
+        let next = book.place("divine_bench_bid", Side::Buy, level, 1.0, 1).unwrap().0.id;
+        std::hint::black_box(book.cancel(queued, 1).ok());
+        queued = next;
+    });
+    // The seeded mix, each run on a fresh copy of the seeded book
+    let (base, ops) = (seeded_book(), book_ops(10_000, SEED));
+    let load = measure(config, "order_book.load_10k", || {
+        let mut book = base.clone();
+        std::hint::black_box(order_book_load(&mut book, &ops));
+    });
+    vec![rest_cancel, match_refill, cancel_queued, load]
+}
+
+/// `bench` results, saved with `--output json` to compare later runs against
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchReport {
+    pub version: String,
+    pub taken_at: i64,
+    pub samples: usize,
+    pub warmup_ms: u64,
+    pub results: Vec<BenchStats>,
+    /// Against `--baseline`, when given
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<BenchChange>,
+}
+
+/// Mean time of a benchmark against the same one in a baseline report
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchChange {
+    pub name: String,
+    pub baseline_ns: f64,
+    pub mean_ns: f64,
+    /// Positive is slower
+    pub change_pct: f64,
+    /// Beyond two standard errors of the difference, not noise
+    pub significant: bool,
+}
+
+impl BenchReport {
+    pub fn new(config: &BenchConfig, results: Vec<BenchStats>) -> Self {
+        Self {
+            version: crate::VERSION.to_string(),
+            taken_at: chrono::Utc::now().timestamp(),
+            samples: config.samples,
+            warmup_ms: config.warmup.as_millis() as u64,
+            results,
+            changes: Vec::new(),
+        }
+    }
+
+    /// Changes of the benchmarks `baseline` also ran
+    pub fn compare(&self, baseline: &BenchReport) -> Vec<BenchChange> {
+        self.results.iter().filter_map(|current| {
+            let before = baseline.results.iter().find(|b| b.name == current.name)?;
+            let diff = current.mean_ns - before.mean_ns;
+            let stderr = (current.stddev_ns.powi(2) / current.samples.max(1) as f64
+                + before.stddev_ns.powi(2) / before.samples.max(1) as f64).sqrt();
+            Some(BenchChange {
+                name: current.name.clone(),
+                baseline_ns: before.mean_ns,
+                mean_ns: current.mean_ns,
+                change_pct: if before.mean_ns > 0.0 { diff / before.mean_ns * 100.0 } else { 0.0 },
+                significant: diff.abs() > 2.0 * stderr,
+            })
+        }).collect()
+    }
+}
+
+/// `ns` with a unit that keeps it readable
+pub fn format_duration(ns: f64) -> String {
+    match ns {
+        n if n >= 1e9 => format!("{:.2} s", n / 1e9),
+        n if n >= 1e6 => format!("{:.2} ms", n / 1e6),
+        n if n >= 1e3 => format!("{:.2} µs", n / 1e3),
+        n => format!("{:.0} ns", n),
+    }
+}
@@ -0,0 +1,225 @@
+//! Chain File Format V7 — portable binary export of the PoC chain
+//!
+//! Layout (all integers little-endian):
+//! - magic `DIVCHAIN` (8 bytes), format version (u16)
+//! - consensus parameters needed to replay the chain
+//! - genesis config as JSON (added in V2)
+//! - validator public keys, then blocks with coinbase (V3), stake ops as
+//!   JSON (V4) and attestations with their fees (V5); each block starts
+//!   with its index and protocol version (V6) and carries its attested
+//!   consciousness (V7)
+//! - SHA-256 checksum of everything above (32 bytes)
+//!
+//! Strings are u32-length-prefixed UTF-8. Decoding only checks framing;
+//! consensus validation happens in `ProofOfConsciousness::import`.
+
+use serde::{Serialize, Deserialize};
+use sha2::{Sha256, Digest};
+
+use crate::consensus::{Coinbase, ConsensusBlock, ConsciousnessProof, GenomeAttestation};
+use crate::genesis::GenesisConfig;
+
+pub const CHAIN_FILE_MAGIC: &[u8; 8] = b"DIVCHAIN";
+pub const CHAIN_FILE_VERSION: u16 = 7;
+
+/// Replay parameters stored alongside the blocks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChainParams {
+    pub difficulty_growth_rate: u32,
+    pub target_block_time_secs: i64,
+    pub retarget_interval: u64,
+    pub max_block_entries: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainFile {
+    pub version: u16,
+    pub params: ChainParams,
+    pub genesis: GenesisConfig,
+    pub validators: Vec<String>,
+    pub blocks: Vec<ConsensusBlock>,
+}
+
+impl ChainFile {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(CHAIN_FILE_MAGIC);
+        out.extend_from_slice(&self.version.to_le_bytes());
+
+        out.extend_from_slice(&self.params.difficulty_growth_rate.to_le_bytes());
+        out.extend_from_slice(&self.params.target_block_time_secs.to_le_bytes());
+        out.extend_from_slice(&self.params.retarget_interval.to_le_bytes());
+        out.extend_from_slice(&self.params.max_block_entries.to_le_bytes());
+        put_str(&mut out, &serde_json::to_string(&self.genesis).expect("genesis config serializes"));
+
+        out.extend_from_slice(&(self.validators.len() as u32).to_le_bytes());
+        for key in &self.validators {
+            put_str(&mut out, key);
+        }
+
+        out.extend_from_slice(&(self.blocks.len() as u64).to_le_bytes());
+        for block in &self.blocks {
+            put_block(&mut out, block);
+        }
+
+        let checksum: [u8; 32] = Sha256::digest(&out).into();
+        out.extend_from_slice(&checksum);
+        out
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, String> {
+        if bytes.len() < CHAIN_FILE_MAGIC.len() + 2 + 32 {
+            return Err("Chain file truncated".to_string());
+        }
+        let (body, checksum) = bytes.split_at(bytes.len() - 32);
+        if Sha256::digest(body).as_slice() != checksum {
+            return Err("Chain file checksum mismatch".to_string());
+        }
+
+        let mut r = Reader { bytes: body, pos: 0 };
+        if r.take(8)? != CHAIN_FILE_MAGIC {
+            return Err("Not a Divine chain file".to_string());
+        }
+        let version = r.u16()?;
+        if version != CHAIN_FILE_VERSION {
+            return Err(format!("Unsupported chain file version {} (expected {})", version, CHAIN_FILE_VERSION));
+        }
+
+        let params = ChainParams {
+            difficulty_growth_rate: r.u32()?,
+            target_block_time_secs: r.i64()?,
+            retarget_interval: r.u64()?,
+            max_block_entries: r.u32()?,
+        };
+        let genesis: GenesisConfig = serde_json::from_str(&r.string()?)
+            .map_err(|e| format!("Invalid genesis config: {}", e))?;
+
+        let validators = (0..r.u32()?).map(|_| r.string()).collect::<Result<Vec<_>, _>>()?;
+
+        let count = r.u64()?;
+        let mut blocks = Vec::new();
+        for _ in 0..count {
+            blocks.push(r.block()?);
+        }
+
+        if r.pos != body.len() {
+            return Err("Trailing bytes in chain file".to_string());
+        }
+        Ok(Self { version, params, genesis, validators, blocks })
+    }
+}
+
+fn put_str(out: &mut Vec<u8>, s: &str) {
+    out.extend_from_slice(&(s.len() as u32).to_le_bytes());
+    out.extend_from_slice(s.as_bytes());
+}
+
+fn put_block(out: &mut Vec<u8>, block: &ConsensusBlock) {
+    out.extend_from_slice(&block.index.to_le_bytes());
+    out.extend_from_slice(&block.version.to_le_bytes());
+    out.extend_from_slice(&block.attested_consciousness.to_le_bytes());
+    out.extend_from_slice(&block.timestamp.to_le_bytes());
+    out.extend_from_slice(&block.nonce.to_le_bytes());
+    out.extend_from_slice(&block.difficulty.to_le_bytes());
+    put_str(out, &block.merkle_root);
+    put_str(out, &block.previous_hash);
+    put_str(out, &block.hash);
+    put_str(out, &block.producer);
+    put_str(out, &block.signature);
+    put_str(out, &block.coinbase.address);
+    out.extend_from_slice(&block.coinbase.amount_rsm.to_le_bytes());
+    put_str(out, &serde_json::to_string(&block.stake_ops).expect("stake ops serialize"));
+
+    out.extend_from_slice(&(block.entries.len() as u32).to_le_bytes());
+    for entry in &block.entries {
+        let proof = &entry.proof;
+        out.extend_from_slice(&entry.genome_id.to_le_bytes());
+        out.extend_from_slice(&proof.genome_hash);
+        out.extend_from_slice(&proof.consciousness.to_le_bytes());
+        put_str(out, &proof.hyper_signature);
+        out.extend_from_slice(&proof.proof_hash);
+        out.extend_from_slice(&proof.timestamp.to_le_bytes());
+        put_str(out, &proof.validator_id);
+        out.extend_from_slice(&proof.block_height.to_le_bytes());
+        out.extend_from_slice(&proof.reward_rsm.to_le_bytes());
+        out.extend_from_slice(&entry.fee_rsm.to_le_bytes());
+        put_str(out, &entry.payer);
+        put_str(out, &entry.payer_signature);
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        let end = self.pos.checked_add(n).filter(|&end| end <= self.bytes.len())
+            .ok_or_else(|| "Chain file truncated".to_string())?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        let mut buf = [0u8; N];
+        buf.copy_from_slice(self.take(N)?);
+        Ok(buf)
+    }
+
+    fn u16(&mut self) -> Result<u16, String> { Ok(u16::from_le_bytes(self.array()?)) }
+    fn u32(&mut self) -> Result<u32, String> { Ok(u32::from_le_bytes(self.array()?)) }
+    fn u64(&mut self) -> Result<u64, String> { Ok(u64::from_le_bytes(self.array()?)) }
+    fn i64(&mut self) -> Result<i64, String> { Ok(i64::from_le_bytes(self.array()?)) }
+    fn f64(&mut self) -> Result<f64, String> { Ok(f64::from_le_bytes(self.array()?)) }
+
+    fn string(&mut self) -> Result<String, String> {
+        let len = self.u32()? as usize;
+        String::from_utf8(self.take(len)?.to_vec()).map_err(|e| format!("Invalid UTF-8 in chain file: {}", e))
+    }
+
+    fn block(&mut self) -> Result<ConsensusBlock, String> {
+        let index = self.u64()?;
+        let version = self.u32()?;
+        let attested_consciousness = self.u64()?;
+        let timestamp = self.i64()?;
+        let nonce = self.u64()?;
+        let difficulty = self.u32()?;
+        let merkle_root = self.string()?;
+        let previous_hash = self.string()?;
+        let hash = self.string()?;
+        let producer = self.string()?;
+        let signature = self.string()?;
+        let coinbase = Coinbase { address: self.string()?, amount_rsm: self.f64()? };
+        let stake_ops = serde_json::from_str(&self.string()?)
+            .map_err(|e| format!("Invalid stake ops in block #{}: {}", index, e))?;
+
+        let mut entries = Vec::new();
+        for _ in 0..self.u32()? {
+            let genome_id = self.i64()?;
+            let proof = ConsciousnessProof {
+                genome_hash: self.array()?,
+                consciousness: self.u32()?,
+                hyper_signature: self.string()?,
+                proof_hash: self.array()?,
+                timestamp: self.i64()?,
+                validator_id: self.string()?,
+                block_height: self.u64()?,
+                reward_rsm: self.f64()?,
+            };
+            entries.push(GenomeAttestation {
+                genome_id,
+                proof,
+                fee_rsm: self.f64()?,
+                payer: self.string()?,
+                payer_signature: self.string()?,
+            });
+        }
+
+        Ok(ConsensusBlock {
+            index, version, timestamp, entries, merkle_root, previous_hash,
+            nonce, difficulty, hash, producer, signature, coinbase, stake_ops, attested_consciousness,
+        })
+    }
+}
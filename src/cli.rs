@@ -1,15 +1,62 @@
 //! CLI Module V15 for Divine AGI
 
-use clap::{Parser, Subcommand};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+
+use crate::api::CrisprEdit;
+use crate::bench::{BenchSuite, DEFAULT_SAMPLES, DEFAULT_WARMUP_MS};
+use crate::genome::{Genome, GenomeBuilder, GenomeDiff, GENOME_SIZE};
+use crate::keystore::DEFAULT_KEYSTORE_FILE;
+use crate::multi_chain::{BlockchainLayer, MAX_ARCHIVE_BATCH};
+use crate::rotation::Rot180;
+use crate::wallet::DEFAULT_MNEMONIC_WORDS;
+use crate::watch::{WatchFilter, WatchTopic};
 
 #[derive(Parser)]
 #[command(name = "divine-agi")]
 #[command(about = "Divine AGI V15 - Kernel v3 🧬⚡", long_about = None)]
 pub struct Cli {
+    /// Result format: `table` on terminals, `json` when piped
+    #[arg(long, global = true, value_enum)]
+    pub output: Option<OutputFormat>,
+    /// Print what a transfer, CRISPR edit, archive or db import would do
+    /// (amounts, rows, layers) without doing it
+    #[arg(long, global = true)]
+    pub dry_run: bool,
     #[command(subcommand)]
     pub command: Commands,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Table,
+    Json,
+    Yaml,
+}
+
+impl OutputFormat {
+    /// `flag`, else a table on terminals and JSON for scripts
+    pub fn resolve(flag: Option<Self>, terminal: bool) -> Self {
+        flag.unwrap_or(if terminal { Self::Table } else { Self::Json })
+    }
+
+    pub fn is_table(self) -> bool {
+        self == Self::Table
+    }
+
+    /// `value` as JSON or YAML; tables are drawn by each command
+    pub fn render<T: Serialize>(self, value: &T) -> Result<String, String> {
+        match self {
+            Self::Json => serde_json::to_string_pretty(value).map(|json| json + "\n").map_err(|e| e.to_string()),
+            Self::Yaml => serde_yaml::to_string(value).map_err(|e| e.to_string()),
+            Self::Table => Err("Tables are printed by the command".into()),
+        }
+    }
+}
+
 #[derive(Subcommand)]
 pub enum Commands {
     /// Start the API server
@@ -19,6 +66,35 @@ pub enum Commands {
         #[arg(long, default_value = "30")]
         rotation_interval: u64,
     },
+    /// Start the API server with every setting as a flag (or its variable)
+    Serve {
+        #[arg(long, env = "DIVINE_BIND", default_value = "0.0.0.0:8080")]
+        bind: SocketAddr,
+        /// Overrides `database_url`
+        #[arg(long)]
+        database_url: Option<String>,
+        /// Genesis config of the network to join; overrides `network`
+        #[arg(long)]
+        network: Option<PathBuf>,
+        /// Evolution jobs run at once; overrides `ttrl.workers`
+        #[arg(long)]
+        workers: Option<usize>,
+        #[arg(long, env = "TLS_CERT_PATH", requires = "tls_key")]
+        tls_cert: Option<PathBuf>,
+        #[arg(long, env = "TLS_KEY_PATH", requires = "tls_cert")]
+        tls_key: Option<PathBuf>,
+        /// CA of client certificates for mutual TLS
+        #[arg(long, env = "TLS_CLIENT_CA_PATH", requires = "tls_cert")]
+        tls_client_ca: Option<PathBuf>,
+        /// Also serve the gRPC API on this port (`grpc` feature builds)
+        #[arg(long, env = "GRPC_PORT")]
+        grpc_port: Option<u16>,
+        /// Mount the `/api/admin/` routes
+        #[arg(long, env = "DIVINE_ENABLE_ADMIN")]
+        enable_admin: bool,
+        #[arg(long, default_value = "30")]
+        rotation_interval: u64,
+    },
     /// Show system status
     Status,
     /// Create a new genome
@@ -26,10 +102,17 @@ pub enum Commands {
         #[arg(short, long, default_value = "elephant")]
         mode: String,
     },
-    /// Evolve a genome
+    /// Evolve a stored genome with TTRL, showing live progress; the best
+    /// genome is stored if it beats the original
     Evolve {
+        #[arg(short = 'i', long, alias = "id")]
+        genome_id: i64,
+        /// Generations to run (at most 10,000; default: `ttrl.budget`, then 100)
         #[arg(short, long)]
-        id: i64,
+        budget: Option<u32>,
+        /// Same seed and genome, same run
+        #[arg(long)]
+        seed: Option<u64>,
     },
     /// Meiosis (sexual reproduction)
     Meiosis {
@@ -43,16 +126,533 @@ pub enum Commands {
         #[arg(short, long)]
         id: i64,
     },
-    /// Archive genome to multi-chain
+    /// Archive a genome, or the genomes a filter selects, to multi-chain
+    #[command(group(clap::ArgGroup::new("target").required(true).args(["genome_id", "min_consciousness", "max_consciousness", "min_p53"]).multiple(true)))]
     Archive {
-        #[arg(short, long)]
-        id: i64,
+        #[arg(short = 'i', long, alias = "id", conflicts_with_all = ["min_consciousness", "max_consciousness", "min_p53"])]
+        genome_id: Option<i64>,
+        #[arg(long)]
+        min_consciousness: Option<u32>,
+        #[arg(long)]
+        max_consciousness: Option<u32>,
+        #[arg(long)]
+        min_p53: Option<u8>,
+        /// Genomes a filter may select at most
+        #[arg(short, long, default_value_t = MAX_ARCHIVE_BATCH)]
+        limit: usize,
+        /// Layer to use instead of the one the T/G ratio selects
+        #[arg(long, value_parser = BlockchainLayer::from_name)]
+        layer: Option<BlockchainLayer>,
     },
     /// Run rotation daemon
     Daemon {
         #[arg(short, long, default_value = "30")]
         interval: u64,
     },
+    /// Create an API key (printed once; only its hash is stored)
+    CreateApiKey {
+        #[arg(short, long)]
+        name: String,
+        /// `reader` (protected reads), `operator` (writes) or `admin` (also `/api/admin/`)
+        #[arg(short, long, default_value = "operator")]
+        role: String,
+    },
+    /// Revoke an API key
+    RevokeApiKey {
+        #[arg(short, long)]
+        id: i64,
+    },
+    /// Build, inspect and edit genomes
+    Genome {
+        #[command(subcommand)]
+        command: GenomeCommand,
+    },
+    /// Inspect and manage the stored Proof of Consciousness chain
+    Chain {
+        #[command(subcommand)]
+        command: ChainCommand,
+    },
+    /// Migrate, inspect, export and back up the configured database
+    Db {
+        #[command(subcommand)]
+        command: DbCommand,
+    },
+    /// Manage an encrypted wallet file
+    Wallet {
+        /// Wallet file (default: the configured `wallet_file`, then `divine.wallet`)
+        #[arg(short, long, global = true)]
+        file: Option<PathBuf>,
+        #[command(subcommand)]
+        command: WalletCommand,
+    },
+    /// Live terminal dashboard of the chain, TTRL jobs, database, wallet and archives
+    Dashboard {
+        /// Read a running server (alone: the configured `api_url`) instead of the database
+        #[arg(long, value_name = "URL")]
+        api: Option<Option<String>>,
+        /// Bearer token for `--api`; the node wallet needs a reader role
+        #[arg(long, requires = "api")]
+        token: Option<String>,
+        /// Wallet file shown without `--api` (default: the configured `wallet_file`, then `divine.wallet`)
+        #[arg(short, long, conflicts_with = "api")]
+        wallet: Option<PathBuf>,
+        /// Seconds between refreshes
+        #[arg(short, long, default_value = "2", value_parser = clap::value_parser!(u64).range(1..))]
+        interval: u64,
+    },
+    /// Print new blocks, genomes or archives as they are stored
+    Watch {
+        #[arg(value_enum, required = true)]
+        topics: Vec<WatchTopic>,
+        /// `<column><op><value>` (op: = != > >= < <= ~), e.g. `consciousness>=5000`; repeat to narrow
+        #[arg(short, long, value_parser = WatchFilter::parse)]
+        filter: Vec<WatchFilter>,
+    },
+    /// Generate a validator signing key, show its recovery phrase once and
+    /// write it to an encrypted keystore
+    Keygen {
+        #[arg(short, long, default_value_t = DEFAULT_MNEMONIC_WORDS)]
+        words: usize,
+        #[arg(short, long, default_value = DEFAULT_KEYSTORE_FILE)]
+        out: PathBuf,
+        /// Key index under the phrase; the next index rotates to a new key
+        #[arg(long, default_value = "0")]
+        index: u32,
+        /// Rebuild the keystore from an existing phrase (prompted)
+        #[arg(long, conflicts_with = "words")]
+        recover: bool,
+        /// Replace an existing keystore
+        #[arg(long)]
+        force: bool,
+        /// Skip retyping words of the phrase (scripts)
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Benchmark rotation, hashing, TTRL, the database and the order book on this machine
+    Bench {
+        /// Suites to run (default: all)
+        #[arg(value_enum)]
+        suites: Vec<BenchSuite>,
+        /// Timed samples per benchmark
+        #[arg(short, long, default_value_t = DEFAULT_SAMPLES)]
+        samples: usize,
+        /// Warmup per benchmark, in milliseconds
+        #[arg(long, default_value_t = DEFAULT_WARMUP_MS)]
+        warmup_ms: u64,
+        /// Earlier `bench --output json` report to compare with
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+    },
+    /// Run commands from a file, one per line, sharing one database
+    /// connection and wallet password
+    Script {
+        /// Script file, or `-` for stdin
+        file: PathBuf,
+        /// Run the remaining commands after one fails (still exits with failure)
+        #[arg(long)]
+        continue_on_error: bool,
+    },
+    /// Show or change `~/.divine/config.toml`
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+}
+
+impl Commands {
+    /// Commands that change state but have no `--dry-run` preview; given the
+    /// flag they stop instead of acting
+    pub fn lacks_dry_run(&self) -> bool {
+        match self {
+            Self::Server { .. } | Self::Serve { .. } | Self::Daemon { .. } | Self::Create { .. } | Self::Evolve { .. } | Self::Meiosis { .. }
+            | Self::Telomerase { .. } | Self::CreateApiKey { .. } | Self::RevokeApiKey { .. } | Self::Keygen { .. } => true,
+            Self::Genome { command } => matches!(command,
+                GenomeCommand::New { save: true, .. } | GenomeCommand::Mutate { save: true, .. } | GenomeCommand::Rotate { save: true, .. }),
+            Self::Chain { command } => matches!(command, ChainCommand::Mine { .. } | ChainCommand::Import { .. }),
+            Self::Wallet { command, .. } => matches!(command,
+                WalletCommand::Create { .. } | WalletCommand::Recover { .. } | WalletCommand::Addresses { new: true }),
+            Self::Config { command } => matches!(command, ConfigCommand::Set { .. }),
+            _ => false,
+        }
+    }
+}
+
+/// `genome` subcommands; `<GENOME>` is a stored genome id or a 27-base DNA
+/// string (which needs no database)
+#[derive(Subcommand)]
+pub enum GenomeCommand {
+    /// Build a genome from DNA, at random (the default) or from a seed
+    New {
+        #[arg(long, conflicts_with_all = ["random", "seed"])]
+        dna: Option<String>,
+        #[arg(long, conflicts_with = "seed")]
+        random: bool,
+        /// Same seed, same DNA
+        #[arg(long)]
+        seed: Option<u64>,
+        /// 40 p53 copies instead of 20
+        #[arg(long)]
+        whale: bool,
+        /// Store it in the database
+        #[arg(long)]
+        save: bool,
+    },
+    /// Show a genome with its 3×3×3 cube
+    Show {
+        genome: String,
+    },
+    /// Apply random point mutations
+    Mutate {
+        genome: String,
+        #[arg(short = 'n', long, default_value = "1")]
+        count: usize,
+        /// Same seed, same mutations
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Store the result as a new genome
+        #[arg(long)]
+        save: bool,
+    },
+    /// Turn the cube around its vertical axis
+    Rotate {
+        genome: String,
+        /// Angle in degrees: 0, 90, 180 or 270
+        #[arg(long, value_parser = ["0", "90", "180", "270"])]
+        to: String,
+        /// Store the result as a new genome
+        #[arg(long)]
+        save: bool,
+    },
+    /// Every metric of a genome
+    Metrics {
+        genome: String,
+    },
+    /// Compare two genomes base by base, with metric deltas and, for stored
+    /// genomes, the evolution jobs linking them
+    Diff {
+        a: String,
+        b: String,
+    },
+    /// CRISPR edit: a stored genome is changed in place, DNA is only shown
+    Crispr {
+        genome: String,
+        #[command(subcommand)]
+        edit: CrisprCommand,
+    },
+    /// Write genomes as FASTA (importable with `POST /api/genomes/import`) or JSON
+    Export {
+        #[arg(required = true)]
+        genomes: Vec<String>,
+        #[arg(short, long, value_enum, default_value = "fasta")]
+        format: GenomeExportFormat,
+        /// File to write; stdout when unset
+        #[arg(short, long)]
+        out: Option<PathBuf>,
+    },
+}
+
+/// `genome crispr` edits; positions are 0-26
+#[derive(Subcommand)]
+pub enum CrisprCommand {
+    /// Replace the base at a position
+    Splice { position: usize, base: char },
+    /// Swap two bases
+    Join { pos1: usize, pos2: usize },
+    /// Replace the base at a position with a random one
+    Delete { position: usize },
+}
+
+impl CrisprCommand {
+    pub fn edit(&self) -> CrisprEdit {
+        match *self {
+            Self::Splice { position, base } => CrisprEdit::Splice { position, new_base: base },
+            Self::Join { pos1, pos2 } => CrisprEdit::Join { pos1, pos2 },
+            Self::Delete { position } => CrisprEdit::Delete { position },
+        }
+    }
+}
+
+/// `chain` subcommands, on the chain in the configured database (genesis and
+/// keys from the same environment as the server)
+#[derive(Subcommand)]
+pub enum ChainCommand {
+    /// Re-check every block: hashes, PoW, difficulty schedule, signatures
+    Validate {
+        /// Check an exported chain file instead
+        #[arg(long)]
+        file: Option<PathBuf>,
+    },
+    /// Show a block (the tip by default)
+    Show {
+        #[arg(long)]
+        height: Option<u64>,
+    },
+    /// Attest a stored genome and mine it into a block (`VALIDATOR_SECRET_KEY`)
+    Mine {
+        #[arg(short = 'i', long)]
+        genome_id: i64,
+    },
+    /// Write the chain to a chain file
+    Export {
+        #[arg(short, long)]
+        out: PathBuf,
+        /// Write a snapshot instead (needed once the chain is pruned)
+        #[arg(long)]
+        snapshot: bool,
+    },
+    /// Validate a chain file and merge it into the stored chain by fork choice
+    Import {
+        file: PathBuf,
+    },
+    /// Height, threshold, difficulty, rewards and block-time health
+    Stats,
+}
+
+/// `db` subcommands; dumps are JSONL (see `db_dump`)
+#[derive(Subcommand)]
+pub enum DbCommand {
+    /// Create missing tables and columns
+    Migrate,
+    /// Rows and disk use per table
+    Stats,
+    /// Write the data tables (no keys, accounts or webhook secrets)
+    Export {
+        #[arg(short, long, value_enum, default_value = "jsonl")]
+        format: DbExportFormat,
+        /// Only these tables (repeatable)
+        #[arg(short, long)]
+        table: Vec<String>,
+        /// File to write; stdout when unset
+        #[arg(short, long)]
+        out: Option<PathBuf>,
+    },
+    /// Add the rows of an export or backup; rows whose key exists are skipped
+    Import {
+        file: PathBuf,
+    },
+    /// Write every table, credentials included
+    Backup {
+        #[arg(short, long)]
+        out: PathBuf,
+    },
+    /// Replace the tables in a backup with its contents, after confirmation
+    Restore {
+        file: PathBuf,
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DbExportFormat {
+    Jsonl,
+}
+
+/// `wallet` subcommands; the password comes from `WALLET_PASSWORD` or a prompt
+#[derive(Subcommand)]
+pub enum WalletCommand {
+    /// Create a wallet and print its recovery phrase (once)
+    Create {
+        #[arg(short, long, default_value_t = DEFAULT_MNEMONIC_WORDS)]
+        words: usize,
+        /// Replace an existing wallet file
+        #[arg(long)]
+        force: bool,
+    },
+    /// Rebuild a wallet from its recovery phrase (prompted when not given)
+    Recover {
+        #[arg(long)]
+        phrase: Option<String>,
+        #[arg(long)]
+        force: bool,
+        /// Find accounts, balances and history again on the stored chain
+        #[arg(long)]
+        scan: bool,
+    },
+    /// Create a watch-only wallet: addresses to read balances of, no keys
+    Watch {
+        /// divine_ addresses, at most one Solana and one Ethereum (0x) address
+        #[arg(required = true)]
+        addresses: Vec<String>,
+        #[arg(long)]
+        force: bool,
+    },
+    /// Spendable, staked and earned RSM
+    Balance {
+        /// Also read what the wallet's key holds on its network
+        #[arg(long)]
+        chain: bool,
+    },
+    /// Show or set the network the wallet's on-chain balance and sends use
+    Network {
+        /// mock, solana-devnet, solana-mainnet, ethereum-sepolia or ethereum-mainnet
+        name: Option<String>,
+        /// RPC endpoint instead of the network's public one (empty: public)
+        #[arg(long)]
+        rpc_url: Option<String>,
+        /// RSM's token on the network (an SPL mint, or an ERC-20 contract)
+        #[arg(long)]
+        rsm_token: Option<String>,
+    },
+    /// Fund the wallet's address with test SOL (devnet faucet, or the mock ledger)
+    Airdrop {
+        #[arg(short, long, default_value = "1")]
+        amount: f64,
+    },
+    /// Tokens followed besides RSM on the wallet's network
+    Tokens {
+        /// Follow this mint first
+        #[arg(long)]
+        add: Option<String>,
+    },
+    /// Send RSM on the wallet's network, signed with the wallet's key
+    Send {
+        #[arg(long)]
+        to: String,
+        #[arg(short, long)]
+        amount: f64,
+        /// Send this followed token (a mint or contract) instead of RSM
+        #[arg(long, conflicts_with = "native")]
+        token: Option<String>,
+        /// Send the network's coin (SOL or ETH) instead of RSM
+        #[arg(long)]
+        native: bool,
+        /// Write the unsigned transaction to this file instead of sending it,
+        /// to sign offline (`wallet sign`); works on watch-only wallets
+        #[arg(long)]
+        build: Option<PathBuf>,
+        /// Skip the confirmation (scripts)
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Sign an unsigned transaction file offline (`send --build`)
+    Sign {
+        file: PathBuf,
+        /// Where the signed transaction goes
+        #[arg(short, long)]
+        out: PathBuf,
+        /// Skip the confirmation (scripts)
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Send a signed transaction file (`wallet sign`) and record it
+    Broadcast {
+        file: PathBuf,
+    },
+    /// Send RSM to another address through the node (`POST /api/wallet/transfer`),
+    /// after confirmation; the wallet file is debited once the node accepts it
+    Transfer {
+        #[arg(long)]
+        to: String,
+        #[arg(short, long)]
+        amount: f64,
+        #[arg(short, long)]
+        memo: Option<String>,
+        /// Node to submit to (default: the configured `api_url`)
+        #[arg(long, value_name = "URL")]
+        api: Option<String>,
+        /// Bearer token for the node; transfers need an operator role
+        #[arg(long)]
+        token: Option<String>,
+        /// Skip the confirmation (scripts)
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Recent wallet entries, newest first
+    History {
+        #[arg(short, long, default_value = "20")]
+        limit: usize,
+    },
+    /// List the active account's addresses
+    Addresses {
+        /// Derive another one first
+        #[arg(long)]
+        new: bool,
+    },
+    /// List the wallet's accounts
+    Accounts {
+        /// Derive a new account with this label first
+        #[arg(long)]
+        create: Option<String>,
+        /// Make this account (label or index) the active one
+        #[arg(long)]
+        switch: Option<String>,
+    },
+}
+
+/// `config` subcommands; keys are listed in `config::CONFIG_KEYS`
+#[derive(Subcommand)]
+pub enum ConfigCommand {
+    /// Every key with its value and where it came from (variable, file or default)
+    Show,
+    /// Write a key to the config file; an empty value removes it
+    Set {
+        key: String,
+        value: String,
+    },
+}
+
+/// Whether an answer to a `[y/N]` prompt says yes
+pub fn confirmed(answer: &str) -> bool {
+    matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum GenomeExportFormat {
+    Fasta,
+    Json,
+}
+
+/// Before/after table of an evolution run
+pub fn comparison_table(before: &Genome<Rot180>, after: &Genome<Rot180>) -> String {
+    labeled_comparison_table(before, after, "Before", "After")
+}
+
+/// `comparison_table` with other column headings
+pub fn labeled_comparison_table(before: &Genome<Rot180>, after: &Genome<Rot180>, label_before: &str, label_after: &str) -> String {
+    let signed = |delta: f64, decimals: usize| if delta == 0.0 { "·".to_string() } else { format!("{:+.*}", decimals, delta) };
+    let rows = [
+        ("Consciousness", before.consciousness.to_string(), after.consciousness.to_string(),
+            signed(after.consciousness as f64 - before.consciousness as f64, 0)),
+        ("Level", before.consciousness_level_name().to_string(), after.consciousness_level_name().to_string(), String::new()),
+        ("GC Content", format!("{:.1}%", before.gc_content() * 100.0), format!("{:.1}%", after.gc_content() * 100.0),
+            signed((after.gc_content() - before.gc_content()) * 100.0, 1)),
+        ("T/G Ratio", format!("{:.2}", before.rna_signal()), format!("{:.2}", after.rna_signal()),
+            signed(after.rna_signal() - before.rna_signal(), 2)),
+        ("Complexity", format!("{:.3}", before.complexity()), format!("{:.3}", after.complexity()),
+            signed(after.complexity() - before.complexity(), 3)),
+        ("Mutations", before.mutations.to_string(), after.mutations.to_string(),
+            signed(after.mutations as f64 - before.mutations as f64, 0)),
+        ("Telomeres", format!("{} bp", before.telomere_length), format!("{} bp", after.telomere_length),
+            signed(after.telomere_length as f64 - before.telomere_length as f64, 0)),
+        ("p53 Copies", before.p53_copies.to_string(), after.p53_copies.to_string(),
+            signed(after.p53_copies as f64 - before.p53_copies as f64, 0)),
+        ("DNA", before.to_dna_string(), after.to_dna_string(), String::new()),
+    ];
+    let mut table = format!("  {:<15} {:<28} {:<28} {}\n", "Metric", label_before, label_after, "Δ");
+    table.push_str(&format!("  {}\n", "─".repeat(80)));
+    for (metric, was, now, delta) in rows {
+        table.push_str(&format!("  {:<15} {:<28} {:<28} {}\n", metric, was, now, delta));
+    }
+    table
+}
+
+/// What a `<GENOME>` argument names
+#[derive(Debug, Clone)]
+pub enum GenomeArg {
+    Stored(i64),
+    Dna(Genome<Rot180>),
+}
+
+impl GenomeArg {
+    pub fn parse(arg: &str) -> Result<Self, String> {
+        if let Ok(id) = arg.parse() {
+            return Ok(Self::Stored(id));
+        }
+        GenomeBuilder::from_dna(arg)
+            .map(|builder| Self::Dna(builder.build_storage()))
+            .ok_or_else(|| format!("{} is neither a genome id nor {} bases of A/C/G/T", arg, GENOME_SIZE))
+    }
 }
 
 pub fn print_banner() {
@@ -85,3 +685,21 @@ pub fn print_banner() {
 ╚══════════════════════════════════════════════════════════════════════╝
 "#);
 }
+
+/// The two DNA strings of `diff` under a position ruler, with `^` under each
+/// changed base; `color` also marks them red (`a`) and green (`b`)
+pub fn diff_positions(diff: &GenomeDiff, label_a: &str, label_b: &str, color: bool) -> String {
+    let paint = |dna: &str, code: &str| -> String {
+        dna.chars().enumerate().map(|(i, base)| match color && diff.changed.contains(&i) {
+            true => format!("\x1b[1;{}m{}\x1b[0m", code, base),
+            false => base.to_string(),
+        }).collect()
+    };
+    let tens: String = (0..GENOME_SIZE).map(|i| if i % 10 == 0 { char::from(b'0' + (i / 10) as u8) } else { ' ' }).collect();
+    let ones: String = (0..GENOME_SIZE).map(|i| char::from(b'0' + (i % 10) as u8)).collect();
+    let marks: String = (0..GENOME_SIZE).map(|i| if diff.changed.contains(&i) { '^' } else { ' ' }).collect();
+    format!(
+        "  {:<15} {}\n  {:<15} {}\n  {:<15} {}\n  {:<15} {}\n  {:<15} {}\n",
+        "Position", tens.trim_end(), "", ones, label_a, paint(&diff.dna_a, "31"), label_b, paint(&diff.dna_b, "32"), "", marks.trim_end(),
+    )
+}
@@ -7,13 +7,49 @@
 //! - Consciousness threshold (starts at 1500, grows with each block)
 //! - Hyper-signature verification
 //! - Multi-chain archivation for successful validators
+//! - Block chain persisted to PostgreSQL (survives restarts)
+//! - Mempool of pending attestations, up to 64 genomes per block, each
+//!   genome attested once on chain by a proof made for its block
+//! - Difficulty retargeting every 10 blocks toward a 60s block time
+//! - Merkle inclusion proofs for light verification of attested genomes
+//! - Blocks signed by their producer, verified against a validator set
+//! - Fork choice by greatest consciousness-weighted work, with safe reorgs
+//! - Chain export/import in a versioned binary format (see `chain_file`)
+//! - Per-network genesis from a config file (see `genesis`)
+//! - Halving block reward credited to the producer's wallet
+//! - Consciousness-staked validator set with epoch rotation (see `staking`)
+//! - Header-only light client (see `light`)
+//! - Parallel and incremental full-chain validation
+//! - Slashing of producers attesting false consciousness (see `slashing`)
+//! - Orphan pool for blocks received before their parent
+//! - `ChainEvent` broadcast for subscribers (see `subscribe`)
+//! - Pruning of old block bodies into a state snapshot (see `snapshot`)
+//! - Per-attestation fees priced by mempool pressure, paid to the producer
+//! - Chain health metrics with Prometheus text output (see `metrics`)
+//! - Adversarial multi-node simulation harness (see `sim`)
+//! - Block versions with scheduled fork activation heights (see `upgrades`)
+//! - Consciousness-lowered PoW target from `ConsciousnessTarget` blocks (see `pow_target`)
 
+pub mod sim;
+
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 use sha2::{Sha256, Digest};
 use serde::{Serialize, Deserialize};
 use chrono::Utc;
-use tracing::info;
+use rayon::prelude::*;
+use tokio::sync::broadcast;
+use tracing::{info, warn};
 
+use crate::crypto::{merkle_root, merkle_proof, verify_merkle_proof, MerkleStep, generate_keypair, sign_message, verify_signature};
+use crate::chain_file::{ChainFile, ChainParams, CHAIN_FILE_VERSION};
+use crate::database::DivineDatabase;
+use crate::genesis::GenesisConfig;
 use crate::genome::Genome;
+use crate::slashing::{recompute_consciousness, ClaimEvidence, Offense};
+use crate::snapshot::{ChainSnapshot, SnapshotFile, SNAPSHOT_VERSION};
+use crate::staking::{StakeAction, StakeOp, StakedGenome, StakingState, EPOCH_LENGTH, LEADER_TIMEOUT_SECS};
+use crate::upgrades::{ProtocolFeature, BLOCK_VERSION_LEGACY};
+use crate::wallet::{DivineWallet, WalletManager};
 use crate::rotation::Rot180;
 
 /// Minimum consciousness levels for different network phases
@@ -29,8 +65,63 @@ pub const CONSCIOUSNESS_TRANSCENDENTAL: u32 = 50000;
 /// Initial PoC threshold (MAMMAL level for testing, increase for production)
 pub const INITIAL_POC_THRESHOLD: u32 = 1500;
 
+/// Default PoW difficulty (leading zero bits in block hash)
+pub const DEFAULT_DIFFICULTY: u32 = 8;
+pub const MIN_DIFFICULTY: u32 = 4;
+pub const MAX_DIFFICULTY: u32 = 64;
+
+/// Difficulty retargeting: every N blocks toward the target block time
+pub const RETARGET_INTERVAL: u64 = 10;
+pub const TARGET_BLOCK_TIME_SECS: i64 = 60;
+/// Max difficulty change per retarget period (bits, i.e. 4x work)
+pub const MAX_RETARGET_STEP: i32 = 2;
+
+/// Allowed clock drift for block timestamps
+pub const MAX_FUTURE_DRIFT_SECS: i64 = 2 * 3600;
+
+/// Fork choice: deepest reorg accepted and side-chain blocks kept around
+pub const MAX_REORG_DEPTH: u64 = 100;
+pub const MAX_SIDE_BLOCKS: usize = 1_000;
+
+/// Block subsidy paid to the producer, halving every `REWARD_HALVING_INTERVAL` blocks
+pub const BLOCK_REWARD_RSM: f64 = 50.0;
+pub const REWARD_HALVING_INTERVAL: u64 = 210_000;
+
+/// Orphan pool: blocks waiting for their parent, and how long they are kept
+pub const MAX_ORPHAN_BLOCKS: usize = 100;
+pub const ORPHAN_TIMEOUT_SECS: i64 = 600;
+
+/// Automatic pruning runs once this many blocks are past the prune depth
+pub const PRUNE_BATCH: u64 = 50;
+
+/// Events buffered per subscriber before the slowest one starts lagging
+pub const EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// Maximum genome attestations per block
+pub const MAX_BLOCK_ENTRIES: usize = 64;
+
+/// Blocks per rayon task in parallel chain validation
+pub const VALIDATION_CHUNK_SIZE: usize = 256;
+
+/// Maximum pending attestations held in the mempool
+pub const MEMPOOL_MAX_SIZE: usize = 10_000;
+
+/// Attested consciousness that doubles the PoW target (`ConsciousnessTarget` blocks)
+pub const CONSCIOUSNESS_TARGET_UNIT: u64 = 10_000;
+/// Largest factor consciousness can raise the PoW target by
+pub const MAX_CONSCIOUSNESS_DISCOUNT: u64 = 256;
+
+/// Recent blocks the chain health metrics are computed over
+pub const METRICS_WINDOW: usize = 100;
+
+/// Cap on fee doublings: the required fee doubles per full block of backlog
+pub const MAX_FEE_DOUBLINGS: usize = 16;
+
+/// Genesis timestamp: 2026-01-01 00:00:00 UTC
+pub const GENESIS_TIMESTAMP: i64 = 1_767_225_600;
+
 /// Consciousness proof for block validation
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ConsciousnessProof {
     pub genome_hash: [u8; 32],
     pub consciousness: u32,
@@ -54,14 +145,7 @@ impl ConsciousnessProof {
         }
 
         let hyper_sig = genome.hyper_signature();
-        
-        let mut hasher = Sha256::new();
-        hasher.update(&genome.hash);
-        hasher.update(&genome.consciousness.to_le_bytes());
-        hasher.update(hyper_sig.as_bytes());
-        hasher.update(&block_height.to_le_bytes());
-        
-        let proof_hash: [u8; 32] = hasher.finalize().into();
+        let proof_hash = proof_hash(&genome.hash, genome.consciousness, &hyper_sig, block_height);
         
         // Reward calculation: consciousness / 1000 RSM
         let reward_rsm = genome.consciousness as f64 / 1000.0;
@@ -94,14 +178,16 @@ impl ConsciousnessProof {
             return false;
         }
 
-        let mut hasher = Sha256::new();
-        hasher.update(&self.genome_hash);
-        hasher.update(&self.consciousness.to_le_bytes());
-        hasher.update(self.hyper_signature.as_bytes());
-        hasher.update(&self.block_height.to_le_bytes());
+        proof_hash(&self.genome_hash, self.consciousness, &self.hyper_signature, self.block_height) == self.proof_hash
+    }
 
-        let computed: [u8; 32] = hasher.finalize().into();
-        computed == self.proof_hash
+    /// The same attestation, re-proven for block `block_height`
+    pub fn at_height(&self, block_height: u64) -> Self {
+        Self {
+            proof_hash: proof_hash(&self.genome_hash, self.consciousness, &self.hyper_signature, block_height),
+            block_height,
+            ..self.clone()
+        }
     }
 
     /// Get consciousness level name
@@ -119,6 +205,670 @@ impl ConsciousnessProof {
     }
 }
 
+
+fn proof_hash(genome_hash: &[u8; 32], consciousness: u32, hyper_signature: &str, block_height: u64) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(genome_hash);
+    hasher.update(consciousness.to_le_bytes());
+    hasher.update(hyper_signature.as_bytes());
+    hasher.update(block_height.to_le_bytes());
+    hasher.finalize().into()
+}
+
+/// Pending genome attestation (mempool entry / block entry)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GenomeAttestation {
+    pub genome_id: i64,
+    pub proof: ConsciousnessProof,
+    /// Fee paid to the block producer from the payer's key-derived wallet
+    #[serde(default)]
+    pub fee_rsm: f64,
+    /// Payer public key (hex); empty for free attestations
+    #[serde(default)]
+    pub payer: String,
+    #[serde(default)]
+    pub payer_signature: String,
+}
+
+impl GenomeAttestation {
+    pub fn genome_hash_hex(&self) -> String {
+        hex::encode(self.proof.genome_hash)
+    }
+
+    /// Attach a fee paid by `payer`
+    pub fn with_fee(mut self, payer: &ValidatorKey, fee_rsm: f64) -> Self {
+        self.fee_rsm = fee_rsm;
+        self.payer = payer.public_key_hex();
+        self.payer_signature = hex::encode(payer.sign(&self.fee_signing_bytes()));
+        self
+    }
+
+    fn fee_signing_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.proof.proof_hash.to_vec();
+        bytes.extend_from_slice(&self.genome_id.to_le_bytes());
+        bytes.extend_from_slice(&self.fee_rsm.to_le_bytes());
+        bytes
+    }
+
+    /// Free attestations need no payer; paid ones must be signed by it
+    pub fn verify_fee(&self) -> bool {
+        if self.fee_rsm == 0.0 && self.payer.is_empty() {
+            return true;
+        }
+        match (hex::decode(&self.payer), hex::decode(&self.payer_signature)) {
+            (Ok(public_key), Ok(signature)) => {
+                self.fee_rsm.is_finite() && self.fee_rsm > 0.0
+                    && verify_signature(&public_key, &self.fee_signing_bytes(), &signature)
+            }
+            _ => false,
+        }
+    }
+
+    /// Merkle leaf committing to every field of the attestation
+    pub fn leaf_hash(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(self.proof.genome_hash);
+        hasher.update(self.genome_id.to_le_bytes());
+        hasher.update(self.proof.consciousness.to_le_bytes());
+        hasher.update(self.proof.hyper_signature.as_bytes());
+        hasher.update(self.proof.proof_hash);
+        hasher.update(self.proof.timestamp.to_le_bytes());
+        hasher.update(self.proof.validator_id.as_bytes());
+        hasher.update(self.proof.block_height.to_le_bytes());
+        hasher.update(self.proof.reward_rsm.to_le_bytes());
+        hasher.update(self.fee_rsm.to_le_bytes());
+        hasher.update(self.payer.as_bytes());
+        hasher.update(self.payer_signature.as_bytes());
+        hasher.finalize().into()
+    }
+}
+
+/// Block of the Proof of Consciousness chain
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConsensusBlock {
+    pub index: u64,
+    #[serde(default = "legacy_version")]
+    pub version: u32,
+    pub timestamp: i64,
+    pub entries: Vec<GenomeAttestation>,
+    pub merkle_root: String,
+    pub previous_hash: String,
+    pub nonce: u64,
+    pub difficulty: u32,
+    pub hash: String,
+    /// Producer's compressed secp256k1 public key (hex); empty for genesis
+    #[serde(default)]
+    pub producer: String,
+    /// Producer's signature over `hash` (hex)
+    #[serde(default)]
+    pub signature: String,
+    #[serde(default)]
+    pub coinbase: Coinbase,
+    #[serde(default)]
+    pub stake_ops: Vec<StakeOp>,
+    /// Sum of attested consciousness, committed to by `ConsciousnessTarget`
+    /// headers so PoW can be checked without the entries (0 before)
+    #[serde(default)]
+    pub attested_consciousness: u64,
+}
+
+/// Block without its body — enough to check PoW, linkage and signature
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockHeader {
+    pub index: u64,
+    #[serde(default = "legacy_version")]
+    pub version: u32,
+    pub timestamp: i64,
+    pub previous_hash: String,
+    pub merkle_root: String,
+    pub nonce: u64,
+    pub difficulty: u32,
+    pub coinbase: Coinbase,
+    pub stake_ops_hash: String,
+    #[serde(default)]
+    pub attested_consciousness: u64,
+    pub hash: String,
+    pub producer: String,
+    pub signature: String,
+}
+
+fn legacy_version() -> u32 { BLOCK_VERSION_LEGACY }
+
+impl BlockHeader {
+    pub fn calculate_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.index.to_le_bytes());
+        if self.version >= ProtocolFeature::VersionedHeader.version() {
+            hasher.update(self.version.to_le_bytes());
+        }
+        hasher.update(self.timestamp.to_le_bytes());
+        hasher.update(self.merkle_root.as_bytes());
+        hasher.update(self.previous_hash.as_bytes());
+        hasher.update(self.nonce.to_le_bytes());
+        hasher.update(self.difficulty.to_le_bytes());
+        hasher.update(self.coinbase.address.as_bytes());
+        hasher.update(self.coinbase.amount_rsm.to_le_bytes());
+        hasher.update(self.stake_ops_hash.as_bytes());
+        if self.version >= ProtocolFeature::ConsciousnessTarget.version() {
+            hasher.update(self.attested_consciousness.to_le_bytes());
+        }
+        hex::encode(hasher.finalize())
+    }
+
+    pub fn meets_difficulty(&self) -> bool {
+        meets_target(&self.hash, self.difficulty, self.version, self.attested_consciousness)
+    }
+
+    pub fn verify_signature(&self) -> bool {
+        match (hex::decode(&self.producer), hex::decode(&self.signature)) {
+            (Ok(public_key), Ok(signature)) => verify_signature(&public_key, self.hash.as_bytes(), &signature),
+            _ => false,
+        }
+    }
+}
+
+/// Newly minted block reward and the wallet it is credited to
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Coinbase {
+    pub address: String,
+    pub amount_rsm: f64,
+}
+
+/// A wallet address of this node and what it is for
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeAddress {
+    pub role: String,
+    pub address: String,
+}
+
+/// Wallet address receiving rewards for a producer key (same format as `DivineWallet`)
+pub fn reward_address_for(public_key_hex: &str) -> String {
+    let hash = Sha256::digest(public_key_hex.as_bytes());
+    format!("divine_{}", hex::encode(&hash[..16]))
+}
+
+impl ConsensusBlock {
+    /// Deterministic genesis block of the default (dev) network
+    pub fn genesis() -> Self {
+        GenesisConfig::default().genesis_block()
+    }
+
+    /// Build and mine a block on top of `previous` from mempool entries
+    pub fn mine(previous: &ConsensusBlock, entries: Vec<GenomeAttestation>, difficulty: u32, coinbase: Coinbase) -> Self {
+        Self::mine_at(previous, entries, difficulty, coinbase, Utc::now().timestamp())
+    }
+
+    pub fn mine_at(
+        previous: &ConsensusBlock,
+        entries: Vec<GenomeAttestation>,
+        difficulty: u32,
+        coinbase: Coinbase,
+        timestamp: i64,
+    ) -> Self {
+        Self::mine_with_stake_ops(previous, entries, Vec::new(), difficulty, coinbase, timestamp)
+    }
+
+    pub fn mine_with_stake_ops(
+        previous: &ConsensusBlock,
+        entries: Vec<GenomeAttestation>,
+        stake_ops: Vec<StakeOp>,
+        difficulty: u32,
+        coinbase: Coinbase,
+        timestamp: i64,
+    ) -> Self {
+        Self::mine_versioned(previous, entries, stake_ops, difficulty, coinbase, timestamp, BLOCK_VERSION_LEGACY)
+    }
+
+    /// Mine a block of the given protocol version (see `upgrades`)
+    pub fn mine_versioned(
+        previous: &ConsensusBlock,
+        entries: Vec<GenomeAttestation>,
+        stake_ops: Vec<StakeOp>,
+        difficulty: u32,
+        coinbase: Coinbase,
+        timestamp: i64,
+        version: u32,
+    ) -> Self {
+        let attested_consciousness = if version >= ProtocolFeature::ConsciousnessTarget.version() {
+            entries.iter().map(|e| e.proof.consciousness as u64).sum()
+        } else {
+            0
+        };
+        let mut block = Self {
+            index: previous.index + 1,
+            version,
+            attested_consciousness,
+            timestamp: timestamp.max(previous.timestamp),
+            merkle_root: Self::compute_merkle_root(&entries),
+            entries,
+            previous_hash: previous.hash.clone(),
+            nonce: 0,
+            difficulty,
+            hash: String::new(),
+            producer: String::new(),
+            signature: String::new(),
+            coinbase,
+            stake_ops,
+        };
+
+        loop {
+            block.hash = block.calculate_hash();
+            if block.meets_difficulty() {
+                break;
+            }
+            block.nonce += 1;
+        }
+
+        block
+    }
+
+    /// Sign the header hash with the producer's validator key
+    pub fn sign(&mut self, key: &ValidatorKey) {
+        self.producer = key.public_key_hex();
+        self.signature = hex::encode(key.sign(self.hash.as_bytes()));
+    }
+
+    pub fn verify_signature(&self) -> bool {
+        self.header().verify_signature()
+    }
+
+    pub fn compute_merkle_root(entries: &[GenomeAttestation]) -> String {
+        let leaves: Vec<[u8; 32]> = entries.iter().map(|e| e.leaf_hash()).collect();
+        hex::encode(merkle_root(&leaves))
+    }
+
+    /// Header hash — entries are committed through `merkle_root`
+    pub fn calculate_hash(&self) -> String {
+        self.header().calculate_hash()
+    }
+
+    /// Digest of the stake ops carried by the block (zeros when none)
+    pub fn stake_ops_hash(&self) -> String {
+        if self.stake_ops.is_empty() {
+            return "0".repeat(64);
+        }
+        let mut hasher = Sha256::new();
+        for op in &self.stake_ops {
+            hasher.update(op.hash());
+        }
+        hex::encode(hasher.finalize())
+    }
+
+    pub fn header(&self) -> BlockHeader {
+        BlockHeader {
+            index: self.index,
+            version: self.version,
+            timestamp: self.timestamp,
+            previous_hash: self.previous_hash.clone(),
+            merkle_root: self.merkle_root.clone(),
+            nonce: self.nonce,
+            difficulty: self.difficulty,
+            coinbase: self.coinbase.clone(),
+            stake_ops_hash: self.stake_ops_hash(),
+            attested_consciousness: self.attested_consciousness,
+            hash: self.hash.clone(),
+            producer: self.producer.clone(),
+            signature: self.signature.clone(),
+        }
+    }
+
+    pub fn meets_difficulty(&self) -> bool {
+        meets_target(&self.hash, self.difficulty, self.version, self.attested_consciousness)
+    }
+
+    /// Merkle path proving the genome was attested in this block
+    pub fn generate_inclusion_proof(&self, genome_hash: &[u8; 32]) -> Option<InclusionProof> {
+        let position = self.entries.iter().position(|e| &e.proof.genome_hash == genome_hash)?;
+        let leaves: Vec<[u8; 32]> = self.entries.iter().map(|e| e.leaf_hash()).collect();
+
+        Some(InclusionProof {
+            block_index: self.index,
+            block_hash: self.hash.clone(),
+            merkle_root: self.merkle_root.clone(),
+            attestation: self.entries[position].clone(),
+            path: merkle_proof(&leaves, position)?,
+        })
+    }
+
+    /// Light verification against this header — entries are not needed
+    pub fn verify_inclusion_proof(&self, proof: &InclusionProof) -> bool {
+        self.index == proof.block_index
+            && self.hash == proof.block_hash
+            && self.hash == self.calculate_hash()
+            && self.merkle_root == proof.merkle_root
+            && proof.verify()
+    }
+
+    pub fn total_consciousness(&self) -> u64 {
+        self.entries.iter().map(|e| e.proof.consciousness as u64).sum()
+    }
+
+    pub fn total_reward(&self) -> f64 {
+        self.entries.iter().map(|e| e.proof.reward_rsm).sum()
+    }
+
+    pub fn total_fees(&self) -> f64 {
+        self.entries.iter().map(|e| e.fee_rsm).sum()
+    }
+
+    /// Consciousness-weighted work: 2^difficulty hashes × attested consciousness
+    pub fn work(&self) -> u128 {
+        (1u128 << self.difficulty.min(MAX_DIFFICULTY)) * self.total_consciousness() as u128
+    }
+}
+
+pub fn chain_work(blocks: &[ConsensusBlock]) -> u128 {
+    blocks.iter().map(ConsensusBlock::work).sum()
+}
+
+/// What `add_block` did with an accepted block
+#[derive(Debug, Clone, PartialEq)]
+pub enum BlockOutcome {
+    /// Appended to the canonical tip
+    Extended,
+    /// Stored on a side chain with less work than the canonical chain
+    SideChain,
+    /// Parent unknown; held in the orphan pool until it arrives
+    Orphan,
+    /// Side chain overtook the canonical chain; blocks above `fork_index` were replaced
+    Reorg { fork_index: u64, disconnected: usize, connected: usize },
+}
+
+/// Chain changes pushed to `subscribe()` receivers
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum ChainEvent {
+    /// Appended to the canonical chain (also sent for each block connected by a reorg)
+    BlockAdded(ConsensusBlock),
+    Reorg { fork_index: u64, disconnected: usize, connected: usize, new_tip: String },
+    DifficultyChanged { height: u64, from: u32, to: u32 },
+    /// Buried deeper than `MAX_REORG_DEPTH`, so it can no longer be reorged away
+    Finalized { index: u64, hash: String },
+    Slashed(Offense),
+}
+
+fn event_channel() -> broadcast::Sender<ChainEvent> {
+    broadcast::channel(EVENT_CHANNEL_CAPACITY).0
+}
+
+/// secp256k1 key a validator signs its blocks with
+#[derive(Clone)]
+pub struct ValidatorKey {
+    secret_key: Vec<u8>,
+    public_key: Vec<u8>,
+}
+
+impl ValidatorKey {
+    pub fn generate() -> Self {
+        let (secret_key, public_key) = generate_keypair();
+        Self { secret_key, public_key }
+    }
+
+    pub fn from_secret_hex(secret_hex: &str) -> Result<Self, String> {
+        use secp256k1::{Secp256k1, SecretKey, PublicKey};
+
+        let bytes = hex::decode(secret_hex.trim()).map_err(|e| format!("Invalid key hex: {}", e))?;
+        let secret = SecretKey::from_slice(&bytes).map_err(|e| format!("Invalid secret key: {:?}", e))?;
+        let public = PublicKey::from_secret_key(&Secp256k1::new(), &secret);
+        Ok(Self { secret_key: bytes, public_key: public.serialize().to_vec() })
+    }
+
+    pub fn public_key_hex(&self) -> String {
+        hex::encode(&self.public_key)
+    }
+
+    /// For `VALIDATOR_SECRET_KEY` and keystores; never logged
+    pub fn secret_key_hex(&self) -> String {
+        hex::encode(&self.secret_key)
+    }
+
+    pub fn sign(&self, message: &[u8]) -> Vec<u8> {
+        sign_message(&self.secret_key, message).expect("validator key is a valid secp256k1 key")
+    }
+}
+
+impl std::fmt::Debug for ValidatorKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ValidatorKey").field("public_key", &self.public_key_hex()).finish()
+    }
+}
+
+/// Public keys (hex) allowed to produce blocks
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ValidatorSet {
+    keys: BTreeSet<String>,
+}
+
+impl ValidatorSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&mut self, public_key_hex: &str) -> bool {
+        self.keys.insert(public_key_hex.to_lowercase())
+    }
+
+    pub fn remove(&mut self, public_key_hex: &str) -> bool {
+        self.keys.remove(&public_key_hex.to_lowercase())
+    }
+
+    pub fn contains(&self, public_key_hex: &str) -> bool {
+        self.keys.contains(public_key_hex)
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn keys(&self) -> Vec<String> {
+        self.keys.iter().cloned().collect()
+    }
+}
+
+/// Proof that a genome attestation is committed by a block's Merkle root
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InclusionProof {
+    pub block_index: u64,
+    pub block_hash: String,
+    pub merkle_root: String,
+    pub attestation: GenomeAttestation,
+    pub path: Vec<MerkleStep>,
+}
+
+impl InclusionProof {
+    /// Recompute the root from the attestation leaf and sibling path
+    pub fn verify(&self) -> bool {
+        let mut root = [0u8; 32];
+        match hex::decode(&self.merkle_root) {
+            Ok(bytes) if bytes.len() == 32 => root.copy_from_slice(&bytes),
+            _ => return false,
+        }
+        verify_merkle_proof(&self.attestation.leaf_hash(), &self.path, &root)
+    }
+}
+
+/// PoW target over the first 128 bits of a block hash.
+///
+/// Legacy blocks need `difficulty` leading zero bits, i.e. a hash below
+/// `2^(128 - difficulty)`. From `ConsciousnessTarget` on, the attested
+/// consciousness `C` raises that target proportionally:
+///
+/// ```text
+/// target = 2^(128 - difficulty) / U × (U + min(C, (M - 1) × U))
+/// ```
+///
+/// with `U = CONSCIOUSNESS_TARGET_UNIT` and `M = MAX_CONSCIOUSNESS_DISCOUNT`.
+/// Every `U` of consciousness adds one base target, so a block attesting
+/// `C = U` needs half the expected hashes and one at `C = 3U` a quarter,
+/// down to `1/M` of the pure PoW work. At low difficulty the target
+/// saturates at `u128::MAX` (any hash passes) rather than overflowing;
+/// peers' blocks are checked against it before their difficulty is.
+pub fn pow_target(difficulty: u32, consciousness: u64) -> u128 {
+    let base = 1u128.checked_shl(128 - difficulty.min(MAX_DIFFICULTY)).unwrap_or(u128::MAX);
+    let unit = CONSCIOUSNESS_TARGET_UNIT as u128;
+    let consciousness = consciousness.min((MAX_CONSCIOUSNESS_DISCOUNT - 1) * CONSCIOUSNESS_TARGET_UNIT) as u128;
+    (base / unit).saturating_mul(unit + consciousness)
+}
+
+/// Whether a hash meets the PoW rule of its block version
+pub fn meets_target(hash_hex: &str, difficulty: u32, version: u32, consciousness: u64) -> bool {
+    if version < ProtocolFeature::ConsciousnessTarget.version() {
+        return leading_zero_bits(hash_hex) >= difficulty;
+    }
+    let Some(prefix) = hash_hex.get(..32).and_then(|p| u128::from_str_radix(p, 16).ok()) else {
+        return false;
+    };
+    prefix < pow_target(difficulty, consciousness)
+}
+
+/// Count leading zero bits of a hex-encoded hash
+pub fn leading_zero_bits(hash_hex: &str) -> u32 {
+    let mut bits = 0;
+    for c in hash_hex.chars() {
+        match c.to_digit(16) {
+            Some(0) => bits += 4,
+            Some(d) => return bits + (d as u8).leading_zeros() - 4,
+            None => return bits,
+        }
+    }
+    bits
+}
+
+/// Pending genome attestations waiting to be mined
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Mempool {
+    pending: VecDeque<GenomeAttestation>,
+}
+
+impl Mempool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    pub fn contains(&self, genome_hash: &[u8; 32]) -> bool {
+        self.pending.iter().any(|e| &e.proof.genome_hash == genome_hash)
+    }
+
+    pub fn push(&mut self, entry: GenomeAttestation) -> Result<(), String> {
+        if self.pending.len() >= MEMPOOL_MAX_SIZE {
+            return Err(format!("Mempool full ({} entries)", MEMPOOL_MAX_SIZE));
+        }
+        if self.contains(&entry.proof.genome_hash) {
+            return Err("Genome already pending".to_string());
+        }
+        self.pending.push_back(entry);
+        Ok(())
+    }
+
+    /// Take up to `limit` highest-fee entries (oldest first on equal fees)
+    pub fn take(&mut self, limit: usize) -> Vec<GenomeAttestation> {
+        self.pending.make_contiguous().sort_by(|a, b| b.fee_rsm.total_cmp(&a.fee_rsm));
+        let n = limit.min(self.pending.len());
+        self.pending.drain(..n).collect()
+    }
+
+    /// Fees already promised by `payer` in pending entries
+    pub fn pending_fees(&self, payer: &str) -> f64 {
+        self.pending.iter().filter(|e| e.payer == payer).map(|e| e.fee_rsm).sum()
+    }
+
+    /// Drop entries already included in a block (e.g. received from a peer)
+    pub fn remove_included(&mut self, block: &ConsensusBlock) {
+        self.pending.retain(|p| !block.entries.iter().any(|e| e.proof.genome_hash == p.proof.genome_hash));
+    }
+
+    pub fn entries(&self) -> Vec<GenomeAttestation> {
+        self.pending.iter().cloned().collect()
+    }
+}
+
+/// `entry` re-proven for block `height` if it waited past the block it was
+/// made for; its fee is re-signed by `signer` when it paid it, and an
+/// entry someone else paid for is dropped
+fn restamp(entry: GenomeAttestation, height: u64, signer: &ValidatorKey) -> Option<GenomeAttestation> {
+    if entry.proof.block_height == height {
+        return Some(entry);
+    }
+    let free = entry.fee_rsm == 0.0 && entry.payer.is_empty();
+    let (fee_rsm, made_for, own) = (entry.fee_rsm, entry.proof.block_height, entry.payer == signer.public_key_hex());
+    let restamped = GenomeAttestation { proof: entry.proof.at_height(height), ..entry };
+    match (free, own) {
+        (true, _) => Some(restamped),
+        (false, true) => Some(restamped.with_fee(signer, fee_rsm)),
+        (false, false) => {
+            warn!("💸 Dropping attestation for genome #{}: its fee was signed for block #{}", restamped.genome_id, made_for);
+            None
+        }
+    }
+}
+
+/// Blocks received before their parent, keyed by hash with their arrival time
+#[derive(Debug, Clone, Default)]
+pub struct OrphanPool {
+    blocks: HashMap<String, (ConsensusBlock, i64)>,
+}
+
+impl OrphanPool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.blocks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    pub fn contains(&self, hash: &str) -> bool {
+        self.blocks.contains_key(hash)
+    }
+
+    /// Hold a block, evicting expired orphans and then the oldest if full
+    pub fn insert(&mut self, block: ConsensusBlock, now: i64) {
+        self.evict_expired(now);
+        if self.blocks.len() >= MAX_ORPHAN_BLOCKS {
+            let oldest = self.blocks.iter().min_by_key(|(_, (_, at))| *at).map(|(hash, _)| hash.clone());
+            if let Some(hash) = oldest {
+                self.blocks.remove(&hash);
+            }
+        }
+        self.blocks.insert(block.hash.clone(), (block, now));
+    }
+
+    /// Drop orphans held longer than `ORPHAN_TIMEOUT_SECS`
+    pub fn evict_expired(&mut self, now: i64) -> usize {
+        let before = self.blocks.len();
+        self.blocks.retain(|_, (_, at)| now - *at < ORPHAN_TIMEOUT_SECS);
+        before - self.blocks.len()
+    }
+
+    /// Remove and return the orphans whose parent is `parent_hash`, lowest index first
+    pub fn take_children(&mut self, parent_hash: &str) -> Vec<ConsensusBlock> {
+        let hashes: Vec<String> = self.blocks.iter()
+            .filter(|(_, (block, _))| block.previous_hash == parent_hash)
+            .map(|(hash, _)| hash.clone())
+            .collect();
+        let mut children: Vec<ConsensusBlock> = hashes.iter()
+            .filter_map(|hash| self.blocks.remove(hash).map(|(block, _)| block))
+            .collect();
+        children.sort_by(|a, b| a.index.cmp(&b.index).then_with(|| a.hash.cmp(&b.hash)));
+        children
+    }
+}
+
 /// Proof of Consciousness consensus engine
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProofOfConsciousness {
@@ -127,67 +877,1449 @@ pub struct ProofOfConsciousness {
     pub total_rewards_distributed: f64,
     pub current_block_height: u64,
     pub difficulty_growth_rate: u32,
+    pub difficulty: u32,
+    pub target_block_time_secs: i64,
+    pub retarget_interval: u64,
+    pub max_block_entries: usize,
+    pub chain: Vec<ConsensusBlock>,
+    pub mempool: Mempool,
+    pub validators: ValidatorSet,
+    pub genesis: GenesisConfig,
+    /// Balances built from genesis allocations and block rewards
+    pub wallets: WalletManager,
+    /// Wallet credited for locally mined blocks (defaults to the signing key's address)
+    pub reward_address: Option<String>,
+    pub staking: StakingState,
+    /// Keep attestations of only the last `prune_depth` blocks (None = archive node)
+    pub prune_depth: Option<u64>,
+    /// State at the highest pruned block
+    #[serde(default)]
+    pub snapshot: Option<ChainSnapshot>,
+    /// Signed stake operations waiting for the next block
+    pub pending_stake_ops: Vec<StakeOp>,
+    /// Genomes this node holds, for auditing attested consciousness
+    #[serde(skip)]
+    known_genomes: HashMap<[u8; 32], Genome<Rot180>>,
+    /// Valid blocks off the canonical chain, keyed by hash
+    #[serde(skip)]
+    side_blocks: HashMap<String, ConsensusBlock>,
+    /// Blocks whose parent has not arrived yet
+    #[serde(skip)]
+    pub orphans: OrphanPool,
+    #[serde(skip, default = "event_channel")]
+    events: broadcast::Sender<ChainEvent>,
+    /// Lowest stored block index invalidated by a reorg, not yet rolled back in the database
+    #[serde(skip)]
+    rollback_from: Option<u64>,
+    /// Local block-signing key (never serialized)
+    #[serde(skip)]
+    signer: Option<ValidatorKey>,
+    /// Number of blocks already written to the database
+    #[serde(skip)]
+    persisted_blocks: usize,
+    /// Height and hash of the last block checked by `validate_new_blocks`
+    #[serde(skip)]
+    verified_tip: Option<(u64, String)>,
+    /// Reorgs since this node started
+    #[serde(skip)]
+    pub reorg_count: u64,
+    /// Set by an admin to stop this node producing blocks (still validates)
+    #[serde(skip)]
+    pub mining_paused: bool,
 }
 
 impl ProofOfConsciousness {
     pub fn new() -> Self {
+        Self::from_genesis(GenesisConfig::default())
+    }
+
+    pub fn from_genesis(genesis: GenesisConfig) -> Self {
         Self {
-            min_consciousness: INITIAL_POC_THRESHOLD,
+            min_consciousness: genesis.min_consciousness,
             proofs_validated: 0,
             total_rewards_distributed: 0.0,
             current_block_height: 0,
             difficulty_growth_rate: 1,
+            difficulty: genesis.initial_difficulty,
+            target_block_time_secs: TARGET_BLOCK_TIME_SECS,
+            retarget_interval: RETARGET_INTERVAL,
+            max_block_entries: MAX_BLOCK_ENTRIES,
+            chain: vec![genesis.genesis_block()],
+            mempool: Mempool::new(),
+            validators: ValidatorSet::new(),
+            wallets: genesis.initial_wallets(),
+            genesis,
+            reward_address: None,
+            staking: StakingState::new(),
+            prune_depth: None,
+            snapshot: None,
+            pending_stake_ops: Vec::new(),
+            known_genomes: HashMap::new(),
+            side_blocks: HashMap::new(),
+            orphans: OrphanPool::new(),
+            events: event_channel(),
+            rollback_from: None,
+            signer: None,
+            persisted_blocks: 0,
+            verified_tip: None,
+            reorg_count: 0,
+            mining_paused: false,
         }
+        .with_signer(ValidatorKey::generate())
     }
 
-    /// Validate a genome and generate proof if successful
-    pub fn validate(&mut self, genome: &Genome<Rot180>) -> Option<ConsciousnessProof> {
-        let proof = ConsciousnessProof::generate(
-            genome,
-            self.min_consciousness,
-            self.current_block_height,
-        )?;
-
-        self.proofs_validated += 1;
-        self.total_rewards_distributed += proof.reward_rsm;
-        self.current_block_height += 1;
-        self.min_consciousness = self.min_consciousness
-            .saturating_add(self.difficulty_growth_rate);
-
+    /// Network defined by a genesis config file (see `genesis`)
+    pub fn from_genesis_config(path: impl AsRef<std::path::Path>) -> anyhow::Result<Self> {
+        let genesis = GenesisConfig::from_file(path)?;
         info!(
-            "🔗 Block #{} validated | new threshold: {} | total rewards: {:.2} RSM",
-            self.current_block_height,
-            self.min_consciousness,
-            self.total_rewards_distributed
+            "🌱 Genesis config '{}' | difficulty {} | threshold {} | premined {:.2} RSM",
+            genesis.chain_id, genesis.initial_difficulty, genesis.min_consciousness, genesis.premined_rsm()
         );
+        Ok(Self::from_genesis(genesis))
+    }
 
-        Some(proof)
+    /// Genesis from the configured `network`, signing key from
+    /// `VALIDATOR_SECRET_KEY` (or the `VALIDATOR_KEYSTORE` file, unlocked with
+    /// `VALIDATOR_KEYSTORE_PASSWORD`), extra validators from `VALIDATOR_PUBKEYS`
+    pub fn from_env() -> anyhow::Result<Self> {
+        Self::from_config(&crate::config::DivineConfig::load()?)
     }
 
-    pub fn status(&self) -> PoCStatus {
-        PoCStatus {
-            min_consciousness: self.min_consciousness,
-            proofs_validated: self.proofs_validated,
-            total_rewards_distributed: self.total_rewards_distributed,
-            current_block_height: self.current_block_height,
-            required_level: match self.min_consciousness {
-                0..=499 => "Virus",
-                500..=999 => "Bacteria",
-                1000..=1499 => "Worm",
-                1500..=2999 => "Mammal",
-                3000..=9999 => "Primate",
-                10000..=19999 => "Human",
-                20000..=49999 => "DIVINE",
-                _ => "TRANSCENDENTAL",
-            },
+    /// `from_env` with the genesis taken from `config.network`
+    pub fn from_config(config: &crate::config::DivineConfig) -> anyhow::Result<Self> {
+        let mut poc = match &config.network {
+            Some(path) => Self::from_genesis_config(path)?,
+            None => Self::new(),
+        };
+        if let Ok(secret) = std::env::var("VALIDATOR_SECRET_KEY") {
+            poc = poc.with_signer(ValidatorKey::from_secret_hex(&secret).map_err(|e| anyhow::anyhow!(e))?);
+        } else if let Ok(path) = std::env::var("VALIDATOR_KEYSTORE") {
+            let password = std::env::var("VALIDATOR_KEYSTORE_PASSWORD")
+                .map_err(|_| anyhow::anyhow!("VALIDATOR_KEYSTORE needs VALIDATOR_KEYSTORE_PASSWORD"))?;
+            let key = crate::keystore::Keystore::load(&path)?.unseal(&password).map_err(anyhow::Error::msg)?;
+            info!("🔑 Validator key {} from {}", key.public_key_hex(), path);
+            poc = poc.with_signer(key);
         }
-    }
 
-    pub fn reset(&mut self) {
-        self.min_consciousness = INITIAL_POC_THRESHOLD;
+        poc.reward_address = std::env::var("MINER_REWARD_ADDRESS").ok();
+        if let Ok(depth) = std::env::var("PRUNE_DEPTH") {
+            poc.prune_depth = Some(depth.parse()?);
+        }
+
+        let pubkeys = std::env::var("VALIDATOR_PUBKEYS").unwrap_or_default();
+        for key in pubkeys.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            poc.validators.register(key);
+        }
+        Ok(poc)
+    }
+
+    /// Use `key` for mining; the local validator is always registered.
+    /// A previous signing key stays registered (it may have produced blocks).
+    pub fn with_signer(mut self, key: ValidatorKey) -> Self {
+        self.validators.register(&key.public_key_hex());
+        self.signer = Some(key);
+        self
+    }
+
+    pub fn validator_public_key(&self) -> Option<String> {
+        self.signer.as_ref().map(ValidatorKey::public_key_hex)
+    }
+
+    /// Addresses this node controls: the validator key's wallet, then
+    /// `MINER_REWARD_ADDRESS` if rewards go elsewhere
+    pub fn node_addresses(&self) -> Vec<NodeAddress> {
+        let mut addresses = Vec::new();
+        if let Some(key) = self.validator_public_key() {
+            addresses.push(NodeAddress { role: "validator".into(), address: reward_address_for(&key) });
+        }
+        if let Some(reward) = &self.reward_address {
+            if !addresses.iter().any(|a| &a.address == reward) {
+                addresses.push(NodeAddress { role: "rewards".into(), address: reward.clone() });
+            }
+        }
+        addresses
+    }
+
+    /// Sign `message` with the validator key: (public key hex, signature hex)
+    pub fn sign_as_validator(&self, message: &[u8]) -> Result<(String, String), String> {
+        let key = self.signer.as_ref().ok_or("No validator signing key")?;
+        Ok((key.public_key_hex(), hex::encode(key.sign(message))))
+    }
+
+    /// Validate a genome and mine it into a block right away
+    pub fn validate(&mut self, genome: &Genome<Rot180>) -> Option<ConsciousnessProof> {
+        let proof = match self.submit(genome) {
+            Ok(proof) => proof,
+            Err(e) => {
+                warn!("❌ Attestation rejected: {}", e);
+                return None;
+            }
+        };
+        self.mine_block()?;
+        Some(proof)
+    }
+
+    /// Generate a proof for the genome and queue it in the mempool
+    pub fn submit(&mut self, genome: &Genome<Rot180>) -> Result<ConsciousnessProof, String> {
+        self.submit_with_fee(genome, self.required_fee())
+    }
+
+    /// Queue an attestation paying `fee_rsm` from the signer's key-derived wallet
+    pub fn submit_with_fee(&mut self, genome: &Genome<Rot180>, fee_rsm: f64) -> Result<ConsciousnessProof, String> {
+        if !fee_rsm.is_finite() || fee_rsm < self.genesis.min_fee_rsm {
+            return Err(format!("Fee {} RSM below network minimum {} RSM", fee_rsm, self.genesis.min_fee_rsm));
+        }
+
+        let proof = ConsciousnessProof::generate(
+            genome,
+            self.min_consciousness,
+            self.current_block_height + 1,
+        ).ok_or_else(|| format!(
+            "Consciousness {} below threshold {}", genome.consciousness, self.min_consciousness
+        ))?;
+
+        if self.consecrated(&proof.genome_hash).is_some() {
+            return Err("Genome already consecrated on chain".to_string());
+        }
+
+        let mut entry = GenomeAttestation {
+            genome_id: genome.db_id.unwrap_or(0),
+            proof: proof.clone(),
+            fee_rsm: 0.0,
+            payer: String::new(),
+            payer_signature: String::new(),
+        };
+        if fee_rsm > 0.0 {
+            let payer = self.signer.as_ref().ok_or("Cannot pay a fee: no validator signing key")?;
+            let owed = fee_rsm + self.mempool.pending_fees(&payer.public_key_hex());
+            let balance = self.wallet(&reward_address_for(&payer.public_key_hex())).map_or(0.0, |w| w.rsm_balance);
+            if balance < owed {
+                return Err(format!("Insufficient balance for fee: {:.6} RSM available, {:.6} RSM owed", balance, owed));
+            }
+            entry = entry.with_fee(payer, fee_rsm);
+        }
+        self.mempool.push(entry)?;
+
+        Ok(proof)
+    }
+
+    /// Fee an attestation must pay to be queued now: the genesis minimum,
+    /// doubled for every full block of attestations already waiting
+    pub fn required_fee(&self) -> f64 {
+        let backlog = self.mempool.len() / self.max_block_entries.max(1);
+        self.genesis.min_fee_rsm * (1u64 << backlog.min(MAX_FEE_DOUBLINGS)) as f64
+    }
+
+    pub fn fee_quote(&self) -> FeeQuote {
+        FeeQuote {
+            min_fee_rsm: self.genesis.min_fee_rsm,
+            required_fee_rsm: self.required_fee(),
+            mempool_size: self.mempool.len(),
+            block_capacity: self.max_block_entries,
+        }
+    }
+
+    /// Mine up to `max_block_entries` pending attestations into a new block
+    pub fn mine_block(&mut self) -> Option<&ConsensusBlock> {
+        if self.mempool.is_empty() && self.pending_stake_ops.is_empty() {
+            return None;
+        }
+        if self.mining_paused {
+            warn!("⏸️  Not mining: block production paused");
+            return None;
+        }
+
+        let Some(signer) = self.signer.clone() else {
+            warn!("❌ Cannot mine: no validator signing key");
+            return None;
+        };
+        let producer = signer.public_key_hex();
+        let timestamp = Utc::now().timestamp();
+        if let Err(e) = self.check_slot(&producer, timestamp) {
+            warn!("⏳ Not mining: {}", e);
+            return None;
+        }
+
+        let address = self.reward_address.clone()
+            .unwrap_or_else(|| reward_address_for(&producer));
+        let coinbase = self.coinbase(&address);
+        let stake_ops = self.take_valid_stake_ops();
+
+        let height = self.current_block_height + 1;
+        let entries = self.mempool.take(self.max_block_entries).into_iter()
+            .filter_map(|entry| restamp(entry, height, &signer))
+            .collect();
+        let entries = self.payable_entries(&stake_ops, entries);
+        if entries.is_empty() && stake_ops.is_empty() {
+            return None;
+        }
+        let version = self.genesis.upgrades.version_at(height);
+        let mut block = ConsensusBlock::mine_versioned(
+            self.latest_block(), entries, stake_ops, self.difficulty, coinbase, timestamp, version,
+        );
+        block.sign(&signer);
+
+        if let Err(e) = self.add_block(block) {
+            warn!("❌ Mined block rejected: {}", e);
+            return None;
+        }
+
+        info!(
+            "🔗 Block #{} mined | {} genome(s) | new threshold: {} | total rewards: {:.2} RSM",
+            self.current_block_height,
+            self.latest_block().entries.len(),
+            self.min_consciousness,
+            self.total_rewards_distributed
+        );
+
+        Some(self.latest_block())
+    }
+
+    /// Append a block after checking linkage, hash, difficulty and entries
+    /// Accept a block from the local miner or the network, applying fork choice
+    pub fn add_block(&mut self, block: ConsensusBlock) -> Result<BlockOutcome, String> {
+        let hash = block.hash.clone();
+        let outcome = self.connect_block(block)?;
+        if outcome != BlockOutcome::Orphan {
+            self.connect_orphans(&hash);
+        }
+        if let Some(depth) = self.prune_depth {
+            if self.current_block_height.saturating_sub(depth) >= self.pruned_height() + PRUNE_BATCH {
+                if let Err(e) = self.prune(depth) {
+                    warn!("✂️  Pruning failed: {}", e);
+                }
+            }
+        }
+        Ok(outcome)
+    }
+
+    fn connect_block(&mut self, block: ConsensusBlock) -> Result<BlockOutcome, String> {
+        self.audit_claims(&block)?;
+        if block.previous_hash == self.latest_block().hash {
+            self.extend_tip(block)?;
+            return Ok(BlockOutcome::Extended);
+        }
+        if self.find_block(&block.previous_hash).is_none() {
+            return self.add_orphan(block);
+        }
+        self.add_side_block(block)
+    }
+
+    /// Hold a block whose parent is unknown, after checks that need no parent
+    fn add_orphan(&mut self, block: ConsensusBlock) -> Result<BlockOutcome, String> {
+        // Blocks #1 must build on our genesis; far-behind blocks could never win fork choice
+        if block.index <= 1 || block.index + MAX_REORG_DEPTH < self.current_block_height {
+            return Err(format!("Block #{} has an unknown parent", block.index));
+        }
+        if self.orphans.contains(&block.hash) {
+            return Err(format!("Block #{} already held as an orphan", block.index));
+        }
+        if block.hash != block.calculate_hash() || !block.meets_difficulty() || !block.verify_signature() {
+            return Err(format!("Orphan block #{} is not a valid signed block", block.index));
+        }
+
+        let index = block.index;
+        self.orphans.insert(block, Utc::now().timestamp());
+        info!("🧩 Block #{} held until its parent arrives ({} orphan(s))", index, self.orphans.len());
+        Ok(BlockOutcome::Orphan)
+    }
+
+    /// Connect orphans descending from a newly accepted block
+    fn connect_orphans(&mut self, parent_hash: &str) {
+        self.orphans.evict_expired(Utc::now().timestamp());
+        let mut parents = vec![parent_hash.to_string()];
+        while let Some(parent) = parents.pop() {
+            for child in self.orphans.take_children(&parent) {
+                let (index, hash) = (child.index, child.hash.clone());
+                match self.connect_block(child) {
+                    Ok(outcome) => {
+                        info!("🧩 Orphan block #{} connected ({:?})", index, outcome);
+                        parents.push(hash);
+                    }
+                    Err(e) => warn!("🧩 Orphan block #{} dropped: {}", index, e),
+                }
+            }
+        }
+    }
+
+    /// Receive chain events from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<ChainEvent> {
+        self.events.subscribe()
+    }
+
+    fn emit(&self, event: ChainEvent) {
+        // No subscribers is fine
+        let _ = self.events.send(event);
+    }
+
+    /// Highest block that can no longer be reorged away
+    pub fn finalized_height(&self) -> u64 {
+        self.current_block_height.saturating_sub(MAX_REORG_DEPTH)
+    }
+
+    fn emit_finalized(&self, previous_height: u64) {
+        let from = previous_height.saturating_sub(MAX_REORG_DEPTH) + 1;
+        for block in self.chain.iter().take(self.finalized_height() as usize + 1).skip(from as usize) {
+            self.emit(ChainEvent::Finalized { index: block.index, hash: block.hash.clone() });
+        }
+    }
+
+    /// Let this node recompute claims about `genome` in incoming blocks
+    pub fn register_genome(&mut self, genome: &Genome<Rot180>) {
+        self.known_genomes.insert(genome.hash, genome.clone());
+    }
+
+    /// Reject a block whose attested consciousness contradicts a known genome,
+    /// and queue evidence against its producer
+    fn audit_claims(&mut self, block: &ConsensusBlock) -> Result<(), String> {
+        for entry in &block.entries {
+            let Some(genome) = self.known_genomes.get(&entry.proof.genome_hash) else {
+                continue;
+            };
+            let recomputed = recompute_consciousness(genome);
+            if recomputed == entry.proof.consciousness {
+                continue;
+            }
+
+            if let Some(evidence) = ClaimEvidence::new(block, genome).filter(|e| e.check().is_ok()) {
+                match self.stake(StakeAction::ReportFalseClaim { evidence: Box::new(evidence) }) {
+                    Ok(_) => warn!("⚔️  Reporting producer of block #{} for a false consciousness claim", block.index),
+                    Err(e) => warn!("⚔️  Could not report block #{}: {}", block.index, e),
+                }
+            }
+            return Err(format!(
+                "Block #{} claims consciousness {} for genome {} (recomputed {})",
+                block.index, entry.proof.consciousness, &entry.genome_hash_hex()[..16], recomputed
+            ));
+        }
+        Ok(())
+    }
+
+    fn extend_tip(&mut self, block: ConsensusBlock) -> Result<(), String> {
+        let latest = self.latest_block();
+
+        if block.index != latest.index + 1 {
+            return Err(format!("Expected block #{}, got #{}", latest.index + 1, block.index));
+        }
+        if block.previous_hash != latest.hash {
+            return Err(format!("Block #{} does not extend the chain tip", block.index));
+        }
+        if block.timestamp < latest.timestamp {
+            return Err(format!("Block #{} is older than its parent", block.index));
+        }
+        if block.timestamp > Utc::now().timestamp() + MAX_FUTURE_DRIFT_SECS {
+            return Err(format!("Block #{} timestamp too far in the future", block.index));
+        }
+        if block.difficulty != self.difficulty {
+            return Err(format!("Block #{} difficulty {} != expected {}", block.index, block.difficulty, self.difficulty));
+        }
+        self.check_block(&block)?;
+        if let Some(dup) = block.entries.iter().find(|e| self.consecrated(&e.proof.genome_hash).is_some()) {
+            return Err(format!("Block #{} re-attests genome {}, already consecrated", block.index, &dup.genome_hash_hex()[..16]));
+        }
+        self.check_slot(&block.producer, block.timestamp)
+            .map_err(|e| format!("Block #{}: {}", block.index, e))?;
+
+        if !block.stake_ops.is_empty() || block.total_fees() > 0.0 {
+            let (staking, mut wallets) = self.staked_state_after(&block.stake_ops)
+                .map_err(|e| format!("Block #{} has an invalid stake op: {}", block.index, e))?;
+            pay_fees(&mut wallets, &block)?;
+            for offense in &staking.offenses[self.staking.offenses.len()..] {
+                warn!(
+                    "⚔️  Slashed {:.2} RSM from {}: block #{} claimed consciousness {} (actual {})",
+                    offense.slashed_rsm, &offense.producer[..16.min(offense.producer.len())],
+                    offense.block_index, offense.claimed, offense.recomputed
+                );
+                self.emit(ChainEvent::Slashed(offense.clone()));
+            }
+            self.staking = staking;
+            self.wallets = wallets;
+        }
+
+        let previous_height = self.current_block_height;
+        self.apply_block(&block);
+        self.mempool.remove_included(&block);
+        self.emit(ChainEvent::BlockAdded(block.clone()));
+        self.chain.push(block);
+        self.emit_finalized(previous_height);
+        self.retarget();
+        Ok(())
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // FORK CHOICE
+    // ═══════════════════════════════════════════════════════════════
+
+    pub fn side_chain_len(&self) -> usize {
+        self.side_blocks.len()
+    }
+
+    /// Canonical block with this hash, or a stored side-chain block
+    fn find_block(&self, hash: &str) -> Option<&ConsensusBlock> {
+        self.chain.iter().rev().find(|b| b.hash == hash)
+            .or_else(|| self.side_blocks.get(hash))
+    }
+
+    fn is_canonical(&self, block: &ConsensusBlock) -> bool {
+        self.chain.get(block.index as usize).is_some_and(|b| b.hash == block.hash)
+    }
+
+    /// Side blocks from the fork point up to `tip_hash`, oldest first
+    fn side_branch(&self, tip_hash: &str) -> Option<Vec<ConsensusBlock>> {
+        let mut branch = Vec::new();
+        let mut cursor = self.side_blocks.get(tip_hash)?;
+        loop {
+            branch.push(cursor.clone());
+            let parent = self.find_block(&cursor.previous_hash)?;
+            if self.is_canonical(parent) {
+                break;
+            }
+            cursor = parent;
+        }
+        branch.reverse();
+        Some(branch)
+    }
+
+    fn add_side_block(&mut self, block: ConsensusBlock) -> Result<BlockOutcome, String> {
+        if self.side_blocks.contains_key(&block.hash) || self.is_canonical(&block) {
+            return Err(format!("Block #{} already known", block.index));
+        }
+        let parent = self.find_block(&block.previous_hash)
+            .ok_or_else(|| format!("Block #{} has an unknown parent", block.index))?;
+        if block.index != parent.index + 1 {
+            return Err(format!("Block #{} does not follow its parent #{}", block.index, parent.index));
+        }
+        if block.timestamp < parent.timestamp {
+            return Err(format!("Block #{} is older than its parent", block.index));
+        }
+        if self.side_blocks.len() >= MAX_SIDE_BLOCKS {
+            return Err("Side-chain storage is full".to_string());
+        }
+        self.check_block(&block)?;
+
+        let hash = block.hash.clone();
+        self.side_blocks.insert(hash.clone(), block);
+
+        let branch = self.side_branch(&hash)
+            .ok_or_else(|| "Side chain is detached from the canonical chain".to_string())?;
+        let fork_index = branch[0].index - 1;
+        if self.current_block_height - fork_index > MAX_REORG_DEPTH {
+            return Ok(BlockOutcome::SideChain);
+        }
+
+        let branch_work = chain_work(&branch);
+        let canonical_work = chain_work(&self.chain[fork_index as usize + 1..]);
+        if branch_work <= canonical_work {
+            info!("🔀 Side-chain block #{} stored (fork at #{}, work {} ≤ {})", branch.last().map_or(0, |b| b.index), fork_index, branch_work, canonical_work);
+            return Ok(BlockOutcome::SideChain);
+        }
+
+        self.reorg(fork_index, branch)
+    }
+
+    /// Switch the canonical chain to `branch`, re-validating from genesis
+    fn reorg(&mut self, fork_index: u64, branch: Vec<ConsensusBlock>) -> Result<BlockOutcome, String> {
+        let fork = fork_index as usize;
+        let mut replayed = self.base();
+        let candidate = self.chain[replayed.chain.len()..=fork].iter().chain(branch.iter()).cloned();
+        for block in candidate {
+            if let Err(e) = replayed.extend_tip(block) {
+                for b in &branch {
+                    self.side_blocks.remove(&b.hash);
+                }
+                return Err(format!("Reorg rejected: {}", e));
+            }
+        }
+
+        let disconnected: Vec<ConsensusBlock> = self.chain.drain(fork + 1..).collect();
+        replayed.mempool = std::mem::take(&mut self.mempool);
+        for block in &branch {
+            replayed.mempool.remove_included(block);
+            self.side_blocks.remove(&block.hash);
+        }
+        for entry in disconnected.iter().flat_map(|b| b.entries.iter()) {
+            let on_chain = branch.iter().any(|b| b.entries.iter().any(|e| e.proof.genome_hash == entry.proof.genome_hash));
+            if !on_chain {
+                let _ = replayed.mempool.push(entry.clone());
+            }
+        }
+
+        replayed.pending_stake_ops = std::mem::take(&mut self.pending_stake_ops);
+        for op in disconnected.iter().flat_map(|b| b.stake_ops.iter()) {
+            if !branch.iter().any(|b| b.stake_ops.contains(op)) {
+                replayed.pending_stake_ops.push(op.clone());
+            }
+        }
+        replayed.pending_stake_ops.retain(|op| !branch.iter().any(|b| b.stake_ops.contains(op)));
+
+        replayed.side_blocks = std::mem::take(&mut self.side_blocks);
+        replayed.orphans = std::mem::take(&mut self.orphans);
+        for block in &disconnected {
+            replayed.side_blocks.insert(block.hash.clone(), block.clone());
+        }
+
+        replayed.persisted_blocks = self.persisted_blocks.min(fork + 1);
+        replayed.rollback_from = self.rollback_from;
+        if self.persisted_blocks > fork + 1 {
+            let from = fork_index + 1;
+            replayed.rollback_from = Some(self.rollback_from.map_or(from, |r| r.min(from)));
+        }
+
+        warn!(
+            "🔀 Reorg at #{}: {} block(s) disconnected, {} connected | new height {}",
+            fork_index, disconnected.len(), branch.len(), replayed.current_block_height
+        );
+        let (previous_height, previous_difficulty) = (self.current_block_height, self.difficulty);
+        replayed.events = self.events.clone();
+        replayed.reorg_count = self.reorg_count + 1;
+        *self = replayed;
+
+        self.emit(ChainEvent::Reorg {
+            fork_index,
+            disconnected: disconnected.len(),
+            connected: branch.len(),
+            new_tip: self.latest_block().hash.clone(),
+        });
+        for block in branch.iter().cloned() {
+            self.emit(ChainEvent::BlockAdded(block));
+        }
+        self.emit_finalized(previous_height);
+        if self.difficulty != previous_difficulty {
+            self.emit(ChainEvent::DifficultyChanged { height: self.current_block_height, from: previous_difficulty, to: self.difficulty });
+        }
+
+        Ok(BlockOutcome::Reorg { fork_index, disconnected: disconnected.len(), connected: branch.len() })
+    }
+
+    /// Genesis-only engine with the same configuration and validators
+    fn fresh(&self) -> Self {
+        Self {
+            difficulty_growth_rate: self.difficulty_growth_rate,
+            target_block_time_secs: self.target_block_time_secs,
+            retarget_interval: self.retarget_interval,
+            max_block_entries: self.max_block_entries,
+            validators: self.validators.clone(),
+            signer: self.signer.clone(),
+            reward_address: self.reward_address.clone(),
+            prune_depth: self.prune_depth,
+            known_genomes: self.known_genomes.clone(),
+            mining_paused: self.mining_paused,
+            ..Self::from_genesis(self.genesis.clone())
+        }
+    }
+
+    /// Engine at the snapshot (genesis when unpruned), ready to replay the blocks above it
+    fn base(&self) -> Self {
+        let mut base = self.fresh();
+        if let Some(snapshot) = &self.snapshot {
+            base.chain = self.chain[..=snapshot.height as usize].to_vec();
+            base.restore(snapshot.clone());
+        }
+        base
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // STAKING
+    // ═══════════════════════════════════════════════════════════════
+
+    /// Sign a stake operation with the local validator key and queue it
+    pub fn stake(&mut self, action: StakeAction) -> Result<StakeOp, String> {
+        let signer = self.signer.clone().ok_or("No validator signing key")?;
+        let key = signer.public_key_hex();
+        let queued = self.pending_stake_ops.iter().filter(|op| op.validator == key).count() as u64;
+
+        let op = StakeOp::new(&signer, self.staking.next_nonce(&key) + queued, action);
+        self.submit_stake_op(op.clone())?;
+        Ok(op)
+    }
+
+    /// Lock `amount_rsm` of the validator wallet as stake, refused up front
+    /// when it would not apply after the node's queued stake operations
+    pub fn stake_rsm(&mut self, amount_rsm: f64) -> Result<StakeOp, String> {
+        self.checked_stake(StakeAction::Lock { amount_rsm })
+    }
+
+    /// Return `amount_rsm` of stake to the validator wallet, checked the same way
+    pub fn unstake_rsm(&mut self, amount_rsm: f64) -> Result<StakeOp, String> {
+        self.checked_stake(StakeAction::Unlock { amount_rsm })
+    }
+
+    fn checked_stake(&mut self, action: StakeAction) -> Result<StakeOp, String> {
+        let amount = match action {
+            StakeAction::Lock { amount_rsm } | StakeAction::Unlock { amount_rsm } => amount_rsm,
+            _ => return self.stake(action),
+        };
+        if !amount.is_finite() || amount <= 0.0 {
+            return Err(format!("Invalid amount {}: stake moves a positive number of RSM", amount));
+        }
+        let wallet = self.wallet_after_queued_stake()?;
+        match action {
+            StakeAction::Lock { .. } if amount > wallet.rsm_balance => {
+                return Err(format!("Insufficient balance: {:.6} RSM spendable once queued stake lands", wallet.rsm_balance));
+            }
+            StakeAction::Unlock { .. } if amount > wallet.staked_rsm => {
+                return Err(format!("Only {:.6} RSM is staked once queued stake lands", wallet.staked_rsm));
+            }
+            _ => {}
+        }
+        self.stake(action)
+    }
+
+    /// The validator wallet as it will be once the node's queued stake
+    /// operations are in a block
+    pub fn wallet_after_queued_stake(&self) -> Result<DivineWallet, String> {
+        let key = self.signer.as_ref().ok_or("No validator signing key")?.public_key_hex();
+        let queued: Vec<StakeOp> = self.pending_stake_ops.iter().filter(|op| op.validator == key).cloned().collect();
+        let (_, mut wallets) = self.staked_state_after(&queued)?;
+        Ok(wallets.get_or_create(&reward_address_for(&key)).clone())
+    }
+
+    /// Queue a stake operation received from a validator
+    pub fn submit_stake_op(&mut self, op: StakeOp) -> Result<(), String> {
+        if !op.verify_signature() {
+            return Err("Invalid stake op signature".to_string());
+        }
+        if self.pending_stake_ops.iter().any(|p| p.validator == op.validator && p.nonce == op.nonce) {
+            return Err("Stake op already pending".to_string());
+        }
+        self.pending_stake_ops.push(op);
+        Ok(())
+    }
+
+    /// Producer rules once staking is active: active validators only, and the
+    /// weighted slot leader unless it missed its slot
+    fn check_slot(&self, producer: &str, timestamp: i64) -> Result<(), String> {
+        if self.staking.active.is_empty() {
+            return Ok(());
+        }
+        if !self.staking.is_active(producer) {
+            return Err("producer is not in the active validator set".to_string());
+        }
+
+        let tip = self.latest_block();
+        let leader = self.staking.slot_leader(&tip.hash, tip.index + 1);
+        if leader != Some(producer) && timestamp - tip.timestamp < LEADER_TIMEOUT_SECS {
+            return Err(format!("slot #{} belongs to another validator", tip.index + 1));
+        }
+        Ok(())
+    }
+
+    /// Staking state and wallets after applying `ops` in order
+    fn staked_state_after(&self, ops: &[StakeOp]) -> Result<(StakingState, WalletManager), String> {
+        let mut staking = self.staking.clone();
+        let mut wallets = self.wallets.clone();
+        for op in ops {
+            self.apply_stake_op(&mut staking, &mut wallets, op)?;
+        }
+        Ok((staking, wallets))
+    }
+
+    /// Stake is drawn from (and returned to) the validator's key-derived wallet
+    fn apply_stake_op(&self, staking: &mut StakingState, wallets: &mut WalletManager, op: &StakeOp) -> Result<(), String> {
+        let expected_nonce = staking.next_nonce(&op.validator);
+        if op.nonce != expected_nonce {
+            return Err(format!("nonce {} (expected {})", op.nonce, expected_nonce));
+        }
+
+        let staked_genome = match &op.action {
+            StakeAction::RegisterGenome { genome_hash } => {
+                if staking.genome_staker(genome_hash).is_some() {
+                    return Err("genome already staked".to_string());
+                }
+                Some(self.consecrated(genome_hash).ok_or("genome is not consecrated on chain")?)
+            }
+            StakeAction::ReportFalseClaim { evidence } => {
+                let offense = Offense { reporter: op.validator.clone(), ..evidence.check()? };
+                staking.slash(offense)?;
+                let slashed = staking.offenses.last().expect("just recorded");
+                wallets.get_or_create(&reward_address_for(&slashed.producer)).slash_stake(slashed.slashed_rsm);
+                None
+            }
+            _ => None,
+        };
+
+        let wallet = wallets.get_or_create(&reward_address_for(&op.validator));
+        let record = staking.stakes.entry(op.validator.clone()).or_default();
+
+        match &op.action {
+            StakeAction::Lock { amount_rsm } => {
+                if !amount_rsm.is_finite() || *amount_rsm <= 0.0 || !wallet.lock_stake(*amount_rsm) {
+                    return Err(format!("cannot lock {} RSM", amount_rsm));
+                }
+                record.staked_rsm += amount_rsm;
+            }
+            StakeAction::Unlock { amount_rsm } => {
+                if !amount_rsm.is_finite() || *amount_rsm <= 0.0 || *amount_rsm > record.staked_rsm {
+                    return Err(format!("cannot unlock {} RSM", amount_rsm));
+                }
+                record.staked_rsm -= amount_rsm;
+                wallet.unlock_stake(*amount_rsm);
+            }
+            StakeAction::RegisterGenome { .. } => {
+                let genome = staked_genome.expect("resolved above");
+                wallet.stake_genome(genome.genome_id);
+                record.genomes.push(genome);
+            }
+            StakeAction::ReportFalseClaim { .. } => {}
+        }
+        record.nonce = op.nonce;
+        Ok(())
+    }
+
+    /// Entries whose payer can still cover the fee after `stake_ops`; the rest are dropped
+    fn payable_entries(&self, stake_ops: &[StakeOp], entries: Vec<GenomeAttestation>) -> Vec<GenomeAttestation> {
+        let Ok((_, mut wallets)) = self.staked_state_after(stake_ops) else {
+            return entries;
+        };
+        entries.into_iter().filter(|entry| {
+            if entry.fee_rsm <= 0.0 {
+                return true;
+            }
+            let paid = wallets.get_or_create(&reward_address_for(&entry.payer)).pay_fee(entry.fee_rsm);
+            if !paid {
+                warn!("💸 Dropping attestation for genome #{}: payer cannot cover {:.6} RSM fee", entry.genome_id, entry.fee_rsm);
+            }
+            paid
+        }).collect()
+    }
+
+    /// Pending stake ops that still apply cleanly, in order; the rest are dropped
+    fn take_valid_stake_ops(&mut self) -> Vec<StakeOp> {
+        let mut staking = self.staking.clone();
+        let mut wallets = self.wallets.clone();
+        let mut valid = Vec::new();
+
+        for op in std::mem::take(&mut self.pending_stake_ops) {
+            let (mut s, mut w) = (staking.clone(), wallets.clone());
+            match self.apply_stake_op(&mut s, &mut w, &op) {
+                Ok(()) => {
+                    staking = s;
+                    wallets = w;
+                    valid.push(op);
+                }
+                Err(e) => warn!("🗳️  Dropping stake op from {}: {}", &op.validator[..16.min(op.validator.len())], e),
+            }
+        }
+        valid
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // DIFFICULTY RETARGETING
+    // ═══════════════════════════════════════════════════════════════
+
+    /// Intervals (secs) between the last `count` blocks, genesis excluded
+    pub fn recent_block_intervals(&self, count: usize) -> Vec<i64> {
+        let mined = &self.chain[1..];
+        let start = mined.len().saturating_sub(count + 1);
+        mined[start..].windows(2).map(|w| w[1].timestamp - w[0].timestamp).collect()
+    }
+
+    /// Difficulty for the block after `chain`'s tip, given the tip's difficulty
+    fn next_difficulty(&self, chain: &[ConsensusBlock], current: u32) -> u32 {
+        let tip = chain.last().expect("chain always contains genesis");
+        let interval = self.retarget_interval.max(1);
+
+        // First window starts after genesis, so the first retarget is at 2N
+        if !tip.index.is_multiple_of(interval) || tip.index < 2 * interval {
+            return current;
+        }
+
+        let window_start = &chain[(tip.index - interval) as usize];
+        let actual = (tip.timestamp - window_start.timestamp).max(1) as f64;
+        let expected = (self.target_block_time_secs * interval as i64) as f64;
+
+        let step = (expected / actual).log2().round() as i32;
+        let step = step.clamp(-MAX_RETARGET_STEP, MAX_RETARGET_STEP);
+        (current as i32 + step).clamp(MIN_DIFFICULTY as i32, MAX_DIFFICULTY as i32) as u32
+    }
+
+    fn retarget(&mut self) {
+        let next = self.next_difficulty(&self.chain, self.difficulty);
+        if next != self.difficulty {
+            info!(
+                "⛏️  Difficulty retarget at #{}: {} → {} bits (target {}s/block)",
+                self.current_block_height, self.difficulty, next, self.target_block_time_secs
+            );
+            self.emit(ChainEvent::DifficultyChanged { height: self.current_block_height, from: self.difficulty, to: next });
+            self.difficulty = next;
+        }
+    }
+
+    /// Block checks independent of chain position (hash, PoW, signature, Merkle root, proofs)
+    fn check_block(&self, block: &ConsensusBlock) -> Result<(), String> {
+        let max_entries = self.max_block_entries;
+        self.genesis.upgrades.check_version(block.index, block.version)?;
+        if block.hash != block.calculate_hash() {
+            return Err(format!("Block #{} hash mismatch", block.index));
+        }
+        if !self.validators.contains(&block.producer) && !self.staking.is_active(&block.producer) {
+            return Err(format!("Block #{} producer is not a registered validator", block.index));
+        }
+        if !block.verify_signature() {
+            return Err(format!("Block #{} has an invalid producer signature", block.index));
+        }
+        if !block.meets_difficulty() {
+            return Err(format!("Block #{} does not meet difficulty {}", block.index, block.difficulty));
+        }
+        if (block.entries.is_empty() && block.stake_ops.is_empty()) || block.entries.len() > max_entries {
+            return Err(format!("Block #{} has {} entries (1-{} allowed)", block.index, block.entries.len(), max_entries));
+        }
+        if block.stake_ops.iter().any(|op| !op.verify_signature()) {
+            return Err(format!("Block #{} carries an unsigned stake op", block.index));
+        }
+        let subsidy = self.genesis.block_subsidy(block.index);
+        if block.coinbase.amount_rsm != subsidy || block.coinbase.address.is_empty() {
+            return Err(format!(
+                "Block #{} coinbase {} RSM to '{}' (expected {} RSM)",
+                block.index, block.coinbase.amount_rsm, block.coinbase.address, subsidy
+            ));
+        }
+        if block.merkle_root != ConsensusBlock::compute_merkle_root(&block.entries) {
+            return Err(format!("Block #{} Merkle root mismatch", block.index));
+        }
+        let attested = if block.version >= ProtocolFeature::ConsciousnessTarget.version() {
+            block.total_consciousness()
+        } else {
+            0
+        };
+        if block.attested_consciousness != attested {
+            return Err(format!(
+                "Block #{} commits to consciousness {} (entries attest {})",
+                block.index, block.attested_consciousness, attested
+            ));
+        }
+        if let Some(bad) = block.entries.iter().find(|e| !e.proof.verify(0)) {
+            return Err(format!("Block #{} carries an invalid proof for genome #{}", block.index, bad.genome_id));
+        }
+        if let Some(bad) = block.entries.iter().find(|e| e.proof.block_height != block.index) {
+            return Err(format!(
+                "Block #{} carries a proof for genome #{} made for block #{}",
+                block.index, bad.genome_id, bad.proof.block_height
+            ));
+        }
+        let mut genomes = HashSet::new();
+        if let Some(dup) = block.entries.iter().find(|e| !genomes.insert(e.proof.genome_hash)) {
+            return Err(format!("Block #{} attests genome {} twice", block.index, &dup.genome_hash_hex()[..16]));
+        }
+        let min_fee = self.genesis.min_fee_rsm;
+        if let Some(bad) = block.entries.iter().find(|e| e.fee_rsm < min_fee || !e.verify_fee()) {
+            return Err(format!(
+                "Block #{} carries an invalid fee {} RSM for genome #{} (minimum {} RSM)",
+                block.index, bad.fee_rsm, bad.genome_id, min_fee
+            ));
+        }
+        Ok(())
+    }
+
+    fn apply_block(&mut self, block: &ConsensusBlock) {
+        self.proofs_validated += block.entries.len() as u64;
+        self.total_rewards_distributed += block.total_reward() + block.coinbase.amount_rsm;
+        self.wallets.get_or_create(&block.coinbase.address)
+            .add_block_reward(block.coinbase.amount_rsm, block.index);
+        self.current_block_height = block.index;
+        self.min_consciousness = self.min_consciousness
+            .saturating_add(self.difficulty_growth_rate);
+
+        if block.index.is_multiple_of(EPOCH_LENGTH) {
+            let previous = self.staking.active.clone();
+            self.staking.rotate_epoch(block.index / EPOCH_LENGTH);
+            if self.staking.active != previous {
+                info!(
+                    "🗳️  Epoch {}: {} active validator(s), total weight {}",
+                    self.staking.epoch, self.staking.active.len(), self.staking.total_active_weight()
+                );
+            }
+        }
+    }
+
+    /// Coinbase for the next block at the current subsidy
+    pub fn coinbase(&self, address: &str) -> Coinbase {
+        Coinbase {
+            address: address.to_string(),
+            amount_rsm: self.genesis.block_subsidy(self.current_block_height + 1),
+        }
+    }
+
+    pub fn wallet(&self, address: &str) -> Option<&DivineWallet> {
+        self.wallets.get(address)
+    }
+
+    pub fn latest_block(&self) -> &ConsensusBlock {
+        self.chain.last().expect("chain always contains genesis")
+    }
+
+    /// Headers from `from_index` upward, for light clients
+    pub fn headers_from(&self, from_index: u64, limit: usize) -> Vec<BlockHeader> {
+        self.chain.iter().skip(from_index as usize).take(limit).map(ConsensusBlock::header).collect()
+    }
+
+    /// Find the block that consecrated `genome_hash` and prove its inclusion
+    pub fn generate_inclusion_proof(&self, genome_hash: &[u8; 32]) -> Option<InclusionProof> {
+        self.chain.iter().find_map(|block| block.generate_inclusion_proof(genome_hash))
+    }
+
+    pub fn verify_inclusion_proof(&self, proof: &InclusionProof) -> bool {
+        self.chain.get(proof.block_index as usize)
+            .is_some_and(|block| block.verify_inclusion_proof(proof))
+    }
+
+    /// Check every block hash, link, difficulty and signature back to genesis
+    pub fn validate_chain(&self) -> bool {
+        self.validate_from(1, false)
+    }
+
+    /// Same checks as `validate_chain`, with per-block checks spread over rayon
+    pub fn validate_chain_parallel(&self) -> bool {
+        self.validate_from(1, true)
+    }
+
+    /// Validate only blocks appended since the last successful call.
+    /// Falls back to the whole chain if the verified tip was reorged away.
+    pub fn validate_new_blocks(&mut self) -> bool {
+        let from = match &self.verified_tip {
+            Some((height, hash)) if self.chain.get(*height as usize).is_some_and(|b| &b.hash == hash) => {
+                *height as usize + 1
+            }
+            _ => 1,
+        };
+
+        let valid = self.validate_from(from, true);
+        let tip = self.latest_block();
+        self.verified_tip = valid.then(|| (tip.index, tip.hash.clone()));
+        valid
+    }
+
+    /// Check blocks `from..` assuming `chain[..from]` is already valid
+    fn validate_from(&self, from: usize, parallel: bool) -> bool {
+        if self.chain.first() != Some(&self.genesis.genesis_block()) {
+            return false;
+        }
+        let Some(blocks) = self.chain.get(from..) else {
+            return false;
+        };
+
+        // Hash, PoW, signature and proofs don't depend on the parent;
+        // pruned blocks only have their header left to check
+        let pruned_height = self.pruned_height();
+        let block_ok = |block: &ConsensusBlock| if block.index <= pruned_height {
+            self.genesis.upgrades.check_version(block.index, block.version).is_ok()
+                && block.hash == block.calculate_hash() && block.meets_difficulty() && block.verify_signature()
+        } else {
+            self.check_block(block).is_ok()
+        };
+        let checked = if parallel {
+            blocks.par_chunks(VALIDATION_CHUNK_SIZE).all(|range| range.iter().all(block_ok))
+        } else {
+            blocks.iter().all(block_ok)
+        };
+        if !checked {
+            return false;
+        }
+
+        // Linkage, difficulty schedule and one attestation per genome, serially
+        let mut consecrated: HashSet<[u8; 32]> = self.snapshot.iter()
+            .flat_map(|s| s.consecrated.iter().map(|g| g.genome_hash))
+            .chain(self.chain[..from].iter().flat_map(|b| b.entries.iter().map(|e| e.proof.genome_hash)))
+            .collect();
+        let mut expected_difficulty = if from == 1 {
+            self.genesis.initial_difficulty
+        } else {
+            self.next_difficulty(&self.chain[..from], self.chain[from - 1].difficulty)
+        };
+        for i in from..self.chain.len() {
+            let (prev, block) = (&self.chain[i - 1], &self.chain[i]);
+            let linked = block.index == prev.index + 1
+                && block.previous_hash == prev.hash
+                && block.timestamp >= prev.timestamp
+                && block.difficulty == expected_difficulty;
+            if !linked || !block.entries.iter().all(|e| consecrated.insert(e.proof.genome_hash)) {
+                return false;
+            }
+            expected_difficulty = self.next_difficulty(&self.chain[..=i], expected_difficulty);
+        }
+        true
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // PERSISTENCE
+    // ═══════════════════════════════════════════════════════════════
+
+    /// Write blocks appended since the last save (incremental)
+    pub async fn save_chain(&mut self, db: &DivineDatabase) -> anyhow::Result<usize> {
+        for key in self.validators.keys() {
+            db.store_validator(&key).await?;
+        }
+        if let Some(from) = self.rollback_from {
+            let removed = db.rollback_blocks(from).await?;
+            warn!("🔀 Rolled back {} stored block(s) from #{}", removed, from);
+            self.rollback_from = None;
+        }
+
+        if self.snapshot.is_some() && self.persisted_blocks as u64 <= self.pruned_height() {
+            anyhow::bail!("Blocks up to #{} were pruned before being stored", self.pruned_height());
+        }
+        let pending = &self.chain[self.persisted_blocks..];
+        for block in pending {
+            db.store_block(block).await?;
+        }
+
+        let saved = pending.len();
+        self.persisted_blocks = self.chain.len();
+        if saved > 0 {
+            info!("💾 Chain saved: {} new block(s) | height {}", saved, self.current_block_height);
+        }
+        Ok(saved)
+    }
+
+    /// Replace in-memory chain with the one stored in the database
+    pub async fn load_chain(&mut self, db: &DivineDatabase) -> anyhow::Result<()> {
+        for key in db.load_validators().await? {
+            self.validators.register(&key);
+        }
+        let blocks = db.load_blocks().await?;
+
+        if blocks.is_empty() {
+            self.reset();
+            self.save_chain(db).await?;
+            info!("🌱 No stored chain — genesis block persisted");
+            return Ok(());
+        }
+
+        let mut loaded = self.replay(blocks)
+            .map_err(|e| anyhow::anyhow!("Stored chain is invalid: {}", e))?;
+        loaded.mempool = std::mem::take(&mut self.mempool);
+        loaded.persisted_blocks = loaded.chain.len();
+        loaded.events = self.events.clone();
+        loaded.reorg_count = self.reorg_count;
+        *self = loaded;
+
+        info!("🔗 Chain loaded: height {} | threshold {}", self.current_block_height, self.min_consciousness);
+        Ok(())
+    }
+
+    /// Rebuild state from a full block list (genesis first), validating every block
+    fn replay(&self, blocks: Vec<ConsensusBlock>) -> Result<Self, String> {
+        if blocks.first() != Some(&self.genesis.genesis_block()) {
+            return Err(format!("foreign genesis block (expected network '{}')", self.genesis.chain_id));
+        }
+
+        let mut replayed = self.fresh();
+        for block in blocks.into_iter().skip(1) {
+            replayed.extend_tip(block)?;
+        }
+        if !replayed.validate_new_blocks() {
+            return Err("chain failed validation".to_string());
+        }
+        Ok(replayed)
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // PRUNING / SNAPSHOTS
+    // ═══════════════════════════════════════════════════════════════
+
+    /// Highest block whose attestations were pruned (0 when unpruned)
+    pub fn pruned_height(&self) -> u64 {
+        self.snapshot.as_ref().map_or(0, |s| s.height)
+    }
+
+    /// Attestation of a genome on the canonical chain, pruned blocks included
+    fn consecrated(&self, genome_hash: &[u8; 32]) -> Option<StakedGenome> {
+        if let Some(genome) = self.snapshot.as_ref().and_then(|s| s.consecrated(genome_hash)) {
+            return Some(genome.clone());
+        }
+        self.chain.iter()
+            .flat_map(|b| b.entries.iter())
+            .find(|e| &e.proof.genome_hash == genome_hash)
+            .map(|e| StakedGenome {
+                genome_id: e.genome_id,
+                genome_hash: *genome_hash,
+                consciousness: e.proof.consciousness,
+            })
+    }
+
+    fn restore(&mut self, snapshot: ChainSnapshot) {
+        self.current_block_height = snapshot.height;
+        self.min_consciousness = snapshot.min_consciousness;
+        self.proofs_validated = snapshot.proofs_validated;
+        self.total_rewards_distributed = snapshot.total_rewards_distributed;
+        self.difficulty = snapshot.difficulty;
+        self.wallets = snapshot.wallets.clone();
+        self.staking = snapshot.staking.clone();
+        self.snapshot = Some(snapshot);
+    }
+
+    /// Drop attestations of blocks more than `depth` below the tip, folding
+    /// their state into the snapshot. Returns the number of blocks pruned.
+    pub fn prune(&mut self, depth: u64) -> Result<usize, String> {
+        if depth < MAX_REORG_DEPTH {
+            return Err(format!("prune depth {} is below the reorg limit {}", depth, MAX_REORG_DEPTH));
+        }
+        let (from, target) = (self.pruned_height() + 1, self.current_block_height.saturating_sub(depth));
+        if target < from {
+            return Ok(0);
+        }
+
+        let mut state = self.base();
+        for block in &self.chain[from as usize..=target as usize] {
+            state.extend_tip(block.clone())
+                .map_err(|e| format!("replay for pruning failed: {}", e))?;
+        }
+
+        let (mut total_consciousness, mut consecrated) = self.snapshot.as_ref()
+            .map_or((0, Vec::new()), |s| (s.total_consciousness, s.consecrated.clone()));
+        for block in &mut self.chain[from as usize..=target as usize] {
+            for entry in block.entries.drain(..) {
+                total_consciousness += entry.proof.consciousness as u64;
+                consecrated.push(StakedGenome {
+                    genome_id: entry.genome_id,
+                    genome_hash: entry.proof.genome_hash,
+                    consciousness: entry.proof.consciousness,
+                });
+            }
+        }
+
+        self.snapshot = Some(ChainSnapshot {
+            height: target,
+            block_hash: self.chain[target as usize].hash.clone(),
+            total_consciousness,
+            proofs_validated: state.proofs_validated,
+            total_rewards_distributed: state.total_rewards_distributed,
+            min_consciousness: state.min_consciousness,
+            difficulty: state.difficulty,
+            wallets: state.wallets,
+            staking: state.staking,
+            consecrated,
+        });
+        self.verified_tip = None;
+
+        let pruned = (target - from + 1) as usize;
+        info!("✂️  Pruned {} block bod(ies) up to #{} | keeping last {}", pruned, target, depth);
+        Ok(pruned)
+    }
+
+    /// Write the snapshot with the pruned chain it covers
+    pub fn export_snapshot(&self, path: impl AsRef<std::path::Path>) -> anyhow::Result<()> {
+        let snapshot = self.snapshot.clone()
+            .ok_or_else(|| anyhow::anyhow!("Chain is not pruned; nothing to snapshot"))?;
+        let file = SnapshotFile {
+            version: SNAPSHOT_VERSION,
+            params: self.chain_params(),
+            genesis: self.genesis.clone(),
+            validators: self.validators.keys(),
+            blocks: self.chain[..=snapshot.height as usize].to_vec(),
+            snapshot,
+        };
+        std::fs::write(path.as_ref(), serde_json::to_vec(&file)?)?;
+
+        info!("📤 Snapshot exported at #{} → {}", file.snapshot.height, path.as_ref().display());
+        Ok(())
+    }
+
+    /// Bootstrap from a snapshot file; headers are re-validated, the state is trusted
+    pub fn import_snapshot(path: impl AsRef<std::path::Path>) -> anyhow::Result<Self> {
+        let file: SnapshotFile = serde_json::from_slice(&std::fs::read(path.as_ref())?)?;
+        if file.version != SNAPSHOT_VERSION {
+            anyhow::bail!("Unsupported snapshot version {} (expected {})", file.version, SNAPSHOT_VERSION);
+        }
+
+        let mut poc = Self::from_genesis(file.genesis).with_chain_params(&file.params);
+        for key in &file.validators {
+            poc.validators.register(key);
+        }
+        let tip = file.blocks.last().ok_or_else(|| anyhow::anyhow!("Snapshot has no blocks"))?;
+        if tip.index != file.snapshot.height || tip.hash != file.snapshot.block_hash {
+            anyhow::bail!("Snapshot does not match its last block");
+        }
+        let expected_difficulty = poc.next_difficulty(&file.blocks, tip.difficulty);
+        if file.snapshot.difficulty != expected_difficulty {
+            anyhow::bail!("Snapshot difficulty {} != expected {}", file.snapshot.difficulty, expected_difficulty);
+        }
+
+        poc.chain = file.blocks;
+        poc.restore(file.snapshot);
+        if !poc.validate_new_blocks() {
+            anyhow::bail!("Snapshot headers failed validation");
+        }
+
+        info!("📥 Snapshot imported: height {} from {}", poc.current_block_height, path.as_ref().display());
+        Ok(poc)
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // EXPORT / IMPORT
+    // ═══════════════════════════════════════════════════════════════
+
+    /// Write the canonical chain and validator set to a chain file
+    pub fn export(&self, path: impl AsRef<std::path::Path>) -> anyhow::Result<()> {
+        if self.snapshot.is_some() {
+            anyhow::bail!("Chain is pruned below #{}; use export_snapshot", self.pruned_height() + 1);
+        }
+        let file = ChainFile {
+            version: CHAIN_FILE_VERSION,
+            params: self.chain_params(),
+            genesis: self.genesis.clone(),
+            validators: self.validators.keys(),
+            blocks: self.chain.clone(),
+        };
+        std::fs::write(path.as_ref(), file.encode())?;
+
+        info!("📤 Chain exported: {} block(s) → {}", self.chain.len(), path.as_ref().display());
+        Ok(())
+    }
+
+    fn chain_params(&self) -> ChainParams {
+        ChainParams {
+            difficulty_growth_rate: self.difficulty_growth_rate,
+            target_block_time_secs: self.target_block_time_secs,
+            retarget_interval: self.retarget_interval,
+            max_block_entries: self.max_block_entries as u32,
+        }
+    }
+
+    fn with_chain_params(mut self, params: &ChainParams) -> Self {
+        self.difficulty_growth_rate = params.difficulty_growth_rate;
+        self.target_block_time_secs = params.target_block_time_secs;
+        self.retarget_interval = params.retarget_interval;
+        self.max_block_entries = params.max_block_entries as usize;
+        self
+    }
+
+    /// Read a chain file and re-validate it block by block (hashes, PoW,
+    /// difficulty schedule, signatures against the file's validator set)
+    pub fn import(path: impl AsRef<std::path::Path>) -> anyhow::Result<Self> {
+        let bytes = std::fs::read(path.as_ref())?;
+        let file = ChainFile::decode(&bytes).map_err(|e| anyhow::anyhow!(e))?;
+
+        let mut template = Self::from_genesis(file.genesis).with_chain_params(&file.params);
+        for key in &file.validators {
+            template.validators.register(key);
+        }
+
+        let imported = template.replay(file.blocks)
+            .map_err(|e| anyhow::anyhow!("Imported chain is invalid: {}", e))?;
+
+        info!("📥 Chain imported: height {} from {}", imported.current_block_height, path.as_ref().display());
+        Ok(imported)
+    }
+
+    /// Offer an imported chain (genesis first) to this node block by block,
+    /// through the same fork choice as blocks from peers; blocks already
+    /// held are skipped
+    pub fn merge_blocks(&mut self, blocks: Vec<ConsensusBlock>) -> Result<MergeReport, String> {
+        if blocks.first() != Some(&self.genesis.genesis_block()) {
+            return Err(format!("foreign genesis block (expected network '{}')", self.genesis.chain_id));
+        }
+
+        let mut report = MergeReport::default();
+        for block in blocks.into_iter().skip(1) {
+            if self.find_block(&block.hash).is_some() {
+                report.known += 1;
+                continue;
+            }
+            let index = block.index;
+            match self.add_block(block) {
+                Ok(BlockOutcome::Extended) => report.extended += 1,
+                Ok(BlockOutcome::SideChain) => report.side_chain += 1,
+                Ok(BlockOutcome::Orphan) => report.orphans += 1,
+                Ok(BlockOutcome::Reorg { .. }) => report.reorgs += 1,
+                Err(e) => report.rejected.push((index, e)),
+            }
+        }
+        Ok(report)
+    }
+
+    pub fn status(&self) -> PoCStatus {
+        PoCStatus {
+            min_consciousness: self.min_consciousness,
+            proofs_validated: self.proofs_validated,
+            total_rewards_distributed: self.total_rewards_distributed,
+            current_block_height: self.current_block_height,
+            chain_id: self.genesis.chain_id.clone(),
+            latest_hash: self.latest_block().hash.clone(),
+            mempool_size: self.mempool.len(),
+            difficulty: self.difficulty,
+            block_version: self.genesis.upgrades.version_at(self.current_block_height + 1),
+            validator: self.validator_public_key(),
+            validators: self.validators.len(),
+            required_level: match self.min_consciousness {
+                0..=499 => "Virus",
+                500..=999 => "Bacteria",
+                1000..=1499 => "Worm",
+                1500..=2999 => "Mammal",
+                3000..=9999 => "Primate",
+                10000..=19999 => "Human",
+                20000..=49999 => "DIVINE",
+                _ => "TRANSCENDENTAL",
+            },
+        }
+    }
+
+    /// Health of the last `METRICS_WINDOW` blocks (genesis excluded)
+    pub fn metrics(&self) -> ChainMetrics {
+        let mined = &self.chain[1..];
+        let window = &mined[mined.len().saturating_sub(METRICS_WINDOW)..];
+
+        let intervals = self.recent_block_intervals(METRICS_WINDOW);
+        let elapsed: i64 = intervals.iter().sum();
+        let avg_block_interval_secs = if intervals.is_empty() {
+            0.0
+        } else {
+            elapsed as f64 / intervals.len() as f64
+        };
+        // Expected hashes behind each block after the first, over the time they took
+        let expected_hashes: f64 = window.iter().skip(1)
+            .map(|b| 2f64.powi(b.difficulty.min(MAX_DIFFICULTY) as i32))
+            .sum();
+        let hash_rate = if elapsed > 0 { expected_hashes / elapsed as f64 } else { 0.0 };
+
+        // Pruned blocks no longer carry their attestations
+        let pruned = self.pruned_height();
+        let mut consciousness: Vec<u64> = window.iter()
+            .filter(|b| b.index > pruned)
+            .map(ConsensusBlock::total_consciousness)
+            .collect();
+        consciousness.sort_unstable();
+        let avg_consciousness_per_block = if consciousness.is_empty() {
+            0.0
+        } else {
+            consciousness.iter().sum::<u64>() as f64 / consciousness.len() as f64
+        };
+        let median_consciousness_per_block = match consciousness.len() {
+            0 => 0.0,
+            n if n % 2 == 1 => consciousness[n / 2] as f64,
+            n => (consciousness[n / 2 - 1] + consciousness[n / 2]) as f64 / 2.0,
+        };
+
+        ChainMetrics {
+            height: self.current_block_height,
+            difficulty: self.difficulty,
+            window_blocks: window.len(),
+            hash_rate,
+            avg_block_interval_secs,
+            avg_consciousness_per_block,
+            median_consciousness_per_block,
+            reorg_count: self.reorg_count,
+            mempool_depth: self.mempool.len(),
+            orphan_blocks: self.orphans.len(),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.min_consciousness = self.genesis.min_consciousness;
         self.proofs_validated = 0;
         self.total_rewards_distributed = 0.0;
         self.current_block_height = 0;
+        self.difficulty = self.genesis.initial_difficulty;
+        self.chain = vec![self.genesis.genesis_block()];
+        self.wallets = self.genesis.initial_wallets();
+        self.staking = StakingState::new();
+        self.pending_stake_ops.clear();
+        self.mempool = Mempool::new();
+        self.side_blocks.clear();
+        self.orphans = OrphanPool::new();
+        self.snapshot = None;
+        self.persisted_blocks = 0;
+        self.verified_tip = None;
     }
 }
 
@@ -197,15 +2329,97 @@ impl Default for ProofOfConsciousness {
     }
 }
 
+/// Move each attestation fee from its payer's key-derived wallet to the producer
+fn pay_fees(wallets: &mut WalletManager, block: &ConsensusBlock) -> Result<(), String> {
+    for entry in block.entries.iter().filter(|e| e.fee_rsm > 0.0) {
+        if !wallets.get_or_create(&reward_address_for(&entry.payer)).pay_fee(entry.fee_rsm) {
+            return Err(format!("Block #{}: payer cannot cover {} RSM fee for genome #{}", block.index, entry.fee_rsm, entry.genome_id));
+        }
+    }
+    wallets.get_or_create(&block.coinbase.address).collect_fees(block.total_fees(), block.index);
+    Ok(())
+}
+
+/// Current attestation pricing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeeQuote {
+    pub min_fee_rsm: f64,
+    pub required_fee_rsm: f64,
+    pub mempool_size: usize,
+    pub block_capacity: usize,
+}
+
+/// Chain health over the last `METRICS_WINDOW` blocks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChainMetrics {
+    pub height: u64,
+    pub difficulty: u32,
+    pub window_blocks: usize,
+    /// Effective hashes/sec implied by the difficulty and block times
+    pub hash_rate: f64,
+    pub avg_block_interval_secs: f64,
+    pub avg_consciousness_per_block: f64,
+    pub median_consciousness_per_block: f64,
+    pub reorg_count: u64,
+    pub mempool_depth: usize,
+    pub orphan_blocks: usize,
+}
+
+impl ChainMetrics {
+    /// Prometheus text exposition format
+    pub fn to_prometheus(&self) -> String {
+        let metrics: [(&str, &str, &str, f64); 10] = [
+            ("divine_chain_height", "gauge", "Canonical chain height", self.height as f64),
+            ("divine_chain_difficulty", "gauge", "Difficulty of the next block (leading zero bits)", self.difficulty as f64),
+            ("divine_chain_metrics_window_blocks", "gauge", "Blocks the window metrics cover", self.window_blocks as f64),
+            ("divine_chain_hash_rate", "gauge", "Effective hash rate (hashes/sec)", self.hash_rate),
+            ("divine_chain_block_interval_seconds", "gauge", "Average block interval", self.avg_block_interval_secs),
+            ("divine_chain_consciousness_per_block_avg", "gauge", "Average attested consciousness per block", self.avg_consciousness_per_block),
+            ("divine_chain_consciousness_per_block_median", "gauge", "Median attested consciousness per block", self.median_consciousness_per_block),
+            ("divine_chain_reorgs_total", "counter", "Reorgs since the node started", self.reorg_count as f64),
+            ("divine_chain_mempool_depth", "gauge", "Pending attestations", self.mempool_depth as f64),
+            ("divine_chain_orphan_blocks", "gauge", "Blocks waiting for their parent", self.orphan_blocks as f64),
+        ];
+
+        let mut out = String::new();
+        for (name, kind, help, value) in metrics {
+            out.push_str(&format!("# HELP {} {}\n# TYPE {} {}\n{} {}\n", name, help, name, kind, name, value));
+        }
+        out
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PoCStatus {
     pub min_consciousness: u32,
     pub proofs_validated: u64,
     pub total_rewards_distributed: f64,
     pub current_block_height: u64,
+    pub chain_id: String,
+    pub latest_hash: String,
+    pub mempool_size: usize,
+    pub difficulty: u32,
+    /// Protocol version required of the next block
+    pub block_version: u32,
+    pub validator: Option<String>,
+    pub validators: usize,
     pub required_level: &'static str,
 }
 
+/// What `merge_blocks` did with each offered block
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct MergeReport {
+    /// Already on this node (either chain)
+    pub known: usize,
+    pub extended: usize,
+    pub side_chain: usize,
+    pub orphans: usize,
+    /// Blocks that made a side chain overtake the canonical one
+    pub reorgs: usize,
+    /// Block index and why it was refused
+    pub rejected: Vec<(u64, String)>,
+}
+
 pub fn verify_proof(proof: &ConsciousnessProof, threshold: u32) -> bool {
     proof.verify(threshold)
 }
@@ -0,0 +1,352 @@
+//! Consensus Simulation V1 — adversarial in-process nodes
+//!
+//! A `Simulation` runs several `ProofOfConsciousness` engines side by side and
+//! delivers blocks between them directly (no transport). Each round one node,
+//! chosen round-robin, produces a block according to its `Behavior`:
+//!
+//! - `Honest`: mines a genuine attestation and delivers it to every node
+//! - `WithholdBlocks`: mines privately and releases its branch every N blocks
+//! - `InvalidConsciousness`: attests an inflated consciousness value
+//! - `ConflictingForks`: signs two blocks at the same height and sends one to
+//!   each half of the network
+//!
+//! Every genome created in the simulation is known to the honest nodes, so
+//! false claims are caught by the slashing audit. After the last round the
+//! honest nodes exchange their chains, and `SimReport` checks safety (honest
+//! nodes agree on a fully valid chain without false claims) and liveness
+//! (the agreed chain kept growing).
+
+use std::collections::HashMap;
+use tracing::info;
+
+use crate::consensus::{reward_address_for, ConsensusBlock, ProofOfConsciousness, ValidatorKey};
+use crate::genesis::{GenesisAllocation, GenesisConfig};
+use crate::genome::{Genome, GenomeBuilder};
+use crate::rotation::Rot180;
+use crate::slashing::recompute_consciousness;
+use crate::staking::{StakeAction, StakeOp};
+
+/// RSM each simulated node locks as stake before the first round
+pub const SIM_STAKE_RSM: f64 = 1_000.0;
+
+/// Closing honest rounds allowed for the network to agree after the run
+pub const SIM_CLOSING_ROUNDS: u64 = 5;
+
+/// Consciousness added to a claim by `Behavior::InvalidConsciousness`
+pub const SIM_CLAIM_INFLATION: u32 = 1_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Behavior {
+    Honest,
+    /// Keep mined blocks private, releasing them once `release_every` piled up
+    WithholdBlocks { release_every: u64 },
+    InvalidConsciousness,
+    ConflictingForks,
+}
+
+impl Behavior {
+    pub fn is_honest(&self) -> bool {
+        *self == Behavior::Honest
+    }
+}
+
+pub struct SimNode {
+    pub name: String,
+    pub behavior: Behavior,
+    pub chain: ProofOfConsciousness,
+    key: ValidatorKey,
+    withheld: Vec<ConsensusBlock>,
+    /// Blocks this node refused during the rounds
+    pub rejected: u64,
+}
+
+impl SimNode {
+    pub fn public_key(&self) -> String {
+        self.key.public_key_hex()
+    }
+}
+
+pub struct Simulation {
+    pub nodes: Vec<SimNode>,
+    pub round: u64,
+    /// Every genome created so far, as held by the honest nodes
+    genomes: HashMap<[u8; 32], Genome<Rot180>>,
+    /// Hashes of blocks that attested a false consciousness value
+    false_claims: Vec<String>,
+    honest_rounds: u64,
+}
+
+impl Simulation {
+    /// One node per behavior, each staking `SIM_STAKE_RSM` in a setup block
+    pub fn new(behaviors: &[Behavior]) -> Self {
+        assert!(behaviors.iter().any(Behavior::is_honest), "simulation needs an honest node");
+
+        let keys: Vec<ValidatorKey> = behaviors.iter().map(|_| ValidatorKey::generate()).collect();
+        let genesis = GenesisConfig {
+            chain_id: "divine-sim".to_string(),
+            min_consciousness: 0,
+            allocations: keys.iter()
+                .map(|k| GenesisAllocation { address: reward_address_for(&k.public_key_hex()), amount_rsm: SIM_STAKE_RSM })
+                .collect(),
+            ..GenesisConfig::default()
+        };
+
+        let mut nodes: Vec<SimNode> = behaviors.iter().zip(&keys).enumerate().map(|(i, (behavior, key))| {
+            let mut chain = ProofOfConsciousness::from_genesis(genesis.clone()).with_signer(key.clone());
+            chain.difficulty_growth_rate = 0;
+            for other in &keys {
+                chain.validators.register(&other.public_key_hex());
+            }
+            SimNode { name: format!("node{}", i), behavior: *behavior, chain, key: key.clone(), withheld: Vec::new(), rejected: 0 }
+        }).collect();
+
+        // Setup block: everyone locks stake, so slashing has something to burn
+        let miner = nodes.iter().position(|n| n.behavior.is_honest()).expect("checked above");
+        for key in &keys {
+            let op = StakeOp::new(key, 1, StakeAction::Lock { amount_rsm: SIM_STAKE_RSM });
+            nodes[miner].chain.submit_stake_op(op).expect("setup stake op is valid");
+        }
+        let setup = nodes[miner].chain.mine_block().expect("setup block mines").clone();
+        for (i, node) in nodes.iter_mut().enumerate() {
+            if i != miner {
+                node.chain.add_block(setup.clone()).expect("setup block is valid");
+            }
+        }
+
+        Self { nodes, round: 0, genomes: HashMap::new(), false_claims: Vec::new(), honest_rounds: 0 }
+    }
+
+    fn new_genome(&mut self) -> Genome<Rot180> {
+        let genome = GenomeBuilder::random().build_storage();
+        self.genomes.insert(genome.hash, genome.clone());
+        for node in self.nodes.iter_mut().filter(|n| n.behavior.is_honest()) {
+            node.chain.register_genome(&genome);
+        }
+        genome
+    }
+
+    /// Deliver `block` to the given nodes, counting rejections
+    fn deliver(&mut self, block: &ConsensusBlock, to: &[usize]) {
+        for &i in to {
+            if self.nodes[i].chain.add_block(block.clone()).is_err() {
+                self.nodes[i].rejected += 1;
+            }
+        }
+    }
+
+    fn others(&self, producer: usize) -> Vec<usize> {
+        (0..self.nodes.len()).filter(|&i| i != producer).collect()
+    }
+
+    /// Block on `producer`'s tip carrying one attestation of `genome`
+    fn build_block(&mut self, producer: usize, genome: &Genome<Rot180>) -> Option<ConsensusBlock> {
+        let node = &mut self.nodes[producer];
+        node.chain.submit(genome).ok()?;
+        // Entries returned to the mempool by a reorg are re-proven for this block
+        let height = node.chain.current_block_height + 1;
+        let entries = node.chain.mempool.take(1).into_iter()
+            .filter_map(|entry| super::restamp(entry, height, &node.key))
+            .collect();
+        let address = reward_address_for(&node.public_key());
+        let mut block = ConsensusBlock::mine(node.chain.latest_block(), entries, node.chain.difficulty, node.chain.coinbase(&address));
+        block.sign(&node.key);
+        Some(block)
+    }
+
+    /// Mine on `producer`'s tip through the engine (pending stake ops and
+    /// slashing reports included) and deliver the block to everyone else
+    fn mine_honest(&mut self, producer: usize, genome: &Genome<Rot180>) {
+        let chain = &mut self.nodes[producer].chain;
+        if chain.submit(genome).is_err() {
+            return;
+        }
+        if let Some(block) = chain.mine_block().cloned() {
+            self.deliver(&block, &self.others(producer));
+        }
+    }
+
+    /// Play one round: the round-robin producer mines per its behavior
+    pub fn step(&mut self) {
+        self.round += 1;
+        let producer = (self.round as usize - 1) % self.nodes.len();
+        let behavior = self.nodes[producer].behavior;
+        let genome = self.new_genome();
+
+        match behavior {
+            Behavior::Honest => {
+                self.honest_rounds += 1;
+                self.mine_honest(producer, &genome);
+            }
+            Behavior::WithholdBlocks { release_every } => {
+                if let Some(block) = self.build_block(producer, &genome) {
+                    if self.nodes[producer].chain.add_block(block.clone()).is_ok() {
+                        self.nodes[producer].withheld.push(block);
+                    }
+                }
+                if self.nodes[producer].withheld.len() as u64 >= release_every.max(1) {
+                    let released = std::mem::take(&mut self.nodes[producer].withheld);
+                    for block in &released {
+                        self.deliver(block, &self.others(producer));
+                    }
+                }
+            }
+            Behavior::InvalidConsciousness => {
+                let mut inflated = genome.clone();
+                inflated.consciousness += SIM_CLAIM_INFLATION;
+                let Some(block) = self.build_block(producer, &inflated) else { return };
+                if self.nodes[producer].chain.add_block(block.clone()).is_ok() {
+                    self.false_claims.push(block.hash.clone());
+                    self.deliver(&block, &self.others(producer));
+                }
+            }
+            Behavior::ConflictingForks => {
+                let twin = self.new_genome();
+                let (Some(a), Some(b)) = (self.build_block(producer, &genome), self.build_block(producer, &twin)) else { return };
+                if self.nodes[producer].chain.add_block(a.clone()).is_ok() {
+                    let others = self.others(producer);
+                    let (left, right) = others.split_at(others.len() / 2);
+                    self.deliver(&a, left);
+                    self.deliver(&b, right);
+                }
+            }
+        }
+    }
+
+    /// Honest nodes exchange their chains until no new block is learned
+    pub fn settle(&mut self) {
+        let honest: Vec<usize> = (0..self.nodes.len()).filter(|&i| self.nodes[i].behavior.is_honest()).collect();
+        loop {
+            let mut learned = false;
+            for &from in &honest {
+                let blocks = self.nodes[from].chain.chain[1..].to_vec();
+                for &to in honest.iter().filter(|&&to| to != from) {
+                    let chain = &mut self.nodes[to].chain;
+                    for block in &blocks {
+                        if !chain.chain.iter().any(|b| b.hash == block.hash) && chain.add_block(block.clone()).is_ok() {
+                            learned = true;
+                        }
+                    }
+                }
+            }
+            if !learned {
+                break;
+            }
+        }
+    }
+
+    /// Run `rounds` rounds, then honest closing rounds until the honest nodes
+    /// agree and no report is left pending (a reorg can push one back)
+    pub fn run(&mut self, rounds: u64) -> SimReport {
+        for _ in 0..rounds {
+            self.step();
+        }
+
+        let closer = self.nodes.iter().position(|n| n.behavior.is_honest()).expect("simulation has an honest node");
+        for _ in 0..SIM_CLOSING_ROUNDS {
+            self.honest_rounds += 1;
+            let genome = self.new_genome();
+            self.mine_honest(closer, &genome);
+            self.settle();
+
+            let tips = self.report().tips;
+            if tips.windows(2).all(|w| w[0] == w[1]) && self.nodes[closer].chain.pending_stake_ops.is_empty() {
+                break;
+            }
+        }
+
+        let report = self.report();
+        info!(
+            "🧪 Simulation: {} round(s) | honest height {} | {} reorg(s) | {} offense(s)",
+            report.rounds, report.honest_height, report.reorgs, report.offenses
+        );
+        report
+    }
+
+    pub fn report(&self) -> SimReport {
+        let honest: Vec<&SimNode> = self.nodes.iter().filter(|n| n.behavior.is_honest()).collect();
+        let reference = &honest[0].chain;
+
+        let false_claims_on_chain = honest.iter()
+            .flat_map(|n| n.chain.chain.iter())
+            .flat_map(|b| b.entries.iter())
+            .filter(|e| self.genomes.get(&e.proof.genome_hash)
+                .is_some_and(|g| recompute_consciousness(g) != e.proof.consciousness))
+            .count();
+
+        let byzantine: Vec<&SimNode> = self.nodes.iter().filter(|n| !n.behavior.is_honest()).collect();
+        let byzantine_stake = byzantine.iter()
+            .map(|n| reference.staking.stakes.get(&n.public_key()).map_or(0.0, |r| r.staked_rsm))
+            .sum();
+
+        SimReport {
+            rounds: self.round,
+            honest_rounds: self.honest_rounds,
+            honest_height: reference.current_block_height,
+            tips: honest.iter().map(|n| n.chain.latest_block().hash.clone()).collect(),
+            all_valid: honest.iter().all(|n| n.chain.validate_chain()),
+            false_claims_mined: self.false_claims.len(),
+            false_claims_on_chain,
+            offenses: reference.staking.offenses.len(),
+            byzantine_stake,
+            byzantine_stake_locked: byzantine.len() as f64 * SIM_STAKE_RSM,
+            reorgs: honest.iter().map(|n| n.chain.reorg_count).sum(),
+            rejected: honest.iter().map(|n| n.rejected).sum(),
+        }
+    }
+}
+
+/// Outcome of a simulation run, as seen by the honest nodes
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimReport {
+    pub rounds: u64,
+    pub honest_rounds: u64,
+    pub honest_height: u64,
+    /// Tip hash of every honest node
+    pub tips: Vec<String>,
+    pub all_valid: bool,
+    pub false_claims_mined: usize,
+    pub false_claims_on_chain: usize,
+    pub offenses: usize,
+    /// Stake left to the byzantine nodes after slashing
+    pub byzantine_stake: f64,
+    pub byzantine_stake_locked: f64,
+    pub reorgs: u64,
+    pub rejected: u64,
+}
+
+impl SimReport {
+    /// Honest nodes agree on one valid chain with no false claims on it
+    pub fn check_safety(&self) -> Result<(), String> {
+        if self.tips.windows(2).any(|w| w[0] != w[1]) {
+            return Err(format!("honest nodes disagree on the tip: {:?}", self.tips));
+        }
+        if !self.all_valid {
+            return Err("an honest chain fails full validation".to_string());
+        }
+        if self.false_claims_on_chain > 0 {
+            return Err(format!("{} false consciousness claim(s) on honest chains", self.false_claims_on_chain));
+        }
+        Ok(())
+    }
+
+    /// The agreed chain grew by at least one block per two honest rounds
+    pub fn check_liveness(&self) -> Result<(), String> {
+        // +1 for the setup block
+        let required = self.honest_rounds.div_ceil(2) + 1;
+        if self.honest_height < required {
+            return Err(format!("honest height {} after {} honest round(s) (need {})", self.honest_height, self.honest_rounds, required));
+        }
+        Ok(())
+    }
+
+    /// Every false claim that reached an honest node was slashed
+    pub fn check_slashing(&self) -> Result<(), String> {
+        if self.offenses < self.false_claims_mined {
+            return Err(format!("{} false claim(s) but {} offense(s) recorded", self.false_claims_mined, self.offenses));
+        }
+        if self.false_claims_mined > 0 && self.byzantine_stake >= self.byzantine_stake_locked {
+            return Err("byzantine stake was never slashed".to_string());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,129 @@
+//! CORS V1 — which browser origins may call the API
+//!
+//! `CORS_ALLOWED_ORIGINS` lists the origins of dashboards hosted elsewhere
+//! (`https://dash.example.com`, or `https://*.example.com` for its
+//! subdomains); unset or `*` allows every origin, as before. Preflights are
+//! answered with the methods and headers they ask for unless
+//! `CORS_ALLOWED_METHODS` / `CORS_ALLOWED_HEADERS` narrow them, and cached for
+//! `CORS_MAX_AGE_SECS` (default 600). `CORS_ALLOW_CREDENTIALS=true` lets
+//! browsers send cookies and `Authorization`; it needs an explicit origin list.
+//!
+//! `API-Version`, `Deprecation`, `Link`, `Retry-After` and `ETag` are exposed
+//! to scripts.
+
+use std::time::Duration;
+
+use axum::http::{HeaderName, HeaderValue, Method};
+use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin, CorsLayer};
+
+pub const DEFAULT_MAX_AGE_SECS: u64 = 600;
+
+/// Response headers scripts on other origins may read
+pub const EXPOSED_HEADERS: &[&str] = &["api-version", "deprecation", "link", "retry-after", "etag"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorsSettings {
+    /// `None` allows every origin
+    pub origins: Option<Vec<String>>,
+    /// `None` allows what the preflight asks for
+    pub methods: Option<Vec<Method>>,
+    /// `None` allows what the preflight asks for
+    pub headers: Option<Vec<HeaderName>>,
+    pub credentials: bool,
+    pub max_age_secs: u64,
+}
+
+impl Default for CorsSettings {
+    fn default() -> Self {
+        Self { origins: None, methods: None, headers: None, credentials: false, max_age_secs: DEFAULT_MAX_AGE_SECS }
+    }
+}
+
+/// Comma-separated list; `None` when unset, empty or `*`
+fn list(value: Option<String>) -> Option<Vec<String>> {
+    let items: Vec<String> = value?.split(',').map(|item| item.trim().to_string()).filter(|item| !item.is_empty()).collect();
+    (!items.is_empty() && !items.iter().any(|item| item == "*")).then_some(items)
+}
+
+impl CorsSettings {
+    pub fn from_env() -> Result<Self, String> {
+        let var = |name: &str| std::env::var(name).ok();
+        let credentials = match var("CORS_ALLOW_CREDENTIALS").as_deref().map(str::trim) {
+            None | Some("" | "false" | "0") => false,
+            Some("true" | "1") => true,
+            Some(other) => return Err(format!("Invalid CORS_ALLOW_CREDENTIALS {}", other)),
+        };
+        let max_age_secs = match var("CORS_MAX_AGE_SECS") {
+            Some(v) => v.trim().parse().map_err(|_| format!("Invalid CORS_MAX_AGE_SECS {}", v))?,
+            None => DEFAULT_MAX_AGE_SECS,
+        };
+        let settings = Self {
+            origins: list(var("CORS_ALLOWED_ORIGINS")),
+            methods: list(var("CORS_ALLOWED_METHODS")).map(|methods| methods.iter()
+                .map(|m| Method::from_bytes(m.to_ascii_uppercase().as_bytes()).map_err(|_| format!("Invalid CORS method {}", m)))
+                .collect::<Result<_, _>>()).transpose()?,
+            headers: list(var("CORS_ALLOWED_HEADERS")).map(|headers| headers.iter()
+                .map(|h| HeaderName::from_bytes(h.as_bytes()).map_err(|_| format!("Invalid CORS header {}", h)))
+                .collect::<Result<_, _>>()).transpose()?,
+            credentials,
+            max_age_secs,
+        };
+        settings.validate()?;
+        Ok(settings)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.credentials && self.origins.is_none() {
+            return Err("CORS_ALLOW_CREDENTIALS needs CORS_ALLOWED_ORIGINS (credentials cannot go to every origin)".into());
+        }
+        for origin in self.origins.iter().flatten() {
+            let (scheme, host) = origin.split_once("://").ok_or_else(|| format!("CORS origin {} needs a scheme", origin))?;
+            if scheme.is_empty() || host.is_empty() || host.contains('/') || host.strip_prefix("*.").unwrap_or(host).contains('*') {
+                return Err(format!("Invalid CORS origin {} (expected scheme://host[:port])", origin));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether a request's `Origin` is allowed
+    pub fn allows_origin(&self, origin: &str) -> bool {
+        let Some(origins) = &self.origins else {
+            return true;
+        };
+        origins.iter().any(|allowed| match allowed.split_once("://*.") {
+            // `https://*.example.com` covers `https://a.example.com`, not `https://example.com`
+            Some((scheme, domain)) => origin.strip_prefix(scheme)
+                .and_then(|rest| rest.strip_prefix("://"))
+                .and_then(|host| host.strip_suffix(domain))
+                .and_then(|sub| sub.strip_suffix('.'))
+                .is_some_and(|sub| !sub.is_empty() && !sub.contains('/')),
+            None => allowed.eq_ignore_ascii_case(origin),
+        })
+    }
+
+    pub fn layer(&self) -> Result<CorsLayer, String> {
+        self.validate()?;
+        let origin = match &self.origins {
+            None => AllowOrigin::any(),
+            Some(_) => {
+                let settings = self.clone();
+                AllowOrigin::predicate(move |origin: &HeaderValue, _| origin.to_str().is_ok_and(|o| settings.allows_origin(o)))
+            }
+        };
+        let methods = match &self.methods {
+            Some(methods) => AllowMethods::list(methods.clone()),
+            None => AllowMethods::mirror_request(),
+        };
+        let headers = match &self.headers {
+            Some(headers) => AllowHeaders::list(headers.clone()),
+            None => AllowHeaders::mirror_request(),
+        };
+        Ok(CorsLayer::new()
+            .allow_origin(origin)
+            .allow_methods(methods)
+            .allow_headers(headers)
+            .allow_credentials(self.credentials)
+            .expose_headers(EXPOSED_HEADERS.iter().map(|h| HeaderName::from_static(h)).collect::<Vec<_>>())
+            .max_age(Duration::from_secs(self.max_age_secs)))
+    }
+}
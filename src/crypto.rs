@@ -59,6 +59,67 @@ pub fn hash_sha3(data: &[u8]) -> [u8; 32] {
     hasher.finalize().into()
 }
 
+/// Bitcoin-style binary Merkle root (odd levels duplicate the last node)
+pub fn merkle_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    if leaves.is_empty() {
+        return [0u8; 32];
+    }
+
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = level.chunks(2)
+            .map(|pair| merkle_parent(&pair[0], pair.get(1).unwrap_or(&pair[0])))
+            .collect();
+    }
+    level[0]
+}
+
+/// One hop of a Merkle inclusion path
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MerkleStep {
+    pub sibling: [u8; 32],
+    pub sibling_is_left: bool,
+}
+
+/// Sibling path from `leaves[index]` up to the root
+pub fn merkle_proof(leaves: &[[u8; 32]], index: usize) -> Option<Vec<MerkleStep>> {
+    if index >= leaves.len() {
+        return None;
+    }
+
+    let mut path = Vec::new();
+    let mut level = leaves.to_vec();
+    let mut pos = index;
+    while level.len() > 1 {
+        let sibling = if pos.is_multiple_of(2) { level.get(pos + 1).unwrap_or(&level[pos]) } else { &level[pos - 1] };
+        path.push(MerkleStep { sibling: *sibling, sibling_is_left: !pos.is_multiple_of(2) });
+
+        level = level.chunks(2)
+            .map(|pair| merkle_parent(&pair[0], pair.get(1).unwrap_or(&pair[0])))
+            .collect();
+        pos /= 2;
+    }
+    Some(path)
+}
+
+pub fn verify_merkle_proof(leaf: &[u8; 32], path: &[MerkleStep], root: &[u8; 32]) -> bool {
+    let computed = path.iter().fold(*leaf, |node, step| {
+        if step.sibling_is_left {
+            merkle_parent(&step.sibling, &node)
+        } else {
+            merkle_parent(&node, &step.sibling)
+        }
+    });
+    &computed == root
+}
+
+fn merkle_parent(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
 pub fn derive_key(master: &[u8], info: &[u8]) -> [u8; 32] {
     let hkdf = Hkdf::<Sha256>::new(None, master);
     let mut key = [0u8; 32];
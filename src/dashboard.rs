@@ -0,0 +1,392 @@
+//! Dashboard V1 — live terminal panels for a node
+//!
+//! `divine-agi dashboard` draws the chain height and latest blocks, TTRL job
+//! progress, genome and table counts, the wallet balance and archives per
+//! layer, refreshed every `--interval` seconds. With `--api` the panels come
+//! from a running server's `GET /api/dashboard` (the node wallet needs a
+//! reader token); otherwise straight from the configured database and wallet file.
+//!
+//! Keys: `q` or Esc quits, `r` refreshes now. A failed refresh keeps the last
+//! panels on screen and shows the error in the status line.
+
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Bar, BarChart, BarGroup, Block, Borders, Cell, Paragraph, Row, Table};
+use ratatui::{DefaultTerminal, Frame};
+use serde::{Serialize, Deserialize};
+
+use crate::consensus::ConsensusBlock;
+use crate::database::DivineDatabase;
+use crate::multi_chain::BlockchainLayer;
+use crate::ttrl::{EvolutionJobRecord, EvolutionProgress, JobStatus};
+use crate::wallet::DivineWallet;
+
+/// Blocks listed in the chain panel
+pub const RECENT_BLOCKS: usize = 8;
+/// Jobs listed in the TTRL panel
+pub const RECENT_JOBS: usize = 6;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockRow {
+    pub index: u64,
+    pub hash: String,
+    /// Empty for genesis
+    pub producer: String,
+    pub attestations: usize,
+    pub timestamp: i64,
+}
+
+impl From<&ConsensusBlock> for BlockRow {
+    fn from(block: &ConsensusBlock) -> Self {
+        Self {
+            index: block.index,
+            hash: block.hash.clone(),
+            producer: block.producer.clone(),
+            attestations: block.entries.len(),
+            timestamp: block.timestamp,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobRow {
+    pub id: u64,
+    pub genome_id: i64,
+    pub status: JobStatus,
+    pub generation: u32,
+    pub generations: u32,
+    pub best_consciousness: Option<u32>,
+}
+
+impl JobRow {
+    /// A job as stored, with its live progress when this process runs it
+    pub fn new(job: &EvolutionJobRecord, progress: Option<&EvolutionProgress>) -> Self {
+        let done = matches!(job.status, JobStatus::Finished);
+        Self {
+            id: job.id,
+            genome_id: job.genome_id,
+            status: job.status,
+            generation: progress.map_or(if done { job.generations } else { 0 }, |p| p.generation),
+            generations: job.generations,
+            best_consciousness: progress.map(|p| p.best_consciousness).or(job.best_consciousness),
+        }
+    }
+
+    /// Share of the generations run, 0.0–1.0
+    pub fn ratio(&self) -> f64 {
+        if self.generations == 0 {
+            return 0.0;
+        }
+        (self.generation as f64 / self.generations as f64).clamp(0.0, 1.0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WalletRow {
+    pub address: String,
+    pub balance_rsm: f64,
+    pub staked_rsm: f64,
+}
+
+impl From<&DivineWallet> for WalletRow {
+    fn from(wallet: &DivineWallet) -> Self {
+        Self { address: wallet.address.clone(), balance_rsm: wallet.rsm_balance, staked_rsm: wallet.staked_rsm }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayerRow {
+    pub layer: BlockchainLayer,
+    pub archives: u64,
+}
+
+impl LayerRow {
+    /// Every layer, with the counts of the ones named in `counts`
+    pub fn from_counts(counts: &[(String, i64)]) -> Vec<Self> {
+        BlockchainLayer::ALL.into_iter().map(|layer| {
+            let archives = counts.iter()
+                .filter(|(name, _)| BlockchainLayer::from_name(name) == Ok(layer))
+                .map(|(_, count)| (*count).max(0) as u64)
+                .sum();
+            Self { layer, archives }
+        }).collect()
+    }
+}
+
+/// Everything the panels show, taken at `taken_at`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DashboardSnapshot {
+    pub height: u64,
+    pub tip: String,
+    /// Newest first
+    pub blocks: Vec<BlockRow>,
+    /// Newest first
+    pub jobs: Vec<JobRow>,
+    pub genome_count: i64,
+    /// Rows per table
+    pub tables: Vec<(String, i64)>,
+    pub wallet: Option<WalletRow>,
+    pub layers: Vec<LayerRow>,
+    pub taken_at: i64,
+}
+
+impl DashboardSnapshot {
+    /// The newest `RECENT_BLOCKS` blocks of `chain`, newest first
+    pub fn recent_blocks(chain: &[ConsensusBlock]) -> Vec<BlockRow> {
+        chain.iter().rev().take(RECENT_BLOCKS).map(BlockRow::from).collect()
+    }
+}
+
+/// Where the panels come from
+pub enum DashboardSource {
+    /// A running server's `GET /api/dashboard`
+    Api { url: String, token: Option<String>, client: reqwest::Client },
+    /// The database, and the wallet file with its password
+    Local { database: DivineDatabase, wallet: Option<(PathBuf, String)> },
+}
+
+#[derive(Deserialize)]
+struct Envelope {
+    data: Option<DashboardSnapshot>,
+    error: Option<String>,
+}
+
+impl DashboardSource {
+    pub fn api(url: &str, token: Option<String>) -> Self {
+        Self::Api { url: url.trim_end_matches('/').to_string(), token, client: reqwest::Client::new() }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Api { url, .. } => url.clone(),
+            Self::Local { .. } => "local database".to_string(),
+        }
+    }
+
+    pub async fn snapshot(&self) -> anyhow::Result<DashboardSnapshot> {
+        match self {
+            Self::Api { url, token, client } => {
+                let mut request = client.get(format!("{}/api/dashboard", url)).timeout(Duration::from_secs(10));
+                if let Some(token) = token {
+                    request = request.bearer_auth(token);
+                }
+                let body = request.send().await?.text().await?;
+                let envelope: Envelope = serde_json::from_str(&body)
+                    .map_err(|e| anyhow::anyhow!("Unexpected response from {}: {}", url, e))?;
+                envelope.data.ok_or_else(|| anyhow::anyhow!(envelope.error.unwrap_or_else(|| "Empty response".into())))
+            }
+            Self::Local { database, wallet } => {
+                let chain = database.load_blocks().await?;
+                let wallet = match wallet {
+                    Some((path, password)) => Some(WalletRow::from(&DivineWallet::load(path, password)?)),
+                    None => None,
+                };
+                Ok(DashboardSnapshot {
+                    height: chain.last().map_or(0, |b| b.index),
+                    tip: chain.last().map(|b| b.hash.clone()).unwrap_or_default(),
+                    blocks: DashboardSnapshot::recent_blocks(&chain),
+                    jobs: database.recent_evolution_jobs(RECENT_JOBS as i64).await?
+                        .iter().map(|job| JobRow::new(job, None)).collect(),
+                    genome_count: database.genome_count().await?,
+                    tables: database.table_stats().await?.into_iter().map(|t| (t.table, t.rows)).collect(),
+                    wallet,
+                    layers: LayerRow::from_counts(&database.archive_layer_counts().await?),
+                    taken_at: chrono::Utc::now().timestamp(),
+                })
+            }
+        }
+    }
+}
+
+/// `abcdef…` for long hashes and keys
+fn short(text: &str, len: usize) -> String {
+    if text.chars().count() <= len {
+        text.to_string()
+    } else {
+        format!("{}…", text.chars().take(len).collect::<String>())
+    }
+}
+
+fn clock(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0).map_or_else(
+        || timestamp.to_string(),
+        |t| t.with_timezone(&Local).format("%H:%M:%S").to_string(),
+    )
+}
+
+/// `████░░░░` at `ratio` of `width`
+pub fn progress_bar(ratio: f64, width: usize) -> String {
+    let filled = ((ratio.clamp(0.0, 1.0) * width as f64).round() as usize).min(width);
+    format!("{}{}", "█".repeat(filled), "░".repeat(width - filled))
+}
+
+fn panel(title: &str) -> Block<'static> {
+    Block::default().borders(Borders::ALL).title(Span::styled(format!(" {} ", title), Style::default().add_modifier(Modifier::BOLD)))
+}
+
+fn header(cells: &[&'static str]) -> Row<'static> {
+    Row::new(cells.iter().copied().map(Cell::from)).style(Style::default().fg(Color::Yellow))
+}
+
+/// Draw every panel; `None` until the first refresh lands
+pub fn render(frame: &mut Frame, snapshot: Option<&DashboardSnapshot>, status: &str) {
+    let [title, top, jobs, bottom, footer] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Min(8),
+            Constraint::Length(RECENT_JOBS as u16 + 3),
+            Constraint::Length(8),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+    frame.render_widget(Paragraph::new(Line::from(vec![
+        Span::styled(format!("🧬 Divine AGI V{} dashboard", crate::VERSION), Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw("   q quit · r refresh"),
+    ])), title);
+    frame.render_widget(Paragraph::new(status.to_string()).style(Style::default().fg(Color::DarkGray)), footer);
+
+    let Some(snapshot) = snapshot else {
+        frame.render_widget(Paragraph::new("Loading…").block(panel("Divine AGI")), top);
+        return;
+    };
+    let [chain, database] = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(65), Constraint::Percentage(35)])
+        .areas(top);
+    let [wallet, layers] = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .areas(bottom);
+
+    render_chain(frame, chain, snapshot);
+    render_database(frame, database, snapshot);
+    render_jobs(frame, jobs, snapshot);
+    render_wallet(frame, wallet, snapshot);
+    render_layers(frame, layers, snapshot);
+}
+
+fn render_chain(frame: &mut Frame, area: Rect, snapshot: &DashboardSnapshot) {
+    let rows = snapshot.blocks.iter().map(|block| Row::new(vec![
+        Cell::from(format!("#{}", block.index)),
+        Cell::from(short(&block.hash, 16)),
+        Cell::from(if block.producer.is_empty() { "genesis".to_string() } else { short(&block.producer, 12) }),
+        Cell::from(block.attestations.to_string()),
+        Cell::from(clock(block.timestamp)),
+    ]));
+    let table = Table::new(rows, [Constraint::Length(8), Constraint::Length(18), Constraint::Length(14), Constraint::Length(8), Constraint::Length(9)])
+        .header(header(&["Block", "Hash", "Producer", "Genomes", "Time"]))
+        .block(panel(&format!("Chain · height {} · tip {}", snapshot.height, short(&snapshot.tip, 12))));
+    frame.render_widget(table, area);
+}
+
+fn render_database(frame: &mut Frame, area: Rect, snapshot: &DashboardSnapshot) {
+    let mut lines = vec![
+        Line::from(vec![Span::raw("Genomes  "), Span::styled(snapshot.genome_count.to_string(), Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))]),
+        Line::from(""),
+    ];
+    lines.extend(snapshot.tables.iter().filter(|(_, rows)| *rows > 0).map(|(table, rows)| Line::from(format!("{:<24} {:>8}", table, rows))));
+    frame.render_widget(Paragraph::new(lines).block(panel("Database")), area);
+}
+
+fn render_jobs(frame: &mut Frame, area: Rect, snapshot: &DashboardSnapshot) {
+    let rows = snapshot.jobs.iter().map(|job| {
+        let color = match job.status {
+            JobStatus::Running => Color::Cyan,
+            JobStatus::Finished => Color::Green,
+            JobStatus::Failed => Color::Red,
+            JobStatus::Queued => Color::Gray,
+        };
+        Row::new(vec![
+            Cell::from(format!("#{}", job.id)),
+            Cell::from(format!("genome #{}", job.genome_id)),
+            Cell::from(job.status.name()).style(Style::default().fg(color)),
+            Cell::from(format!("{} {}/{}", progress_bar(job.ratio(), 24), job.generation, job.generations)),
+            Cell::from(job.best_consciousness.map_or("—".to_string(), |c| c.to_string())),
+        ])
+    });
+    let table = Table::new(rows, [Constraint::Length(6), Constraint::Length(14), Constraint::Length(9), Constraint::Min(36), Constraint::Length(8)])
+        .header(header(&["Job", "Base", "Status", "Progress", "Best"]))
+        .block(panel("TTRL jobs"));
+    frame.render_widget(table, area);
+}
+
+fn render_wallet(frame: &mut Frame, area: Rect, snapshot: &DashboardSnapshot) {
+    let lines = match &snapshot.wallet {
+        Some(wallet) => vec![
+            Line::from(format!("Address  {}", short(&wallet.address, 24))),
+            Line::from(vec![Span::raw("Balance  "), Span::styled(format!("{:.4} RSM", wallet.balance_rsm), Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))]),
+            Line::from(format!("Staked   {:.4} RSM", wallet.staked_rsm)),
+        ],
+        None => vec![Line::from("No wallet (wallet_file locally, --token for a server)")],
+    };
+    frame.render_widget(Paragraph::new(lines).block(panel("Wallet")), area);
+}
+
+fn render_layers(frame: &mut Frame, area: Rect, snapshot: &DashboardSnapshot) {
+    let bars: Vec<Bar> = snapshot.layers.iter().map(|row| Bar::default()
+        .label(Line::from(format!("{:?}", row.layer)))
+        .value(row.archives)
+    ).collect();
+    let chart = BarChart::default()
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(10)
+        .bar_gap(2)
+        .bar_style(Style::default().fg(Color::Magenta))
+        .block(panel("Archives per layer"));
+    frame.render_widget(chart, area);
+}
+
+/// Take over the terminal until `q`, refreshing every `interval`
+pub async fn run(source: DashboardSource, interval: Duration) -> anyhow::Result<()> {
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &source, interval).await;
+    ratatui::restore();
+    result
+}
+
+async fn event_loop(terminal: &mut DefaultTerminal, source: &DashboardSource, interval: Duration) -> anyhow::Result<()> {
+    let mut snapshot = None;
+    let mut error: Option<String> = None;
+    let mut next_refresh = Instant::now();
+    loop {
+        if Instant::now() >= next_refresh {
+            match source.snapshot().await {
+                Ok(fresh) => {
+                    snapshot = Some(fresh);
+                    error = None;
+                }
+                Err(e) => error = Some(e.to_string()),
+            }
+            next_refresh = Instant::now() + interval;
+        }
+        let status = match (&error, &snapshot) {
+            (Some(e), Some(s)) => format!("⚠️  {} · refresh failed: {} (panels from {})", source.describe(), e, clock(s.taken_at)),
+            (Some(e), None) => format!("⚠️  {} · refresh failed: {}", source.describe(), e),
+            (None, Some(s)) => format!("{} · updated {} · every {}s", source.describe(), clock(s.taken_at), interval.as_secs()),
+            (None, None) => format!("{} · connecting…", source.describe()),
+        };
+        terminal.draw(|frame| render(frame, snapshot.as_ref(), &status))?;
+
+        let wait = next_refresh.saturating_duration_since(Instant::now()).min(Duration::from_millis(250));
+        if tokio::task::block_in_place(|| event::poll(wait))? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return Ok(()),
+                    KeyCode::Char('r') => next_refresh = Instant::now(),
+                    _ => {}
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,180 @@
+//! Database Dumps V1 — JSONL exports, imports, backups and restores
+//!
+//! A dump is JSON lines: a header
+//! `{"format": "divine-dump", "version": 1, "kind": "backup", "created_at": …,
+//! "tables": {"divine_genomes_v15": 42, …}}` and then one
+//! `{"table": "…", "row": {…}}` per row, parents before the tables that
+//! reference them. Rows are Postgres `row_to_json` objects (BYTEA as `\x…`
+//! hex, timestamps as ISO strings), so they load back column by column.
+//!
+//! `db export` covers the data tables (`EXPORT_TABLES`); `db backup` covers
+//! every table, API key hashes, wallet accounts and webhook secrets included.
+//! `db import` adds rows and skips those whose key already exists; `db restore`
+//! empties the tables a dump contains first. The header's row counts catch
+//! truncated files.
+
+use std::collections::BTreeMap;
+
+use serde::{Serialize, Deserialize};
+use serde_json::{Map, Value};
+
+pub const DUMP_FORMAT: &str = "divine-dump";
+pub const DUMP_VERSION: u32 = 1;
+
+/// Every table, parents before the tables referencing them
+pub const TABLES: &[&str] = &[
+    "wallet_accounts",
+    "api_keys",
+    "admin_audit",
+    "webhooks",
+    "webhook_deliveries",
+    "evolution_jobs",
+    "divine_genomes_v15",
+    "genome_listings",
+    "genome_auctions",
+    "rsm_trades",
+    "chain_archives",
+    "ln_mission_control",
+    "consensus_validators",
+    "consensus_blocks",
+    "consensus_block_entries",
+];
+
+/// Tables without credentials or secrets
+pub const EXPORT_TABLES: &[&str] = &[
+    "evolution_jobs",
+    "divine_genomes_v15",
+    "genome_listings",
+    "genome_auctions",
+    "rsm_trades",
+    "chain_archives",
+    "ln_mission_control",
+    "consensus_validators",
+    "consensus_blocks",
+    "consensus_block_entries",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DumpKind {
+    Export,
+    Backup,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DumpHeader {
+    pub format: String,
+    pub version: u32,
+    pub kind: DumpKind,
+    pub created_at: i64,
+    /// Rows that follow, per table
+    pub tables: BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct DumpLine {
+    table: String,
+    row: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dump {
+    pub header: DumpHeader,
+    /// Rows per table, in `TABLES` order
+    pub tables: Vec<(String, Vec<Map<String, Value>>)>,
+}
+
+/// `name` as one of `TABLES`
+pub fn check_table(name: &str) -> Result<&'static str, String> {
+    TABLES.iter().copied().find(|table| *table == name)
+        .ok_or_else(|| format!("Unknown table {} (expected one of {})", name, TABLES.join(", ")))
+}
+
+/// Column names go into SQL quoted; only plain identifiers are accepted
+pub fn check_column(name: &str) -> Result<(), String> {
+    let plain = !name.is_empty() && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if plain { Ok(()) } else { Err(format!("Invalid column name {:?}", name)) }
+}
+
+impl Dump {
+    /// Dump of `tables`, put in `TABLES` order
+    pub fn new(kind: DumpKind, created_at: i64, tables: Vec<(String, Vec<Map<String, Value>>)>) -> Result<Self, String> {
+        let mut ordered = Vec::with_capacity(tables.len());
+        for (name, rows) in tables {
+            let table = check_table(&name)?;
+            if ordered.iter().any(|(t, _): &(String, _)| t == table) {
+                return Err(format!("Table {} listed twice", table));
+            }
+            ordered.push((table.to_string(), rows));
+        }
+        ordered.sort_by_key(|(table, _)| TABLES.iter().position(|t| t == table));
+
+        let header = DumpHeader {
+            format: DUMP_FORMAT.to_string(),
+            version: DUMP_VERSION,
+            kind,
+            created_at,
+            tables: ordered.iter().map(|(table, rows)| (table.clone(), rows.len())).collect(),
+        };
+        Ok(Self { header, tables: ordered })
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.tables.iter().map(|(_, rows)| rows.len()).sum()
+    }
+
+    pub fn to_jsonl(&self) -> String {
+        let mut out = serde_json::to_string(&self.header).expect("dump header serializes");
+        out.push('\n');
+        for (table, rows) in &self.tables {
+            for row in rows {
+                let line = DumpLine { table: table.clone(), row: row.clone() };
+                out.push_str(&serde_json::to_string(&line).expect("dump row serializes"));
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    /// Read a dump written by `to_jsonl`, checking tables, columns and counts
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut lines = text.lines().enumerate().filter(|(_, line)| !line.trim().is_empty());
+        let (_, first) = lines.next().ok_or("Empty dump")?;
+        let header: DumpHeader = serde_json::from_str(first)
+            .map_err(|e| format!("Line 1: not a dump header ({})", e))?;
+        if header.format != DUMP_FORMAT {
+            return Err(format!("Not a Divine dump (format {:?})", header.format));
+        }
+        if header.version != DUMP_VERSION {
+            return Err(format!("Unsupported dump version {} (expected {})", header.version, DUMP_VERSION));
+        }
+
+        let mut tables: BTreeMap<&'static str, Vec<Map<String, Value>>> = BTreeMap::new();
+        for (number, line) in lines {
+            let line: DumpLine = serde_json::from_str(line).map_err(|e| format!("Line {}: {}", number + 1, e))?;
+            let table = check_table(&line.table).map_err(|e| format!("Line {}: {}", number + 1, e))?;
+            if !header.tables.contains_key(table) {
+                return Err(format!("Line {}: table {} is not in the header", number + 1, table));
+            }
+            for column in line.row.keys() {
+                check_column(column).map_err(|e| format!("Line {}: {}", number + 1, e))?;
+            }
+            tables.entry(table).or_default().push(line.row);
+        }
+
+        for (table, expected) in &header.tables {
+            let found = tables.get(table.as_str()).map_or(0, Vec::len);
+            if found != *expected {
+                return Err(format!("Dump truncated: {} has {} of {} rows", table, found, expected));
+            }
+        }
+        let mut ordered: Vec<(String, Vec<Map<String, Value>>)> = header.tables.keys()
+            .map(|table| (table.clone(), tables.remove(table.as_str()).unwrap_or_default()))
+            .collect();
+        for (table, _) in &ordered {
+            check_table(table)?;
+        }
+        ordered.sort_by_key(|(table, _)| TABLES.iter().position(|t| t == table));
+        Ok(Self { header, tables: ordered })
+    }
+}
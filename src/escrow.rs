@@ -0,0 +1,157 @@
+//! Settlement Escrow V1 — exchange payments released by consensus finality
+//!
+//! An escrow holds a payer's RSM in its own wallet (`escrow_address`) until
+//! the consensus block it is anchored to is final — buried deeper than
+//! `MAX_REORG_DEPTH`, so no reorg can undo it — and then pays the payee.
+//! The anchor is either the next block after the escrow opened (`Block`)
+//! or the block that attests a genome (`Attestation`), so a genome sale
+//! pays out only once the chain has settled the genome's consciousness
+//! proof. An escrow whose block is not final by `expires_at` is refunded
+//! to the payer.
+//!
+//! Anchors are looked up on the canonical chain each time escrows are
+//! resolved, so an anchored block that is reorged away before finality
+//! waits for whichever block takes its place.
+
+use serde::{Serialize, Deserialize};
+
+use crate::consensus::ProofOfConsciousness;
+use crate::exchange::Transaction;
+
+pub const DEFAULT_ESCROW_TIMEOUT_SECS: i64 = 24 * 3600;
+pub const MIN_ESCROW_TIMEOUT_SECS: i64 = 60;
+pub const MAX_ESCROW_TIMEOUT_SECS: i64 = 30 * 24 * 3600;
+/// How often the server looks for escrows to release or refund
+pub const ESCROW_CHECK_SECS: u64 = 10;
+
+/// Consensus block an escrow waits on
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EscrowAnchor {
+    /// The canonical block at this height
+    Block { index: u64 },
+    /// The block attesting this genome (hash in hex)
+    Attestation { genome_hash: String },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EscrowStatus {
+    Held,
+    Released,
+    Refunded,
+}
+
+impl EscrowStatus {
+    pub fn name(self) -> &'static str {
+        match self {
+            EscrowStatus::Held => "held",
+            EscrowStatus::Released => "released",
+            EscrowStatus::Refunded => "refunded",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [EscrowStatus::Held, EscrowStatus::Released, EscrowStatus::Refunded]
+            .into_iter()
+            .find(|status| status.name() == name)
+    }
+}
+
+/// A canonical block an anchor resolved to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockRef {
+    pub index: u64,
+    pub hash: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Escrow {
+    pub id: u64,
+    pub payer: String,
+    pub payee: String,
+    pub amount_rsm: f64,
+    pub anchor: EscrowAnchor,
+    pub status: EscrowStatus,
+    /// The final block that released the escrow
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block: Option<BlockRef>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+    /// The deposit, then the release or refund
+    pub transactions: Vec<Transaction>,
+    pub created_at: i64,
+    pub expires_at: i64,
+    pub updated_at: i64,
+}
+
+/// What a payer escrows, for whom and until when
+#[derive(Debug, Clone, PartialEq)]
+pub struct EscrowTerms {
+    pub payee: String,
+    pub amount_rsm: f64,
+    pub anchor: EscrowAnchor,
+    /// Refund the payer if the block is not final by then
+    pub timeout_secs: i64,
+    pub memo: Option<String>,
+}
+
+/// Wallet holding the RSM of escrow `id`
+pub fn escrow_address(id: u64) -> String {
+    format!("settlement_escrow_{}", id)
+}
+
+pub fn check_timeout(timeout_secs: i64) -> Result<(), String> {
+    if !(MIN_ESCROW_TIMEOUT_SECS..=MAX_ESCROW_TIMEOUT_SECS).contains(&timeout_secs) {
+        return Err(format!(
+            "Escrows time out after {}–{} seconds, not {}", MIN_ESCROW_TIMEOUT_SECS, MAX_ESCROW_TIMEOUT_SECS, timeout_secs
+        ));
+    }
+    Ok(())
+}
+
+impl EscrowAnchor {
+    /// The next block `consensus` will add, for settlements made now
+    pub fn next_block(consensus: &ProofOfConsciousness) -> Self {
+        EscrowAnchor::Block { index: consensus.current_block_height + 1 }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if let EscrowAnchor::Attestation { genome_hash } = self {
+            parse_genome_hash(genome_hash)?;
+        }
+        Ok(())
+    }
+
+    /// The canonical block this anchor stands for, if on the chain yet
+    pub fn block(&self, consensus: &ProofOfConsciousness) -> Option<BlockRef> {
+        match self {
+            EscrowAnchor::Block { index } => consensus.chain.get(*index as usize)
+                .map(|block| BlockRef { index: block.index, hash: block.hash.clone() }),
+            EscrowAnchor::Attestation { genome_hash } => parse_genome_hash(genome_hash).ok()
+                .and_then(|hash| consensus.generate_inclusion_proof(&hash))
+                .map(|proof| BlockRef { index: proof.block_index, hash: proof.block_hash }),
+        }
+    }
+
+    /// The anchored block, once it can no longer be reorged away
+    pub fn final_block(&self, consensus: &ProofOfConsciousness) -> Option<BlockRef> {
+        self.block(consensus).filter(|block| block.index <= consensus.finalized_height())
+    }
+}
+
+fn parse_genome_hash(genome_hash: &str) -> Result<[u8; 32], String> {
+    hex::decode(genome_hash).ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| format!("Invalid genome hash {:?}", genome_hash))
+}
+
+impl Escrow {
+    pub fn is_held(&self) -> bool {
+        self.status == EscrowStatus::Held
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        now >= self.expires_at
+    }
+}
@@ -0,0 +1,867 @@
+//! Ethereum Archiving V1 — genome hashes on Sepolia or mainnet
+//!
+//! With `ETH_RPC_URL` and `ETH_PRIVATE_KEY` set, an Ethereum archive is a
+//! real EIP-1559 transaction, signed here (secp256k1 over the RLP encoding,
+//! so the node never sees the key) and sent over the node's JSON-RPC API.
+//! It goes to the genome registry (`contracts/GenomeRegistry.sol`) in one of
+//! two modes:
+//! - `calldata`: the DNA hash tagged `DIVINE` as plain calldata, which the
+//!   registry's fallback accepts — the cheapest, read back from the
+//!   transaction itself. Without `ETH_REGISTRY_ADDRESS` it is sent to the
+//!   archiver's own address, so needs no contract at all.
+//! - `event`: a call to `archive(bytes32)`, which emits
+//!   `GenomeArchived(bytes32 indexed genomeHash, address indexed archiver)`,
+//!   so archives can be looked up by hash from the logs.
+//!
+//! Fees follow EIP-1559: twice the latest base fee plus the priority fee
+//! (`ETH_PRIORITY_FEE_GWEI`, or the node's suggestion), never above
+//! `ETH_MAX_FEE_GWEI`. Nonces come from the node's pending count once and are
+//! then counted locally, so several archives can wait in the mempool; they
+//! are re-read when the node rejects one. Sent transactions are tracked until
+//! their receipt is `ETH_CONFIRMATIONS` blocks deep, or reverted.
+//!
+//! Without the variables the layer stays simulated.
+//!
+//! The wallet's Ethereum networks use the same pieces: keys derived from the
+//! recovery phrase (`EthSigner::derive`, BIP-32 on `m/44'/60'/0'/0/n`), ETH
+//! and ERC-20 balances read with `eth_getBalance` / `eth_call`, and
+//! transfers built by `EthRpc::prepare_tx`.
+
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use secp256k1::ecdsa::{RecoverableSignature, RecoveryId};
+use secp256k1::{Message, PublicKey, Scalar, Secp256k1, SecretKey};
+use serde::{Serialize, Deserialize};
+use serde_json::{json, Value};
+use sha2::Sha512;
+use sha3::{Digest, Keccak256};
+use tracing::info;
+
+pub type Address = [u8; 20];
+
+/// Prefix of `calldata` mode archives
+pub const CALLDATA_TAG: &[u8] = b"DIVINE";
+pub const ARCHIVE_FUNCTION: &str = "archive(bytes32)";
+pub const ARCHIVED_EVENT: &str = "GenomeArchived(bytes32,address)";
+pub const DEFAULT_MAX_FEE_GWEI: f64 = 100.0;
+/// Headroom added to the node's gas estimate, in percent
+pub const GAS_MARGIN_PERCENT: u64 = 20;
+/// How often the server re-reads receipts of unconfirmed archives
+pub const ETH_CONFIRMATION_CHECK_SECS: u64 = 15;
+pub const RPC_TIMEOUT_SECS: u64 = 15;
+/// Archive transactions remembered before the oldest are dropped
+pub const MAX_TRACKED_TXS: usize = 10_000;
+
+/// Error for Ethereum calls made while the layer is simulated
+/// ETH is counted in wei
+pub const ETH_DECIMALS: u8 = 18;
+/// Gas of a plain ETH transfer to an account
+pub const ETH_TRANSFER_GAS: u64 = 21_000;
+pub const ERC20_TRANSFER: &str = "transfer(address,uint256)";
+pub const ERC20_BALANCE_OF: &str = "balanceOf(address)";
+pub const ERC20_DECIMALS: &str = "decimals()";
+/// Set on hardened BIP-32 indices
+pub const BIP32_HARDENED: u32 = 1 << 31;
+pub const NO_ETHEREUM: &str = "needs a real Ethereum layer (set ETH_RPC_URL and ETH_PRIVATE_KEY)";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EthNetwork {
+    Mainnet,
+    Sepolia,
+}
+
+impl EthNetwork {
+    pub fn chain_id(self) -> u64 {
+        match self {
+            EthNetwork::Mainnet => 1,
+            EthNetwork::Sepolia => 11_155_111,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            EthNetwork::Mainnet => "mainnet",
+            EthNetwork::Sepolia => "sepolia",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "mainnet" => Ok(EthNetwork::Mainnet),
+            "sepolia" => Ok(EthNetwork::Sepolia),
+            _ => Err(format!("Unknown Ethereum network {} (expected mainnet or sepolia)", name)),
+        }
+    }
+
+    /// A public JSON-RPC endpoint of the network
+    pub fn default_rpc_url(self) -> &'static str {
+        match self {
+            EthNetwork::Mainnet => "https://ethereum-rpc.publicnode.com",
+            EthNetwork::Sepolia => "https://ethereum-sepolia-rpc.publicnode.com",
+        }
+    }
+
+    /// Blocks deep a receipt must be before its archive counts as confirmed
+    pub fn default_confirmations(self) -> u64 {
+        match self {
+            EthNetwork::Mainnet => 12,
+            EthNetwork::Sepolia => 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EthArchiveMode {
+    Calldata,
+    Event,
+}
+
+impl EthArchiveMode {
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "calldata" => Ok(EthArchiveMode::Calldata),
+            "event" => Ok(EthArchiveMode::Event),
+            _ => Err(format!("Unknown Ethereum archive mode {} (expected calldata or event)", name)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EthConfig {
+    pub rpc_url: String,
+    pub network: EthNetwork,
+    pub mode: EthArchiveMode,
+    /// The genome registry; `calldata` archives without one go to the
+    /// archiver's own address
+    pub registry: Option<Address>,
+    pub confirmations: u64,
+    pub max_fee_gwei: f64,
+    /// `None` to take the node's `eth_maxPriorityFeePerGas`
+    pub priority_fee_gwei: Option<f64>,
+}
+
+impl EthConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.rpc_url.starts_with("http://") && !self.rpc_url.starts_with("https://") {
+            return Err(format!("ETH_RPC_URL must be http(s)://, not {}", self.rpc_url));
+        }
+        if self.mode == EthArchiveMode::Event && self.registry.is_none() {
+            return Err("Event archives need ETH_REGISTRY_ADDRESS".into());
+        }
+        if self.confirmations == 0 {
+            return Err("ETH_CONFIRMATIONS must be at least 1".into());
+        }
+        if self.max_fee_gwei.is_nan() || self.max_fee_gwei <= 0.0 {
+            return Err("ETH_MAX_FEE_GWEI must be positive".into());
+        }
+        if self.priority_fee_gwei.is_some_and(|fee| fee.is_nan() || fee < 0.0 || fee > self.max_fee_gwei) {
+            return Err("ETH_PRIORITY_FEE_GWEI must be between 0 and ETH_MAX_FEE_GWEI".into());
+        }
+        Ok(())
+    }
+}
+
+pub fn gwei_to_wei(gwei: f64) -> u128 {
+    (gwei * 1e9) as u128
+}
+
+pub fn wei_to_gwei(wei: u128) -> f64 {
+    wei as f64 / 1e9
+}
+
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    Keccak256::digest(data).into()
+}
+
+/// First four bytes of the keccak of a Solidity signature
+pub fn function_selector(signature: &str) -> [u8; 4] {
+    let hash = keccak256(signature.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// Calldata archiving `dna_hash` in `mode`
+pub fn archive_calldata(mode: EthArchiveMode, dna_hash: [u8; 32]) -> Vec<u8> {
+    let prefix = match mode {
+        EthArchiveMode::Calldata => CALLDATA_TAG.to_vec(),
+        EthArchiveMode::Event => function_selector(ARCHIVE_FUNCTION).to_vec(),
+    };
+    [prefix, dna_hash.to_vec()].concat()
+}
+
+/// The DNA hash an archive transaction's calldata carries, in either mode
+pub fn decode_archive_calldata(data: &[u8]) -> Option<[u8; 32]> {
+    let selector = function_selector(ARCHIVE_FUNCTION);
+    let hash = data.strip_prefix(CALLDATA_TAG).or_else(|| data.strip_prefix(&selector[..]))?;
+    hash.try_into().ok()
+}
+
+pub fn parse_address(text: &str) -> Result<Address, String> {
+    hex::decode(text.trim().trim_start_matches("0x")).ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| format!("Invalid Ethereum address {:?}", text))
+}
+
+/// EIP-55 mixed-case checksum address
+pub fn format_address(address: &Address) -> String {
+    let lower = hex::encode(address);
+    let hash = keccak256(lower.as_bytes());
+    let checksummed: String = lower.chars().enumerate().map(|(i, c)| {
+        let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+        if nibble >= 8 { c.to_ascii_uppercase() } else { c }
+    }).collect();
+    format!("0x{}", checksummed)
+}
+
+pub fn address_of(public_key: &PublicKey) -> Address {
+    let hash = keccak256(&public_key.serialize_uncompressed()[1..]);
+    hash[12..].try_into().expect("20 bytes")
+}
+
+/// `bytes` left-padded to a 32-byte ABI word
+fn abi_word(bytes: &[u8]) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[32 - bytes.len()..].copy_from_slice(bytes);
+    word
+}
+
+/// Calldata of an ERC-20 `transfer` of `amount` (the token's smallest unit)
+pub fn erc20_transfer_calldata(to: &Address, amount: u128) -> Vec<u8> {
+    [&function_selector(ERC20_TRANSFER)[..], &abi_word(to), &abi_word(&amount.to_be_bytes())].concat()
+}
+
+pub fn erc20_balance_of_calldata(owner: &Address) -> Vec<u8> {
+    [&function_selector(ERC20_BALANCE_OF)[..], &abi_word(owner)].concat()
+}
+
+/// A `uint256` return word, refused above `u128`
+pub fn decode_uint(word: &[u8]) -> Result<u128, String> {
+    if word.len() != 32 || word[..16].iter().any(|&b| b != 0) {
+        return Err(format!("Not a uint128 word: 0x{}", hex::encode(word)));
+    }
+    Ok(u128::from_be_bytes(word[16..].try_into().expect("16 bytes")))
+}
+
+// ═══════════════════════════════════════════════════════════════
+// RLP & TRANSACTIONS
+// ═══════════════════════════════════════════════════════════════
+
+fn rlp_length(len: usize, offset: u8) -> Vec<u8> {
+    if len < 56 {
+        return vec![offset + len as u8];
+    }
+    let bytes: Vec<u8> = len.to_be_bytes().into_iter().skip_while(|&b| b == 0).collect();
+    [vec![offset + 55 + bytes.len() as u8], bytes].concat()
+}
+
+pub fn rlp_bytes(bytes: &[u8]) -> Vec<u8> {
+    match bytes {
+        [byte] if *byte < 0x80 => vec![*byte],
+        _ => [rlp_length(bytes.len(), 0x80), bytes.to_vec()].concat(),
+    }
+}
+
+/// An integer as its big-endian bytes without leading zeros (0 is empty)
+pub fn rlp_uint(value: u128) -> Vec<u8> {
+    let bytes: Vec<u8> = value.to_be_bytes().into_iter().skip_while(|&b| b == 0).collect();
+    rlp_bytes(&bytes)
+}
+
+/// A list of already encoded items
+pub fn rlp_list(items: &[Vec<u8>]) -> Vec<u8> {
+    let payload = items.concat();
+    [rlp_length(payload.len(), 0xc0), payload].concat()
+}
+
+/// An EIP-1559 (type 2) transaction without an access list
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Eip1559Tx {
+    pub chain_id: u64,
+    pub nonce: u64,
+    pub max_priority_fee_per_gas: u128,
+    pub max_fee_per_gas: u128,
+    pub gas_limit: u64,
+    #[serde(with = "hex::serde")]
+    pub to: Address,
+    pub value: u128,
+    #[serde(with = "hex::serde")]
+    pub data: Vec<u8>,
+}
+
+/// A signed transaction as sent with `eth_sendRawTransaction`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedTx {
+    pub raw: Vec<u8>,
+    pub hash: [u8; 32],
+    /// `r ‖ s ‖ y_parity`
+    pub signature: [u8; 65],
+}
+
+impl Eip1559Tx {
+    fn fields(&self) -> Vec<Vec<u8>> {
+        vec![
+            rlp_uint(self.chain_id as u128),
+            rlp_uint(self.nonce as u128),
+            rlp_uint(self.max_priority_fee_per_gas),
+            rlp_uint(self.max_fee_per_gas),
+            rlp_uint(self.gas_limit as u128),
+            rlp_bytes(&self.to),
+            rlp_uint(self.value),
+            rlp_bytes(&self.data),
+            rlp_list(&[]),
+        ]
+    }
+
+    /// What the sender signs: keccak of `0x02 ‖ rlp(fields)`
+    pub fn signing_hash(&self) -> [u8; 32] {
+        keccak256(&[vec![0x02], rlp_list(&self.fields())].concat())
+    }
+
+    pub fn sign(&self, signer: &EthSigner) -> SignedTx {
+        self.with_signature(signer.sign(self.signing_hash()))
+    }
+
+    /// The transaction carrying `signature` (`r ‖ s ‖ y_parity` over
+    /// `signing_hash`), made elsewhere
+    pub fn with_signature(&self, signature: [u8; 65]) -> SignedTx {
+        let trimmed = |bytes: &[u8]| bytes.iter().copied().skip_while(|&b| b == 0).collect::<Vec<u8>>();
+        let mut fields = self.fields();
+        fields.push(rlp_uint(signature[64] as u128));
+        fields.push(rlp_bytes(&trimmed(&signature[..32])));
+        fields.push(rlp_bytes(&trimmed(&signature[32..64])));
+        let raw = [vec![0x02], rlp_list(&fields)].concat();
+        SignedTx { hash: keccak256(&raw), raw, signature }
+    }
+}
+
+/// The address that made `signature` (`r ‖ s ‖ y_parity`) over `hash`
+pub fn recover_address(hash: [u8; 32], signature: &[u8; 65]) -> Result<Address, String> {
+    let recovery_id = RecoveryId::from_i32(signature[64] as i32).map_err(|e| format!("Bad recovery id: {}", e))?;
+    let signature = RecoverableSignature::from_compact(&signature[..64], recovery_id).map_err(|e| format!("Bad signature: {}", e))?;
+    let public_key = Secp256k1::new().recover_ecdsa(&Message::from_digest(hash), &signature)
+        .map_err(|e| format!("Cannot recover the signer: {}", e))?;
+    Ok(address_of(&public_key))
+}
+
+/// secp256k1 key `path` below a BIP-39 seed, per BIP-32 (`BIP32_HARDENED`
+/// marks hardened indices)
+pub fn bip32_secp256k1(seed: &[u8], path: &[u32]) -> Result<SecretKey, String> {
+    let hmac = |key: &[u8], data: &[u8]| -> [u8; 64] {
+        let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC takes any key");
+        mac.update(data);
+        mac.finalize().into_bytes().into()
+    };
+    let invalid = |_| "The seed derives an invalid key; use the next index".to_string();
+    let master = hmac(b"Bitcoin seed", seed);
+    let mut key = SecretKey::from_slice(&master[..32]).map_err(invalid)?;
+    let mut chain_code = master[32..].to_vec();
+    for &index in path {
+        let data = match index & BIP32_HARDENED {
+            0 => [&PublicKey::from_secret_key(&Secp256k1::new(), &key).serialize()[..], &index.to_be_bytes()].concat(),
+            _ => [&[0u8][..], &key.secret_bytes(), &index.to_be_bytes()].concat(),
+        };
+        let child = hmac(&chain_code, &data);
+        let tweak = Scalar::from_be_bytes(child[..32].try_into().expect("32 bytes")).map_err(|_| "The seed derives an invalid key; use the next index".to_string())?;
+        key = key.add_tweak(&tweak).map_err(invalid)?;
+        chain_code = child[32..].to_vec();
+    }
+    Ok(key)
+}
+
+/// The archiver's Ethereum key; `Debug` shows the address only
+#[derive(Clone)]
+pub struct EthSigner {
+    key: SecretKey,
+    pub address: Address,
+}
+
+impl EthSigner {
+    pub fn from_hex(key: &str) -> Result<Self, String> {
+        let bytes = hex::decode(key.trim().trim_start_matches("0x")).map_err(|_| "ETH_PRIVATE_KEY is not hex".to_string())?;
+        let key = SecretKey::from_slice(&bytes).map_err(|_| "ETH_PRIVATE_KEY is not a secp256k1 key".to_string())?;
+        Ok(Self::from_key(key))
+    }
+
+    fn from_key(key: SecretKey) -> Self {
+        let address = address_of(&PublicKey::from_secret_key(&Secp256k1::new(), &key));
+        Self { key, address }
+    }
+
+    /// Key `account` of a BIP-39 seed on `m/44'/60'/0'/0/<account>`, the
+    /// addresses Ethereum wallets list for the same phrase
+    pub fn derive(seed: &[u8], account: u32) -> Result<Self, String> {
+        let path = [44 | BIP32_HARDENED, 60 | BIP32_HARDENED, BIP32_HARDENED, 0, account];
+        bip32_secp256k1(seed, &path).map(Self::from_key)
+    }
+
+    /// `r ‖ s ‖ y_parity` over `hash`
+    pub fn sign(&self, hash: [u8; 32]) -> [u8; 65] {
+        let signature = Secp256k1::new().sign_ecdsa_recoverable(&Message::from_digest(hash), &self.key);
+        let (recovery_id, compact) = signature.serialize_compact();
+        let mut out = [0u8; 65];
+        out[..64].copy_from_slice(&compact);
+        out[64] = recovery_id.to_i32() as u8;
+        out
+    }
+}
+
+impl std::fmt::Debug for EthSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EthSigner").field("address", &format_address(&self.address)).finish()
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════
+// FEES, NONCES & CONFIRMATIONS
+// ═══════════════════════════════════════════════════════════════
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Eip1559Fees {
+    pub max_fee_per_gas: u128,
+    pub max_priority_fee_per_gas: u128,
+}
+
+impl Eip1559Fees {
+    /// Twice the base fee plus the tip, so the transaction stays includable
+    /// while the base fee rises, capped at `cap_wei`; refused when the cap
+    /// does not even cover the current base fee plus the tip
+    pub fn new(base_fee_wei: u128, priority_wei: u128, cap_wei: u128) -> Result<Self, String> {
+        let needed = base_fee_wei + priority_wei;
+        if needed > cap_wei {
+            return Err(format!(
+                "Insufficient fee cap: {:.2} gwei base + {:.2} tip is above the {:.2} gwei maximum",
+                wei_to_gwei(base_fee_wei), wei_to_gwei(priority_wei), wei_to_gwei(cap_wei)
+            ));
+        }
+        Ok(Self { max_fee_per_gas: (needed + base_fee_wei).min(cap_wei), max_priority_fee_per_gas: priority_wei })
+    }
+}
+
+pub fn with_gas_margin(gas: u64) -> u64 {
+    gas + gas * GAS_MARGIN_PERCENT / 100
+}
+
+/// Nonces of the archiver's transactions, counted locally between syncs
+#[derive(Debug, Clone, Default)]
+pub struct NonceManager {
+    next: Option<u64>,
+}
+
+impl NonceManager {
+    /// The nonce to use next, `None` until read from the node
+    pub fn next(&self) -> Option<u64> {
+        self.next
+    }
+
+    /// A transaction with `nonce` was accepted by the node
+    pub fn sent(&mut self, nonce: u64) {
+        self.next = Some(self.next.map_or(nonce + 1, |next| next.max(nonce + 1)));
+    }
+
+    /// Re-read the next nonce from the node
+    pub fn reset(&mut self) {
+        self.next = None;
+    }
+}
+
+/// Whether a node's rejection means the local nonce is off
+pub fn is_nonce_error(error: &str) -> bool {
+    let error = error.to_ascii_lowercase();
+    ["nonce too low", "nonce too high", "already known", "replacement transaction underpriced"].iter().any(|e| error.contains(e))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EthTxStatus {
+    /// Sent, not in a block yet
+    Pending,
+    /// In a block, not yet `confirmations` deep
+    Included,
+    Confirmed,
+    Reverted,
+}
+
+/// What `eth_getTransactionReceipt` says about a mined transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Receipt {
+    pub block_number: u64,
+    pub success: bool,
+    pub gas_used: u64,
+    pub effective_gas_price: u128,
+}
+
+/// An archive transaction, tracked until confirmed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EthArchiveTx {
+    pub tx_hash: String,
+    pub genome_id: i64,
+    pub dna_hash: String,
+    pub network: EthNetwork,
+    pub mode: EthArchiveMode,
+    pub from: String,
+    pub to: String,
+    pub nonce: u64,
+    pub gas_limit: u64,
+    pub max_fee_gwei: f64,
+    pub priority_fee_gwei: f64,
+    pub status: EthTxStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+    pub confirmations: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_used: Option<u64>,
+    /// What the archive cost, once mined
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_gwei: Option<f64>,
+    pub sent_at: i64,
+    pub updated_at: i64,
+}
+
+impl EthArchiveTx {
+    pub fn is_settled(&self) -> bool {
+        matches!(self.status, EthTxStatus::Confirmed | EthTxStatus::Reverted)
+    }
+
+    /// Update from the latest receipt (`None`: not mined yet) at block
+    /// `latest_block`, confirmed once `required` blocks deep
+    pub fn apply_receipt(&mut self, receipt: Option<Receipt>, latest_block: u64, required: u64, now: i64) {
+        self.updated_at = now;
+        let Some(receipt) = receipt else {
+            // A reorg can take a mined transaction back to the mempool
+            self.status = EthTxStatus::Pending;
+            self.block_number = None;
+            self.confirmations = 0;
+            return;
+        };
+        self.block_number = Some(receipt.block_number);
+        self.confirmations = latest_block.saturating_sub(receipt.block_number) + 1;
+        self.gas_used = Some(receipt.gas_used);
+        self.fee_gwei = Some(wei_to_gwei(receipt.gas_used as u128 * receipt.effective_gas_price));
+        self.status = if !receipt.success {
+            EthTxStatus::Reverted
+        } else if self.confirmations >= required {
+            EthTxStatus::Confirmed
+        } else {
+            EthTxStatus::Included
+        };
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════
+// JSON-RPC
+// ═══════════════════════════════════════════════════════════════
+
+/// A hex quantity (`"0x1a"`) from a JSON-RPC reply
+pub fn parse_quantity(value: &Value) -> Result<u128, String> {
+    value.as_str()
+        .and_then(|s| s.strip_prefix("0x"))
+        .and_then(|digits| u128::from_str_radix(if digits.is_empty() { "0" } else { digits }, 16).ok())
+        .ok_or_else(|| format!("Invalid quantity {}", value))
+}
+
+#[derive(Clone)]
+pub struct EthRpc {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl EthRpc {
+    pub fn new(url: &str) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(RPC_TIMEOUT_SECS))
+            .user_agent(format!("divine-agi/{}", crate::VERSION))
+            .build()
+            .unwrap_or_default();
+        Self { client, url: url.to_string() }
+    }
+
+    async fn call(&self, method: &str, params: Value) -> Result<Value, String> {
+        let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let response = self.client.post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.to_string())
+            .send().await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| format!("{}: {}", method, e))?;
+        let bytes = response.bytes().await.map_err(|e| format!("{}: {}", method, e))?;
+        let mut reply: Value = serde_json::from_slice(&bytes).map_err(|e| format!("{}: {}", method, e))?;
+        if let Some(error) = reply.get("error") {
+            return Err(format!("{}: {}", method, error["message"].as_str().unwrap_or("error")));
+        }
+        Ok(reply["result"].take())
+    }
+
+    pub async fn chain_id(&self) -> Result<u64, String> {
+        parse_quantity(&self.call("eth_chainId", json!([])).await?).map(|id| id as u64)
+    }
+
+    pub async fn block_number(&self) -> Result<u64, String> {
+        parse_quantity(&self.call("eth_blockNumber", json!([])).await?).map(|n| n as u64)
+    }
+
+    pub async fn base_fee(&self) -> Result<u128, String> {
+        let block = self.call("eth_getBlockByNumber", json!(["latest", false])).await?;
+        parse_quantity(&block["baseFeePerGas"]).map_err(|_| "The node's latest block has no base fee (pre-London chain?)".to_string())
+    }
+
+    pub async fn max_priority_fee(&self) -> Result<u128, String> {
+        parse_quantity(&self.call("eth_maxPriorityFeePerGas", json!([])).await?)
+    }
+
+    /// Transactions sent from `address`, mempool included
+    pub async fn pending_nonce(&self, address: &Address) -> Result<u64, String> {
+        parse_quantity(&self.call("eth_getTransactionCount", json!([format_address(address), "pending"])).await?).map(|n| n as u64)
+    }
+
+    pub async fn estimate_gas(&self, from: &Address, to: &Address, data: &[u8]) -> Result<u64, String> {
+        let call = json!({ "from": format_address(from), "to": format_address(to), "data": format!("0x{}", hex::encode(data)) });
+        parse_quantity(&self.call("eth_estimateGas", json!([call])).await?).map(|gas| gas as u64)
+    }
+
+    /// Wei held by `address`
+    pub async fn balance(&self, address: &Address) -> Result<u128, String> {
+        parse_quantity(&self.call("eth_getBalance", json!([format_address(address), "latest"])).await?)
+    }
+
+    /// What a read-only call of `to` with `data` returns
+    pub async fn eth_call(&self, to: &Address, data: &[u8]) -> Result<Vec<u8>, String> {
+        let call = json!({ "to": format_address(to), "data": format!("0x{}", hex::encode(data)) });
+        let reply = self.call("eth_call", json!([call, "latest"])).await?;
+        let output = reply.as_str().and_then(|output| output.strip_prefix("0x")).ok_or("eth_call: no output")?;
+        hex::decode(output).map_err(|e| format!("eth_call: {}", e))
+    }
+
+    /// What `owner` holds of ERC-20 `token`, in its smallest unit
+    pub async fn erc20_balance(&self, token: &Address, owner: &Address) -> Result<u128, String> {
+        decode_uint(&self.eth_call(token, &erc20_balance_of_calldata(owner)).await?)
+    }
+
+    pub async fn erc20_decimals(&self, token: &Address) -> Result<u8, String> {
+        let decimals = decode_uint(&self.eth_call(token, &function_selector(ERC20_DECIMALS)).await?)?;
+        u8::try_from(decimals).map_err(|_| format!("{} reports {} decimals", format_address(token), decimals))
+    }
+
+    /// An unsigned transaction from `from` sending `value` wei and `data` to
+    /// `to` on chain `chain_id`, at the node's pending nonce and current fees
+    /// (at most `max_fee_wei` per gas); refused when the node serves another
+    /// chain
+    pub async fn prepare_tx(&self, chain_id: u64, from: &Address, to: &Address, value: u128, data: Vec<u8>, max_fee_wei: u128) -> Result<Eip1559Tx, String> {
+        let node_chain = self.chain_id().await?;
+        if node_chain != chain_id {
+            return Err(format!("The RPC endpoint serves chain {}, not {}", node_chain, chain_id));
+        }
+        let gas_limit = match data.is_empty() {
+            true => ETH_TRANSFER_GAS,
+            false => with_gas_margin(self.estimate_gas(from, to, &data).await?),
+        };
+        let fees = Eip1559Fees::new(self.base_fee().await?, self.max_priority_fee().await?, max_fee_wei)?;
+        Ok(Eip1559Tx {
+            chain_id,
+            nonce: self.pending_nonce(from).await?,
+            max_priority_fee_per_gas: fees.max_priority_fee_per_gas,
+            max_fee_per_gas: fees.max_fee_per_gas,
+            gas_limit,
+            to: *to,
+            value,
+            data,
+        })
+    }
+
+    pub async fn send_raw_transaction(&self, raw: &[u8]) -> Result<String, String> {
+        let hash = self.call("eth_sendRawTransaction", json!([format!("0x{}", hex::encode(raw))])).await?;
+        hash.as_str().map(str::to_string).ok_or_else(|| "eth_sendRawTransaction: no hash".into())
+    }
+
+    /// The calldata of `tx_hash`, `None` if the node does not know it
+    pub async fn transaction_input(&self, tx_hash: &str) -> Result<Option<Vec<u8>>, String> {
+        let tx = self.call("eth_getTransactionByHash", json!([tx_hash])).await?;
+        if tx.is_null() {
+            return Ok(None);
+        }
+        let input = tx["input"].as_str().and_then(|input| input.strip_prefix("0x")).ok_or("eth_getTransactionByHash: no input")?;
+        hex::decode(input).map(Some).map_err(|e| format!("eth_getTransactionByHash: {}", e))
+    }
+
+    pub async fn receipt(&self, tx_hash: &str) -> Result<Option<Receipt>, String> {
+        let receipt = self.call("eth_getTransactionReceipt", json!([tx_hash])).await?;
+        if receipt.is_null() {
+            return Ok(None);
+        }
+        Ok(Some(Receipt {
+            block_number: parse_quantity(&receipt["blockNumber"])? as u64,
+            success: parse_quantity(&receipt["status"])? == 1,
+            gas_used: parse_quantity(&receipt["gasUsed"])? as u64,
+            effective_gas_price: parse_quantity(&receipt["effectiveGasPrice"]).unwrap_or(0),
+        }))
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════
+// ARCHIVING LAYER
+// ═══════════════════════════════════════════════════════════════
+
+/// The real Ethereum layer: key, node, nonces and sent archives
+pub struct EthereumLayer {
+    pub config: EthConfig,
+    signer: EthSigner,
+    rpc: EthRpc,
+    nonces: NonceManager,
+    chain_checked: bool,
+    /// Sent archives, oldest first (at most `MAX_TRACKED_TXS`)
+    pub txs: Vec<EthArchiveTx>,
+}
+
+impl EthereumLayer {
+    pub fn new(config: EthConfig, signer: EthSigner) -> Self {
+        let rpc = EthRpc::new(&config.rpc_url);
+        Self { config, signer, rpc, nonces: NonceManager::default(), chain_checked: false, txs: Vec::new() }
+    }
+
+    /// The layer the environment configures, `None` to stay simulated
+    pub fn from_env() -> Result<Option<Self>, String> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        let (Some(rpc_url), Some(key)) = (var("ETH_RPC_URL"), var("ETH_PRIVATE_KEY")) else {
+            return match var("ETH_RPC_URL").or(var("ETH_PRIVATE_KEY")) {
+                Some(_) => Err("Ethereum archiving needs both ETH_RPC_URL and ETH_PRIVATE_KEY".into()),
+                None => Ok(None),
+            };
+        };
+        let network = var("ETH_NETWORK").map_or(Ok(EthNetwork::Sepolia), |name| EthNetwork::from_name(&name))?;
+        let number = |name: &str| var(name).map(|v| v.parse::<f64>().map_err(|_| format!("Invalid {}: {}", name, v))).transpose();
+        let config = EthConfig {
+            rpc_url,
+            network,
+            mode: var("ETH_ARCHIVE_MODE").map_or(Ok(EthArchiveMode::Calldata), |name| EthArchiveMode::from_name(&name))?,
+            registry: var("ETH_REGISTRY_ADDRESS").map(|a| parse_address(&a)).transpose()?,
+            confirmations: match var("ETH_CONFIRMATIONS") {
+                Some(v) => v.parse().map_err(|_| format!("Invalid ETH_CONFIRMATIONS: {}", v))?,
+                None => network.default_confirmations(),
+            },
+            max_fee_gwei: number("ETH_MAX_FEE_GWEI")?.unwrap_or(DEFAULT_MAX_FEE_GWEI),
+            priority_fee_gwei: number("ETH_PRIORITY_FEE_GWEI")?,
+        };
+        config.validate()?;
+        Ok(Some(Self::new(config, EthSigner::from_hex(&key)?)))
+    }
+
+    pub fn address(&self) -> Address {
+        self.signer.address
+    }
+
+    /// Where archives are sent: the registry, or the archiver itself
+    pub fn recipient(&self) -> Address {
+        self.config.registry.unwrap_or(self.signer.address)
+    }
+
+    /// Sign and send the archive of `dna_hash`; returns the tx hash once
+    /// the node accepted it (confirmations are tracked afterwards)
+    pub async fn archive(&mut self, genome_id: i64, dna_hash: [u8; 32], now: i64) -> Result<String, String> {
+        if !self.chain_checked {
+            let chain_id = self.rpc.chain_id().await?;
+            if chain_id != self.config.network.chain_id() {
+                return Err(format!("ETH_RPC_URL serves chain {}, not {} ({})", chain_id, self.config.network.chain_id(), self.config.network.name()));
+            }
+            self.chain_checked = true;
+        }
+
+        let (from, to) = (self.signer.address, self.recipient());
+        let data = archive_calldata(self.config.mode, dna_hash);
+        let gas_limit = with_gas_margin(self.rpc.estimate_gas(&from, &to, &data).await?);
+        let priority = match self.config.priority_fee_gwei {
+            Some(gwei) => gwei_to_wei(gwei),
+            None => self.rpc.max_priority_fee().await?,
+        };
+        let fees = Eip1559Fees::new(self.rpc.base_fee().await?, priority, gwei_to_wei(self.config.max_fee_gwei))?;
+        let nonce = match self.nonces.next() {
+            Some(nonce) => nonce,
+            None => self.rpc.pending_nonce(&from).await?,
+        };
+
+        let tx = Eip1559Tx {
+            chain_id: self.config.network.chain_id(),
+            nonce,
+            max_priority_fee_per_gas: fees.max_priority_fee_per_gas,
+            max_fee_per_gas: fees.max_fee_per_gas,
+            gas_limit,
+            to,
+            value: 0,
+            data,
+        };
+        let signed = tx.sign(&self.signer);
+        if let Err(e) = self.rpc.send_raw_transaction(&signed.raw).await {
+            if is_nonce_error(&e) {
+                self.nonces.reset();
+            }
+            return Err(e);
+        }
+        self.nonces.sent(nonce);
+
+        let tx_hash = format!("0x{}", hex::encode(signed.hash));
+        info!("🔷 Ethereum archive sent: {} | nonce {} | max fee {:.2} gwei", tx_hash, nonce, wei_to_gwei(fees.max_fee_per_gas));
+        if self.txs.len() >= MAX_TRACKED_TXS {
+            self.txs.remove(0);
+        }
+        self.txs.push(EthArchiveTx {
+            tx_hash: tx_hash.clone(),
+            genome_id,
+            dna_hash: hex::encode(dna_hash),
+            network: self.config.network,
+            mode: self.config.mode,
+            from: format_address(&from),
+            to: format_address(&to),
+            nonce,
+            gas_limit,
+            max_fee_gwei: wei_to_gwei(fees.max_fee_per_gas),
+            priority_fee_gwei: wei_to_gwei(fees.max_priority_fee_per_gas),
+            status: EthTxStatus::Pending,
+            block_number: None,
+            confirmations: 0,
+            gas_used: None,
+            fee_gwei: None,
+            sent_at: now,
+            updated_at: now,
+        });
+        Ok(tx_hash)
+    }
+
+    /// The DNA hash archived by `tx_hash`, read back from the chain; the
+    /// transaction must be mined and not reverted
+    pub async fn read_archive(&self, tx_hash: &str) -> Result<[u8; 32], String> {
+        let input = self.rpc.transaction_input(tx_hash).await?
+            .ok_or_else(|| format!("Ethereum transaction {} not found on {}", tx_hash, self.config.network.name()))?;
+        match self.rpc.receipt(tx_hash).await? {
+            None => return Err(format!("Ethereum transaction {} is not mined yet", tx_hash)),
+            Some(receipt) if !receipt.success => return Err(format!("Ethereum transaction {} reverted", tx_hash)),
+            Some(_) => {}
+        }
+        decode_archive_calldata(&input).ok_or_else(|| format!("Ethereum transaction {} carries no genome archive", tx_hash))
+    }
+
+    /// Re-read the receipt of `tx_hash`
+    pub async fn refresh_tx(&mut self, tx_hash: &str, now: i64) -> Result<EthArchiveTx, String> {
+        let index = self.txs.iter().rposition(|tx| tx.tx_hash.eq_ignore_ascii_case(tx_hash))
+            .ok_or_else(|| format!("Ethereum archive {} not found", tx_hash))?;
+        let latest = self.rpc.block_number().await?;
+        let receipt = self.rpc.receipt(&self.txs[index].tx_hash).await?;
+        self.txs[index].apply_receipt(receipt, latest, self.config.confirmations, now);
+        Ok(self.txs[index].clone())
+    }
+
+    /// Re-read the receipts of every archive not yet confirmed or reverted;
+    /// returns how many settled
+    pub async fn refresh(&mut self, now: i64) -> Result<usize, String> {
+        if self.txs.iter().all(EthArchiveTx::is_settled) {
+            return Ok(0);
+        }
+        let latest = self.rpc.block_number().await?;
+        let mut settled = 0;
+        for tx in self.txs.iter_mut().filter(|tx| !tx.is_settled()) {
+            let receipt = self.rpc.receipt(&tx.tx_hash).await?;
+            tx.apply_receipt(receipt, latest, self.config.confirmations, now);
+            settled += tx.is_settled() as usize;
+        }
+        Ok(settled)
+    }
+}
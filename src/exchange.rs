@@ -3,19 +3,54 @@
 //! RSM-COIN: $88,000 USD (защита до $1,000,000)
 //! Total Supply: 10 QUADRILLION (10^16)
 //! Features: Burn mechanism, Debt absorption tracker, Wallet balances
+//!
+//! Transfers out of a wallet can be guarded by a `SpendingPolicy`: a rolling
+//! 24h spend limit, an allow-list of recipients, and a confirmation callback
+//! that must approve transfers above a threshold (refused when none is set).
+//!
+//! `subscribe()` streams `WalletEvent`s: every balance change, every
+//! confirmed transfer, and `LowBalance` when a wallet drops below its
+//! alert threshold. Exchange transfers settle in the call that makes them,
+//! so there is no separate submitted state to report.
+//!
+//! Limit and market orders trade on the `OrderBook`: sell orders hold their
+//! RSM until they fill or are cancelled, and every fill credits the buyer and
+//! is recorded as a `Trade` transaction. `simulate_swap` previews a swap
+//! between RSM, USD and the oracle's coins without trading (`swap`).
+//!
+//! Settlements can go through an `Escrow` that pays out only once its
+//! consensus block is final and refunds the payer on timeout (`escrow`).
+//!
+//! Fills pay maker and taker fees into an accrual wallet, distributed
+//! periodically to the burn, the treasury and makers (`fees`).
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
 use serde::{Serialize, Deserialize};
 use num_bigint::BigUint;
 use num_traits::{ToPrimitive, Zero};
 use chrono::Utc;
+use tokio::sync::broadcast;
 use tracing::info;
 
+use crate::consensus::{ProofOfConsciousness, EVENT_CHANNEL_CAPACITY};
+use crate::escrow::{self, BlockRef, Escrow, EscrowStatus, EscrowTerms};
+use crate::fees::{FeeAccounts, FeeRates, FeeReport, FeeSchedule, LpReward, OrderFees, FEE_ACCRUAL_WALLET};
+use crate::marketplace::check_wallet;
+use crate::oracle::{Pair, ReferencePrice};
+use crate::order_book::{Order, OrderBook, OrderStatus, Side, Trade};
+use crate::swap::{self, Asset, SwapMarket, SwapQuote};
+use crate::wallet::PaymentRequest;
+
 pub const RSM_PRICE_USD: f64 = 88_000.0;
 pub const RSM_PRICE_MAX: f64 = 1_000_000.0;
 pub const RSM_TOTAL_SUPPLY: u128 = 10_000_000_000_000_000; // 10 quadrillion
 pub const FOUNDER_RATIO: f64 = 1.0 / 7.0;
 pub const WORLD_DEBT_USD: f64 = 350_000_000_000_000.0; // $350 trillion
+/// Window of a policy's daily spend limit
+pub const SPEND_WINDOW_SECS: i64 = 24 * 3600;
+/// Share of RSM sold to the desk (`sell_rsm`) burned as a trading fee
+pub const DESK_SELL_FEE: f64 = 0.001;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RSMExchange {
@@ -32,9 +67,72 @@ pub struct RSMExchange {
     pub total_transactions: u64,
     pub total_burns: u64,
     pub balances: HashMap<String, f64>,
+    #[serde(default)]
+    pub policies: HashMap<String, SpendingPolicy>,
+    #[serde(skip)]
+    confirmations: HashMap<String, TransferConfirmation>,
+    /// Balance below which a wallet's `LowBalance` event fires
+    #[serde(default)]
+    pub low_balance_alerts: HashMap<String, f64>,
+    #[serde(skip, default = "wallet_event_channel")]
+    events: broadcast::Sender<WalletEvent>,
+    #[serde(default)]
+    pub order_book: OrderBook,
+    /// USD per BTC, ETH and SOL from the oracle, for `simulate_swap`
+    #[serde(skip)]
+    pub reference_prices: HashMap<Pair, f64>,
+    #[serde(default)]
+    pub escrows: BTreeMap<u64, Escrow>,
+    #[serde(default)]
+    pub fees: FeeAccounts,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Balance changes pushed to `subscribe()` receivers
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum WalletEvent {
+    BalanceChanged { wallet: String, previous_rsm: f64, balance_rsm: f64 },
+    TransferConfirmed(Transaction),
+    /// Balance crossed below the wallet's alert threshold
+    LowBalance { wallet: String, balance_rsm: f64, threshold_rsm: f64 },
+}
+
+fn wallet_event_channel() -> broadcast::Sender<WalletEvent> {
+    broadcast::channel(EVENT_CHANNEL_CAPACITY).0
+}
+
+/// Guardrails on transfers out of one wallet; unset fields allow everything
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpendingPolicy {
+    /// Maximum RSM sent in any `SPEND_WINDOW_SECS`
+    pub daily_limit_rsm: Option<f64>,
+    /// Only these recipients may be paid
+    pub allowed_recipients: Option<Vec<String>>,
+    /// Transfers above this need the wallet's confirmation callback
+    pub confirm_above_rsm: Option<f64>,
+}
+
+/// Transfer awaiting a policy confirmation
+#[derive(Debug, Clone)]
+pub struct TransferProposal<'a> {
+    pub from: &'a str,
+    pub to: &'a str,
+    pub amount_rsm: f64,
+    pub memo: Option<&'a str>,
+}
+
+type ConfirmFn = dyn Fn(&TransferProposal) -> bool + Send + Sync;
+
+#[derive(Clone)]
+struct TransferConfirmation(Arc<ConfirmFn>);
+
+impl std::fmt::Debug for TransferConfirmation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("TransferConfirmation")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Transaction {
     pub id: u64,
     pub tx_type: TransactionType,
@@ -47,6 +145,9 @@ pub struct Transaction {
     pub timestamp: i64,
     pub status: TxStatus,
     pub hash: String,
+    /// Payment reference carried over from a payment request
+    #[serde(default)]
+    pub memo: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -70,6 +171,10 @@ pub enum TransactionType {
     GenomeStake,
     Meiosis,
     LNBroadcast,
+    /// Order book fill, seller to buyer
+    Trade,
+    /// Maker or taker fee of a fill, to the fee accrual wallet
+    Fee,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -114,15 +219,45 @@ impl RSMExchange {
             total_transactions: 0,
             total_burns: 0,
             balances: HashMap::new(),
+            policies: HashMap::new(),
+            confirmations: HashMap::new(),
+            low_balance_alerts: HashMap::new(),
+            events: wallet_event_channel(),
+            order_book: OrderBook::new(),
+            reference_prices: HashMap::new(),
+            escrows: BTreeMap::new(),
+            fees: FeeAccounts::default(),
         }
     }
 
-    fn get_balance(&self, wallet: &str) -> f64 {
+    pub fn get_balance(&self, wallet: &str) -> f64 {
         *self.balances.get(wallet).unwrap_or(&0.0)
     }
 
     fn set_balance(&mut self, wallet: &str, amount: f64) {
-        self.balances.insert(wallet.to_string(), amount);
+        let previous = self.balances.insert(wallet.to_string(), amount).unwrap_or(0.0);
+        self.emit(WalletEvent::BalanceChanged { wallet: wallet.to_string(), previous_rsm: previous, balance_rsm: amount });
+
+        if let Some(&threshold) = self.low_balance_alerts.get(wallet) {
+            if amount < threshold && previous >= threshold {
+                self.emit(WalletEvent::LowBalance { wallet: wallet.to_string(), balance_rsm: amount, threshold_rsm: threshold });
+            }
+        }
+    }
+
+    fn emit(&self, event: WalletEvent) {
+        // No subscribers is fine
+        let _ = self.events.send(event);
+    }
+
+    /// Receive wallet events from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<WalletEvent> {
+        self.events.subscribe()
+    }
+
+    /// Fire `LowBalance` when `wallet` drops below `threshold_rsm`
+    pub fn set_low_balance_alert(&mut self, wallet: &str, threshold_rsm: f64) {
+        self.low_balance_alerts.insert(wallet.to_string(), threshold_rsm);
     }
 
     fn generate_tx_hash(&self) -> String {
@@ -214,6 +349,7 @@ impl RSMExchange {
             timestamp: Utc::now().timestamp(),
             status: TxStatus::Confirmed,
             hash: self.generate_tx_hash(),
+            memo: None,
         };
 
         info!("💸 BUY: {:.6} RSM for ${:.2} | Debt absorbed: ${:.2}", 
@@ -231,8 +367,7 @@ impl RSMExchange {
 
         let usd_amount = rsm_amount * self.price_usd;
 
-        // Burn 0.1% fee
-        let fee = rsm_amount * 0.001;
+        let fee = rsm_amount * DESK_SELL_FEE;
         self.burn(fee, BurnReason::TradingFee, None, consciousness, consciousness);
 
         self.set_balance(seller, balance - rsm_amount);
@@ -251,6 +386,7 @@ impl RSMExchange {
             timestamp: Utc::now().timestamp(),
             status: TxStatus::Confirmed,
             hash: self.generate_tx_hash(),
+            memo: None,
         };
 
         info!("💰 SELL: {:.6} RSM for ${:.2} (fee burned: {:.6})", rsm_amount - fee, usd_amount, fee);
@@ -259,10 +395,80 @@ impl RSMExchange {
     }
 
     pub fn transfer(&mut self, from: &str, to: &str, amount: f64) -> Option<Transaction> {
+        self.transfer_checked(from, to, amount).ok()
+    }
+
+    /// Transfer, with the reason when the balance or the sender's policy refuses it
+    pub fn transfer_checked(&mut self, from: &str, to: &str, amount: f64) -> Result<Transaction, String> {
+        self.transfer_with_memo(from, to, amount, None)
+    }
+
+    /// Pay a payment request (see `DivineWallet::payment_request`) from `from`
+    pub fn pay_request(&mut self, from: &str, request: &PaymentRequest) -> Result<Transaction, String> {
+        self.transfer_with_memo(from, &request.address, request.amount_rsm, request.memo.clone())
+    }
+
+    pub fn set_policy(&mut self, wallet: &str, policy: SpendingPolicy) {
+        self.policies.insert(wallet.to_string(), policy);
+    }
+
+    /// Callback approving `wallet`'s transfers above its `confirm_above_rsm`
+    pub fn set_confirmation<F>(&mut self, wallet: &str, confirm: F)
+    where
+        F: Fn(&TransferProposal) -> bool + Send + Sync + 'static,
+    {
+        self.confirmations.insert(wallet.to_string(), TransferConfirmation(Arc::new(confirm)));
+    }
+
+    /// RSM sent by `wallet` in the last `SPEND_WINDOW_SECS`
+    pub fn spent_last_day(&self, wallet: &str) -> f64 {
+        let since = Utc::now().timestamp() - SPEND_WINDOW_SECS;
+        self.transactions.iter().rev()
+            .take_while(|tx| tx.timestamp > since)
+            .filter(|tx| tx.tx_type == TransactionType::Transfer && tx.from_address == wallet)
+            .map(|tx| tx.amount_rsm)
+            .sum()
+    }
+
+    fn check_policy(&self, proposal: &TransferProposal) -> Result<(), String> {
+        let Some(policy) = self.policies.get(proposal.from) else {
+            return Ok(());
+        };
+        if let Some(allowed) = &policy.allowed_recipients {
+            if !allowed.iter().any(|r| r == proposal.to) {
+                return Err(format!("Recipient {} is not allow-listed for {}", proposal.to, proposal.from));
+            }
+        }
+        if let Some(limit) = policy.daily_limit_rsm {
+            let spent = self.spent_last_day(proposal.from);
+            if spent + proposal.amount_rsm > limit {
+                return Err(format!(
+                    "Daily limit of {} RSM exceeded ({} RSM already sent in 24h)", limit, spent
+                ));
+            }
+        }
+        if let Some(threshold) = policy.confirm_above_rsm {
+            if proposal.amount_rsm > threshold {
+                let confirmed = self.confirmations.get(proposal.from).is_some_and(|c| (c.0)(proposal));
+                if !confirmed {
+                    return Err(format!("Transfers above {} RSM need confirmation", threshold));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Transfer carrying a payment reference, subject to the sender's policy;
+    /// the amount must be a positive number of RSM
+    pub fn transfer_with_memo(&mut self, from: &str, to: &str, amount: f64, memo: Option<String>) -> Result<Transaction, String> {
+        if !amount.is_finite() || amount <= 0.0 {
+            return Err(format!("Invalid amount {}: transfers move a positive number of RSM", amount));
+        }
         let from_balance = self.get_balance(from);
         if from_balance < amount {
-            return None;
+            return Err("Insufficient balance".to_string());
         }
+        self.check_policy(&TransferProposal { from, to, amount_rsm: amount, memo: memo.as_deref() })?;
 
         self.set_balance(from, from_balance - amount);
         let to_balance = self.get_balance(to);
@@ -281,11 +487,160 @@ impl RSMExchange {
             timestamp: Utc::now().timestamp(),
             status: TxStatus::Confirmed,
             hash: self.generate_tx_hash(),
+            memo,
         };
 
         info!("📤 TRANSFER: {:.6} RSM {} → {}", amount, from, to);
         self.transactions.push(tx.clone());
-        Some(tx)
+        self.emit(WalletEvent::TransferConfirmed(tx.clone()));
+        Ok(tx)
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // ORDER BOOK
+    // ═══════════════════════════════════════════════════════════════
+
+    /// Place a limit order; a sell order's RSM is held until it fills or
+    /// is cancelled
+    pub fn place_order(&mut self, wallet: &str, side: Side, price_usd: f64, amount_rsm: f64) -> Result<OrderPlacement, String> {
+        OrderBook::validate(price_usd, amount_rsm)?;
+        self.execute_order(wallet, side, amount_rsm, |book, now| book.place(wallet, side, price_usd, amount_rsm, now))
+    }
+
+    /// Place a market order; the part the book cannot fill is cancelled
+    /// (and a sell order's share of it released)
+    pub fn place_market_order(&mut self, wallet: &str, side: Side, amount_rsm: f64) -> Result<OrderPlacement, String> {
+        OrderBook::validate_amount(amount_rsm)?;
+        self.execute_order(wallet, side, amount_rsm, |book, now| book.place_market(wallet, side, amount_rsm, now))
+    }
+
+    /// Hold a sell order's RSM and its fees, `place` the order, release
+    /// what was cancelled instead of resting, and settle the fills
+    fn execute_order(
+        &mut self,
+        wallet: &str,
+        side: Side,
+        amount_rsm: f64,
+        place: impl FnOnce(&mut OrderBook, i64) -> Result<(Order, Vec<Trade>), String>,
+    ) -> Result<OrderPlacement, String> {
+        let now = Utc::now().timestamp();
+        let rates = self.fees.rates(wallet, now);
+        let fee_hold = if side == Side::Sell { amount_rsm * rates.taker_fee } else { 0.0 };
+        if side == Side::Sell {
+            let balance = self.get_balance(wallet);
+            if balance < amount_rsm + fee_hold {
+                return Err("Insufficient balance".to_string());
+            }
+            self.set_balance(wallet, balance - amount_rsm - fee_hold);
+        }
+
+        let placed = place(&mut self.order_book, now);
+        let (released, fees_left) = match &placed {
+            Err(_) => (amount_rsm, fee_hold),
+            Ok((order, trades)) => {
+                let used: f64 = trades.iter().map(|trade| trade.amount_rsm * rates.taker_fee).sum();
+                let fees_left = (fee_hold - used).max(0.0);
+                if order.is_open() {
+                    self.fees.orders.insert(order.id, OrderFees { rates, held_rsm: fees_left });
+                    (0.0, 0.0)
+                } else if order.status == OrderStatus::Cancelled {
+                    (order.remaining_rsm(), fees_left)
+                } else {
+                    (0.0, fees_left)
+                }
+            }
+        };
+        if side == Side::Sell && released + fees_left > 0.0 {
+            let balance = self.get_balance(wallet);
+            self.set_balance(wallet, balance + released + fees_left);
+        }
+        let (order, trades) = placed?;
+        let transactions = trades.iter().flat_map(|trade| self.settle_trade(trade, rates)).collect();
+        info!("📒 {:?} {:?} order #{}: {:.6} RSM @ ${:.2} | {} fills, {:?}",
+              order.order_type, side, order.id, amount_rsm, order.price_usd, trades.len(), order.status);
+        Ok(OrderPlacement { order, trades, transactions })
+    }
+
+    /// Cancel an open order, releasing a sell order's remaining RSM and
+    /// its fee hold
+    pub fn cancel_order(&mut self, id: u64) -> Result<Order, String> {
+        let order = self.order_book.cancel(id, Utc::now().timestamp())?;
+        let held = self.fees.orders.remove(&id).map_or(0.0, |fees| fees.held_rsm);
+        if order.side == Side::Sell {
+            let balance = self.get_balance(&order.wallet);
+            self.set_balance(&order.wallet, balance + order.remaining_rsm() + held);
+        }
+        Ok(order)
+    }
+
+    /// Credit the buyer of a fill (the seller's RSM is already held) and
+    /// collect both fees: the buyer's out of what it receives, the seller's
+    /// out of its hold. `taker` are the incoming order's rates
+    fn settle_trade(&mut self, trade: &Trade, taker: FeeRates) -> Vec<Transaction> {
+        let (maker_id, maker_wallet, taker_wallet) = match trade.taker_side {
+            Side::Buy => (trade.sell_order_id, &trade.seller, &trade.buyer),
+            Side::Sell => (trade.buy_order_id, &trade.buyer, &trade.seller),
+        };
+        // Orders restored from before fees pay none
+        let maker = self.fees.orders.get(&maker_id).copied().unwrap_or_default();
+        let maker_fee = trade.amount_rsm * maker.rates.maker_fee;
+        let taker_fee = trade.amount_rsm * taker.taker_fee;
+        let buyer_fee = if trade.taker_side == Side::Buy { taker_fee } else { maker_fee };
+
+        let balance = self.get_balance(&trade.buyer);
+        self.set_balance(&trade.buyer, balance + trade.amount_rsm - buyer_fee);
+        let accrued = self.get_balance(FEE_ACCRUAL_WALLET);
+        self.set_balance(FEE_ACCRUAL_WALLET, accrued + maker_fee + taker_fee);
+
+        let amount_usd = trade.amount_rsm * trade.price_usd;
+        self.fees.record_fill(maker_wallet, taker_wallet, amount_usd, maker_fee, taker_fee, trade.timestamp);
+        if let Some(fees) = self.fees.orders.get_mut(&maker_id) {
+            if trade.taker_side == Side::Buy {
+                fees.held_rsm = (fees.held_rsm - maker_fee).max(0.0);
+            }
+        }
+        // A filled maker gets back what its hold did not need
+        if !self.order_book.order(maker_id).is_some_and(Order::is_open) {
+            if let Some(fees) = self.fees.orders.remove(&maker_id) {
+                if trade.taker_side == Side::Buy && fees.held_rsm > 0.0 {
+                    let balance = self.get_balance(maker_wallet);
+                    self.set_balance(maker_wallet, balance + fees.held_rsm);
+                }
+            }
+        }
+
+        self.volume_24h += amount_usd;
+        let mut transactions = vec![
+            self.record_fill_tx(TransactionType::Trade, &trade.seller, &trade.buyer, trade.amount_rsm, trade, format!("trade #{}", trade.id)),
+        ];
+        for (wallet, fee, role) in [(maker_wallet, maker_fee, "maker"), (taker_wallet, taker_fee, "taker")] {
+            if fee > 0.0 {
+                let memo = format!("{} fee, trade #{}", role, trade.id);
+                transactions.push(self.record_fill_tx(TransactionType::Fee, wallet, FEE_ACCRUAL_WALLET, fee, trade, memo));
+            }
+        }
+        transactions
+    }
+
+    /// Log a transaction of `trade` worth `amount_rsm` at its price
+    fn record_fill_tx(&mut self, tx_type: TransactionType, from: &str, to: &str, amount_rsm: f64, trade: &Trade, memo: String) -> Transaction {
+        self.total_transactions += 1;
+        let tx = Transaction {
+            id: self.total_transactions,
+            tx_type,
+            from_address: from.into(),
+            to_address: to.into(),
+            amount_rsm,
+            amount_usd: amount_rsm * trade.price_usd,
+            consciousness_level: 0,
+            discount_applied: 0.0,
+            timestamp: trade.timestamp,
+            status: TxStatus::Confirmed,
+            hash: self.generate_tx_hash(),
+            memo: Some(memo),
+        };
+        self.transactions.push(tx.clone());
+        tx
     }
 
     pub fn consciousness_reward(&mut self, wallet: &str, consciousness: u32) -> Transaction {
@@ -306,6 +661,7 @@ impl RSMExchange {
             timestamp: Utc::now().timestamp(),
             status: TxStatus::Confirmed,
             hash: self.generate_tx_hash(),
+            memo: None,
         };
 
         info!("🎁 REWARD: {:.6} RSM | consciousness: {}", rsm_reward, consciousness);
@@ -330,12 +686,187 @@ impl RSMExchange {
             timestamp: Utc::now().timestamp(),
             status: TxStatus::Confirmed,
             hash: self.generate_tx_hash(),
+            memo: None,
         };
 
         self.transactions.push(tx.clone());
         tx
     }
 
+    // ═══════════════════════════════════════════════════════════════
+    // FEES
+    // ═══════════════════════════════════════════════════════════════
+
+    /// Charge `schedule` from the next order on; orders already placed
+    /// keep their rates
+    pub fn set_fee_schedule(&mut self, schedule: FeeSchedule) -> Result<(), String> {
+        schedule.validate()?;
+        self.fees.schedule = schedule;
+        Ok(())
+    }
+
+    /// Share out the accrual wallet: burn the burn share, pay makers the LP
+    /// share pro rata to their volume this period, and send the rest to the
+    /// treasury. Refused when nothing has accrued
+    pub fn distribute_fees(&mut self, now: i64) -> Result<FeeReport, String> {
+        let distributed = self.get_balance(FEE_ACCRUAL_WALLET);
+        if distributed <= 0.0 {
+            return Err("No fees to distribute".to_string());
+        }
+        let id = self.fees.reports.len() as u64 + 1;
+        let memo = format!("fee distribution #{}", id);
+        let split = self.fees.schedule.split;
+        let treasury_wallet = self.fees.schedule.treasury_wallet.clone();
+
+        let burned = distributed * split.burn;
+        let burn_id = (burned > 0.0).then(|| {
+            self.set_balance(FEE_ACCRUAL_WALLET, distributed - burned);
+            self.burn(burned, BurnReason::TradingFee, None, 0, 0).id
+        });
+
+        let mut makers: Vec<(String, f64)> = std::mem::take(&mut self.fees.maker_volume_usd).into_iter()
+            .filter(|(_, volume)| *volume > 0.0)
+            .collect();
+        makers.sort_by(|a, b| a.0.cmp(&b.0));
+        let maker_volume: f64 = makers.iter().map(|(_, volume)| volume).sum();
+        let lp_pool = distributed * split.lp_rewards;
+        let mut transactions = Vec::new();
+        let mut lp_rewards = Vec::new();
+        for (wallet, volume) in makers {
+            let amount = (lp_pool * volume / maker_volume).min(self.get_balance(FEE_ACCRUAL_WALLET));
+            if amount <= 0.0 {
+                continue;
+            }
+            let tx = self.transfer_with_memo(FEE_ACCRUAL_WALLET, &wallet, amount, Some(memo.clone()))?;
+            lp_rewards.push(LpReward { wallet, maker_volume_usd: volume, amount_rsm: amount, tx_hash: tx.hash.clone() });
+            transactions.push(tx);
+        }
+
+        // The treasury takes the rest, rounding included
+        let treasury = self.get_balance(FEE_ACCRUAL_WALLET);
+        let treasury_tx_hash = if treasury > 0.0 {
+            let tx = self.transfer_with_memo(FEE_ACCRUAL_WALLET, &treasury_wallet, treasury, Some(memo))?;
+            let hash = tx.hash.clone();
+            transactions.push(tx);
+            Some(hash)
+        } else {
+            None
+        };
+
+        let report = FeeReport {
+            id,
+            period_start: self.fees.period_start,
+            period_end: now,
+            maker_fees_rsm: std::mem::take(&mut self.fees.maker_fees_rsm),
+            taker_fees_rsm: std::mem::take(&mut self.fees.taker_fees_rsm),
+            distributed_rsm: distributed,
+            burned_rsm: burned,
+            treasury_wallet,
+            treasury_rsm: treasury,
+            treasury_tx_hash,
+            lp_rewards,
+            burn_id,
+            transactions,
+        };
+        self.fees.period_start = now;
+        info!("🧾 FEES #{}: {:.6} RSM | burned {:.6}, treasury {:.6}, {} maker(s)",
+              id, distributed, burned, treasury, report.lp_rewards.len());
+        self.fees.reports.push(report.clone());
+        Ok(report)
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // SETTLEMENT ESCROW
+    // ═══════════════════════════════════════════════════════════════
+
+    /// Move the `terms`' RSM from `payer` into a new escrow, paid out to
+    /// the payee once the anchored block is final and refunded if it is
+    /// not by the timeout
+    pub fn open_escrow(&mut self, payer: &str, terms: EscrowTerms, now: i64) -> Result<Escrow, String> {
+        let EscrowTerms { payee, amount_rsm, anchor, timeout_secs, memo } = terms;
+        check_wallet(&payee)?;
+        if payer == payee {
+            return Err(format!("{} cannot escrow RSM for itself", payer));
+        }
+        if !amount_rsm.is_finite() || amount_rsm <= 0.0 {
+            return Err(format!("Invalid escrow amount {}", amount_rsm));
+        }
+        escrow::check_timeout(timeout_secs)?;
+        anchor.validate()?;
+
+        let id = self.escrows.keys().next_back().map_or(1, |last| last + 1);
+        let deposit = self.transfer_with_memo(payer, &escrow::escrow_address(id), amount_rsm, Some(format!("escrow #{}", id)))?;
+        let escrow = Escrow {
+            id,
+            payer: payer.into(),
+            payee,
+            amount_rsm,
+            anchor,
+            status: EscrowStatus::Held,
+            block: None,
+            memo,
+            transactions: vec![deposit],
+            created_at: now,
+            expires_at: now + timeout_secs,
+            updated_at: now,
+        };
+        info!("🔐 ESCROW #{}: {:.6} RSM {} → {} until {:?} is final", id, amount_rsm, payer, escrow.payee, escrow.anchor);
+        self.escrows.insert(id, escrow.clone());
+        Ok(escrow)
+    }
+
+    /// Release every held escrow whose block `consensus` has finalized and
+    /// refund the expired rest; returns the escrows resolved
+    pub fn resolve_escrows(&mut self, consensus: &ProofOfConsciousness, now: i64) -> Vec<Escrow> {
+        let due: Vec<(u64, Option<BlockRef>)> = self.escrows.values()
+            .filter(|escrow| escrow.is_held())
+            .filter_map(|escrow| match escrow.anchor.final_block(consensus) {
+                Some(block) => Some((escrow.id, Some(block))),
+                None if escrow.is_expired(now) => Some((escrow.id, None)),
+                None => None,
+            })
+            .collect();
+        due.into_iter()
+            .filter_map(|(id, block)| match self.resolve_escrow(id, block, now) {
+                Ok(escrow) => Some(escrow),
+                Err(e) => {
+                    tracing::warn!("🔐 Escrow #{} not resolved: {}", id, e);
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Pay escrow `id` out to its payee once `block` is final, or back to
+    /// its payer without one
+    fn resolve_escrow(&mut self, id: u64, block: Option<BlockRef>, now: i64) -> Result<Escrow, String> {
+        let (recipient, amount_rsm) = match self.escrows.get(&id) {
+            Some(escrow) if block.is_some() => (escrow.payee.clone(), escrow.amount_rsm),
+            Some(escrow) => (escrow.payer.clone(), escrow.amount_rsm),
+            None => return Err(format!("Escrow #{} not found", id)),
+        };
+        let status = if block.is_some() { EscrowStatus::Released } else { EscrowStatus::Refunded };
+        let memo = format!("escrow #{} {}", id, status.name());
+        let tx = self.transfer_with_memo(&escrow::escrow_address(id), &recipient, amount_rsm, Some(memo))?;
+
+        let escrow = self.escrows.get_mut(&id).expect("escrow looked up above");
+        escrow.status = status;
+        escrow.block = block;
+        escrow.transactions.push(tx);
+        escrow.updated_at = now;
+        info!("🔐 ESCROW #{} {}: {:.6} RSM → {}", id, status.name(), amount_rsm, recipient);
+        Ok(escrow.clone())
+    }
+
+    /// Escrows, newest first, optionally only those in `status`
+    pub fn list_escrows(&self, status: Option<EscrowStatus>, limit: usize) -> Vec<Escrow> {
+        self.escrows.values().rev()
+            .filter(|escrow| status.is_none_or(|status| escrow.status == status))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
     // ═══════════════════════════════════════════════════════════════
     // STATS & QUERIES
     // ═══════════════════════════════════════════════════════════════
@@ -348,6 +879,38 @@ impl RSMExchange {
         self.burn_events.iter().rev().take(limit).cloned().collect()
     }
 
+    /// Mark RSM at `price_usd` (the oracle's RSM/USD reference); USD
+    /// amounts of later transactions and the market cap follow it
+    pub fn set_mark_price(&mut self, price_usd: f64) -> Result<(), String> {
+        if !price_usd.is_finite() || price_usd <= 0.0 {
+            return Err(format!("Invalid mark price {}", price_usd));
+        }
+        if price_usd != self.price_usd {
+            info!("💱 RSM marked at ${}", price_usd);
+        }
+        self.price_usd = price_usd;
+        Ok(())
+    }
+
+    /// Coin prices `simulate_swap` converts at
+    pub fn set_reference_prices(&mut self, prices: &[ReferencePrice]) {
+        self.reference_prices = prices.iter().map(|price| (price.pair, price.price_usd)).collect();
+    }
+
+    /// Preview swapping `amount` of `from` into `to` against the whole
+    /// order book (at the first fee tier's taker fee), the desk and the
+    /// reference prices, without trading
+    pub fn simulate_swap(&self, from: Asset, to: Asset, amount: f64) -> Result<SwapQuote, String> {
+        let depth = self.order_book.depth(usize::MAX);
+        let market = SwapMarket {
+            depth: &depth,
+            desk_price_usd: self.price_usd,
+            reference_prices: &self.reference_prices,
+            taker_fee: self.fees.schedule.tiers[0].taker_fee,
+        };
+        swap::simulate(&market, from, to, amount)
+    }
+
     pub fn market_cap(&self) -> f64 {
         self.circulating.to_f64().unwrap_or(0.0) * self.price_usd
     }
@@ -412,6 +975,14 @@ impl Default for RSMExchange {
     }
 }
 
+/// A placed order as it stands, its fills and their settlement
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderPlacement {
+    pub order: Order,
+    pub trades: Vec<Trade>,
+    pub transactions: Vec<Transaction>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExchangeStats {
     pub total_supply_str: String,
@@ -0,0 +1,290 @@
+//! Exchange Fees V1 — maker/taker fee schedule and distribution
+//!
+//! Every order book fill charges the wallet of the resting order the maker
+//! fee and the wallet of the incoming order the taker fee, at the rates of
+//! the tier its 30-day traded volume (USD, as maker or taker) reached when
+//! the order was placed. Fees are paid in RSM to the accrual wallet
+//! (`FEE_ACCRUAL_WALLET`): a buyer's is withheld from the RSM it receives,
+//! a seller's comes on top of the RSM it sells. Sell orders hold their fee
+//! at the taker rate with the order and get back what they did not use.
+//!
+//! `RSMExchange::distribute_fees` empties the accrual wallet: one share is
+//! burned, LP rewards are paid to the wallets whose resting orders filled
+//! since the last distribution (pro rata to that maker volume) and the rest
+//! goes to the treasury, by wallet transfers. Each distribution is kept as
+//! a `FeeReport`, exportable as CSV or JSON (`export_reports`).
+//!
+//! `FEE_SCHEDULE` names a JSON file with the server's schedule; unset, it
+//! charges `FeeSchedule::standard()`. A new exchange charges nothing.
+
+use std::collections::{HashMap, VecDeque};
+use std::path::Path;
+
+use serde::{Serialize, Deserialize};
+
+use crate::exchange::Transaction;
+use crate::history::{csv_field, ExportFormat};
+use crate::marketplace::check_wallet;
+
+/// Wallet fees are paid into until they are distributed
+pub const FEE_ACCRUAL_WALLET: &str = "exchange_fee_accrual";
+pub const TREASURY_WALLET: &str = "DIVINE_TREASURY";
+/// Highest maker or taker fee a schedule may charge (1%)
+pub const MAX_FEE: f64 = 0.01;
+pub const VOLUME_WINDOW_DAYS: i64 = 30;
+pub const DEFAULT_DISTRIBUTION_SECS: u64 = 24 * 3600;
+pub const MIN_DISTRIBUTION_SECS: u64 = 60;
+pub const FEE_REPORT_CSV_HEADER: &str = "report_id,period_start,period_end,kind,wallet,amount_rsm,maker_volume_usd,tx_hash";
+
+const DAY_SECS: i64 = 24 * 3600;
+
+/// Fees of wallets whose 30-day volume reaches `min_volume_usd`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FeeTier {
+    pub min_volume_usd: f64,
+    /// Share of the RSM traded (0.001 = 0.1%)
+    pub maker_fee: f64,
+    pub taker_fee: f64,
+}
+
+/// How distributed fees are shared out; the shares add up to 1
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FeeSplit {
+    pub burn: f64,
+    pub treasury: f64,
+    /// To makers, pro rata to their filled volume. With no maker volume in
+    /// the period this share goes to the treasury
+    pub lp_rewards: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeeSchedule {
+    /// Lowest volume first, the first from 0
+    pub tiers: Vec<FeeTier>,
+    pub split: FeeSplit,
+    #[serde(default = "default_treasury_wallet")]
+    pub treasury_wallet: String,
+    /// How often the server distributes the accrued fees
+    #[serde(default = "default_distribution_secs")]
+    pub distribution_secs: u64,
+}
+
+fn default_treasury_wallet() -> String { TREASURY_WALLET.to_string() }
+fn default_distribution_secs() -> u64 { DEFAULT_DISTRIBUTION_SECS }
+
+/// Charges nothing: the schedule of a new exchange
+impl Default for FeeSchedule {
+    fn default() -> Self {
+        Self {
+            tiers: vec![FeeTier { min_volume_usd: 0.0, maker_fee: 0.0, taker_fee: 0.0 }],
+            ..Self::standard()
+        }
+    }
+}
+
+impl FeeSchedule {
+    /// 0.10%/0.20% maker/taker, down to 0%/0.04% from $100M a month;
+    /// 30% burned, 40% to the treasury, 30% to makers
+    pub fn standard() -> Self {
+        let tier = |min_volume_usd, maker_fee, taker_fee| FeeTier { min_volume_usd, maker_fee, taker_fee };
+        Self {
+            tiers: vec![
+                tier(0.0, 0.0010, 0.0020),
+                tier(100_000.0, 0.0008, 0.0016),
+                tier(1_000_000.0, 0.0005, 0.0010),
+                tier(10_000_000.0, 0.0002, 0.0006),
+                tier(100_000_000.0, 0.0, 0.0004),
+            ],
+            split: FeeSplit { burn: 0.3, treasury: 0.4, lp_rewards: 0.3 },
+            treasury_wallet: default_treasury_wallet(),
+            distribution_secs: DEFAULT_DISTRIBUTION_SECS,
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let schedule: Self = serde_json::from_str(text).map_err(|e| format!("Invalid fee schedule: {}", e))?;
+        schedule.validate()?;
+        Ok(schedule)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Self::parse(&text).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// The file `FEE_SCHEDULE` names; `standard()` when unset
+    pub fn from_env() -> Result<Self, String> {
+        match std::env::var("FEE_SCHEDULE") {
+            Ok(path) if !path.is_empty() => Self::load(path),
+            _ => Ok(Self::standard()),
+        }
+    }
+
+    /// Tiers must start at 0 and rise, and no maker fee may exceed its
+    /// taker fee: sell orders hold the taker rate for either
+    pub fn validate(&self) -> Result<(), String> {
+        if self.tiers.first().map(|tier| tier.min_volume_usd) != Some(0.0) {
+            return Err("The first fee tier must start at 0 volume".into());
+        }
+        for (i, tier) in self.tiers.iter().enumerate() {
+            if i > 0 && (tier.min_volume_usd.is_nan() || tier.min_volume_usd <= self.tiers[i - 1].min_volume_usd) {
+                return Err(format!("Fee tier {} does not start above tier {}", i, i - 1));
+            }
+            for fee in [tier.maker_fee, tier.taker_fee] {
+                if !(0.0..=MAX_FEE).contains(&fee) {
+                    return Err(format!("Fee tier {}: fees are between 0 and {}, not {}", i, MAX_FEE, fee));
+                }
+            }
+            if tier.maker_fee > tier.taker_fee {
+                return Err(format!("Fee tier {}: maker fee above the taker fee", i));
+            }
+        }
+        let FeeSplit { burn, treasury, lp_rewards } = self.split;
+        if [burn, treasury, lp_rewards].iter().any(|share| !(0.0..=1.0).contains(share)) || (burn + treasury + lp_rewards - 1.0).abs() > 1e-9 {
+            return Err(format!("Fee split shares must add up to 1, not {}", burn + treasury + lp_rewards));
+        }
+        check_wallet(&self.treasury_wallet)?;
+        if self.distribution_secs < MIN_DISTRIBUTION_SECS {
+            return Err(format!("Fees are distributed at most every {} seconds", MIN_DISTRIBUTION_SECS));
+        }
+        Ok(())
+    }
+
+    /// Index of the tier `volume_usd` reaches
+    pub fn tier(&self, volume_usd: f64) -> usize {
+        self.tiers.iter().rposition(|tier| volume_usd >= tier.min_volume_usd).unwrap_or(0)
+    }
+}
+
+/// Rates an order pays, fixed when it is placed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct FeeRates {
+    pub maker_fee: f64,
+    pub taker_fee: f64,
+}
+
+/// Fee terms of an order on the book
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct OrderFees {
+    pub rates: FeeRates,
+    /// RSM a sell order holds for the fees of its remaining amount
+    pub held_rsm: f64,
+}
+
+/// Fees, volumes and reports of an exchange
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FeeAccounts {
+    pub schedule: FeeSchedule,
+    /// Traded USD per wallet per day, oldest day first
+    volumes: HashMap<String, VecDeque<(i64, f64)>>,
+    /// Fee terms of resting orders by id
+    pub orders: HashMap<u64, OrderFees>,
+    /// Start of the period the next distribution covers
+    pub period_start: i64,
+    pub maker_fees_rsm: f64,
+    pub taker_fees_rsm: f64,
+    /// USD of each wallet's resting orders filled this period
+    pub maker_volume_usd: HashMap<String, f64>,
+    pub reports: Vec<FeeReport>,
+}
+
+impl FeeAccounts {
+    /// USD `wallet` traded in the last `VOLUME_WINDOW_DAYS` days
+    pub fn volume_30d(&self, wallet: &str, now: i64) -> f64 {
+        let since = now.div_euclid(DAY_SECS) - VOLUME_WINDOW_DAYS;
+        self.volumes.get(wallet)
+            .map_or(0.0, |days| days.iter().filter(|(day, _)| *day > since).map(|(_, usd)| usd).sum())
+    }
+
+    /// Rates of the tier `wallet` is in now
+    pub fn rates(&self, wallet: &str, now: i64) -> FeeRates {
+        let tier = self.schedule.tiers[self.schedule.tier(self.volume_30d(wallet, now))];
+        FeeRates { maker_fee: tier.maker_fee, taker_fee: tier.taker_fee }
+    }
+
+    /// Count a fill of `amount_usd` to both wallets' volume and its fees
+    /// to the period
+    pub fn record_fill(&mut self, maker: &str, taker: &str, amount_usd: f64, maker_fee_rsm: f64, taker_fee_rsm: f64, now: i64) {
+        let day = now.div_euclid(DAY_SECS);
+        for wallet in [maker, taker] {
+            let days = self.volumes.entry(wallet.to_string()).or_default();
+            match days.back_mut() {
+                Some((last, usd)) if *last == day => *usd += amount_usd,
+                _ => days.push_back((day, amount_usd)),
+            }
+            while days.front().is_some_and(|(first, _)| *first <= day - VOLUME_WINDOW_DAYS) {
+                days.pop_front();
+            }
+        }
+        *self.maker_volume_usd.entry(maker.to_string()).or_default() += amount_usd;
+        self.maker_fees_rsm += maker_fee_rsm;
+        self.taker_fees_rsm += taker_fee_rsm;
+    }
+
+    /// Latest reports, newest first
+    pub fn recent_reports(&self, limit: usize) -> Vec<FeeReport> {
+        self.reports.iter().rev().take(limit).cloned().collect()
+    }
+}
+
+/// Payout to one maker
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LpReward {
+    pub wallet: String,
+    pub maker_volume_usd: f64,
+    pub amount_rsm: f64,
+    pub tx_hash: String,
+}
+
+/// One distribution of the accrued fees
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeeReport {
+    pub id: u64,
+    pub period_start: i64,
+    pub period_end: i64,
+    pub maker_fees_rsm: f64,
+    pub taker_fees_rsm: f64,
+    /// RSM of the accrual wallet shared out
+    pub distributed_rsm: f64,
+    pub burned_rsm: f64,
+    pub treasury_wallet: String,
+    pub treasury_rsm: f64,
+    pub treasury_tx_hash: Option<String>,
+    pub lp_rewards: Vec<LpReward>,
+    /// The `BurnEvent` of the burned share
+    pub burn_id: Option<u64>,
+    /// The LP reward transfers, then the treasury's
+    pub transactions: Vec<Transaction>,
+}
+
+/// `reports` as CSV (one row per burn, treasury transfer and LP reward)
+/// or as JSON
+pub fn export_reports(reports: &[FeeReport], format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Json => serde_json::to_string_pretty(reports).expect("fee reports serialize"),
+        ExportFormat::Csv => {
+            let mut out = String::from(FEE_REPORT_CSV_HEADER);
+            out.push('\n');
+            for report in reports {
+                let mut row = |kind: &str, wallet: &str, amount_rsm: f64, volume: Option<f64>, hash: &str| {
+                    out.push_str(&format!(
+                        "{},{},{},{},{},{},{},{}\n",
+                        report.id, report.period_start, report.period_end, kind, csv_field(wallet), amount_rsm,
+                        volume.map(|v| v.to_string()).unwrap_or_default(), hash,
+                    ));
+                };
+                if report.burned_rsm > 0.0 {
+                    row("burn", "", report.burned_rsm, None, "");
+                }
+                if let Some(hash) = &report.treasury_tx_hash {
+                    row("treasury", &report.treasury_wallet, report.treasury_rsm, None, hash);
+                }
+                for reward in &report.lp_rewards {
+                    row("lp_reward", &reward.wallet, reward.amount_rsm, Some(reward.maker_volume_usd), &reward.tx_hash);
+                }
+            }
+            out
+        }
+    }
+}
@@ -0,0 +1,187 @@
+//! Genesis Configuration — per-network genesis block parameters
+//!
+//! Networks (dev/test/main) are told apart by their genesis block. The
+//! config is loaded from a JSON file:
+//!
+//! ```json
+//! {
+//!   "chain_id": "divine-testnet",
+//!   "timestamp": 1767225600,
+//!   "initial_difficulty": 12,
+//!   "min_consciousness": 3000,
+//!   "block_reward_rsm": 50.0,
+//!   "reward_halving_interval": 210000,
+//!   "min_fee_rsm": 0.01,
+//!   "upgrades": [{ "feature": "VersionedHeader", "activation_height": 1 }],
+//!   "allocations": [{ "address": "divine_founder", "amount_rsm": 1000000.0 }]
+//! }
+//! ```
+//!
+//! Every field except the upgrade schedule is committed to the genesis block's
+//! Merkle root, so nodes with different configs never accept each other's
+//! blocks. Upgrades can be scheduled on a running network (see `upgrades`).
+
+use serde::{Serialize, Deserialize};
+use sha2::{Sha256, Digest};
+
+use crate::consensus::{
+    ConsensusBlock, Coinbase, BLOCK_REWARD_RSM, DEFAULT_DIFFICULTY, GENESIS_TIMESTAMP,
+    INITIAL_POC_THRESHOLD, MAX_DIFFICULTY, MIN_DIFFICULTY, REWARD_HALVING_INTERVAL,
+};
+use crate::upgrades::{UpgradeSchedule, BLOCK_VERSION_LEGACY};
+use crate::wallet::WalletManager;
+
+pub const DEV_CHAIN_ID: &str = "divine-dev";
+
+/// RSM premined to an address at genesis
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GenesisAllocation {
+    pub address: String,
+    pub amount_rsm: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GenesisConfig {
+    pub chain_id: String,
+    #[serde(default = "default_timestamp")]
+    pub timestamp: i64,
+    #[serde(default = "default_difficulty")]
+    pub initial_difficulty: u32,
+    #[serde(default = "default_min_consciousness")]
+    pub min_consciousness: u32,
+    #[serde(default = "default_block_reward")]
+    pub block_reward_rsm: f64,
+    #[serde(default = "default_halving_interval")]
+    pub reward_halving_interval: u64,
+    /// Minimum fee per attestation; 0 keeps block space free
+    #[serde(default)]
+    pub min_fee_rsm: f64,
+    #[serde(default)]
+    pub allocations: Vec<GenesisAllocation>,
+    /// Fork activation heights (not committed to the genesis block)
+    #[serde(default)]
+    pub upgrades: UpgradeSchedule,
+}
+
+fn default_timestamp() -> i64 { GENESIS_TIMESTAMP }
+fn default_difficulty() -> u32 { DEFAULT_DIFFICULTY }
+fn default_min_consciousness() -> u32 { INITIAL_POC_THRESHOLD }
+fn default_block_reward() -> f64 { BLOCK_REWARD_RSM }
+fn default_halving_interval() -> u64 { REWARD_HALVING_INTERVAL }
+
+impl Default for GenesisConfig {
+    fn default() -> Self {
+        Self {
+            chain_id: DEV_CHAIN_ID.to_string(),
+            timestamp: GENESIS_TIMESTAMP,
+            initial_difficulty: DEFAULT_DIFFICULTY,
+            min_consciousness: INITIAL_POC_THRESHOLD,
+            block_reward_rsm: BLOCK_REWARD_RSM,
+            reward_halving_interval: REWARD_HALVING_INTERVAL,
+            min_fee_rsm: 0.0,
+            allocations: Vec::new(),
+            upgrades: UpgradeSchedule::new(),
+        }
+    }
+}
+
+impl GenesisConfig {
+    pub fn from_file(path: impl AsRef<std::path::Path>) -> anyhow::Result<Self> {
+        let json = std::fs::read_to_string(path.as_ref())?;
+        let config: Self = serde_json::from_str(&json)?;
+        config.check().map_err(|e| anyhow::anyhow!("Invalid genesis config: {}", e))?;
+        Ok(config)
+    }
+
+    pub fn check(&self) -> Result<(), String> {
+        if self.chain_id.trim().is_empty() {
+            return Err("chain_id is empty".to_string());
+        }
+        if !(MIN_DIFFICULTY..=MAX_DIFFICULTY).contains(&self.initial_difficulty) {
+            return Err(format!(
+                "initial_difficulty {} outside {}-{}", self.initial_difficulty, MIN_DIFFICULTY, MAX_DIFFICULTY
+            ));
+        }
+        if !self.block_reward_rsm.is_finite() || self.block_reward_rsm < 0.0 {
+            return Err(format!("block_reward_rsm {} is invalid", self.block_reward_rsm));
+        }
+        if !self.min_fee_rsm.is_finite() || self.min_fee_rsm < 0.0 {
+            return Err(format!("min_fee_rsm {} is invalid", self.min_fee_rsm));
+        }
+        if self.reward_halving_interval == 0 {
+            return Err("reward_halving_interval must be positive".to_string());
+        }
+        self.upgrades.check()?;
+        if let Some(bad) = self.allocations.iter().find(|a| !a.amount_rsm.is_finite() || a.amount_rsm < 0.0) {
+            return Err(format!("allocation to {} has invalid amount {}", bad.address, bad.amount_rsm));
+        }
+        Ok(())
+    }
+
+    pub fn premined_rsm(&self) -> f64 {
+        self.allocations.iter().map(|a| a.amount_rsm).sum()
+    }
+
+    /// Reward minted by block `height`: halves every `reward_halving_interval` blocks
+    pub fn block_subsidy(&self, height: u64) -> f64 {
+        if height == 0 {
+            return 0.0;
+        }
+        let halvings = (height - 1) / self.reward_halving_interval.max(1);
+        if halvings >= 64 {
+            return 0.0;
+        }
+        self.block_reward_rsm / (1u64 << halvings) as f64
+    }
+
+    /// Wallets funded by the premine
+    pub fn initial_wallets(&self) -> WalletManager {
+        let mut wallets = WalletManager::new();
+        for allocation in &self.allocations {
+            wallets.get_or_create(&allocation.address).deposit(allocation.amount_rsm);
+        }
+        wallets
+    }
+
+    /// Hash committing to every config field
+    pub fn commitment(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(self.chain_id.as_bytes());
+        hasher.update(self.timestamp.to_le_bytes());
+        hasher.update(self.initial_difficulty.to_le_bytes());
+        hasher.update(self.min_consciousness.to_le_bytes());
+        hasher.update(self.block_reward_rsm.to_le_bytes());
+        hasher.update(self.reward_halving_interval.to_le_bytes());
+        // Committed only when set, so fee-free genesis hashes are unchanged
+        if self.min_fee_rsm != 0.0 {
+            hasher.update(self.min_fee_rsm.to_le_bytes());
+        }
+        for allocation in &self.allocations {
+            hasher.update(allocation.address.as_bytes());
+            hasher.update(allocation.amount_rsm.to_le_bytes());
+        }
+        hasher.finalize().into()
+    }
+
+    /// Deterministic genesis block for this network
+    pub fn genesis_block(&self) -> ConsensusBlock {
+        let mut block = ConsensusBlock {
+            index: 0,
+            version: BLOCK_VERSION_LEGACY,
+            timestamp: self.timestamp,
+            entries: Vec::new(),
+            merkle_root: hex::encode(self.commitment()),
+            previous_hash: "0".repeat(64),
+            nonce: 0,
+            difficulty: 0,
+            hash: String::new(),
+            producer: String::new(),
+            signature: String::new(),
+            coinbase: Coinbase::default(),
+            stake_ops: Vec::new(),
+            attested_consciousness: 0,
+        };
+        block.hash = block.calculate_hash();
+        block
+    }
+}
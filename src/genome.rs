@@ -8,7 +8,7 @@
 
 use std::marker::PhantomData;
 use sha2::{Sha256, Sha512, Digest};
-use rand::Rng;
+use rand::{Rng, SeedableRng};
 use serde::{Serialize, Deserialize};
 use crate::rotation::{Rotation, Rot0, Rot180, Rot270, DynamicRotation};
 
@@ -27,7 +27,11 @@ pub enum Tetrad {
 
 impl Tetrad {
     pub fn random() -> Self {
-        match rand::thread_rng().gen_range(0..4) {
+        Self::random_with(&mut rand::thread_rng())
+    }
+
+    pub fn random_with<G: Rng + ?Sized>(rng: &mut G) -> Self {
+        match rng.gen_range(0..4) {
             0 => Self::A,
             1 => Self::T,
             2 => Self::G,
@@ -230,10 +234,15 @@ impl<R: Rotation> Genome<R> {
     }
 
     pub fn divide(&mut self) -> bool {
+        self.divide_with(&mut rand::thread_rng())
+    }
+
+    /// `divide` with the telomere loss drawn from `rng`
+    pub fn divide_with<G: Rng + ?Sized>(&mut self, rng: &mut G) -> bool {
         if self.telomere_length < 100 || self.division_count >= HAYFLICK_LIMIT {
             return false;
         }
-        let loss = rand::thread_rng().gen_range(50..150);
+        let loss = rng.gen_range(50..150);
         self.telomere_length = self.telomere_length.saturating_sub(loss);
         self.division_count += 1;
         true
@@ -469,6 +478,102 @@ impl<R: Rotation> Genome<R> {
         hex::encode(result)
     }
 
+    /// Every metric of the genome, for reports
+    pub fn metrics(&self) -> GenomeMetrics {
+        let mut v4 = self.clone();
+        v4.calculate_consciousness_v4();
+        let (t_count, g_count) = self.tg_counts();
+        GenomeMetrics {
+            consciousness: self.consciousness,
+            consciousness_level: self.consciousness_level_name().to_string(),
+            consciousness_v4: v4.consciousness,
+            gc_content: self.gc_content(),
+            complexity: self.complexity(),
+            t_count,
+            g_count,
+            tg_ratio: self.rna_signal(),
+            tg_balance: self.tg_balance_score(),
+            balance: self.balance_score(),
+            archival_score: self.archival_score(),
+            biological_age: self.biological_age(),
+            symmetric_90: self.has_rotational_symmetry(90),
+            symmetric_180: self.has_rotational_symmetry(180),
+            symmetric_270: self.has_rotational_symmetry(270),
+            fractal_similarity: self.fractal_similarity(),
+            bell_violation: self.bell_inequality_violation(),
+            hyper_symmetry: self.hyper_symmetry_score(),
+            hyper_collapse_variance: self.hyper_collapse_variance(),
+            suggested_rotation: self.suggested_rotation(),
+        }
+    }
+
+    /// Base-by-base and metric comparison of `other` against this genome
+    pub fn diff(&self, other: &Self) -> GenomeDiff {
+        let changed: Vec<usize> = (0..GENOME_SIZE).filter(|&i| self.data[i] != other.data[i]).collect();
+        GenomeDiff {
+            dna_a: self.to_dna_string(),
+            dna_b: other.to_dna_string(),
+            identity: (GENOME_SIZE - changed.len()) as f64 / GENOME_SIZE as f64,
+            changed,
+            consciousness: MetricDelta::new(self.consciousness as f64, other.consciousness as f64),
+            gc_content: MetricDelta::new(self.gc_content(), other.gc_content()),
+            tg_ratio: MetricDelta::new(self.rna_signal(), other.rna_signal()),
+            p53_copies: MetricDelta::new(self.p53_copies as f64, other.p53_copies as f64),
+            telomere_length: MetricDelta::new(self.telomere_length as f64, other.telomere_length as f64),
+        }
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // CUBE VIEW & EXPORT
+    // ═══════════════════════════════════════════════════════════════
+
+    /// The cube turned `angle` degrees (0, 90, 180 or 270) around its
+    /// vertical axis; everything but the layout is kept
+    pub fn rotated(&self, angle: u16) -> Result<Self, String> {
+        if !matches!(angle, 0 | 90 | 180 | 270) {
+            return Err(format!("Invalid rotation {}° (expected 0, 90, 180 or 270)", angle));
+        }
+        let mut rotated = self.clone();
+        rotated.data = self.rotate_cube(angle as u32);
+        rotated.rehash();
+        rotated.calculate_consciousness();
+        Ok(rotated)
+    }
+
+    /// `count` point mutations drawn from `rng`, each to a different base;
+    /// returns the positions changed, in order
+    pub fn mutate_with<G: Rng + ?Sized>(&mut self, count: usize, rng: &mut G) -> Vec<usize> {
+        let positions: Vec<usize> = (0..count).map(|_| {
+            let position = rng.gen_range(0..GENOME_SIZE);
+            let shift = rng.gen_range(1..4u8);
+            self.data[position] = Tetrad::from_u8(self.data[position] as u8 + shift);
+            position
+        }).collect();
+        self.mutations += count as u64;
+        self.rehash();
+        self.calculate_consciousness();
+        positions
+    }
+
+    /// The 27 bases as three 3×3 layers side by side, bottom layer
+    /// (z = 0) first; `(x, y, z)` is base `x + 3y + 9z`
+    pub fn render_cube(&self) -> String {
+        let mut lines = vec!["z=0     z=1     z=2".to_string()];
+        for y in 0..3 {
+            let rows: Vec<String> = (0..3).map(|z| {
+                (0..3).map(|x| self.data[x + y * 3 + z * 9].to_char().to_string()).collect::<Vec<_>>().join(" ")
+            }).collect();
+            lines.push(rows.join("   "));
+        }
+        lines.join("\n")
+    }
+
+    /// FASTA record `>name` with its consciousness and p53 copies (the
+    /// header fields after the name are ignored on import)
+    pub fn to_fasta(&self, name: &str) -> String {
+        format!(">{} consciousness={} p53={}\n{}\n", name, self.consciousness, self.p53_copies, self.to_dna_string())
+    }
+
     pub fn consciousness_level_name(&self) -> &'static str {
         match self.consciousness {
             0..=499 => "Virus",
@@ -483,6 +588,62 @@ impl<R: Rotation> Genome<R> {
     }
 }
 
+/// Metrics of one genome (`Genome::metrics`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GenomeMetrics {
+    pub consciousness: u32,
+    pub consciousness_level: String,
+    /// V4 formula (rotational, fractal, Bell and hyper bonuses)
+    pub consciousness_v4: u32,
+    pub gc_content: f64,
+    pub complexity: f64,
+    pub t_count: u32,
+    pub g_count: u32,
+    pub tg_ratio: f64,
+    pub tg_balance: f64,
+    pub balance: f64,
+    pub archival_score: f64,
+    pub biological_age: f64,
+    pub symmetric_90: bool,
+    pub symmetric_180: bool,
+    pub symmetric_270: bool,
+    pub fractal_similarity: f64,
+    pub bell_violation: f64,
+    pub hyper_symmetry: f64,
+    pub hyper_collapse_variance: f64,
+    pub suggested_rotation: DynamicRotation,
+}
+
+/// Two genomes compared (`Genome::diff`): `b` against `a`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GenomeDiff {
+    pub dna_a: String,
+    pub dna_b: String,
+    /// Positions (0-26) whose bases differ
+    pub changed: Vec<usize>,
+    /// Share of positions with the same base
+    pub identity: f64,
+    pub consciousness: MetricDelta,
+    pub gc_content: MetricDelta,
+    pub tg_ratio: MetricDelta,
+    pub p53_copies: MetricDelta,
+    pub telomere_length: MetricDelta,
+}
+
+/// A metric of both genomes and how much `b` differs
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MetricDelta {
+    pub a: f64,
+    pub b: f64,
+    pub delta: f64,
+}
+
+impl MetricDelta {
+    pub fn new(a: f64, b: f64) -> Self {
+        Self { a, b, delta: b - a }
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════
 // GENOME BUILDER
 // ═══════════════════════════════════════════════════════════════
@@ -510,6 +671,16 @@ impl GenomeBuilder {
         Self { data, p53_copies: 20, telomere_length: TELOMERE_MAX }
     }
 
+    /// Random bases from `seed`; the same seed gives the same DNA
+    pub fn seeded(seed: u64) -> Self {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+        let mut data = [Tetrad::A; GENOME_SIZE];
+        for tetrad in data.iter_mut() {
+            *tetrad = Tetrad::from_u8(rng.gen_range(0..4));
+        }
+        Self { data, p53_copies: 20, telomere_length: TELOMERE_MAX }
+    }
+
     pub fn from_dna(dna: &str) -> Option<Self> {
         if dna.len() != GENOME_SIZE { return None; }
         let mut data = [Tetrad::A; GENOME_SIZE];
@@ -541,6 +712,7 @@ impl GenomeBuilder {
         let mut genome = Genome::<R>::new(self.data);
         genome.p53_copies = self.p53_copies;
         genome.telomere_length = self.telomere_length;
+        genome.rehash();
         genome.calculate_consciousness();
         genome
     }
@@ -0,0 +1,250 @@
+//! Genome Import V1 — bulk FASTA and JSONL onboarding
+//!
+//! `POST /api/genomes/import` takes a multipart upload of one or more files.
+//! Each file is FASTA (`>name` headers followed by sequence lines) or JSONL
+//! (`{"dna": "...", "name": "...", "whale": true}` per line), told apart by
+//! its extension, content type or first line. Files are parsed as they
+//! stream in; every record is validated like `POST /api/genomes` and valid
+//! genomes are inserted `IMPORT_BATCH_SIZE` at a time. The `ImportReport`
+//! lists the new ids and, for each rejected record, its line and reason.
+
+use serde::{Serialize, Deserialize};
+
+use crate::genome::{Genome, GenomeBuilder, GENOME_SIZE};
+use crate::rotation::Rot180;
+
+/// Genomes inserted per database round-trip
+pub const IMPORT_BATCH_SIZE: usize = 500;
+/// Records read from one upload before the rest is refused
+pub const MAX_IMPORT_RECORDS: usize = 100_000;
+/// Upload size accepted by the route
+pub const MAX_IMPORT_BYTES: usize = 64 * 1024 * 1024;
+/// Rejections listed in a report (all are counted)
+pub const MAX_REPORTED_ERRORS: usize = 100;
+/// Longest line considered; longer ones are rejected
+pub const MAX_LINE_BYTES: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImportFormat {
+    Fasta,
+    Jsonl,
+}
+
+impl ImportFormat {
+    /// Format named by a file's extension or content type
+    pub fn detect(file_name: Option<&str>, content_type: Option<&str>) -> Option<Self> {
+        let extension = file_name.and_then(|name| name.rsplit_once('.')).map(|(_, ext)| ext.to_ascii_lowercase());
+        match extension.as_deref() {
+            Some("fa" | "fasta" | "fna" | "fas") => return Some(Self::Fasta),
+            Some("jsonl" | "ndjson") => return Some(Self::Jsonl),
+            _ => {}
+        }
+        match content_type {
+            Some("application/x-fasta" | "text/x-fasta") => Some(Self::Fasta),
+            Some("application/jsonl" | "application/x-ndjson" | "application/x-jsonlines") => Some(Self::Jsonl),
+            _ => None,
+        }
+    }
+
+    /// Format of a file starting with `line`
+    fn sniff(line: &str) -> Option<Self> {
+        match line.chars().next() {
+            Some('>') => Some(Self::Fasta),
+            Some('{') => Some(Self::Jsonl),
+            _ => None,
+        }
+    }
+}
+
+/// One genome read from an upload
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportRecord {
+    /// Line of the FASTA header or JSON object
+    pub line: usize,
+    pub name: Option<String>,
+    pub dna: String,
+    pub whale: bool,
+}
+
+impl ImportRecord {
+    pub fn build(&self) -> Result<Genome<Rot180>, String> {
+        let builder = GenomeBuilder::from_dna(&self.dna)
+            .ok_or_else(|| format!("Invalid DNA (expected {} bases of A/C/G/T, got {} characters)", GENOME_SIZE, self.dna.chars().count()))?;
+        let builder = if self.whale { builder.whale_mode() } else { builder.elephant_mode() };
+        Ok(builder.build_storage())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportError {
+    /// Uploaded file (multipart file name, or field name)
+    pub file: String,
+    pub line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub error: String,
+}
+
+#[derive(Deserialize)]
+struct JsonlRecord {
+    dna: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    whale: Option<bool>,
+}
+
+/// Incremental parser for one file; feed it chunks as they arrive
+#[derive(Debug)]
+pub struct ImportParser {
+    file: String,
+    format: Option<ImportFormat>,
+    whale: bool,
+    buffer: Vec<u8>,
+    /// Lines completed so far
+    line: usize,
+    overlong: bool,
+    /// FASTA record being read: header line, name, sequence so far
+    fasta: Option<(usize, Option<String>, String)>,
+}
+
+pub type ParsedRecord = Result<ImportRecord, ImportError>;
+
+impl ImportParser {
+    /// Parser for `file` in `format` (detected from its first line when
+    /// `None`); `whale` applies to records that do not set it
+    pub fn new(file: &str, format: Option<ImportFormat>, whale: bool) -> Self {
+        Self { file: file.to_string(), format, whale, buffer: Vec::new(), line: 0, overlong: false, fasta: None }
+    }
+
+    pub fn format(&self) -> Option<ImportFormat> {
+        self.format
+    }
+
+    fn error(&self, line: usize, name: Option<String>, error: impl Into<String>) -> ParsedRecord {
+        Err(ImportError { file: self.file.clone(), line, name, error: error.into() })
+    }
+
+    /// Records completed by `chunk`
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<ParsedRecord> {
+        let mut records = Vec::new();
+        let mut rest = chunk;
+        while let Some(end) = rest.iter().position(|&b| b == b'\n') {
+            self.push(&rest[..end]);
+            let line = std::mem::take(&mut self.buffer);
+            self.parse_line(&line, &mut records);
+            rest = &rest[end + 1..];
+        }
+        self.push(rest);
+        records
+    }
+
+    /// Records left when the file ends
+    pub fn finish(&mut self) -> Vec<ParsedRecord> {
+        let mut records = Vec::new();
+        if !self.buffer.is_empty() || self.overlong {
+            let line = std::mem::take(&mut self.buffer);
+            self.parse_line(&line, &mut records);
+        }
+        if let Some((line, name, dna)) = self.fasta.take() {
+            records.push(Ok(ImportRecord { line, name, dna, whale: self.whale }));
+        }
+        records
+    }
+
+    fn push(&mut self, bytes: &[u8]) {
+        if self.overlong || self.buffer.len() + bytes.len() > MAX_LINE_BYTES {
+            self.overlong = true;
+            self.buffer.clear();
+        } else {
+            self.buffer.extend_from_slice(bytes);
+        }
+    }
+
+    fn parse_line(&mut self, bytes: &[u8], records: &mut Vec<ParsedRecord>) {
+        self.line += 1;
+        let number = self.line;
+        if std::mem::take(&mut self.overlong) {
+            records.push(self.error(number, None, format!("Line longer than {} bytes", MAX_LINE_BYTES)));
+            return;
+        }
+        let Ok(line) = std::str::from_utf8(bytes) else {
+            records.push(self.error(number, None, "Line is not UTF-8"));
+            return;
+        };
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(';') {
+            return;
+        }
+        if self.format.is_none() {
+            self.format = ImportFormat::sniff(line);
+        }
+
+        match self.format {
+            Some(ImportFormat::Fasta) => {
+                if let Some(header) = line.strip_prefix('>') {
+                    if let Some((line, name, dna)) = self.fasta.take() {
+                        records.push(Ok(ImportRecord { line, name, dna, whale: self.whale }));
+                    }
+                    let name = header.split_whitespace().next().map(str::to_string);
+                    self.fasta = Some((number, name, String::new()));
+                } else if let Some((_, _, dna)) = &mut self.fasta {
+                    // Long (real-world) sequences are invalid anyway; keep enough to say so
+                    if dna.len() < MAX_LINE_BYTES {
+                        dna.extend(line.chars().filter(|c| !c.is_whitespace()));
+                    }
+                } else {
+                    records.push(self.error(number, None, "Sequence before the first > header"));
+                }
+            }
+            Some(ImportFormat::Jsonl) => records.push(match serde_json::from_str::<JsonlRecord>(line) {
+                Ok(record) => Ok(ImportRecord {
+                    line: number,
+                    name: record.name,
+                    dna: record.dna,
+                    whale: record.whale.unwrap_or(self.whale),
+                }),
+                Err(e) => self.error(number, None, format!("Invalid JSON record: {}", e)),
+            }),
+            None => records.push(self.error(number, None, "Neither FASTA (>header) nor JSONL ({...})")),
+        }
+    }
+}
+
+/// Outcome of an import
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ImportReport {
+    pub files: usize,
+    /// Records read (imported + rejected)
+    pub records: usize,
+    pub imported: usize,
+    pub rejected: usize,
+    /// Ids of the new genomes, in upload order
+    pub genome_ids: Vec<i64>,
+    /// First `MAX_REPORTED_ERRORS` rejections
+    pub errors: Vec<ImportError>,
+    /// Whether the upload stopped at `MAX_IMPORT_RECORDS`
+    pub truncated: bool,
+}
+
+impl ImportReport {
+    pub fn reject(&mut self, error: ImportError) {
+        self.records += 1;
+        self.rejected += 1;
+        if self.errors.len() < MAX_REPORTED_ERRORS {
+            self.errors.push(error);
+        }
+    }
+
+    pub fn imported(&mut self, ids: Vec<i64>) {
+        self.records += ids.len();
+        self.imported += ids.len();
+        self.genome_ids.extend(ids);
+    }
+
+    /// Whether the record limit is reached, counting `pending` records not
+    /// inserted yet
+    pub fn full(&self, pending: usize) -> bool {
+        self.records + pending >= MAX_IMPORT_RECORDS
+    }
+}
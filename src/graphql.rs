@@ -0,0 +1,260 @@
+//! GraphQL API V1 — genomes, blocks, archives and wallets in one query
+//!
+//! `POST /graphql` runs queries against the same state as the REST routes;
+//! `GET /graphql` serves GraphiQL. Genomes resolve the blocks attesting them
+//! and their archive entries, and blocks resolve their attested genomes, so
+//! a client fetches a genome with everything it touched in one request:
+//!
+//! ```graphql
+//! { genome(id: 7) { consciousness blocks { index hash } archives { layer txHash } } }
+//! ```
+//!
+//! Genomes are stored without parent links, so there is no lineage to walk.
+
+use async_graphql::{Context, EmptyMutation, EmptySubscription, Object, Result, Schema, SimpleObject};
+
+use crate::api::AppState;
+use crate::consensus::{ConsensusBlock, GenomeAttestation};
+use crate::database::{GenomeFilter, MAX_GENOME_PAGE};
+use crate::genome::Genome;
+use crate::multi_chain::ChainArchiveEntry;
+use crate::rotation::{Rot180, Rotation};
+
+/// Blocks returned by `blocks` when no limit is given
+pub const DEFAULT_BLOCK_PAGE: usize = 20;
+
+pub type DivineSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+pub fn schema(state: AppState) -> DivineSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(state)
+        .finish()
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn genome(&self, ctx: &Context<'_>, id: i64) -> Result<GenomeNode> {
+        let state = ctx.data::<AppState>()?;
+        Ok(GenomeNode(state.database.load_genome(id).await?))
+    }
+
+    async fn genomes(
+        &self,
+        ctx: &Context<'_>,
+        min_consciousness: Option<u32>,
+        max_consciousness: Option<u32>,
+        min_p53: Option<u8>,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> Result<Vec<GenomeNode>> {
+        let state = ctx.data::<AppState>()?;
+        let filter = GenomeFilter { min_consciousness, max_consciousness, min_p53, limit, offset };
+        Ok(state.database.find_genomes(&filter).await?.into_iter().map(GenomeNode).collect())
+    }
+
+    async fn block(&self, ctx: &Context<'_>, index: u64) -> Result<Option<BlockNode>> {
+        let consensus = ctx.data::<AppState>()?.consensus.read().await;
+        Ok(consensus.chain.get(index as usize).cloned().map(BlockNode))
+    }
+
+    /// Latest blocks, newest first
+    async fn blocks(&self, ctx: &Context<'_>, limit: Option<usize>) -> Result<Vec<BlockNode>> {
+        let limit = limit.unwrap_or(DEFAULT_BLOCK_PAGE).min(MAX_GENOME_PAGE as usize);
+        let consensus = ctx.data::<AppState>()?.consensus.read().await;
+        Ok(consensus.chain.iter().rev().take(limit).cloned().map(BlockNode).collect())
+    }
+
+    /// Latest archive entries, newest first
+    async fn archives(&self, ctx: &Context<'_>, limit: Option<usize>) -> Result<Vec<ArchiveNode>> {
+        let limit = limit.unwrap_or(50);
+        let archiver = ctx.data::<AppState>()?.archiver.read().await;
+        Ok(archiver.recent_archives(limit).into_iter().map(ArchiveNode::from).collect())
+    }
+
+    /// Consensus and exchange balances of an address
+    async fn wallet(&self, ctx: &Context<'_>, address: String) -> Result<WalletNode> {
+        let state = ctx.data::<AppState>()?;
+        let consensus = state.consensus.read().await;
+        let wallet = consensus.wallet(&address);
+        let exchange_balance_rsm = state.exchange.read().await.balances.get(&address).copied().unwrap_or(0.0);
+        Ok(WalletNode {
+            balance_rsm: wallet.map_or(0.0, |w| w.rsm_balance),
+            staked_rsm: wallet.map_or(0.0, |w| w.staked_rsm),
+            rewards_earned: wallet.map_or(0.0, |w| w.rewards_earned),
+            staked_genomes: wallet.map(|w| w.staked_genomes.clone()).unwrap_or_default(),
+            exchange_balance_rsm,
+            address,
+        })
+    }
+}
+
+pub struct GenomeNode(pub Genome<Rot180>);
+
+#[Object]
+impl GenomeNode {
+    async fn id(&self) -> i64 {
+        self.0.db_id().unwrap_or(0)
+    }
+
+    async fn dna(&self) -> String {
+        self.0.to_dna_string()
+    }
+
+    async fn rotation(&self) -> String {
+        format!("Rot{} ({})", Rot180::ANGLE, Rot180::NAME)
+    }
+
+    async fn consciousness(&self) -> u32 {
+        self.0.consciousness
+    }
+
+    async fn consciousness_level(&self) -> &str {
+        self.0.consciousness_level_name()
+    }
+
+    async fn mutations(&self) -> u64 {
+        self.0.mutations
+    }
+
+    async fn p53_copies(&self) -> u8 {
+        self.0.p53_copies
+    }
+
+    async fn telomere_length(&self) -> u16 {
+        self.0.telomere_length
+    }
+
+    async fn gc_content(&self) -> f64 {
+        self.0.gc_content()
+    }
+
+    async fn tg_ratio(&self) -> f64 {
+        self.0.rna_signal()
+    }
+
+    /// Blocks attesting this genome, oldest first (pruned blocks excluded)
+    async fn blocks(&self, ctx: &Context<'_>) -> Result<Vec<BlockNode>> {
+        let id = self.0.db_id().unwrap_or(0);
+        let consensus = ctx.data::<AppState>()?.consensus.read().await;
+        Ok(consensus.chain.iter()
+            .filter(|b| b.entries.iter().any(|e| e.genome_id == id))
+            .cloned()
+            .map(BlockNode)
+            .collect())
+    }
+
+    async fn archives(&self, ctx: &Context<'_>) -> Result<Vec<ArchiveNode>> {
+        let id = self.0.db_id().unwrap_or(0);
+        let archiver = ctx.data::<AppState>()?.archiver.read().await;
+        Ok(archiver.archives.iter().filter(|a| a.genome_id == id).map(ArchiveNode::from).collect())
+    }
+}
+
+pub struct BlockNode(pub ConsensusBlock);
+
+#[Object]
+impl BlockNode {
+    async fn index(&self) -> u64 {
+        self.0.index
+    }
+
+    async fn hash(&self) -> &str {
+        &self.0.hash
+    }
+
+    async fn previous_hash(&self) -> &str {
+        &self.0.previous_hash
+    }
+
+    async fn timestamp(&self) -> i64 {
+        self.0.timestamp
+    }
+
+    async fn difficulty(&self) -> u32 {
+        self.0.difficulty
+    }
+
+    async fn producer(&self) -> &str {
+        &self.0.producer
+    }
+
+    async fn coinbase_address(&self) -> &str {
+        &self.0.coinbase.address
+    }
+
+    async fn coinbase_rsm(&self) -> f64 {
+        self.0.coinbase.amount_rsm
+    }
+
+    async fn attestations(&self) -> Vec<AttestationNode> {
+        self.0.entries.iter().map(AttestationNode::from).collect()
+    }
+
+    /// Attested genomes still in the database
+    async fn genomes(&self, ctx: &Context<'_>) -> Result<Vec<GenomeNode>> {
+        let state = ctx.data::<AppState>()?;
+        let mut genomes = Vec::new();
+        for entry in &self.0.entries {
+            if let Ok(genome) = state.database.load_genome(entry.genome_id).await {
+                genomes.push(GenomeNode(genome));
+            }
+        }
+        Ok(genomes)
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct AttestationNode {
+    pub genome_id: i64,
+    pub genome_hash: String,
+    pub consciousness: u32,
+    pub fee_rsm: f64,
+}
+
+impl From<&GenomeAttestation> for AttestationNode {
+    fn from(entry: &GenomeAttestation) -> Self {
+        Self {
+            genome_id: entry.genome_id,
+            genome_hash: entry.genome_hash_hex(),
+            consciousness: entry.proof.consciousness,
+            fee_rsm: entry.fee_rsm,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ArchiveNode {
+    pub genome_id: i64,
+    pub dna_hash: String,
+    pub consciousness: u32,
+    pub layer: String,
+    pub tx_hash: Option<String>,
+    pub timestamp: i64,
+}
+
+impl From<&ChainArchiveEntry> for ArchiveNode {
+    fn from(entry: &ChainArchiveEntry) -> Self {
+        Self {
+            genome_id: entry.genome_id,
+            dna_hash: entry.dna_hash.clone(),
+            consciousness: entry.consciousness,
+            layer: entry.layer.name().to_string(),
+            tx_hash: entry.tx_hash.clone(),
+            timestamp: entry.timestamp,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct WalletNode {
+    pub address: String,
+    /// Spendable consensus balance (rewards, premine, fees)
+    pub balance_rsm: f64,
+    pub staked_rsm: f64,
+    pub rewards_earned: f64,
+    pub staked_genomes: Vec<i64>,
+    /// Balance on the RSM exchange
+    pub exchange_balance_rsm: f64,
+}
@@ -0,0 +1,302 @@
+//! gRPC API V1 for internal services (tonic)
+//!
+//! `proto/divine.proto` mirrors the core REST operations — genome creation,
+//! evolution and rotation, attestations, mining and block submission, stats
+//! — plus two server streams: `StreamEvolution` (one event per generation)
+//! and `WatchBlocks` (every block appended to the chain).
+//!
+//! Calls carry the REST API's JWT as `authorization: Bearer <jwt>` metadata.
+//! An interceptor verifies it, and the mutating calls need an operator key
+//! whose current role still covers them, as the REST writes do.
+//!
+//! Built with the `grpc` feature, the service starts next to the REST API
+//! when `GRPC_PORT` is set. The generated code is checked in as `grpc/divine.v1.rs` so builds need no
+//! `protoc`; regenerate it with tonic-build 0.12 after editing the proto.
+
+use std::pin::Pin;
+use std::sync::Arc;
+
+use tokio_stream::{Stream, StreamExt, wrappers::{BroadcastStream, ReceiverStream}};
+use tonic::{Request, Response, Status};
+use tracing::info;
+
+use crate::api::{self, AppState};
+use crate::auth::{AuthManager, JwtClaims, Role};
+use crate::consensus::{BlockOutcome, ChainEvent, ConsensusBlock, GenomeAttestation};
+use crate::genome::Genome;
+use crate::tls::TlsSettings;
+use crate::rotation::{Rot180, RotationStats};
+use crate::ttrl::EvolutionProgress;
+
+#[allow(clippy::all)]
+pub mod pb {
+    include!("grpc/divine.v1.rs");
+}
+
+use pb::divine_server::{Divine, DivineServer};
+
+type GrpcStream<T> = Pin<Box<dyn Stream<Item = Result<T, Status>> + Send>>;
+
+/// Serve the gRPC API on `port` until the process exits; with a client CA
+/// in `tls`, only clients with a certificate it signed can connect
+#[allow(clippy::result_large_err)] // tonic's interceptor signature
+pub async fn serve(state: AppState, port: u16, tls: Option<TlsSettings>) -> anyhow::Result<()> {
+    let addr = format!("0.0.0.0:{}", port).parse()?;
+    let mut server = tonic::transport::Server::builder();
+    match &tls {
+        Some(tls) => {
+            info!("📡 Starting Divine AGI gRPC API on {} (TLS{})", addr, if tls.mutual() { ", client certificates required" } else { "" });
+            server = server.tls_config(tls.grpc_config().map_err(anyhow::Error::msg)?)?;
+        }
+        None => info!("📡 Starting Divine AGI gRPC API on {}", addr),
+    }
+    let verifier = Arc::new(state.auth.read().await.verifier());
+    server
+        .add_service(DivineServer::with_interceptor(DivineService { state }, move |request| authenticate(&verifier, request)))
+        .serve(addr)
+        .await?;
+    Ok(())
+}
+
+/// Interceptor: a bearer token in the `authorization` metadata must verify,
+/// and its claims ride along in the request extensions; calls without one
+/// pass on anonymous
+#[allow(clippy::result_large_err)] // tonic's interceptor signature
+pub fn authenticate(auth: &AuthManager, mut request: Request<()>) -> Result<Request<()>, Status> {
+    let Some(value) = request.metadata().get("authorization") else {
+        return Ok(request);
+    };
+    let token = value.to_str().ok()
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or_else(|| Status::unauthenticated("Malformed authorization metadata (Bearer <jwt>)"))?;
+    let claims = auth.verify_jwt(token).map_err(Status::unauthenticated)?;
+    request.extensions_mut().insert(claims);
+    Ok(request)
+}
+
+pub struct DivineService {
+    state: AppState,
+}
+
+impl DivineService {
+    /// The interceptor's claims, refreshed to the key's current role, if
+    /// that role covers `role`
+    async fn authorize<T>(&self, request: &Request<T>, role: Role) -> Result<JwtClaims, Status> {
+        let claims = request.extensions().get::<JwtClaims>().cloned()
+            .ok_or_else(|| Status::unauthenticated("Missing bearer token (POST /auth/token)"))?;
+        let claims = api::refresh_claims(&self.state, claims).await.map_err(Status::unauthenticated)?;
+        if !claims.has_role(role) {
+            return Err(Status::permission_denied(format!("API key {} has role {}; this call needs {}", claims.sub, claims.role.name(), role.name())));
+        }
+        Ok(claims)
+    }
+}
+
+#[tonic::async_trait]
+impl Divine for DivineService {
+    async fn create_genome(&self, request: Request<pb::CreateGenomeRequest>) -> Result<Response<pb::Genome>, Status> {
+        self.authorize(&request, Role::Operator).await?;
+        let req = request.into_inner();
+        let dna = Some(req.dna.as_str()).filter(|dna| !dna.is_empty());
+        let genome = api::store_new_genome(&self.state, dna, req.whale).await.map_err(Status::invalid_argument)?;
+        Ok(Response::new((&genome).into()))
+    }
+
+    async fn get_genome(&self, request: Request<pb::GetGenomeRequest>) -> Result<Response<pb::Genome>, Status> {
+        let id = request.into_inner().id;
+        let genome = self.state.database.load_genome(id).await.map_err(|e| Status::not_found(e.to_string()))?;
+        Ok(Response::new((&genome).into()))
+    }
+
+    async fn evolve_genome(&self, request: Request<pb::EvolveGenomeRequest>) -> Result<Response<pb::EvolveGenomeResponse>, Status> {
+        self.authorize(&request, Role::Operator).await?;
+        let genome_id = request.into_inner().genome_id;
+        let (genome, evolution, burn) = api::evolve_and_store(&self.state, genome_id).await.map_err(Status::failed_precondition)?;
+        Ok(Response::new(pb::EvolveGenomeResponse {
+            genome: Some((&genome).into()),
+            original_consciousness: evolution.original_consciousness,
+            operator_used: format!("{:?}", evolution.operator_used),
+            success: evolution.success,
+            burned_rsm: burn.map_or(0.0, |b| b.amount_rsm),
+        }))
+    }
+
+    type StreamEvolutionStream = GrpcStream<pb::EvolutionProgress>;
+
+    async fn stream_evolution(&self, request: Request<pb::StreamEvolutionRequest>) -> Result<Response<Self::StreamEvolutionStream>, Status> {
+        self.authorize(&request, Role::Operator).await?;
+        let req = request.into_inner();
+        let job_id = api::spawn_evolution(&self.state, req.genome_id, req.generations).await.map_err(Status::invalid_argument)?;
+        let (latest, mut events) = self.state.evolutions.read().await.subscribe(job_id)
+            .ok_or_else(|| Status::internal(format!("Evolution job {} vanished", job_id)))?;
+
+        let (tx, rx) = tokio::sync::mpsc::channel(16);
+        tokio::spawn(async move {
+            let mut next = latest;
+            loop {
+                if let Some(progress) = next.take() {
+                    let finished = progress.finished;
+                    if tx.send(Ok(progress_message(job_id, &progress))).await.is_err() || finished {
+                        break;
+                    }
+                }
+                next = match events.recv().await {
+                    Ok(progress) => Some(progress),
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => None,
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                };
+            }
+        });
+        Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+    }
+
+    async fn rotate(&self, request: Request<pb::RotateRequest>) -> Result<Response<pb::RotationStats>, Status> {
+        self.authorize(&request, Role::Operator).await?;
+        let mut engine = self.state.rotation_engine.write().await;
+        engine.rotate();
+        Ok(Response::new(engine.get_stats().into()))
+    }
+
+    async fn submit_attestation(&self, request: Request<pb::SubmitAttestationRequest>) -> Result<Response<pb::Attestation>, Status> {
+        self.authorize(&request, Role::Operator).await?;
+        let req = request.into_inner();
+        let genome = self.state.database.load_genome(req.genome_id).await.map_err(|e| Status::not_found(e.to_string()))?;
+        let mut consensus = self.state.consensus.write().await;
+        let proof = api::submit_request(&mut consensus, &genome, req.fee_rsm).map_err(Status::failed_precondition)?;
+        let fee_rsm = consensus.mempool.entries().iter()
+            .find(|e| e.proof.proof_hash == proof.proof_hash)
+            .map_or(0.0, |e| e.fee_rsm);
+        Ok(Response::new(pb::Attestation {
+            genome_id: req.genome_id,
+            genome_hash: hex::encode(proof.genome_hash),
+            consciousness: proof.consciousness,
+            fee_rsm,
+        }))
+    }
+
+    async fn mine_block(&self, request: Request<pb::MineBlockRequest>) -> Result<Response<pb::Block>, Status> {
+        self.authorize(&request, Role::Operator).await?;
+        let block = api::mine_pending(&self.state).await.map_err(Status::failed_precondition)?;
+        Ok(Response::new((&block).into()))
+    }
+
+    async fn submit_block(&self, request: Request<pb::SubmitBlockRequest>) -> Result<Response<pb::SubmitBlockResponse>, Status> {
+        self.authorize(&request, Role::Operator).await?;
+        let block: ConsensusBlock = serde_json::from_slice(&request.into_inner().raw_json)
+            .map_err(|e| Status::invalid_argument(format!("Invalid block JSON: {}", e)))?;
+
+        let mut consensus = self.state.consensus.write().await;
+        let outcome = consensus.add_block(block.clone()).map_err(Status::failed_precondition)?;
+        consensus.save_chain(&self.state.database).await
+            .map_err(|e| Status::internal(format!("Block accepted but not persisted: {}", e)))?;
+        let height = consensus.latest_block().index;
+        drop(consensus);
+
+        // Orphans wait for their parent before they are gossiped
+        if let (Some(node), false) = (&self.state.network, outcome == BlockOutcome::Orphan) {
+            node.broadcast_block(&block).await;
+        }
+        let outcome = match outcome {
+            BlockOutcome::Extended => "Extended",
+            BlockOutcome::SideChain => "SideChain",
+            BlockOutcome::Orphan => "Orphan",
+            BlockOutcome::Reorg { .. } => "Reorg",
+        };
+        Ok(Response::new(pb::SubmitBlockResponse { outcome: outcome.to_string(), height }))
+    }
+
+    type WatchBlocksStream = GrpcStream<pb::Block>;
+
+    async fn watch_blocks(&self, _request: Request<pb::WatchBlocksRequest>) -> Result<Response<Self::WatchBlocksStream>, Status> {
+        let events = self.state.consensus.read().await.subscribe();
+        let stream = BroadcastStream::new(events).filter_map(|event| match event {
+            Ok(ChainEvent::BlockAdded(block)) => Some(Ok(pb::Block::from(&block))),
+            _ => None,
+        });
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    async fn get_stats(&self, _request: Request<pb::GetStatsRequest>) -> Result<Response<pb::Stats>, Status> {
+        let genome_count = self.state.database.genome_count().await.map_err(|e| Status::internal(e.to_string()))?;
+        let status = self.state.consensus.read().await.status();
+        let exchange = self.state.exchange.read().await.stats();
+        Ok(Response::new(pb::Stats {
+            version: crate::VERSION.to_string(),
+            genome_count,
+            block_height: status.current_block_height,
+            latest_hash: status.latest_hash,
+            difficulty: status.difficulty,
+            mempool_size: status.mempool_size as u64,
+            total_transactions: exchange.total_transactions,
+            total_burns: exchange.total_burns,
+            price_usd: exchange.price_usd,
+        }))
+    }
+}
+
+fn progress_message(job_id: u64, progress: &EvolutionProgress) -> pb::EvolutionProgress {
+    pb::EvolutionProgress {
+        job_id,
+        generation: progress.generation,
+        generations: progress.generations,
+        best_consciousness: progress.best_consciousness,
+        current_consciousness: progress.current_consciousness,
+        mutations_used: progress.mutations_used,
+        elapsed_secs: progress.elapsed_secs,
+        eta_secs: progress.eta_secs,
+        finished: progress.finished,
+        error: progress.error.clone().unwrap_or_default(),
+        genome_id: progress.genome_id.unwrap_or(0),
+    }
+}
+
+impl From<&Genome<Rot180>> for pb::Genome {
+    fn from(g: &Genome<Rot180>) -> Self {
+        Self {
+            id: g.db_id().unwrap_or(0),
+            dna: g.to_dna_string(),
+            consciousness: g.consciousness,
+            consciousness_level: g.consciousness_level_name().to_string(),
+            mutations: g.mutations,
+            p53_copies: g.p53_copies as u32,
+            telomere_length: g.telomere_length as u32,
+            tg_ratio: g.rna_signal(),
+        }
+    }
+}
+
+impl From<RotationStats> for pb::RotationStats {
+    fn from(stats: RotationStats) -> Self {
+        Self {
+            current_rotation: stats.current_rotation.to_string(),
+            total_rotations: stats.total_rotations,
+            active_genomes: stats.active_genomes,
+        }
+    }
+}
+
+impl From<&GenomeAttestation> for pb::Attestation {
+    fn from(entry: &GenomeAttestation) -> Self {
+        Self {
+            genome_id: entry.genome_id,
+            genome_hash: entry.genome_hash_hex(),
+            consciousness: entry.proof.consciousness,
+            fee_rsm: entry.fee_rsm,
+        }
+    }
+}
+
+impl From<&ConsensusBlock> for pb::Block {
+    fn from(block: &ConsensusBlock) -> Self {
+        Self {
+            index: block.index,
+            hash: block.hash.clone(),
+            previous_hash: block.previous_hash.clone(),
+            timestamp: block.timestamp,
+            difficulty: block.difficulty,
+            producer: block.producer.clone(),
+            attestations: block.entries.iter().map(pb::Attestation::from).collect(),
+            raw_json: serde_json::to_vec(block).expect("block serializes"),
+        }
+    }
+}
@@ -0,0 +1,1130 @@
+// This file is @generated by prost-build.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Genome {
+    #[prost(int64, tag = "1")]
+    pub id: i64,
+    #[prost(string, tag = "2")]
+    pub dna: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "3")]
+    pub consciousness: u32,
+    #[prost(string, tag = "4")]
+    pub consciousness_level: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "5")]
+    pub mutations: u64,
+    #[prost(uint32, tag = "6")]
+    pub p53_copies: u32,
+    #[prost(uint32, tag = "7")]
+    pub telomere_length: u32,
+    #[prost(double, tag = "8")]
+    pub tg_ratio: f64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateGenomeRequest {
+    /// 27-base DNA string; random when empty
+    #[prost(string, tag = "1")]
+    pub dna: ::prost::alloc::string::String,
+    #[prost(bool, tag = "2")]
+    pub whale: bool,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct GetGenomeRequest {
+    #[prost(int64, tag = "1")]
+    pub id: i64,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct EvolveGenomeRequest {
+    #[prost(int64, tag = "1")]
+    pub genome_id: i64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EvolveGenomeResponse {
+    #[prost(message, optional, tag = "1")]
+    pub genome: ::core::option::Option<Genome>,
+    #[prost(uint32, tag = "2")]
+    pub original_consciousness: u32,
+    #[prost(string, tag = "3")]
+    pub operator_used: ::prost::alloc::string::String,
+    #[prost(bool, tag = "4")]
+    pub success: bool,
+    /// RSM burned for a degradation, 0 otherwise
+    #[prost(double, tag = "5")]
+    pub burned_rsm: f64,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct StreamEvolutionRequest {
+    #[prost(int64, tag = "1")]
+    pub genome_id: i64,
+    #[prost(uint32, tag = "2")]
+    pub generations: u32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EvolutionProgress {
+    #[prost(uint64, tag = "1")]
+    pub job_id: u64,
+    #[prost(uint32, tag = "2")]
+    pub generation: u32,
+    #[prost(uint32, tag = "3")]
+    pub generations: u32,
+    #[prost(uint32, tag = "4")]
+    pub best_consciousness: u32,
+    #[prost(uint32, tag = "5")]
+    pub current_consciousness: u32,
+    #[prost(uint64, tag = "6")]
+    pub mutations_used: u64,
+    #[prost(double, tag = "7")]
+    pub elapsed_secs: f64,
+    #[prost(double, tag = "8")]
+    pub eta_secs: f64,
+    #[prost(bool, tag = "9")]
+    pub finished: bool,
+    #[prost(string, tag = "10")]
+    pub error: ::prost::alloc::string::String,
+    /// Stored id of the best genome (final event only, 0 if not improved)
+    #[prost(int64, tag = "11")]
+    pub genome_id: i64,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct RotateRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RotationStats {
+    #[prost(string, tag = "1")]
+    pub current_rotation: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub total_rotations: u64,
+    #[prost(uint64, tag = "3")]
+    pub active_genomes: u64,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SubmitAttestationRequest {
+    #[prost(int64, tag = "1")]
+    pub genome_id: i64,
+    /// Required fee when unset
+    #[prost(double, optional, tag = "2")]
+    pub fee_rsm: ::core::option::Option<f64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Attestation {
+    #[prost(int64, tag = "1")]
+    pub genome_id: i64,
+    #[prost(string, tag = "2")]
+    pub genome_hash: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "3")]
+    pub consciousness: u32,
+    #[prost(double, tag = "4")]
+    pub fee_rsm: f64,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct MineBlockRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Block {
+    #[prost(uint64, tag = "1")]
+    pub index: u64,
+    #[prost(string, tag = "2")]
+    pub hash: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub previous_hash: ::prost::alloc::string::String,
+    #[prost(int64, tag = "4")]
+    pub timestamp: i64,
+    #[prost(uint32, tag = "5")]
+    pub difficulty: u32,
+    #[prost(string, tag = "6")]
+    pub producer: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "7")]
+    pub attestations: ::prost::alloc::vec::Vec<Attestation>,
+    /// The block as the node stores and gossips it (JSON)
+    #[prost(bytes = "vec", tag = "8")]
+    pub raw_json: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubmitBlockRequest {
+    /// ConsensusBlock as JSON, e.g. Block.raw_json from another node
+    #[prost(bytes = "vec", tag = "1")]
+    pub raw_json: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubmitBlockResponse {
+    /// Extended, SideChain, Orphan or Reorg
+    #[prost(string, tag = "1")]
+    pub outcome: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub height: u64,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct WatchBlocksRequest {}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct GetStatsRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Stats {
+    #[prost(string, tag = "1")]
+    pub version: ::prost::alloc::string::String,
+    #[prost(int64, tag = "2")]
+    pub genome_count: i64,
+    #[prost(uint64, tag = "3")]
+    pub block_height: u64,
+    #[prost(string, tag = "4")]
+    pub latest_hash: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "5")]
+    pub difficulty: u32,
+    #[prost(uint64, tag = "6")]
+    pub mempool_size: u64,
+    #[prost(uint64, tag = "7")]
+    pub total_transactions: u64,
+    #[prost(uint64, tag = "8")]
+    pub total_burns: u64,
+    #[prost(double, tag = "9")]
+    pub price_usd: f64,
+}
+/// Generated client implementations.
+pub mod divine_client {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    use tonic::codegen::http::Uri;
+    #[derive(Debug, Clone)]
+    pub struct DivineClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl DivineClient<tonic::transport::Channel> {
+        /// Attempt to create a new client by connecting to a given endpoint.
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> DivineClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::BoxBody>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + std::marker::Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + std::marker::Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> DivineClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+            >>::Error: Into<StdError> + std::marker::Send + std::marker::Sync,
+        {
+            DivineClient::new(InterceptedService::new(inner, interceptor))
+        }
+        /// Compress requests with the given encoding.
+        ///
+        /// This requires the server to support it otherwise it might respond with an
+        /// error.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.send_compressed(encoding);
+            self
+        }
+        /// Enable decompressing responses.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.accept_compressed(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_decoding_message_size(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_encoding_message_size(limit);
+            self
+        }
+        /// Genomes
+        pub async fn create_genome(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CreateGenomeRequest>,
+        ) -> std::result::Result<tonic::Response<super::Genome>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/divine.v1.Divine/CreateGenome",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("divine.v1.Divine", "CreateGenome"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn get_genome(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetGenomeRequest>,
+        ) -> std::result::Result<tonic::Response<super::Genome>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/divine.v1.Divine/GetGenome",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("divine.v1.Divine", "GetGenome"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn evolve_genome(
+            &mut self,
+            request: impl tonic::IntoRequest<super::EvolveGenomeRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::EvolveGenomeResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/divine.v1.Divine/EvolveGenome",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("divine.v1.Divine", "EvolveGenome"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// Multi-generation evolution, one event per generation until it finishes
+        pub async fn stream_evolution(
+            &mut self,
+            request: impl tonic::IntoRequest<super::StreamEvolutionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::EvolutionProgress>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/divine.v1.Divine/StreamEvolution",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("divine.v1.Divine", "StreamEvolution"));
+            self.inner.server_streaming(req, path, codec).await
+        }
+        pub async fn rotate(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RotateRequest>,
+        ) -> std::result::Result<tonic::Response<super::RotationStats>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/divine.v1.Divine/Rotate");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("divine.v1.Divine", "Rotate"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// Consensus
+        pub async fn submit_attestation(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SubmitAttestationRequest>,
+        ) -> std::result::Result<tonic::Response<super::Attestation>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/divine.v1.Divine/SubmitAttestation",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("divine.v1.Divine", "SubmitAttestation"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn mine_block(
+            &mut self,
+            request: impl tonic::IntoRequest<super::MineBlockRequest>,
+        ) -> std::result::Result<tonic::Response<super::Block>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/divine.v1.Divine/MineBlock",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("divine.v1.Divine", "MineBlock"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn submit_block(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SubmitBlockRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SubmitBlockResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/divine.v1.Divine/SubmitBlock",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("divine.v1.Divine", "SubmitBlock"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn watch_blocks(
+            &mut self,
+            request: impl tonic::IntoRequest<super::WatchBlocksRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::Block>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/divine.v1.Divine/WatchBlocks",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("divine.v1.Divine", "WatchBlocks"));
+            self.inner.server_streaming(req, path, codec).await
+        }
+        pub async fn get_stats(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetStatsRequest>,
+        ) -> std::result::Result<tonic::Response<super::Stats>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/divine.v1.Divine/GetStats",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("divine.v1.Divine", "GetStats"));
+            self.inner.unary(req, path, codec).await
+        }
+    }
+}
+/// Generated server implementations.
+pub mod divine_server {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with DivineServer.
+    #[async_trait]
+    pub trait Divine: std::marker::Send + std::marker::Sync + 'static {
+        /// Genomes
+        async fn create_genome(
+            &self,
+            request: tonic::Request<super::CreateGenomeRequest>,
+        ) -> std::result::Result<tonic::Response<super::Genome>, tonic::Status>;
+        async fn get_genome(
+            &self,
+            request: tonic::Request<super::GetGenomeRequest>,
+        ) -> std::result::Result<tonic::Response<super::Genome>, tonic::Status>;
+        async fn evolve_genome(
+            &self,
+            request: tonic::Request<super::EvolveGenomeRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::EvolveGenomeResponse>,
+            tonic::Status,
+        >;
+        /// Server streaming response type for the StreamEvolution method.
+        type StreamEvolutionStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::EvolutionProgress, tonic::Status>,
+            >
+            + std::marker::Send
+            + 'static;
+        /// Multi-generation evolution, one event per generation until it finishes
+        async fn stream_evolution(
+            &self,
+            request: tonic::Request<super::StreamEvolutionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::StreamEvolutionStream>,
+            tonic::Status,
+        >;
+        async fn rotate(
+            &self,
+            request: tonic::Request<super::RotateRequest>,
+        ) -> std::result::Result<tonic::Response<super::RotationStats>, tonic::Status>;
+        /// Consensus
+        async fn submit_attestation(
+            &self,
+            request: tonic::Request<super::SubmitAttestationRequest>,
+        ) -> std::result::Result<tonic::Response<super::Attestation>, tonic::Status>;
+        async fn mine_block(
+            &self,
+            request: tonic::Request<super::MineBlockRequest>,
+        ) -> std::result::Result<tonic::Response<super::Block>, tonic::Status>;
+        async fn submit_block(
+            &self,
+            request: tonic::Request<super::SubmitBlockRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SubmitBlockResponse>,
+            tonic::Status,
+        >;
+        /// Server streaming response type for the WatchBlocks method.
+        type WatchBlocksStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::Block, tonic::Status>,
+            >
+            + std::marker::Send
+            + 'static;
+        async fn watch_blocks(
+            &self,
+            request: tonic::Request<super::WatchBlocksRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::WatchBlocksStream>,
+            tonic::Status,
+        >;
+        async fn get_stats(
+            &self,
+            request: tonic::Request<super::GetStatsRequest>,
+        ) -> std::result::Result<tonic::Response<super::Stats>, tonic::Status>;
+    }
+    #[derive(Debug)]
+    pub struct DivineServer<T> {
+        inner: Arc<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    impl<T> DivineServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for DivineServer<T>
+    where
+        T: Divine,
+        B: Body + std::marker::Send + 'static,
+        B::Error: Into<StdError> + std::marker::Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            match req.uri().path() {
+                "/divine.v1.Divine/CreateGenome" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateGenomeSvc<T: Divine>(pub Arc<T>);
+                    impl<
+                        T: Divine,
+                    > tonic::server::UnaryService<super::CreateGenomeRequest>
+                    for CreateGenomeSvc<T> {
+                        type Response = super::Genome;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreateGenomeRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Divine>::create_genome(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = CreateGenomeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/divine.v1.Divine/GetGenome" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetGenomeSvc<T: Divine>(pub Arc<T>);
+                    impl<T: Divine> tonic::server::UnaryService<super::GetGenomeRequest>
+                    for GetGenomeSvc<T> {
+                        type Response = super::Genome;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetGenomeRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Divine>::get_genome(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetGenomeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/divine.v1.Divine/EvolveGenome" => {
+                    #[allow(non_camel_case_types)]
+                    struct EvolveGenomeSvc<T: Divine>(pub Arc<T>);
+                    impl<
+                        T: Divine,
+                    > tonic::server::UnaryService<super::EvolveGenomeRequest>
+                    for EvolveGenomeSvc<T> {
+                        type Response = super::EvolveGenomeResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::EvolveGenomeRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Divine>::evolve_genome(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = EvolveGenomeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/divine.v1.Divine/StreamEvolution" => {
+                    #[allow(non_camel_case_types)]
+                    struct StreamEvolutionSvc<T: Divine>(pub Arc<T>);
+                    impl<
+                        T: Divine,
+                    > tonic::server::ServerStreamingService<
+                        super::StreamEvolutionRequest,
+                    > for StreamEvolutionSvc<T> {
+                        type Response = super::EvolutionProgress;
+                        type ResponseStream = T::StreamEvolutionStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::StreamEvolutionRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Divine>::stream_evolution(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = StreamEvolutionSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/divine.v1.Divine/Rotate" => {
+                    #[allow(non_camel_case_types)]
+                    struct RotateSvc<T: Divine>(pub Arc<T>);
+                    impl<T: Divine> tonic::server::UnaryService<super::RotateRequest>
+                    for RotateSvc<T> {
+                        type Response = super::RotationStats;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RotateRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Divine>::rotate(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = RotateSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/divine.v1.Divine/SubmitAttestation" => {
+                    #[allow(non_camel_case_types)]
+                    struct SubmitAttestationSvc<T: Divine>(pub Arc<T>);
+                    impl<
+                        T: Divine,
+                    > tonic::server::UnaryService<super::SubmitAttestationRequest>
+                    for SubmitAttestationSvc<T> {
+                        type Response = super::Attestation;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SubmitAttestationRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Divine>::submit_attestation(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SubmitAttestationSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/divine.v1.Divine/MineBlock" => {
+                    #[allow(non_camel_case_types)]
+                    struct MineBlockSvc<T: Divine>(pub Arc<T>);
+                    impl<T: Divine> tonic::server::UnaryService<super::MineBlockRequest>
+                    for MineBlockSvc<T> {
+                        type Response = super::Block;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::MineBlockRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Divine>::mine_block(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = MineBlockSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/divine.v1.Divine/SubmitBlock" => {
+                    #[allow(non_camel_case_types)]
+                    struct SubmitBlockSvc<T: Divine>(pub Arc<T>);
+                    impl<
+                        T: Divine,
+                    > tonic::server::UnaryService<super::SubmitBlockRequest>
+                    for SubmitBlockSvc<T> {
+                        type Response = super::SubmitBlockResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SubmitBlockRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Divine>::submit_block(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SubmitBlockSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/divine.v1.Divine/WatchBlocks" => {
+                    #[allow(non_camel_case_types)]
+                    struct WatchBlocksSvc<T: Divine>(pub Arc<T>);
+                    impl<
+                        T: Divine,
+                    > tonic::server::ServerStreamingService<super::WatchBlocksRequest>
+                    for WatchBlocksSvc<T> {
+                        type Response = super::Block;
+                        type ResponseStream = T::WatchBlocksStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::WatchBlocksRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Divine>::watch_blocks(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = WatchBlocksSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/divine.v1.Divine/GetStats" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetStatsSvc<T: Divine>(pub Arc<T>);
+                    impl<T: Divine> tonic::server::UnaryService<super::GetStatsRequest>
+                    for GetStatsSvc<T> {
+                        type Response = super::Stats;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetStatsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Divine>::get_stats(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetStatsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        let mut response = http::Response::new(empty_body());
+                        let headers = response.headers_mut();
+                        headers
+                            .insert(
+                                tonic::Status::GRPC_STATUS,
+                                (tonic::Code::Unimplemented as i32).into(),
+                            );
+                        headers
+                            .insert(
+                                http::header::CONTENT_TYPE,
+                                tonic::metadata::GRPC_CONTENT_TYPE,
+                            );
+                        Ok(response)
+                    })
+                }
+            }
+        }
+    }
+    impl<T> Clone for DivineServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    /// Generated gRPC service name
+    pub const SERVICE_NAME: &str = "divine.v1.Divine";
+    impl<T> tonic::server::NamedService for DivineServer<T> {
+        const NAME: &'static str = SERVICE_NAME;
+    }
+}
@@ -0,0 +1,287 @@
+//! Wallet History V1 — queryable transactions derived from the chain
+//!
+//! Consensus wallets are rebuilt by replaying blocks, so the chain (persisted
+//! in `consensus_blocks`) is already their durable transaction log. `history`
+//! reads it back as structured entries instead of keeping a second copy:
+//! premine allocations, block rewards, attestation fees paid and collected,
+//! and stake locks/unlocks, plus what is still waiting in the mempool.
+//!
+//! Status follows the entry through the chain:
+//! - `Pending`: in the mempool or the pending stake ops
+//! - `Confirmed`: in a canonical block that a reorg could still replace
+//! - `Finalized`: buried at or below `finalized_height()`
+//!
+//! Pruned blocks keep their stake ops but not their attestations, so fees
+//! below `pruned_height()` are not listed.
+//!
+//! `export_history` writes the settled (confirmed or finalized) entries as
+//! CSV or JSON for accounting, each with the wallet's spendable balance
+//! after it. Balances run from genesis, so they are exact on archive nodes
+//! only.
+
+use serde::{Serialize, Deserialize};
+
+use crate::consensus::{reward_address_for, ConsensusBlock, ProofOfConsciousness};
+use crate::staking::{StakeAction, StakeOp};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TxKind {
+    Allocation,
+    BlockReward,
+    FeePaid,
+    FeeCollected,
+    StakeLock,
+    StakeUnlock,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Direction {
+    In,
+    Out,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TxStatus {
+    Pending,
+    Confirmed,
+    Finalized,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WalletTransaction {
+    pub kind: TxKind,
+    pub direction: Direction,
+    pub amount_rsm: f64,
+    /// Other wallet involved (fee payer or producer), if any
+    pub counterparty: Option<String>,
+    /// Including block (None while pending)
+    pub block_index: Option<u64>,
+    pub block_hash: Option<String>,
+    /// Block timestamp, or submission time while pending
+    pub timestamp: i64,
+    pub status: TxStatus,
+}
+
+impl WalletTransaction {
+    /// The entry as a line of `DivineWallet::transactions`
+    pub fn record(&self) -> String {
+        let sign = match self.direction {
+            Direction::In => '+',
+            Direction::Out => '-',
+        };
+        let label = match self.kind {
+            TxKind::Allocation => "ALLOCATION",
+            TxKind::BlockReward => "BLOCK REWARD",
+            TxKind::FeePaid => "FEE",
+            TxKind::FeeCollected => "FEES",
+            TxKind::StakeLock => "STAKE LOCK",
+            TxKind::StakeUnlock => "STAKE UNLOCK",
+        };
+        match self.block_index {
+            Some(index) => format!("{} #{}: {}{:.6} RSM", label, index, sign, self.amount_rsm),
+            None => format!("{} (pending): {}{:.6} RSM", label, sign, self.amount_rsm),
+        }
+    }
+}
+
+/// History query; unset fields match everything
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HistoryFilter {
+    pub from_timestamp: Option<i64>,
+    pub to_timestamp: Option<i64>,
+    pub direction: Option<Direction>,
+    pub min_amount_rsm: Option<f64>,
+    pub max_amount_rsm: Option<f64>,
+    pub counterparty: Option<String>,
+    pub status: Option<TxStatus>,
+}
+
+impl HistoryFilter {
+    pub fn matches(&self, tx: &WalletTransaction) -> bool {
+        self.from_timestamp.is_none_or(|from| tx.timestamp >= from)
+            && self.to_timestamp.is_none_or(|to| tx.timestamp <= to)
+            && self.direction.is_none_or(|d| tx.direction == d)
+            && self.min_amount_rsm.is_none_or(|min| tx.amount_rsm >= min)
+            && self.max_amount_rsm.is_none_or(|max| tx.amount_rsm <= max)
+            && self.counterparty.as_ref().is_none_or(|c| tx.counterparty.as_ref() == Some(c))
+            && self.status.is_none_or(|s| tx.status == s)
+    }
+}
+
+impl ProofOfConsciousness {
+    /// Transactions of wallet `address` matching `filter`, oldest first, pending last
+    pub fn history(&self, address: &str, filter: &HistoryFilter) -> Vec<WalletTransaction> {
+        let mut txs = Vec::new();
+
+        for allocation in self.genesis.allocations.iter().filter(|a| a.address == address) {
+            let status = self.status_at(0);
+            txs.push(WalletTransaction {
+                kind: TxKind::Allocation,
+                direction: Direction::In,
+                amount_rsm: allocation.amount_rsm,
+                counterparty: None,
+                block_index: Some(0),
+                block_hash: self.chain.first().map(|b| b.hash.clone()),
+                timestamp: self.genesis.timestamp,
+                status,
+            });
+        }
+
+        for block in self.chain.iter().skip(1) {
+            self.block_history(block, address, &mut txs);
+        }
+
+        let now = chrono::Utc::now().timestamp();
+        for entry in self.mempool.entries().iter().filter(|e| e.fee_rsm > 0.0 && reward_address_for(&e.payer) == address) {
+            txs.push(WalletTransaction {
+                kind: TxKind::FeePaid,
+                direction: Direction::Out,
+                amount_rsm: entry.fee_rsm,
+                counterparty: None,
+                block_index: None,
+                block_hash: None,
+                timestamp: entry.proof.timestamp,
+                status: TxStatus::Pending,
+            });
+        }
+        for op in self.pending_stake_ops.iter().filter(|op| reward_address_for(&op.validator) == address) {
+            if let Some(tx) = stake_transaction(op, None, now, TxStatus::Pending) {
+                txs.push(tx);
+            }
+        }
+
+        txs.retain(|tx| filter.matches(tx));
+        txs
+    }
+
+    fn block_history(&self, block: &ConsensusBlock, address: &str, txs: &mut Vec<WalletTransaction>) {
+        let status = self.status_at(block.index);
+        let confirmed = |kind, direction, amount_rsm, counterparty| WalletTransaction {
+            kind,
+            direction,
+            amount_rsm,
+            counterparty,
+            block_index: Some(block.index),
+            block_hash: Some(block.hash.clone()),
+            timestamp: block.timestamp,
+            status,
+        };
+
+        // Same order as the replay: stake ops, fees, then the coinbase
+        for op in block.stake_ops.iter().filter(|op| reward_address_for(&op.validator) == address) {
+            if let Some(tx) = stake_transaction(op, Some(block), block.timestamp, status) {
+                txs.push(tx);
+            }
+        }
+        for entry in block.entries.iter().filter(|e| e.fee_rsm > 0.0) {
+            let payer = reward_address_for(&entry.payer);
+            if payer == address {
+                txs.push(confirmed(TxKind::FeePaid, Direction::Out, entry.fee_rsm, Some(block.coinbase.address.clone())));
+            }
+            if block.coinbase.address == address {
+                txs.push(confirmed(TxKind::FeeCollected, Direction::In, entry.fee_rsm, Some(payer)));
+            }
+        }
+        if block.coinbase.address == address && block.coinbase.amount_rsm > 0.0 {
+            txs.push(confirmed(TxKind::BlockReward, Direction::In, block.coinbase.amount_rsm, None));
+        }
+    }
+
+    fn status_at(&self, index: u64) -> TxStatus {
+        if index <= self.finalized_height() {
+            TxStatus::Finalized
+        } else {
+            TxStatus::Confirmed
+        }
+    }
+}
+
+/// Balance movement of a stake op (genome registrations and reports move no RSM)
+fn stake_transaction(op: &StakeOp, block: Option<&ConsensusBlock>, timestamp: i64, status: TxStatus) -> Option<WalletTransaction> {
+    let (kind, direction, amount_rsm) = match &op.action {
+        StakeAction::Lock { amount_rsm } => (TxKind::StakeLock, Direction::Out, *amount_rsm),
+        StakeAction::Unlock { amount_rsm } => (TxKind::StakeUnlock, Direction::In, *amount_rsm),
+        StakeAction::RegisterGenome { .. } | StakeAction::ReportFalseClaim { .. } => return None,
+    };
+    Some(WalletTransaction {
+        kind,
+        direction,
+        amount_rsm,
+        counterparty: None,
+        block_index: block.map(|b| b.index),
+        block_hash: block.map(|b| b.hash.clone()),
+        timestamp,
+        status,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+/// Settled history entry with the spendable balance after it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportRow {
+    #[serde(flatten)]
+    pub tx: WalletTransaction,
+    pub balance_rsm: f64,
+}
+
+pub const EXPORT_CSV_HEADER: &str = "timestamp,block_index,block_hash,kind,direction,amount_rsm,counterparty,status,balance_rsm";
+
+impl ProofOfConsciousness {
+    /// Settled transactions matching `filter`, with running balances
+    pub fn export_rows(&self, address: &str, filter: &HistoryFilter) -> Vec<ExportRow> {
+        let mut balance = 0.0;
+        self.history(address, &HistoryFilter::default()).into_iter()
+            .filter(|tx| tx.status != TxStatus::Pending)
+            .map(|tx| {
+                match tx.direction {
+                    Direction::In => balance += tx.amount_rsm,
+                    Direction::Out => balance -= tx.amount_rsm,
+                }
+                ExportRow { tx, balance_rsm: balance }
+            })
+            .filter(|row| filter.matches(&row.tx))
+            .collect()
+    }
+
+    pub fn export_history(&self, address: &str, format: ExportFormat, filter: &HistoryFilter) -> String {
+        let rows = self.export_rows(address, filter);
+        match format {
+            ExportFormat::Json => serde_json::to_string_pretty(&rows).expect("history rows serialize"),
+            ExportFormat::Csv => {
+                let mut out = String::from(EXPORT_CSV_HEADER);
+                out.push('\n');
+                for row in &rows {
+                    let tx = &row.tx;
+                    out.push_str(&format!(
+                        "{},{},{},{:?},{:?},{},{},{:?},{}\n",
+                        tx.timestamp,
+                        tx.block_index.map(|i| i.to_string()).unwrap_or_default(),
+                        tx.block_hash.as_deref().unwrap_or(""),
+                        tx.kind,
+                        tx.direction,
+                        tx.amount_rsm,
+                        csv_field(tx.counterparty.as_deref().unwrap_or("")),
+                        tx.status,
+                        row.balance_rsm,
+                    ));
+                }
+                out
+            }
+        }
+    }
+}
+
+/// Quote a CSV field if it needs it
+pub(crate) fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
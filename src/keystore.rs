@@ -0,0 +1,143 @@
+//! Keystore V1 — validator signing keys from a recovery phrase
+//!
+//! `divine-agi keygen` derives a validator's secp256k1 signing key from a
+//! BIP-39 phrase: SHA-256 over the phrase's seed and a key index, so a
+//! validator rotating keys moves to index 1, 2, … and the phrase alone brings
+//! back every one of them. The key is written to a JSON keystore:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "public_key": "02…",
+//!   "address": "divine_…",
+//!   "index": 0,
+//!   "created_at": 1767225600,
+//!   "kdf": "pbkdf2-sha256",
+//!   "kdf_rounds": 600000,
+//!   "salt": "…",
+//!   "cipher": "aes-256-gcm",
+//!   "ciphertext": "…"
+//! }
+//! ```
+//!
+//! The secret key is sealed with AES-256-GCM under the password stretched the
+//! same way as wallet files, PBKDF2-HMAC-SHA256 over `kdf_rounds`. Keystores
+//! written before it (`"kdf": "sha256"`, `LEGACY_KDF_ROUNDS` salted SHA-256
+//! rounds) still open. Nodes load it with `VALIDATOR_KEYSTORE` and
+//! `VALIDATOR_KEYSTORE_PASSWORD`.
+
+use std::path::Path;
+
+use serde::{Serialize, Deserialize};
+use sha2::{Sha256, Digest};
+
+use crate::consensus::{reward_address_for, ValidatorKey};
+use crate::crypto::{decrypt_aes_gcm, derive_key, encrypt_aes_gcm};
+use crate::wallet::{legacy_stretch_password, pbkdf2_password, write_atomically, LEGACY_KDF_ROUNDS, WALLET_KDF_ITERATIONS};
+
+pub const KEYSTORE_VERSION: u32 = 1;
+pub const DEFAULT_KEYSTORE_FILE: &str = "keystore.json";
+const KEYSTORE_KDF: &str = "pbkdf2-sha256";
+/// The salted SHA-256 rounds of keystores written before PBKDF2
+const LEGACY_KEYSTORE_KDF: &str = "sha256";
+const KEYSTORE_CIPHER: &str = "aes-256-gcm";
+
+/// Signing key `index` of a recovery phrase: same phrase and index, same key
+pub fn derive_validator_key(phrase: &str, index: u32) -> Result<ValidatorKey, String> {
+    let mnemonic = bip39::Mnemonic::parse(phrase.trim()).map_err(|e| format!("Invalid recovery phrase: {}", e))?;
+    let seed = mnemonic.to_seed("");
+    // A digest outside the curve order is vanishingly rare; the counter steps past it
+    Ok((0u32..).find_map(|counter| {
+        let secret = Sha256::new()
+            .chain_update(b"DIVINE_VALIDATOR_KEY_V1")
+            .chain_update(seed)
+            .chain_update(index.to_le_bytes())
+            .chain_update(counter.to_le_bytes())
+            .finalize();
+        ValidatorKey::from_secret_hex(&hex::encode(secret)).ok()
+    }).expect("a valid secp256k1 key"))
+}
+
+fn keystore_key(stretched: &[u8; 32]) -> [u8; 32] {
+    derive_key(stretched, b"DIVINE_KEYSTORE_V1")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keystore {
+    pub version: u32,
+    pub public_key: String,
+    /// Where the key's block rewards go (`reward_address_for`)
+    pub address: String,
+    /// Derivation index under the recovery phrase
+    pub index: u32,
+    pub created_at: i64,
+    pub kdf: String,
+    pub kdf_rounds: u32,
+    /// Hex
+    pub salt: String,
+    pub cipher: String,
+    /// Hex nonce and sealed secret key
+    pub ciphertext: String,
+}
+
+impl Keystore {
+    /// `key` encrypted with `password`
+    pub fn seal(key: &ValidatorKey, index: u32, password: &str) -> Result<Self, String> {
+        if password.is_empty() {
+            return Err("The keystore password cannot be empty".into());
+        }
+        let salt: [u8; 16] = rand::random();
+        let secret = hex::decode(key.secret_key_hex()).map_err(|e| e.to_string())?;
+        let sealed = encrypt_aes_gcm(&keystore_key(&pbkdf2_password(password, &salt, WALLET_KDF_ITERATIONS)?), &secret)?;
+        let public_key = key.public_key_hex();
+        Ok(Self {
+            version: KEYSTORE_VERSION,
+            address: reward_address_for(&public_key),
+            public_key,
+            index,
+            created_at: chrono::Utc::now().timestamp(),
+            kdf: KEYSTORE_KDF.into(),
+            kdf_rounds: WALLET_KDF_ITERATIONS,
+            salt: hex::encode(salt),
+            cipher: KEYSTORE_CIPHER.into(),
+            ciphertext: hex::encode(sealed),
+        })
+    }
+
+    /// The signing key, if `password` opens it and it matches `public_key`
+    pub fn unseal(&self, password: &str) -> Result<ValidatorKey, String> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(format!("Unsupported keystore version {}", self.version));
+        }
+        let legacy = self.kdf == LEGACY_KEYSTORE_KDF && self.kdf_rounds == LEGACY_KDF_ROUNDS;
+        if !(legacy || self.kdf == KEYSTORE_KDF) || self.cipher != KEYSTORE_CIPHER {
+            return Err(format!("Unsupported keystore encryption {} ({} rounds) / {}", self.kdf, self.kdf_rounds, self.cipher));
+        }
+        let salt = hex::decode(&self.salt).map_err(|_| "Invalid keystore salt")?;
+        let sealed = hex::decode(&self.ciphertext).map_err(|_| "Invalid keystore ciphertext")?;
+        let stretched = if legacy {
+            legacy_stretch_password(password, &salt)
+        } else {
+            pbkdf2_password(password, &salt, self.kdf_rounds)?
+        };
+        let secret = decrypt_aes_gcm(&keystore_key(&stretched), &sealed)
+            .map_err(|_| "Wrong password or corrupted keystore")?;
+        let key = ValidatorKey::from_secret_hex(&hex::encode(secret))?;
+        if key.public_key_hex() != self.public_key {
+            return Err("Keystore public key does not match its secret key".into());
+        }
+        Ok(key)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)?;
+        serde_json::from_str(&text).map_err(|e| anyhow::anyhow!("{}: not a keystore ({})", path.display(), e))
+    }
+
+    /// Write next to the target and rename, like wallet files
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        write_atomically(path.as_ref(), (serde_json::to_string_pretty(self)? + "\n").as_bytes())?;
+        Ok(())
+    }
+}
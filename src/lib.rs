@@ -16,21 +16,64 @@
 //! - Mission Control pathfinding
 //! - T/G RNA coordination
 //! - Divine Wallet with founder pool
+//! - P2P block gossip between nodes
 //! - Railway deployment ready
 
 pub mod rotation;
 pub mod genome;
 pub mod database;
+pub mod db_dump;
 pub mod ttrl;
 pub mod crypto;
 pub mod wallet;
+pub mod keystore;
+pub mod history;
 pub mod exchange;
 pub mod consensus;
+pub mod chain_file;
+pub mod genesis;
+pub mod upgrades;
+pub mod staking;
+pub mod slashing;
+pub mod snapshot;
+pub mod network;
+pub mod light;
 pub mod multi_chain;
+pub mod archive_policy;
+pub mod lightning;
+pub mod ethereum;
+pub mod solana;
+pub mod merkle_archive;
+pub mod storage;
+pub mod swarm;
+pub mod spv;
 pub mod rotation_daemon;
 pub mod api;
+pub mod graphql;
+#[cfg(feature = "grpc")]
+pub mod grpc;
 pub mod cli;
+pub mod dashboard;
+pub mod config;
+pub mod watch;
+pub mod bench;
+pub mod script;
 pub mod auth;
+pub mod rate_limit;
+pub mod response_cache;
+pub mod listing;
+pub mod tls;
+pub mod cors;
+pub mod versioning;
+pub mod genome_import;
+pub mod webhooks;
+pub mod order_book;
+pub mod marketplace;
+pub mod auction;
+pub mod oracle;
+pub mod swap;
+pub mod escrow;
+pub mod fees;
 
 pub mod prelude {
     pub use crate::rotation::*;
@@ -44,11 +87,13 @@ pub mod prelude {
 pub use rotation::{Rotation, Rot0, Rot90, Rot180, Rot270, RotationEngine, DynamicRotation};
 pub use genome::{Genome, Tetrad, GenomeBuilder};
 pub use database::{DivineDatabase, DEFAULT_DATABASE_URL};
+pub use config::DivineConfig;
 pub use ttrl::{TTRLEngine, MutationOperator, EvolutionResult};
 pub use exchange::{RSMExchange, Transaction, ExchangeStats, BurnEvent, DebtStats};
 pub use multi_chain::{MultiChainArchiver, BlockchainLayer, MissionControl};
+pub use consensus::{ProofOfConsciousness, ConsensusBlock, ConsciousnessProof};
 pub use rotation_daemon::RotationDaemon;
-pub use auth::{AuthManager, WalletAccount, SessionToken, LoginRequest, RegisterRequest, LoginResponse, WalletInfo};
+pub use auth::{AuthManager, ApiKeyRecord, JwtClaims, Role, WalletAccount, SessionToken, LoginRequest, RegisterRequest, LoginResponse, WalletInfo};
 
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -59,7 +104,7 @@ pub struct DivineKernel {
     pub wallet: Arc<RwLock<wallet::DivineWallet>>,
     pub rotation_engine: Arc<RwLock<rotation::RotationEngine>>,
     pub ttrl_engine: Arc<ttrl::TTRLEngine>,
-    pub consensus: Arc<consensus::ProofOfConsciousness>,
+    pub consensus: Arc<RwLock<consensus::ProofOfConsciousness>>,
     pub exchange: Arc<RwLock<exchange::RSMExchange>>,
     pub archiver: Arc<RwLock<MultiChainArchiver>>,
     pub auth: Arc<RwLock<auth::AuthManager>>,
@@ -67,11 +112,18 @@ pub struct DivineKernel {
 
 impl DivineKernel {
     pub async fn new() -> anyhow::Result<Self> {
-        let database_url = std::env::var("DATABASE_URL")
-            .unwrap_or_else(|_| DEFAULT_DATABASE_URL.to_string());
+        let config = DivineConfig::load()?;
 
-        let database = Arc::new(DivineDatabase::connect_with_url(&database_url).await?);
+        let database = DivineDatabase::connect_with_url(&config.database_url).await?;
         database.init_tables().await?;
+        Self::with_database(database, &config).await
+    }
+
+    /// Kernel over an already connected and migrated `database`
+    pub async fn with_database(database: DivineDatabase, config: &DivineConfig) -> anyhow::Result<Self> {
+        let database = Arc::new(database);
+        let mut consensus = consensus::ProofOfConsciousness::from_env()?;
+        consensus.load_chain(&database).await?;
 
         info!("🧬 Divine Kernel V15 initialized - Kernel v3");
         info!("🔥 Burn mechanism: ACTIVE");
@@ -84,16 +136,35 @@ impl DivineKernel {
 
         Ok(Self {
             database,
-            wallet: Arc::new(RwLock::new(wallet::DivineWallet::new())),
+            wallet: Arc::new(RwLock::new(Self::open_wallet(config)?)),
             rotation_engine: Arc::new(RwLock::new(rotation::RotationEngine::new())),
             ttrl_engine: Arc::new(ttrl::TTRLEngine::new()),
-            consensus: Arc::new(consensus::ProofOfConsciousness::new()),
+            consensus: Arc::new(RwLock::new(consensus)),
             exchange: Arc::new(RwLock::new(exchange::RSMExchange::new())),
             archiver: Arc::new(RwLock::new(MultiChainArchiver::new())),
             auth: Arc::new(RwLock::new(auth::AuthManager::new())),
         })
     }
 
+    /// Node wallet from the configured `wallet_file` (encrypted with
+    /// `WALLET_PASSWORD`), created on first start; in-memory only when either is unset
+    fn open_wallet(config: &DivineConfig) -> anyhow::Result<wallet::DivineWallet> {
+        let (Some(path), Ok(password)) = (&config.wallet_file, std::env::var("WALLET_PASSWORD")) else {
+            return Ok(wallet::DivineWallet::new());
+        };
+
+        if path.exists() {
+            let wallet = wallet::DivineWallet::load(path, &password)?;
+            info!("👛 Wallet {} loaded from {}", wallet.address, path.display());
+            return Ok(wallet);
+        }
+
+        let wallet = wallet::DivineWallet::new();
+        wallet.save(path, &password)?;
+        info!("👛 Wallet {} created at {}", wallet.address, path.display());
+        Ok(wallet)
+    }
+
     pub async fn genome_count(&self) -> anyhow::Result<i64> {
         self.database.genome_count().await
     }
@@ -0,0 +1,107 @@
+//! Light Client V1 — header-only view of the PoC chain
+//!
+//! `LightChain` keeps block headers only. Each header is checked as it
+//! arrives (linkage, hash, PoW and producer signature), which is enough to
+//! verify Merkle inclusion proofs for wallet-only deployments.
+//!
+//! Headers cannot reveal which validators were staking at a given height, so
+//! producers are checked against the configured `ValidatorSet`.
+
+use serde::{Serialize, Deserialize};
+use tracing::info;
+
+use crate::consensus::{BlockHeader, InclusionProof, ValidatorSet, MIN_DIFFICULTY};
+use crate::genesis::GenesisConfig;
+use crate::network::{self, NetworkMessage};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LightChain {
+    pub genesis: GenesisConfig,
+    pub validators: ValidatorSet,
+    headers: Vec<BlockHeader>,
+}
+
+impl LightChain {
+    pub fn new(genesis: GenesisConfig, validators: ValidatorSet) -> Self {
+        let headers = vec![genesis.genesis_block().header()];
+        Self { genesis, validators, headers }
+    }
+
+    pub fn height(&self) -> u64 {
+        self.tip().index
+    }
+
+    pub fn tip(&self) -> &BlockHeader {
+        self.headers.last().expect("light chain always contains genesis")
+    }
+
+    pub fn header(&self, index: u64) -> Option<&BlockHeader> {
+        self.headers.get(index as usize)
+    }
+
+    /// Verify a header against the current tip and append it
+    pub fn add_header(&mut self, header: BlockHeader) -> Result<(), String> {
+        let tip = self.tip();
+        if header.index != tip.index + 1 || header.previous_hash != tip.hash {
+            return Err(format!("Header #{} does not extend tip #{}", header.index, tip.index));
+        }
+        if header.timestamp < tip.timestamp {
+            return Err(format!("Header #{} is older than its parent", header.index));
+        }
+        self.genesis.upgrades.check_version(header.index, header.version)?;
+        if header.hash != header.calculate_hash() {
+            return Err(format!("Header #{} hash mismatch", header.index));
+        }
+        if header.difficulty < MIN_DIFFICULTY || !header.meets_difficulty() {
+            return Err(format!("Header #{} does not meet difficulty {}", header.index, header.difficulty));
+        }
+        if !self.validators.contains(&header.producer) || !header.verify_signature() {
+            return Err(format!("Header #{} is not signed by a known validator", header.index));
+        }
+
+        self.headers.push(header);
+        Ok(())
+    }
+
+    /// Append a batch of headers, stopping at the first invalid one
+    pub fn add_headers(&mut self, headers: Vec<BlockHeader>) -> Result<usize, String> {
+        let mut added = 0;
+        for header in headers {
+            if header.index <= self.height() {
+                continue;
+            }
+            self.add_header(header)?;
+            added += 1;
+        }
+        Ok(added)
+    }
+
+    /// Check that the proof's block is one of our verified headers
+    pub fn verify_inclusion_proof(&self, proof: &InclusionProof) -> bool {
+        self.header(proof.block_index).is_some_and(|header| {
+            header.hash == proof.block_hash
+                && header.merkle_root == proof.merkle_root
+                && proof.verify()
+        })
+    }
+
+    /// Download and verify headers from a full node until caught up
+    pub async fn sync_from(&mut self, peer: &str) -> anyhow::Result<usize> {
+        let mut total = 0;
+        loop {
+            let request = NetworkMessage::GetHeaders { from_index: self.height() + 1 };
+            let headers = match network::request(peer, &[request]).await?.pop() {
+                Some(NetworkMessage::Headers(headers)) => headers,
+                _ => return Err(anyhow::anyhow!("Peer {} did not answer with headers", peer)),
+            };
+            let added = self.add_headers(headers).map_err(|e| anyhow::anyhow!(e))?;
+            if added == 0 {
+                break;
+            }
+            total += added;
+        }
+
+        info!("💡 Light chain synced from {}: {} header(s) | height {}", peer, total, self.height());
+        Ok(total)
+    }
+}
@@ -0,0 +1,473 @@
+//! Lightning Archiving V1 — keysend payments through LND
+//!
+//! A Lightning archive is a keysend payment to each swarm node. The payment
+//! carries three TLV records for its final hop: the keysend preimage
+//! (`KEYSEND_PREIMAGE_RECORD`), the genome's DNA hash (`GENOME_HASH_RECORD`)
+//! and the archive record (`ARCHIVE_RECORD_RECORD`). Its payment hash, the
+//! SHA-256 of the preimage, is the archive's tx reference (`ln_` + hex).
+//!
+//! Built with the `lightning` feature and given `LND_GRPC_URL`,
+//! `LND_MACAROON_PATH` and `LND_TLS_CERT_PATH`, the archiver pays through
+//! LND's gRPC API: `Router/SendPaymentV2` sends a keysend and waits for it to
+//! settle, `Router/TrackPaymentV2` reports its state later, `LookupInvoice`
+//! reads the genome hash back out of a keysend this node received, and
+//! `GetInfo` plus `ListChannels` make up the node's channel health. Without
+//! either, keysends stay simulated: each succeeds with Mission Control's
+//! probability for its node.
+//!
+//! The generated code is checked in as `lightning/lnrpc.rs` and
+//! `lightning/routerrpc.rs` (from `proto/lightning.proto` and
+//! `proto/router.proto`, trimmed from lnd's) so builds need no `protoc`.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use serde::{Serialize, Deserialize};
+use sha2::{Sha256, Digest};
+
+/// TLV type of the keysend preimage (lnd's `record.KeySendType`)
+pub const KEYSEND_PREIMAGE_RECORD: u64 = 5_482_373_484;
+/// TLV type of the archived genome's DNA hash. Custom records start at
+/// 2^16 and odd types are ignored by nodes that do not know them.
+pub const GENOME_HASH_RECORD: u64 = 1_684_633_193; // "divi"
+/// TLV type of the archive record (`DIVINE_GENOME|v15|...`)
+pub const ARCHIVE_RECORD_RECORD: u64 = GENOME_HASH_RECORD + 2;
+
+pub const DEFAULT_KEYSEND_SAT: i64 = 1;
+pub const DEFAULT_FEE_LIMIT_SAT: i64 = 10;
+pub const DEFAULT_PAYMENT_TIMEOUT_SECS: i32 = 60;
+/// Keysends remembered for tracking before the oldest are dropped
+pub const MAX_TRACKED_PAYMENTS: usize = 10_000;
+
+/// Error for Lightning calls made without an LND connection
+pub const NO_LND: &str = "needs LND (build with the `lightning` feature and set LND_GRPC_URL)";
+
+#[cfg(feature = "lightning")]
+#[allow(clippy::all)]
+pub mod lnrpc {
+    include!("lightning/lnrpc.rs");
+}
+
+#[cfg(feature = "lightning")]
+#[allow(clippy::all)]
+pub mod routerrpc {
+    include!("lightning/routerrpc.rs");
+}
+
+/// Where and how the archiver reaches LND
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LndConfig {
+    /// `https://host:10009`
+    pub url: String,
+    /// `admin.macaroon` (or one baked with offchain write permission)
+    pub macaroon_path: PathBuf,
+    /// lnd's `tls.cert`
+    pub tls_cert_path: PathBuf,
+    /// Name checked against the certificate (lnd's names `localhost`)
+    pub tls_domain: String,
+    /// Paid to each swarm node per archive
+    pub amount_sat: i64,
+    /// Most routing fee one keysend may cost
+    pub fee_limit_sat: i64,
+    pub timeout_secs: i32,
+}
+
+impl LndConfig {
+    /// LND settings from the environment, `None` when `LND_GRPC_URL` is
+    /// unset and keysends stay simulated
+    pub fn from_env() -> Result<Option<Self>, String> {
+        let Some(url) = env_var("LND_GRPC_URL") else {
+            return Ok(None);
+        };
+        let path = |name: &str| env_var(name).map(PathBuf::from).ok_or_else(|| format!("LND_GRPC_URL needs {} too", name));
+        let config = Self {
+            url,
+            macaroon_path: path("LND_MACAROON_PATH")?,
+            tls_cert_path: path("LND_TLS_CERT_PATH")?,
+            tls_domain: env_var("LND_TLS_DOMAIN").unwrap_or_else(|| "localhost".into()),
+            amount_sat: env_number("LND_KEYSEND_SAT", DEFAULT_KEYSEND_SAT)?,
+            fee_limit_sat: env_number("LND_FEE_LIMIT_SAT", DEFAULT_FEE_LIMIT_SAT)?,
+            timeout_secs: env_number("LND_PAYMENT_TIMEOUT_SECS", DEFAULT_PAYMENT_TIMEOUT_SECS)?,
+        };
+        config.validate()?;
+        Ok(Some(config))
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.url.starts_with("https://") {
+            return Err(format!("LND_GRPC_URL must be https://, not {}", self.url));
+        }
+        if self.amount_sat < 1 {
+            return Err("Keysends pay at least 1 sat".into());
+        }
+        if self.fee_limit_sat < 0 {
+            return Err("LND_FEE_LIMIT_SAT cannot be negative".into());
+        }
+        if self.timeout_secs < 1 {
+            return Err("LND_PAYMENT_TIMEOUT_SECS must be at least 1".into());
+        }
+        Ok(())
+    }
+}
+
+fn env_var(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|v| !v.is_empty())
+}
+
+fn env_number<T: FromStr>(name: &str, default: T) -> Result<T, String> {
+    match env_var(name) {
+        Some(value) => value.parse().map_err(|_| format!("Invalid {}: {}", name, value)),
+        None => Ok(default),
+    }
+}
+
+/// One keysend's preimage, payment hash and TLV records
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeysendPayload {
+    pub preimage: [u8; 32],
+    pub payment_hash: [u8; 32],
+    pub records: HashMap<u64, Vec<u8>>,
+}
+
+impl KeysendPayload {
+    /// A keysend of a genome with a fresh random preimage
+    pub fn new(dna_hash: [u8; 32], record: &str) -> Self {
+        Self::with_preimage(rand::random(), dna_hash, record)
+    }
+
+    pub fn with_preimage(preimage: [u8; 32], dna_hash: [u8; 32], record: &str) -> Self {
+        let records = HashMap::from([
+            (KEYSEND_PREIMAGE_RECORD, preimage.to_vec()),
+            (GENOME_HASH_RECORD, dna_hash.to_vec()),
+            (ARCHIVE_RECORD_RECORD, record.as_bytes().to_vec()),
+        ]);
+        Self { preimage, payment_hash: Sha256::digest(preimage).into(), records }
+    }
+
+    /// The archive's tx reference: `ln_` + the payment hash
+    pub fn tx_reference(&self) -> String {
+        format!("ln_{}", hex::encode(self.payment_hash))
+    }
+}
+
+/// A genome hash and archive record read back from a keysend's TLV records
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveRecords {
+    pub genome_hash: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub record: Option<String>,
+}
+
+impl ArchiveRecords {
+    pub fn decode(records: &HashMap<u64, Vec<u8>>) -> Result<Self, String> {
+        let hash = records.get(&GENOME_HASH_RECORD).ok_or("No genome hash record")?;
+        if hash.len() != 32 {
+            return Err(format!("Genome hash record has {} bytes, not 32", hash.len()));
+        }
+        let record = match records.get(&ARCHIVE_RECORD_RECORD) {
+            Some(bytes) => Some(String::from_utf8(bytes.clone()).map_err(|_| "Archive record is not UTF-8")?),
+            None => None,
+        };
+        Ok(Self { genome_hash: hex::encode(hash), record })
+    }
+}
+
+/// A payment hash from hex, with or without the `ln_` of tx references
+pub fn parse_payment_hash(text: &str) -> Result<[u8; 32], String> {
+    let hex_hash = text.strip_prefix("ln_").unwrap_or(text);
+    hex::decode(hex_hash).ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| format!("Invalid payment hash {:?}", text))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentStatus {
+    InFlight,
+    Succeeded,
+    Failed,
+}
+
+/// State of a keysend as last reported
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaymentUpdate {
+    pub status: PaymentStatus,
+    pub fee_msat: i64,
+    pub failure_reason: Option<String>,
+}
+
+impl PaymentUpdate {
+    pub fn succeeded(fee_msat: i64) -> Self {
+        Self { status: PaymentStatus::Succeeded, fee_msat, failure_reason: None }
+    }
+
+    pub fn failed(reason: String) -> Self {
+        Self { status: PaymentStatus::Failed, fee_msat: 0, failure_reason: Some(reason) }
+    }
+}
+
+/// A keysend the archiver sent, tracked until it settles or fails
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeysendPayment {
+    /// Hex, without `ln_`
+    pub payment_hash: String,
+    pub dest_pubkey: String,
+    pub genome_id: i64,
+    pub dna_hash: String,
+    pub amount_sat: i64,
+    pub status: PaymentStatus,
+    pub fee_msat: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure_reason: Option<String>,
+    /// Not sent through LND
+    pub simulated: bool,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl KeysendPayment {
+    pub fn apply(&mut self, update: PaymentUpdate, now: i64) {
+        self.status = update.status;
+        self.fee_msat = update.fee_msat;
+        self.failure_reason = update.failure_reason;
+        self.updated_at = now;
+    }
+}
+
+/// A keysend this node received, looked up as an invoice
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReceivedKeysend {
+    pub payment_hash: String,
+    /// `open`, `settled`, `canceled` or `accepted`
+    pub state: String,
+    pub amount_paid_msat: i64,
+    pub is_keysend: bool,
+    /// What the payment archived, if it carried the genome records
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive: Option<ArchiveRecords>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settled_at: Option<i64>,
+}
+
+/// The LND node as `GetInfo` reports it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeStatus {
+    pub pubkey: String,
+    pub alias: String,
+    pub version: String,
+    pub block_height: u32,
+    pub synced_to_chain: bool,
+    pub synced_to_graph: bool,
+    pub peers: u32,
+    pub pending_channels: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelStatus {
+    pub chan_id: u64,
+    pub remote_pubkey: String,
+    pub active: bool,
+    pub capacity_sat: i64,
+    pub local_balance_sat: i64,
+    pub remote_balance_sat: i64,
+}
+
+/// Whether the node can archive: synced, with active channels whose
+/// outbound balance covers a broadcast to the swarm
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelHealth {
+    pub node: NodeStatus,
+    pub channels: Vec<ChannelStatus>,
+    pub active_channels: usize,
+    pub inactive_channels: usize,
+    /// Spendable over active channels
+    pub outbound_sat: i64,
+    /// Most one archive broadcast can cost, fees included
+    pub broadcast_sat: i64,
+    pub problems: Vec<String>,
+    pub healthy: bool,
+}
+
+impl ChannelHealth {
+    pub fn assess(node: NodeStatus, channels: Vec<ChannelStatus>, broadcast_sat: i64) -> Self {
+        let active: Vec<&ChannelStatus> = channels.iter().filter(|c| c.active).collect();
+        let outbound_sat = active.iter().map(|c| c.local_balance_sat).sum();
+        let mut problems = Vec::new();
+        if !node.synced_to_chain {
+            problems.push("Not synced to the chain".to_string());
+        }
+        if !node.synced_to_graph {
+            problems.push("Not synced to the channel graph; keysends may find no route".to_string());
+        }
+        if active.is_empty() {
+            problems.push("No active channels".to_string());
+        } else if outbound_sat < broadcast_sat {
+            problems.push(format!("{} sat outbound, a broadcast needs up to {}", outbound_sat, broadcast_sat));
+        }
+        Self {
+            node,
+            active_channels: active.len(),
+            inactive_channels: channels.len() - active.len(),
+            channels,
+            outbound_sat,
+            broadcast_sat,
+            healthy: problems.is_empty(),
+            problems,
+        }
+    }
+}
+
+#[cfg(feature = "lightning")]
+pub use lnd::LndClient;
+
+#[cfg(feature = "lightning")]
+mod lnd {
+    use std::path::Path;
+
+    use tonic::metadata::{Ascii, MetadataValue};
+    use tonic::service::interceptor::InterceptedService;
+    use tonic::transport::{Certificate, Channel, ClientTlsConfig, Endpoint};
+
+    use super::lnrpc::{self, lightning_client::LightningClient, payment, FeatureBit};
+    use super::routerrpc::{router_client::RouterClient, SendPaymentRequest, TrackPaymentRequest};
+    use super::*;
+
+    /// Adds the macaroon every LND call is authorized by
+    #[derive(Clone)]
+    pub struct MacaroonInterceptor {
+        macaroon: MetadataValue<Ascii>,
+    }
+
+    impl tonic::service::Interceptor for MacaroonInterceptor {
+        fn call(&mut self, mut request: tonic::Request<()>) -> Result<tonic::Request<()>, tonic::Status> {
+            request.metadata_mut().insert("macaroon", self.macaroon.clone());
+            Ok(request)
+        }
+    }
+
+    type Authorized = InterceptedService<Channel, MacaroonInterceptor>;
+
+    /// LND gRPC client; cheap to clone, all clones share one connection
+    #[derive(Clone)]
+    pub struct LndClient {
+        pub config: LndConfig,
+        lightning: LightningClient<Authorized>,
+        router: RouterClient<Authorized>,
+    }
+
+    impl LndClient {
+        /// Client for `config`; it connects on the first call
+        pub fn connect(config: LndConfig) -> Result<Self, String> {
+            let read = |path: &Path| std::fs::read(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e));
+            let macaroon = hex::encode(read(&config.macaroon_path)?).parse()
+                .map_err(|_| format!("Bad macaroon in {}", config.macaroon_path.display()))?;
+            let tls = ClientTlsConfig::new()
+                .ca_certificate(Certificate::from_pem(read(&config.tls_cert_path)?))
+                .domain_name(config.tls_domain.clone());
+            let channel = Endpoint::from_shared(config.url.clone())
+                .map_err(|e| format!("Invalid LND_GRPC_URL {}: {}", config.url, e))?
+                .tls_config(tls)
+                .map_err(|e| format!("LND TLS: {}", e))?
+                .connect_lazy();
+            let interceptor = MacaroonInterceptor { macaroon };
+            Ok(Self {
+                lightning: LightningClient::with_interceptor(channel.clone(), interceptor.clone()),
+                router: RouterClient::with_interceptor(channel, interceptor),
+                config,
+            })
+        }
+
+        /// Keysend `payload` to `dest_pubkey` and wait until it settles or fails
+        pub async fn keysend(&mut self, dest_pubkey: &str, payload: &KeysendPayload) -> Result<PaymentUpdate, String> {
+            let dest = hex::decode(dest_pubkey).ok()
+                .filter(|dest| dest.len() == 33)
+                .ok_or_else(|| format!("Invalid node pubkey {}", dest_pubkey))?;
+            let request = SendPaymentRequest {
+                dest,
+                amt: self.config.amount_sat,
+                payment_hash: payload.payment_hash.to_vec(),
+                final_cltv_delta: 0, // lnd's default
+                timeout_seconds: self.config.timeout_secs,
+                fee_limit_sat: self.config.fee_limit_sat,
+                dest_custom_records: payload.records.clone(),
+                dest_features: vec![FeatureBit::TlvOnionOpt as i32],
+                no_inflight_updates: true,
+            };
+            let mut stream = self.router.send_payment_v2(request).await.map_err(status_error)?.into_inner();
+            while let Some(payment) = stream.message().await.map_err(status_error)? {
+                let update = payment_update(&payment);
+                if update.status != PaymentStatus::InFlight {
+                    return Ok(update);
+                }
+            }
+            Err("LND closed the payment stream before the keysend finished".into())
+        }
+
+        /// Current state of a payment this node sent
+        pub async fn track_payment(&mut self, payment_hash: &[u8; 32]) -> Result<PaymentUpdate, String> {
+            let request = TrackPaymentRequest { payment_hash: payment_hash.to_vec(), no_inflight_updates: false };
+            let mut stream = self.router.track_payment_v2(request).await.map_err(status_error)?.into_inner();
+            match stream.message().await.map_err(status_error)? {
+                Some(payment) => Ok(payment_update(&payment)),
+                None => Err("LND closed the payment stream".into()),
+            }
+        }
+
+        /// A keysend this node received, with the genome it archived
+        pub async fn lookup_invoice(&mut self, payment_hash: &[u8; 32]) -> Result<ReceivedKeysend, String> {
+            let request = lnrpc::PaymentHash { r_hash: payment_hash.to_vec() };
+            let invoice = self.lightning.lookup_invoice(request).await.map_err(status_error)?.into_inner();
+            let archive = invoice.htlcs.iter().find_map(|htlc| ArchiveRecords::decode(&htlc.custom_records).ok());
+            Ok(ReceivedKeysend {
+                payment_hash: hex::encode(payment_hash),
+                state: invoice.state().as_str_name().to_ascii_lowercase(),
+                amount_paid_msat: invoice.amt_paid_msat,
+                is_keysend: invoice.is_keysend,
+                archive,
+                settled_at: Some(invoice.settle_date).filter(|&date| date > 0),
+            })
+        }
+
+        /// The node's sync state and channels, judged against `broadcast_sat`
+        pub async fn channel_health(&mut self, broadcast_sat: i64) -> Result<ChannelHealth, String> {
+            let info = self.lightning.get_info(lnrpc::GetInfoRequest {}).await.map_err(status_error)?.into_inner();
+            let channels = self.lightning.list_channels(lnrpc::ListChannelsRequest::default()).await
+                .map_err(status_error)?.into_inner().channels;
+            let node = NodeStatus {
+                pubkey: info.identity_pubkey,
+                alias: info.alias,
+                version: info.version,
+                block_height: info.block_height,
+                synced_to_chain: info.synced_to_chain,
+                synced_to_graph: info.synced_to_graph,
+                peers: info.num_peers,
+                pending_channels: info.num_pending_channels,
+            };
+            let channels = channels.into_iter().map(|c| ChannelStatus {
+                chan_id: c.chan_id,
+                remote_pubkey: c.remote_pubkey,
+                active: c.active,
+                capacity_sat: c.capacity,
+                local_balance_sat: c.local_balance,
+                remote_balance_sat: c.remote_balance,
+            }).collect();
+            Ok(ChannelHealth::assess(node, channels, broadcast_sat))
+        }
+    }
+
+    fn payment_update(payment: &lnrpc::Payment) -> PaymentUpdate {
+        match payment.status() {
+            payment::PaymentStatus::Succeeded => PaymentUpdate::succeeded(payment.fee_msat),
+            payment::PaymentStatus::Failed => {
+                let reason = payment.failure_reason().as_str_name().trim_start_matches("FAILURE_REASON_");
+                PaymentUpdate::failed(reason.to_ascii_lowercase().replace('_', " "))
+            }
+            _ => PaymentUpdate { status: PaymentStatus::InFlight, fee_msat: payment.fee_msat, failure_reason: None },
+        }
+    }
+
+    fn status_error(status: tonic::Status) -> String {
+        format!("LND: {}", status.message())
+    }
+}
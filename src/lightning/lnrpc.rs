@@ -0,0 +1,437 @@
+// This file is @generated by prost-build.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct GetInfoRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetInfoResponse {
+    #[prost(string, tag = "1")]
+    pub identity_pubkey: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub alias: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "3")]
+    pub num_pending_channels: u32,
+    #[prost(uint32, tag = "4")]
+    pub num_active_channels: u32,
+    #[prost(uint32, tag = "5")]
+    pub num_peers: u32,
+    #[prost(uint32, tag = "6")]
+    pub block_height: u32,
+    #[prost(bool, tag = "9")]
+    pub synced_to_chain: bool,
+    #[prost(string, tag = "14")]
+    pub version: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "15")]
+    pub num_inactive_channels: u32,
+    #[prost(bool, tag = "18")]
+    pub synced_to_graph: bool,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct ListChannelsRequest {
+    #[prost(bool, tag = "1")]
+    pub active_only: bool,
+    #[prost(bool, tag = "2")]
+    pub inactive_only: bool,
+    #[prost(bool, tag = "3")]
+    pub public_only: bool,
+    #[prost(bool, tag = "4")]
+    pub private_only: bool,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListChannelsResponse {
+    #[prost(message, repeated, tag = "11")]
+    pub channels: ::prost::alloc::vec::Vec<Channel>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Channel {
+    #[prost(bool, tag = "1")]
+    pub active: bool,
+    #[prost(string, tag = "2")]
+    pub remote_pubkey: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub channel_point: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "4")]
+    pub chan_id: u64,
+    #[prost(int64, tag = "5")]
+    pub capacity: i64,
+    #[prost(int64, tag = "6")]
+    pub local_balance: i64,
+    #[prost(int64, tag = "7")]
+    pub remote_balance: i64,
+    #[prost(bool, tag = "17")]
+    pub private: bool,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PaymentHash {
+    #[prost(bytes = "vec", tag = "2")]
+    pub r_hash: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Invoice {
+    #[prost(string, tag = "1")]
+    pub memo: ::prost::alloc::string::String,
+    #[prost(bytes = "vec", tag = "4")]
+    pub r_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(int64, tag = "5")]
+    pub value: i64,
+    #[prost(int64, tag = "7")]
+    pub creation_date: i64,
+    #[prost(int64, tag = "8")]
+    pub settle_date: i64,
+    #[prost(int64, tag = "20")]
+    pub amt_paid_msat: i64,
+    #[prost(enumeration = "invoice::InvoiceState", tag = "21")]
+    pub state: i32,
+    #[prost(message, repeated, tag = "22")]
+    pub htlcs: ::prost::alloc::vec::Vec<InvoiceHtlc>,
+    #[prost(bool, tag = "25")]
+    pub is_keysend: bool,
+}
+/// Nested message and enum types in `Invoice`.
+pub mod invoice {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum InvoiceState {
+        Open = 0,
+        Settled = 1,
+        Canceled = 2,
+        Accepted = 3,
+    }
+    impl InvoiceState {
+        /// String value of the enum field names used in the ProtoBuf definition.
+        ///
+        /// The values are not transformed in any way and thus are considered stable
+        /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+        pub fn as_str_name(&self) -> &'static str {
+            match self {
+                Self::Open => "OPEN",
+                Self::Settled => "SETTLED",
+                Self::Canceled => "CANCELED",
+                Self::Accepted => "ACCEPTED",
+            }
+        }
+        /// Creates an enum from field names used in the ProtoBuf definition.
+        pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+            match value {
+                "OPEN" => Some(Self::Open),
+                "SETTLED" => Some(Self::Settled),
+                "CANCELED" => Some(Self::Canceled),
+                "ACCEPTED" => Some(Self::Accepted),
+                _ => None,
+            }
+        }
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct InvoiceHtlc {
+    #[prost(uint64, tag = "1")]
+    pub chan_id: u64,
+    #[prost(uint64, tag = "3")]
+    pub amt_msat: u64,
+    /// TLV records of the payment's final hop, keysend preimage included
+    #[prost(map = "uint64, bytes", tag = "9")]
+    pub custom_records: ::std::collections::HashMap<u64, ::prost::alloc::vec::Vec<u8>>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Payment {
+    #[prost(string, tag = "1")]
+    pub payment_hash: ::prost::alloc::string::String,
+    #[prost(string, tag = "6")]
+    pub payment_preimage: ::prost::alloc::string::String,
+    #[prost(int64, tag = "7")]
+    pub value_sat: i64,
+    #[prost(int64, tag = "8")]
+    pub value_msat: i64,
+    #[prost(enumeration = "payment::PaymentStatus", tag = "10")]
+    pub status: i32,
+    #[prost(int64, tag = "11")]
+    pub fee_sat: i64,
+    #[prost(int64, tag = "12")]
+    pub fee_msat: i64,
+    #[prost(int64, tag = "13")]
+    pub creation_time_ns: i64,
+    #[prost(uint64, tag = "15")]
+    pub payment_index: u64,
+    #[prost(enumeration = "PaymentFailureReason", tag = "16")]
+    pub failure_reason: i32,
+}
+/// Nested message and enum types in `Payment`.
+pub mod payment {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum PaymentStatus {
+        Unknown = 0,
+        InFlight = 1,
+        Succeeded = 2,
+        Failed = 3,
+        Initiated = 4,
+    }
+    impl PaymentStatus {
+        /// String value of the enum field names used in the ProtoBuf definition.
+        ///
+        /// The values are not transformed in any way and thus are considered stable
+        /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+        pub fn as_str_name(&self) -> &'static str {
+            match self {
+                Self::Unknown => "UNKNOWN",
+                Self::InFlight => "IN_FLIGHT",
+                Self::Succeeded => "SUCCEEDED",
+                Self::Failed => "FAILED",
+                Self::Initiated => "INITIATED",
+            }
+        }
+        /// Creates an enum from field names used in the ProtoBuf definition.
+        pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+            match value {
+                "UNKNOWN" => Some(Self::Unknown),
+                "IN_FLIGHT" => Some(Self::InFlight),
+                "SUCCEEDED" => Some(Self::Succeeded),
+                "FAILED" => Some(Self::Failed),
+                "INITIATED" => Some(Self::Initiated),
+                _ => None,
+            }
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum PaymentFailureReason {
+    FailureReasonNone = 0,
+    FailureReasonTimeout = 1,
+    FailureReasonNoRoute = 2,
+    FailureReasonError = 3,
+    FailureReasonIncorrectPaymentDetails = 4,
+    FailureReasonInsufficientBalance = 5,
+    FailureReasonCanceled = 6,
+}
+impl PaymentFailureReason {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::FailureReasonNone => "FAILURE_REASON_NONE",
+            Self::FailureReasonTimeout => "FAILURE_REASON_TIMEOUT",
+            Self::FailureReasonNoRoute => "FAILURE_REASON_NO_ROUTE",
+            Self::FailureReasonError => "FAILURE_REASON_ERROR",
+            Self::FailureReasonIncorrectPaymentDetails => "FAILURE_REASON_INCORRECT_PAYMENT_DETAILS",
+            Self::FailureReasonInsufficientBalance => "FAILURE_REASON_INSUFFICIENT_BALANCE",
+            Self::FailureReasonCanceled => "FAILURE_REASON_CANCELED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "FAILURE_REASON_NONE" => Some(Self::FailureReasonNone),
+            "FAILURE_REASON_TIMEOUT" => Some(Self::FailureReasonTimeout),
+            "FAILURE_REASON_NO_ROUTE" => Some(Self::FailureReasonNoRoute),
+            "FAILURE_REASON_ERROR" => Some(Self::FailureReasonError),
+            "FAILURE_REASON_INCORRECT_PAYMENT_DETAILS" => Some(Self::FailureReasonIncorrectPaymentDetails),
+            "FAILURE_REASON_INSUFFICIENT_BALANCE" => Some(Self::FailureReasonInsufficientBalance),
+            "FAILURE_REASON_CANCELED" => Some(Self::FailureReasonCanceled),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum FeatureBit {
+    DatalossProtectReq = 0,
+    DatalossProtectOpt = 1,
+    InitialRouingSync = 3,
+    UpfrontShutdownScriptReq = 4,
+    UpfrontShutdownScriptOpt = 5,
+    GossipQueriesReq = 6,
+    GossipQueriesOpt = 7,
+    TlvOnionReq = 8,
+    TlvOnionOpt = 9,
+}
+impl FeatureBit {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::DatalossProtectReq => "DATALOSS_PROTECT_REQ",
+            Self::DatalossProtectOpt => "DATALOSS_PROTECT_OPT",
+            Self::InitialRouingSync => "INITIAL_ROUING_SYNC",
+            Self::UpfrontShutdownScriptReq => "UPFRONT_SHUTDOWN_SCRIPT_REQ",
+            Self::UpfrontShutdownScriptOpt => "UPFRONT_SHUTDOWN_SCRIPT_OPT",
+            Self::GossipQueriesReq => "GOSSIP_QUERIES_REQ",
+            Self::GossipQueriesOpt => "GOSSIP_QUERIES_OPT",
+            Self::TlvOnionReq => "TLV_ONION_REQ",
+            Self::TlvOnionOpt => "TLV_ONION_OPT",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "DATALOSS_PROTECT_REQ" => Some(Self::DatalossProtectReq),
+            "DATALOSS_PROTECT_OPT" => Some(Self::DatalossProtectOpt),
+            "INITIAL_ROUING_SYNC" => Some(Self::InitialRouingSync),
+            "UPFRONT_SHUTDOWN_SCRIPT_REQ" => Some(Self::UpfrontShutdownScriptReq),
+            "UPFRONT_SHUTDOWN_SCRIPT_OPT" => Some(Self::UpfrontShutdownScriptOpt),
+            "GOSSIP_QUERIES_REQ" => Some(Self::GossipQueriesReq),
+            "GOSSIP_QUERIES_OPT" => Some(Self::GossipQueriesOpt),
+            "TLV_ONION_REQ" => Some(Self::TlvOnionReq),
+            "TLV_ONION_OPT" => Some(Self::TlvOnionOpt),
+            _ => None,
+        }
+    }
+}
+/// Generated client implementations.
+pub mod lightning_client {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    use tonic::codegen::http::Uri;
+    #[derive(Debug, Clone)]
+    pub struct LightningClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl LightningClient<tonic::transport::Channel> {
+        /// Attempt to create a new client by connecting to a given endpoint.
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> LightningClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::BoxBody>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + std::marker::Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + std::marker::Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> LightningClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+            >>::Error: Into<StdError> + std::marker::Send + std::marker::Sync,
+        {
+            LightningClient::new(InterceptedService::new(inner, interceptor))
+        }
+        /// Compress requests with the given encoding.
+        ///
+        /// This requires the server to support it otherwise it might respond with an
+        /// error.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.send_compressed(encoding);
+            self
+        }
+        /// Enable decompressing responses.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.accept_compressed(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_decoding_message_size(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_encoding_message_size(limit);
+            self
+        }
+        pub async fn get_info(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetInfoRequest>,
+        ) -> std::result::Result<tonic::Response<super::GetInfoResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/lnrpc.Lightning/GetInfo",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("lnrpc.Lightning", "GetInfo"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn list_channels(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListChannelsRequest>,
+        ) -> std::result::Result<tonic::Response<super::ListChannelsResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/lnrpc.Lightning/ListChannels",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("lnrpc.Lightning", "ListChannels"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn lookup_invoice(
+            &mut self,
+            request: impl tonic::IntoRequest<super::PaymentHash>,
+        ) -> std::result::Result<tonic::Response<super::Invoice>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/lnrpc.Lightning/LookupInvoice",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("lnrpc.Lightning", "LookupInvoice"));
+            self.inner.unary(req, path, codec).await
+        }
+    }
+}
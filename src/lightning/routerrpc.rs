@@ -0,0 +1,173 @@
+// This file is @generated by prost-build.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SendPaymentRequest {
+    #[prost(bytes = "vec", tag = "1")]
+    pub dest: ::prost::alloc::vec::Vec<u8>,
+    #[prost(int64, tag = "2")]
+    pub amt: i64,
+    #[prost(bytes = "vec", tag = "3")]
+    pub payment_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(int32, tag = "4")]
+    pub final_cltv_delta: i32,
+    #[prost(int32, tag = "6")]
+    pub timeout_seconds: i32,
+    #[prost(int64, tag = "7")]
+    pub fee_limit_sat: i64,
+    /// TLV records for the final hop: keysend preimage, genome hash, record
+    #[prost(map = "uint64, bytes", tag = "11")]
+    pub dest_custom_records: ::std::collections::HashMap<u64, ::prost::alloc::vec::Vec<u8>>,
+    #[prost(enumeration = "super::lnrpc::FeatureBit", repeated, tag = "16")]
+    pub dest_features: ::prost::alloc::vec::Vec<i32>,
+    #[prost(bool, tag = "18")]
+    pub no_inflight_updates: bool,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TrackPaymentRequest {
+    #[prost(bytes = "vec", tag = "1")]
+    pub payment_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bool, tag = "2")]
+    pub no_inflight_updates: bool,
+}
+/// Generated client implementations.
+pub mod router_client {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    use tonic::codegen::http::Uri;
+    #[derive(Debug, Clone)]
+    pub struct RouterClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl RouterClient<tonic::transport::Channel> {
+        /// Attempt to create a new client by connecting to a given endpoint.
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> RouterClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::BoxBody>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + std::marker::Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + std::marker::Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> RouterClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+            >>::Error: Into<StdError> + std::marker::Send + std::marker::Sync,
+        {
+            RouterClient::new(InterceptedService::new(inner, interceptor))
+        }
+        /// Compress requests with the given encoding.
+        ///
+        /// This requires the server to support it otherwise it might respond with an
+        /// error.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.send_compressed(encoding);
+            self
+        }
+        /// Enable decompressing responses.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.accept_compressed(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_decoding_message_size(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_encoding_message_size(limit);
+            self
+        }
+        /// Send a payment and stream its state until it succeeds or fails
+        pub async fn send_payment_v2(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SendPaymentRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::super::lnrpc::Payment>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/routerrpc.Router/SendPaymentV2",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("routerrpc.Router", "SendPaymentV2"));
+            self.inner.server_streaming(req, path, codec).await
+        }
+        /// Stream the state of a payment sent earlier, the current one first
+        pub async fn track_payment_v2(
+            &mut self,
+            request: impl tonic::IntoRequest<super::TrackPaymentRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::super::lnrpc::Payment>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/routerrpc.Router/TrackPaymentV2",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("routerrpc.Router", "TrackPaymentV2"));
+            self.inner.server_streaming(req, path, codec).await
+        }
+    }
+}
@@ -0,0 +1,325 @@
+//! List Queries V1 — filtering, sorting and cursor pagination
+//!
+//! List endpoints (`/api/genomes`, `/api/transactions`, `/api/burns`,
+//! `/api/archives`, `/api/rsm/trades/history`) take the same query
+//! parameters:
+//!
+//! ```text
+//! ?filter[consciousness_gte]=700&filter[p53_copies_eq]=40&sort=-created_at&limit=50&cursor=…
+//! ```
+//!
+//! - `filter[<field>_<op>]=<number>` with `op` one of `eq`, `gt`, `gte`, `lt`, `lte`
+//! - `sort=<field>` ascending or `sort=-<field>` descending (default `-id`, newest first)
+//! - `limit` from 1 to `MAX_PAGE_LIMIT` (default `DEFAULT_PAGE_LIMIT`)
+//! - `cursor`: the `next_cursor` of the previous page
+//!
+//! and answer with a `Page`: `data`, `next_cursor` (absent on the last page)
+//! and `total_estimate`, the number of matches when the page was read.
+//!
+//! Pages are keyset-paginated on (sort field, id): the cursor holds both
+//! values of the last row, so rows added while a client pages through never
+//! shift or repeat entries.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use serde::{Serialize, Deserialize};
+
+use crate::exchange::{BurnEvent, Transaction};
+use crate::genome::Genome;
+use crate::multi_chain::ChainArchiveEntry;
+use crate::order_book::Trade;
+use crate::rotation::Rot180;
+
+pub use crate::database::{DEFAULT_GENOME_PAGE as DEFAULT_PAGE_LIMIT, MAX_GENOME_PAGE as MAX_PAGE_LIMIT};
+
+/// Sortable and filterable fields of each list (plus `id`)
+pub const GENOME_FIELDS: &[&str] = &["consciousness", "mutations", "p53_copies", "telomere_length", "division_count", "created_at"];
+pub const TRANSACTION_FIELDS: &[&str] = &["amount_rsm", "amount_usd", "consciousness_level", "timestamp"];
+pub const BURN_FIELDS: &[&str] = &["amount_rsm", "genome_id", "consciousness_before", "consciousness_after", "timestamp"];
+pub const ARCHIVE_FIELDS: &[&str] = &["genome_id", "consciousness", "tg_ratio", "timestamp"];
+pub const TRADE_FIELDS: &[&str] = &["price_usd", "amount_rsm", "timestamp"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterOp {
+    Eq,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+}
+
+impl FilterOp {
+    const SUFFIXES: [(&'static str, FilterOp); 5] = [
+        ("_gte", FilterOp::Gte),
+        ("_lte", FilterOp::Lte),
+        ("_gt", FilterOp::Gt),
+        ("_lt", FilterOp::Lt),
+        ("_eq", FilterOp::Eq),
+    ];
+
+    pub fn sql(&self) -> &'static str {
+        match self {
+            FilterOp::Eq => "=",
+            FilterOp::Gt => ">",
+            FilterOp::Gte => ">=",
+            FilterOp::Lt => "<",
+            FilterOp::Lte => "<=",
+        }
+    }
+
+    pub fn matches(&self, value: f64, bound: f64) -> bool {
+        match self {
+            FilterOp::Eq => value == bound,
+            FilterOp::Gt => value > bound,
+            FilterOp::Gte => value >= bound,
+            FilterOp::Lt => value < bound,
+            FilterOp::Lte => value <= bound,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldFilter {
+    pub field: String,
+    pub op: FilterOp,
+    pub value: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortKey {
+    pub field: String,
+    pub descending: bool,
+}
+
+/// Position after the last row of a page
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cursor {
+    /// Sort field value of the last row
+    pub value: f64,
+    pub id: i64,
+}
+
+impl Cursor {
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).expect("cursor serializes"))
+    }
+
+    pub fn decode(cursor: &str) -> Result<Self, String> {
+        URL_SAFE_NO_PAD.decode(cursor).ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .ok_or_else(|| format!("Invalid cursor {}", cursor))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListQuery {
+    pub filters: Vec<FieldFilter>,
+    pub sort: SortKey,
+    pub cursor: Option<Cursor>,
+    pub limit: i64,
+}
+
+impl Default for ListQuery {
+    fn default() -> Self {
+        Self {
+            filters: Vec::new(),
+            sort: SortKey { field: "id".into(), descending: true },
+            cursor: None,
+            limit: DEFAULT_PAGE_LIMIT,
+        }
+    }
+}
+
+impl ListQuery {
+    /// Parse the query string of a list whose fields are `fields` (`id` is
+    /// always allowed). Unknown fields, operators and parameters are errors.
+    pub fn parse(params: &HashMap<String, String>, fields: &[&str]) -> Result<Self, String> {
+        let known = |field: &str| field == "id" || fields.contains(&field);
+        let mut query = Self::default();
+
+        for (key, value) in params {
+            if let Some(spec) = key.strip_prefix("filter[").and_then(|k| k.strip_suffix(']')) {
+                let (field, op) = FilterOp::SUFFIXES.iter()
+                    .find_map(|(suffix, op)| spec.strip_suffix(suffix).map(|field| (field, *op)))
+                    .ok_or_else(|| format!("Filter {} needs an _eq, _gt, _gte, _lt or _lte suffix", spec))?;
+                if !known(field) {
+                    return Err(format!("Cannot filter on {}", field));
+                }
+                let value = value.parse::<f64>().ok().filter(|v| v.is_finite())
+                    .ok_or_else(|| format!("Filter {} needs a number, got {}", spec, value))?;
+                query.filters.push(FieldFilter { field: field.to_string(), op, value });
+                continue;
+            }
+            match key.as_str() {
+                "sort" => {
+                    let (field, descending) = match value.strip_prefix('-') {
+                        Some(field) => (field, true),
+                        None => (value.as_str(), false),
+                    };
+                    if !known(field) {
+                        return Err(format!("Cannot sort on {}", field));
+                    }
+                    query.sort = SortKey { field: field.to_string(), descending };
+                }
+                "cursor" => query.cursor = Some(Cursor::decode(value)?),
+                "limit" => {
+                    query.limit = value.parse::<i64>().ok().filter(|l| (1..=MAX_PAGE_LIMIT).contains(l))
+                        .ok_or_else(|| format!("limit must be 1..={}", MAX_PAGE_LIMIT))?;
+                }
+                _ => return Err(format!("Unknown list parameter {}", key)),
+            }
+        }
+        // Stable order whatever the map's iteration order was
+        query.filters.sort_by(|a, b| a.field.cmp(&b.field).then_with(|| a.value.total_cmp(&b.value)));
+        Ok(query)
+    }
+}
+
+/// Genome list parameters from before list queries, and their filters
+const LEGACY_GENOME_PARAMS: [(&str, &str); 3] = [
+    ("min_consciousness", "filter[consciousness_gte]"),
+    ("max_consciousness", "filter[consciousness_lte]"),
+    ("min_p53", "filter[p53_copies_gte]"),
+];
+
+/// Parse a `/api/genomes` query, still accepting `min_consciousness`,
+/// `max_consciousness` and `min_p53`
+pub fn parse_genome_query(params: &HashMap<String, String>) -> Result<ListQuery, String> {
+    if params.contains_key("offset") {
+        return Err("offset is not supported, page with cursor=<next_cursor>".into());
+    }
+    let params: HashMap<String, String> = params.iter()
+        .map(|(key, value)| {
+            let key = LEGACY_GENOME_PARAMS.iter().find(|(old, _)| old == key).map_or(key.as_str(), |(_, new)| new);
+            (key.to_string(), value.clone())
+        })
+        .collect();
+    ListQuery::parse(&params, GENOME_FIELDS)
+}
+
+/// One page of a list
+#[derive(Debug, Clone, Serialize)]
+pub struct Page<T> {
+    pub data: Vec<T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    pub total_estimate: i64,
+}
+
+impl<T: Listable> Page<T> {
+    /// Build a page from up to `limit + 1` matching rows in sort order; the
+    /// extra row only tells that there is a next page
+    pub fn new(mut rows: Vec<(i64, T)>, query: &ListQuery, total_estimate: i64) -> Self {
+        let more = rows.len() as i64 > query.limit;
+        rows.truncate(query.limit as usize);
+        let next_cursor = rows.last()
+            .filter(|_| more)
+            .map(|(id, row)| Cursor { value: sort_value(*id, row, &query.sort.field), id: *id }.encode());
+        Self { data: rows.into_iter().map(|(_, row)| row).collect(), next_cursor, total_estimate }
+    }
+}
+
+impl<T> Page<T> {
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page { data: self.data.into_iter().map(f).collect(), next_cursor: self.next_cursor, total_estimate: self.total_estimate }
+    }
+}
+
+/// Records that can be filtered and sorted by field name
+pub trait Listable {
+    fn field(&self, name: &str) -> Option<f64>;
+}
+
+impl<T: Listable> Listable for &T {
+    fn field(&self, name: &str) -> Option<f64> {
+        (*self).field(name)
+    }
+}
+
+fn sort_value<T: Listable>(id: i64, row: &T, field: &str) -> f64 {
+    if field == "id" { id as f64 } else { row.field(field).unwrap_or(0.0) }
+}
+
+/// Filter, sort and page records held in memory, keyed by their id
+pub fn paginate<T: Listable>(rows: Vec<(i64, T)>, query: &ListQuery) -> Page<T> {
+    let field = query.sort.field.as_str();
+    let mut matching: Vec<(i64, T)> = rows.into_iter()
+        .filter(|(id, row)| query.filters.iter().all(|f| f.op.matches(sort_value(*id, row, &f.field), f.value)))
+        .collect();
+    let total = matching.len() as i64;
+
+    let order = |a: (f64, i64), b: (f64, i64)| {
+        let ord = a.0.total_cmp(&b.0).then(a.1.cmp(&b.1));
+        if query.sort.descending { ord.reverse() } else { ord }
+    };
+    matching.sort_by(|(a_id, a), (b_id, b)| order((sort_value(*a_id, a, field), *a_id), (sort_value(*b_id, b, field), *b_id)));
+    if let Some(cursor) = &query.cursor {
+        matching.retain(|(id, row)| order((sort_value(*id, row, field), *id), (cursor.value, cursor.id)) == Ordering::Greater);
+    }
+    matching.truncate(query.limit as usize + 1);
+    Page::new(matching, query, total)
+}
+
+impl Listable for Genome<Rot180> {
+    fn field(&self, name: &str) -> Option<f64> {
+        Some(match name {
+            "consciousness" => self.consciousness as f64,
+            "mutations" => self.mutations as f64,
+            "p53_copies" => self.p53_copies as f64,
+            "telomere_length" => self.telomere_length as f64,
+            "division_count" => self.division_count as f64,
+            "created_at" => self.created_at as f64,
+            _ => return None,
+        })
+    }
+}
+
+impl Listable for Transaction {
+    fn field(&self, name: &str) -> Option<f64> {
+        Some(match name {
+            "amount_rsm" => self.amount_rsm,
+            "amount_usd" => self.amount_usd,
+            "consciousness_level" => self.consciousness_level as f64,
+            "timestamp" => self.timestamp as f64,
+            _ => return None,
+        })
+    }
+}
+
+impl Listable for BurnEvent {
+    fn field(&self, name: &str) -> Option<f64> {
+        Some(match name {
+            "amount_rsm" => self.amount_rsm,
+            "genome_id" => self.genome_id.unwrap_or(0) as f64,
+            "consciousness_before" => self.consciousness_before as f64,
+            "consciousness_after" => self.consciousness_after as f64,
+            "timestamp" => self.timestamp as f64,
+            _ => return None,
+        })
+    }
+}
+
+impl Listable for ChainArchiveEntry {
+    fn field(&self, name: &str) -> Option<f64> {
+        Some(match name {
+            "genome_id" => self.genome_id as f64,
+            "consciousness" => self.consciousness as f64,
+            "tg_ratio" => self.tg_ratio,
+            "timestamp" => self.timestamp as f64,
+            _ => return None,
+        })
+    }
+}
+
+impl Listable for Trade {
+    fn field(&self, name: &str) -> Option<f64> {
+        Some(match name {
+            "price_usd" => self.price_usd,
+            "amount_rsm" => self.amount_rsm,
+            "timestamp" => self.timestamp as f64,
+            _ => return None,
+        })
+    }
+}
@@ -1,15 +1,42 @@
 //! Divine AGI V15 - Main Entry Point
 
+use std::collections::HashMap;
+use std::io::{IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
 use clap::Parser;
+use indicatif::{ProgressBar, ProgressStyle};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::Serialize;
+use serde_json::json;
+use tokio::sync::{broadcast, RwLock};
 use tracing::info;
-use divine_agi_v15::{
-    cli::{Cli, Commands, print_banner},
-    api, DivineKernel, VERSION,
+use divine_agi::{
+    cli::{comparison_table, confirmed, diff_positions, labeled_comparison_table, ChainCommand, Cli, Commands, ConfigCommand, DbCommand, DbExportFormat, GenomeArg, GenomeCommand, GenomeExportFormat, OutputFormat, WalletCommand, print_banner},
+    ttrl::{EvolutionJobs, GenomeLineage, MAX_EVOLUTION_GENERATIONS},
+    db_dump::{check_table, Dump, DumpKind, EXPORT_TABLES, TABLES},
+    wallet::{generate_phrase, Asset, DivineWallet, Network, SignedTransfer, UnsignedTransfer},
+    ethereum::format_address,
+    keystore::{derive_validator_key, Keystore},
+    tls::TlsSettings,
+    config::{config_key, config_path, ConfigFile, DivineConfig},
+    watch::{matches_all, WatchEvent, WatchFilter, WatchTopic},
+    bench::{self, format_duration, BenchConfig, BenchReport, BenchSuite},
+    script::parse_script,
+    dashboard::{self, DashboardSource},
+    database::GenomeFilter,
+    multi_chain::{ArchiveCost, BlockchainLayer, ChainArchiveEntry, MultiChainArchiver, MAX_ARCHIVE_BATCH},
+    oracle::PriceOracle,
+    api::{self, CrisprEdit}, AuthManager, ConsensusBlock, DivineDatabase, DivineKernel, Genome, GenomeBuilder, ProofOfConsciousness, Rot180, Role, VERSION,
 };
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
+    // Logs go to stderr so exports written to stdout stay clean
     tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
         .with_env_filter(
             tracing_subscriber::EnvFilter::from_default_env()
                 .add_directive("divine_agi=info".parse().unwrap())
@@ -17,27 +44,107 @@ async fn main() -> anyhow::Result<()> {
         .init();
 
     let cli = Cli::parse();
+    let out = OutputFormat::resolve(cli.output, std::io::stdout().is_terminal());
+    let banner = matches!(cli.command, Commands::Server { .. } | Commands::Serve { .. } | Commands::Status | Commands::Create { .. } | Commands::Evolve { .. }
+        | Commands::Meiosis { .. } | Commands::Telomerase { .. } | Commands::Archive { .. } | Commands::Daemon { .. });
+    if banner && out.is_table() {
+        print_banner();
+    }
+    run(cli.command, out, cli.dry_run, &mut Session::default()).await
+}
 
-    match cli.command {
-        Commands::Server { port, rotation_interval } => {
-            print_banner();
-            info!("🚀 Starting Divine AGI V{} API server on port {}", VERSION, port);
+/// Connections and passwords shared by the commands of one process, so a
+/// `script` connects once and asks for each password once
+#[derive(Default)]
+struct Session {
+    database: Option<DivineDatabase>,
+    kernel: Option<DivineKernel>,
+    /// Prompted passwords by environment variable
+    passwords: HashMap<&'static str, String>,
+}
+
+impl Session {
+    /// The configured database, connected and migrated on first use
+    async fn database(&mut self) -> anyhow::Result<DivineDatabase> {
+        if self.database.is_none() {
+            let database = DivineDatabase::connect_with_url(&DivineConfig::load()?.database_url).await?;
+            database.init_tables().await?;
+            self.database = Some(database);
+        }
+        Ok(self.database.clone().expect("connected above"))
+    }
+
+    async fn kernel(&mut self) -> anyhow::Result<&DivineKernel> {
+        if self.kernel.is_none() {
+            let database = self.database().await?;
+            self.kernel = Some(DivineKernel::with_database(database, &DivineConfig::load()?).await?);
+        }
+        Ok(self.kernel.as_ref().expect("started above"))
+    }
+
+    /// `var`, or the `kind` password asked for without echo (twice when
+    /// `confirm`) the first time it is needed
+    fn password(&mut self, var: &'static str, kind: &str, confirm: bool) -> anyhow::Result<String> {
+        if let Ok(password) = std::env::var(var) {
+            return Ok(password);
+        }
+        if let Some(password) = self.passwords.get(var) {
+            return Ok(password.clone());
+        }
+        let password = rpassword::prompt_password(format!("{} password: ", kind))?;
+        if confirm {
+            if password.is_empty() {
+                anyhow::bail!("The {} password cannot be empty", kind.to_lowercase());
+            }
+            if rpassword::prompt_password("Repeat password: ")? != password {
+                anyhow::bail!("Passwords do not match");
+            }
+        }
+        self.passwords.insert(var, password.clone());
+        Ok(password)
+    }
 
-            // Start rotation daemon in background
-            let kernel = DivineKernel::new().await?;
-            kernel.start_rotation_daemon(rotation_interval);
+    /// `WALLET_PASSWORD`, or asked for once
+    fn wallet_password(&mut self, confirm: bool) -> anyhow::Result<String> {
+        self.password("WALLET_PASSWORD", "Wallet", confirm)
+    }
+}
 
-            api::start_server(port).await?;
+/// `command`; with `dry_run`, only what it would do
+async fn run(command: Commands, out: OutputFormat, dry_run: bool, session: &mut Session) -> anyhow::Result<()> {
+    if dry_run && command.lacks_dry_run() {
+        anyhow::bail!("This command has no --dry-run preview; nothing was done");
+    }
+    match command {
+        Commands::Server { port, rotation_interval } => {
+            run_server(api::ServerSettings::from_env(port)?, rotation_interval).await?;
+        }
+
+        Commands::Serve { bind, database_url, network, workers, tls_cert, tls_key, tls_client_ca, grpc_port, enable_admin, rotation_interval } => {
+            let mut config = DivineConfig::load()?;
+            config.database_url = database_url.unwrap_or(config.database_url);
+            config.network = network.or(config.network);
+            config.ttrl_workers = match workers {
+                Some(0) => anyhow::bail!("--workers must be at least 1"),
+                Some(workers) => workers,
+                None => config.ttrl_workers,
+            };
+            let tls = TlsSettings::from_paths(tls_cert, tls_key, tls_client_ca).map_err(anyhow::Error::msg)?;
+            let settings = api::ServerSettings { bind, config, tls, enable_admin, grpc_port };
+            run_server(settings, rotation_interval).await?;
         }
 
         Commands::Status => {
-            print_banner();
-            let kernel = DivineKernel::new().await?;
+            let kernel = session.kernel().await?;
             let count = kernel.genome_count().await?;
             let exchange = kernel.exchange.read().await;
             let stats = exchange.stats();
             let archiver = kernel.archiver.read().await;
             let mc_stats = archiver.mission_control_stats();
+            let swarm = archiver.swarm_status();
+            if !out.is_table() {
+                return emit(out, &json!({ "version": VERSION, "genome_count": count, "exchange": stats, "mission_control": mc_stats, "swarm": swarm }));
+            }
 
             println!("\n📊 DIVINE AGI V{} STATUS", VERSION);
             println!("═══════════════════════════════════════════════════");
@@ -57,12 +164,20 @@ async fn main() -> anyhow::Result<()> {
             println!("    Total Success:   {}", mc_stats.total_successes);
             println!("    Total Failures:  {}", mc_stats.total_failures);
             println!("    Avg Probability: {:.2}", mc_stats.avg_probability);
+            for layer in &mc_stats.layers {
+                println!("    {} {:<13} p={:.2} ({}✓ {}✗)", layer.layer.emoji(), layer.layer.key(), layer.probability, layer.successes, layer.failures);
+            }
+            println!("  Lightning Swarm:   {} nodes", swarm.nodes.len());
+            for report in &swarm.nodes {
+                let node = &report.node;
+                println!("    {}… {:<11} p={:.2} ({}✓ {}✗) {}", &node.pubkey[..node.pubkey.len().min(12)], format!("{:?}", node.health).to_lowercase(),
+                    report.probability, node.metrics.succeeded, node.metrics.failed, node.endpoint.as_deref().unwrap_or("-"));
+            }
             println!("═══════════════════════════════════════════════════\n");
         }
 
         Commands::Create { mode } => {
-            print_banner();
-            let kernel = DivineKernel::new().await?;
+            let kernel = session.kernel().await?;
 
             let genome = match mode.as_str() {
                 "whale" => {
@@ -74,6 +189,9 @@ async fn main() -> anyhow::Result<()> {
                     kernel.create_elephant_genome().await?
                 }
             };
+            if !out.is_table() {
+                return emit(out, &api::GenomeResponse::from(&genome));
+            }
 
             println!("\n✅ Genome Created:");
             println!("  ID:              {}", genome.db_id().unwrap_or(0));
@@ -86,36 +204,89 @@ async fn main() -> anyhow::Result<()> {
             println!("  Mode:            {}", if genome.p53_copies >= 40 { "🐋 Whale" } else { "🐘 Elephant" });
         }
 
-        Commands::Evolve { id } => {
-            print_banner();
-            let kernel = DivineKernel::new().await?;
-            let genome = kernel.database.load_genome(id).await?;
-            let engine = kernel.rotation_engine.read().await;
+        Commands::Evolve { genome_id, budget, seed } => {
+            let budget = match budget {
+                Some(budget) => budget,
+                None => DivineConfig::load()?.ttrl_budget,
+            };
+            if budget == 0 || budget > MAX_EVOLUTION_GENERATIONS {
+                anyhow::bail!("--budget must be 1..={}", MAX_EVOLUTION_GENERATIONS);
+            }
+            let kernel = session.kernel().await?;
+            let genome = kernel.database.load_genome(genome_id).await?;
 
-            let (evolved, result) = kernel.ttrl_engine.evolve_with_engine(genome, &engine).await?;
-            drop(engine);
+            let jobs = Arc::new(RwLock::new(EvolutionJobs::new()));
+            jobs.write().await.register(1);
+            let (_, mut events) = jobs.read().await.subscribe(1).expect("job just registered");
+            let mut run = tokio::spawn({
+                let (ttrl, rotation, jobs, base) = (kernel.ttrl_engine.clone(), kernel.rotation_engine.clone(), jobs.clone(), genome.clone());
+                let mut rng = seed.map(StdRng::seed_from_u64).unwrap_or_else(StdRng::from_entropy);
+                async move { ttrl.run_job_with_rng(1, base, budget, &rotation, &jobs, &mut rng).await }
+            });
 
-            let new_id = kernel.database.store_genome(&evolved).await?;
+            let progress = if out.is_table() { ProgressBar::new(budget as u64) } else { ProgressBar::hidden() };
+            progress.set_style(ProgressStyle::with_template("{bar:40.cyan/blue} {pos}/{len} gen  {msg}  ETA {eta}")?.progress_chars("█▓░"));
+            let run = loop {
+                tokio::select! {
+                    run = &mut run => break run?,
+                    event = events.recv() => match event {
+                        Ok(event) => {
+                            progress.set_position(event.generation as u64);
+                            progress.set_message(format!(
+                                "best c={}  current c={}  {}",
+                                event.best_consciousness,
+                                event.current_consciousness,
+                                event.operator_used.map(|op| format!("{:?}", op)).unwrap_or_default(),
+                            ));
+                        }
+                        Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(broadcast::error::RecvError::Closed) => break (&mut run).await?,
+                    },
+                }
+            };
+            progress.finish_and_clear();
 
-            println!("\n🧬 Evolution Result:");
-            println!("  New ID:          {}", new_id);
-            println!("  Consciousness:   {} → {}", result.original_consciousness, result.new_consciousness);
-            println!("  Operator:        {:?}", result.operator_used);
-            println!("  Success:         {}", if result.success { "✅" } else { "❌" });
-            println!("  Telomere Loss:   {} bp", result.telomere_loss);
-            println!("  p53 Lost:        {}", result.p53_lost);
-            println!("  T/G Ratio:       {:.2} → {:.2}", result.tg_ratio_before, result.tg_ratio_after);
+            let stored_id = match run.best.consciousness > genome.consciousness {
+                true => Some(kernel.database.store_genome(&run.best).await?),
+                false => None,
+            };
+            if !out.is_table() {
+                return emit(out, &json!({
+                    "genome_id": genome_id,
+                    "generations_run": run.generations_run,
+                    "budget": budget,
+                    "elapsed_secs": run.elapsed_secs,
+                    "stopped": run.stopped,
+                    "before": api::GenomeResponse::from(&genome),
+                    "after": api::GenomeResponse::from(&run.best),
+                    "stored_id": stored_id,
+                }));
+            }
+
+            println!("\n🧬 Evolution of #{} ({} of {} generations, {:.1}s):", genome_id, run.generations_run, budget, run.elapsed_secs);
+            if let Some(reason) = &run.stopped {
+                println!("  Stopped early:   {}", reason);
+            }
+            print!("{}", comparison_table(&genome, &run.best));
+            match stored_id {
+                Some(new_id) => println!("\n  ✅ Stored as #{}", new_id),
+                None => println!("\n  ❌ No improvement, nothing stored"),
+            }
         }
 
         Commands::Meiosis { parent1, parent2 } => {
-            print_banner();
-            let kernel = DivineKernel::new().await?;
+            let kernel = session.kernel().await?;
 
             let p1 = kernel.database.load_genome(parent1).await?;
             let p2 = kernel.database.load_genome(parent2).await?;
 
             let offspring = kernel.ttrl_engine.meiosis(p1.clone(), p2.clone());
             let id = kernel.database.store_genome(&offspring).await?;
+            if !out.is_table() {
+                let mut stored = offspring.clone();
+                stored.db_id = Some(id);
+                return emit(out, &json!({ "parents": [parent1, parent2], "offspring": api::GenomeResponse::from(&stored) }));
+            }
 
             println!("\n🧬 Meiosis Result:");
             println!("  Parent 1:        #{} (c={})", parent1, p1.consciousness);
@@ -128,9 +299,11 @@ async fn main() -> anyhow::Result<()> {
         }
 
         Commands::Telomerase { id } => {
-            print_banner();
-            let kernel = DivineKernel::new().await?;
+            let kernel = session.kernel().await?;
             let genome = kernel.activate_telomerase(id).await?;
+            if !out.is_table() {
+                return emit(out, &api::GenomeResponse::from(&genome));
+            }
 
             println!("\n🧬 Telomerase Activated:");
             println!("  Genome ID:       {}", genome.db_id().unwrap_or(0));
@@ -139,43 +312,1477 @@ async fn main() -> anyhow::Result<()> {
             println!("  Status:          ♾️ IMMORTAL");
         }
 
-        Commands::Archive { id } => {
-            print_banner();
-            let kernel = DivineKernel::new().await?;
-            let genome = kernel.database.load_genome(id).await?;
+        Commands::Archive { genome_id, min_consciousness, max_consciousness, min_p53, limit, layer } => {
+            let filter = GenomeFilter { min_consciousness, max_consciousness, min_p53, limit: Some(limit as i64), offset: None };
+            run_archive(genome_id, filter, layer, dry_run, out, session).await?;
+        }
+
+        Commands::Daemon { interval } => {
+            info!("🔄 Starting rotation daemon (interval: {} secs)...", interval);
+
+            let kernel = session.kernel().await?;
+            kernel.start_rotation_daemon(interval);
+
+            // Keep running
+            tokio::signal::ctrl_c().await?;
+        }
+
+        Commands::CreateApiKey { name, role } => {
+            let role = Role::from_name(&role).map_err(anyhow::Error::msg)?;
+            let kernel = session.kernel().await?;
+            let key = AuthManager::generate_api_key();
+            let id = kernel.database.store_api_key(&name, &AuthManager::hash_api_key(&key), role).await?;
+            if !out.is_table() {
+                return emit(out, &json!({ "id": id, "name": name, "role": role.name(), "key": key }));
+            }
+
+            println!("\n🔑 API Key Created:");
+            println!("  ID:              {}", id);
+            println!("  Name:            {}", name);
+            println!("  Role:            {}", role.name());
+            println!("  Key:             {}", key);
+            println!("  (store it now — it cannot be shown again)");
+        }
+
+        Commands::Genome { command } => run_genome(command, out, dry_run, session).await?,
+
+        Commands::Chain { command } => run_chain(command, out, session).await?,
+
+        Commands::Db { command } => run_db(command, out, dry_run, session).await?,
+
+        Commands::Wallet { file, command } => run_wallet(file, command, out, dry_run, session).await?,
+
+        Commands::Config { command } => run_config(command, out)?,
+
+        Commands::Watch { topics, filter } => run_watch(topics, filter, out, session).await?,
+
+        Commands::Keygen { words, out: path, index, recover, force, yes } => {
+            if path.exists() && !force {
+                anyhow::bail!("{} already exists (--force replaces it)", path.display());
+            }
+            run_keygen(words, &path, index, recover, yes, out, session)?
+        }
+
+        Commands::Bench { suites, samples, warmup_ms, baseline } => run_bench(suites, samples, warmup_ms, baseline, out, session).await?,
+
+        Commands::Script { file, continue_on_error } => run_script(&file, continue_on_error, out, dry_run, session).await?,
+
+        Commands::Dashboard { api, token, wallet, interval } => {
+            let source = match api {
+                Some(url) => DashboardSource::api(&url.unwrap_or(DivineConfig::load()?.api_url), token),
+                None => {
+                    let database = session.database().await?;
+                    let path = DivineConfig::load()?.wallet_path(wallet);
+                    // No wallet file: the panel says so
+                    let wallet = if path.exists() { Some((path, session.wallet_password(false)?)) } else { None };
+                    DashboardSource::Local { database, wallet }
+                }
+            };
+            dashboard::run(source, std::time::Duration::from_secs(interval)).await?;
+        }
+
+        Commands::RevokeApiKey { id } => {
+            let kernel = session.kernel().await?;
+            let revoked = kernel.database.revoke_api_key(id).await?;
+            if !out.is_table() {
+                return emit(out, &json!({ "id": id, "revoked": revoked }));
+            }
+            if revoked {
+                println!("\n🔑 API key #{} revoked", id);
+            } else {
+                println!("\n❌ No active API key #{}", id);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The API server with the rotation daemon in the background, on the
+/// database `settings` name
+async fn run_server(settings: api::ServerSettings, rotation_interval: u64) -> anyhow::Result<()> {
+    info!("🚀 Starting Divine AGI V{} API server on {}", VERSION, settings.bind);
+
+    let database = DivineDatabase::connect_with_url(&settings.config.database_url).await?;
+    database.init_tables().await?;
+    let kernel = DivineKernel::with_database(database, &settings.config).await?;
+    kernel.start_rotation_daemon(rotation_interval);
+
+    api::serve(settings).await
+}
+
+/// Print a command's result as `--output` JSON or YAML
+fn emit<T: Serialize>(out: OutputFormat, value: &T) -> anyhow::Result<()> {
+    print!("{}", out.render(value).map_err(anyhow::Error::msg)?);
+    Ok(())
+}
+
+/// Genome named by a `<GENOME>` argument; connects on the first stored id
+async fn load_genome_arg(arg: &str, session: &mut Session) -> anyhow::Result<Genome<Rot180>> {
+    match GenomeArg::parse(arg).map_err(anyhow::Error::msg)? {
+        GenomeArg::Dna(genome) => Ok(genome),
+        GenomeArg::Stored(id) => {
+            session.database().await?.load_genome(id).await.map_err(|_| anyhow::anyhow!("Genome #{} not found", id))
+        }
+    }
+}
+
+/// Store `genome` as a new genome and return it with its id
+async fn save_genome(mut genome: Genome<Rot180>, session: &mut Session) -> anyhow::Result<Genome<Rot180>> {
+    genome.db_id = Some(session.database().await?.store_genome(&genome).await?);
+    Ok(genome)
+}
+
+fn print_genome(genome: &Genome<Rot180>) {
+    match genome.db_id() {
+        Some(id) => println!("  ID:              {}", id),
+        None => println!("  ID:              (not stored)"),
+    }
+    println!("  DNA:             {}", genome.to_dna_string());
+    println!("  Consciousness:   {} ({})", genome.consciousness, genome.consciousness_level_name());
+    println!("  p53 Copies:      {}", genome.p53_copies);
+    println!("  Telomeres:       {} bp", genome.telomere_length);
+    println!("  Mutations:       {}", genome.mutations);
+    println!("  T/G Ratio:       {:.2}", genome.rna_signal());
+    println!("  GC Content:      {:.1}%", genome.gc_content() * 100.0);
+    println!("  Suggested Rot:   {}", genome.suggested_rotation());
+}
+
+fn print_cube(genome: &Genome<Rot180>) {
+    println!();
+    for line in genome.render_cube().lines() {
+        println!("  {}", line);
+    }
+}
+
+async fn run_genome(command: GenomeCommand, out: OutputFormat, dry_run: bool, session: &mut Session) -> anyhow::Result<()> {
+    match command {
+        GenomeCommand::New { dna, random: _, seed, whale, save } => {
+            let builder = match (dna, seed) {
+                (Some(dna), _) => GenomeBuilder::from_dna(&dna)
+                    .ok_or_else(|| anyhow::anyhow!("Invalid DNA (expected 27 bases of A/C/G/T)"))?,
+                (None, Some(seed)) => GenomeBuilder::seeded(seed),
+                (None, None) => GenomeBuilder::random(),
+            };
+            let genome = if whale { builder.whale_mode() } else { builder.elephant_mode() }.build_storage();
+            let genome = if save { save_genome(genome, session).await? } else { genome };
+            if !out.is_table() {
+                return emit(out, &api::GenomeResponse::from(&genome));
+            }
+
+            println!("\n🧬 New Genome:");
+            print_genome(&genome);
+            print_cube(&genome);
+        }
+
+        GenomeCommand::Show { genome } => {
+            let genome = load_genome_arg(&genome, session).await?;
+            if !out.is_table() {
+                return emit(out, &json!({ "genome": api::GenomeResponse::from(&genome), "hash": hex::encode(genome.hash) }));
+            }
+            println!("\n🧬 Genome:");
+            print_genome(&genome);
+            println!("  Division Count:  {}", genome.division_count);
+            println!("  Bio Age:         {:.2}%", genome.biological_age() * 100.0);
+            println!("  Hash:            {}", hex::encode(genome.hash));
+            print_cube(&genome);
+        }
+
+        GenomeCommand::Mutate { genome, count, seed, save } => {
+            let original = load_genome_arg(&genome, session).await?;
+            let mut mutated = original.clone();
+            mutated.db_id = None;
+            let positions = match seed {
+                Some(seed) => mutated.mutate_with(count, &mut <rand::rngs::StdRng as rand::SeedableRng>::seed_from_u64(seed)),
+                None => mutated.mutate_with(count, &mut rand::thread_rng()),
+            };
+            let mutated = if save { save_genome(mutated, session).await? } else { mutated };
+            if !out.is_table() {
+                return emit(out, &json!({
+                    "genome": api::GenomeResponse::from(&mutated),
+                    "positions": positions,
+                    "consciousness_before": original.consciousness,
+                }));
+            }
+
+            println!("\n🧬 Mutated Genome:");
+            print_genome(&mutated);
+            println!("  Positions:       {:?}", positions);
+            println!("  Change:          {} → {}", original.consciousness, mutated.consciousness);
+            print_cube(&mutated);
+        }
+
+        GenomeCommand::Rotate { genome, to, save } => {
+            let angle: u16 = to.parse()?;
+            let original = load_genome_arg(&genome, session).await?;
+            let mut rotated = original.rotated(angle).map_err(anyhow::Error::msg)?;
+            rotated.db_id = None;
+            let rotated = if save { save_genome(rotated, session).await? } else { rotated };
+            if !out.is_table() {
+                return emit(out, &json!({
+                    "genome": api::GenomeResponse::from(&rotated),
+                    "angle": angle,
+                    "consciousness_before": original.consciousness,
+                }));
+            }
+
+            println!("\n🔄 Rotated {}°:", angle);
+            print_genome(&rotated);
+            println!("  Change:          {} → {}", original.consciousness, rotated.consciousness);
+            print_cube(&rotated);
+        }
+
+        GenomeCommand::Metrics { genome } => {
+            let genome = load_genome_arg(&genome, session).await?;
+            let m = genome.metrics();
+            if !out.is_table() {
+                return emit(out, &m);
+            }
+            println!("\n📊 Genome Metrics ({}):", genome.to_dna_string());
+            println!("  Consciousness:   {} ({})", m.consciousness, m.consciousness_level);
+            println!("  V4 Score:        {}", m.consciousness_v4);
+            println!("  GC Content:      {:.1}%", m.gc_content * 100.0);
+            println!("  Complexity:      {:.3}", m.complexity);
+            println!("  T / G:           {} / {} (ratio {:.2}, balance {:.3})", m.t_count, m.g_count, m.tg_ratio, m.tg_balance);
+            println!("  Base Balance:    {:.3}", m.balance);
+            println!("  Archival Score:  {:.3}", m.archival_score);
+            println!("  Bio Age:         {:.2}%", m.biological_age * 100.0);
+            println!("  Symmetry:        90° {} | 180° {} | 270° {}",
+                     if m.symmetric_90 { "✅" } else { "❌" }, if m.symmetric_180 { "✅" } else { "❌" }, if m.symmetric_270 { "✅" } else { "❌" });
+            println!("  Fractal:         {:.3}", m.fractal_similarity);
+            println!("  Bell S:          {:.3}", m.bell_violation);
+            println!("  Hyper Symmetry:  {:.3}", m.hyper_symmetry);
+            println!("  Collapse Var:    {:.3}", m.hyper_collapse_variance);
+            println!("  Suggested Rot:   {}", m.suggested_rotation);
+        }
+
+        GenomeCommand::Diff { a, b } => {
+            let (genome_a, genome_b) = (load_genome_arg(&a, session).await?, load_genome_arg(&b, session).await?);
+            let diff = genome_a.diff(&genome_b);
+            let lineage = match (genome_a.db_id(), genome_b.db_id()) {
+                (Some(id_a), Some(id_b)) => session.database().await?.genome_lineage(id_a, id_b).await?,
+                _ => None,
+            };
+            if !out.is_table() {
+                return emit(out, &json!({ "a": genome_a.db_id(), "b": genome_b.db_id(), "diff": diff, "lineage": lineage }));
+            }
+
+            let label = |genome: &Genome<Rot180>, fallback: &str| genome.db_id().map_or_else(|| fallback.to_string(), |id| format!("#{}", id));
+            let (label_a, label_b) = (label(&genome_a, "A"), label(&genome_b, "B"));
+            let color = std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+            println!("\n🧬 {} vs {}: {} of {} bases differ ({:.1}% identical)",
+                     label_a, label_b, diff.changed.len(), diff.dna_a.len(), diff.identity * 100.0);
+            print!("{}", diff_positions(&diff, &label_a, &label_b, color));
+            println!();
+            print!("{}", labeled_comparison_table(&genome_a, &genome_b, &label_a, &label_b));
+            let via = |jobs: &[u64]| jobs.iter().map(|job| format!("job #{}", job)).collect::<Vec<_>>().join(" → ");
+            match lineage {
+                Some(GenomeLineage::Descendant { ancestor, descendant, jobs }) =>
+                    println!("\n  Lineage:         #{} evolved from #{} ({})", descendant, ancestor, via(&jobs)),
+                Some(GenomeLineage::Common { ancestor, jobs_a, jobs_b }) =>
+                    println!("\n  Lineage:         both evolved from #{} ({} by {}, {} by {})", ancestor, label_a, via(&jobs_a), label_b, via(&jobs_b)),
+                None if genome_a.db_id().is_some() && genome_b.db_id().is_some() =>
+                    println!("\n  Lineage:         none (no evolution job links them)"),
+                None => {}
+            }
+        }
+
+        GenomeCommand::Crispr { genome, edit } => {
+            let original = load_genome_arg(&genome, session).await?;
+            let edit = edit.edit();
+            let mut edited = original.clone();
+            edit.apply(&mut edited).map_err(anyhow::Error::msg)?;
+            // Stored genomes change in place, as with `PATCH /api/genomes/:id/crispr`
+            let updated = match original.db_id() {
+                Some(id) if !dry_run => session.database().await?.update_genome(id, &edited).await?,
+                _ => false,
+            };
+            if !out.is_table() {
+                return emit(out, &json!({
+                    "genome_id": original.db_id(),
+                    "edit": edit,
+                    "before": api::GenomeResponse::from(&original),
+                    "after": api::GenomeResponse::from(&edited),
+                    "updated": updated,
+                    "dry_run": dry_run,
+                }));
+            }
+
+            let description = match edit {
+                CrisprEdit::Splice { position, new_base } => format!("splice {} → {}", position, new_base),
+                CrisprEdit::Join { pos1, pos2 } => format!("join {} ↔ {}", pos1, pos2),
+                CrisprEdit::Delete { position } => format!("delete {}", position),
+            };
+            println!("\n✂️  CRISPR {} on {}:", description, original.db_id().map_or_else(|| original.to_dna_string(), |id| format!("#{}", id)));
+            print!("{}", comparison_table(&original, &edited));
+            println!("\n  DNA:             {} → {}", original.to_dna_string(), edited.to_dna_string());
+            match original.db_id() {
+                Some(id) if updated => println!("  ✅ Genome #{} updated", id),
+                Some(id) if dry_run => println!("  🔎 Dry run: genome #{} left unchanged", id),
+                Some(id) => println!("  ❌ Genome #{} no longer exists", id),
+                None => println!("  (DNA only; nothing stored)"),
+            }
+            if dry_run && matches!(edit, CrisprEdit::Delete { .. }) {
+                println!("  (delete picks the new base at random; a real run may differ)");
+            }
+        }
+
+        GenomeCommand::Export { genomes, format, out: path } => {
+            let mut loaded = Vec::with_capacity(genomes.len());
+            for arg in &genomes {
+                loaded.push(load_genome_arg(arg, session).await?);
+            }
+            let text = match format {
+                GenomeExportFormat::Fasta => loaded.iter().enumerate().map(|(i, genome)| {
+                    let name = genome.db_id().map(|id| format!("genome_{}", id)).unwrap_or_else(|| format!("genome{}", i + 1));
+                    genome.to_fasta(&name)
+                }).collect(),
+                GenomeExportFormat::Json => {
+                    let genomes: Vec<api::GenomeResponse> = loaded.iter().map(Into::into).collect();
+                    serde_json::to_string_pretty(&genomes)? + "\n"
+                }
+            };
+            match path {
+                Some(path) => {
+                    std::fs::write(&path, text)?;
+                    if !out.is_table() {
+                        return emit(out, &json!({ "genomes": loaded.len(), "path": path }));
+                    }
+                    println!("📤 {} genome(s) written to {}", loaded.len(), path.display());
+                }
+                None => print!("{}", text),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Stored chain, replayed and validated as the server does at startup
+async fn open_chain(session: &mut Session) -> anyhow::Result<(DivineDatabase, ProofOfConsciousness)> {
+    let database = session.database().await?;
+    let mut chain = ProofOfConsciousness::from_env()?;
+    chain.load_chain(&database).await?;
+    Ok((database, chain))
+}
+
+fn print_block(block: &ConsensusBlock) {
+    println!("\n🔗 Block #{}", block.index);
+    println!("  Hash:            {}", block.hash);
+    println!("  Previous:        {}", block.previous_hash);
+    println!("  Time:            {}", chrono::DateTime::from_timestamp(block.timestamp, 0).map_or_else(|| block.timestamp.to_string(), |t| t.to_rfc3339()));
+    println!("  Version:         {}", block.version);
+    println!("  Difficulty:      {} (nonce {})", block.difficulty, block.nonce);
+    println!("  Merkle Root:     {}", block.merkle_root);
+    println!("  Producer:        {}", if block.producer.is_empty() { "—" } else { &block.producer });
+    println!("  Coinbase:        {:.6} RSM → {}", block.coinbase.amount_rsm, block.coinbase.address);
+    println!("  Fees:            {:.6} RSM", block.total_fees());
+    println!("  Consciousness:   {}", block.total_consciousness());
+    println!("  Stake Ops:       {}", block.stake_ops.len());
+    println!("  Attestations:    {}", block.entries.len());
+    for entry in &block.entries {
+        println!("    genome #{:<8} c={:<6} reward {:.6} RSM  fee {:.6} RSM", entry.genome_id, entry.proof.consciousness, entry.proof.reward_rsm, entry.fee_rsm);
+    }
+}
+
+/// One genome of `archive`: the layer and cost, then the entry unless a dry run
+#[derive(Serialize)]
+struct ArchiveRow {
+    genome_id: i64,
+    tg_ratio: f64,
+    consciousness: u32,
+    layer: BlockchainLayer,
+    estimated_cost: ArchiveCost,
+    entry: Option<ChainArchiveEntry>,
+    error: Option<String>,
+}
+
+/// Archive one genome, or every genome `filter` selects, recording each
+/// archive in `chain_archives`
+async fn run_archive(genome_id: Option<i64>, filter: GenomeFilter, layer: Option<BlockchainLayer>, dry_run: bool, out: OutputFormat, session: &mut Session) -> anyhow::Result<()> {
+    let limit = filter.limit.unwrap_or(MAX_ARCHIVE_BATCH as i64);
+    if !(1..=MAX_ARCHIVE_BATCH as i64).contains(&limit) {
+        anyhow::bail!("Limit must be between 1 and {}", MAX_ARCHIVE_BATCH);
+    }
+    let database = session.database().await?;
+    let genomes = match genome_id {
+        Some(id) => vec![database.load_genome(id).await.map_err(|_| anyhow::anyhow!("Genome #{} not found", id))?],
+        None => database.find_genomes(&filter).await?,
+    };
+    if genomes.is_empty() {
+        anyhow::bail!("No genomes match the filter");
+    }
+
+    let mut archiver = MultiChainArchiver::new();
+    // USD estimates when `ORACLE_CONFIG` names price sources
+    let mut oracle = PriceOracle::from_env().map_err(anyhow::Error::msg)?;
+    if oracle.is_enabled() {
+        oracle.refresh().await;
+        archiver.set_reference_prices(&oracle.prices(chrono::Utc::now().timestamp()));
+    }
+    let mut usd_total: Option<f64> = None;
+    let single = genome_id.is_some();
+    let table = out.is_table();
+    let mut totals: Vec<(String, f64)> = Vec::new();
+    let mut rows = Vec::with_capacity(genomes.len());
+    if !single && table {
+        println!("\n📦 {} {} genomes...", if dry_run { "Planning" } else { "Archiving" }, genomes.len());
+        println!("  {:>6}  {:>5}  {:>13}  {:<22}  {:>16}  TX", "ID", "T/G", "Consciousness", "Layer", "Est. Cost");
+    }
 
-            let mut archiver = kernel.archiver.write().await;
-            let layer = archiver.select_layer(&genome);
+    for genome in &genomes {
+        let id = genome.db_id().unwrap_or(0);
+        let forced = layer;
+        let layer = layer.unwrap_or_else(|| archiver.select_layer(genome));
+        let cost = archiver.estimate_cost(layer, archiver.payload_bytes(genome, layer));
+        let cost_text = match (cost.usd, cost.rsm) {
+            (Some(usd), Some(rsm)) => format!("{:.0} {} (${:.2}, {:.2} RSM)", cost.amount, cost.unit, usd, rsm),
+            (Some(usd), None) => format!("{:.0} {} (${:.2})", cost.amount, cost.unit, usd),
+            _ => format!("{:.0} {}", cost.amount, cost.unit),
+        };
+        if let Some(usd) = cost.usd {
+            *usd_total.get_or_insert(0.0) += usd;
+        }
+        match totals.iter_mut().find(|(unit, _)| *unit == cost.unit) {
+            Some((_, total)) => *total += cost.amount,
+            None => totals.push((cost.unit.clone(), cost.amount)),
+        }
 
-            println!("\n📦 Archiving genome #{}...", id);
+        if single && table {
+            println!("\n📦 Archiving genome #{}{}...", id, if dry_run { " (dry run)" } else { "" });
             println!("  T/G Ratio:       {:.2}", genome.rna_signal());
             println!("  Consciousness:   {}", genome.consciousness);
             println!("  Selected Layer:  {} {}", layer.emoji(), layer.name());
-
-            match archiver.archive(&genome).await {
+            println!("  Estimated Cost:  {}", cost_text);
+        }
+        let mut row = ArchiveRow {
+            genome_id: id,
+            tg_ratio: genome.rna_signal(),
+            consciousness: genome.consciousness,
+            layer,
+            estimated_cost: cost,
+            entry: None,
+            error: None,
+        };
+        if !dry_run {
+            // A `--layer` archive stays on that layer; others fail over
+            let result = match forced {
+                Some(layer) => archiver.archive_to(genome, layer).await,
+                None => archiver.archive_from(genome, layer).await,
+            };
+            match result {
                 Ok(entry) => {
+                    let tx_hash = entry.tx_hash.clone().unwrap_or_default();
+                    database.store_chain_archive(id, &entry.dna_hash, &format!("{:?}", entry.layer), &tx_hash).await?;
+                    row.entry = Some(entry);
+                }
+                Err(e) if single => anyhow::bail!("Archive failed: {}", e),
+                Err(e) => row.error = Some(e),
+            }
+        }
+
+        if table && single {
+            match &row.entry {
+                None => println!("\n🔍 Dry run: nothing archived"),
+                Some(entry) => {
                     println!("\n✅ Archive Success:");
                     println!("  Layer:           {} {}", entry.layer.emoji(), entry.layer.name());
-                    println!("  TX Hash:         {}", entry.tx_hash.unwrap_or_default());
+                    for failed in &entry.fallbacks {
+                        println!("  Failed Over:     {} after {} attempts: {}", failed.layer.name(), failed.attempts, failed.error);
+                    }
+                    println!("  TX Hash:         {}", entry.tx_hash.clone().unwrap_or_default());
+                    if let Some(anchor) = &entry.anchor {
+                        println!("  Anchored:        {} {}", anchor.layer.emoji(), anchor.tx_hash);
+                    }
                     println!("  DNA Hash:        {}", entry.dna_hash);
                 }
-                Err(e) => {
-                    println!("\n❌ Archive Failed: {}", e);
+            }
+        } else if table {
+            let tx = match (&row.entry, &row.error) {
+                (Some(entry), _) if entry.layer != layer => format!("{} (on {})", entry.tx_hash.clone().unwrap_or_default(), entry.layer.key()),
+                (Some(entry), _) => entry.tx_hash.clone().unwrap_or_default(),
+                (None, Some(e)) => format!("❌ {}", e),
+                (None, None) => "-".to_string(),
+            };
+            println!("  {:>6}  {:>5.2}  {:>13}  {:<22}  {:>16}  {}", id, genome.rna_signal(), genome.consciousness, layer.name(), cost_text, tx);
+        }
+        rows.push(row);
+    }
+
+    let archived = rows.iter().filter(|row| row.entry.is_some()).count();
+    let failed = rows.iter().filter(|row| row.error.is_some()).count();
+    if !table {
+        if single {
+            return emit(out, &rows[0]);
+        }
+        let mut totals: serde_json::Map<String, serde_json::Value> = totals.into_iter().map(|(unit, amount)| (unit, json!(amount))).collect();
+        if let Some(usd) = usd_total {
+            totals.insert("usd".into(), json!(usd));
+        }
+        return emit(out, &json!({ "dry_run": dry_run, "archived": archived, "failed": failed, "estimated_total": totals, "genomes": rows }));
+    }
+    if !single {
+        let mut totals: Vec<String> = totals.iter().map(|(unit, amount)| format!("{:.0} {}", amount, unit)).collect();
+        if let Some(usd) = usd_total {
+            totals.push(format!("${:.2}", usd));
+        }
+        println!("\n  Estimated Total: {}", totals.join(", "));
+        if dry_run {
+            println!("🔍 Dry run: nothing archived");
+        } else {
+            println!("{} Archived {} of {} ({} failed)", if failed == 0 { "✅" } else { "⚠️" }, archived, genomes.len(), failed);
+        }
+    }
+    Ok(())
+}
+
+async fn run_chain(command: ChainCommand, out: OutputFormat, session: &mut Session) -> anyhow::Result<()> {
+    match command {
+        ChainCommand::Validate { file } => {
+            let started = std::time::Instant::now();
+            let (chain, source) = match file {
+                Some(path) => (ProofOfConsciousness::import(&path)?, path.display().to_string()),
+                None => (open_chain(session).await?.1, "database".to_string()),
+            };
+            // Loading already replayed every block; check the result once more
+            if !chain.validate_chain_parallel() {
+                anyhow::bail!("Chain from {} failed validation", source);
+            }
+            if !out.is_table() {
+                return emit(out, &json!({
+                    "source": source,
+                    "valid": true,
+                    "height": chain.current_block_height,
+                    "tip": chain.latest_block().hash,
+                    "chain_id": chain.genesis.chain_id,
+                    "validators": chain.validators.len(),
+                    "elapsed_secs": started.elapsed().as_secs_f64(),
+                }));
+            }
+            println!("\n✅ Chain from {} is valid", source);
+            println!("  Height:          {}", chain.current_block_height);
+            println!("  Tip:             {}", chain.latest_block().hash);
+            println!("  Network:         {}", chain.genesis.chain_id);
+            println!("  Validators:      {}", chain.validators.len());
+            println!("  Checked In:      {:.2}s", started.elapsed().as_secs_f64());
+        }
+
+        ChainCommand::Show { height } => {
+            let (_, chain) = open_chain(session).await?;
+            let height = height.unwrap_or(chain.current_block_height);
+            let block = chain.chain.get(height as usize)
+                .ok_or_else(|| anyhow::anyhow!("No block #{} (height is {})", height, chain.current_block_height))?;
+            if !out.is_table() {
+                return emit(out, block);
+            }
+            print_block(block);
+        }
+
+        ChainCommand::Mine { genome_id } => {
+            let (database, mut chain) = open_chain(session).await?;
+            let genome = database.load_genome(genome_id).await?;
+            let proof = chain.submit(&genome).map_err(anyhow::Error::msg)?;
+            if out.is_table() {
+                println!("🧾 Genome #{} attested (c={}, threshold {})", genome_id, proof.consciousness, chain.min_consciousness);
+            }
+            let block = chain.mine_block().cloned()
+                .ok_or_else(|| anyhow::anyhow!("No block mined (see the warning above)"))?;
+            chain.save_chain(&database).await?;
+            if !out.is_table() {
+                return emit(out, &json!({ "proof": proof, "block": block, "min_consciousness": chain.min_consciousness }));
+            }
+            print_block(&block);
+            println!("\n  New threshold:   {}", chain.min_consciousness);
+        }
+
+        ChainCommand::Export { out: path, snapshot } => {
+            let (_, chain) = open_chain(session).await?;
+            if snapshot {
+                chain.export_snapshot(&path)?;
+            } else {
+                chain.export(&path)?;
+            }
+            if !out.is_table() {
+                return emit(out, &json!({ "path": path, "snapshot": snapshot, "height": chain.current_block_height }));
+            }
+            println!("📤 {} of height {} written to {}", if snapshot { "Snapshot" } else { "Chain" }, chain.current_block_height, path.display());
+        }
+
+        ChainCommand::Import { file } => {
+            let imported = ProofOfConsciousness::import(&file)?;
+            let (database, mut chain) = open_chain(session).await?;
+            let before = chain.current_block_height;
+            let report = chain.merge_blocks(imported.chain).map_err(anyhow::Error::msg)?;
+            chain.save_chain(&database).await?;
+            if !out.is_table() {
+                return emit(out, &json!({
+                    "file": file,
+                    "imported_height": imported.current_block_height,
+                    "report": report,
+                    "height_before": before,
+                    "height": chain.current_block_height,
+                }));
+            }
+
+            println!("\n📥 Merged {} (height {})", file.display(), imported.current_block_height);
+            println!("  Already Known:   {}", report.known);
+            println!("  Extended Tip:    {}", report.extended);
+            println!("  Side Chain:      {}", report.side_chain);
+            println!("  Reorgs:          {}", report.reorgs);
+            println!("  Orphans:         {}", report.orphans);
+            for (index, reason) in &report.rejected {
+                println!("  ❌ Block #{}:     {}", index, reason);
+            }
+            println!("  Height:          {} → {}", before, chain.current_block_height);
+        }
+
+        ChainCommand::Stats => {
+            let (_, chain) = open_chain(session).await?;
+            let status = chain.status();
+            let metrics = chain.metrics();
+            if !out.is_table() {
+                return emit(out, &json!({
+                    "status": status,
+                    "metrics": metrics,
+                    "finalized_height": chain.finalized_height(),
+                    "pruned_height": chain.pruned_height(),
+                }));
+            }
+            println!("\n⛓️  Chain {}", status.chain_id);
+            println!("  Height:          {}", status.current_block_height);
+            println!("  Tip:             {}", status.latest_hash);
+            println!("  Finalized:       #{}", chain.finalized_height());
+            println!("  Pruned Below:    #{}", chain.pruned_height());
+            println!("  Threshold:       {} ({})", status.min_consciousness, status.required_level);
+            println!("  Difficulty:      {}", status.difficulty);
+            println!("  Block Version:   {}", status.block_version);
+            println!("  Proofs:          {}", status.proofs_validated);
+            println!("  Rewards:         {:.6} RSM", status.total_rewards_distributed);
+            println!("  Validators:      {}", status.validators);
+            println!("  Avg Block Time:  {:.1}s (last {} blocks)", metrics.avg_block_interval_secs, metrics.window_blocks);
+            println!("  Hash Rate:       {:.1} H/s", metrics.hash_rate);
+            println!("  Consciousness:   {:.0} avg / {:.0} median per block", metrics.avg_consciousness_per_block, metrics.median_consciousness_per_block);
+        }
+    }
+    Ok(())
+}
+
+/// Dump of `tables` read from the database
+async fn dump_tables(database: &DivineDatabase, kind: DumpKind, tables: &[&str]) -> anyhow::Result<Dump> {
+    let mut rows = Vec::with_capacity(tables.len());
+    for table in tables {
+        rows.push((table.to_string(), database.dump_table(table).await?));
+    }
+    Dump::new(kind, chrono::Utc::now().timestamp(), rows).map_err(anyhow::Error::msg)
+}
+
+fn read_dump(path: &Path) -> anyhow::Result<Dump> {
+    let text = std::fs::read_to_string(path)?;
+    Dump::parse(&text).map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))
+}
+
+fn print_loaded(inserted: &[(String, u64)], dump: &Dump) {
+    for ((table, count), (_, rows)) in inserted.iter().zip(&dump.tables) {
+        println!("  {:<26} {:>8} of {:>8} rows", table, count, rows.len());
+    }
+}
+
+/// `{"table", "inserted", "rows"}` per table loaded from `dump`
+fn loaded_json(inserted: &[(String, u64)], dump: &Dump) -> Vec<serde_json::Value> {
+    inserted.iter().zip(&dump.tables)
+        .map(|((table, count), (_, rows))| json!({ "table": table, "inserted": count, "rows": rows.len() }))
+        .collect()
+}
+
+/// Write `text` readable by the owner only (backups hold credentials)
+fn write_private(path: &Path, text: &str) -> anyhow::Result<()> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options.open(path)?.write_all(text.as_bytes())?;
+    Ok(())
+}
+
+async fn run_db(command: DbCommand, out: OutputFormat, dry_run: bool, session: &mut Session) -> anyhow::Result<()> {
+    let database = session.database().await?;
+    match command {
+        DbCommand::Migrate => {
+            // Connecting already brought the schema up to date
+            if !out.is_table() {
+                return emit(out, &json!({ "tables": TABLES }));
+            }
+            println!("\n📦 Schema up to date ({} tables)", TABLES.len());
+        }
+
+        DbCommand::Stats => {
+            let (bytes, tables) = (database.database_size().await?, database.table_stats().await?);
+            if !out.is_table() {
+                return emit(out, &json!({ "bytes": bytes, "tables": tables }));
+            }
+            println!("\n📦 Database ({:.1} MiB)", bytes as f64 / (1024.0 * 1024.0));
+            for stats in tables {
+                println!("  {:<26} {:>10} rows  {:>10.1} KiB", stats.table, stats.rows, stats.bytes as f64 / 1024.0);
+            }
+        }
+
+        DbCommand::Export { format: DbExportFormat::Jsonl, table, out: path } => {
+            let tables = if table.is_empty() {
+                EXPORT_TABLES.to_vec()
+            } else {
+                table.iter().map(|t| check_table(t)).collect::<Result<Vec<_>, _>>().map_err(anyhow::Error::msg)?
+            };
+            let dump = dump_tables(&database, DumpKind::Export, &tables).await?;
+            match path {
+                Some(path) => {
+                    std::fs::write(&path, dump.to_jsonl())?;
+                    if !out.is_table() {
+                        return emit(out, &json!({ "path": path, "rows": dump.row_count(), "tables": dump.header.tables }));
+                    }
+                    println!("📤 {} rows from {} table(s) written to {}", dump.row_count(), dump.tables.len(), path.display());
                 }
+                None => print!("{}", dump.to_jsonl()),
             }
         }
 
-        Commands::Daemon { interval } => {
-            print_banner();
-            info!("🔄 Starting rotation daemon (interval: {} secs)...", interval);
+        DbCommand::Import { file } => {
+            let dump = read_dump(&file)?;
+            let inserted = match dry_run {
+                true => database.preview_dump(&dump).await?,
+                false => database.load_dump(&dump, false).await?,
+            };
+            if !out.is_table() {
+                return emit(out, &json!({ "file": file, "dry_run": dry_run, "tables": loaded_json(&inserted, &dump) }));
+            }
+            println!("\n📥 {} {}:", if dry_run { "Would import" } else { "Imported" }, file.display());
+            print_loaded(&inserted, &dump);
+            if dry_run {
+                println!("  🔎 Dry run: nothing was written");
+            }
+        }
 
-            let kernel = DivineKernel::new().await?;
-            kernel.start_rotation_daemon(interval);
+        DbCommand::Backup { out: path } => {
+            let dump = dump_tables(&database, DumpKind::Backup, TABLES).await?;
+            write_private(&path, &dump.to_jsonl())?;
+            if !out.is_table() {
+                return emit(out, &json!({ "path": path, "rows": dump.row_count(), "tables": dump.header.tables }));
+            }
+            println!("💾 Backup of {} rows written to {} (it holds credentials; keep it private)", dump.row_count(), path.display());
+        }
 
-            // Keep running
-            tokio::signal::ctrl_c().await?;
+        DbCommand::Restore { file, yes } => {
+            let dump = read_dump(&file)?;
+            if out.is_table() {
+                println!("\n♻️  Restore {} ({:?} of {}):", file.display(), dump.header.kind,
+                    chrono::DateTime::from_timestamp(dump.header.created_at, 0).map_or_else(|| dump.header.created_at.to_string(), |t| t.to_rfc3339()));
+                for (table, rows) in &dump.tables {
+                    println!("  {:<26} {:>8} rows", table, rows.len());
+                }
+            }
+            if dry_run {
+                // Restores empty each table first: every current row goes
+                let current = database.table_stats().await?;
+                let replaced: Vec<_> = dump.tables.iter().map(|(table, rows)| {
+                    let deleted = current.iter().find(|stats| &stats.table == table).map_or(0, |stats| stats.rows);
+                    (table, deleted, rows.len())
+                }).collect();
+                if !out.is_table() {
+                    let tables: Vec<_> = replaced.iter()
+                        .map(|(table, deleted, rows)| json!({ "table": table, "deleted": deleted, "inserted": rows }))
+                        .collect();
+                    return emit(out, &json!({ "file": file, "dry_run": true, "restored": false, "tables": tables }));
+                }
+                println!("\n🔎 Dry run, nothing was changed; the restore would replace:");
+                for (table, deleted, rows) in replaced {
+                    println!("  {:<26} {:>8} rows → {:>8}", table, deleted, rows);
+                }
+                return Ok(());
+            }
+            if !yes && !confirm(&format!("\nReplace everything in {} tables?", dump.tables.len()))? {
+                if !out.is_table() {
+                    return emit(out, &json!({ "file": file, "restored": false }));
+                }
+                println!("❌ Restore cancelled");
+                return Ok(());
+            }
+            let inserted = database.load_dump(&dump, true).await?;
+            if !out.is_table() {
+                return emit(out, &json!({ "file": file, "restored": true, "tables": loaded_json(&inserted, &dump) }));
+            }
+            println!("\n✅ Restored:");
+            print_loaded(&inserted, &dump);
+        }
+    }
+    Ok(())
+}
+
+/// Ask a `[y/N]` question on the terminal; anything but yes (or EOF) is no.
+/// Asked on stderr, so `--output json` stays parseable
+fn confirm(question: &str) -> anyhow::Result<bool> {
+    eprint!("{} [y/N] ", question);
+    std::io::stderr().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(confirmed(&answer))
+}
+
+/// Numbered words, four per row
+fn print_phrase(phrase: &str) {
+    println!("\n  Recovery phrase ({} words) — write it down, it is not shown again:\n", phrase.split_whitespace().count());
+    for (i, word) in phrase.split_whitespace().enumerate() {
+        print!("  {:>2}. {:<10}", i + 1, word);
+        if i % 4 == 3 {
+            println!();
         }
     }
+    println!();
+}
+
+/// Encrypt a new or recovered wallet to `path`, refusing to replace a file
+/// unless `force`
+fn create_wallet_file(path: &Path, wallet: &DivineWallet, force: bool, session: &mut Session) -> anyhow::Result<String> {
+    if path.exists() && !force {
+        anyhow::bail!("{} already exists (--force replaces it)", path.display());
+    }
+    let password = session.wallet_password(true)?;
+    wallet.save(path, &password)?;
+    Ok(password)
+}
+
+/// `POST /api/wallet/transfer` on `node`; the hash of the node's transaction
+async fn submit_node_transfer(node: &str, token: Option<&str>, from: &str, to: &str, amount: f64, memo: Option<&str>) -> anyhow::Result<String> {
+    let mut request = reqwest::Client::new()
+        .post(format!("{}/api/wallet/transfer", node))
+        .timeout(std::time::Duration::from_secs(30))
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(json!({ "from": from, "to": to, "amount_rsm": amount, "memo": memo }).to_string());
+    if let Some(token) = token {
+        request = request.bearer_auth(token);
+    }
+    let response = request.send().await.map_err(|e| anyhow::anyhow!("Node {} unreachable: {}", node, e))?;
+    let status = response.status();
+    let text = response.text().await?;
+    let body: serde_json::Value = serde_json::from_str(&text)
+        .map_err(|e| anyhow::anyhow!("Unexpected response from {} ({}): {}", node, status, e))?;
+    match body["data"]["transaction"]["hash"].as_str() {
+        Some(hash) if body["success"] == true => Ok(hash.to_string()),
+        _ => anyhow::bail!("Node refused the transfer: {}", body["error"].as_str().unwrap_or(status.as_str())),
+    }
+}
+
+async fn run_wallet(file: Option<PathBuf>, command: WalletCommand, out: OutputFormat, dry_run: bool, session: &mut Session) -> anyhow::Result<()> {
+    let path = DivineConfig::load()?.wallet_path(file);
+    let open = |session: &mut Session| -> anyhow::Result<(DivineWallet, String)> {
+        let password = session.wallet_password(false)?;
+        let wallet = DivineWallet::load(&path, &password)
+            .map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))?;
+        Ok((wallet, password))
+    };
+
+    match command {
+        WalletCommand::Create { words, force } => {
+            let (wallet, phrase) = DivineWallet::generate(words).map_err(anyhow::Error::msg)?;
+            create_wallet_file(&path, &wallet, force, session)?;
+            if !out.is_table() {
+                return emit(out, &json!({ "path": path, "address": wallet.address, "recovery_phrase": phrase }));
+            }
+            println!("\n👛 Wallet created at {}", path.display());
+            println!("  Address:         {}", wallet.address);
+            print_phrase(&phrase);
+        }
+
+        WalletCommand::Recover { phrase, force, scan } => {
+            let phrase = match phrase {
+                Some(phrase) => phrase,
+                None => rpassword::prompt_password("Recovery phrase: ")?,
+            };
+            let wallet = match scan {
+                true => {
+                    let (_, chain) = open_chain(session).await?;
+                    DivineWallet::recover_from_chain(&phrase, &chain)
+                }
+                false => DivineWallet::recover(&phrase),
+            }.map_err(anyhow::Error::msg)?;
+            create_wallet_file(&path, &wallet, force, session)?;
+            if !out.is_table() {
+                return emit(out, &json!({
+                    "path": path,
+                    "address": wallet.address,
+                    "accounts": wallet.accounts(),
+                    "validator_addresses": wallet.validator_addresses,
+                    "transactions": wallet.transactions.len(),
+                }));
+            }
+            println!("\n👛 Wallet recovered to {}", path.display());
+            println!("  Address:         {}", wallet.address);
+            if !scan {
+                println!("  (derive further addresses again with `wallet addresses --new`, or recover with --scan)");
+                return Ok(());
+            }
+            for account in wallet.accounts() {
+                println!("  {:>3}  {:<16} {} address(es)  {:.6} RSM", account.index, account.label, account.addresses, account.rsm_balance);
+            }
+            println!("  Validator Keys:  {}", wallet.validator_addresses.len());
+            println!("  History:         {} entries", wallet.transactions.len());
+        }
 
+        WalletCommand::Watch { addresses, force } => {
+            let addresses: Vec<&str> = addresses.iter().map(String::as_str).collect();
+            let wallet = DivineWallet::watch_only(&addresses).map_err(anyhow::Error::msg)?;
+            create_wallet_file(&path, &wallet, force, session)?;
+            let solana = wallet.watched().and_then(|watched| watched.solana.clone());
+            let ethereum = wallet.watched().and_then(|watched| watched.ethereum.clone());
+            if !out.is_table() {
+                return emit(out, &json!({ "path": path, "addresses": wallet.all_addresses(), "solana": solana, "ethereum": ethereum, "watch_only": true }));
+            }
+            println!("\n👀 Watch-only wallet created at {}", path.display());
+            for address in wallet.all_addresses() {
+                println!("  Address:         {}", address);
+            }
+            if let Some(address) = solana {
+                println!("  Solana:          {}", address);
+            }
+            if let Some(address) = ethereum {
+                println!("  Ethereum:        {}", address);
+            }
+            println!("  (no keys: balances only, nothing can be signed with it)");
+        }
+
+        WalletCommand::Balance { chain } => {
+            let (mut wallet, password) = open(session)?;
+            let on_chain = match chain {
+                true => {
+                    let balance = wallet.refresh_balance().await.map_err(anyhow::Error::msg)?;
+                    wallet.save(&path, &password)?;
+                    Some(balance)
+                }
+                false => None,
+            };
+            if !out.is_table() {
+                return emit(out, &json!({
+                    "address": wallet.address,
+                    "account": wallet.account_label,
+                    "spendable_rsm": wallet.rsm_balance,
+                    "staked_rsm": wallet.staked_rsm,
+                    "total_rsm": wallet.total_rsm(),
+                    "rewards_earned": wallet.rewards_earned,
+                    "staked_genomes": wallet.staked_genomes,
+                    "chain": on_chain,
+                }));
+            }
+            println!("\n👛 {} ({})", wallet.address, wallet.account_label);
+            println!("  Spendable:       {:.6} RSM", wallet.rsm_balance);
+            println!("  Staked:          {:.6} RSM", wallet.staked_rsm);
+            println!("  Total:           {:.6} RSM", wallet.total_rsm());
+            println!("  Rewards Earned:  {:.6} RSM", wallet.rewards_earned);
+            println!("  Staked Genomes:  {}", wallet.staked_genomes.len());
+            if let Some(balance) = on_chain {
+                println!("\n⛓️  {} on {}", balance.address, balance.network.name());
+                println!("  {:<16} {}", format!("{}:", balance.network.native_symbol()), wallet.native_display());
+                println!("  RSM:             {}", wallet.rsm_display());
+                for token in wallet.tokens() {
+                    println!("  Token:           {}", wallet.token_display(&token.mint).unwrap_or_default());
+                }
+            }
+        }
+
+        WalletCommand::Network { name, rpc_url, rsm_token } => {
+            let (mut wallet, password) = open(session)?;
+            let changed = name.is_some() || rpc_url.is_some() || rsm_token.is_some();
+            if let Some(name) = name {
+                wallet.set_network(Network::from_name(&name).map_err(anyhow::Error::msg)?);
+            }
+            if let Some(url) = rpc_url {
+                wallet.rpc_url = Some(url).filter(|url| !url.is_empty());
+            }
+            if let Some(mint) = rsm_token {
+                wallet.rsm_token = Some(mint).filter(|mint| !mint.is_empty());
+            }
+            if changed {
+                wallet.save(&path, &password)?;
+            }
+            let address = wallet.chain_address().ok();
+            if !out.is_table() {
+                return emit(out, &json!({
+                    "network": wallet.network,
+                    "rpc_url": wallet.rpc_url,
+                    "rsm_token": wallet.rsm_token,
+                    "address": address,
+                }));
+            }
+            println!("\n⛓️  Network:         {}", wallet.network.name());
+            println!("  RPC:             {}", wallet.rpc_url.as_deref().unwrap_or("(public endpoint)"));
+            println!("  RSM Token:       {}", wallet.rsm_token.as_deref().unwrap_or("(none)"));
+            println!("  Address:         {}", address.as_deref().unwrap_or("(needs a recovery phrase)"));
+        }
+
+        WalletCommand::Airdrop { amount } => {
+            let (mut wallet, password) = open(session)?;
+            let signature = wallet.request_airdrop(amount).await.map_err(anyhow::Error::msg)?;
+            wallet.save(&path, &password)?;
+            let address = wallet.chain_address().map_err(anyhow::Error::msg)?;
+            if !out.is_table() {
+                return emit(out, &json!({ "network": wallet.network, "address": address, "amount_sol": amount, "signature": signature }));
+            }
+            println!("\n🚰 Airdrop of {} SOL to {} on {}", amount, address, wallet.network.name());
+            println!("  Signature:       {}", signature);
+        }
+
+        WalletCommand::Tokens { add } => {
+            let (mut wallet, password) = open(session)?;
+            if let Some(mint) = add {
+                let token = wallet.add_token(&mint).await.map_err(anyhow::Error::msg)?;
+                wallet.save(&path, &password)?;
+                if out.is_table() {
+                    println!("\n🪙 Following {} ({} decimals) on {}", token.mint, token.decimals, token.network.name());
+                }
+            }
+            let tokens: Vec<_> = wallet.tokens().into_iter()
+                .map(|token| json!({ "mint": token.mint, "decimals": token.decimals, "balance": wallet.chain_balance.token(&token.mint) }))
+                .collect();
+            if !out.is_table() {
+                return emit(out, &json!({ "network": wallet.network, "rsm_token": wallet.rsm_token, "tokens": tokens }));
+            }
+            println!("\n🪙 Tokens on {} (balances as of `wallet balance --chain`)", wallet.network.name());
+            println!("  RSM              {}", wallet.rsm_token.as_deref().unwrap_or("(no mint set)"));
+            for token in wallet.tokens() {
+                println!("  {:<16} {}", wallet.token_display(&token.mint).unwrap_or_default(), token.mint);
+            }
+        }
+
+        WalletCommand::Send { to, amount, token, native, build: Some(file), .. } => {
+            let (wallet, _) = open(session)?;
+            let asset = match &token {
+                _ if native => Asset::Native,
+                Some(mint) => wallet.token_asset(mint).map_err(anyhow::Error::msg)?,
+                None => Asset::Rsm,
+            };
+            let unsigned = wallet.build_unsigned_transfer(asset, &to, amount).await.map_err(anyhow::Error::msg)?;
+            std::fs::write(&file, serde_json::to_string_pretty(&unsigned)?)?;
+            if !out.is_table() {
+                return emit(out, &json!({ "file": file, "transfer": unsigned.describe(), "signer": unsigned.signer }));
+            }
+            println!("\n📝 Unsigned: {}", unsigned.describe());
+            println!("  Signer:          {} (account {})", unsigned.signer, unsigned.account);
+            println!("  Written to:      {}", file.display());
+            println!("  (sign it with `wallet sign`, then send it with `wallet broadcast`)");
+        }
+
+        WalletCommand::Sign { file, out: signed_file, yes } => {
+            let (wallet, _) = open(session)?;
+            let unsigned: UnsignedTransfer = serde_json::from_str(&std::fs::read_to_string(&file)?)?;
+            if out.is_table() {
+                println!("\n✍️  Sign: {}", unsigned.describe());
+                println!("  Signer:          {} (account {})", unsigned.signer, unsigned.account);
+            }
+            if !yes && !confirm(&format!("\nSign {}?", unsigned.describe()))? {
+                if !out.is_table() {
+                    return emit(out, &json!({ "signed": false }));
+                }
+                println!("❌ Not signed");
+                return Ok(());
+            }
+            let signed = wallet.sign_transaction(&unsigned).map_err(anyhow::Error::msg)?;
+            std::fs::write(&signed_file, serde_json::to_string_pretty(&signed)?)?;
+            if !out.is_table() {
+                return emit(out, &json!({ "signed": true, "file": signed_file, "signature": signed.signature }));
+            }
+            println!("✅ Signed to {}", signed_file.display());
+        }
+
+        WalletCommand::Broadcast { file } => {
+            let (mut wallet, password) = open(session)?;
+            let signed: SignedTransfer = serde_json::from_str(&std::fs::read_to_string(&file)?)?;
+            if dry_run {
+                if !out.is_table() {
+                    return emit(out, &json!({ "transfer": signed.transfer.describe(), "dry_run": true, "id": null }));
+                }
+                println!("\n🔎 Dry run: {} was not sent", signed.transfer.describe());
+                return Ok(());
+            }
+            let id = wallet.broadcast_transaction(&signed).await.map_err(anyhow::Error::msg)?;
+            wallet.save(&path, &password)?;
+            if !out.is_table() {
+                return emit(out, &json!({ "transfer": signed.transfer.describe(), "dry_run": false, "id": id }));
+            }
+            println!("\n📡 Sent: {}", signed.transfer.describe());
+            println!("  Transaction:     {}", id);
+        }
+
+        WalletCommand::Send { to, amount, token, native, yes, .. } => {
+            let (mut wallet, password) = open(session)?;
+            let network = wallet.network;
+            let symbol = match native {
+                true => network.native_symbol().to_string(),
+                false => token.clone().unwrap_or_else(|| "RSM".to_string()),
+            };
+            let result = |signature: Option<&str>| json!({
+                "network": network,
+                "to": to,
+                "amount": amount,
+                "token": token,
+                "native": native,
+                "dry_run": dry_run,
+                "signature": signature,
+            });
+            if out.is_table() {
+                println!("\n📤 Send on {}", network.name());
+                println!("  To:              {}", to);
+                println!("  Amount:          {} {}", amount, symbol);
+            }
+            if dry_run {
+                if !out.is_table() {
+                    return emit(out, &result(None));
+                }
+                println!("🔎 Dry run: nothing was sent");
+                return Ok(());
+            }
+            if !yes && !confirm(&format!("\nSend {} {} to {} on {}?", amount, symbol, to, network.name()))? {
+                if !out.is_table() {
+                    return emit(out, &result(None));
+                }
+                println!("❌ Send cancelled");
+                return Ok(());
+            }
+            let signature = match &token {
+                _ if native => wallet.transfer_native(&to, amount).await,
+                Some(mint) => wallet.transfer_token(mint, &to, amount).await,
+                None => wallet.transfer_rsm(&to, amount).await,
+            }.map_err(anyhow::Error::msg)?;
+            wallet.save(&path, &password)?;
+            if !out.is_table() {
+                return emit(out, &result(Some(&signature)));
+            }
+            println!("✅ Sent: {}", signature);
+        }
+
+        WalletCommand::Transfer { to, amount, memo, api, token, yes } => {
+            let (mut wallet, password) = open(session)?;
+            wallet.check_transfer(&to, amount).map_err(anyhow::Error::msg)?;
+            let from = wallet.spending_address().to_string();
+            let node = match api {
+                Some(url) => url.trim_end_matches('/').to_string(),
+                None => DivineConfig::load()?.api_url,
+            };
+            let balance = wallet.rsm_balance;
+            let result = |sent: Option<(&str, &str)>| json!({
+                "from": from,
+                "to": to,
+                "amount_rsm": amount,
+                "memo": memo,
+                "node": node,
+                "balance_before_rsm": balance,
+                "balance_after_rsm": if sent.is_some() { balance - amount } else { balance },
+                "sent": sent.is_some(),
+                "dry_run": dry_run,
+                "tx_hash": sent.map(|(hash, _)| hash),
+                "record": sent.map(|(_, record)| record),
+            });
+
+            if out.is_table() {
+                println!("\n📤 Transfer");
+                println!("  From:            {}", from);
+                println!("  To:              {}", to);
+                println!("  Amount:          {:.6} RSM", amount);
+                if let Some(memo) = &memo {
+                    println!("  Memo:            {}", memo);
+                }
+                println!("  Node:            {}", node);
+                println!("  Balance After:   {:.6} RSM (was {:.6})", balance - amount, balance);
+            }
+            if dry_run {
+                if !out.is_table() {
+                    return emit(out, &result(None));
+                }
+                println!("🔎 Dry run: nothing was sent");
+                return Ok(());
+            }
+            if !yes && !confirm(&format!("\nSend {:.6} RSM to {}?", amount, to))? {
+                if !out.is_table() {
+                    return emit(out, &result(None));
+                }
+                println!("❌ Transfer cancelled");
+                return Ok(());
+            }
+            // The wallet file is only debited once the node has moved the funds
+            let hash = submit_node_transfer(&node, token.as_deref(), &from, &to, amount, memo.as_deref()).await?;
+            let record = wallet.record_transfer(&to, amount, memo.as_deref());
+            wallet.save(&path, &password)?;
+            if !out.is_table() {
+                return emit(out, &result(Some((&hash, &record))));
+            }
+            println!("✅ {} ({})", record, hash);
+        }
+
+        WalletCommand::History { limit } => {
+            let (wallet, _) = open(session)?;
+            if !out.is_table() {
+                let entries: Vec<&String> = wallet.transactions.iter().rev().take(limit).collect();
+                return emit(out, &json!({ "address": wallet.address, "total": wallet.transactions.len(), "entries": entries }));
+            }
+            println!("\n📜 History of {} ({} entries)", wallet.address, wallet.transactions.len());
+            for entry in wallet.transactions.iter().rev().take(limit) {
+                println!("  {}", entry);
+            }
+        }
+
+        WalletCommand::Addresses { new } => {
+            let (mut wallet, password) = open(session)?;
+            let created = match new {
+                true => {
+                    let address = wallet.new_address().map_err(anyhow::Error::msg)?;
+                    wallet.save(&path, &password)?;
+                    Some(address)
+                }
+                false => None,
+            };
+            let solana = wallet.solana_keypair(wallet.account_index).ok().map(|keypair| keypair.address());
+            let ethereum = wallet.eth_signer(wallet.account_index).ok().map(|signer| format_address(&signer.address));
+            if !out.is_table() {
+                return emit(out, &json!({ "new": created, "addresses": wallet.all_addresses(), "solana": solana, "ethereum": ethereum }));
+            }
+            if let Some(address) = created {
+                println!("\n✨ New address:    {}", address);
+            }
+            println!("\n👛 Addresses of {}:", path.display());
+            for (i, address) in wallet.all_addresses().iter().enumerate() {
+                println!("  {:>3}  {}", i, address);
+            }
+            if let Some(address) = solana {
+                println!("  sol  {}", address);
+            }
+            if let Some(address) = ethereum {
+                println!("  eth  {}", address);
+            }
+        }
+
+        WalletCommand::Accounts { create, switch } => {
+            let (mut wallet, password) = open(session)?;
+            let created = match &create {
+                Some(label) => Some(wallet.create_account(label).map_err(anyhow::Error::msg)?),
+                None => None,
+            };
+            if let Some(account) = &switch {
+                wallet.switch_account(account).map_err(anyhow::Error::msg)?;
+            }
+            if create.is_some() || switch.is_some() {
+                wallet.save(&path, &password)?;
+            }
+            if !out.is_table() {
+                return emit(out, &json!({ "new": created, "active": wallet.account_label, "accounts": wallet.accounts() }));
+            }
+            if let Some(account) = created {
+                println!("\n✨ New account:    {} ({})", account.label, account.address);
+            }
+            println!("\n👛 Accounts of {}:", path.display());
+            for account in wallet.accounts() {
+                println!("  {} {:>3}  {:<16} {}  {:.6} RSM", if account.active { "*" } else { " " },
+                         account.index, account.label, account.address, account.rsm_balance);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn run_config(command: ConfigCommand, out: OutputFormat) -> anyhow::Result<()> {
+    let path = config_path();
+    let mut file = ConfigFile::load(&path)?;
+
+    match command {
+        ConfigCommand::Show => {
+            let entries = DivineConfig::entries(&file, |name| std::env::var(name).ok()).map_err(anyhow::Error::msg)?;
+            if !out.is_table() {
+                return emit(out, &json!({ "file": path, "exists": path.exists(), "values": entries }));
+            }
+            println!("\n⚙️  Config {}{}", path.display(), if path.exists() { "" } else { " (not written yet)" });
+            println!("  {:<14} {:<48} SOURCE", "KEY", "VALUE");
+            for entry in &entries {
+                println!("  {:<14} {:<48} {}", entry.key, entry.value.as_deref().unwrap_or("(unset)"), entry.source);
+            }
+        }
+
+        ConfigCommand::Set { key, value } => {
+            file.set(&key, &value).map_err(anyhow::Error::msg)?;
+            file.save(&path)?;
+            let value = file.get(&key).map_err(anyhow::Error::msg)?;
+            // A variable still wins over the file
+            let key = config_key(&key).map_err(anyhow::Error::msg)?;
+            let overridden_by = [Some(key.env), key.legacy].into_iter().flatten()
+                .find(|name| std::env::var(name).is_ok_and(|v| !v.trim().is_empty()));
+            if !out.is_table() {
+                return emit(out, &json!({ "file": path, "key": key.name, "value": value, "overridden_by": overridden_by }));
+            }
+            match &value {
+                Some(value) => println!("\n✅ {} = {} in {}", key.name, value, path.display()),
+                None => println!("\n✅ {} removed from {}", key.name, path.display()),
+            }
+            if let Some(name) = overridden_by {
+                println!("  ⚠️  {} is set and overrides it", name);
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn run_watch(topics: Vec<WatchTopic>, filters: Vec<WatchFilter>, out: OutputFormat, session: &mut Session) -> anyhow::Result<()> {
+    let database = session.database().await?;
+    let mut listener = database.watch(&topics).await?;
+    if out.is_table() {
+        let names: Vec<_> = topics.iter().map(|topic| format!("{:?}", topic).to_lowercase()).collect();
+        eprintln!("\n👀 Watching {} (Ctrl-C to stop)", names.join(", "));
+    }
+
+    loop {
+        let notification = listener.recv().await?;
+        let Some(topic) = WatchTopic::ALL.into_iter().find(|topic| topic.channel() == notification.channel()) else {
+            continue;
+        };
+        let row: serde_json::Value = serde_json::from_str(notification.payload())?;
+        if !matches_all(&filters, &row) {
+            continue;
+        }
+        match out {
+            OutputFormat::Table => println!("{} {}", chrono::Local::now().format("%H:%M:%S"), topic.describe(&row)),
+            // One event per line, for `jq` and log shippers
+            OutputFormat::Json => println!("{}", serde_json::to_string(&WatchEvent { topic, row })?),
+            OutputFormat::Yaml => print!("---\n{}", out.render(&WatchEvent { topic, row }).map_err(anyhow::Error::msg)?),
+        }
+    }
+}
+
+async fn run_bench(suites: Vec<BenchSuite>, samples: usize, warmup_ms: u64, baseline: Option<PathBuf>, out: OutputFormat, session: &mut Session) -> anyhow::Result<()> {
+    let config = BenchConfig::new(warmup_ms, samples).map_err(anyhow::Error::msg)?;
+    // Read first: a bad path should not cost a full run
+    let baseline: Option<BenchReport> = match baseline {
+        Some(path) => Some(serde_json::from_str(&std::fs::read_to_string(&path)?)
+            .map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))?),
+        None => None,
+    };
+    let suites = if suites.is_empty() { BenchSuite::ALL.to_vec() } else { suites };
+
+    let mut results = Vec::new();
+    for suite in suites {
+        eprintln!("⏱️  {:?} ...", suite);
+        results.extend(match suite {
+            BenchSuite::Rotation => bench::rotation_benches(&config),
+            BenchSuite::Hashing => bench::hashing_benches(&config),
+            BenchSuite::Ttrl => bench::ttrl_benches(&config),
+            BenchSuite::Db => bench::db_benches(&config, &session.database().await?).await?,
+            BenchSuite::OrderBook => bench::order_book_benches(&config),
+        });
+    }
+    let mut report = BenchReport::new(&config, results);
+    if let Some(baseline) = &baseline {
+        report.changes = report.compare(baseline);
+    }
+    if !out.is_table() {
+        return emit(out, &report);
+    }
+
+    println!("\n⏱️  Divine AGI V{} benchmarks ({} samples, {} ms warmup):", report.version, report.samples, report.warmup_ms);
+    println!("  {:<24} {:>11} {:>11} {:>11} {:>11} {:>13} {:>10}", "BENCHMARK", "MEAN", "MEDIAN", "P95", "STDDEV", "OPS/S", "CHANGE");
+    for stats in &report.results {
+        let change = match report.changes.iter().find(|c| c.name == stats.name) {
+            Some(c) if c.significant => format!("{:+.1}%", c.change_pct),
+            Some(c) => format!("~{:+.1}%", c.change_pct),
+            None => "-".into(),
+        };
+        println!("  {:<24} {:>11} {:>11} {:>11} {:>11} {:>13.0} {:>10}", stats.name, format_duration(stats.mean_ns),
+                 format_duration(stats.median_ns), format_duration(stats.p95_ns), format_duration(stats.stddev_ns),
+                 stats.ops_per_sec, change);
+    }
+    if let Some(baseline) = &baseline {
+        println!("\n  Change against V{} from {} (~ within noise)", baseline.version,
+                 chrono::DateTime::from_timestamp(baseline.taken_at, 0).map(|t| t.format("%Y-%m-%d %H:%M").to_string()).unwrap_or_default());
+    }
+    Ok(())
+}
+
+fn run_keygen(words: usize, path: &Path, index: u32, recover: bool, yes: bool, out: OutputFormat, session: &mut Session) -> anyhow::Result<()> {
+    let (phrase, shown) = match recover {
+        true => (rpassword::prompt_password("Recovery phrase: ")?, false),
+        false => (generate_phrase(words).map_err(anyhow::Error::msg)?, true),
+    };
+    let key = derive_validator_key(&phrase, index).map_err(anyhow::Error::msg)?;
+
+    if shown && out.is_table() {
+        println!("\n🔑 Validator key #{} {}", index, key.public_key_hex());
+        print_phrase(&phrase);
+        // Nothing is written until two words are typed back
+        if !yes {
+            let words: Vec<&str> = phrase.split_whitespace().collect();
+            let first = rand::random::<usize>() % words.len();
+            let second = (first + 1 + rand::random::<usize>() % (words.len() - 1)) % words.len();
+            for position in [first.min(second), first.max(second)] {
+                eprint!("Word #{} of the phrase: ", position + 1);
+                std::io::stderr().flush()?;
+                let mut answer = String::new();
+                std::io::stdin().read_line(&mut answer)?;
+                if answer.trim().to_lowercase() != words[position] {
+                    anyhow::bail!("Word #{} does not match; nothing was written", position + 1);
+                }
+            }
+        }
+    }
+
+    let password = session.password("VALIDATOR_KEYSTORE_PASSWORD", "Keystore", true)?;
+    let keystore = Keystore::seal(&key, index, &password).map_err(anyhow::Error::msg)?;
+    keystore.save(path)?;
+
+    if !out.is_table() {
+        let mut value = json!({ "path": path, "public_key": keystore.public_key, "address": keystore.address, "index": index });
+        if shown {
+            value["recovery_phrase"] = json!(phrase);
+        }
+        return emit(out, &value);
+    }
+    println!("\n✅ Keystore written to {}", path.display());
+    println!("  Public key:      {}", keystore.public_key);
+    println!("  Reward address:  {}", keystore.address);
+    println!("  Key index:       {} (`keygen --recover --index {}` rotates to the next key)", index, index + 1);
+    println!("\n  Start a validator with VALIDATOR_KEYSTORE={} VALIDATOR_KEYSTORE_PASSWORD=<password>", path.display());
+    Ok(())
+}
+
+/// Every command of `file` (`-`: stdin) in this process and session; stops at
+/// the first failure unless `continue_on_error`
+async fn run_script(file: &Path, continue_on_error: bool, out: OutputFormat, dry_run: bool, session: &mut Session) -> anyhow::Result<()> {
+    let (name, text) = if file == Path::new("-") {
+        let mut text = String::new();
+        std::io::stdin().read_to_string(&mut text)?;
+        ("stdin".to_string(), text)
+    } else {
+        let text = std::fs::read_to_string(file).map_err(|e| anyhow::anyhow!("{}: {}", file.display(), e))?;
+        (file.display().to_string(), text)
+    };
+    let steps = parse_script(&text).map_err(|e| anyhow::anyhow!("{}: nothing was run\n{}", name, e))?;
+    let unpreviewable: Vec<String> = steps.iter()
+        .filter(|step| (dry_run || step.cli.dry_run) && step.cli.command.lacks_dry_run())
+        .map(|step| step.line.to_string())
+        .collect();
+    if !unpreviewable.is_empty() {
+        anyhow::bail!("{}: nothing was run; no --dry-run preview for line {}", name, unpreviewable.join(", "));
+    }
+
+    let total = steps.len();
+    let mut failed = Vec::new();
+    for (i, step) in steps.into_iter().enumerate() {
+        // Progress on stderr, so `--output json` results stay parseable
+        eprintln!("▶ {}:{}  {}", name, step.line, step.text);
+        let out = step.cli.output.unwrap_or(out);
+        // Boxed: `run` is what called us
+        if let Err(e) = Box::pin(run(step.cli.command, out, dry_run || step.cli.dry_run, session)).await {
+            if !continue_on_error {
+                anyhow::bail!("{}:{} `{}` failed after {} of {} commands: {}", name, step.line, step.text, i, total, e);
+            }
+            eprintln!("❌ {}:{}  {}", name, step.line, e);
+            failed.push(step.line.to_string());
+        }
+    }
+    if !failed.is_empty() {
+        anyhow::bail!("{}: {} of {} commands failed (line {})", name, failed.len(), total, failed.join(", "));
+    }
+    if out.is_table() {
+        println!("\n📜 {}: {} commands done", name, total);
+    }
     Ok(())
 }
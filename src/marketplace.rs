@@ -0,0 +1,122 @@
+//! Genome Marketplace V1 — stored genomes for sale in RSM
+//!
+//! A stored genome may belong to a wallet address, its `owner`: given when
+//! the genome is created (`POST /api/genomes` with `owner`) or claimed while
+//! it has none (`POST /api/genomes/:id/owner`). The owner lists it at a
+//! price in RSM; a genome has at most one active listing.
+//!
+//! Buying a listing is one database transaction: the listing is locked and
+//! marked sold, the genome is handed from the seller to the buyer, and the
+//! RSM transfer from buyer to seller is made last, inside it. A transfer the
+//! exchange refuses (balance, spending policy) rolls everything back, and a
+//! listing sells once however many buyers race for it.
+//!
+//! Active listings are browsed with the list query parameters
+//! (`listing::ListQuery`) over `LISTING_FIELDS`, the genome's current
+//! consciousness and T/G ratio included:
+//!
+//! ```text
+//! /api/market/listings?filter[consciousness_gte]=800&filter[tg_ratio_lte]=1.2&sort=price_rsm
+//! ```
+
+use std::collections::HashMap;
+
+use serde::{Serialize, Deserialize};
+
+use crate::exchange::Transaction;
+use crate::listing::{Listable, ListQuery};
+
+/// Filterable and sortable fields of listings (plus `id`)
+pub const LISTING_FIELDS: &[&str] = &["price_rsm", "consciousness", "tg_ratio", "created_at"];
+pub const MAX_WALLET_LEN: usize = 128;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ListingStatus {
+    Active,
+    Sold,
+    Cancelled,
+}
+
+impl ListingStatus {
+    pub fn name(self) -> &'static str {
+        match self {
+            ListingStatus::Active => "active",
+            ListingStatus::Sold => "sold",
+            ListingStatus::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [ListingStatus::Active, ListingStatus::Sold, ListingStatus::Cancelled].into_iter().find(|status| status.name() == name)
+    }
+}
+
+/// A genome offered for sale, with the genome's current profile
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Listing {
+    pub id: i64,
+    pub genome_id: i64,
+    pub seller: String,
+    pub price_rsm: f64,
+    pub status: ListingStatus,
+    /// Set once sold
+    pub buyer: Option<String>,
+    pub dna: String,
+    pub consciousness: u32,
+    pub tg_ratio: f64,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl Listable for Listing {
+    fn field(&self, name: &str) -> Option<f64> {
+        match name {
+            "price_rsm" => Some(self.price_rsm),
+            "consciousness" => Some(self.consciousness as f64),
+            "tg_ratio" => Some(self.tg_ratio),
+            "created_at" => Some(self.created_at as f64),
+            _ => None,
+        }
+    }
+}
+
+/// A sold listing and the payment that bought it
+#[derive(Debug, Clone, Serialize)]
+pub struct Purchase {
+    pub listing: Listing,
+    pub transaction: Transaction,
+}
+
+pub fn check_price(price_rsm: f64) -> Result<(), String> {
+    if !price_rsm.is_finite() || price_rsm <= 0.0 {
+        return Err(format!("Invalid price {} RSM", price_rsm));
+    }
+    Ok(())
+}
+
+/// Wallet addresses are non-empty, without whitespace and at most
+/// `MAX_WALLET_LEN` bytes
+pub fn check_wallet(wallet: &str) -> Result<(), String> {
+    if wallet.is_empty() || wallet.len() > MAX_WALLET_LEN || wallet.chars().any(char::is_whitespace) {
+        return Err(format!("Invalid wallet address {:?}", wallet));
+    }
+    Ok(())
+}
+
+/// Parse a `/api/market/listings` query
+pub fn parse_listing_query(params: &HashMap<String, String>) -> Result<ListQuery, String> {
+    ListQuery::parse(params, LISTING_FIELDS)
+}
+
+/// SQL column of a listing field (`l` is the listing, `g` its genome)
+pub fn listing_column(field: &str) -> Result<&'static str, String> {
+    match field {
+        "id" => Ok("l.id"),
+        "price_rsm" => Ok("l.price_rsm"),
+        "created_at" => Ok("l.created_at"),
+        "consciousness" => Ok("g.consciousness"),
+        "tg_ratio" => Ok("g.tg_ratio"),
+        _ => Err(format!("Cannot query listings on {}", field)),
+    }
+}
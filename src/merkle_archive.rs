@@ -0,0 +1,282 @@
+//! Merkle Archive Commitments V1 — one on-chain write for many genomes
+//!
+//! A genome queued for a Merkle archive waits in the `MerkleBatcher` as the
+//! leaf `SHA-256("DIVINE|leaf|" ‖ genome id (8 bytes, big-endian) ‖ DNA
+//! hash)`. Once the oldest leaf has waited `ARCHIVE_MERKLE_INTERVAL_SECS`
+//! (default 3600), or `ARCHIVE_MERKLE_MAX_LEAVES` (default 4096) wait, the
+//! root of their tree (`crypto::merkle_root`) is archived on
+//! `ARCHIVE_MERKLE_LAYER` (default `bitcoin`) exactly as a DNA hash would be:
+//! an OP_RETURN, calldata or a memo. A thousand genomes cost one transaction.
+//!
+//! Each genome then keeps, locally, an inclusion proof from its leaf to the
+//! root and the transaction that carries it (`prove_archived`). Anyone
+//! holding the proof checks it by recomputing the root and reading it back
+//! from the chain. Lightning cannot carry roots: a keysend is no lasting
+//! record. Nor can the storage layers, which keep genomes, not hashes.
+
+use std::collections::HashMap;
+
+use serde::{Serialize, Deserialize};
+use sha2::{Digest, Sha256};
+
+use crate::crypto::{merkle_proof, merkle_root, verify_merkle_proof, MerkleStep};
+use crate::genome::hash_genome_dna;
+use crate::multi_chain::BlockchainLayer;
+
+/// Domain separation of leaves from inner nodes and other hashes
+pub const MERKLE_LEAF_TAG: &[u8] = b"DIVINE|leaf|";
+pub const DEFAULT_MERKLE_INTERVAL_SECS: u64 = 3600;
+pub const DEFAULT_MERKLE_MAX_LEAVES: usize = 4096;
+/// How often the server checks whether a root is due
+pub const MERKLE_CHECK_SECS: u64 = 30;
+
+/// The leaf committing to `genome_id` having DNA hash `dna_hash`
+pub fn leaf_hash(genome_id: i64, dna_hash: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(MERKLE_LEAF_TAG);
+    hasher.update(genome_id.to_be_bytes());
+    hasher.update(dna_hash);
+    hasher.finalize().into()
+}
+
+fn parse_hash(hex_hash: &str) -> Result<[u8; 32], String> {
+    hex::decode(hex_hash).ok().and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| format!("Invalid 32-byte hash: {}", hex_hash))
+}
+
+/// Where and how often Merkle roots are committed
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MerkleConfig {
+    pub layer: BlockchainLayer,
+    pub interval_secs: u64,
+    pub max_leaves: usize,
+}
+
+impl MerkleConfig {
+    /// `ARCHIVE_MERKLE_LAYER`, `ARCHIVE_MERKLE_INTERVAL_SECS` and
+    /// `ARCHIVE_MERKLE_MAX_LEAVES`
+    pub fn from_env() -> Result<Self, String> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        let defaults = Self::default();
+        let config = Self {
+            layer: var("ARCHIVE_MERKLE_LAYER").map_or(Ok(defaults.layer), |name| BlockchainLayer::from_name(&name))?,
+            interval_secs: match var("ARCHIVE_MERKLE_INTERVAL_SECS") {
+                Some(v) => v.parse().map_err(|_| format!("Invalid ARCHIVE_MERKLE_INTERVAL_SECS: {}", v))?,
+                None => defaults.interval_secs,
+            },
+            max_leaves: match var("ARCHIVE_MERKLE_MAX_LEAVES") {
+                Some(v) => v.parse().map_err(|_| format!("Invalid ARCHIVE_MERKLE_MAX_LEAVES: {}", v))?,
+                None => defaults.max_leaves,
+            },
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.layer == BlockchainLayer::Lightning || self.layer.is_storage() {
+            return Err(format!("Merkle roots are committed on-chain; ARCHIVE_MERKLE_LAYER cannot be {}", self.layer.key()));
+        }
+        if self.interval_secs == 0 {
+            return Err("ARCHIVE_MERKLE_INTERVAL_SECS must be at least 1".into());
+        }
+        if self.max_leaves == 0 {
+            return Err("ARCHIVE_MERKLE_MAX_LEAVES must be at least 1".into());
+        }
+        Ok(())
+    }
+}
+
+impl Default for MerkleConfig {
+    fn default() -> Self {
+        Self { layer: BlockchainLayer::Bitcoin, interval_secs: DEFAULT_MERKLE_INTERVAL_SECS, max_leaves: DEFAULT_MERKLE_MAX_LEAVES }
+    }
+}
+
+/// A genome waiting for the next root
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingLeaf {
+    pub genome_id: i64,
+    pub dna_hash: String,
+    pub queued_at: i64,
+}
+
+/// A Merkle root written to a layer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MerkleCommitment {
+    pub id: u64,
+    pub root: String,
+    pub layer: BlockchainLayer,
+    pub tx_hash: String,
+    /// Genomes the root covers
+    pub leaves: usize,
+    /// Estimated fee of the one transaction, in USD
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost_usd: Option<f64>,
+    pub committed_at: i64,
+}
+
+/// Proof that a genome's DNA hash is in a Merkle root archived on a layer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchiveProof {
+    pub genome_id: i64,
+    pub dna_hash: String,
+    pub leaf: String,
+    pub leaf_index: usize,
+    pub leaf_count: usize,
+    pub path: Vec<MerkleStep>,
+    pub commitment_id: u64,
+    pub root: String,
+    pub layer: BlockchainLayer,
+    pub tx_hash: String,
+    pub committed_at: i64,
+}
+
+impl ArchiveProof {
+    /// Recompute the leaf from the genome id and DNA hash, then the root
+    /// from the leaf and sibling path
+    pub fn verify(&self) -> bool {
+        let (Ok(dna_hash), Ok(root)) = (parse_hash(&self.dna_hash), parse_hash(&self.root)) else {
+            return false;
+        };
+        let leaf = leaf_hash(self.genome_id, &dna_hash);
+        hex::encode(leaf) == self.leaf && verify_merkle_proof(&leaf, &self.path, &root)
+    }
+
+    /// Whether the proof holds and covers `dna` (the genome as stored now)
+    pub fn covers(&self, dna: &str) -> bool {
+        hex::encode(hash_genome_dna(dna)) == self.dna_hash && self.verify()
+    }
+}
+
+/// Where Merkle archives stand
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MerkleStatus {
+    pub config: MerkleConfig,
+    pub pending: usize,
+    /// When the waiting leaves' root is due, by interval
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_commit_at: Option<i64>,
+    pub commitments: usize,
+    /// Genomes with an inclusion proof
+    pub proven_genomes: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_commitment: Option<MerkleCommitment>,
+}
+
+/// Leaves waiting for a root, committed roots and each genome's latest proof
+#[derive(Debug, Clone, Default)]
+pub struct MerkleBatcher {
+    pub config: MerkleConfig,
+    pending: Vec<PendingLeaf>,
+    commitments: Vec<MerkleCommitment>,
+    proofs: HashMap<i64, ArchiveProof>,
+}
+
+impl MerkleBatcher {
+    pub fn new(config: MerkleConfig) -> Self {
+        Self { config, ..Self::default() }
+    }
+
+    /// Queue `genome_id` for the next root; a genome already waiting is
+    /// updated to `dna_hash` and keeps its place
+    pub fn queue(&mut self, genome_id: i64, dna_hash: [u8; 32], now: i64) -> PendingLeaf {
+        let dna_hash = hex::encode(dna_hash);
+        if let Some(leaf) = self.pending.iter_mut().find(|leaf| leaf.genome_id == genome_id) {
+            leaf.dna_hash = dna_hash;
+            return leaf.clone();
+        }
+        let leaf = PendingLeaf { genome_id, dna_hash, queued_at: now };
+        self.pending.push(leaf.clone());
+        leaf
+    }
+
+    pub fn pending(&self) -> &[PendingLeaf] {
+        &self.pending
+    }
+
+    pub fn commitments(&self) -> &[MerkleCommitment] {
+        &self.commitments
+    }
+
+    /// When the oldest waiting leaf's interval runs out
+    pub fn next_commit_at(&self) -> Option<i64> {
+        self.pending.iter().map(|leaf| leaf.queued_at).min()
+            .map(|oldest| oldest.saturating_add(self.config.interval_secs as i64))
+    }
+
+    /// Whether the waiting leaves are due for a root at `now`
+    pub fn is_due(&self, now: i64) -> bool {
+        self.pending.len() >= self.config.max_leaves || self.next_commit_at().is_some_and(|due| now >= due)
+    }
+
+    /// Leaves of the waiting genomes, in queue order
+    fn leaves(&self) -> Result<Vec<[u8; 32]>, String> {
+        self.pending.iter().map(|leaf| Ok(leaf_hash(leaf.genome_id, &parse_hash(&leaf.dna_hash)?))).collect()
+    }
+
+    /// Root over the waiting leaves, if any wait
+    pub fn root(&self) -> Option<[u8; 32]> {
+        if self.pending.is_empty() {
+            return None;
+        }
+        self.leaves().ok().map(|leaves| merkle_root(&leaves))
+    }
+
+    /// Id the next commitment gets
+    pub fn next_id(&self) -> u64 {
+        self.commitments.len() as u64 + 1
+    }
+
+    /// Record that `root` landed as `tx_hash` on `layer`: the waiting
+    /// leaves become a commitment and each genome gets its proof
+    pub fn committed(&mut self, root: [u8; 32], layer: BlockchainLayer, tx_hash: &str, cost_usd: Option<f64>, now: i64) -> Result<MerkleCommitment, String> {
+        let leaves = self.leaves()?;
+        if leaves.is_empty() || merkle_root(&leaves) != root {
+            return Err("The Merkle root no longer matches the waiting leaves".into());
+        }
+        let commitment = MerkleCommitment {
+            id: self.next_id(),
+            root: hex::encode(root),
+            layer,
+            tx_hash: tx_hash.to_string(),
+            leaves: leaves.len(),
+            cost_usd,
+            committed_at: now,
+        };
+        for (index, pending) in std::mem::take(&mut self.pending).into_iter().enumerate() {
+            let path = merkle_proof(&leaves, index).ok_or("Leaf outside the tree")?;
+            self.proofs.insert(pending.genome_id, ArchiveProof {
+                genome_id: pending.genome_id,
+                dna_hash: pending.dna_hash,
+                leaf: hex::encode(leaves[index]),
+                leaf_index: index,
+                leaf_count: leaves.len(),
+                path,
+                commitment_id: commitment.id,
+                root: commitment.root.clone(),
+                layer,
+                tx_hash: commitment.tx_hash.clone(),
+                committed_at: now,
+            });
+        }
+        self.commitments.push(commitment.clone());
+        Ok(commitment)
+    }
+
+    /// Proof of `genome_id`'s most recently committed DNA hash
+    pub fn prove(&self, genome_id: i64) -> Option<&ArchiveProof> {
+        self.proofs.get(&genome_id)
+    }
+
+    pub fn status(&self) -> MerkleStatus {
+        MerkleStatus {
+            config: self.config,
+            pending: self.pending.len(),
+            next_commit_at: self.next_commit_at(),
+            commitments: self.commitments.len(),
+            proven_genomes: self.proofs.len(),
+            last_commitment: self.commitments.last().cloned(),
+        }
+    }
+}
@@ -1,21 +1,66 @@
 //! Multi-Chain Archivation V15 — Lightning Network Swarm + Mission Control
 //!
 //! Layers:
-//! - Lightning (Rot0/Rot90): Dynamic fast layer, keysend broadcast
-//! - Solana (Rot90): Fast on-chain layer
-//! - Ethereum (Rot180): Balanced layer
+//! - Lightning (Rot0/Rot90): Dynamic fast layer, keysend broadcast (through
+//!   LND with the `lightning` feature, see `lightning`)
+//! - Solana (Rot90): Fast on-chain layer (memo transactions paid by the
+//!   wallet's Solana key once configured, see `solana`; each entry then
+//!   tracks its slot and commitment)
+//! - Ethereum (Rot180): Balanced layer (EIP-1559 transactions to the genome
+//!   registry once configured, see `ethereum`)
 //! - Bitcoin (Rot180): Immortal OP_RETURN layer
+//! - IPFS, Arweave: content-addressed storage of the whole genome rather
+//!   than its hash, chosen explicitly, never by `select_layer`; the
+//!   document can be anchored on Bitcoin or Ethereum, see `storage`
 //!
 //! Mission Control: Probabilistic pathfinding with learning
+//!
+//! Policy: an `ArchivePolicy` (default `ThresholdPolicy`) ranks the layers
+//! for each genome from its consciousness, T/G ratio, payload costs and
+//! learned probabilities, see `archive_policy`
+//!
+//! Batches: many genomes archived in the background, one after another,
+//! with per-genome layer assignments and tx references tracked as they land
+//!
+//! Costs: `estimate_cost` prices an archive at fixed reference rates
+//! (`BITCOIN_FEE_RATE_SAT_VB` etc.), in the layer's own unit, and in USD
+//! (and RSM) once the price oracle has quoted the layer's coin. An
+//! `ArchiveBudget` caps the USD a genome's archive and a day's archives may
+//! spend; layer selection and failover pass over layers that would break it
+//!
+//! Merkle archives: genomes queued with `queue_merkle` share one on-chain
+//! write, their tree's root, and each keeps an inclusion proof
+//! (`prove_archived`), see `merkle_archive`
+//!
+//! Verification: an archived entry is re-checked against its own DNA hash,
+//! the tx reference format of its layer and the genome as stored now, and
+//! its transaction is read back (`restore_hash_from_chain`) to extract the
+//! hash it embeds. Real layers are asked over their node's API; simulated
+//! archives are recorded in a `MockChain` as their layer would carry them
+//! (OP_RETURN script, calldata, memo, keysend record), so they read back the
+//! same way
 
 use std::collections::HashMap;
 use sha2::{Sha256, Digest};
 use serde::{Serialize, Deserialize};
-use tracing::{info, warn};
+use tracing::{debug, info, warn};
 use chrono::Utc;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 
+use crate::archive_policy::{ArchivePolicy, ArchiveRequest, LayerOption, ThresholdPolicy};
+use crate::ethereum::{archive_calldata, decode_archive_calldata, EthArchiveMode, EthArchiveTx, EthereumLayer, CALLDATA_TAG, NO_ETHEREUM};
 use crate::genome::{Genome, hash_genome_dna};
+use crate::merkle_archive::{ArchiveProof, MerkleBatcher, MerkleCommitment, MerkleConfig, PendingLeaf};
+#[cfg(feature = "lightning")]
+use crate::lightning::{LndClient, LndConfig};
+use crate::lightning::{parse_payment_hash, ChannelHealth, KeysendPayload, KeysendPayment, PaymentStatus, PaymentUpdate, ReceivedKeysend, DEFAULT_KEYSEND_SAT, GENOME_HASH_RECORD, MAX_TRACKED_PAYMENTS, NO_LND};
+use crate::oracle::{Pair, ReferencePrice};
 use crate::rotation::Rot180;
+use crate::solana::{archive_memo, decode_archive_memo, is_signature, SolanaConfirmation, SolanaLayer, NO_SOLANA};
+use crate::spv::{BitcoinChain, SpvConfig, SpvProof, SpvVerification};
+use crate::swarm::{probe_all, Swarm, SwarmStatus};
+use crate::storage::{arweave_id, cid_digest, content_digest, ipfs_cid, is_arweave_id, ContentStorage, StorageAnchor, StorageConfig, StoredGenome, ARWEAVE_WINSTON_PER_BYTE};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BlockchainLayer {
@@ -23,6 +68,8 @@ pub enum BlockchainLayer {
     Solana,      // Fast on-chain
     Ethereum,    // Balanced
     Bitcoin,     // Immortal OP_RETURN
+    Ipfs,        // Content-addressed genome document
+    Arweave,     // Permanent genome document
 }
 
 impl BlockchainLayer {
@@ -32,6 +79,59 @@ impl BlockchainLayer {
             Self::Solana => "Solana (fast)",
             Self::Ethereum => "Ethereum (balanced)",
             Self::Bitcoin => "Bitcoin (immortal)",
+            Self::Ipfs => "IPFS (content)",
+            Self::Arweave => "Arweave (permanent)",
+        }
+    }
+
+    /// The layers archives are selected among: each carries a DNA hash
+    pub const ALL: [BlockchainLayer; 4] = [Self::Lightning, Self::Solana, Self::Ethereum, Self::Bitcoin];
+    /// The layers that store the genome itself, only used when asked for
+    pub const STORAGE: [BlockchainLayer; 2] = [Self::Ipfs, Self::Arweave];
+
+    pub fn is_storage(&self) -> bool {
+        Self::STORAGE.contains(self)
+    }
+
+    /// Lowercase name, as `from_name` accepts it
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::Lightning => "lightning",
+            Self::Solana => "solana",
+            Self::Ethereum => "ethereum",
+            Self::Bitcoin => "bitcoin",
+            Self::Ipfs => "ipfs",
+            Self::Arweave => "arweave",
+        }
+    }
+
+    /// `lightning`, `solana`, `ethereum`, `bitcoin`, `ipfs` or `arweave`
+    /// (any case)
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "lightning" | "ln" => Ok(Self::Lightning),
+            "solana" | "sol" => Ok(Self::Solana),
+            "ethereum" | "eth" => Ok(Self::Ethereum),
+            "bitcoin" | "btc" => Ok(Self::Bitcoin),
+            "ipfs" => Ok(Self::Ipfs),
+            "arweave" | "ar" => Ok(Self::Arweave),
+            _ => Err(format!("Unknown layer {} (expected lightning, solana, ethereum, bitcoin, ipfs or arweave)", name)),
+        }
+    }
+
+    /// Whether `tx` has the shape this layer's archiver produces: keysend
+    /// payment hashes (`ln_` + 64 hex, comma-separated; archives from before
+    /// real keysends have 32), a raw CIDv1 on IPFS, an Arweave transaction
+    /// id, or a 32-byte tx hash (on Solana also a base58 transaction
+    /// signature)
+    pub fn valid_tx_reference(&self, tx: &str) -> bool {
+        let hex_of = |s: &str, len: usize| s.len() == len && s.bytes().all(|b| b.is_ascii_hexdigit());
+        match self {
+            Self::Lightning => tx.split(',').all(|hash| hash.strip_prefix("ln_").is_some_and(|h| hex_of(h, 64) || hex_of(h, 32))),
+            Self::Solana if is_signature(tx) => true,
+            Self::Ipfs => cid_digest(tx).is_some(),
+            Self::Arweave => is_arweave_id(tx),
+            _ => tx.strip_prefix("0x").is_some_and(|h| hex_of(h, 64)),
         }
     }
 
@@ -41,12 +141,185 @@ impl BlockchainLayer {
             Self::Solana => "🟣",
             Self::Ethereum => "🔷",
             Self::Bitcoin => "🟠",
+            Self::Ipfs => "📦",
+            Self::Arweave => "🗄️",
+        }
+    }
+
+    /// Coin the layer's fees are paid in, and its size in the fee unit
+    /// (`None` for the storage layers: the oracle does not price AR)
+    pub fn fee_coin(&self) -> Option<(Pair, f64)> {
+        match self {
+            Self::Lightning | Self::Bitcoin => Some((Pair::BtcUsd, 1e-8)), // sat
+            Self::Ethereum => Some((Pair::EthUsd, 1e-9)),                  // gwei
+            Self::Solana => Some((Pair::SolUsd, 1e-9)),                    // lamports
+            Self::Ipfs | Self::Arweave => None,
+        }
+    }
+}
+
+/// Routing fee assumed per swarm node reached by keysend
+pub const LIGHTNING_BASE_FEE_SAT: f64 = 1.0;
+pub const BITCOIN_FEE_RATE_SAT_VB: f64 = 10.0;
+/// Size of an OP_RETURN transaction without its data
+pub const BITCOIN_TX_BASE_VBYTES: usize = 150;
+/// Data an OP_RETURN output carries
+pub const BITCOIN_OP_RETURN_MAX_BYTES: usize = 80;
+pub const ETHEREUM_GAS_PRICE_GWEI: f64 = 20.0;
+pub const SOLANA_SIGNATURE_FEE_LAMPORTS: f64 = 5000.0;
+
+/// Expected fee of one archive, in the layer's unit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchiveCost {
+    pub layer: BlockchainLayer,
+    pub payload_bytes: usize,
+    pub amount: f64,
+    /// `sat`, `gwei`, `lamports` or `winston` (`free` on IPFS, pinned on
+    /// one's own node)
+    pub unit: String,
+    /// `amount` at the oracle's reference price, when it has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usd: Option<f64>,
+    /// `usd` in RSM at the oracle's RSM/USD price
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rsm: Option<f64>,
+}
+
+/// USD caps on archive fees: per archive of a genome, and per UTC day.
+/// Only layers the oracle prices are held to them
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ArchiveBudget {
+    pub per_genome_usd: Option<f64>,
+    pub daily_usd: Option<f64>,
+}
+
+impl ArchiveBudget {
+    /// `ARCHIVE_BUDGET_PER_GENOME_USD` and `ARCHIVE_BUDGET_DAILY_USD`, both
+    /// unlimited when unset
+    pub fn from_env() -> Result<Self, String> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        let usd = |name: &str| var(name).map(|v| v.parse::<f64>().map_err(|_| format!("Invalid {}: {}", name, v))).transpose();
+        let budget = Self { per_genome_usd: usd("ARCHIVE_BUDGET_PER_GENOME_USD")?, daily_usd: usd("ARCHIVE_BUDGET_DAILY_USD")? };
+        budget.validate()?;
+        Ok(budget)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, cap) in [("per-genome", self.per_genome_usd), ("daily", self.daily_usd)] {
+            if let Some(cap) = cap {
+                if !cap.is_finite() || cap <= 0.0 {
+                    return Err(format!("The {} archive budget must be a positive USD amount, not {}", name, cap));
+                }
+            }
         }
+        Ok(())
+    }
+}
+
+/// Estimated USD archives have spent on one UTC day
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct DailySpend {
+    /// Days since the epoch
+    pub day: i64,
+    pub usd: f64,
+}
+
+impl DailySpend {
+    /// Spent on the day of `now`
+    pub fn on(&self, now: i64) -> f64 {
+        if self.day == utc_day(now) { self.usd } else { 0.0 }
+    }
+
+    pub fn add(&mut self, usd: f64, now: i64) {
+        *self = Self { day: utc_day(now), usd: self.on(now) + usd };
+    }
+}
+
+fn utc_day(timestamp: i64) -> i64 {
+    timestamp.div_euclid(86_400)
+}
+
+/// The budget and what today's archives have spent of it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetStatus {
+    pub per_genome_usd: Option<f64>,
+    pub daily_usd: Option<f64>,
+    /// Start of the current UTC day
+    pub day_start: i64,
+    pub spent_today_usd: f64,
+    /// What is left of `daily_usd`
+    pub remaining_today_usd: Option<f64>,
+}
+
+const OP_RETURN: u8 = 0x6a;
+
+/// The OP_RETURN output script archiving `dna_hash`: the `DIVINE` tag, then
+/// the hash, as one push
+pub fn op_return_script(dna_hash: [u8; 32]) -> Vec<u8> {
+    let data = [CALLDATA_TAG, &dna_hash[..]].concat();
+    [vec![OP_RETURN, data.len() as u8], data].concat()
+}
+
+/// The DNA hash an archive's OP_RETURN script carries
+pub fn decode_op_return(script: &[u8]) -> Option<[u8; 32]> {
+    let [OP_RETURN, len, data @ ..] = script else {
+        return None;
+    };
+    if *len as usize != data.len() {
+        return None;
     }
+    data.strip_prefix(CALLDATA_TAG)?.try_into().ok()
 }
 
+/// The DNA hash in `payload` as `layer` embeds it (a storage layer's
+/// payload is the genome document)
+pub fn embedded_hash(layer: BlockchainLayer, payload: &[u8]) -> Option<[u8; 32]> {
+    match layer {
+        BlockchainLayer::Lightning => payload.try_into().ok(),
+        BlockchainLayer::Bitcoin => decode_op_return(payload),
+        BlockchainLayer::Ethereum => decode_archive_calldata(payload),
+        BlockchainLayer::Solana => std::str::from_utf8(payload).ok().and_then(decode_archive_memo).map(|(_, hash)| hash),
+        BlockchainLayer::Ipfs | BlockchainLayer::Arweave => StoredGenome::from_bytes(payload).ok().map(|document| document.dna_hash()),
+    }
+}
+
+/// What simulated archives wrote, by tx reference (one per keysend on
+/// Lightning), so they read back like real ones
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MockChain {
+    txs: HashMap<String, Vec<u8>>,
+}
+
+impl MockChain {
+    pub fn record(&mut self, tx_reference: &str, payload: Vec<u8>) {
+        self.txs.insert(tx_reference.to_string(), payload);
+    }
+
+    /// The payload simulated transaction `tx_reference` embeds
+    pub fn payload(&self, tx_reference: &str) -> Option<&[u8]> {
+        self.txs.get(tx_reference).map(Vec::as_slice)
+    }
+
+    pub fn len(&self) -> usize {
+        self.txs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.txs.is_empty()
+    }
+}
+
+/// Genomes accepted by one archive batch
+pub const MAX_ARCHIVE_BATCH: usize = 100;
+
+/// Finished batches kept for polling before the oldest are dropped
+pub const MAX_FINISHED_BATCHES: usize = 64;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChainArchiveEntry {
+    /// 1-based position in the archive log
+    #[serde(default)]
+    pub id: u64,
     pub genome_id: i64,
     pub dna_hash: String,
     pub dna_string: String,
@@ -55,8 +328,134 @@ pub struct ChainArchiveEntry {
     pub layer: BlockchainLayer,
     pub tx_hash: Option<String>,
     pub timestamp: i64,
+    /// Slot and commitment of a real Solana archive
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confirmation: Option<SolanaConfirmation>,
+    /// Tries `layer` took, the last one landing
+    #[serde(default = "one_attempt")]
+    pub attempts: u32,
+    /// Layers that failed every attempt before `layer`, in the order tried
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallbacks: Vec<LayerFailure>,
+    /// Estimated fee in USD, counted against the daily budget
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost_usd: Option<f64>,
+    /// Where a storage layer's document is anchored on-chain
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anchor: Option<StorageAnchor>,
+    /// A Bitcoin archive's inclusion proof, once fetched
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spv: Option<SpvProof>,
+}
+
+fn one_attempt() -> u32 {
+    1
+}
+
+/// A layer's archive transaction, as it landed
+struct SentArchive {
+    tx_hash: String,
+    confirmation: Option<SolanaConfirmation>,
+    anchor: Option<StorageAnchor>,
+    attempts: u32,
 }
 
+impl SentArchive {
+    fn tx(tx_hash: String) -> Self {
+        Self { tx_hash, confirmation: None, anchor: None, attempts: 1 }
+    }
+}
+
+/// A Merkle archive proof with its checks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofVerification {
+    pub proof: ArchiveProof,
+    /// The leaf and sibling path lead to the proof's root
+    pub path_valid: bool,
+    /// The root its transaction carries, when it could be read back
+    pub chain_root: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain_error: Option<String>,
+    pub chain_root_matches: bool,
+    /// Whether the proof covers the genome as stored now
+    pub current_dna_matches: Option<bool>,
+}
+
+/// A layer an archive gave up on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayerFailure {
+    pub layer: BlockchainLayer,
+    /// 0 when the layer was over budget and never tried
+    pub attempts: u32,
+    /// The last attempt's error
+    pub error: String,
+}
+
+pub const DEFAULT_ARCHIVE_ATTEMPTS: u32 = 3;
+pub const DEFAULT_RETRY_DELAY_MS: u64 = 500;
+/// Backoff stops doubling here
+pub const MAX_RETRY_DELAY_MS: u64 = 8_000;
+
+/// Attempts per layer before an archive fails over, and the backoff
+/// between them
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    pub attempts: u32,
+    /// Wait before the first retry; doubled for each one after
+    pub base_delay_ms: u64,
+}
+
+impl RetryPolicy {
+    /// `ARCHIVE_RETRY_ATTEMPTS` (default 3) and `ARCHIVE_RETRY_DELAY_MS`
+    /// (default 500)
+    pub fn from_env() -> Result<Self, String> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        let policy = Self {
+            attempts: match var("ARCHIVE_RETRY_ATTEMPTS") {
+                Some(v) => v.parse().map_err(|_| format!("Invalid ARCHIVE_RETRY_ATTEMPTS: {}", v))?,
+                None => DEFAULT_ARCHIVE_ATTEMPTS,
+            },
+            base_delay_ms: match var("ARCHIVE_RETRY_DELAY_MS") {
+                Some(v) => v.parse().map_err(|_| format!("Invalid ARCHIVE_RETRY_DELAY_MS: {}", v))?,
+                None => DEFAULT_RETRY_DELAY_MS,
+            },
+        };
+        policy.validate()?;
+        Ok(policy)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.attempts == 0 {
+            return Err("ARCHIVE_RETRY_ATTEMPTS must be at least 1".into());
+        }
+        Ok(())
+    }
+
+    /// Wait before retry `retry` (1 = the first): the base delay doubled
+    /// per earlier retry, capped at `MAX_RETRY_DELAY_MS`
+    pub fn delay(&self, retry: u32) -> std::time::Duration {
+        let doubled = self.base_delay_ms.saturating_mul(1u64 << retry.saturating_sub(1).min(32));
+        std::time::Duration::from_millis(doubled.min(MAX_RETRY_DELAY_MS))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self { attempts: DEFAULT_ARCHIVE_ATTEMPTS, base_delay_ms: DEFAULT_RETRY_DELAY_MS }
+    }
+}
+
+/// Success probability of a pair nothing is known about
+pub const MC_APRIORI_PROBABILITY: f64 = 0.5;
+/// Pseudo-observations the apriori counts as, so single outcomes move
+/// the estimate without swinging it to either bound
+pub const MC_APRIORI_WEIGHT: f64 = 2.0;
+/// Learned probabilities stay within these bounds
+pub const MC_MIN_PROBABILITY: f64 = 0.01;
+pub const MC_MAX_PROBABILITY: f64 = 0.99;
+/// Mission Control source for the archiver's own layer outcomes
+pub const MC_LAYER_SOURCE: &str = "archiver";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MissionControlPair {
     pub from_pubkey: String,
@@ -67,6 +466,16 @@ pub struct MissionControlPair {
     pub last_failure_time: Option<i64>,
     pub last_amount_msat: u64,
     pub probability: f64,
+    /// Time-decayed outcome weights the probability is estimated from
+    #[serde(default)]
+    pub success_weight: f64,
+    #[serde(default)]
+    pub failure_weight: f64,
+    /// When the weights were last decayed
+    #[serde(default)]
+    pub updated_at: i64,
+    #[serde(default)]
+    pub last_failure_reason: Option<String>,
 }
 
 impl MissionControlPair {
@@ -79,43 +488,69 @@ impl MissionControlPair {
             last_success_time: None,
             last_failure_time: None,
             last_amount_msat: 0,
-            probability: 0.5, // Apriori
+            probability: MC_APRIORI_PROBABILITY,
+            success_weight: 0.0,
+            failure_weight: 0.0,
+            updated_at: 0,
+            last_failure_reason: None,
         }
     }
 
-    /// Bayesian-like update on success
-    pub fn record_success(&mut self, amount_msat: u64) {
+    /// Apriori-weighted estimate from outcome weights decayed to `now`:
+    /// `(W·apriori + successes) / (W + successes + failures)`, bounded
+    pub fn probability_at(&self, half_life_secs: i64, now: i64) -> f64 {
+        let factor = decay_factor(now - self.updated_at, half_life_secs);
+        let (successes, failures) = (self.success_weight * factor, self.failure_weight * factor);
+        let estimate = (MC_APRIORI_WEIGHT * MC_APRIORI_PROBABILITY + successes) / (MC_APRIORI_WEIGHT + successes + failures);
+        estimate.clamp(MC_MIN_PROBABILITY, MC_MAX_PROBABILITY)
+    }
+
+    /// Decay the outcome weights to `now`, so old results count less and
+    /// the pair drifts back toward the apriori
+    pub fn decay(&mut self, half_life_secs: i64, now: i64) {
+        let factor = decay_factor(now - self.updated_at, half_life_secs);
+        self.success_weight *= factor;
+        self.failure_weight *= factor;
+        self.updated_at = self.updated_at.max(now);
+        self.probability = self.probability_at(half_life_secs, now);
+    }
+
+    pub fn report_success(&mut self, half_life_secs: i64, now: i64) {
+        self.decay(half_life_secs, now);
         self.success_count += 1;
-        self.last_success_time = Some(Utc::now().timestamp());
-        self.last_amount_msat = amount_msat;
-        
-        // Increase probability
-        self.probability = (self.probability + 0.1).min(0.99);
+        self.success_weight += 1.0;
+        self.last_success_time = Some(now);
+        self.probability = self.probability_at(half_life_secs, now);
     }
 
-    /// Bayesian-like update on failure
-    pub fn record_failure(&mut self, amount_msat: u64) {
+    pub fn report_failure(&mut self, reason: &str, half_life_secs: i64, now: i64) {
+        self.decay(half_life_secs, now);
         self.failure_count += 1;
-        self.last_failure_time = Some(Utc::now().timestamp());
-        self.last_amount_msat = amount_msat;
-        
-        // Sharp decrease
-        self.probability = (self.probability * 0.5).max(0.01);
+        self.failure_weight += 1.0;
+        self.last_failure_time = Some(now);
+        self.last_failure_reason = Some(reason.to_string());
+        self.probability = self.probability_at(half_life_secs, now);
     }
 
     /// Time decay - failures "забываются"
     pub fn apply_time_decay(&mut self, half_life_secs: i64) {
-        if let Some(last_fail) = self.last_failure_time {
-            let elapsed = Utc::now().timestamp() - last_fail;
-            if elapsed > half_life_secs {
-                // Recover toward apriori (0.5)
-                let recovery = (elapsed as f64 / half_life_secs as f64) * 0.1;
-                self.probability = (self.probability + recovery).min(0.5);
-            }
-        }
+        self.decay(half_life_secs, Utc::now().timestamp());
     }
 }
 
+/// Weight left of an outcome `elapsed` seconds old
+fn decay_factor(elapsed: i64, half_life_secs: i64) -> f64 {
+    if elapsed <= 0 || half_life_secs <= 0 {
+        return 1.0;
+    }
+    0.5f64.powf(elapsed as f64 / half_life_secs as f64)
+}
+
+/// First 12 characters of a pubkey or layer name, for logs
+fn short(key: &str) -> &str {
+    key.get(..12).unwrap_or(key)
+}
+
 #[derive(Debug, Clone)]
 pub struct MissionControl {
     pairs: HashMap<(String, String), MissionControlPair>,
@@ -140,14 +575,18 @@ impl MissionControl {
         self.pairs.entry(key).or_insert_with(|| MissionControlPair::new(from, to))
     }
 
-    pub fn record_success(&mut self, from: &str, to: &str, amount_msat: u64) {
-        self.get_pair(from, to).record_success(amount_msat);
-        info!("⚡ MC: SUCCESS {} → {} | amt {} msat", &from[..12], &to[..12], amount_msat);
+    pub fn report_success(&mut self, from: &str, to: &str) {
+        let half_life = self.half_life_secs;
+        let pair = self.get_pair(from, to);
+        pair.report_success(half_life, Utc::now().timestamp());
+        info!("⚡ MC: SUCCESS {} → {} | p={:.2}", short(from), short(to), pair.probability);
     }
 
-    pub fn record_failure(&mut self, from: &str, to: &str, amount_msat: u64) {
-        self.get_pair(from, to).record_failure(amount_msat);
-        warn!("⚡ MC: FAILURE {} → {} | amt {} msat", &from[..12], &to[..12], amount_msat);
+    pub fn report_failure(&mut self, from: &str, to: &str, reason: &str) {
+        let half_life = self.half_life_secs;
+        let pair = self.get_pair(from, to);
+        pair.report_failure(reason, half_life, Utc::now().timestamp());
+        warn!("⚡ MC: FAILURE {} → {} | p={:.2} | {}", short(from), short(to), pair.probability, reason);
     }
 
     pub fn get_probability(&mut self, from: &str, to: &str) -> f64 {
@@ -158,6 +597,13 @@ impl MissionControl {
         pair.probability
     }
 
+    /// Decayed probability of a pair without recording it; the apriori
+    /// for pairs never reported on
+    pub fn probability(&self, from: &str, to: &str) -> f64 {
+        self.pairs.get(&(from.to_string(), to.to_string()))
+            .map_or(MC_APRIORI_PROBABILITY, |pair| pair.probability_at(self.half_life_secs, Utc::now().timestamp()))
+    }
+
     pub fn reset(&mut self) {
         self.pairs.clear();
         info!("⚡ MC: RESET - all pairs cleared");
@@ -179,6 +625,7 @@ impl MissionControl {
             total_failures,
             avg_probability,
             half_life_secs: self.half_life_secs,
+            layers: Vec::new(),
         }
     }
 }
@@ -196,14 +643,95 @@ pub struct MissionControlStats {
     pub total_failures: u64,
     pub avg_probability: f64,
     pub half_life_secs: i64,
+    /// Learned archive success probability of each layer
+    #[serde(default)]
+    pub layers: Vec<LayerReliability>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LayerReliability {
+    pub layer: BlockchainLayer,
+    pub probability: f64,
+    pub successes: u64,
+    pub failures: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_failure_reason: Option<String>,
 }
 
 pub struct MultiChainArchiver {
-    pub swarm_pubkeys: Vec<String>,
+    /// Lightning nodes each archive is keysent to, with their health
+    pub swarm: Swarm,
     pub blinded_routes: HashMap<String, Vec<u8>>,
     pub mission_control: MissionControl,
+    /// Attempts and backoff per layer before failing over
+    pub retry: RetryPolicy,
+    /// Ranks the layers each archive starts on and fails over to
+    pub policy: Box<dyn ArchivePolicy>,
+    /// Fee caps `select_layer` and every archive respect
+    pub budget: ArchiveBudget,
+    pub spend: DailySpend,
+    /// Genomes waiting for the next Merkle root, and their proofs once in one
+    pub merkle: MerkleBatcher,
     pub own_pubkey: String,
     pub archives: Vec<ChainArchiveEntry>,
+    /// USD per coin, from the price oracle
+    pub reference_prices: HashMap<Pair, f64>,
+    /// Keysends sent, oldest first (at most `MAX_TRACKED_PAYMENTS`)
+    pub payments: Vec<KeysendPayment>,
+    /// Keysends go through LND when set, else they are simulated
+    #[cfg(feature = "lightning")]
+    pub lnd: Option<LndClient>,
+    /// Ethereum archives are real transactions when set, else simulated
+    pub ethereum: Option<EthereumLayer>,
+    /// Solana archives are real memo transactions when set, else simulated
+    pub solana: Option<SolanaLayer>,
+    /// IPFS node and Arweave gateway, and where stored documents are anchored
+    pub storage: ContentStorage,
+    /// Where SPV proofs of Bitcoin archives come from, and the simulated chain
+    pub bitcoin: BitcoinChain,
+    /// Payloads of simulated archives, read back by verification
+    pub mock_chain: MockChain,
+    /// Whether simulated keysends fail, and how
+    pub simulated_failures: SimulatedFailures,
+}
+
+/// How simulated keysends fail
+#[derive(Debug, Clone)]
+pub enum SimulatedFailures {
+    /// With one minus Mission Control's probability, drawn from this generator
+    Random(Box<StdRng>),
+    /// Never: every simulated keysend lands
+    Never,
+}
+
+impl SimulatedFailures {
+    /// Random failures from a generator seeded with `seed`, the same ones every run
+    pub fn seeded(seed: u64) -> Self {
+        SimulatedFailures::Random(Box::new(StdRng::seed_from_u64(seed)))
+    }
+
+    /// Seeded from `LN_SIMULATION_SEED` when set, else from entropy
+    pub fn from_env() -> Result<Self, String> {
+        match std::env::var("LN_SIMULATION_SEED") {
+            Ok(seed) => seed.trim().parse().map(Self::seeded)
+                .map_err(|_| format!("LN_SIMULATION_SEED must be an integer, got {:?}", seed)),
+            Err(_) => Ok(SimulatedFailures::Random(Box::new(StdRng::from_entropy()))),
+        }
+    }
+
+    /// Whether a keysend that succeeds with `probability` lands this time
+    pub fn lands(&mut self, probability: f64) -> bool {
+        match self {
+            SimulatedFailures::Random(rng) => rng.gen::<f64>() < probability,
+            SimulatedFailures::Never => true,
+        }
+    }
+}
+
+impl Default for SimulatedFailures {
+    fn default() -> Self {
+        SimulatedFailures::Random(Box::new(StdRng::from_entropy()))
+    }
 }
 
 impl MultiChainArchiver {
@@ -211,18 +739,12 @@ impl MultiChainArchiver {
         let own_pubkey = std::env::var("LN_NODE_PUBKEY")
             .unwrap_or_else(|_| "02divine_node_pubkey_placeholder".to_string());
 
-        // Load swarm from env
-        let swarm_list = std::env::var("LN_SWARM_PUBKEYS").unwrap_or_default();
-        let mut swarm_pubkeys: Vec<String> = swarm_list
-            .split(',')
-            .filter(|s| !s.is_empty())
-            .map(|s| s.trim().to_string())
-            .collect();
-        
-        // Add self
-        if !swarm_pubkeys.contains(&own_pubkey) {
-            swarm_pubkeys.push(own_pubkey.clone());
-        }
+        // Load swarm from env, self included
+        let now = Utc::now().timestamp();
+        let swarm = Swarm::from_env(&own_pubkey, now).unwrap_or_else(|e| {
+            warn!("⚡ Swarm nodes ignored: {}", e);
+            Swarm::from_pubkeys([own_pubkey.clone()], now)
+        });
 
         // Load blinded routes
         let blinded_config = std::env::var("LN_BLINDED_ROUTES").unwrap_or_default();
@@ -237,51 +759,322 @@ impl MultiChainArchiver {
             }
         }
 
+        #[cfg(feature = "lightning")]
+        let lnd = match LndConfig::from_env().and_then(|config| config.map(LndClient::connect).transpose()) {
+            Ok(lnd) => lnd,
+            Err(e) => {
+                warn!("⚡ LND disabled, keysends simulated: {}", e);
+                None
+            }
+        };
+
+        let ethereum = EthereumLayer::from_env().unwrap_or_else(|e| {
+            warn!("🔷 Ethereum layer simulated: {}", e);
+            None
+        });
+
+        let solana = SolanaLayer::from_env().unwrap_or_else(|e| {
+            warn!("🟣 Solana layer simulated: {}", e);
+            None
+        });
+
+        let retry = RetryPolicy::from_env().unwrap_or_else(|e| {
+            warn!("⛓️ Archive retries at defaults: {}", e);
+            RetryPolicy::default()
+        });
+
+        let budget = ArchiveBudget::from_env().unwrap_or_else(|e| {
+            warn!("⛓️ Archive budget ignored: {}", e);
+            ArchiveBudget::default()
+        });
+
+        let merkle = MerkleConfig::from_env().unwrap_or_else(|e| {
+            warn!("🌳 Merkle archives at defaults: {}", e);
+            MerkleConfig::default()
+        });
+
+        let storage = StorageConfig::from_env().unwrap_or_else(|e| {
+            warn!("📦 Storage layers simulated: {}", e);
+            StorageConfig::default()
+        });
+
+        let spv = SpvConfig::from_env().unwrap_or_else(|e| {
+            warn!("🟠 SPV proofs from the simulated chain only: {}", e);
+            SpvConfig::default()
+        });
+
+        let simulated_failures = SimulatedFailures::from_env().unwrap_or_else(|e| {
+            warn!("⚡ Simulated keysends seeded from entropy: {}", e);
+            SimulatedFailures::default()
+        });
+
         info!("⚡ MultiChainArchiver V15 initialized");
         info!("   Own pubkey: {}...{}", &own_pubkey[..8], &own_pubkey[own_pubkey.len().saturating_sub(8)..]);
-        info!("   Swarm nodes: {}", swarm_pubkeys.len());
+        info!("   Swarm nodes: {} ({} with endpoints)", swarm.len(), swarm.endpoints().len());
         info!("   Blinded routes: {}", blinded_routes.len());
+        #[cfg(feature = "lightning")]
+        if let Some(lnd) = &lnd {
+            info!("   Keysends: LND at {}", lnd.config.url);
+        }
+        if let Some(ethereum) = &ethereum {
+            info!("   Ethereum: {} from {}", ethereum.config.network.name(), crate::ethereum::format_address(&ethereum.address()));
+        }
+        if let Some(solana) = &solana {
+            info!("   Solana: {} from {}", solana.config.cluster.name(), solana.address());
+        }
+        if let Some(url) = &storage.ipfs_api_url {
+            info!("   IPFS: node at {}", url);
+        }
+        if let Some(anchor) = storage.anchor {
+            info!("   Storage anchored on {}", anchor.name());
+        }
+        if let Some(url) = &spv.esplora_url {
+            info!("   SPV proofs: {} from {}", spv.network.name(), url);
+        }
 
         Self {
-            swarm_pubkeys,
+            swarm,
             blinded_routes,
             mission_control: MissionControl::new(),
+            retry,
+            policy: Box::new(ThresholdPolicy::default()),
+            budget,
+            spend: DailySpend::default(),
+            merkle: MerkleBatcher::new(merkle),
             own_pubkey,
             archives: Vec::new(),
+            reference_prices: HashMap::new(),
+            payments: Vec::new(),
+            #[cfg(feature = "lightning")]
+            lnd,
+            ethereum,
+            solana,
+            storage: ContentStorage::new(storage),
+            bitcoin: BitcoinChain::new(spv),
+            mock_chain: MockChain::default(),
+            simulated_failures,
         }
     }
 
-    /// Select layer based on T/G signal and consciousness
+    /// Layers in the order `genome`'s T/G signal and consciousness prefer
+    /// them under the default policy, see `ThresholdPolicy::preferences`
+    pub fn layer_preferences(genome: &Genome<Rot180>) -> Vec<BlockchainLayer> {
+        ThresholdPolicy::preferences(genome.consciousness, genome.rna_signal())
+    }
+
+    /// Learned probability that an archive to `layer` succeeds
+    pub fn layer_probability(&self, layer: BlockchainLayer) -> f64 {
+        self.mission_control.probability(MC_LAYER_SOURCE, layer.key())
+    }
+
+    /// What the policy decides `genome`'s archive from: each hash-carrying
+    /// layer with its cost, budget check and learned probability
+    pub fn archive_request(&self, genome: &Genome<Rot180>) -> ArchiveRequest {
+        let now = Utc::now().timestamp();
+        let layers = BlockchainLayer::ALL.into_iter().map(|layer| LayerOption {
+            layer,
+            cost: self.estimate_cost(layer, self.payload_bytes(genome, layer)),
+            probability: self.layer_probability(layer),
+            over_budget: self.check_budget(genome, layer, now).err(),
+        }).collect();
+        ArchiveRequest { genome_id: genome.db_id, consciousness: genome.consciousness, tg_ratio: genome.rna_signal(), layers }
+    }
+
+    /// The layers the policy ranks for `genome`, best first, scored
+    pub fn layer_ranking(&self, genome: &Genome<Rot180>) -> Vec<(BlockchainLayer, f64)> {
+        let request = self.archive_request(genome);
+        self.policy.rank(&request).into_iter()
+            .filter(|(layer, _)| request.layer(*layer).is_some())
+            .collect()
+    }
+
+    /// The layer the policy starts `genome`'s archive on. By default the
+    /// genome's own layer while it archives reliably and affordably, a
+    /// fallback once Mission Control has seen it fail or its fee is over
+    /// budget. Ethereum if the policy ranks no layer
     pub fn select_layer(&self, genome: &Genome<Rot180>) -> BlockchainLayer {
-        let signal = genome.rna_signal();
-        let consciousness = genome.consciousness;
-
-        if signal > 1.5 && consciousness > 600 {
-            BlockchainLayer::Lightning // High T → dynamic
-        } else if consciousness > 900 && signal < 0.6 {
-            BlockchainLayer::Bitcoin   // Ultra-genome + high G → immortal
-        } else if signal > 1.2 {
-            BlockchainLayer::Solana    // Moderate T → fast
-        } else {
-            BlockchainLayer::Ethereum  // Default → balanced
+        let request = self.archive_request(genome);
+        self.policy.select(&request)
+            .filter(|layer| request.layer(*layer).is_some())
+            .unwrap_or(BlockchainLayer::Ethereum)
+    }
+
+    /// Expected fee of writing `payload_bytes` to `layer`
+    pub fn estimate_cost(&self, layer: BlockchainLayer, payload_bytes: usize) -> ArchiveCost {
+        let (amount, unit) = match layer {
+            BlockchainLayer::Lightning => (self.broadcast_targets().len() as f64 * LIGHTNING_BASE_FEE_SAT, "sat"),
+            BlockchainLayer::Bitcoin => {
+                let vbytes = BITCOIN_TX_BASE_VBYTES + payload_bytes.min(BITCOIN_OP_RETURN_MAX_BYTES);
+                (vbytes as f64 * BITCOIN_FEE_RATE_SAT_VB, "sat")
+            }
+            // Intrinsic gas plus 16 gas per calldata byte
+            BlockchainLayer::Ethereum => ((21_000 + 16 * payload_bytes) as f64 * ETHEREUM_GAS_PRICE_GWEI, "gwei"),
+            BlockchainLayer::Solana => (SOLANA_SIGNATURE_FEE_LAMPORTS, "lamports"),
+            BlockchainLayer::Ipfs => (0.0, "free"),
+            BlockchainLayer::Arweave => (payload_bytes as f64 * ARWEAVE_WINSTON_PER_BYTE, "winston"),
+        };
+        let usd = layer.fee_coin()
+            .and_then(|(coin, scale)| self.reference_prices.get(&coin).map(|price| amount * scale * price));
+        let rsm = usd.zip(self.reference_prices.get(&Pair::RsmUsd)).map(|(usd, price)| usd / price);
+        ArchiveCost { layer, payload_bytes, amount, unit: unit.to_string(), usd, rsm }
+    }
+
+    /// The USD estimate of archiving `genome` to `layer`, with the anchor
+    /// of a storage layer's document (`None` if the oracle prices neither)
+    pub fn archive_usd(&self, genome: &Genome<Rot180>, layer: BlockchainLayer) -> Option<f64> {
+        let own = self.estimate_cost(layer, self.payload_bytes(genome, layer)).usd;
+        let anchor = self.storage.config.anchor.filter(|_| layer.is_storage())
+            .and_then(|anchor| self.estimate_cost(anchor, 32).usd);
+        match (own, anchor) {
+            (None, None) => None,
+            (own, anchor) => Some(own.unwrap_or(0.0) + anchor.unwrap_or(0.0)),
         }
     }
 
-    /// Archive genome to appropriate chain (simulation)
+    /// The USD estimate of archiving `genome` to `layer` (`None` if the
+    /// oracle has no price for its coin), or why it would break the budget
+    pub fn check_budget(&self, genome: &Genome<Rot180>, layer: BlockchainLayer, now: i64) -> Result<Option<f64>, String> {
+        let Some(usd) = self.archive_usd(genome, layer) else {
+            return Ok(None);
+        };
+        if let Some(cap) = self.budget.per_genome_usd.filter(|cap| usd > *cap) {
+            return Err(format!("{} costs ${:.4}, over the ${:.4} per-genome budget", layer.key(), usd, cap));
+        }
+        if let Some(cap) = self.budget.daily_usd {
+            let left = cap - self.spend.on(now);
+            if usd > left {
+                return Err(format!("{} costs ${:.4}, over the ${:.4} left of today's ${:.4} budget", layer.key(), usd, left.max(0.0), cap));
+            }
+        }
+        Ok(Some(usd))
+    }
+
+    pub fn budget_status(&self, now: i64) -> BudgetStatus {
+        let spent = self.spend.on(now);
+        BudgetStatus {
+            per_genome_usd: self.budget.per_genome_usd,
+            daily_usd: self.budget.daily_usd,
+            day_start: utc_day(now) * 86_400,
+            spent_today_usd: spent,
+            remaining_today_usd: self.budget.daily_usd.map(|cap| (cap - spent).max(0.0)),
+        }
+    }
+
+    /// Price archive costs in USD at the oracle's reference prices
+    pub fn set_reference_prices(&mut self, prices: &[ReferencePrice]) {
+        self.reference_prices = prices.iter().map(|price| (price.pair, price.price_usd)).collect();
+    }
+
+    /// Bytes an archive of `genome` writes: the keysend record on
+    /// Lightning, the genome document on the storage layers, the DNA
+    /// elsewhere
+    pub fn payload_bytes(&self, genome: &Genome<Rot180>, layer: BlockchainLayer) -> usize {
+        match layer {
+            BlockchainLayer::Lightning => archive_record(genome, Utc::now().timestamp()).len(),
+            BlockchainLayer::Ipfs | BlockchainLayer::Arweave => StoredGenome::new(genome).to_bytes().len(),
+            _ => genome.to_dna_string().len(),
+        }
+    }
+
+    /// Archive genome to the layer Mission Control ranks best for it,
+    /// failing over down the ranking
     pub async fn archive(&mut self, genome: &Genome<Rot180>) -> Result<ChainArchiveEntry, String> {
-        let layer = self.select_layer(genome);
+        self.archive_from(genome, self.select_layer(genome)).await
+    }
+
+    /// Archive genome to `layer` whatever its T/G ratio selects, retrying
+    /// with backoff but never on another layer
+    pub async fn archive_to(&mut self, genome: &Genome<Rot180>, layer: BlockchainLayer) -> Result<ChainArchiveEntry, String> {
+        let cost_usd = self.check_budget(genome, layer, Utc::now().timestamp())?;
+        match self.send_with_retries(genome, layer).await {
+            Ok(sent) => Ok(self.record_archive(genome, layer, sent, cost_usd, Vec::new())),
+            Err(failure) => Err(failure.error),
+        }
+    }
+
+    /// Archive genome to `layer`, retrying with backoff, then to the next
+    /// layer Mission Control ranks best for it until one takes the archive.
+    /// Layers over budget are passed over untried. The layers given up on
+    /// are the entry's `fallbacks`
+    pub async fn archive_from(&mut self, genome: &Genome<Rot180>, layer: BlockchainLayer) -> Result<ChainArchiveEntry, String> {
+        let mut fallbacks: Vec<LayerFailure> = Vec::new();
+        let mut layer = layer;
+        loop {
+            let failure = match self.check_budget(genome, layer, Utc::now().timestamp()) {
+                Err(error) => LayerFailure { layer, attempts: 0, error },
+                Ok(cost_usd) => match self.send_with_retries(genome, layer).await {
+                    Ok(sent) => return Ok(self.record_archive(genome, layer, sent, cost_usd, fallbacks)),
+                    Err(failure) => failure,
+                },
+            };
+            fallbacks.push(failure);
+            let next = self.layer_ranking(genome).into_iter()
+                .map(|(next, _)| next)
+                .find(|next| fallbacks.iter().all(|f| f.layer != *next));
+            let Some(next) = next else {
+                let failures: Vec<String> = fallbacks.iter()
+                    .map(|f| format!("{} ({} attempts): {}", f.layer.key(), f.attempts, f.error))
+                    .collect();
+                return Err(format!("Every layer failed: {}", failures.join("; ")));
+            };
+            match fallbacks[fallbacks.len() - 1].attempts {
+                0 => info!("⛓️ {} over budget, trying {}", layer.name(), next.name()),
+                attempts => warn!("⛓️ {} failed {} times, failing over to {}", layer.name(), attempts, next.name()),
+            }
+            layer = next;
+        }
+    }
+
+    /// Up to `retry.attempts` archives to `layer`, backing off between them
+    async fn send_with_retries(&mut self, genome: &Genome<Rot180>, layer: BlockchainLayer) -> Result<SentArchive, LayerFailure> {
+        let attempts = self.retry.attempts.max(1);
+        let mut attempt = 1;
+        loop {
+            match self.send(genome, layer).await {
+                Ok(sent) => return Ok(SentArchive { attempts: attempt, ..sent }),
+                Err(error) if attempt >= attempts => return Err(LayerFailure { layer, attempts, error }),
+                Err(error) => {
+                    let delay = self.retry.delay(attempt);
+                    debug!("{} Archive attempt {}/{} failed, retrying in {:?}: {}", layer.emoji(), attempt, attempts, delay, error);
+                    if !delay.is_zero() {
+                        tokio::time::sleep(delay).await;
+                    }
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    /// One archive to `layer`, reported to Mission Control
+    async fn send(&mut self, genome: &Genome<Rot180>, layer: BlockchainLayer) -> Result<SentArchive, String> {
+        let sent = match layer {
+            BlockchainLayer::Lightning => self.archive_lightning(genome).await.map(SentArchive::tx),
+            BlockchainLayer::Bitcoin => self.archive_bitcoin(genome).await.map(SentArchive::tx),
+            BlockchainLayer::Solana => self.archive_solana(genome).await
+                .map(|(tx_hash, confirmation)| SentArchive { confirmation, ..SentArchive::tx(tx_hash) }),
+            BlockchainLayer::Ethereum => self.archive_ethereum(genome).await.map(SentArchive::tx),
+            BlockchainLayer::Ipfs | BlockchainLayer::Arweave => self.archive_storage(genome, layer).await
+                .map(|(tx_hash, anchor)| SentArchive { anchor, ..SentArchive::tx(tx_hash) }),
+        };
+        match &sent {
+            Ok(_) => self.mission_control.report_success(MC_LAYER_SOURCE, layer.key()),
+            Err(e) => self.mission_control.report_failure(MC_LAYER_SOURCE, layer.key(), e),
+        }
+        sent
+    }
+
+    fn record_archive(&mut self, genome: &Genome<Rot180>, layer: BlockchainLayer, sent: SentArchive, cost_usd: Option<f64>, fallbacks: Vec<LayerFailure>) -> ChainArchiveEntry {
+        let SentArchive { tx_hash, confirmation, anchor, attempts } = sent;
+        let timestamp = Utc::now().timestamp();
+        if let Some(usd) = cost_usd {
+            self.spend.add(usd, timestamp);
+        }
         let dna = genome.to_dna_string();
         let dna_hash = hex::encode(hash_genome_dna(&dna));
         let tg_ratio = genome.rna_signal();
 
-        let tx_hash = match layer {
-            BlockchainLayer::Lightning => self.archive_lightning(genome).await?,
-            BlockchainLayer::Bitcoin => self.archive_bitcoin(genome).await?,
-            BlockchainLayer::Solana => self.archive_solana(genome).await?,
-            BlockchainLayer::Ethereum => self.archive_ethereum(genome).await?,
-        };
-
         let entry = ChainArchiveEntry {
+            id: self.archives.len() as u64 + 1,
             genome_id: genome.db_id.unwrap_or(0),
             dna_hash,
             dna_string: dna,
@@ -289,7 +1082,13 @@ impl MultiChainArchiver {
             tg_ratio,
             layer,
             tx_hash: Some(tx_hash.clone()),
-            timestamp: Utc::now().timestamp(),
+            timestamp,
+            confirmation,
+            attempts,
+            fallbacks,
+            cost_usd,
+            anchor,
+            spv: None,
         };
 
         self.archives.push(entry.clone());
@@ -300,44 +1099,62 @@ impl MultiChainArchiver {
             genome.consciousness, tg_ratio, tx_hash
         );
 
-        Ok(entry)
+        entry
     }
 
     async fn archive_lightning(&mut self, genome: &Genome<Rot180>) -> Result<String, String> {
-        let dna = genome.to_dna_string();
-        let custom_data = format!(
-            "DIVINE_GENOME|v15|id:{}|dna:{}|c:{}|tg:{:.3}|ts:{}",
-            genome.db_id.unwrap_or(0), dna, genome.consciousness,
-            genome.rna_signal(), Utc::now().timestamp()
-        );
+        let custom_data = archive_record(genome, Utc::now().timestamp());
+        let dna_hash = hash_genome_dna(&genome.to_dna_string());
+        let amount_sat = self.keysend_sat();
 
-        // Simulate keysend broadcast with Mission Control
-        let mut success_count = 0;
-        let mut hashes = Vec::new();
+        // Keysend broadcast with Mission Control
+        let mut sends = Vec::new();
+        for dest_pubkey in self.broadcast_targets() {
+            let prob = self.mission_control.get_probability(&self.own_pubkey, &dest_pubkey);
 
-        for dest_pubkey in &self.swarm_pubkeys.clone() {
-            let prob = self.mission_control.get_probability(&self.own_pubkey, dest_pubkey);
-            
             // Skip low-probability nodes (jamming protection)
             if prob < 0.3 {
-                warn!("⚡ Skipping low-probability node {}... (p={:.2})", &dest_pubkey[..12], prob);
+                warn!("⚡ Skipping low-probability node {}... (p={:.2})", short(&dest_pubkey), prob);
                 continue;
             }
+            self.mission_control.get_pair(&self.own_pubkey, &dest_pubkey).last_amount_msat = amount_sat as u64 * 1000;
+            sends.push((dest_pubkey, KeysendPayload::new(dna_hash, &custom_data), prob));
+        }
+        let attempted = sends.len();
 
-            // Simulate keysend (real impl would use LND gRPC)
-            let success = rand::random::<f64>() < prob;
-            
-            if success {
-                let fake_hash = self.generate_payment_hash(&custom_data, dest_pubkey);
-                hashes.push(fake_hash.clone());
-                self.mission_control.record_success(&self.own_pubkey, dest_pubkey, 0);
-                success_count += 1;
+        let mut hashes = Vec::new();
+        for (dest_pubkey, payload, update) in self.fan_out(sends).await {
+            let now = Utc::now().timestamp();
+            if update.status == PaymentStatus::Succeeded {
+                if !self.uses_lnd() {
+                    self.mock_chain.record(&payload.tx_reference(), payload.records[&GENOME_HASH_RECORD].clone());
+                }
+                hashes.push(payload.tx_reference());
+                self.mission_control.report_success(&self.own_pubkey, &dest_pubkey);
             } else {
-                self.mission_control.record_failure(&self.own_pubkey, dest_pubkey, 0);
+                let reason = update.failure_reason.clone().unwrap_or_else(|| format!("{:?}", update.status));
+                self.mission_control.report_failure(&self.own_pubkey, &dest_pubkey, &reason);
             }
+            self.swarm.record(&dest_pubkey, &update, now);
+
+            let mut payment = KeysendPayment {
+                payment_hash: hex::encode(payload.payment_hash),
+                dest_pubkey,
+                genome_id: genome.db_id.unwrap_or(0),
+                dna_hash: hex::encode(dna_hash),
+                amount_sat,
+                status: PaymentStatus::InFlight,
+                fee_msat: 0,
+                failure_reason: None,
+                simulated: !self.uses_lnd(),
+                created_at: now,
+                updated_at: now,
+            };
+            payment.apply(update, now);
+            self.track(payment);
         }
 
-        info!("⚡ Lightning broadcast: {}/{} nodes | MC updated", success_count, self.swarm_pubkeys.len());
+        info!("⚡ Lightning broadcast: {}/{} nodes ({} swarm) | MC updated", hashes.len(), attempted, self.swarm.len());
 
         if hashes.is_empty() {
             Err("All keysend failed".to_string())
@@ -346,34 +1163,377 @@ impl MultiChainArchiver {
         }
     }
 
-    async fn archive_bitcoin(&self, genome: &Genome<Rot180>) -> Result<String, String> {
-        // Simulate Bitcoin OP_RETURN
+    /// Swarm nodes a Lightning archive goes to: not unreachable, with
+    /// capacity for one keysend, and not this node when LND sends
+    pub fn broadcast_targets(&self) -> Vec<String> {
+        let amount_sat = self.keysend_sat();
+        self.swarm.nodes().iter()
+            .filter(|node| node.in_broadcast(amount_sat) && !(self.uses_lnd() && node.pubkey == self.own_pubkey))
+            .map(|node| node.pubkey.clone())
+            .collect()
+    }
+
+    /// Send every keysend at once through LND, or one by one when simulated;
+    /// outcomes in the order given
+    async fn fan_out(&mut self, sends: Vec<(String, KeysendPayload, f64)>) -> Vec<(String, KeysendPayload, PaymentUpdate)> {
+        #[cfg(feature = "lightning")]
+        if let Some(lnd) = &self.lnd {
+            let mut keysends = tokio::task::JoinSet::new();
+            for (index, (dest_pubkey, payload, _)) in sends.into_iter().enumerate() {
+                let mut lnd = lnd.clone();
+                keysends.spawn(async move {
+                    let update = lnd.keysend(&dest_pubkey, &payload).await.unwrap_or_else(PaymentUpdate::failed);
+                    (index, dest_pubkey, payload, update)
+                });
+            }
+            let mut outcomes = Vec::new();
+            while let Some(outcome) = keysends.join_next().await {
+                match outcome {
+                    Ok(outcome) => outcomes.push(outcome),
+                    Err(e) => warn!("⚡ Keysend task lost: {}", e),
+                }
+            }
+            outcomes.sort_by_key(|(index, ..)| *index);
+            return outcomes.into_iter().map(|(_, dest_pubkey, payload, update)| (dest_pubkey, payload, update)).collect();
+        }
+        let mut outcomes = Vec::new();
+        for (dest_pubkey, payload, probability) in sends {
+            let update = self.send_keysend(&dest_pubkey, &payload, probability).await;
+            outcomes.push((dest_pubkey, payload, update));
+        }
+        outcomes
+    }
+
+    /// Swarm nodes with their health, metrics and Mission Control probability
+    pub fn swarm_status(&self) -> SwarmStatus {
+        self.swarm.status(|pubkey| self.mission_control.probability(&self.own_pubkey, pubkey))
+    }
+
+    /// Take the results of dialing swarm endpoints: an unreachable node
+    /// counts as a failure in Mission Control
+    pub fn swarm_probed(&mut self, results: Vec<(String, Result<(), String>)>) -> SwarmStatus {
+        let now = Utc::now().timestamp();
+        for (pubkey, result) in results {
+            let Some(node) = self.swarm.get_mut(&pubkey) else { continue };
+            if let Err(e) = &result {
+                self.mission_control.report_failure(&self.own_pubkey, &pubkey, &format!("unreachable: {}", e));
+            }
+            node.probed(result, now);
+        }
+        let status = self.swarm_status();
+        info!("⚡ Swarm health: {} healthy, {} degraded, {} unreachable, {} unknown", status.healthy, status.degraded, status.unreachable, status.unknown);
+        status
+    }
+
+    /// Dial every swarm endpoint and update the nodes' health
+    pub async fn check_swarm_health(&mut self) -> SwarmStatus {
+        let results = probe_all(self.swarm.endpoints()).await;
+        self.swarm_probed(results)
+    }
+
+    /// Keysend through LND when connected; simulated keysends succeed with
+    /// Mission Control's `probability`, as `simulated_failures` draws them
+    async fn send_keysend(&mut self, dest_pubkey: &str, payload: &KeysendPayload, probability: f64) -> PaymentUpdate {
+        #[cfg(feature = "lightning")]
+        if let Some(lnd) = self.lnd.as_mut() {
+            return lnd.keysend(dest_pubkey, payload).await.unwrap_or_else(PaymentUpdate::failed);
+        }
+        debug!("⚡ Simulated keysend {} → {}", payload.tx_reference(), dest_pubkey);
+        if self.simulated_failures.lands(probability) {
+            PaymentUpdate::succeeded(0)
+        } else {
+            PaymentUpdate::failed("simulated keysend failure".into())
+        }
+    }
+
+    fn track(&mut self, payment: KeysendPayment) {
+        if self.payments.len() >= MAX_TRACKED_PAYMENTS {
+            self.payments.remove(0);
+        }
+        self.payments.push(payment);
+    }
+
+    /// Whether keysends go through LND
+    #[cfg(feature = "lightning")]
+    pub fn uses_lnd(&self) -> bool {
+        self.lnd.is_some()
+    }
+
+    #[cfg(not(feature = "lightning"))]
+    pub fn uses_lnd(&self) -> bool {
+        false
+    }
+
+    /// Sat paid to each swarm node per Lightning archive
+    pub fn keysend_sat(&self) -> i64 {
+        #[cfg(feature = "lightning")]
+        if let Some(lnd) = &self.lnd {
+            return lnd.config.amount_sat;
+        }
+        DEFAULT_KEYSEND_SAT
+    }
+
+    /// Keysends sent, newest first
+    pub fn recent_payments(&self, limit: usize) -> Vec<&KeysendPayment> {
+        self.payments.iter().rev().take(limit).collect()
+    }
+
+    /// A keysend by payment hash (hex, or an `ln_` tx reference), its state
+    /// refreshed from LND when it went through LND
+    pub async fn track_payment(&mut self, payment_hash: &str) -> Result<KeysendPayment, String> {
+        let hash = parse_payment_hash(payment_hash)?;
+        let index = self.payments.iter().rposition(|p| p.payment_hash == hex::encode(hash))
+            .ok_or_else(|| format!("Keysend {} not found", hex::encode(hash)))?;
+        #[cfg(feature = "lightning")]
+        if let (Some(lnd), false) = (self.lnd.as_mut(), self.payments[index].simulated) {
+            let update = lnd.track_payment(&hash).await?;
+            self.payments[index].apply(update, Utc::now().timestamp());
+        }
+        Ok(self.payments[index].clone())
+    }
+
+    /// A keysend archive this node received, read back from its invoice
+    pub async fn lookup_invoice(&self, payment_hash: &str) -> Result<ReceivedKeysend, String> {
+        let hash = parse_payment_hash(payment_hash)?;
+        #[cfg(feature = "lightning")]
+        if let Some(lnd) = &self.lnd {
+            return lnd.clone().lookup_invoice(&hash).await;
+        }
+        Err(format!("Looking up keysend {} {}", hex::encode(hash), NO_LND))
+    }
+
+    /// Ethereum archive transactions, newest first
+    pub fn ethereum_txs(&self, limit: usize) -> Result<Vec<&EthArchiveTx>, String> {
+        let ethereum = self.ethereum.as_ref().ok_or_else(|| format!("Ethereum archive tracking {}", NO_ETHEREUM))?;
+        Ok(ethereum.txs.iter().rev().take(limit).collect())
+    }
+
+    /// An Ethereum archive transaction with its receipt re-read
+    pub async fn track_ethereum_tx(&mut self, tx_hash: &str) -> Result<EthArchiveTx, String> {
+        let ethereum = self.ethereum.as_mut().ok_or_else(|| format!("Ethereum archive tracking {}", NO_ETHEREUM))?;
+        ethereum.refresh_tx(tx_hash, Utc::now().timestamp()).await
+    }
+
+    /// LND's sync state and channels, judged against one archive broadcast
+    pub async fn channel_health(&self) -> Result<ChannelHealth, String> {
+        #[cfg(feature = "lightning")]
+        if let Some(lnd) = &self.lnd {
+            let nodes = self.broadcast_targets().len() as i64;
+            let broadcast_sat = nodes * (lnd.config.amount_sat + lnd.config.fee_limit_sat);
+            return lnd.clone().channel_health(broadcast_sat).await;
+        }
+        Err(format!("Channel health {}", NO_LND))
+    }
+
+    async fn archive_bitcoin(&mut self, genome: &Genome<Rot180>) -> Result<String, String> {
+        // Simulate Bitcoin OP_RETURN, mined into the simulated chain
         let dna = genome.to_dna_string();
-        let fake_txid = self.generate_tx_hash(&dna, "btc");
-        info!("🟠 Bitcoin OP_RETURN: {} | DNA: {}", fake_txid, dna);
-        Ok(fake_txid)
+        let script = op_return_script(hash_genome_dna(&dna));
+        let txid = self.bitcoin.send_simulated(&script, Utc::now().timestamp())?;
+        self.mock_chain.record(&txid, script);
+        info!("🟠 Bitcoin OP_RETURN: {} | DNA: {}", txid, dna);
+        Ok(txid)
     }
 
-    async fn archive_solana(&self, genome: &Genome<Rot180>) -> Result<String, String> {
+    async fn archive_solana(&mut self, genome: &Genome<Rot180>) -> Result<(String, Option<SolanaConfirmation>), String> {
         let dna = genome.to_dna_string();
+        if let Some(solana) = self.solana.as_mut() {
+            let (signature, sent) = solana.archive(genome.db_id.unwrap_or(0), hash_genome_dna(&dna), Utc::now().timestamp()).await?;
+            return Ok((signature, Some(sent)));
+        }
         let fake_sig = self.generate_tx_hash(&dna, "sol");
+        self.mock_chain.record(&fake_sig, archive_memo(genome.db_id.unwrap_or(0), hash_genome_dna(&dna)).into_bytes());
         info!("🟣 Solana TX: {} | DNA: {}", fake_sig, dna);
-        Ok(fake_sig)
+        Ok((fake_sig, None))
+    }
+
+    /// Re-read the status of every Solana archive not yet finalized, failed
+    /// or expired; returns how many settled
+    pub async fn refresh_solana(&mut self, now: i64) -> Result<usize, String> {
+        let indexes: Vec<usize> = self.archives.iter().enumerate()
+            .filter(|(_, e)| e.confirmation.as_ref().is_some_and(|c| !c.is_settled()))
+            .map(|(i, _)| i)
+            .collect();
+        self.apply_solana_statuses(&indexes, now).await?;
+        Ok(indexes.iter().filter(|&&i| self.archives[i].confirmation.as_ref().is_some_and(SolanaConfirmation::is_settled)).count())
+    }
+
+    /// The Solana archive with signature `signature`, its status re-read
+    pub async fn track_solana(&mut self, signature: &str) -> Result<ChainArchiveEntry, String> {
+        if self.solana.is_none() {
+            return Err(format!("Solana archive tracking {}", NO_SOLANA));
+        }
+        let index = self.archives.iter()
+            .rposition(|e| e.confirmation.is_some() && e.tx_hash.as_deref() == Some(signature))
+            .ok_or_else(|| format!("Solana archive {} not found", signature))?;
+        self.apply_solana_statuses(&[index], Utc::now().timestamp()).await?;
+        Ok(self.archives[index].clone())
+    }
+
+    async fn apply_solana_statuses(&mut self, indexes: &[usize], now: i64) -> Result<(), String> {
+        let Some(solana) = self.solana.as_ref() else {
+            return Ok(());
+        };
+        if indexes.is_empty() {
+            return Ok(());
+        }
+        let signatures: Vec<String> = indexes.iter().map(|&i| self.archives[i].tx_hash.clone().unwrap_or_default()).collect();
+        let (block_height, statuses) = solana.statuses(&signatures).await?;
+        for (&i, status) in indexes.iter().zip(statuses) {
+            if let Some(confirmation) = self.archives[i].confirmation.as_mut() {
+                confirmation.apply(status, block_height, now);
+            }
+        }
+        Ok(())
     }
 
-    async fn archive_ethereum(&self, genome: &Genome<Rot180>) -> Result<String, String> {
+    async fn archive_ethereum(&mut self, genome: &Genome<Rot180>) -> Result<String, String> {
         let dna = genome.to_dna_string();
+        if let Some(ethereum) = self.ethereum.as_mut() {
+            return ethereum.archive(genome.db_id.unwrap_or(0), hash_genome_dna(&dna), Utc::now().timestamp()).await;
+        }
         let fake_hash = self.generate_tx_hash(&dna, "eth");
+        self.mock_chain.record(&fake_hash, archive_calldata(EthArchiveMode::Calldata, hash_genome_dna(&dna)));
         info!("🔷 Ethereum TX: {} | DNA: {}", fake_hash, dna);
         Ok(fake_hash)
     }
 
-    fn generate_payment_hash(&self, data: &str, dest: &str) -> String {
-        let mut hasher = Sha256::new();
-        hasher.update(data.as_bytes());
-        hasher.update(dest.as_bytes());
-        hasher.update(Utc::now().timestamp_nanos_opt().unwrap_or(0).to_le_bytes());
-        format!("ln_{}", hex::encode(&hasher.finalize()[..16]))
+    /// Store `genome`'s document on `layer` (IPFS through the node when
+    /// configured, else simulated) and anchor its digest if an anchor layer
+    /// is set; returns the CID or Arweave id and the anchor
+    async fn archive_storage(&mut self, genome: &Genome<Rot180>, layer: BlockchainLayer) -> Result<(String, Option<StorageAnchor>), String> {
+        let document = StoredGenome::new(genome).to_bytes();
+        let stored = match layer {
+            BlockchainLayer::Ipfs => self.storage.add_ipfs(&document).await?,
+            _ => None,
+        };
+        let reference = match stored {
+            Some(cid) => cid,
+            None => {
+                let reference = match layer {
+                    BlockchainLayer::Ipfs => ipfs_cid(&document),
+                    _ => arweave_id(&document),
+                };
+                self.mock_chain.record(&reference, document.clone());
+                info!("{} {} stored: {} | {} bytes", layer.emoji(), layer.name(), reference, document.len());
+                reference
+            }
+        };
+        let Some(anchor_layer) = self.storage.config.anchor else {
+            return Ok((reference, None));
+        };
+        let digest = content_digest(&document);
+        let tx_hash = self.write_hash(anchor_layer, genome.db_id.unwrap_or(0), digest).await
+            .map_err(|e| format!("Anchoring {} on {}: {}", reference, anchor_layer.key(), e))?;
+        info!("{} Anchored {} → {} | TX: {}", anchor_layer.emoji(), reference, anchor_layer.name(), tx_hash);
+        Ok((reference, Some(StorageAnchor { layer: anchor_layer, tx_hash, content_hash: hex::encode(digest) })))
+    }
+
+    /// The genome document `reference` names on storage layer `layer`,
+    /// fetched from the node or gateway (or the mock chain when simulated);
+    /// IPFS content must hash to its CID
+    pub async fn restore_genome(&self, reference: &str, layer: BlockchainLayer) -> Result<StoredGenome, String> {
+        if !layer.is_storage() {
+            return Err(format!("{} archives carry a DNA hash, not the genome", layer.name()));
+        }
+        let content = match self.mock_chain.payload(reference) {
+            Some(payload) => payload.to_vec(),
+            None => self.storage.fetch(layer, reference).await?,
+        };
+        if layer == BlockchainLayer::Ipfs && cid_digest(reference) != Some(content_digest(&content)) {
+            return Err(format!("The content behind {} does not hash to its CID", reference));
+        }
+        StoredGenome::from_bytes(&content)
+    }
+
+    /// Queue `genome` for the next Merkle root instead of archiving it alone
+    pub fn queue_merkle(&mut self, genome: &Genome<Rot180>) -> PendingLeaf {
+        let leaf = self.merkle.queue(genome.db_id.unwrap_or(0), hash_genome_dna(&genome.to_dna_string()), Utc::now().timestamp());
+        debug!("🌳 Merkle: genome #{} queued ({} waiting)", leaf.genome_id, self.merkle.pending().len());
+        leaf
+    }
+
+    /// Archive the waiting genomes' Merkle root on the configured layer, in
+    /// one transaction; `None` if no genome waits. On failure the genomes
+    /// keep waiting for the next try
+    pub async fn commit_merkle(&mut self) -> Result<Option<MerkleCommitment>, String> {
+        let Some(root) = self.merkle.root() else {
+            return Ok(None);
+        };
+        let layer = self.merkle.config.layer;
+        let cost_usd = self.estimate_cost(layer, root.len()).usd;
+        let written = self.write_hash(layer, self.merkle.next_id() as i64, root).await;
+        let tx_hash = match written {
+            Ok(tx_hash) => {
+                self.mission_control.report_success(MC_LAYER_SOURCE, layer.key());
+                tx_hash
+            }
+            Err(e) => {
+                self.mission_control.report_failure(MC_LAYER_SOURCE, layer.key(), &e);
+                return Err(e);
+            }
+        };
+        let now = Utc::now().timestamp();
+        if let Some(usd) = cost_usd {
+            self.spend.add(usd, now);
+        }
+        let commitment = self.merkle.committed(root, layer, &tx_hash, cost_usd, now)?;
+        info!("🌳 Merkle root {} → {} | {} genomes | TX: {}", commitment.root, layer.name(), commitment.leaves, tx_hash);
+        Ok(Some(commitment))
+    }
+
+    /// Inclusion proof of `genome_id`'s latest Merkle-archived DNA hash
+    pub fn prove_archived(&self, genome_id: i64) -> Result<ArchiveProof, String> {
+        if let Some(proof) = self.merkle.prove(genome_id) {
+            return Ok(proof.clone());
+        }
+        match self.merkle.pending().iter().any(|leaf| leaf.genome_id == genome_id) {
+            true => Err(format!("Genome #{} waits for the next Merkle root", genome_id)),
+            false => Err(format!("Genome #{} has no Merkle archive", genome_id)),
+        }
+    }
+
+    /// `genome_id`'s proof, checked on its own and against the root read
+    /// back from the transaction that commits it
+    pub async fn verify_archived(&mut self, genome_id: i64, current_dna: Option<&str>) -> Result<ProofVerification, String> {
+        let proof = self.prove_archived(genome_id)?;
+        let (chain_root, chain_error) = match self.restore_hash_from_chain(&proof.tx_hash, proof.layer).await {
+            Ok(root) => (Some(hex::encode(root)), None),
+            Err(e) => (None, Some(e)),
+        };
+        Ok(ProofVerification {
+            path_valid: proof.verify(),
+            chain_root_matches: chain_root.as_deref() == Some(proof.root.as_str()),
+            current_dna_matches: current_dna.map(|dna| proof.covers(dna)),
+            chain_root,
+            chain_error,
+            proof,
+        })
+    }
+
+    /// Write `hash` to `layer` as an archive of `id` would carry a DNA hash
+    async fn write_hash(&mut self, layer: BlockchainLayer, id: i64, hash: [u8; 32]) -> Result<String, String> {
+        let now = Utc::now().timestamp();
+        let simulated = self.generate_tx_hash(&hex::encode(hash), layer.key());
+        match layer {
+            BlockchainLayer::Lightning => return Err("Merkle roots are committed on-chain, not by keysend".into()),
+            BlockchainLayer::Solana => match self.solana.as_mut() {
+                Some(solana) => return solana.archive(id, hash, now).await.map(|(signature, _)| signature),
+                None => self.mock_chain.record(&simulated, archive_memo(id, hash).into_bytes()),
+            },
+            BlockchainLayer::Ethereum => match self.ethereum.as_mut() {
+                Some(ethereum) => return ethereum.archive(id, hash, now).await,
+                None => self.mock_chain.record(&simulated, archive_calldata(EthArchiveMode::Calldata, hash)),
+            },
+            BlockchainLayer::Bitcoin => {
+                let script = op_return_script(hash);
+                let txid = self.bitcoin.send_simulated(&script, now)?;
+                self.mock_chain.record(&txid, script);
+                return Ok(txid);
+            }
+            BlockchainLayer::Ipfs | BlockchainLayer::Arweave => return Err(format!("Hashes are committed on-chain; {} stores genomes", layer.name())),
+        }
+        Ok(simulated)
     }
 
     fn generate_tx_hash(&self, data: &str, chain: &str) -> String {
@@ -389,7 +1549,336 @@ impl MultiChainArchiver {
     }
 
     pub fn mission_control_stats(&self) -> MissionControlStats {
-        self.mission_control.stats()
+        let mut stats = self.mission_control.stats();
+        stats.layers = BlockchainLayer::ALL.into_iter().chain(BlockchainLayer::STORAGE).map(|layer| {
+            let pair = self.mission_control.pairs.get(&(MC_LAYER_SOURCE.to_string(), layer.key().to_string()));
+            LayerReliability {
+                layer,
+                probability: self.layer_probability(layer),
+                successes: pair.map_or(0, |p| p.success_count),
+                failures: pair.map_or(0, |p| p.failure_count),
+                last_failure_reason: pair.and_then(|p| p.last_failure_reason.clone()),
+            }
+        }).collect();
+        stats
+    }
+
+    pub fn entry(&self, id: u64) -> Option<&ChainArchiveEntry> {
+        id.checked_sub(1).and_then(|index| self.archives.get(index as usize))
+    }
+
+    /// Every archive of a genome, oldest first
+    pub fn entries_for_genome(&self, genome_id: i64) -> Vec<&ChainArchiveEntry> {
+        self.archives.iter().filter(|e| e.genome_id == genome_id).collect()
+    }
+
+    /// Archive counts and averages per layer (every layer, used or not)
+    pub fn layer_stats(&self) -> Vec<LayerStats> {
+        BlockchainLayer::ALL.into_iter().chain(BlockchainLayer::STORAGE).map(|layer| {
+            let entries: Vec<&ChainArchiveEntry> = self.archives.iter().filter(|e| e.layer == layer).collect();
+            let mut genomes: Vec<i64> = entries.iter().map(|e| e.genome_id).collect();
+            genomes.sort_unstable();
+            genomes.dedup();
+            let count = entries.len().max(1) as f64;
+            LayerStats {
+                layer,
+                name: layer.name().to_string(),
+                archives: entries.len(),
+                genomes: genomes.len(),
+                avg_consciousness: entries.iter().map(|e| e.consciousness as f64).sum::<f64>() / count,
+                avg_tg_ratio: entries.iter().map(|e| e.tg_ratio).sum::<f64>() / count,
+                last_archived_at: entries.iter().map(|e| e.timestamp).max(),
+            }
+        }).collect()
+    }
+
+    /// The DNA hash transaction `tx_reference` embeds on `layer`, read back
+    /// from the layer's node (or the mock chain for simulated archives); on
+    /// Lightning every keysend of the reference must carry the same one
+    pub async fn restore_hash_from_chain(&mut self, tx_reference: &str, layer: BlockchainLayer) -> Result<[u8; 32], String> {
+        if layer == BlockchainLayer::Lightning {
+            let mut hashes = Vec::new();
+            for reference in tx_reference.split(',') {
+                hashes.push(self.restore_keysend_hash(reference.trim()).await?);
+            }
+            hashes.dedup();
+            return match hashes[..] {
+                [hash] => Ok(hash),
+                _ => Err(format!("The keysends of {} carry different genome hashes", tx_reference)),
+            };
+        }
+        if layer.is_storage() {
+            return self.restore_genome(tx_reference, layer).await.map(|document| document.dna_hash());
+        }
+        if let Some(payload) = self.mock_chain.payload(tx_reference) {
+            return embedded_hash(layer, payload).ok_or_else(|| format!("{} transaction {} carries no genome archive", layer.name(), tx_reference));
+        }
+        if let (BlockchainLayer::Ethereum, Some(ethereum)) = (layer, &self.ethereum) {
+            return ethereum.read_archive(tx_reference).await;
+        }
+        if let (BlockchainLayer::Solana, Some(solana)) = (layer, &self.solana) {
+            return solana.read_archive(tx_reference).await;
+        }
+        Err(format!("{} transaction {} not found", layer.name(), tx_reference))
+    }
+
+    /// The genome hash a keysend carried: LND must report it settled, the
+    /// record is the one it was sent with
+    async fn restore_keysend_hash(&mut self, reference: &str) -> Result<[u8; 32], String> {
+        if let Some(payload) = self.mock_chain.payload(reference) {
+            return embedded_hash(BlockchainLayer::Lightning, payload).ok_or_else(|| format!("Keysend {} carries no genome hash", reference));
+        }
+        if !self.uses_lnd() {
+            return Err(format!("Keysend {} not found", reference));
+        }
+        let payment = self.track_payment(reference).await?;
+        if payment.status != PaymentStatus::Succeeded {
+            return Err(format!("Keysend {} is {:?}, not settled", reference, payment.status));
+        }
+        hex::decode(&payment.dna_hash).ok().and_then(|hash| hash.try_into().ok())
+            .ok_or_else(|| format!("Keysend {} has no genome hash", reference))
+    }
+
+    /// Re-check archive entry `id` and read its transaction back;
+    /// `current_dna` is the genome as stored now (`None` if it was deleted)
+    pub async fn verify(&mut self, id: u64, current_dna: Option<&str>) -> Result<ArchiveVerification, String> {
+        let entry = self.entry(id).cloned().ok_or_else(|| format!("Archive entry {} not found", id))?;
+        let dna_hash_valid = hex::encode(hash_genome_dna(&entry.dna_string)) == entry.dna_hash;
+        let tx_reference_valid = entry.tx_hash.as_deref().is_some_and(|tx| entry.layer.valid_tx_reference(tx));
+        let restored = match entry.tx_hash.as_deref() {
+            Some(tx) => self.restore_hash_from_chain(tx, entry.layer).await.map(hex::encode),
+            None => Err("The entry has no tx reference".to_string()),
+        };
+        let chain_hash = restored.as_ref().ok().cloned();
+        let chain_hash_matches = chain_hash.as_deref() == Some(entry.dna_hash.as_str());
+        let anchor_matches = match &entry.anchor {
+            Some(anchor) => Some(self.restore_hash_from_chain(&anchor.tx_hash, anchor.layer).await
+                .is_ok_and(|hash| hex::encode(hash) == anchor.content_hash)),
+            None => None,
+        };
+        let spv = match entry.spv {
+            Some(_) => Some(self.verify_spv(&entry)?),
+            None => None,
+        };
+        let spv_verified = spv.as_ref().is_none_or(|spv| spv.verified);
+        Ok(ArchiveVerification {
+            entry_id: id,
+            genome_id: entry.genome_id,
+            layer: entry.layer,
+            tx_hash: entry.tx_hash.clone(),
+            dna_hash_valid,
+            tx_reference_valid,
+            current_dna_matches: current_dna.zip(chain_hash.as_deref()).map(|(dna, chain)| hex::encode(hash_genome_dna(dna)) == chain),
+            chain_hash,
+            chain_error: restored.err(),
+            chain_hash_matches,
+            genome_unchanged: current_dna.map(|dna| dna == entry.dna_string),
+            anchor_matches,
+            spv,
+            verified: dna_hash_valid && tx_reference_valid && chain_hash_matches && anchor_matches != Some(false) && spv_verified,
+        })
+    }
+
+    /// Fetch the SPV proof of Bitcoin archive `id` and keep it on the entry
+    pub async fn prove_bitcoin_archive(&mut self, id: u64) -> Result<SpvProof, String> {
+        let entry = self.entry(id).ok_or_else(|| format!("Archive entry {} not found", id))?;
+        if entry.layer != BlockchainLayer::Bitcoin {
+            return Err(format!("Archive entry {} is on {}; SPV proofs are for Bitcoin", id, entry.layer.name()));
+        }
+        let tx_hash = entry.tx_hash.clone().ok_or_else(|| format!("Archive entry {} has no tx reference", id))?;
+        let proof = self.bitcoin.fetch_proof(&tx_hash, Utc::now().timestamp()).await?;
+        self.archives[id as usize - 1].spv = Some(proof.clone());
+        Ok(proof)
+    }
+
+    /// Check a Bitcoin entry's SPV proof locally: the transaction, its
+    /// Merkle branch, the header chain's links and work, and its depth
+    pub fn verify_spv(&self, entry: &ChainArchiveEntry) -> Result<SpvVerification, String> {
+        let proof = entry.spv.as_ref().ok_or_else(|| format!("Archive entry {} has no SPV proof yet", entry.id))?;
+        let tx_hash = entry.tx_hash.as_deref().ok_or_else(|| format!("Archive entry {} has no tx reference", entry.id))?;
+        self.bitcoin.verify(proof, tx_hash, &entry.dna_hash)
+    }
+
+    /// Fetch proofs of Bitcoin archives without one, or not yet deep
+    /// enough; returns how many reach the required depth now
+    pub async fn refresh_spv_proofs(&mut self) -> usize {
+        let required = self.bitcoin.config.confirmations;
+        let pending: Vec<u64> = self.archives.iter()
+            .filter(|e| e.layer == BlockchainLayer::Bitcoin && e.spv.as_ref().is_none_or(|proof| proof.confirmations() < required))
+            .map(|e| e.id)
+            .collect();
+        let mut proven = 0;
+        for id in pending {
+            match self.prove_bitcoin_archive(id).await {
+                Ok(proof) if proof.confirmations() >= required => proven += 1,
+                Ok(_) => {}
+                Err(e) => debug!("🟠 SPV proof of entry {} not ready: {}", id, e),
+            }
+        }
+        proven
+    }
+}
+
+/// Keysend TLV record carrying a genome
+fn archive_record(genome: &Genome<Rot180>, timestamp: i64) -> String {
+    format!(
+        "DIVINE_GENOME|v15|id:{}|dna:{}|c:{}|tg:{:.3}|ts:{}",
+        genome.db_id.unwrap_or(0), genome.to_dna_string(), genome.consciousness,
+        genome.rna_signal(), timestamp
+    )
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayerStats {
+    pub layer: BlockchainLayer,
+    pub name: String,
+    pub archives: usize,
+    /// Distinct genomes archived on the layer
+    pub genomes: usize,
+    pub avg_consciousness: f64,
+    pub avg_tg_ratio: f64,
+    pub last_archived_at: Option<i64>,
+}
+
+/// Outcome of re-checking an archive entry after the fact
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchiveVerification {
+    pub entry_id: u64,
+    pub genome_id: i64,
+    pub layer: BlockchainLayer,
+    pub tx_hash: Option<String>,
+    /// The archived DNA still hashes to the recorded `dna_hash`
+    pub dna_hash_valid: bool,
+    /// The tx reference has its layer's format
+    pub tx_reference_valid: bool,
+    /// The hash read back from the transaction (`None` when it could not be)
+    pub chain_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain_error: Option<String>,
+    /// The transaction embeds the entry's `dna_hash`
+    pub chain_hash_matches: bool,
+    /// The transaction embeds the hash of the genome's current DNA (`None`
+    /// once deleted or when nothing was read back)
+    pub current_dna_matches: Option<bool>,
+    /// Whether the stored genome still has the archived DNA (`None` once
+    /// deleted); edits after archiving do not invalidate the entry
+    pub genome_unchanged: Option<bool>,
+    /// The anchor transaction carries the stored document's digest (`None`
+    /// without an anchor)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anchor_matches: Option<bool>,
+    /// The entry's SPV proof, checked locally (`None` without one)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spv: Option<SpvVerification>,
+    pub verified: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArchiveItemStatus {
+    Pending,
+    Archived,
+    Failed,
+}
+
+/// One genome of a batch: its layer is known up front, the tx once archived
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveBatchItem {
+    pub genome_id: i64,
+    pub layer: Option<BlockchainLayer>,
+    pub status: ArchiveItemStatus,
+    pub tx_hash: Option<String>,
+    pub error: Option<String>,
+}
+
+impl ArchiveBatchItem {
+    pub fn pending(genome_id: i64, layer: BlockchainLayer) -> Self {
+        Self { genome_id, layer: Some(layer), status: ArchiveItemStatus::Pending, tx_hash: None, error: None }
+    }
+
+    pub fn failed(genome_id: i64, error: String) -> Self {
+        Self { genome_id, layer: None, status: ArchiveItemStatus::Failed, tx_hash: None, error: Some(error) }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveBatch {
+    pub id: u64,
+    pub items: Vec<ArchiveBatchItem>,
+    pub archived: usize,
+    pub failed: usize,
+    pub created_at: i64,
+    pub finished: bool,
+}
+
+impl ArchiveBatch {
+    fn new(id: u64, items: Vec<ArchiveBatchItem>) -> Self {
+        let failed = items.iter().filter(|i| i.status == ArchiveItemStatus::Failed).count();
+        let finished = failed == items.len();
+        Self { id, items, archived: 0, failed, created_at: Utc::now().timestamp(), finished }
+    }
+
+    /// Record the outcome of archiving one pending genome
+    pub fn complete(&mut self, genome_id: i64, result: Result<&ChainArchiveEntry, String>) {
+        let Some(item) = self.items.iter_mut().find(|i| i.genome_id == genome_id && i.status == ArchiveItemStatus::Pending) else {
+            return;
+        };
+        match result {
+            Ok(entry) => {
+                item.status = ArchiveItemStatus::Archived;
+                item.layer = Some(entry.layer);
+                item.tx_hash = entry.tx_hash.clone();
+                self.archived += 1;
+            }
+            Err(e) => {
+                item.status = ArchiveItemStatus::Failed;
+                item.error = Some(e);
+                self.failed += 1;
+            }
+        }
+        self.finished = self.archived + self.failed == self.items.len();
+    }
+}
+
+/// Running and recently finished archive batches, by id
+#[derive(Default)]
+pub struct ArchiveBatches {
+    next_id: u64,
+    batches: HashMap<u64, ArchiveBatch>,
+}
+
+impl ArchiveBatches {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a batch and return it as first reported
+    pub fn create(&mut self, items: Vec<ArchiveBatchItem>) -> ArchiveBatch {
+        self.prune_finished();
+        self.next_id += 1;
+        let batch = ArchiveBatch::new(self.next_id, items);
+        self.batches.insert(batch.id, batch.clone());
+        batch
+    }
+
+    pub fn get(&self, id: u64) -> Option<&ArchiveBatch> {
+        self.batches.get(&id)
+    }
+
+    pub fn complete(&mut self, id: u64, genome_id: i64, result: Result<&ChainArchiveEntry, String>) {
+        if let Some(batch) = self.batches.get_mut(&id) {
+            batch.complete(genome_id, result);
+        }
+    }
+
+    fn prune_finished(&mut self) {
+        let mut finished: Vec<u64> = self.batches.values().filter(|b| b.finished).map(|b| b.id).collect();
+        if finished.len() < MAX_FINISHED_BATCHES {
+            return;
+        }
+        finished.sort_unstable();
+        for id in &finished[..=finished.len() - MAX_FINISHED_BATCHES] {
+            self.batches.remove(id);
+        }
     }
 }
 
@@ -0,0 +1,575 @@
+//! P2P Network V16 — Block gossip between Divine nodes
+//!
+//! Newly mined `ConsensusBlock`s are published on a libp2p gossipsub topic.
+//! Received blocks are validated through the local chain before gossipsub
+//! forwards them: accepted blocks propagate, invalid ones are rejected (and
+//! count against the peer that sent them), duplicates and orphans are kept
+//! to ourselves.
+//!
+//! Catch-up sync, light-client headers and peer exchange run over a plain TCP
+//! request port with newline-delimited JSON.
+//!
+//! Peer discovery:
+//! - Bootstrap peers from `P2P_BOOTSTRAP_PEERS` (comma-separated host:port)
+//! - Peer exchange: every `Hello` is answered with the known peer list, and
+//!   its gossip addresses are dialed so the two nodes share the topic
+
+use std::collections::{HashSet, VecDeque};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::{Arc, OnceLock};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use libp2p::futures::StreamExt;
+use libp2p::gossipsub::{self, IdentTopic, MessageAcceptance, MessageAuthenticity, MessageId};
+use libp2p::multiaddr::{Multiaddr, Protocol};
+use libp2p::swarm::dial_opts::{DialOpts, PeerCondition};
+use libp2p::swarm::SwarmEvent;
+use libp2p::{identity, noise, tcp, yamux, PeerId, Swarm};
+use serde::{Serialize, Deserialize};
+use sha2::{Digest, Sha256};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{mpsc, RwLock};
+use tracing::{info, warn};
+
+use crate::consensus::{BlockHeader, BlockOutcome, ConsensusBlock, ProofOfConsciousness};
+use crate::database::DivineDatabase;
+
+/// Maximum number of blocks served per `GetBlocks` request
+pub const MAX_BLOCKS_PER_REQUEST: usize = 500;
+/// Maximum number of headers served per `GetHeaders` request
+pub const MAX_HEADERS_PER_REQUEST: usize = 2_000;
+/// Maximum number of peers kept in the peer table
+pub const MAX_PEERS: usize = 128;
+/// Maximum number of block hashes remembered as already received
+pub const MAX_SEEN_BLOCKS: usize = 10_000;
+/// Longest request line a node reads before dropping the connection
+pub const MAX_REQUEST_BYTES: usize = 64 * 1024;
+/// Longest reply line read from a peer (a full `Blocks` batch)
+pub const MAX_REPLY_BYTES: usize = 32 * 1024 * 1024;
+/// Largest gossiped block
+pub const MAX_GOSSIP_BYTES: usize = 4 * 1024 * 1024;
+/// Gossipsub topic carrying new blocks
+pub const BLOCK_TOPIC: &str = "divine/blocks/1";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum NetworkMessage {
+    Hello {
+        node_id: String,
+        listen_addr: String,
+        height: u64,
+        /// libp2p addresses (with `/p2p/<peer id>`) the sender gossips on
+        #[serde(default)]
+        gossip_addrs: Vec<String>,
+    },
+    Peers(Vec<String>),
+    GetBlocks { from_index: u64 },
+    Blocks(Vec<ConsensusBlock>),
+    GetHeaders { from_index: u64 },
+    Headers(Vec<BlockHeader>),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkStats {
+    pub node_id: String,
+    pub peer_id: String,
+    pub listen_addr: String,
+    pub gossip_addrs: Vec<String>,
+    pub peers: Vec<String>,
+    /// libp2p peers subscribed to the block topic
+    pub gossip_peers: Vec<String>,
+    pub blocks_received: u64,
+    pub blocks_rejected: u64,
+    pub blocks_broadcast: u64,
+}
+
+/// Work handed to the task that drives the libp2p swarm
+enum SwarmCommand {
+    Publish(Vec<u8>),
+    Dial { peer: PeerId, addrs: Vec<Multiaddr> },
+    Report { message_id: MessageId, source: PeerId, acceptance: MessageAcceptance },
+}
+
+/// Recently received block hashes, oldest evicted first
+#[derive(Default)]
+struct SeenBlocks {
+    hashes: HashSet<String>,
+    order: VecDeque<String>,
+}
+
+impl SeenBlocks {
+    fn contains(&self, hash: &str) -> bool {
+        self.hashes.contains(hash)
+    }
+
+    fn insert(&mut self, hash: &str) {
+        if !self.hashes.insert(hash.to_string()) {
+            return;
+        }
+        self.order.push_back(hash.to_string());
+        while self.order.len() > MAX_SEEN_BLOCKS {
+            if let Some(oldest) = self.order.pop_front() {
+                self.hashes.remove(&oldest);
+            }
+        }
+    }
+}
+
+pub struct P2PNode {
+    pub node_id: String,
+    pub listen_addr: String,
+    /// Where the gossipsub swarm listens (`P2P_GOSSIP_ADDR`)
+    pub gossip_listen: Multiaddr,
+    keypair: identity::Keypair,
+    peers: RwLock<HashSet<String>>,
+    gossip_addrs: RwLock<Vec<Multiaddr>>,
+    gossip_peers: RwLock<HashSet<PeerId>>,
+    seen_blocks: RwLock<SeenBlocks>,
+    commands: OnceLock<mpsc::UnboundedSender<SwarmCommand>>,
+    consensus: Arc<RwLock<ProofOfConsciousness>>,
+    database: Option<Arc<DivineDatabase>>,
+    blocks_received: AtomicU64,
+    blocks_rejected: AtomicU64,
+    blocks_broadcast: AtomicU64,
+}
+
+impl P2PNode {
+    /// A node serving requests on `listen_addr` and gossiping on an
+    /// ephemeral port of the same host
+    pub fn new(
+        listen_addr: &str,
+        consensus: Arc<RwLock<ProofOfConsciousness>>,
+        database: Option<Arc<DivineDatabase>>,
+    ) -> Self {
+        let node_id = format!("divine_node_{}", hex::encode(rand::random::<[u8; 8]>()));
+        let host = match listen_addr.parse::<SocketAddr>().map(|addr| addr.ip()) {
+            Ok(IpAddr::V4(ip)) => Protocol::Ip4(ip),
+            Ok(IpAddr::V6(ip)) => Protocol::Ip6(ip),
+            Err(_) => Protocol::Ip4(Ipv4Addr::UNSPECIFIED),
+        };
+        Self {
+            node_id,
+            listen_addr: listen_addr.to_string(),
+            gossip_listen: Multiaddr::empty().with(host).with(Protocol::Tcp(0)),
+            keypair: identity::Keypair::generate_ed25519(),
+            peers: RwLock::new(HashSet::new()),
+            gossip_addrs: RwLock::new(Vec::new()),
+            gossip_peers: RwLock::new(HashSet::new()),
+            seen_blocks: RwLock::new(SeenBlocks::default()),
+            commands: OnceLock::new(),
+            consensus,
+            database,
+            blocks_received: AtomicU64::new(0),
+            blocks_rejected: AtomicU64::new(0),
+            blocks_broadcast: AtomicU64::new(0),
+        }
+    }
+
+    /// Build a node from `P2P_LISTEN_ADDR` / `P2P_GOSSIP_ADDR` /
+    /// `P2P_BOOTSTRAP_PEERS`, if configured
+    pub async fn from_env(
+        consensus: Arc<RwLock<ProofOfConsciousness>>,
+        database: Option<Arc<DivineDatabase>>,
+    ) -> Option<Arc<Self>> {
+        let listen_addr = std::env::var("P2P_LISTEN_ADDR").ok()?;
+        let mut node = Self::new(&listen_addr, consensus, database);
+        if let Ok(addr) = std::env::var("P2P_GOSSIP_ADDR") {
+            match addr.parse() {
+                Ok(addr) => node.gossip_listen = addr,
+                Err(e) => warn!("🌐 Ignoring P2P_GOSSIP_ADDR {}: {}", addr, e),
+            }
+        }
+        let node = Arc::new(node);
+
+        let bootstrap = std::env::var("P2P_BOOTSTRAP_PEERS").unwrap_or_default();
+        for peer in bootstrap.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            node.add_peer(peer).await;
+        }
+        Some(node)
+    }
+
+    pub fn peer_id(&self) -> PeerId {
+        self.keypair.public().to_peer_id()
+    }
+
+    /// Add a peer to the table; `false` if it is us, already known, or the
+    /// table holds `MAX_PEERS`
+    pub async fn add_peer(&self, addr: &str) -> bool {
+        if addr == self.listen_addr {
+            return false;
+        }
+        let mut peers = self.peers.write().await;
+        if peers.len() >= MAX_PEERS {
+            return false;
+        }
+        peers.insert(addr.to_string())
+    }
+
+    pub async fn peers(&self) -> Vec<String> {
+        self.peers.read().await.iter().cloned().collect()
+    }
+
+    /// libp2p peers currently subscribed to the block topic
+    pub async fn gossip_peers(&self) -> Vec<String> {
+        self.gossip_peers.read().await.iter().map(PeerId::to_string).collect()
+    }
+
+    /// Our gossip addresses, dialable by other nodes
+    pub async fn gossip_addrs(&self) -> Vec<String> {
+        self.gossip_addrs.read().await.iter().map(Multiaddr::to_string).collect()
+    }
+
+    /// Bind the request listener and the gossip swarm, announce ourselves to
+    /// bootstrap peers and serve forever
+    pub async fn start(self: Arc<Self>) -> anyhow::Result<()> {
+        let listener = TcpListener::bind(&self.listen_addr).await?;
+        let mut swarm = self.build_swarm()?;
+        swarm.listen_on(self.gossip_listen.clone())?;
+
+        // Hello advertises our gossip address, so wait until we have one
+        loop {
+            if let SwarmEvent::NewListenAddr { address, .. } = swarm.select_next_some().await {
+                self.record_listen_addr(address).await;
+                break;
+            }
+        }
+        info!("🌐 P2P node {} listening on {} (gossip {})", self.node_id, self.listen_addr, self.gossip_addrs().await.join(", "));
+
+        let (commands, receiver) = mpsc::unbounded_channel();
+        if self.commands.set(commands).is_err() {
+            return Err(anyhow::anyhow!("P2P node {} already started", self.node_id));
+        }
+        tokio::spawn(Arc::clone(&self).drive_swarm(swarm, receiver));
+
+        let node = Arc::clone(&self);
+        tokio::spawn(async move {
+            node.discover().await;
+        });
+
+        tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((stream, _)) => {
+                        let node = Arc::clone(&self);
+                        tokio::spawn(async move {
+                            if let Err(e) = node.handle_connection(stream).await {
+                                warn!("🌐 P2P connection error: {}", e);
+                            }
+                        });
+                    }
+                    Err(e) => warn!("🌐 P2P accept failed: {}", e),
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    fn build_swarm(&self) -> anyhow::Result<Swarm<gossipsub::Behaviour>> {
+        let config = gossipsub::ConfigBuilder::default()
+            .heartbeat_interval(Duration::from_secs(1))
+            .max_transmit_size(MAX_GOSSIP_BYTES)
+            // Forward only what the local chain accepted
+            .validate_messages()
+            // Identify blocks by content, so the same block from two sources is one message
+            .message_id_fn(|message: &gossipsub::Message| MessageId::from(hex::encode(Sha256::digest(&message.data))))
+            .build()
+            .map_err(|e| anyhow::anyhow!("Invalid gossipsub config: {}", e))?;
+
+        let mut swarm = libp2p::SwarmBuilder::with_existing_identity(self.keypair.clone())
+            .with_tokio()
+            .with_tcp(tcp::Config::default(), noise::Config::new, yamux::Config::default)?
+            .with_behaviour(|key| {
+                gossipsub::Behaviour::new(MessageAuthenticity::Signed(key.clone()), config)
+                    .map_err(Box::<dyn std::error::Error + Send + Sync>::from)
+            })
+            .map_err(|e| anyhow::anyhow!("Gossipsub setup failed: {}", e))?
+            .with_swarm_config(|config| config.with_idle_connection_timeout(Duration::from_secs(60)))
+            .build();
+        swarm.behaviour_mut().subscribe(&IdentTopic::new(BLOCK_TOPIC))?;
+        Ok(swarm)
+    }
+
+    async fn record_listen_addr(&self, address: Multiaddr) {
+        let address = address.with(Protocol::P2p(self.peer_id()));
+        let mut addrs = self.gossip_addrs.write().await;
+        if !addrs.contains(&address) {
+            addrs.push(address);
+        }
+    }
+
+    async fn drive_swarm(self: Arc<Self>, mut swarm: Swarm<gossipsub::Behaviour>, mut commands: mpsc::UnboundedReceiver<SwarmCommand>) {
+        let topic = IdentTopic::new(BLOCK_TOPIC);
+        loop {
+            tokio::select! {
+                command = commands.recv() => match command {
+                    Some(SwarmCommand::Publish(data)) => match swarm.behaviour_mut().publish(topic.clone(), data) {
+                        Ok(_) => {
+                            self.blocks_broadcast.fetch_add(1, Ordering::Relaxed);
+                        }
+                        Err(e) => warn!("🌐 Block gossip failed: {}", e),
+                    },
+                    Some(SwarmCommand::Dial { peer, addrs }) => {
+                        let opts = DialOpts::peer_id(peer).condition(PeerCondition::Disconnected).addresses(addrs).build();
+                        if let Err(e) = swarm.dial(opts) {
+                            warn!("🌐 Dialing {} failed: {}", peer, e);
+                        }
+                    }
+                    Some(SwarmCommand::Report { message_id, source, acceptance }) => {
+                        let _ = swarm.behaviour_mut().report_message_validation_result(&message_id, &source, acceptance);
+                    }
+                    None => break,
+                },
+                event = swarm.select_next_some() => match event {
+                    SwarmEvent::NewListenAddr { address, .. } => self.record_listen_addr(address).await,
+                    SwarmEvent::Behaviour(gossipsub::Event::Message { propagation_source, message_id, message }) => {
+                        let node = Arc::clone(&self);
+                        tokio::spawn(async move {
+                            let acceptance = node.receive_gossip(&message.data, &propagation_source).await;
+                            node.command(SwarmCommand::Report { message_id, source: propagation_source, acceptance });
+                        });
+                    }
+                    SwarmEvent::Behaviour(gossipsub::Event::Subscribed { peer_id, .. }) => {
+                        self.gossip_peers.write().await.insert(peer_id);
+                    }
+                    SwarmEvent::Behaviour(gossipsub::Event::Unsubscribed { peer_id, .. }) => {
+                        self.gossip_peers.write().await.remove(&peer_id);
+                    }
+                    SwarmEvent::ConnectionClosed { peer_id, num_established: 0, .. } => {
+                        self.gossip_peers.write().await.remove(&peer_id);
+                    }
+                    _ => {}
+                },
+            }
+        }
+    }
+
+    fn command(&self, command: SwarmCommand) {
+        match self.commands.get() {
+            Some(commands) => {
+                let _ = commands.send(command);
+            }
+            None => warn!("🌐 P2P node {} is not started", self.node_id),
+        }
+    }
+
+    /// Dial the gossip addresses a peer announced (`/p2p/<peer id>` suffixed)
+    fn dial_gossip(&self, addrs: &[String]) {
+        let own = self.peer_id();
+        for addr in addrs {
+            let Ok(addr) = addr.parse::<Multiaddr>() else {
+                continue;
+            };
+            if let Some(Protocol::P2p(peer)) = addr.iter().last() {
+                if peer != own {
+                    self.command(SwarmCommand::Dial { peer, addrs: vec![addr] });
+                }
+            }
+        }
+    }
+
+    /// Say hello to every known peer, learn their peers and catch up on
+    /// blocks; newly learned peers are greeted in turn
+    pub async fn discover(&self) {
+        let height = self.consensus.read().await.current_block_height;
+        let hello = NetworkMessage::Hello {
+            node_id: self.node_id.clone(),
+            listen_addr: self.listen_addr.clone(),
+            height,
+            gossip_addrs: self.gossip_addrs().await,
+        };
+
+        let mut queue: VecDeque<String> = self.peers().await.into();
+        let mut greeted = HashSet::new();
+        while let Some(peer) = queue.pop_front() {
+            if !greeted.insert(peer.clone()) {
+                continue;
+            }
+            let replies = match request(&peer, &[hello.clone(), NetworkMessage::GetBlocks { from_index: height + 1 }]).await {
+                Ok(replies) => replies,
+                Err(e) => {
+                    warn!("🌐 Peer {} unreachable: {}", peer, e);
+                    continue;
+                }
+            };
+
+            for reply in replies {
+                match reply {
+                    NetworkMessage::Peers(addrs) => {
+                        for addr in addrs {
+                            if self.add_peer(&addr).await {
+                                info!("🌐 Discovered peer {} via {}", addr, peer);
+                                queue.push_back(addr);
+                            }
+                        }
+                    }
+                    NetworkMessage::Blocks(blocks) => {
+                        for block in blocks {
+                            self.receive_block(block, Some(&peer)).await;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    /// Gossip a locally mined block to the network
+    pub async fn broadcast_block(&self, block: &ConsensusBlock) {
+        self.seen_blocks.write().await.insert(&block.hash);
+        match serde_json::to_vec(block) {
+            Ok(data) => {
+                self.command(SwarmCommand::Publish(data));
+                info!("🌐 Block #{} published to {} gossip peer(s)", block.index, self.gossip_peers.read().await.len());
+            }
+            Err(e) => warn!("🌐 Block #{} not serializable: {}", block.index, e),
+        }
+    }
+
+    /// Validate a gossiped block and tell gossipsub whether to forward it
+    async fn receive_gossip(&self, data: &[u8], source: &PeerId) -> MessageAcceptance {
+        let block: ConsensusBlock = match serde_json::from_slice(data) {
+            Ok(block) => block,
+            Err(e) => {
+                self.blocks_rejected.fetch_add(1, Ordering::Relaxed);
+                warn!("🌐 Undecodable block from {}: {}", source, e);
+                return MessageAcceptance::Reject;
+            }
+        };
+        match self.ingest(block, Some(&source.to_string())).await {
+            Ok(Some(BlockOutcome::Orphan)) | Ok(None) => MessageAcceptance::Ignore,
+            Ok(Some(_)) => MessageAcceptance::Accept,
+            Err(_) => MessageAcceptance::Reject,
+        }
+    }
+
+    /// Validate a block from the network and append it to the local chain
+    pub async fn receive_block(&self, block: ConsensusBlock, from: Option<&str>) -> bool {
+        matches!(self.ingest(block, from).await, Ok(Some(outcome)) if outcome != BlockOutcome::Orphan)
+    }
+
+    /// Run a block through the local chain: `None` if it was already seen.
+    /// A block is only remembered as seen once accepted or held as an orphan,
+    /// so a rejected copy cannot shadow a later valid one
+    async fn ingest(&self, block: ConsensusBlock, from: Option<&str>) -> Result<Option<BlockOutcome>, String> {
+        if self.seen_blocks.read().await.contains(&block.hash) {
+            return Ok(None);
+        }
+
+        let mut consensus = self.consensus.write().await;
+        let outcome = match consensus.add_block(block.clone()) {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                self.blocks_rejected.fetch_add(1, Ordering::Relaxed);
+                warn!("🌐 Rejected block #{} from {}: {}", block.index, from.unwrap_or("?"), e);
+                return Err(e);
+            }
+        };
+        self.seen_blocks.write().await.insert(&block.hash);
+        if outcome == BlockOutcome::Orphan {
+            info!("🌐 Holding block #{} from {} until its parent arrives", block.index, from.unwrap_or("?"));
+            return Ok(Some(outcome));
+        }
+
+        if let Some(db) = &self.database {
+            if let Err(e) = consensus.save_chain(db).await {
+                warn!("🌐 Block #{} accepted but not persisted: {}", block.index, e);
+            }
+        }
+        drop(consensus);
+
+        self.blocks_received.fetch_add(1, Ordering::Relaxed);
+        info!("🌐 Accepted block #{} from {} ({:?})", block.index, from.unwrap_or("?"), outcome);
+        Ok(Some(outcome))
+    }
+
+    async fn handle_connection(&self, stream: TcpStream) -> anyhow::Result<()> {
+        let (reader, mut writer) = stream.into_split();
+        let mut reader = BufReader::new(reader);
+
+        while let Some(line) = read_line(&mut reader, MAX_REQUEST_BYTES).await? {
+            let message: NetworkMessage = serde_json::from_str(&line)?;
+            let reply = match message {
+                NetworkMessage::Hello { node_id, listen_addr, height, gossip_addrs } => {
+                    info!("🌐 Hello from {} ({}) at height {}", node_id, listen_addr, height);
+                    let known = self.peers().await;
+                    self.add_peer(&listen_addr).await;
+                    if self.peers.read().await.contains(&listen_addr) {
+                        self.dial_gossip(&gossip_addrs);
+                    }
+                    Some(NetworkMessage::Peers(known))
+                }
+                NetworkMessage::GetBlocks { from_index } => {
+                    let consensus = self.consensus.read().await;
+                    let blocks = consensus.chain.iter()
+                        .filter(|b| b.index >= from_index)
+                        .take(MAX_BLOCKS_PER_REQUEST)
+                        .cloned()
+                        .collect();
+                    Some(NetworkMessage::Blocks(blocks))
+                }
+                NetworkMessage::GetHeaders { from_index } => {
+                    let consensus = self.consensus.read().await;
+                    Some(NetworkMessage::Headers(consensus.headers_from(from_index, MAX_HEADERS_PER_REQUEST)))
+                }
+                NetworkMessage::Peers(_) | NetworkMessage::Blocks(_) | NetworkMessage::Headers(_) => None,
+            };
+
+            if let Some(reply) = reply {
+                write_message(&mut writer, &reply).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn stats(&self) -> NetworkStats {
+        NetworkStats {
+            node_id: self.node_id.clone(),
+            peer_id: self.peer_id().to_string(),
+            listen_addr: self.listen_addr.clone(),
+            gossip_addrs: self.gossip_addrs().await,
+            peers: self.peers().await,
+            gossip_peers: self.gossip_peers().await,
+            blocks_received: self.blocks_received.load(Ordering::Relaxed),
+            blocks_rejected: self.blocks_rejected.load(Ordering::Relaxed),
+            blocks_broadcast: self.blocks_broadcast.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Read one newline-terminated message of at most `limit` bytes
+async fn read_line<R: AsyncBufRead + Unpin>(reader: &mut R, limit: usize) -> anyhow::Result<Option<String>> {
+    let mut line = Vec::new();
+    if reader.take(limit as u64 + 1).read_until(b'\n', &mut line).await? == 0 {
+        return Ok(None);
+    }
+    if line.len() > limit {
+        return Err(anyhow::anyhow!("Message exceeds {} bytes", limit));
+    }
+    Ok(Some(String::from_utf8(line)?))
+}
+
+async fn write_message<W: AsyncWriteExt + Unpin>(writer: &mut W, message: &NetworkMessage) -> anyhow::Result<()> {
+    let mut line = serde_json::to_string(message)?;
+    line.push('\n');
+    writer.write_all(line.as_bytes()).await?;
+    Ok(())
+}
+
+/// Send messages and collect one reply per message
+pub async fn request(peer: &str, messages: &[NetworkMessage]) -> anyhow::Result<Vec<NetworkMessage>> {
+    let stream = TcpStream::connect(peer).await?;
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader);
+    let mut replies = Vec::new();
+
+    for message in messages {
+        write_message(&mut writer, message).await?;
+        if let Some(line) = read_line(&mut reader, MAX_REPLY_BYTES).await? {
+            replies.push(serde_json::from_str(&line)?);
+        }
+    }
+
+    Ok(replies)
+}
@@ -0,0 +1,381 @@
+//! Price Oracle V1 — external reference prices from HTTP sources
+//!
+//! `ORACLE_CONFIG` points at a JSON file naming the sources:
+//!
+//! ```json
+//! {
+//!   "sources": [
+//!     { "name": "coingecko-btc", "url": "https://api.coingecko.com/api/v3/simple/price?ids=bitcoin&vs_currencies=usd",
+//!       "pair": "BTC/USD", "path": "/bitcoin/usd" },
+//!     { "name": "desk-sol", "url": "https://prices.example.com/sol", "pair": "SOL/USD",
+//!       "public_key": "02ab…" }
+//!   ],
+//!   "min_sources": 1,
+//!   "max_age_secs": 300,
+//!   "refresh_secs": 60
+//! }
+//! ```
+//!
+//! Each source answers with JSON; `path` (a JSON pointer, `/price` by
+//! default) picks the price and `/timestamp` its time, when present. A
+//! source with a `public_key` must sign its quotes: `/signature` holds a
+//! compact secp256k1 signature over `quote_message`, and `/timestamp` is
+//! required. Unsigned or badly signed quotes from such a source are refused.
+//!
+//! A pair's reference price is the median of the latest quote of every
+//! source for it that is at most `max_age_secs` old, and only once
+//! `min_sources` sources agree to quote. RSM/USD, when a source quotes it,
+//! becomes the exchange's mark price; BTC, ETH and SOL prices turn archive
+//! costs into USD (`MultiChainArchiver::set_reference_prices`).
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+
+use crate::crypto::verify_signature;
+
+pub const DEFAULT_MIN_SOURCES: usize = 1;
+pub const DEFAULT_MAX_AGE_SECS: i64 = 300;
+pub const DEFAULT_REFRESH_SECS: u64 = 60;
+/// Quotes stamped further ahead than this are refused
+pub const MAX_CLOCK_SKEW_SECS: i64 = 60;
+const FETCH_TIMEOUT_SECS: u64 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Pair {
+    #[serde(rename = "BTC/USD")]
+    BtcUsd,
+    #[serde(rename = "ETH/USD")]
+    EthUsd,
+    #[serde(rename = "SOL/USD")]
+    SolUsd,
+    #[serde(rename = "RSM/USD")]
+    RsmUsd,
+}
+
+impl Pair {
+    pub const ALL: [Pair; 4] = [Pair::BtcUsd, Pair::EthUsd, Pair::SolUsd, Pair::RsmUsd];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Pair::BtcUsd => "BTC/USD",
+            Pair::EthUsd => "ETH/USD",
+            Pair::SolUsd => "SOL/USD",
+            Pair::RsmUsd => "RSM/USD",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Pair::ALL.into_iter().find(|pair| pair.name().eq_ignore_ascii_case(name))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OracleSource {
+    pub name: String,
+    pub url: String,
+    pub pair: Pair,
+    /// JSON pointer to the price in the response
+    #[serde(default = "default_price_path")]
+    pub path: String,
+    /// Compressed secp256k1 key (hex) the source signs its quotes with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+}
+
+fn default_price_path() -> String {
+    "/price".into()
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OracleConfig {
+    #[serde(default)]
+    pub sources: Vec<OracleSource>,
+    /// Sources that must quote a pair before it has a price
+    #[serde(default = "default_min_sources")]
+    pub min_sources: usize,
+    #[serde(default = "default_max_age_secs")]
+    pub max_age_secs: i64,
+    #[serde(default = "default_refresh_secs")]
+    pub refresh_secs: u64,
+}
+
+fn default_min_sources() -> usize { DEFAULT_MIN_SOURCES }
+fn default_max_age_secs() -> i64 { DEFAULT_MAX_AGE_SECS }
+fn default_refresh_secs() -> u64 { DEFAULT_REFRESH_SECS }
+
+impl Default for OracleConfig {
+    fn default() -> Self {
+        Self {
+            sources: Vec::new(),
+            min_sources: DEFAULT_MIN_SOURCES,
+            max_age_secs: DEFAULT_MAX_AGE_SECS,
+            refresh_secs: DEFAULT_REFRESH_SECS,
+        }
+    }
+}
+
+impl OracleConfig {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let config: Self = serde_json::from_str(text).map_err(|e| format!("Invalid oracle config: {}", e))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Self::parse(&text).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// The file `ORACLE_CONFIG` names; no sources when unset
+    pub fn from_env() -> Result<Self, String> {
+        match std::env::var("ORACLE_CONFIG") {
+            Ok(path) if !path.is_empty() => Self::load(path),
+            _ => Ok(Self::default()),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.min_sources == 0 || self.max_age_secs <= 0 || self.refresh_secs == 0 {
+            return Err("min_sources, max_age_secs and refresh_secs must be positive".into());
+        }
+        for (i, source) in self.sources.iter().enumerate() {
+            if self.sources[..i].iter().any(|other| other.name == source.name) {
+                return Err(format!("Duplicate oracle source {}", source.name));
+            }
+            if !source.url.starts_with("https://") && !source.url.starts_with("http://") {
+                return Err(format!("Oracle source {} needs an http(s) URL", source.name));
+            }
+            if !source.path.starts_with('/') {
+                return Err(format!("Oracle source {}: path {} is not a JSON pointer", source.name, source.path));
+            }
+            if let Some(key) = &source.public_key {
+                if !hex::decode(key).is_ok_and(|bytes| bytes.len() == 33) {
+                    return Err(format!("Oracle source {}: public_key must be a compressed secp256k1 key", source.name));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// One price reported by one source
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Quote {
+    pub source: String,
+    pub pair: Pair,
+    pub price_usd: f64,
+    pub timestamp: i64,
+    pub signed: bool,
+}
+
+/// Median of the fresh quotes for a pair
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReferencePrice {
+    pub pair: Pair,
+    pub price_usd: f64,
+    /// Quotes the median was taken over
+    pub sources: usize,
+    /// Time of the oldest of them
+    pub timestamp: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourceError {
+    pub source: String,
+    pub error: String,
+    pub timestamp: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OracleStatus {
+    pub prices: Vec<ReferencePrice>,
+    pub quotes: Vec<Quote>,
+    pub errors: Vec<SourceError>,
+}
+
+/// Bytes a signing source signs: `<pair>|<price>|<timestamp>`
+pub fn quote_message(pair: Pair, price_usd: f64, timestamp: i64) -> Vec<u8> {
+    format!("{}|{}|{}", pair.name(), price_usd, timestamp).into_bytes()
+}
+
+/// Read `source`'s quote out of its response `body`, fetched at `now`
+pub fn parse_quote(source: &OracleSource, body: &Value, now: i64) -> Result<Quote, String> {
+    let price_usd = body.pointer(&source.path).and_then(number)
+        .ok_or_else(|| format!("{}: no price at {}", source.name, source.path))?;
+    if !price_usd.is_finite() || price_usd <= 0.0 {
+        return Err(format!("{}: invalid price {}", source.name, price_usd));
+    }
+    let timestamp = body.pointer("/timestamp").and_then(Value::as_i64);
+
+    let signed = match &source.public_key {
+        Some(key) => {
+            let timestamp = timestamp.ok_or_else(|| format!("{}: signed quotes need a timestamp", source.name))?;
+            let signature = body.pointer("/signature").and_then(Value::as_str)
+                .and_then(|sig| hex::decode(sig).ok())
+                .ok_or_else(|| format!("{}: quote is not signed", source.name))?;
+            let key = hex::decode(key).map_err(|e| format!("{}: {}", source.name, e))?;
+            if !verify_signature(&key, &quote_message(source.pair, price_usd, timestamp), &signature) {
+                return Err(format!("{}: bad quote signature", source.name));
+            }
+            true
+        }
+        None => false,
+    };
+
+    let timestamp = timestamp.unwrap_or(now);
+    if timestamp > now + MAX_CLOCK_SKEW_SECS {
+        return Err(format!("{}: quote dated {}s in the future", source.name, timestamp - now));
+    }
+    Ok(Quote { source: source.name.clone(), pair: source.pair, price_usd, timestamp, signed })
+}
+
+/// Prices come as numbers or as decimal strings
+fn number(value: &Value) -> Option<f64> {
+    value.as_f64().or_else(|| value.as_str().and_then(|s| s.trim().parse().ok()))
+}
+
+/// Median of the `pair` quotes at most `max_age_secs` old at `now`, once
+/// `min_sources` of them are
+pub fn medianize(pair: Pair, quotes: &[Quote], now: i64, min_sources: usize, max_age_secs: i64) -> Result<ReferencePrice, String> {
+    let mut fresh: Vec<&Quote> = quotes.iter()
+        .filter(|q| q.pair == pair && now - q.timestamp <= max_age_secs)
+        .collect();
+    if fresh.len() < min_sources.max(1) {
+        return Err(format!("{}: {} fresh quote(s), {} needed", pair.name(), fresh.len(), min_sources.max(1)));
+    }
+    fresh.sort_by(|a, b| a.price_usd.total_cmp(&b.price_usd));
+    let mid = fresh.len() / 2;
+    let price_usd = if fresh.len().is_multiple_of(2) {
+        (fresh[mid - 1].price_usd + fresh[mid].price_usd) / 2.0
+    } else {
+        fresh[mid].price_usd
+    };
+    Ok(ReferencePrice {
+        pair,
+        price_usd,
+        sources: fresh.len(),
+        timestamp: fresh.iter().map(|q| q.timestamp).min().unwrap_or(now),
+    })
+}
+
+/// HTTP client and source list, cloned out of the oracle so fetching
+/// does not hold its lock
+#[derive(Clone)]
+pub struct QuoteFetcher {
+    client: reqwest::Client,
+    sources: Vec<OracleSource>,
+}
+
+impl QuoteFetcher {
+    /// Ask every source at once; one result per source, in config order
+    pub async fn fetch(&self) -> Vec<(String, Result<Quote, String>)> {
+        let mut requests = tokio::task::JoinSet::new();
+        for (i, source) in self.sources.iter().enumerate() {
+            let (client, source) = (self.client.clone(), source.clone());
+            requests.spawn(async move { (i, fetch_source(&client, &source).await) });
+        }
+        let mut results: Vec<Option<Result<Quote, String>>> = vec![None; self.sources.len()];
+        while let Some(joined) = requests.join_next().await {
+            if let Ok((i, result)) = joined {
+                results[i] = Some(result);
+            }
+        }
+        self.sources.iter().zip(results).map(|(source, result)| {
+            (source.name.clone(), result.unwrap_or_else(|| Err(format!("{}: fetch aborted", source.name))))
+        }).collect()
+    }
+}
+
+async fn fetch_source(client: &reqwest::Client, source: &OracleSource) -> Result<Quote, String> {
+    let response = client.get(&source.url).send().await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| format!("{}: {}", source.name, e))?;
+    let bytes = response.bytes().await.map_err(|e| format!("{}: {}", source.name, e))?;
+    let body: Value = serde_json::from_slice(&bytes).map_err(|e| format!("{}: {}", source.name, e))?;
+    parse_quote(source, &body, chrono::Utc::now().timestamp())
+}
+
+/// Latest quote and latest failure of every source
+pub struct PriceOracle {
+    config: OracleConfig,
+    client: reqwest::Client,
+    quotes: HashMap<String, Quote>,
+    errors: HashMap<String, SourceError>,
+}
+
+impl PriceOracle {
+    pub fn new(config: OracleConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(FETCH_TIMEOUT_SECS))
+            .user_agent(format!("divine-agi/{}", crate::VERSION))
+            .build()
+            .unwrap_or_default();
+        Self { config, client, quotes: HashMap::new(), errors: HashMap::new() }
+    }
+
+    pub fn from_env() -> Result<Self, String> {
+        OracleConfig::from_env().map(Self::new)
+    }
+
+    pub fn config(&self) -> &OracleConfig {
+        &self.config
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.config.sources.is_empty()
+    }
+
+    pub fn fetcher(&self) -> QuoteFetcher {
+        QuoteFetcher { client: self.client.clone(), sources: self.config.sources.clone() }
+    }
+
+    /// Keep each source's newest quote, or its error
+    pub fn record(&mut self, results: Vec<(String, Result<Quote, String>)>, now: i64) {
+        for (source, result) in results {
+            match result {
+                Ok(quote) => {
+                    self.errors.remove(&source);
+                    let newer = self.quotes.get(&source).is_none_or(|old| quote.timestamp >= old.timestamp);
+                    if newer {
+                        self.quotes.insert(source, quote);
+                    }
+                }
+                Err(error) => {
+                    tracing::warn!("💱 Oracle source {} failed: {}", source, error);
+                    self.errors.insert(source.clone(), SourceError { source, error, timestamp: now });
+                }
+            }
+        }
+    }
+
+    /// Fetch every source once and record the results
+    pub async fn refresh(&mut self) {
+        let results = self.fetcher().fetch().await;
+        self.record(results, chrono::Utc::now().timestamp());
+    }
+
+    pub fn price(&self, pair: Pair, now: i64) -> Result<ReferencePrice, String> {
+        let quotes: Vec<Quote> = self.quotes.values().cloned().collect();
+        medianize(pair, &quotes, now, self.config.min_sources, self.config.max_age_secs)
+    }
+
+    /// Every pair with a reference price at `now`
+    pub fn prices(&self, now: i64) -> Vec<ReferencePrice> {
+        Pair::ALL.into_iter().filter_map(|pair| self.price(pair, now).ok()).collect()
+    }
+
+    pub fn status(&self, now: i64) -> OracleStatus {
+        let mut quotes: Vec<Quote> = self.quotes.values().cloned().collect();
+        quotes.sort_by(|a, b| (a.pair, &a.source).cmp(&(b.pair, &b.source)));
+        let mut errors: Vec<SourceError> = self.errors.values().cloned().collect();
+        errors.sort_by(|a, b| a.source.cmp(&b.source));
+        OracleStatus { prices: self.prices(now), quotes, errors }
+    }
+}
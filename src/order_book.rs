@@ -0,0 +1,617 @@
+//! RSM Order Book V1 — limit orders for the RSM/USD market
+//!
+//! Bids and asks rest at whole-cent prices (`PRICE_TICK_USD`). An incoming
+//! order takes the best opposite price first and, within a price, the
+//! oldest order (price-time priority); fills trade at the resting order's
+//! price and whatever is left rests on the book. Market orders take any
+//! price and never rest: what the book cannot fill is cancelled. An order
+//! never trades with its own wallet: matching stops at the wallet's first
+//! resting order and the rest of the incoming order is cancelled (so the
+//! book never crosses). `RSMExchange::place_order` escrows the RSM of sell
+//! orders and credits buyers as they fill; USD is not held, as with
+//! `buy_rsm`.
+//!
+//! Every change is published as an `OrderBookUpdate` (depth plus the trades
+//! it made) to `subscribe()` receivers; trades aggregate into OHLC candles
+//! (`aggregate_candles`). The server stores every fill in `rsm_trades`
+//! and numbers new trades after the stored ones (`resume_ids`), so trade
+//! history and candles outlive the process.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+use serde::{Serialize, Deserialize};
+use tokio::sync::broadcast;
+
+use crate::consensus::EVENT_CHANNEL_CAPACITY;
+
+pub const PRICE_TICK_USD: f64 = 0.01;
+/// Smallest order; remainders below it count as filled
+pub const MIN_ORDER_RSM: f64 = 1e-8;
+pub const DEFAULT_DEPTH_LEVELS: usize = 20;
+pub const MAX_DEPTH_LEVELS: usize = 500;
+/// Candle intervals by name, in seconds
+pub const CANDLE_INTERVALS: &[(&str, i64)] = &[
+    ("1m", 60),
+    ("5m", 300),
+    ("15m", 900),
+    ("1h", 3600),
+    ("4h", 4 * 3600),
+    ("1d", 24 * 3600),
+];
+pub const MAX_CANDLES: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OrderType {
+    /// Trades at its price or better; the rest stays on the book
+    #[default]
+    Limit,
+    /// Trades at the best prices there are; the rest is cancelled
+    Market,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrderStatus {
+    Open,
+    PartiallyFilled,
+    Filled,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Order {
+    pub id: u64,
+    pub wallet: String,
+    pub side: Side,
+    #[serde(default)]
+    pub order_type: OrderType,
+    /// Limit price; 0 for market orders
+    pub price_usd: f64,
+    pub amount_rsm: f64,
+    pub filled_rsm: f64,
+    pub status: OrderStatus,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl Side {
+    pub fn name(self) -> &'static str {
+        match self {
+            Side::Buy => "buy",
+            Side::Sell => "sell",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Side::Buy, Side::Sell].into_iter().find(|side| side.name() == name)
+    }
+}
+
+impl Order {
+    pub fn remaining_rsm(&self) -> f64 {
+        self.amount_rsm - self.filled_rsm
+    }
+
+    pub fn is_open(&self) -> bool {
+        matches!(self.status, OrderStatus::Open | OrderStatus::PartiallyFilled)
+    }
+
+    fn fill(&mut self, amount_rsm: f64, now: i64) {
+        self.filled_rsm += amount_rsm;
+        self.updated_at = now;
+        self.status = if self.remaining_rsm() < MIN_ORDER_RSM { OrderStatus::Filled } else { OrderStatus::PartiallyFilled };
+    }
+}
+
+/// One fill between a resting (maker) and an incoming (taker) order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Trade {
+    pub id: u64,
+    /// The maker's price
+    pub price_usd: f64,
+    pub amount_rsm: f64,
+    pub buy_order_id: u64,
+    pub sell_order_id: u64,
+    pub buyer: String,
+    pub seller: String,
+    /// Side of the incoming order
+    pub taker_side: Side,
+    pub timestamp: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriceLevel {
+    pub price_usd: f64,
+    /// Remaining RSM resting at this price
+    pub amount_rsm: f64,
+    pub orders: usize,
+}
+
+/// Best price levels of each side: bids highest first, asks lowest first
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Depth {
+    pub bids: Vec<PriceLevel>,
+    pub asks: Vec<PriceLevel>,
+}
+
+impl Depth {
+    pub fn best_bid(&self) -> Option<f64> {
+        self.bids.first().map(|level| level.price_usd)
+    }
+
+    pub fn best_ask(&self) -> Option<f64> {
+        self.asks.first().map(|level| level.price_usd)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Candle {
+    /// Start of the interval (unix seconds)
+    pub open_time: i64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume_rsm: f64,
+    pub volume_usd: f64,
+    pub trades: usize,
+}
+
+/// Book after a change, and the trades the change made
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OrderBookUpdate {
+    /// Increases by one per change
+    pub sequence: u64,
+    pub depth: Depth,
+    pub trades: Vec<Trade>,
+}
+
+/// Seconds of a candle interval name (`1m`, `5m`, `15m`, `1h`, `4h`, `1d`)
+pub fn candle_interval(name: &str) -> Result<i64, String> {
+    CANDLE_INTERVALS.iter().find(|(n, _)| *n == name).map(|(_, secs)| *secs).ok_or_else(|| {
+        let names: Vec<&str> = CANDLE_INTERVALS.iter().map(|(n, _)| *n).collect();
+        format!("Unknown candle interval {} (expected one of {})", name, names.join(", "))
+    })
+}
+
+/// OHLC candles of `interval_secs` over `trades` (oldest first), the last
+/// `limit` of them; intervals without trades are skipped
+pub fn aggregate_candles<'a>(trades: impl IntoIterator<Item = &'a Trade>, interval_secs: i64, limit: usize) -> Vec<Candle> {
+    let mut candles: Vec<Candle> = Vec::new();
+    for trade in trades {
+        let open_time = trade.timestamp - trade.timestamp.rem_euclid(interval_secs);
+        match candles.last_mut() {
+            Some(candle) if candle.open_time == open_time => {
+                candle.high = candle.high.max(trade.price_usd);
+                candle.low = candle.low.min(trade.price_usd);
+                candle.close = trade.price_usd;
+                candle.volume_rsm += trade.amount_rsm;
+                candle.volume_usd += trade.amount_rsm * trade.price_usd;
+                candle.trades += 1;
+            }
+            _ => candles.push(Candle {
+                open_time,
+                open: trade.price_usd,
+                high: trade.price_usd,
+                low: trade.price_usd,
+                close: trade.price_usd,
+                volume_rsm: trade.amount_rsm,
+                volume_usd: trade.amount_rsm * trade.price_usd,
+                trades: 1,
+            }),
+        }
+    }
+    let skip = candles.len().saturating_sub(limit);
+    candles.split_off(skip)
+}
+
+fn update_channel() -> broadcast::Sender<OrderBookUpdate> {
+    broadcast::channel(EVENT_CHANNEL_CAPACITY).0
+}
+
+/// Index of an arena slot; `NIL` ends a level's queue
+type SlotId = u32;
+const NIL: SlotId = SlotId::MAX;
+
+/// A resting order, linked into the queue of its price level
+#[derive(Debug, Clone)]
+struct Slot {
+    order: Order,
+    ticks: u64,
+    prev: SlotId,
+    next: SlotId,
+}
+
+/// Orders resting at one price, oldest first, and their total
+#[derive(Debug, Clone, Copy)]
+struct Level {
+    head: SlotId,
+    tail: SlotId,
+    amount_rsm: f64,
+    orders: usize,
+}
+
+/// Resting orders live in an arena of slots reused once freed, each linked
+/// into its price level's queue, so resting, filling and cancelling an
+/// order (even from the middle of a queue) touch only that order and its
+/// neighbours, and depth reads the per-level totals
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "BookState", into = "BookState")]
+pub struct OrderBook {
+    bids: BTreeMap<u64, Level>,
+    asks: BTreeMap<u64, Level>,
+    slots: Vec<Option<Slot>>,
+    free: Vec<SlotId>,
+    /// Slot of every resting order
+    resting: HashMap<u64, SlotId>,
+    /// Filled and cancelled orders
+    closed: HashMap<u64, Order>,
+    trades: Vec<Trade>,
+    /// Trades stored by earlier runs (see `resume_ids`)
+    trade_id_base: u64,
+    next_order_id: u64,
+    sequence: u64,
+    updates: broadcast::Sender<OrderBookUpdate>,
+}
+
+/// How an `OrderBook` is serialized: resting order ids per price tick,
+/// oldest first, and every order by id
+#[derive(Serialize, Deserialize)]
+struct BookState {
+    bids: BTreeMap<u64, VecDeque<u64>>,
+    asks: BTreeMap<u64, VecDeque<u64>>,
+    orders: HashMap<u64, Order>,
+    trades: Vec<Trade>,
+    #[serde(default)]
+    trade_id_base: u64,
+    next_order_id: u64,
+    sequence: u64,
+}
+
+impl From<OrderBook> for BookState {
+    fn from(book: OrderBook) -> Self {
+        let queues = |levels: &BTreeMap<u64, Level>| -> BTreeMap<u64, VecDeque<u64>> {
+            levels.iter().map(|(&ticks, level)| {
+                let mut ids = VecDeque::with_capacity(level.orders);
+                let mut at = level.head;
+                while at != NIL {
+                    let slot = book.slot(at);
+                    ids.push_back(slot.order.id);
+                    at = slot.next;
+                }
+                (ticks, ids)
+            }).collect()
+        };
+        let (bids, asks) = (queues(&book.bids), queues(&book.asks));
+        let mut orders = book.closed.clone();
+        orders.extend(book.slots.iter().flatten().map(|slot| (slot.order.id, slot.order.clone())));
+        BookState {
+            bids,
+            asks,
+            orders,
+            trades: book.trades,
+            trade_id_base: book.trade_id_base,
+            next_order_id: book.next_order_id,
+            sequence: book.sequence,
+        }
+    }
+}
+
+impl From<BookState> for OrderBook {
+    fn from(mut state: BookState) -> Self {
+        let mut book = OrderBook::with_capacity(state.orders.len());
+        for (ticks, ids) in state.bids.iter().chain(&state.asks) {
+            for id in ids {
+                if let Some(order) = state.orders.remove(id) {
+                    book.rest(order, *ticks);
+                }
+            }
+        }
+        book.closed = state.orders;
+        book.trades = state.trades;
+        book.trade_id_base = state.trade_id_base;
+        book.next_order_id = state.next_order_id;
+        book.sequence = state.sequence;
+        book
+    }
+}
+
+impl Default for OrderBook {
+    fn default() -> Self {
+        Self::with_capacity(0)
+    }
+}
+
+impl OrderBook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Book with room for `orders` resting orders before it reallocates
+    pub fn with_capacity(orders: usize) -> Self {
+        Self {
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+            slots: Vec::with_capacity(orders),
+            free: Vec::new(),
+            resting: HashMap::with_capacity(orders),
+            closed: HashMap::new(),
+            trades: Vec::new(),
+            trade_id_base: 0,
+            next_order_id: 0,
+            sequence: 0,
+            updates: update_channel(),
+        }
+    }
+
+    /// Price in ticks, if `price_usd` and `amount_rsm` make a valid order
+    pub fn validate(price_usd: f64, amount_rsm: f64) -> Result<u64, String> {
+        if !price_usd.is_finite() || price_usd <= 0.0 {
+            return Err(format!("Invalid price {}", price_usd));
+        }
+        Self::validate_amount(amount_rsm)?;
+        let ticks = (price_usd / PRICE_TICK_USD).round();
+        if (ticks * PRICE_TICK_USD - price_usd).abs() > PRICE_TICK_USD * 1e-6 || ticks > u64::MAX as f64 {
+            return Err(format!("Price {} is not a multiple of ${}", price_usd, PRICE_TICK_USD));
+        }
+        Ok(ticks as u64)
+    }
+
+    pub fn validate_amount(amount_rsm: f64) -> Result<(), String> {
+        if !amount_rsm.is_finite() || amount_rsm < MIN_ORDER_RSM {
+            return Err(format!("Invalid amount {} (minimum {} RSM)", amount_rsm, MIN_ORDER_RSM));
+        }
+        Ok(())
+    }
+
+    /// Match a new limit order against the book and rest what is left;
+    /// returns the order as it stands and the trades it made
+    pub fn place(&mut self, wallet: &str, side: Side, price_usd: f64, amount_rsm: f64, now: i64) -> Result<(Order, Vec<Trade>), String> {
+        let ticks = Self::validate(price_usd, amount_rsm)?;
+        Ok(self.execute(wallet, side, Some(ticks), amount_rsm, now))
+    }
+
+    /// Match a new market order against the book; what it cannot fill is
+    /// cancelled. Refused when the other side of the book is empty
+    pub fn place_market(&mut self, wallet: &str, side: Side, amount_rsm: f64, now: i64) -> Result<(Order, Vec<Trade>), String> {
+        Self::validate_amount(amount_rsm)?;
+        let (resting, other) = match side {
+            Side::Buy => (&self.asks, "sell"),
+            Side::Sell => (&self.bids, "buy"),
+        };
+        if resting.is_empty() {
+            return Err(format!("No {} orders on the book", other));
+        }
+        Ok(self.execute(wallet, side, None, amount_rsm, now))
+    }
+
+    /// Match an order limited to `limit` ticks (`None`: a market order)
+    /// and rest what a limit order has left
+    fn execute(&mut self, wallet: &str, side: Side, limit: Option<u64>, amount_rsm: f64, now: i64) -> (Order, Vec<Trade>) {
+        self.next_order_id += 1;
+        let mut order = Order {
+            id: self.next_order_id,
+            wallet: wallet.to_string(),
+            side,
+            order_type: if limit.is_some() { OrderType::Limit } else { OrderType::Market },
+            price_usd: limit.map_or(0.0, |ticks| ticks as f64 * PRICE_TICK_USD),
+            amount_rsm,
+            filled_rsm: 0.0,
+            status: OrderStatus::Open,
+            created_at: now,
+            updated_at: now,
+        };
+
+        let mut trades = Vec::new();
+        let mut self_match = false;
+        while order.remaining_rsm() >= MIN_ORDER_RSM {
+            let book = match side {
+                Side::Buy => &mut self.asks,
+                Side::Sell => &mut self.bids,
+            };
+            let best = match side {
+                Side::Buy => book.first_key_value().map(|(&t, _)| t).filter(|&t| limit.is_none_or(|l| t <= l)),
+                Side::Sell => book.last_key_value().map(|(&t, _)| t).filter(|&t| limit.is_none_or(|l| t >= l)),
+            };
+            let Some(level_ticks) = best else {
+                break;
+            };
+            let level = book.get_mut(&level_ticks).expect("level exists");
+            let head = level.head;
+            let maker = &mut self.slots[head as usize].as_mut().expect("levels link live slots").order;
+            if maker.wallet == order.wallet {
+                self_match = true;
+                break;
+            }
+
+            let amount = order.remaining_rsm().min(maker.remaining_rsm());
+            maker.fill(amount, now);
+            order.fill(amount, now);
+            level.amount_rsm -= amount;
+            let (buy, sell) = match side {
+                Side::Buy => (&order, &*maker),
+                Side::Sell => (&*maker, &order),
+            };
+            trades.push(Trade {
+                id: self.trade_id_base + self.trades.len() as u64 + trades.len() as u64 + 1,
+                price_usd: maker.price_usd,
+                amount_rsm: amount,
+                buy_order_id: buy.id,
+                sell_order_id: sell.id,
+                buyer: buy.wallet.clone(),
+                seller: sell.wallet.clone(),
+                taker_side: side,
+                timestamp: now,
+            });
+
+            if maker.status == OrderStatus::Filled {
+                let filled = self.unrest(head);
+                self.closed.insert(filled.id, filled);
+            }
+        }
+
+        match limit {
+            Some(ticks) if order.is_open() && !self_match => self.rest(order.clone(), ticks),
+            _ => {
+                if order.is_open() {
+                    order.status = OrderStatus::Cancelled;
+                }
+                self.closed.insert(order.id, order.clone());
+            }
+        }
+        self.trades.extend(trades.iter().cloned());
+        self.publish(&trades);
+        (order, trades)
+    }
+
+    /// Put `order` at the back of the queue at `ticks`
+    fn rest(&mut self, order: Order, ticks: u64) {
+        let book = match order.side {
+            Side::Buy => &mut self.bids,
+            Side::Sell => &mut self.asks,
+        };
+        let (id, remaining) = (order.id, order.remaining_rsm());
+        let slot = Slot { order, ticks, prev: NIL, next: NIL };
+        let at = match self.free.pop() {
+            Some(at) => {
+                self.slots[at as usize] = Some(slot);
+                at
+            }
+            None => {
+                self.slots.push(Some(slot));
+                (self.slots.len() - 1) as SlotId
+            }
+        };
+        self.resting.insert(id, at);
+
+        match book.get_mut(&ticks) {
+            Some(level) => {
+                self.slots[level.tail as usize].as_mut().expect("levels link live slots").next = at;
+                self.slots[at as usize].as_mut().expect("slot just filled").prev = level.tail;
+                level.tail = at;
+                level.amount_rsm += remaining;
+                level.orders += 1;
+            }
+            None => {
+                book.insert(ticks, Level { head: at, tail: at, amount_rsm: remaining, orders: 1 });
+            }
+        }
+    }
+
+    /// Take the order in slot `at` off the book and free the slot
+    fn unrest(&mut self, at: SlotId) -> Order {
+        let Slot { order, ticks, prev, next } = self.slots[at as usize].take().expect("resting orders have a slot");
+        self.free.push(at);
+        self.resting.remove(&order.id);
+
+        if prev != NIL {
+            self.slots[prev as usize].as_mut().expect("levels link live slots").next = next;
+        }
+        if next != NIL {
+            self.slots[next as usize].as_mut().expect("levels link live slots").prev = prev;
+        }
+        let book = match order.side {
+            Side::Buy => &mut self.bids,
+            Side::Sell => &mut self.asks,
+        };
+        let level = book.get_mut(&ticks).expect("resting orders have a level");
+        level.orders -= 1;
+        if level.orders == 0 {
+            book.remove(&ticks);
+        } else {
+            if level.head == at {
+                level.head = next;
+            }
+            if level.tail == at {
+                level.tail = prev;
+            }
+            level.amount_rsm -= order.remaining_rsm();
+        }
+        order
+    }
+
+    fn slot(&self, at: SlotId) -> &Slot {
+        self.slots[at as usize].as_ref().expect("levels link live slots")
+    }
+
+    /// Take an open order off the book
+    pub fn cancel(&mut self, id: u64, now: i64) -> Result<Order, String> {
+        let Some(&at) = self.resting.get(&id) else {
+            return Err(match self.closed.get(&id) {
+                Some(order) => format!("Order {} is {:?}", id, order.status),
+                None => format!("Order {} not found", id),
+            });
+        };
+        let mut order = self.unrest(at);
+        order.status = OrderStatus::Cancelled;
+        order.updated_at = now;
+        self.closed.insert(id, order.clone());
+        self.publish(&[]);
+        Ok(order)
+    }
+
+    pub fn order(&self, id: u64) -> Option<&Order> {
+        match self.resting.get(&id) {
+            Some(&at) => Some(&self.slot(at).order),
+            None => self.closed.get(&id),
+        }
+    }
+
+    /// Up to `levels` best prices per side
+    pub fn depth(&self, levels: usize) -> Depth {
+        let level = |(&ticks, level): (&u64, &Level)| PriceLevel {
+            price_usd: ticks as f64 * PRICE_TICK_USD,
+            amount_rsm: level.amount_rsm,
+            orders: level.orders,
+        };
+        Depth {
+            bids: self.bids.iter().rev().take(levels).map(level).collect(),
+            asks: self.asks.iter().take(levels).map(level).collect(),
+        }
+    }
+
+    /// Latest trades, newest first
+    pub fn recent_trades(&self, limit: usize) -> Vec<Trade> {
+        self.trades.iter().rev().take(limit).cloned().collect()
+    }
+
+    /// Last `limit` candles of `interval_secs`, oldest first; intervals
+    /// without trades are skipped
+    pub fn candles(&self, interval_secs: i64, limit: usize) -> Vec<Candle> {
+        aggregate_candles(&self.trades, interval_secs, limit)
+    }
+
+    /// Number trades after `last_trade_id` and orders after
+    /// `last_order_id`, those of an earlier run already stored
+    pub fn resume_ids(&mut self, last_trade_id: u64, last_order_id: u64) {
+        self.trade_id_base = self.trade_id_base.max(last_trade_id.saturating_sub(self.trades.len() as u64));
+        self.next_order_id = self.next_order_id.max(last_order_id);
+    }
+
+    /// Current book as an update without trades (for new subscribers)
+    pub fn snapshot(&self) -> OrderBookUpdate {
+        OrderBookUpdate { sequence: self.sequence, depth: self.depth(DEFAULT_DEPTH_LEVELS), trades: Vec::new() }
+    }
+
+    /// Receive book updates from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<OrderBookUpdate> {
+        self.updates.subscribe()
+    }
+
+    fn publish(&mut self, trades: &[Trade]) {
+        self.sequence += 1;
+        // Nobody to build the depth for
+        if self.updates.receiver_count() == 0 {
+            return;
+        }
+        let update = OrderBookUpdate { sequence: self.sequence, depth: self.depth(DEFAULT_DEPTH_LEVELS), trades: trades.to_vec() };
+        let _ = self.updates.send(update);
+    }
+}
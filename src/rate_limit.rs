@@ -0,0 +1,148 @@
+//! Rate Limiting V1 — per-client token buckets
+//!
+//! Every client (API key, or IP address without one) has a bucket holding up
+//! to `burst` tokens that refills at `refill_per_sec`. A request takes its
+//! route's cost from the bucket; when the bucket cannot cover it the API
+//! answers `429 Too Many Requests` with `Retry-After` set to the seconds
+//! until it can. Expensive routes cost more than one token, so a client can
+//! make many cheap reads but only a few evolution jobs per minute.
+//!
+//! Configured with `RATE_LIMIT_BURST` (default 120) and `RATE_LIMIT_PER_SEC`
+//! (default 2); `RATE_LIMIT_PER_SEC=0` turns limiting off.
+//!
+//! Anonymous clients are keyed by the socket peer address. Behind a reverse
+//! proxy (Railway) every request comes from the proxy, so
+//! `RATE_LIMIT_TRUST_PROXY=true` keys them by the last `X-Forwarded-For`
+//! hop instead: the one the proxy appended. Earlier hops come from the
+//! client and are ignored.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::time::Instant;
+
+use axum::http::HeaderMap;
+use serde::{Serialize, Deserialize};
+
+pub const DEFAULT_BURST: f64 = 120.0;
+pub const DEFAULT_REFILL_PER_SEC: f64 = 2.0;
+
+/// Most buckets kept: full (idle) ones are dropped first, then the least
+/// recently used until `PRUNED_CLIENTS` remain
+pub const MAX_TRACKED_CLIENTS: usize = 10_000;
+/// Buckets left after a prune, so the next one is a thousand clients away
+pub const PRUNED_CLIENTS: usize = MAX_TRACKED_CLIENTS * 9 / 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RateLimitConfig {
+    pub burst: f64,
+    pub refill_per_sec: f64,
+    /// Key anonymous clients by the proxy's `X-Forwarded-For` hop
+    #[serde(default)]
+    pub trust_proxy: bool,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self { burst: DEFAULT_BURST, refill_per_sec: DEFAULT_REFILL_PER_SEC, trust_proxy: false }
+    }
+}
+
+impl RateLimitConfig {
+    pub fn from_env() -> Self {
+        let var = |name: &str, default: f64| {
+            std::env::var(name).ok().and_then(|v| v.parse().ok()).filter(|v: &f64| *v >= 0.0).unwrap_or(default)
+        };
+        Self {
+            burst: var("RATE_LIMIT_BURST", DEFAULT_BURST),
+            refill_per_sec: var("RATE_LIMIT_PER_SEC", DEFAULT_REFILL_PER_SEC),
+            trust_proxy: std::env::var("RATE_LIMIT_TRUST_PROXY").is_ok_and(|v| matches!(v.as_str(), "1" | "true")),
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.refill_per_sec > 0.0
+    }
+
+    /// The address an anonymous client is limited by: the last
+    /// `X-Forwarded-For` hop behind a trusted proxy, else the socket peer
+    pub fn client_ip(&self, headers: &HeaderMap, peer: Option<IpAddr>) -> String {
+        let forwarded = self.trust_proxy
+            .then(|| headers.get_all("x-forwarded-for").iter().next_back())
+            .flatten()
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.rsplit(',').next())
+            .and_then(|hop| hop.trim().parse::<IpAddr>().ok());
+        forwarded.or(peer).map_or_else(|| "unknown".into(), |ip| ip.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    pub config: RateLimitConfig,
+    buckets: HashMap<String, Bucket>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self { config, buckets: HashMap::new() }
+    }
+
+    pub fn check(&mut self, client: &str, cost: f64) -> Result<(), u64> {
+        self.check_at(client, cost, Instant::now())
+    }
+
+    /// Take `cost` tokens from `client`'s bucket, or the whole seconds until
+    /// it holds enough. Costs above the burst are capped at it.
+    pub fn check_at(&mut self, client: &str, cost: f64, now: Instant) -> Result<(), u64> {
+        if !self.config.enabled() {
+            return Ok(());
+        }
+        if self.buckets.len() >= MAX_TRACKED_CLIENTS {
+            self.prune(now);
+        }
+
+        let RateLimitConfig { burst, refill_per_sec, .. } = self.config;
+        let bucket = self.buckets.entry(client.to_string()).or_insert(Bucket { tokens: burst, updated: now });
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * refill_per_sec).min(burst);
+        bucket.updated = now;
+
+        let cost = cost.min(burst);
+        if bucket.tokens >= cost {
+            bucket.tokens -= cost;
+            Ok(())
+        } else {
+            Err(((cost - bucket.tokens) / refill_per_sec).ceil().max(1.0) as u64)
+        }
+    }
+
+    /// Clients currently tracked
+    pub fn clients(&self) -> usize {
+        self.buckets.len()
+    }
+
+    /// Drop buckets that have refilled completely (same as new ones), then
+    /// the least recently used until at most `PRUNED_CLIENTS` remain
+    fn prune(&mut self, now: Instant) {
+        let RateLimitConfig { burst, refill_per_sec, .. } = self.config;
+        self.buckets.retain(|_, b| b.tokens + now.saturating_duration_since(b.updated).as_secs_f64() * refill_per_sec < burst);
+        if self.buckets.len() > PRUNED_CLIENTS {
+            let mut updated: Vec<Instant> = self.buckets.values().map(|b| b.updated).collect();
+            let excess = updated.len() - PRUNED_CLIENTS;
+            let (_, cutoff, _) = updated.select_nth_unstable(excess - 1);
+            let cutoff = *cutoff;
+            let mut evicted = 0;
+            self.buckets.retain(|_, b| {
+                let evict = evicted < excess && b.updated <= cutoff;
+                evicted += evict as usize;
+                !evict
+            });
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! Response Cache V1 — ETags and a short-TTL cache for hot reads
+//!
+//! Every `200` GET response gets a strong `ETag` (a hash of its body) and
+//! `Cache-Control: no-cache`, so browsers revalidate with `If-None-Match` and
+//! get an empty `304 Not Modified` while nothing changed. Expensive reads
+//! (stats, list queries, genome metrics) are also kept in memory for
+//! `RESPONSE_CACHE_TTL_MS` (default 1000): a dashboard polling every second
+//! costs one Postgres query per TTL however many tabs are open. Any
+//! successful write empties the cache; chain and exchange changes made in the
+//! background show up within one TTL. `RESPONSE_CACHE_TTL_MS=0` turns the
+//! cache off (ETags stay).
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use axum::body::Bytes;
+use sha2::{Digest, Sha256};
+
+pub const DEFAULT_TTL_MS: u64 = 1000;
+
+/// Responses kept before expired ones are dropped (then the oldest)
+const MAX_CACHED_RESPONSES: usize = 1024;
+
+/// A stored `200` response body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedResponse {
+    pub body: Bytes,
+    pub content_type: Option<String>,
+    pub etag: String,
+    stored: Instant,
+}
+
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    pub ttl: Duration,
+    entries: HashMap<String, CachedResponse>,
+}
+
+impl ResponseCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self { ttl, entries: HashMap::new() }
+    }
+
+    /// `RESPONSE_CACHE_TTL_MS`, or `DEFAULT_TTL_MS`
+    pub fn from_env() -> Self {
+        let ttl = std::env::var("RESPONSE_CACHE_TTL_MS").ok().and_then(|v| v.parse().ok()).unwrap_or(DEFAULT_TTL_MS);
+        Self::new(Duration::from_millis(ttl))
+    }
+
+    pub fn enabled(&self) -> bool {
+        !self.ttl.is_zero()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&CachedResponse> {
+        self.get_at(key, Instant::now())
+    }
+
+    /// Response stored under `key` less than a TTL before `now`
+    pub fn get_at(&self, key: &str, now: Instant) -> Option<&CachedResponse> {
+        self.entries.get(key).filter(|entry| now.saturating_duration_since(entry.stored) < self.ttl)
+    }
+
+    pub fn insert(&mut self, key: String, body: Bytes, content_type: Option<String>) -> String {
+        self.insert_at(key, body, content_type, Instant::now())
+    }
+
+    /// Store a response and return its ETag
+    pub fn insert_at(&mut self, key: String, body: Bytes, content_type: Option<String>, now: Instant) -> String {
+        let tag = etag(&body);
+        if !self.enabled() {
+            return tag;
+        }
+        if self.entries.len() >= MAX_CACHED_RESPONSES && !self.entries.contains_key(&key) {
+            let ttl = self.ttl;
+            self.entries.retain(|_, entry| now.saturating_duration_since(entry.stored) < ttl);
+            if self.entries.len() >= MAX_CACHED_RESPONSES {
+                let oldest = self.entries.iter().min_by_key(|(_, entry)| entry.stored).map(|(key, _)| key.clone());
+                self.entries.remove(&oldest.unwrap_or_default());
+            }
+        }
+        self.entries.insert(key, CachedResponse { body, content_type, etag: tag.clone(), stored: now });
+        tag
+    }
+
+    /// Forget everything (after a write)
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Strong ETag of a response body
+pub fn etag(body: &[u8]) -> String {
+    format!("\"{}\"", hex::encode(&Sha256::digest(body)[..16]))
+}
+
+/// Whether an `If-None-Match` header matches `etag` (weak comparison, as
+/// RFC 9110 asks for GET)
+pub fn if_none_match(header: &str, etag: &str) -> bool {
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    header.trim() == "*" || header.split(',').any(|tag| opaque(tag) == opaque(etag))
+}
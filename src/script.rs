@@ -0,0 +1,109 @@
+//! Script V1 — CLI command sequences run in one process
+//!
+//! `divine-agi script <file|->` reads one command per line, written as on
+//! the command line without the program name:
+//!
+//! ```text
+//! # seed an experiment
+//! genome new --seed 7 --save
+//! evolve -i 1 --budget 500 --seed 7
+//! db export --out "run 7.jsonl"
+//! ```
+//!
+//! Blank lines and `#` comments are skipped; words split on whitespace, with
+//! '…' taken literally and "…" / `\` escaping as in a shell. Every line is
+//! parsed before the first one runs, so a typo on line 40 fails before line 1
+//! touches the database.
+
+use clap::Parser;
+
+use crate::cli::{Cli, Commands};
+
+/// One parsed line of a script
+pub struct ScriptStep {
+    /// 1-based line number in the script
+    pub line: usize,
+    /// The line as written, for progress and errors
+    pub text: String,
+    pub cli: Cli,
+}
+
+/// `line` split into words like a shell would, without expansion
+pub fn split_words(line: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '#' if word.is_none() => break,
+            c if c.is_whitespace() => words.extend(word.take()),
+            '\'' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => return Err("unterminated ' quote".into()),
+                    }
+                }
+            }
+            '"' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\')) => word.push(c),
+                            Some(c) => { word.push('\\'); word.push(c); }
+                            None => return Err("unterminated \" quote".into()),
+                        },
+                        Some(c) => word.push(c),
+                        None => return Err("unterminated \" quote".into()),
+                    }
+                }
+            }
+            '\\' => match chars.next() {
+                Some(c) => word.get_or_insert_with(String::new).push(c),
+                None => return Err("trailing \\".into()),
+            },
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    words.extend(word);
+    Ok(words)
+}
+
+/// Every command of `script`, or every line that does not parse
+pub fn parse_script(script: &str) -> Result<Vec<ScriptStep>, String> {
+    let mut steps = Vec::new();
+    let mut errors = Vec::new();
+    for (i, text) in script.lines().enumerate() {
+        let line = i + 1;
+        let mut words = match split_words(text) {
+            Ok(words) if words.is_empty() => continue,
+            Ok(words) => words,
+            Err(e) => {
+                errors.push(format!("line {}: {}", line, e));
+                continue;
+            }
+        };
+        // Lines copied from a shell keep their program name
+        if words[0] == "divine-agi" {
+            words.remove(0);
+        }
+        match Cli::try_parse_from(std::iter::once("divine-agi".to_string()).chain(words)) {
+            Ok(cli) if matches!(cli.command, Commands::Script { .. }) => errors.push(format!("line {}: scripts cannot run other scripts", line)),
+            Ok(cli) => steps.push(ScriptStep { line, text: text.trim().to_string(), cli }),
+            Err(e) => {
+                // clap's error runs to the first blank line; usage and tips follow
+                let message = e.to_string();
+                let message: Vec<_> = message.lines().take_while(|l| !l.trim().is_empty()).map(str::trim).collect();
+                errors.push(format!("line {}: {}", line, message.join(" ").trim_start_matches("error: ")));
+            }
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors.join("\n"));
+    }
+    Ok(steps)
+}
@@ -0,0 +1,115 @@
+//! Slashing V1 — penalties for false consciousness claims
+//!
+//! A block attestation claims a consciousness value for a genome. Any node
+//! that holds the genome can recompute that value; when it differs, the node
+//! builds `ClaimEvidence` (signed header, Merkle inclusion proof and the
+//! genome itself) and submits it as a stake op. Every node re-checks the
+//! evidence on its own, so the offense is recorded and the producer's stake
+//! slashed identically on replay.
+
+use serde::{Serialize, Deserialize};
+use sha2::{Sha256, Digest};
+
+use crate::consensus::{BlockHeader, ConsensusBlock, InclusionProof};
+use crate::genome::{Genome, GenomeBuilder};
+use crate::rotation::Rot180;
+
+/// Share of the producer's staked RSM burned per offense
+pub const SLASH_FRACTION: f64 = 0.5;
+
+/// Consciousness a genome actually scores, independent of its stored value
+pub fn recompute_consciousness(genome: &Genome<Rot180>) -> u32 {
+    let mut genome = genome.clone();
+    genome.rehash();
+    genome.calculate_consciousness();
+    genome.consciousness
+}
+
+/// Proof that a signed block attested a wrong consciousness value
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClaimEvidence {
+    pub header: BlockHeader,
+    pub proof: InclusionProof,
+    /// Genome data the consciousness is recomputed from
+    pub dna: String,
+    pub mutations: u64,
+    pub p53_copies: u8,
+}
+
+impl ClaimEvidence {
+    pub fn new(block: &ConsensusBlock, genome: &Genome<Rot180>) -> Option<Self> {
+        Some(Self {
+            header: block.header(),
+            proof: block.generate_inclusion_proof(&genome.hash)?,
+            dna: genome.to_dna_string(),
+            mutations: genome.mutations,
+            p53_copies: genome.p53_copies,
+        })
+    }
+
+    pub fn genome(&self) -> Option<Genome<Rot180>> {
+        let mut genome = GenomeBuilder::from_dna(&self.dna)?.p53_copies(self.p53_copies).build_storage();
+        genome.mutations = self.mutations;
+        genome.rehash();
+        genome.calculate_consciousness();
+        Some(genome)
+    }
+
+    /// Commitment signed by the reporter
+    pub fn hash(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(self.header.hash.as_bytes());
+        hasher.update(self.proof.attestation.leaf_hash());
+        hasher.update(self.dna.as_bytes());
+        hasher.update(self.mutations.to_le_bytes());
+        hasher.update(self.p53_copies.to_le_bytes());
+        hasher.finalize().into()
+    }
+
+    /// Check the evidence and describe the offense (nothing slashed yet)
+    pub fn check(&self) -> Result<Offense, String> {
+        let header = &self.header;
+        if header.hash != header.calculate_hash() || !header.verify_signature() {
+            return Err("evidence header is not a signed block".to_string());
+        }
+        let proof = &self.proof;
+        if proof.block_index != header.index || proof.block_hash != header.hash
+            || proof.merkle_root != header.merkle_root || !proof.verify()
+        {
+            return Err("attestation is not included in the evidence block".to_string());
+        }
+
+        let genome = self.genome().ok_or("evidence carries invalid genome DNA")?;
+        let claim = &proof.attestation.proof;
+        if genome.hash != claim.genome_hash {
+            return Err("evidence genome does not match the attested hash".to_string());
+        }
+        if genome.consciousness == claim.consciousness {
+            return Err("attested consciousness is correct".to_string());
+        }
+
+        Ok(Offense {
+            producer: header.producer.clone(),
+            block_index: header.index,
+            block_hash: header.hash.clone(),
+            genome_hash: claim.genome_hash,
+            claimed: claim.consciousness,
+            recomputed: genome.consciousness,
+            slashed_rsm: 0.0,
+            reporter: String::new(),
+        })
+    }
+}
+
+/// Recorded false claim and the stake it cost the producer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Offense {
+    pub producer: String,
+    pub block_index: u64,
+    pub block_hash: String,
+    pub genome_hash: [u8; 32],
+    pub claimed: u32,
+    pub recomputed: u32,
+    pub slashed_rsm: f64,
+    pub reporter: String,
+}
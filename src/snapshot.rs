@@ -0,0 +1,55 @@
+//! State Snapshots V1 — pruned chain state
+//!
+//! Pruning drops the attestations of blocks deeper than a configurable depth.
+//! Their headers (and stake ops, which the header hash commits to) stay on
+//! the chain, and the state they produced is folded into a `ChainSnapshot`:
+//! counters, total consciousness, the reward ledger, stakes and the genomes
+//! already consecrated. Replays (reorgs, pruning itself) start from the
+//! snapshot instead of genesis.
+//!
+//! A snapshot file (JSON) carries the snapshot with the pruned chain, so a
+//! new node can bootstrap from it and fetch only the blocks above it.
+
+use serde::{Serialize, Deserialize};
+
+use crate::chain_file::ChainParams;
+use crate::consensus::ConsensusBlock;
+use crate::genesis::GenesisConfig;
+use crate::staking::{StakedGenome, StakingState};
+use crate::wallet::WalletManager;
+
+pub const SNAPSHOT_VERSION: u16 = 1;
+
+/// Engine state right after block `height`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainSnapshot {
+    pub height: u64,
+    pub block_hash: String,
+    pub total_consciousness: u64,
+    pub proofs_validated: u64,
+    pub total_rewards_distributed: f64,
+    pub min_consciousness: u32,
+    /// Difficulty expected for block `height + 1`
+    pub difficulty: u32,
+    pub wallets: WalletManager,
+    pub staking: StakingState,
+    /// Genomes attested in pruned blocks
+    pub consecrated: Vec<StakedGenome>,
+}
+
+impl ChainSnapshot {
+    pub fn consecrated(&self, genome_hash: &[u8; 32]) -> Option<&StakedGenome> {
+        self.consecrated.iter().find(|g| &g.genome_hash == genome_hash)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotFile {
+    pub version: u16,
+    pub params: ChainParams,
+    pub genesis: GenesisConfig,
+    pub validators: Vec<String>,
+    pub snapshot: ChainSnapshot,
+    /// Genesis through the snapshot block, attestations pruned
+    pub blocks: Vec<ConsensusBlock>,
+}
@@ -0,0 +1,814 @@
+//! Solana Archiving V1 — genome hashes as memos on devnet or mainnet
+//!
+//! With `SOLANA_WALLET` set, a Solana archive is a real transaction with one
+//! instruction to the SPL memo program carrying
+//! `DIVINE|v15|id:<genome>|dna:<hash>`, so anyone can read the archive back
+//! from the transaction. It is paid and signed by the wallet's own Solana
+//! key: the wallet file (`SOLANA_WALLET`, sealed with
+//! `SOLANA_WALLET_PASSWORD`) must come from a recovery phrase, and account
+//! `SOLANA_ACCOUNT` (default 0) is derived from its seed on the path
+//! `m/44'/501'/<account>'/0'` (SLIP-0010), the one Solana wallets use — the
+//! same phrase shows the same address there.
+//!
+//! `SOLANA_CLUSTER` picks `devnet` (default) or `mainnet`; `SOLANA_RPC_URL`
+//! overrides the cluster's public endpoint, and must serve that cluster. Every
+//! archive entry then carries its signature's status: the slot it landed in
+//! and its commitment (`processed`, `confirmed`, `finalized`), or `failed`,
+//! or `expired` when its blockhash ran out before it landed.
+//!
+//! Without the wallet the layer stays simulated.
+//!
+//! The same key moves the wallet's coins: `compile_message` builds legacy
+//! messages paid by it, `sol_transfer` and `spl_transfer` the system and SPL
+//! token instructions (to the recipient's associated token account,
+//! `find_program_address` as the runtime derives it), and `SolanaRpc` reads
+//! SOL and token balances. On devnet `request_airdrop` asks the faucet for
+//! test SOL (up to `MAX_AIRDROP_SOL` at once).
+
+use std::time::Duration;
+
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use ring::signature::{Ed25519KeyPair, KeyPair, UnparsedPublicKey, ED25519};
+use serde::{Serialize, Deserialize};
+use serde_json::{json, Value};
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use sha2::{Digest, Sha256, Sha512};
+use tracing::info;
+
+use crate::wallet::DivineWallet;
+
+/// SPL memo program (v2)
+pub const MEMO_PROGRAM_ID: &str = "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr";
+pub const SYSTEM_PROGRAM_ID: &str = "11111111111111111111111111111111";
+/// SPL token program
+pub const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+/// SPL associated token account program
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+/// The base fee of one signature
+pub const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+pub const SOL_DECIMALS: u8 = 9;
+/// Largest airdrop asked of the devnet faucet at once
+pub const MAX_AIRDROP_SOL: f64 = 5.0;
+/// Prefix of archive memos
+pub const MEMO_TAG: &str = "DIVINE|v15";
+/// How often pending archives have their signature status re-read
+pub const SOLANA_CONFIRMATION_CHECK_SECS: u64 = 10;
+pub const RPC_TIMEOUT_SECS: u64 = 15;
+/// Signatures one `getSignatureStatuses` call accepts
+pub const MAX_SIGNATURE_STATUSES: usize = 256;
+/// Solana's BIP-44 coin type
+pub const SOLANA_COIN_TYPE: u32 = 501;
+
+pub const NO_SOLANA: &str = "needs a real Solana layer (set SOLANA_WALLET and SOLANA_WALLET_PASSWORD)";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SolanaCluster {
+    Devnet,
+    Mainnet,
+}
+
+impl SolanaCluster {
+    pub fn name(self) -> &'static str {
+        match self {
+            SolanaCluster::Devnet => "devnet",
+            SolanaCluster::Mainnet => "mainnet",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "devnet" => Ok(SolanaCluster::Devnet),
+            "mainnet" | "mainnet-beta" => Ok(SolanaCluster::Mainnet),
+            _ => Err(format!("Unknown Solana cluster {} (expected devnet or mainnet)", name)),
+        }
+    }
+
+    /// The cluster's public RPC endpoint
+    pub fn default_rpc_url(self) -> &'static str {
+        match self {
+            SolanaCluster::Devnet => "https://api.devnet.solana.com",
+            SolanaCluster::Mainnet => "https://api.mainnet-beta.solana.com",
+        }
+    }
+
+    /// What `getGenesisHash` answers on the cluster
+    pub fn genesis_hash(self) -> &'static str {
+        match self {
+            SolanaCluster::Devnet => "EtWTRABZaYq6iMfeYKouRu166VU2xqa1wcaWoxPkrZBG",
+            SolanaCluster::Mainnet => "5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolanaConfig {
+    pub rpc_url: String,
+    pub cluster: SolanaCluster,
+    /// Account index the archiving key is derived at
+    pub account: u32,
+}
+
+impl SolanaConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.rpc_url.starts_with("http://") && !self.rpc_url.starts_with("https://") {
+            return Err(format!("SOLANA_RPC_URL must be http(s)://, not {}", self.rpc_url));
+        }
+        if self.account >= 1 << 31 {
+            return Err(format!("SOLANA_ACCOUNT {} is not a hardened index", self.account));
+        }
+        Ok(())
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════
+// KEYS & ENCODING
+// ═══════════════════════════════════════════════════════════════
+
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Bitcoin-alphabet base58, as Solana writes keys and signatures
+pub fn base58_encode(bytes: &[u8]) -> String {
+    let zeros = bytes.iter().take_while(|&&b| b == 0).count();
+    // Little-endian base-58 digits
+    let mut digits: Vec<u8> = Vec::new();
+    for &byte in &bytes[zeros..] {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    std::iter::repeat_n('1', zeros)
+        .chain(digits.iter().rev().map(|&d| BASE58_ALPHABET[d as usize] as char))
+        .collect()
+}
+
+pub fn base58_decode(text: &str) -> Result<Vec<u8>, String> {
+    let zeros = text.bytes().take_while(|&c| c == b'1').count();
+    // Little-endian bytes
+    let mut bytes: Vec<u8> = Vec::new();
+    for c in text.bytes().skip(zeros) {
+        let mut carry = BASE58_ALPHABET.iter().position(|&a| a == c)
+            .ok_or_else(|| format!("Invalid base58 character {:?}", c as char))? as u32;
+        for byte in bytes.iter_mut() {
+            carry += (*byte as u32) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+    Ok(std::iter::repeat_n(0, zeros).chain(bytes.into_iter().rev()).collect())
+}
+
+/// A 32-byte public key, account address or blockhash from base58
+pub fn parse_pubkey(text: &str) -> Result<[u8; 32], String> {
+    base58_decode(text.trim()).ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| format!("Invalid Solana key {:?}", text))
+}
+
+/// Whether `text` is a base58 transaction signature (64 bytes)
+pub fn is_signature(text: &str) -> bool {
+    base58_decode(text).is_ok_and(|bytes| bytes.len() == 64)
+}
+
+/// The `compact-u16` length prefix of Solana's wire format
+pub fn compact_u16(value: u16) -> Vec<u8> {
+    let mut value = value;
+    let mut out = Vec::new();
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return out;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+/// Ed25519 key `path` (every index hardened) below a BIP-39 seed, per SLIP-0010
+pub fn slip10_ed25519(seed: &[u8], path: &[u32]) -> [u8; 32] {
+    let hmac = |key: &[u8], data: &[u8]| -> [u8; 64] {
+        let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC takes any key");
+        mac.update(data);
+        mac.finalize().into_bytes().into()
+    };
+    let mut node = hmac(b"ed25519 seed", seed);
+    for index in path {
+        let data = [&[0u8][..], &node[..32], &(index | 1 << 31).to_be_bytes()].concat();
+        node = hmac(&node[32..], &data);
+    }
+    node[..32].try_into().expect("32 bytes")
+}
+
+/// An Ed25519 signing key; Debug shows only its address
+#[derive(Clone)]
+pub struct SolanaKeypair {
+    seed: [u8; 32],
+    pub pubkey: [u8; 32],
+}
+
+impl SolanaKeypair {
+    pub fn from_seed(seed: [u8; 32]) -> Self {
+        let pair = Ed25519KeyPair::from_seed_unchecked(&seed).expect("any 32 bytes are an Ed25519 seed");
+        let pubkey = pair.public_key().as_ref().try_into().expect("32 bytes");
+        Self { seed, pubkey }
+    }
+
+    /// Account `account` of a BIP-39 seed, at `m/44'/501'/<account>'/0'`
+    pub fn derive(bip39_seed: &[u8], account: u32) -> Self {
+        Self::from_seed(slip10_ed25519(bip39_seed, &[44, SOLANA_COIN_TYPE, account, 0]))
+    }
+
+    pub fn address(&self) -> String {
+        base58_encode(&self.pubkey)
+    }
+
+    pub fn sign(&self, message: &[u8]) -> [u8; 64] {
+        let pair = Ed25519KeyPair::from_seed_unchecked(&self.seed).expect("checked in from_seed");
+        pair.sign(message).as_ref().try_into().expect("64 bytes")
+    }
+}
+
+impl std::fmt::Debug for SolanaKeypair {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SolanaKeypair").field("address", &self.address()).finish()
+    }
+}
+
+pub fn verify_signature(pubkey: &[u8; 32], message: &[u8], signature: &[u8; 64]) -> bool {
+    UnparsedPublicKey::new(&ED25519, pubkey).verify(message, signature).is_ok()
+}
+
+// ═══════════════════════════════════════════════════════════════
+// MEMO TRANSACTIONS
+// ═══════════════════════════════════════════════════════════════
+
+/// The memo archiving `dna_hash` of genome `genome_id`
+pub fn archive_memo(genome_id: i64, dna_hash: [u8; 32]) -> String {
+    format!("{}|id:{}|dna:{}", MEMO_TAG, genome_id, hex::encode(dna_hash))
+}
+
+/// The genome id and DNA hash an archive memo carries
+pub fn decode_archive_memo(memo: &str) -> Option<(i64, [u8; 32])> {
+    let rest = memo.strip_prefix(MEMO_TAG)?.strip_prefix("|id:")?;
+    let (id, hash) = rest.split_once("|dna:")?;
+    Some((id.parse().ok()?, hex::decode(hash).ok()?.try_into().ok()?))
+}
+
+/// A legacy message with one memo instruction, paid and signed by `payer`
+pub fn memo_message(payer: &[u8; 32], recent_blockhash: &[u8; 32], memo: &str) -> Vec<u8> {
+    let memo_program = parse_pubkey(MEMO_PROGRAM_ID).expect("valid program id");
+    [
+        // One signature; no read-only signers; the memo program is read-only
+        vec![1, 0, 1],
+        compact_u16(2),
+        payer.to_vec(),
+        memo_program.to_vec(),
+        recent_blockhash.to_vec(),
+        compact_u16(1),
+        // Program index 1; the payer (index 0) signs the memo
+        vec![1],
+        compact_u16(1),
+        vec![0],
+        compact_u16(memo.len() as u16),
+        memo.as_bytes().to_vec(),
+    ].concat()
+}
+
+/// A signed memo transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoTx {
+    pub message: Vec<u8>,
+    pub signature: [u8; 64],
+}
+
+impl MemoTx {
+    pub fn new(payer: &SolanaKeypair, recent_blockhash: &[u8; 32], memo: &str) -> Self {
+        let message = memo_message(&payer.pubkey, recent_blockhash, memo);
+        let signature = payer.sign(&message);
+        Self { message, signature }
+    }
+
+    /// The transaction as sent: its signatures, then the message
+    pub fn wire(&self) -> Vec<u8> {
+        [compact_u16(1), self.signature.to_vec(), self.message.clone()].concat()
+    }
+
+    /// The transaction's id on chain
+    pub fn signature_base58(&self) -> String {
+        base58_encode(&self.signature)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════
+// TRANSFERS
+// ═══════════════════════════════════════════════════════════════
+
+/// An account an instruction reads or writes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccountMeta {
+    pub pubkey: [u8; 32],
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl AccountMeta {
+    pub fn writable(pubkey: [u8; 32], is_signer: bool) -> Self {
+        Self { pubkey, is_signer, is_writable: true }
+    }
+
+    pub fn readonly(pubkey: [u8; 32], is_signer: bool) -> Self {
+        Self { pubkey, is_signer, is_writable: false }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    pub program_id: [u8; 32],
+    pub accounts: Vec<AccountMeta>,
+    pub data: Vec<u8>,
+}
+
+/// A legacy message running `instructions`, paid and signed by `payer`
+/// alone (the only signer these transactions have)
+pub fn compile_message(payer: &[u8; 32], recent_blockhash: &[u8; 32], instructions: &[Instruction]) -> Result<Vec<u8>, String> {
+    // Every account once, with the strongest access any instruction asks
+    let mut accounts: Vec<AccountMeta> = Vec::new();
+    let metas = instructions.iter()
+        .flat_map(|ix| ix.accounts.iter().copied().chain([AccountMeta::readonly(ix.program_id, false)]));
+    for meta in metas.filter(|meta| meta.pubkey != *payer) {
+        if meta.is_signer {
+            return Err(format!("{} would have to sign too: only the fee payer signs", base58_encode(&meta.pubkey)));
+        }
+        match accounts.iter_mut().find(|account| account.pubkey == meta.pubkey) {
+            Some(account) => account.is_writable |= meta.is_writable,
+            None => accounts.push(meta),
+        }
+    }
+    // The payer, then writable accounts, then read-only ones
+    let (writable, readonly): (Vec<AccountMeta>, Vec<AccountMeta>) = accounts.into_iter().partition(|account| account.is_writable);
+    let keys: Vec<[u8; 32]> = std::iter::once(*payer)
+        .chain(writable.iter().chain(&readonly).map(|account| account.pubkey))
+        .collect();
+    if keys.len() > u8::MAX as usize {
+        return Err(format!("A transaction addresses at most 255 accounts, not {}", keys.len()));
+    }
+    let index = |pubkey: &[u8; 32]| keys.iter().position(|key| key == pubkey).expect("every account is a key") as u8;
+
+    let mut message = vec![1, 0, readonly.len() as u8];
+    message.extend(compact_u16(keys.len() as u16));
+    keys.iter().for_each(|key| message.extend_from_slice(key));
+    message.extend_from_slice(recent_blockhash);
+    message.extend(compact_u16(instructions.len() as u16));
+    for ix in instructions {
+        message.push(index(&ix.program_id));
+        message.extend(compact_u16(ix.accounts.len() as u16));
+        message.extend(ix.accounts.iter().map(|account| index(&account.pubkey)));
+        message.extend(compact_u16(ix.data.len() as u16));
+        message.extend_from_slice(&ix.data);
+    }
+    Ok(message)
+}
+
+/// The address `seeds` give under `program_id`, `None` if it falls on the
+/// Ed25519 curve (and so could have a private key)
+pub fn create_program_address(seeds: &[&[u8]], program_id: &[u8; 32]) -> Option<[u8; 32]> {
+    let mut hasher = Sha256::new();
+    seeds.iter().for_each(|seed| hasher.update(seed));
+    let address: [u8; 32] = hasher.chain_update(program_id).chain_update(b"ProgramDerivedAddress").finalize().into();
+    CompressedEdwardsY(address).decompress().is_none().then_some(address)
+}
+
+/// The program address of `seeds` with the highest bump seed off the curve
+pub fn find_program_address(seeds: &[&[u8]], program_id: &[u8; 32]) -> ([u8; 32], u8) {
+    (0..=u8::MAX).rev()
+        .find_map(|bump| create_program_address(&[seeds, &[&[bump][..]]].concat(), program_id).map(|address| (address, bump)))
+        .expect("some bump seed is off the curve")
+}
+
+/// The token account `owner` holds `mint` in
+pub fn associated_token_address(owner: &[u8; 32], mint: &[u8; 32]) -> [u8; 32] {
+    let token_program = parse_pubkey(TOKEN_PROGRAM_ID).expect("valid program id");
+    let program = parse_pubkey(ASSOCIATED_TOKEN_PROGRAM_ID).expect("valid program id");
+    find_program_address(&[owner, &token_program, mint], &program).0
+}
+
+/// Move `lamports` SOL from `from` to `to`
+pub fn sol_transfer(from: &[u8; 32], to: &[u8; 32], lamports: u64) -> Instruction {
+    Instruction {
+        program_id: parse_pubkey(SYSTEM_PROGRAM_ID).expect("valid program id"),
+        accounts: vec![AccountMeta::writable(*from, true), AccountMeta::writable(*to, false)],
+        // SystemInstruction::Transfer
+        data: [&2u32.to_le_bytes()[..], &lamports.to_le_bytes()].concat(),
+    }
+}
+
+/// Move `amount` (smallest units) of `mint` from `owner`'s associated token
+/// account to `recipient`'s, creating the recipient's at `owner`'s expense
+/// when it has none yet
+pub fn spl_transfer(owner: &[u8; 32], mint: &[u8; 32], recipient: &[u8; 32], amount: u64, decimals: u8) -> Vec<Instruction> {
+    let token_program = parse_pubkey(TOKEN_PROGRAM_ID).expect("valid program id");
+    let source = associated_token_address(owner, mint);
+    let destination = associated_token_address(recipient, mint);
+    vec![
+        Instruction {
+            program_id: parse_pubkey(ASSOCIATED_TOKEN_PROGRAM_ID).expect("valid program id"),
+            accounts: vec![
+                AccountMeta::writable(*owner, true),
+                AccountMeta::writable(destination, false),
+                AccountMeta::readonly(*recipient, false),
+                AccountMeta::readonly(*mint, false),
+                AccountMeta::readonly(parse_pubkey(SYSTEM_PROGRAM_ID).expect("valid program id"), false),
+                AccountMeta::readonly(token_program, false),
+            ],
+            // CreateIdempotent
+            data: vec![1],
+        },
+        Instruction {
+            program_id: token_program,
+            accounts: vec![
+                AccountMeta::writable(source, false),
+                AccountMeta::readonly(*mint, false),
+                AccountMeta::writable(destination, false),
+                AccountMeta::readonly(*owner, true),
+            ],
+            // TokenInstruction::TransferChecked
+            data: [&[12u8][..], &amount.to_le_bytes(), &[decimals]].concat(),
+        },
+    ]
+}
+
+/// A message signed by its one signer, the fee payer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolanaTx {
+    pub message: Vec<u8>,
+    pub signature: [u8; 64],
+}
+
+impl SolanaTx {
+    pub fn sign(payer: &SolanaKeypair, message: Vec<u8>) -> Self {
+        let signature = payer.sign(&message);
+        Self { message, signature }
+    }
+
+    /// The transaction as sent: its signature, then the message
+    pub fn wire(&self) -> Vec<u8> {
+        [compact_u16(1), self.signature.to_vec(), self.message.clone()].concat()
+    }
+
+    /// The transaction's id on chain
+    pub fn signature_base58(&self) -> String {
+        base58_encode(&self.signature)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════
+// CONFIRMATIONS
+// ═══════════════════════════════════════════════════════════════
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SolanaCommitment {
+    /// Sent, not seen by the cluster yet
+    Pending,
+    Processed,
+    Confirmed,
+    Finalized,
+    Failed,
+    /// Its blockhash expired before it landed: never will
+    Expired,
+}
+
+/// What `getSignatureStatuses` says about a signature the cluster has seen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureStatus {
+    pub slot: u64,
+    /// `None` once finalized
+    pub confirmations: Option<u64>,
+    pub error: Option<String>,
+    pub commitment: SolanaCommitment,
+}
+
+impl SignatureStatus {
+    pub fn from_rpc(value: &Value) -> Result<Option<Self>, String> {
+        if value.is_null() {
+            return Ok(None);
+        }
+        let confirmations = value["confirmations"].as_u64();
+        let error = (!value["err"].is_null()).then(|| value["err"].to_string());
+        let commitment = match value["confirmationStatus"].as_str() {
+            Some("processed") => SolanaCommitment::Processed,
+            Some("confirmed") => SolanaCommitment::Confirmed,
+            Some("finalized") => SolanaCommitment::Finalized,
+            // Older nodes: no confirmation count means rooted
+            None if confirmations.is_none() => SolanaCommitment::Finalized,
+            None => SolanaCommitment::Processed,
+            Some(other) => return Err(format!("Unknown confirmation status {}", other)),
+        };
+        let slot = value["slot"].as_u64().ok_or("Signature status without a slot")?;
+        Ok(Some(Self { slot, confirmations, error, commitment }))
+    }
+}
+
+/// On-chain status of a real Solana archive, kept on its archive entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SolanaConfirmation {
+    pub cluster: SolanaCluster,
+    pub status: SolanaCommitment,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slot: Option<u64>,
+    /// Blocks voted on top of `slot`, until finalized
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confirmations: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// The transaction cannot land after this block height
+    pub last_valid_block_height: u64,
+    pub updated_at: i64,
+}
+
+impl SolanaConfirmation {
+    pub fn sent(cluster: SolanaCluster, last_valid_block_height: u64, now: i64) -> Self {
+        Self {
+            cluster,
+            status: SolanaCommitment::Pending,
+            slot: None,
+            confirmations: None,
+            error: None,
+            last_valid_block_height,
+            updated_at: now,
+        }
+    }
+
+    pub fn is_settled(&self) -> bool {
+        matches!(self.status, SolanaCommitment::Finalized | SolanaCommitment::Failed | SolanaCommitment::Expired)
+    }
+
+    /// Update from the latest signature status (`None`: not seen) at
+    /// `block_height`
+    pub fn apply(&mut self, status: Option<SignatureStatus>, block_height: u64, now: i64) {
+        self.updated_at = now;
+        match status {
+            Some(status) => {
+                self.slot = Some(status.slot);
+                self.confirmations = status.confirmations;
+                self.status = if status.error.is_some() { SolanaCommitment::Failed } else { status.commitment };
+                self.error = status.error;
+            }
+            None => {
+                // A processed transaction on a dropped fork is unseen again
+                self.slot = None;
+                self.confirmations = None;
+                self.status = if block_height > self.last_valid_block_height {
+                    SolanaCommitment::Expired
+                } else {
+                    SolanaCommitment::Pending
+                };
+            }
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════
+// JSON-RPC
+// ═══════════════════════════════════════════════════════════════
+
+#[derive(Clone)]
+pub struct SolanaRpc {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl SolanaRpc {
+    pub fn new(url: &str) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(RPC_TIMEOUT_SECS))
+            .user_agent(format!("divine-agi/{}", crate::VERSION))
+            .build()
+            .unwrap_or_default();
+        Self { client, url: url.to_string() }
+    }
+
+    async fn call(&self, method: &str, params: Value) -> Result<Value, String> {
+        let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let response = self.client.post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.to_string())
+            .send().await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| format!("{}: {}", method, e))?;
+        let bytes = response.bytes().await.map_err(|e| format!("{}: {}", method, e))?;
+        let mut reply: Value = serde_json::from_slice(&bytes).map_err(|e| format!("{}: {}", method, e))?;
+        if let Some(error) = reply.get("error") {
+            return Err(format!("{}: {}", method, error["message"].as_str().unwrap_or("error")));
+        }
+        Ok(reply["result"].take())
+    }
+
+    pub async fn genesis_hash(&self) -> Result<String, String> {
+        let hash = self.call("getGenesisHash", json!([])).await?;
+        hash.as_str().map(str::to_string).ok_or_else(|| "getGenesisHash: no hash".into())
+    }
+
+    /// A recent blockhash and the last block height a transaction using it
+    /// can land at
+    pub async fn latest_blockhash(&self) -> Result<([u8; 32], u64), String> {
+        let reply = self.call("getLatestBlockhash", json!([{ "commitment": "confirmed" }])).await?;
+        let blockhash = parse_pubkey(reply["value"]["blockhash"].as_str().unwrap_or_default())?;
+        let last_valid = reply["value"]["lastValidBlockHeight"].as_u64().ok_or("getLatestBlockhash: no lastValidBlockHeight")?;
+        Ok((blockhash, last_valid))
+    }
+
+    /// Lamports held by `address`
+    pub async fn balance(&self, address: &str) -> Result<u64, String> {
+        self.call("getBalance", json!([address, { "commitment": "confirmed" }])).await?["value"]
+            .as_u64().ok_or_else(|| "getBalance: no balance".into())
+    }
+
+    /// Ask the cluster's faucet (devnet only) for `lamports`; returns the
+    /// airdrop's signature
+    pub async fn request_airdrop(&self, address: &str, lamports: u64) -> Result<String, String> {
+        let signature = self.call("requestAirdrop", json!([address, lamports, { "commitment": "confirmed" }])).await?;
+        signature.as_str().map(str::to_string).ok_or_else(|| "requestAirdrop: no signature".into())
+    }
+
+    /// Decimals of SPL mint `mint`
+    pub async fn mint_decimals(&self, mint: &str) -> Result<u8, String> {
+        let reply = self.call("getTokenSupply", json!([mint, { "commitment": "confirmed" }])).await?;
+        reply["value"]["decimals"].as_u64().and_then(|d| u8::try_from(d).ok())
+            .ok_or_else(|| "getTokenSupply: no decimals".into())
+    }
+
+    /// What `owner` holds of SPL mint `mint` over all its token accounts, in
+    /// the mint's smallest unit, and the mint's decimals
+    pub async fn token_balance(&self, owner: &str, mint: &str) -> Result<(u64, u8), String> {
+        let params = json!([owner, { "mint": mint }, { "encoding": "jsonParsed", "commitment": "confirmed" }]);
+        let reply = self.call("getTokenAccountsByOwner", params).await?;
+        let accounts = reply["value"].as_array().ok_or("getTokenAccountsByOwner: no accounts")?;
+        let mut total: u64 = 0;
+        let mut decimals = None;
+        for account in accounts {
+            let amount = &account["account"]["data"]["parsed"]["info"]["tokenAmount"];
+            let raw: u64 = amount["amount"].as_str().and_then(|a| a.parse().ok())
+                .ok_or("getTokenAccountsByOwner: no token amount")?;
+            total = total.checked_add(raw).ok_or("getTokenAccountsByOwner: balance overflows")?;
+            decimals = amount["decimals"].as_u64().and_then(|d| u8::try_from(d).ok());
+        }
+        match decimals {
+            Some(decimals) => Ok((total, decimals)),
+            None => Ok((total, self.mint_decimals(mint).await?)),
+        }
+    }
+
+    pub async fn block_height(&self) -> Result<u64, String> {
+        self.call("getBlockHeight", json!([{ "commitment": "confirmed" }])).await?
+            .as_u64().ok_or_else(|| "getBlockHeight: no height".into())
+    }
+
+    pub async fn send_transaction(&self, wire: &[u8]) -> Result<String, String> {
+        let encoded = base64::engine::general_purpose::STANDARD.encode(wire);
+        let signature = self.call("sendTransaction", json!([encoded, { "encoding": "base64", "preflightCommitment": "confirmed" }])).await?;
+        signature.as_str().map(str::to_string).ok_or_else(|| "sendTransaction: no signature".into())
+    }
+
+    /// The memos of transaction `signature` (`None` if the cluster does not
+    /// know it), refused when it failed
+    pub async fn transaction_memos(&self, signature: &str) -> Result<Option<Vec<String>>, String> {
+        let tx = self.call("getTransaction", json!([signature, { "encoding": "json", "commitment": "confirmed", "maxSupportedTransactionVersion": 0 }])).await?;
+        if tx.is_null() {
+            return Ok(None);
+        }
+        if !tx["meta"]["err"].is_null() {
+            return Err(format!("Solana transaction {} failed: {}", signature, tx["meta"]["err"]));
+        }
+        let message = &tx["transaction"]["message"];
+        let keys: Vec<&str> = message["accountKeys"].as_array().ok_or("getTransaction: no account keys")?
+            .iter().filter_map(Value::as_str).collect();
+        let instructions = message["instructions"].as_array().ok_or("getTransaction: no instructions")?;
+        instructions.iter()
+            .filter(|ix| ix["programIdIndex"].as_u64().and_then(|i| keys.get(i as usize)) == Some(&MEMO_PROGRAM_ID))
+            .map(|ix| {
+                let data = base58_decode(ix["data"].as_str().unwrap_or_default())?;
+                String::from_utf8(data).map_err(|_| "Memo is not UTF-8".to_string())
+            })
+            .collect::<Result<Vec<String>, String>>()
+            .map(Some)
+    }
+
+    /// Statuses of up to `MAX_SIGNATURE_STATUSES` signatures, in order
+    pub async fn signature_statuses(&self, signatures: &[String]) -> Result<Vec<Option<SignatureStatus>>, String> {
+        let reply = self.call("getSignatureStatuses", json!([signatures, { "searchTransactionHistory": true }])).await?;
+        let values = reply["value"].as_array().ok_or("getSignatureStatuses: no statuses")?;
+        values.iter().map(SignatureStatus::from_rpc).collect()
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════
+// ARCHIVING LAYER
+// ═══════════════════════════════════════════════════════════════
+
+/// The real Solana layer: the wallet's key and the cluster's node
+pub struct SolanaLayer {
+    pub config: SolanaConfig,
+    keypair: SolanaKeypair,
+    rpc: SolanaRpc,
+    cluster_checked: bool,
+}
+
+impl SolanaLayer {
+    pub fn new(config: SolanaConfig, keypair: SolanaKeypair) -> Self {
+        let rpc = SolanaRpc::new(&config.rpc_url);
+        Self { config, keypair, rpc, cluster_checked: false }
+    }
+
+    /// The layer the environment configures, `None` to stay simulated
+    pub fn from_env() -> Result<Option<Self>, String> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        let Some(path) = var("SOLANA_WALLET") else {
+            return Ok(None);
+        };
+        let password = var("SOLANA_WALLET_PASSWORD").ok_or("SOLANA_WALLET needs SOLANA_WALLET_PASSWORD")?;
+        let cluster = var("SOLANA_CLUSTER").map_or(Ok(SolanaCluster::Devnet), |name| SolanaCluster::from_name(&name))?;
+        let config = SolanaConfig {
+            rpc_url: var("SOLANA_RPC_URL").unwrap_or_else(|| cluster.default_rpc_url().to_string()),
+            cluster,
+            account: match var("SOLANA_ACCOUNT") {
+                Some(v) => v.parse().map_err(|_| format!("Invalid SOLANA_ACCOUNT: {}", v))?,
+                None => 0,
+            },
+        };
+        config.validate()?;
+        let wallet = DivineWallet::load(&path, &password).map_err(|e| format!("SOLANA_WALLET {}: {}", path, e))?;
+        Ok(Some(Self::new(config.clone(), wallet.solana_keypair(config.account)?)))
+    }
+
+    /// The address paying for archives (fund it with SOL first)
+    pub fn address(&self) -> String {
+        self.keypair.address()
+    }
+
+    /// Sign and send the memo archiving `dna_hash`; returns its signature
+    /// once the node accepted it, with its status to track
+    pub async fn archive(&mut self, genome_id: i64, dna_hash: [u8; 32], now: i64) -> Result<(String, SolanaConfirmation), String> {
+        if !self.cluster_checked {
+            let genesis = self.rpc.genesis_hash().await?;
+            if genesis != self.config.cluster.genesis_hash() {
+                return Err(format!("SOLANA_RPC_URL serves the cluster with genesis {}, not {}", genesis, self.config.cluster.name()));
+            }
+            self.cluster_checked = true;
+        }
+
+        let (blockhash, last_valid_block_height) = self.rpc.latest_blockhash().await?;
+        let tx = MemoTx::new(&self.keypair, &blockhash, &archive_memo(genome_id, dna_hash));
+        let signature = self.rpc.send_transaction(&tx.wire()).await?;
+        if signature != tx.signature_base58() {
+            return Err(format!("sendTransaction returned {}, not the signed {}", signature, tx.signature_base58()));
+        }
+        info!("🟣 Solana memo sent: {} | {} | valid until block {}", signature, self.config.cluster.name(), last_valid_block_height);
+        Ok((signature, SolanaConfirmation::sent(self.config.cluster, last_valid_block_height, now)))
+    }
+
+    /// The DNA hash archived by `signature`, read back from its memo
+    pub async fn read_archive(&self, signature: &str) -> Result<[u8; 32], String> {
+        let memos = self.rpc.transaction_memos(signature).await?
+            .ok_or_else(|| format!("Solana transaction {} not found on {}", signature, self.config.cluster.name()))?;
+        memos.iter().find_map(|memo| decode_archive_memo(memo)).map(|(_, hash)| hash)
+            .ok_or_else(|| format!("Solana transaction {} carries no genome archive", signature))
+    }
+
+    /// Fund `address` (default the archiving key) with `lamports` from the
+    /// devnet faucet; returns the airdrop's signature
+    pub async fn request_airdrop(&self, address: Option<&str>, lamports: u64) -> Result<(String, String), String> {
+        if self.config.cluster != SolanaCluster::Devnet {
+            return Err(format!("Airdrops only exist on devnet, not {}", self.config.cluster.name()));
+        }
+        let address = address.map_or_else(|| self.address(), str::to_string);
+        parse_pubkey(&address)?;
+        let signature = self.rpc.request_airdrop(&address, lamports).await?;
+        info!("🚰 Devnet airdrop: {} lamports to {} | {}", lamports, address, signature);
+        Ok((address, signature))
+    }
+
+    /// The current block height and the statuses of `signatures`, in order
+    pub async fn statuses(&self, signatures: &[String]) -> Result<(u64, Vec<Option<SignatureStatus>>), String> {
+        let block_height = self.rpc.block_height().await?;
+        let mut statuses = Vec::with_capacity(signatures.len());
+        for chunk in signatures.chunks(MAX_SIGNATURE_STATUSES) {
+            statuses.extend(self.rpc.signature_statuses(chunk).await?);
+        }
+        Ok((block_height, statuses))
+    }
+}
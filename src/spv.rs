@@ -0,0 +1,594 @@
+//! Bitcoin SPV Proofs V1 — Bitcoin archives anyone can check
+//!
+//! A Bitcoin archive entry can hold an SPV proof: the raw transaction, the
+//! Merkle branch from its txid to its block's Merkle root, and the headers
+//! from that block up to `BITCOIN_SPV_CONFIRMATIONS` (default 6) deep.
+//! `verify_spv` checks it locally, with no node to trust:
+//! - the raw transaction hashes (double SHA-256, witness stripped) to the
+//!   entry's txid and has the OP_RETURN output carrying its DNA hash,
+//! - the branch leads from the txid to the first header's Merkle root,
+//! - each header names the one before as its parent and its hash meets the
+//!   target its bits encode, within the network's proof-of-work limit,
+//! - and the headers reach the required depth.
+//!
+//! Difficulty retargets are not re-derived: the work in the headers above
+//! the block is what a forger would have to redo.
+//!
+//! Proofs come from an Esplora API (`BITCOIN_ESPLORA_URL`, e.g.
+//! `https://blockstream.info/api`) on `BITCOIN_NETWORK` (`mainnet`,
+//! `testnet`, `signet` or `regtest`). Simulated Bitcoin archives are real
+//! transactions mined into a simulated regtest chain, so their proofs check
+//! the same way, at regtest difficulty.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use tracing::info;
+
+use crate::multi_chain::op_return_script;
+
+pub const DEFAULT_SPV_CONFIRMATIONS: u32 = 6;
+/// How often the server fetches proofs still missing or short of depth
+pub const SPV_CHECK_SECS: u64 = 600;
+pub const ESPLORA_TIMEOUT_SECS: u64 = 15;
+pub const BLOCK_HEADER_BYTES: usize = 80;
+/// Compact target simulated blocks are mined at (regtest's limit)
+pub const SIMULATED_BITS: u32 = 0x207f_ffff;
+
+/// Error for proofs of real transactions while no Esplora API is set
+pub const NO_ESPLORA: &str = "needs an Esplora API (set BITCOIN_ESPLORA_URL)";
+
+pub fn sha256d(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(Sha256::digest(data)).into()
+}
+
+/// Hex of a hash as Bitcoin displays it: byte-reversed
+pub fn display_hex(hash: &[u8; 32]) -> String {
+    let mut reversed = *hash;
+    reversed.reverse();
+    hex::encode(reversed)
+}
+
+/// A displayed hash (`0x` optional) in internal byte order
+pub fn parse_display_hex(display: &str) -> Result<[u8; 32], String> {
+    let mut hash: [u8; 32] = hex::decode(display.strip_prefix("0x").unwrap_or(display)).ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| format!("Invalid 32-byte hash: {}", display))?;
+    hash.reverse();
+    Ok(hash)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BitcoinNetwork {
+    Mainnet,
+    Testnet,
+    Signet,
+    Regtest,
+}
+
+impl BitcoinNetwork {
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "mainnet" | "bitcoin" => Ok(Self::Mainnet),
+            "testnet" | "testnet3" => Ok(Self::Testnet),
+            "signet" => Ok(Self::Signet),
+            "regtest" => Ok(Self::Regtest),
+            other => Err(format!("Unknown Bitcoin network: {}", other)),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Mainnet => "mainnet",
+            Self::Testnet => "testnet",
+            Self::Signet => "signet",
+            Self::Regtest => "regtest",
+        }
+    }
+
+    /// The easiest target a header may claim, compact
+    pub fn pow_limit_bits(self) -> u32 {
+        match self {
+            Self::Mainnet | Self::Testnet => 0x1d00_ffff,
+            Self::Signet => 0x1e03_77ae,
+            Self::Regtest => SIMULATED_BITS,
+        }
+    }
+}
+
+/// The 256-bit target compact `bits` encode, big-endian; `None` when
+/// negative, zero or too large
+pub fn compact_target(bits: u32) -> Option<[u8; 32]> {
+    let exponent = (bits >> 24) as usize;
+    let mantissa = bits & 0x007f_ffff;
+    if bits & 0x0080_0000 != 0 || mantissa == 0 || exponent > 32 {
+        return None;
+    }
+    let mut target = [0u8; 32];
+    // Mantissa byte `k` (0 the lowest) is worth 256^(k + exponent - 3)
+    for k in 0..3 {
+        let byte = (mantissa >> (8 * k)) as u8;
+        match (k + exponent).checked_sub(3) {
+            Some(power) if power < 32 => target[31 - power] = byte,
+            Some(_) if byte != 0 => return None,
+            _ => {}
+        }
+    }
+    (target != [0u8; 32]).then_some(target)
+}
+
+/// Whether `hash` (internal order, a little-endian number) is at most `target`
+fn hash_meets(hash: &[u8; 32], target: &[u8; 32]) -> bool {
+    let mut number = *hash;
+    number.reverse();
+    number <= *target
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockHeader {
+    pub version: i32,
+    pub prev_block: [u8; 32],
+    pub merkle_root: [u8; 32],
+    pub time: u32,
+    pub bits: u32,
+    pub nonce: u32,
+}
+
+impl BlockHeader {
+    pub fn to_bytes(&self) -> [u8; BLOCK_HEADER_BYTES] {
+        let mut bytes = [0u8; BLOCK_HEADER_BYTES];
+        bytes[..4].copy_from_slice(&self.version.to_le_bytes());
+        bytes[4..36].copy_from_slice(&self.prev_block);
+        bytes[36..68].copy_from_slice(&self.merkle_root);
+        bytes[68..72].copy_from_slice(&self.time.to_le_bytes());
+        bytes[72..76].copy_from_slice(&self.bits.to_le_bytes());
+        bytes[76..].copy_from_slice(&self.nonce.to_le_bytes());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let bytes: &[u8; BLOCK_HEADER_BYTES] = bytes.try_into()
+            .map_err(|_| format!("A block header is {} bytes, not {}", BLOCK_HEADER_BYTES, bytes.len()))?;
+        let u32_at = |at: usize| u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap_or_default());
+        Ok(Self {
+            version: u32_at(0) as i32,
+            prev_block: bytes[4..36].try_into().unwrap_or_default(),
+            merkle_root: bytes[36..68].try_into().unwrap_or_default(),
+            time: u32_at(68),
+            bits: u32_at(72),
+            nonce: u32_at(76),
+        })
+    }
+
+    pub fn from_hex(header: &str) -> Result<Self, String> {
+        Self::from_bytes(&hex::decode(header).map_err(|_| format!("Invalid block header hex: {}", header))?)
+    }
+
+    /// Block hash, internal order
+    pub fn hash(&self) -> [u8; 32] {
+        sha256d(&self.to_bytes())
+    }
+
+    /// Whether the hash meets the header's own target, and that target is
+    /// no easier than `network` allows
+    pub fn has_valid_work(&self, network: BitcoinNetwork) -> bool {
+        let (Some(target), Some(limit)) = (compact_target(self.bits), compact_target(network.pow_limit_bits())) else {
+            return false;
+        };
+        target <= limit && hash_meets(&self.hash(), &target)
+    }
+}
+
+fn merkle_parent(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    sha256d(&[&left[..], &right[..]].concat())
+}
+
+/// Siblings from `txids[index]` up to the block's Merkle root (odd levels
+/// pair their last node with itself)
+pub fn merkle_branch(txids: &[[u8; 32]], index: usize) -> Vec<[u8; 32]> {
+    let mut branch = Vec::new();
+    let mut level = txids.to_vec();
+    let mut pos = index;
+    while level.len() > 1 {
+        branch.push(if pos.is_multiple_of(2) { *level.get(pos + 1).unwrap_or(&level[pos]) } else { level[pos - 1] });
+        level = level.chunks(2).map(|pair| merkle_parent(&pair[0], pair.get(1).unwrap_or(&pair[0]))).collect();
+        pos /= 2;
+    }
+    branch
+}
+
+/// The Merkle root `branch` leads to from `txid` at `index`
+pub fn branch_root(txid: &[u8; 32], branch: &[[u8; 32]], index: usize) -> [u8; 32] {
+    branch.iter().enumerate().fold(*txid, |node, (depth, sibling)| {
+        if (index >> depth) & 1 == 1 { merkle_parent(sibling, &node) } else { merkle_parent(&node, sibling) }
+    })
+}
+
+/// A transaction's txid and output scripts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedTx {
+    pub txid: [u8; 32],
+    pub output_scripts: Vec<Vec<u8>>,
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    at: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let end = self.at.checked_add(len).filter(|end| *end <= self.bytes.len()).ok_or("Truncated transaction")?;
+        let taken = &self.bytes[self.at..end];
+        self.at = end;
+        Ok(taken)
+    }
+
+    fn varint(&mut self) -> Result<usize, String> {
+        let value = match self.take(1)?[0] {
+            0xfd => u16::from_le_bytes(self.take(2)?.try_into().unwrap_or_default()) as u64,
+            0xfe => u32::from_le_bytes(self.take(4)?.try_into().unwrap_or_default()) as u64,
+            0xff => u64::from_le_bytes(self.take(8)?.try_into().unwrap_or_default()),
+            byte => byte as u64,
+        };
+        usize::try_from(value).ok().filter(|value| *value <= self.bytes.len()).ok_or_else(|| "Transaction count out of range".into())
+    }
+}
+
+/// Read a serialized transaction; the txid leaves out segwit data
+pub fn parse_tx(raw: &[u8]) -> Result<ParsedTx, String> {
+    let mut reader = Reader { bytes: raw, at: 0 };
+    reader.take(4)?;
+    let segwit = raw.get(4..6) == Some(&[0x00, 0x01][..]);
+    if segwit {
+        reader.take(2)?;
+    }
+    let inputs_start = reader.at;
+    let inputs = reader.varint()?;
+    for _ in 0..inputs {
+        reader.take(36)?;
+        let script = reader.varint()?;
+        reader.take(script + 4)?;
+    }
+    let mut output_scripts = Vec::new();
+    for _ in 0..reader.varint()? {
+        reader.take(8)?;
+        let script = reader.varint()?;
+        output_scripts.push(reader.take(script)?.to_vec());
+    }
+    let outputs_end = reader.at;
+    if segwit {
+        for _ in 0..inputs {
+            for _ in 0..reader.varint()? {
+                let item = reader.varint()?;
+                reader.take(item)?;
+            }
+        }
+    }
+    let locktime = reader.take(4)?;
+    if reader.at != raw.len() {
+        return Err("Trailing bytes after the transaction".into());
+    }
+    let stripped = [&raw[..4], &raw[inputs_start..outputs_end], locktime].concat();
+    Ok(ParsedTx { txid: sha256d(&stripped), output_scripts })
+}
+
+/// A one-input transaction whose only output is `script`, spending
+/// `prevout`; how simulated Bitcoin archives are built
+pub fn simulated_tx(prevout: [u8; 32], script: &[u8]) -> Vec<u8> {
+    let mut raw = 2i32.to_le_bytes().to_vec();
+    raw.push(1);
+    raw.extend_from_slice(&prevout);
+    raw.extend_from_slice(&0u32.to_le_bytes());
+    raw.push(0);
+    raw.extend_from_slice(&u32::MAX.to_le_bytes());
+    raw.push(1);
+    raw.extend_from_slice(&0u64.to_le_bytes());
+    raw.push(script.len() as u8);
+    raw.extend_from_slice(script);
+    raw.extend_from_slice(&0u32.to_le_bytes());
+    raw
+}
+
+/// Proof that a Bitcoin transaction is in a block with work on top of it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpvProof {
+    /// Displayed txid, as the entry's tx reference carries it
+    pub txid: String,
+    pub raw_tx: String,
+    pub block_height: u64,
+    pub block_hash: String,
+    /// Position of the transaction in its block
+    pub tx_index: usize,
+    /// Sibling hashes from the txid up, displayed order
+    pub branch: Vec<String>,
+    /// 80-byte headers from the transaction's block up, hex
+    pub headers: Vec<String>,
+    /// Mined into the simulated chain: checked at regtest difficulty
+    #[serde(default)]
+    pub simulated: bool,
+    pub fetched_at: i64,
+}
+
+impl SpvProof {
+    pub fn confirmations(&self) -> u32 {
+        self.headers.len() as u32
+    }
+
+    /// Check the proof against `tx_hash` and the DNA hash it should carry
+    pub fn verify(&self, tx_hash: &str, dna_hash: &str, network: BitcoinNetwork, required_confirmations: u32) -> Result<SpvVerification, String> {
+        let txid = parse_display_hex(tx_hash)?;
+        let raw = hex::decode(&self.raw_tx).map_err(|_| "Invalid raw transaction hex".to_string())?;
+        let tx = parse_tx(&raw)?;
+        let dna_hash: [u8; 32] = hex::decode(dna_hash).ok().and_then(|hash| hash.try_into().ok())
+            .ok_or_else(|| format!("Invalid DNA hash: {}", dna_hash))?;
+        let branch = self.branch.iter().map(|hash| parse_display_hex(hash)).collect::<Result<Vec<_>, _>>()?;
+        let headers = self.headers.iter().map(|header| BlockHeader::from_hex(header)).collect::<Result<Vec<_>, _>>()?;
+        let block = headers.first().ok_or("The proof has no block header")?;
+
+        let txid_matches = tx.txid == txid && parse_display_hex(&self.txid)? == txid;
+        let carries_dna_hash = tx.output_scripts.contains(&op_return_script(dna_hash));
+        let included = parse_display_hex(&self.block_hash)? == block.hash() && branch_root(&txid, &branch, self.tx_index) == block.merkle_root;
+        let chain_linked = headers.windows(2).all(|pair| pair[1].prev_block == pair[0].hash());
+        let work_valid = headers.iter().all(|header| header.has_valid_work(network));
+        let confirmations = self.confirmations();
+        Ok(SpvVerification {
+            txid_matches,
+            carries_dna_hash,
+            included,
+            chain_linked,
+            work_valid,
+            confirmations,
+            required_confirmations,
+            verified: txid_matches && carries_dna_hash && included && chain_linked && work_valid && confirmations >= required_confirmations,
+        })
+    }
+}
+
+/// Outcome of checking an SPV proof
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpvVerification {
+    /// The raw transaction hashes to the entry's txid
+    pub txid_matches: bool,
+    /// One of its outputs is the OP_RETURN carrying the entry's DNA hash
+    pub carries_dna_hash: bool,
+    /// The Merkle branch leads from the txid to the block's Merkle root
+    pub included: bool,
+    /// Each header builds on the one before
+    pub chain_linked: bool,
+    /// Each header's hash meets its target, within the network's limit
+    pub work_valid: bool,
+    pub confirmations: u32,
+    pub required_confirmations: u32,
+    pub verified: bool,
+}
+
+/// Where proofs come from and how deep they must be
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpvConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub esplora_url: Option<String>,
+    pub network: BitcoinNetwork,
+    pub confirmations: u32,
+}
+
+impl SpvConfig {
+    /// `BITCOIN_ESPLORA_URL`, `BITCOIN_NETWORK` (default `mainnet` with an
+    /// Esplora API, else `regtest`) and `BITCOIN_SPV_CONFIRMATIONS`
+    pub fn from_env() -> Result<Self, String> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        let esplora_url = var("BITCOIN_ESPLORA_URL").map(|url| url.trim_end_matches('/').to_string());
+        let config = Self {
+            network: match var("BITCOIN_NETWORK") {
+                Some(name) => BitcoinNetwork::from_name(&name)?,
+                None if esplora_url.is_some() => BitcoinNetwork::Mainnet,
+                None => BitcoinNetwork::Regtest,
+            },
+            esplora_url,
+            confirmations: match var("BITCOIN_SPV_CONFIRMATIONS") {
+                Some(v) => v.parse().map_err(|_| format!("Invalid BITCOIN_SPV_CONFIRMATIONS: {}", v))?,
+                None => DEFAULT_SPV_CONFIRMATIONS,
+            },
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(url) = &self.esplora_url {
+            if !(url.starts_with("http://") || url.starts_with("https://")) {
+                return Err(format!("BITCOIN_ESPLORA_URL must be http(s): {}", url));
+            }
+        }
+        if self.confirmations == 0 {
+            return Err("BITCOIN_SPV_CONFIRMATIONS must be at least 1".into());
+        }
+        Ok(())
+    }
+}
+
+impl Default for SpvConfig {
+    fn default() -> Self {
+        Self { esplora_url: None, network: BitcoinNetwork::Regtest, confirmations: DEFAULT_SPV_CONFIRMATIONS }
+    }
+}
+
+/// An Esplora REST API
+#[derive(Clone)]
+pub struct Esplora {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl Esplora {
+    pub fn new(url: &str) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(ESPLORA_TIMEOUT_SECS))
+            .user_agent(format!("divine-agi/{}", crate::VERSION))
+            .build()
+            .unwrap_or_default();
+        Self { client, url: url.to_string() }
+    }
+
+    async fn get(&self, path: &str) -> Result<String, String> {
+        let response = self.client.get(format!("{}{}", self.url, path))
+            .send().await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| format!("Esplora {}: {}", path, e))?;
+        response.text().await.map(|text| text.trim().to_string()).map_err(|e| format!("Esplora {}: {}", path, e))
+    }
+
+    /// The proof of `txid` with headers up to `confirmations` deep, or as
+    /// deep as the chain goes
+    pub async fn proof(&self, txid: &str, confirmations: u32, now: i64) -> Result<SpvProof, String> {
+        let merkle: Value = serde_json::from_str(&self.get(&format!("/tx/{}/merkle-proof", txid)).await?)
+            .map_err(|e| format!("Esplora merkle proof of {}: {}", txid, e))?;
+        let (Some(block_height), Some(tx_index), Some(branch)) = (merkle["block_height"].as_u64(), merkle["pos"].as_u64(), merkle["merkle"].as_array()) else {
+            return Err(format!("Transaction {} is not in a block yet", txid));
+        };
+        let branch = branch.iter().map(|hash| hash.as_str().map(str::to_string).ok_or("Esplora: malformed Merkle branch"))
+            .collect::<Result<Vec<_>, _>>()?;
+        let tip: u64 = self.get("/blocks/tip/height").await?.parse().map_err(|_| "Esplora: malformed tip height".to_string())?;
+        let mut headers = Vec::new();
+        let mut block_hash = String::new();
+        for height in block_height..=tip.min(block_height + confirmations as u64 - 1) {
+            let hash = self.get(&format!("/block-height/{}", height)).await?;
+            headers.push(self.get(&format!("/block/{}/header", hash)).await?);
+            if height == block_height {
+                block_hash = hash;
+            }
+        }
+        Ok(SpvProof {
+            txid: txid.to_string(),
+            raw_tx: self.get(&format!("/tx/{}/hex", txid)).await?,
+            block_height,
+            block_hash,
+            tx_index: tx_index as usize,
+            branch,
+            headers,
+            simulated: false,
+            fetched_at: now,
+        })
+    }
+}
+
+/// A regtest chain simulated Bitcoin archives are mined into, one block each
+#[derive(Debug, Clone, Default)]
+pub struct SimulatedBitcoin {
+    headers: Vec<BlockHeader>,
+    blocks: Vec<Vec<[u8; 32]>>,
+    /// Raw transaction and block height by displayed txid
+    txs: HashMap<String, (Vec<u8>, usize)>,
+}
+
+impl SimulatedBitcoin {
+    pub fn height(&self) -> usize {
+        self.headers.len()
+    }
+
+    pub fn contains(&self, txid: &str) -> bool {
+        self.txs.contains_key(txid.strip_prefix("0x").unwrap_or(txid))
+    }
+
+    /// Mine a block of `txids` after a stand-in coinbase
+    fn mine(&mut self, txids: &[[u8; 32]], now: i64) {
+        let height = self.headers.len();
+        let coinbase = sha256d(format!("DIVINE|coinbase|{}", height).as_bytes());
+        let block: Vec<[u8; 32]> = std::iter::once(coinbase).chain(txids.iter().copied()).collect();
+        let mut header = BlockHeader {
+            version: 0x2000_0000,
+            prev_block: self.headers.last().map_or([0u8; 32], BlockHeader::hash),
+            merkle_root: branch_root(&block[0], &merkle_branch(&block, 0), 0),
+            time: now as u32,
+            bits: SIMULATED_BITS,
+            nonce: 0,
+        };
+        while !header.has_valid_work(BitcoinNetwork::Regtest) {
+            header.nonce += 1;
+        }
+        self.headers.push(header);
+        self.blocks.push(block);
+    }
+
+    /// Mine `raw` into a block of its own; returns its displayed txid
+    pub fn broadcast(&mut self, raw: Vec<u8>, now: i64) -> Result<String, String> {
+        let txid = parse_tx(&raw)?.txid;
+        self.mine(&[txid], now);
+        let display = display_hex(&txid);
+        self.txs.insert(display.clone(), (raw, self.headers.len() - 1));
+        Ok(display)
+    }
+
+    /// The proof of `txid`, mining empty blocks until it is `confirmations` deep
+    pub fn proof(&mut self, txid: &str, confirmations: u32, now: i64) -> Result<SpvProof, String> {
+        let display = txid.strip_prefix("0x").unwrap_or(txid).to_string();
+        let (raw, height) = self.txs.get(&display).cloned().ok_or_else(|| format!("Bitcoin transaction {} not found", txid))?;
+        while self.headers.len() < height + confirmations as usize {
+            self.mine(&[], now);
+        }
+        let block = &self.blocks[height];
+        let internal = parse_display_hex(&display)?;
+        let tx_index = block.iter().position(|id| *id == internal).ok_or("Transaction missing from its block")?;
+        Ok(SpvProof {
+            txid: display,
+            raw_tx: hex::encode(raw),
+            block_height: height as u64,
+            block_hash: display_hex(&self.headers[height].hash()),
+            tx_index,
+            branch: merkle_branch(block, tx_index).iter().map(display_hex).collect(),
+            headers: self.headers[height..height + confirmations as usize].iter().map(|header| hex::encode(header.to_bytes())).collect(),
+            simulated: true,
+            fetched_at: now,
+        })
+    }
+}
+
+/// The Bitcoin side of archives: proofs from Esplora, or the simulated chain
+pub struct BitcoinChain {
+    pub config: SpvConfig,
+    esplora: Option<Esplora>,
+    pub simulated: SimulatedBitcoin,
+}
+
+impl BitcoinChain {
+    pub fn new(config: SpvConfig) -> Self {
+        let esplora = config.esplora_url.as_deref().map(Esplora::new);
+        Self { config, esplora, simulated: SimulatedBitcoin::default() }
+    }
+
+    /// A transaction paying `script`, mined into the simulated chain;
+    /// returns its tx reference (`0x` + displayed txid)
+    pub fn send_simulated(&mut self, script: &[u8], now: i64) -> Result<String, String> {
+        let txid = self.simulated.broadcast(simulated_tx(rand::random(), script), now)?;
+        Ok(format!("0x{}", txid))
+    }
+
+    /// The proof of `tx_hash`, from the simulated chain when it was mined
+    /// there, else from Esplora
+    pub async fn fetch_proof(&mut self, tx_hash: &str, now: i64) -> Result<SpvProof, String> {
+        let confirmations = self.config.confirmations;
+        if self.simulated.contains(tx_hash) {
+            return self.simulated.proof(tx_hash, confirmations, now);
+        }
+        let esplora = self.esplora.as_ref().ok_or_else(|| format!("An SPV proof of {} {}", tx_hash, NO_ESPLORA))?;
+        let proof = esplora.proof(tx_hash.strip_prefix("0x").unwrap_or(tx_hash), confirmations, now).await?;
+        info!("🟠 SPV: {} in block {} with {} confirmations", tx_hash, proof.block_height, proof.confirmations());
+        Ok(proof)
+    }
+
+    /// Check `proof` locally; simulated proofs at regtest difficulty
+    pub fn verify(&self, proof: &SpvProof, tx_hash: &str, dna_hash: &str) -> Result<SpvVerification, String> {
+        let network = if proof.simulated { BitcoinNetwork::Regtest } else { self.config.network };
+        proof.verify(tx_hash, dna_hash, network, self.config.confirmations)
+    }
+}
+
+impl Default for BitcoinChain {
+    fn default() -> Self {
+        Self::new(SpvConfig::default())
+    }
+}
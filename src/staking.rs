@@ -0,0 +1,204 @@
+//! Consciousness Staking V1 — PoS/PoC hybrid validator set
+//!
+//! Validators lock RSM from their reward wallet and register genomes that
+//! were already consecrated on chain. A validator's block-production weight
+//! is the total consciousness of its registered genomes, as long as it keeps
+//! at least `MIN_STAKE_RSM` locked.
+//!
+//! Stake operations are signed by the validator key and carried inside
+//! blocks, so every node derives the same stakes by replaying the chain.
+//! At each epoch boundary the heaviest `MAX_ACTIVE_VALIDATORS` stakers become
+//! the active set; block slots are assigned to them by weighted lottery.
+//!
+//! Producers caught attesting false consciousness lose part of their stake
+//! (see `slashing`).
+
+use std::collections::BTreeMap;
+use serde::{Serialize, Deserialize};
+use sha2::{Sha256, Digest};
+
+use crate::consensus::ValidatorKey;
+use crate::crypto::verify_signature;
+use crate::slashing::{ClaimEvidence, Offense, SLASH_FRACTION};
+
+/// Blocks per staking epoch
+pub const EPOCH_LENGTH: u64 = 20;
+/// Minimum locked RSM for a validator to be eligible
+pub const MIN_STAKE_RSM: f64 = 100.0;
+pub const MAX_ACTIVE_VALIDATORS: usize = 21;
+/// After this long without a block, any active validator may produce
+pub const LEADER_TIMEOUT_SECS: i64 = 120;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum StakeAction {
+    Lock { amount_rsm: f64 },
+    Unlock { amount_rsm: f64 },
+    RegisterGenome { genome_hash: [u8; 32] },
+    ReportFalseClaim { evidence: Box<ClaimEvidence> },
+}
+
+/// Signed stake operation included in a block
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StakeOp {
+    /// Validator public key (hex)
+    pub validator: String,
+    /// Must be the validator's previous nonce + 1 (replay protection)
+    pub nonce: u64,
+    pub action: StakeAction,
+    pub signature: String,
+}
+
+impl StakeOp {
+    pub fn new(key: &ValidatorKey, nonce: u64, action: StakeAction) -> Self {
+        let mut op = Self { validator: key.public_key_hex(), nonce, action, signature: String::new() };
+        op.signature = hex::encode(key.sign(&op.signing_bytes()));
+        op
+    }
+
+    fn signing_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.validator.as_bytes().to_vec();
+        bytes.extend_from_slice(&self.nonce.to_le_bytes());
+        match &self.action {
+            StakeAction::Lock { amount_rsm } => {
+                bytes.push(0);
+                bytes.extend_from_slice(&amount_rsm.to_le_bytes());
+            }
+            StakeAction::Unlock { amount_rsm } => {
+                bytes.push(1);
+                bytes.extend_from_slice(&amount_rsm.to_le_bytes());
+            }
+            StakeAction::RegisterGenome { genome_hash } => {
+                bytes.push(2);
+                bytes.extend_from_slice(genome_hash);
+            }
+            StakeAction::ReportFalseClaim { evidence } => {
+                bytes.push(3);
+                bytes.extend_from_slice(&evidence.hash());
+            }
+        }
+        bytes
+    }
+
+    /// Commitment included in the block header hash
+    pub fn hash(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(self.signing_bytes());
+        hasher.update(self.signature.as_bytes());
+        hasher.finalize().into()
+    }
+
+    pub fn verify_signature(&self) -> bool {
+        match (hex::decode(&self.validator), hex::decode(&self.signature)) {
+            (Ok(public_key), Ok(signature)) => verify_signature(&public_key, &self.signing_bytes(), &signature),
+            _ => false,
+        }
+    }
+}
+
+/// Genome whose consciousness backs a validator's weight
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StakedGenome {
+    pub genome_id: i64,
+    pub genome_hash: [u8; 32],
+    pub consciousness: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StakeRecord {
+    pub staked_rsm: f64,
+    pub genomes: Vec<StakedGenome>,
+    pub nonce: u64,
+}
+
+impl StakeRecord {
+    /// Block-production weight (0 when under the minimum stake)
+    pub fn weight(&self) -> u64 {
+        if self.staked_rsm < MIN_STAKE_RSM {
+            return 0;
+        }
+        self.genomes.iter().map(|g| g.consciousness as u64).sum()
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StakingState {
+    pub stakes: BTreeMap<String, StakeRecord>,
+    /// Active validators of the current epoch with their weights
+    pub active: Vec<(String, u64)>,
+    pub epoch: u64,
+    /// Slashed false claims, oldest first
+    #[serde(default)]
+    pub offenses: Vec<Offense>,
+}
+
+impl StakingState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_active(&self, validator: &str) -> bool {
+        self.active.iter().any(|(key, _)| key == validator)
+    }
+
+    pub fn total_active_weight(&self) -> u64 {
+        self.active.iter().map(|(_, w)| w).sum()
+    }
+
+    pub fn next_nonce(&self, validator: &str) -> u64 {
+        self.stakes.get(validator).map_or(0, |r| r.nonce) + 1
+    }
+
+    pub fn genome_staker(&self, genome_hash: &[u8; 32]) -> Option<&str> {
+        self.stakes.iter()
+            .find(|(_, r)| r.genomes.iter().any(|g| &g.genome_hash == genome_hash))
+            .map(|(key, _)| key.as_str())
+    }
+
+    /// Burn `SLASH_FRACTION` of the producer's stake for a checked offense
+    pub fn slash(&mut self, mut offense: Offense) -> Result<(), String> {
+        if self.offenses.iter().any(|o| o.block_hash == offense.block_hash && o.genome_hash == offense.genome_hash) {
+            return Err("offense already slashed".to_string());
+        }
+        if let Some(record) = self.stakes.get_mut(&offense.producer) {
+            offense.slashed_rsm = record.staked_rsm * SLASH_FRACTION;
+            record.staked_rsm -= offense.slashed_rsm;
+        }
+        self.offenses.push(offense);
+        Ok(())
+    }
+
+    /// Heaviest eligible stakers become the active set for the next epoch
+    pub fn rotate_epoch(&mut self, epoch: u64) {
+        let mut ranked: Vec<(String, u64)> = self.stakes.iter()
+            .map(|(key, record)| (key.clone(), record.weight()))
+            .filter(|(_, weight)| *weight > 0)
+            .collect();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        ranked.truncate(MAX_ACTIVE_VALIDATORS);
+
+        self.active = ranked;
+        self.epoch = epoch;
+    }
+
+    /// Weighted lottery over the active set, seeded by the parent hash
+    pub fn slot_leader(&self, previous_hash: &str, height: u64) -> Option<&str> {
+        let total = self.total_active_weight();
+        if total == 0 {
+            return None;
+        }
+
+        let mut hasher = Sha256::new();
+        hasher.update(previous_hash.as_bytes());
+        hasher.update(height.to_le_bytes());
+        let seed = hasher.finalize();
+        let mut ticket = u64::from_le_bytes(seed[..8].try_into().expect("8 bytes")) % total;
+
+        for (key, weight) in &self.active {
+            if ticket < *weight {
+                return Some(key);
+            }
+            ticket -= weight;
+        }
+        None
+    }
+}
@@ -0,0 +1,330 @@
+//! Content-Addressed Storage V1 — whole genomes on IPFS and Arweave
+//!
+//! The chain layers archive a genome's DNA hash, which proves what a genome
+//! was but cannot bring it back. The storage layers keep the genome itself:
+//! an `ipfs` or `arweave` archive stores the genome's `StoredGenome`
+//! document (JSON: id, DNA, DNA hash and the rest of its state), and the
+//! entry's tx reference says where to fetch it.
+//!
+//! IPFS: the reference is the document's CIDv1 (raw codec, SHA-256, base32:
+//! `bafkrei…`), computed locally, so fetched content proves itself. With
+//! `IPFS_API_URL` (a Kubo node's RPC API, e.g. `http://127.0.0.1:5001`)
+//! documents are added to and pinned on the node, which must answer with
+//! the same CID, and read back from it. Without it IPFS is simulated.
+//!
+//! Arweave: the reference is a 43-character transaction id. Uploads are
+//! simulated (a real one is signed by an Arweave RSA wallet, which the node
+//! does not hold); with `ARWEAVE_GATEWAY_URL` other transactions are read
+//! back from the gateway.
+//!
+//! `STORAGE_ANCHOR_LAYER` (`bitcoin` or `ethereum`) anchors every stored
+//! document there too: its SHA-256, the digest an IPFS CID names, is
+//! archived the way a DNA hash would be.
+
+use std::time::Duration;
+
+use base64::Engine;
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use tracing::info;
+
+use crate::genome::{hash_genome_dna, Genome, GenomeBuilder};
+use crate::multi_chain::BlockchainLayer;
+use crate::rotation::Rot180;
+
+/// `format` of genome documents
+pub const GENOME_DOCUMENT_FORMAT: &str = "divine-genome/v1";
+pub const STORAGE_TIMEOUT_SECS: u64 = 30;
+/// Storage fee Arweave is assumed to charge per byte stored for good
+pub const ARWEAVE_WINSTON_PER_BYTE: f64 = 2_500.0;
+/// CIDv1 header of raw content: version 1, raw codec, a 32-byte SHA-256
+const CID_RAW_SHA256: [u8; 4] = [0x01, 0x55, 0x12, 0x20];
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// A genome as the storage layers keep it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredGenome {
+    pub format: String,
+    pub genome_id: i64,
+    pub dna: String,
+    pub dna_hash: String,
+    pub consciousness: u32,
+    pub mutations: u64,
+    pub p53_copies: u8,
+    pub telomere_length: u16,
+    pub division_count: u8,
+    pub sequencing_errors: u8,
+    pub created_at: i64,
+}
+
+impl StoredGenome {
+    pub fn new(genome: &Genome<Rot180>) -> Self {
+        let dna = genome.to_dna_string();
+        Self {
+            format: GENOME_DOCUMENT_FORMAT.to_string(),
+            genome_id: genome.db_id.unwrap_or(0),
+            dna_hash: hex::encode(hash_genome_dna(&dna)),
+            dna,
+            consciousness: genome.consciousness,
+            mutations: genome.mutations,
+            p53_copies: genome.p53_copies,
+            telomere_length: genome.telomere_length,
+            division_count: genome.division_count,
+            sequencing_errors: genome.sequencing_errors,
+            created_at: genome.created_at,
+        }
+    }
+
+    /// The document as stored; the same genome always gives the same bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(self).unwrap_or_default()
+    }
+
+    /// A document read back, refused unless it is a genome document whose
+    /// DNA still hashes to its `dna_hash`
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let document: Self = serde_json::from_slice(bytes).map_err(|e| format!("Not a genome document: {}", e))?;
+        if document.format != GENOME_DOCUMENT_FORMAT {
+            return Err(format!("Unknown genome document format {}", document.format));
+        }
+        if hex::encode(hash_genome_dna(&document.dna)) != document.dna_hash {
+            return Err(format!("Genome #{}'s DNA does not hash to its dna_hash", document.genome_id));
+        }
+        Ok(document)
+    }
+
+    pub fn dna_hash(&self) -> [u8; 32] {
+        hash_genome_dna(&self.dna)
+    }
+
+    /// The genome back, as it was when stored
+    pub fn genome(&self) -> Result<Genome<Rot180>, String> {
+        let mut genome = GenomeBuilder::from_dna(&self.dna)
+            .ok_or_else(|| format!("Genome #{} has invalid DNA", self.genome_id))?
+            .p53_copies(self.p53_copies)
+            .telomere_length(self.telomere_length)
+            .build_storage();
+        genome.mutations = self.mutations;
+        genome.division_count = self.division_count;
+        genome.sequencing_errors = self.sequencing_errors;
+        genome.created_at = self.created_at;
+        genome.consciousness = self.consciousness;
+        genome.db_id = Some(self.genome_id).filter(|id| *id > 0);
+        genome.rehash();
+        Ok(genome)
+    }
+}
+
+/// Where a stored document is anchored on-chain
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StorageAnchor {
+    pub layer: BlockchainLayer,
+    pub tx_hash: String,
+    /// SHA-256 of the stored document, as the anchor embeds it
+    pub content_hash: String,
+}
+
+pub fn content_digest(bytes: &[u8]) -> [u8; 32] {
+    Sha256::digest(bytes).into()
+}
+
+fn base32(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 8 / 5 + 1);
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for &byte in bytes {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(BASE32_ALPHABET[(buffer >> bits) as usize & 31] as char);
+        }
+        buffer &= (1 << bits) - 1;
+    }
+    if bits > 0 {
+        out.push(BASE32_ALPHABET[(buffer << (5 - bits)) as usize & 31] as char);
+    }
+    out
+}
+
+fn base32_decode(text: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(text.len() * 5 / 8);
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for c in text.bytes() {
+        let value = BASE32_ALPHABET.iter().position(|&a| a == c)? as u32;
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+        buffer &= (1 << bits) - 1;
+    }
+    Some(out)
+}
+
+/// CIDv1 of raw `bytes` (what `ipfs add --cid-version 1 --raw-leaves`
+/// gives a file of one chunk)
+pub fn ipfs_cid(bytes: &[u8]) -> String {
+    format!("b{}", base32(&[&CID_RAW_SHA256[..], &content_digest(bytes)].concat()))
+}
+
+/// The SHA-256 a raw CIDv1 names
+pub fn cid_digest(cid: &str) -> Option<[u8; 32]> {
+    let bytes = base32_decode(cid.strip_prefix('b')?)?;
+    bytes.strip_prefix(&CID_RAW_SHA256[..])?.try_into().ok()
+}
+
+/// Id of a simulated Arweave upload: the document's SHA-256, base64url
+pub fn arweave_id(bytes: &[u8]) -> String {
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(content_digest(bytes))
+}
+
+/// Whether `id` has the shape of an Arweave transaction id
+pub fn is_arweave_id(id: &str) -> bool {
+    id.len() == 43 && id.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+}
+
+/// The storage layers' nodes and anchor
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StorageConfig {
+    /// Kubo RPC API documents are added to; simulated when unset
+    pub ipfs_api_url: Option<String>,
+    /// Gateway Arweave transactions are read from
+    pub arweave_gateway_url: Option<String>,
+    /// Chain each stored document is also anchored on
+    pub anchor: Option<BlockchainLayer>,
+}
+
+impl StorageConfig {
+    /// `IPFS_API_URL`, `ARWEAVE_GATEWAY_URL` and `STORAGE_ANCHOR_LAYER`
+    pub fn from_env() -> Result<Self, String> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        let config = Self {
+            ipfs_api_url: var("IPFS_API_URL").map(|url| url.trim_end_matches('/').to_string()),
+            arweave_gateway_url: var("ARWEAVE_GATEWAY_URL").map(|url| url.trim_end_matches('/').to_string()),
+            anchor: var("STORAGE_ANCHOR_LAYER").map(|name| BlockchainLayer::from_name(&name)).transpose()?,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, url) in [("IPFS_API_URL", &self.ipfs_api_url), ("ARWEAVE_GATEWAY_URL", &self.arweave_gateway_url)] {
+            if let Some(url) = url.as_deref().filter(|url| !url.starts_with("http://") && !url.starts_with("https://")) {
+                return Err(format!("{} must be an http(s) URL, not {}", name, url));
+            }
+        }
+        match self.anchor {
+            None | Some(BlockchainLayer::Bitcoin) | Some(BlockchainLayer::Ethereum) => Ok(()),
+            Some(layer) => Err(format!("STORAGE_ANCHOR_LAYER must be bitcoin or ethereum, not {}", layer.key())),
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════
+// NODES
+// ═══════════════════════════════════════════════════════════════
+
+fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(STORAGE_TIMEOUT_SECS))
+        .user_agent(format!("divine-agi/{}", crate::VERSION))
+        .build()
+        .unwrap_or_default()
+}
+
+/// A Kubo node's RPC API
+#[derive(Clone)]
+pub struct IpfsNode {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl IpfsNode {
+    pub fn new(url: &str) -> Self {
+        Self { client: http_client(), url: url.to_string() }
+    }
+
+    /// Add and pin `bytes`; returns the CID the node stored them under
+    pub async fn add(&self, bytes: &[u8]) -> Result<String, String> {
+        let boundary = format!("divine-{}", hex::encode(&content_digest(bytes)[..8]));
+        let body = [
+            format!("--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"genome.json\"\r\nContent-Type: application/json\r\n\r\n", boundary).as_bytes(),
+            bytes,
+            format!("\r\n--{}--\r\n", boundary).as_bytes(),
+        ].concat();
+        let response = self.client.post(format!("{}/api/v0/add?cid-version=1&raw-leaves=true&pin=true", self.url))
+            .header(reqwest::header::CONTENT_TYPE, format!("multipart/form-data; boundary={}", boundary))
+            .body(body)
+            .send().await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| format!("IPFS add: {}", e))?;
+        let reply = response.bytes().await.map_err(|e| format!("IPFS add: {}", e))?;
+        let reply: Value = serde_json::from_slice(&reply).map_err(|e| format!("IPFS add: {}", e))?;
+        reply["Hash"].as_str().map(str::to_string).ok_or_else(|| "IPFS add: no CID".into())
+    }
+
+    /// The content stored under `cid`
+    pub async fn cat(&self, cid: &str) -> Result<Vec<u8>, String> {
+        let response = self.client.post(format!("{}/api/v0/cat?arg={}", self.url, cid))
+            .send().await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| format!("IPFS cat {}: {}", cid, e))?;
+        response.bytes().await.map(|bytes| bytes.to_vec()).map_err(|e| format!("IPFS cat {}: {}", cid, e))
+    }
+}
+
+/// The real storage layers, where configured
+pub struct ContentStorage {
+    pub config: StorageConfig,
+    ipfs: Option<IpfsNode>,
+    client: reqwest::Client,
+}
+
+impl ContentStorage {
+    pub fn new(config: StorageConfig) -> Self {
+        let ipfs = config.ipfs_api_url.as_deref().map(IpfsNode::new);
+        Self { config, ipfs, client: http_client() }
+    }
+
+    /// Whether IPFS documents go to a real node
+    pub fn uses_ipfs_node(&self) -> bool {
+        self.ipfs.is_some()
+    }
+
+    /// Store `document` on the IPFS node; returns its CID, refused unless
+    /// the node stored it under the CID its content gives
+    pub async fn add_ipfs(&self, document: &[u8]) -> Result<Option<String>, String> {
+        let Some(ipfs) = &self.ipfs else {
+            return Ok(None);
+        };
+        let cid = ipfs.add(document).await?;
+        if cid != ipfs_cid(document) {
+            return Err(format!("The IPFS node stored the document as {}, not {}", cid, ipfs_cid(document)));
+        }
+        info!("📦 IPFS: pinned {} ({} bytes)", cid, document.len());
+        Ok(Some(cid))
+    }
+
+    /// The content `reference` names on `layer`, from its node or gateway
+    pub async fn fetch(&self, layer: BlockchainLayer, reference: &str) -> Result<Vec<u8>, String> {
+        match (layer, &self.ipfs, &self.config.arweave_gateway_url) {
+            (BlockchainLayer::Ipfs, Some(ipfs), _) => ipfs.cat(reference).await,
+            (BlockchainLayer::Arweave, _, Some(gateway)) => {
+                let response = self.client.get(format!("{}/{}", gateway, reference))
+                    .send().await
+                    .and_then(|r| r.error_for_status())
+                    .map_err(|e| format!("Arweave {}: {}", reference, e))?;
+                response.bytes().await.map(|bytes| bytes.to_vec()).map_err(|e| format!("Arweave {}: {}", reference, e))
+            }
+            _ => Err(format!("{} content {} not found", layer.name(), reference)),
+        }
+    }
+}
+
+impl Default for ContentStorage {
+    fn default() -> Self {
+        Self::new(StorageConfig::default())
+    }
+}
@@ -0,0 +1,268 @@
+//! Swap Simulation V1 — preview cross-asset conversions before trading
+//!
+//! A swap is routed through USD: RSM trades against the order book and
+//! the exchange desk (`buy_rsm`/`sell_rsm` at the mark price), BTC, ETH
+//! and SOL convert at the oracle's reference prices. Each RSM leg takes
+//! book levels while they beat the desk and sends the rest to the desk,
+//! so a large order walks the book until the desk is the better price.
+//!
+//! Nothing is traded: `simulate` reads a depth snapshot and returns the
+//! expected output, the price impact against the best price on offer and
+//! the fees each venue would charge, so bots can size large RSM↔SOL
+//! conversions before placing them. Book fills pay the taker fee (`fees`):
+//! withheld from the RSM a buy receives, on top of the RSM a sell gives.
+
+use std::collections::HashMap;
+
+use serde::{Serialize, Deserialize};
+
+use crate::exchange::DESK_SELL_FEE;
+use crate::oracle::Pair;
+use crate::order_book::{Depth, PriceLevel, Side};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Asset {
+    Usd,
+    Rsm,
+    Btc,
+    Eth,
+    Sol,
+}
+
+impl Asset {
+    pub const ALL: [Asset; 5] = [Asset::Usd, Asset::Rsm, Asset::Btc, Asset::Eth, Asset::Sol];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Asset::Usd => "USD",
+            Asset::Rsm => "RSM",
+            Asset::Btc => "BTC",
+            Asset::Eth => "ETH",
+            Asset::Sol => "SOL",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Asset::ALL.into_iter().find(|asset| asset.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Oracle pair a coin converts to USD at; RSM trades on the exchange
+    fn reference_pair(self) -> Option<Pair> {
+        match self {
+            Asset::Btc => Some(Pair::BtcUsd),
+            Asset::Eth => Some(Pair::EthUsd),
+            Asset::Sol => Some(Pair::SolUsd),
+            Asset::Usd | Asset::Rsm => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Venue {
+    /// Resting orders, at their prices
+    OrderBook,
+    /// `buy_rsm`/`sell_rsm` at the exchange's mark price
+    Desk,
+    /// The oracle's reference price
+    Reference,
+}
+
+/// Part of one leg filled at one venue
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SwapFill {
+    pub venue: Venue,
+    pub amount_in: f64,
+    pub amount_out: f64,
+    /// USD per RSM or per coin
+    pub average_price_usd: f64,
+    /// Book price levels taken (0 off the book)
+    pub levels: usize,
+}
+
+/// One conversion of the route, to or from USD
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SwapLeg {
+    pub from: Asset,
+    pub to: Asset,
+    pub amount_in: f64,
+    pub amount_out: f64,
+    /// Output had the whole leg filled at the best price on offer
+    pub spot_out: f64,
+    pub fills: Vec<SwapFill>,
+}
+
+/// A fee a venue charges on the swap. Desk sells burn `DESK_SELL_FEE` of
+/// the RSM sold from supply, without reducing what the seller is paid.
+/// The book's taker fee goes to the exchange's fee accrual wallet.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SwapFee {
+    pub venue: Venue,
+    pub asset: Asset,
+    pub amount: f64,
+    pub amount_usd: f64,
+    pub burned: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SwapQuote {
+    pub from: Asset,
+    pub to: Asset,
+    pub amount_in: f64,
+    /// Expected output, after fees
+    pub amount_out: f64,
+    /// Output at the best prices on offer, before impact and fees
+    pub spot_out: f64,
+    /// Share of `spot_out` lost to walking the book, fees aside (0.01 = 1%)
+    pub price_impact: f64,
+    pub fees: Vec<SwapFee>,
+    pub fees_usd: f64,
+    pub legs: Vec<SwapLeg>,
+}
+
+/// Prices a swap is simulated against
+#[derive(Debug, Clone, Copy)]
+pub struct SwapMarket<'a> {
+    /// Both sides of the order book, as deep as they go
+    pub depth: &'a Depth,
+    /// Desk price of RSM in USD
+    pub desk_price_usd: f64,
+    /// USD per BTC, ETH and SOL
+    pub reference_prices: &'a HashMap<Pair, f64>,
+    /// Share of the RSM traded on the book charged as the taker fee
+    pub taker_fee: f64,
+}
+
+/// Simulate swapping `amount` of `from` into `to` on `market`
+pub fn simulate(market: &SwapMarket<'_>, from: Asset, to: Asset, amount: f64) -> Result<SwapQuote, String> {
+    if from == to {
+        return Err(format!("Cannot swap {} for itself", from.name()));
+    }
+    if !amount.is_finite() || amount <= 0.0 {
+        return Err(format!("Invalid swap amount {}", amount));
+    }
+    if !market.desk_price_usd.is_finite() || market.desk_price_usd <= 0.0 {
+        return Err(format!("Invalid desk price {}", market.desk_price_usd));
+    }
+
+    let mut legs = Vec::new();
+    let mut fees = Vec::new();
+    let mut usd = amount;
+    if from != Asset::Usd {
+        let leg = if from == Asset::Rsm { sell_rsm(market, amount, &mut fees) } else { to_usd(market, from, amount)? };
+        usd = leg.amount_out;
+        legs.push(leg);
+    }
+    if to != Asset::Usd {
+        legs.push(if to == Asset::Rsm { buy_rsm(market, usd, &mut fees) } else { from_usd(market, to, usd)? });
+    }
+
+    let amount_out = legs.last().map_or(amount, |leg| leg.amount_out);
+    let spot_rate: f64 = legs.iter().map(|leg| leg.spot_out / leg.amount_in).product();
+    let spot_out = amount * spot_rate;
+    let fees_usd = fees.iter().map(|fee| fee.amount_usd).sum();
+    // Fees withheld from the output are not impact
+    let withheld: f64 = fees.iter().filter(|fee| fee.asset == to).map(|fee| fee.amount).sum();
+    Ok(SwapQuote {
+        from,
+        to,
+        amount_in: amount,
+        amount_out,
+        spot_out,
+        price_impact: (1.0 - (amount_out + withheld) / spot_out).max(0.0),
+        fees,
+        fees_usd,
+        legs,
+    })
+}
+
+/// RSM → USD: bids above the desk price, then the desk
+fn sell_rsm(market: &SwapMarket<'_>, amount_rsm: f64, fees: &mut Vec<SwapFee>) -> SwapLeg {
+    let desk = market.desk_price_usd;
+    let better: Vec<&PriceLevel> = market.depth.bids.iter().take_while(|level| level.price_usd > desk).collect();
+    let best_price = better.first().map_or(desk, |level| level.price_usd);
+
+    let mut left = amount_rsm;
+    let mut fills: Vec<SwapFill> = walk(&better, Side::Sell, &mut left).into_iter().collect();
+    if let Some(fill) = fills.first().filter(|_| market.taker_fee > 0.0) {
+        let fee = fill.amount_in * market.taker_fee;
+        fees.push(SwapFee { venue: Venue::OrderBook, asset: Asset::Rsm, amount: fee, amount_usd: fee * fill.average_price_usd, burned: false });
+    }
+    if left > 0.0 {
+        let fee = left * DESK_SELL_FEE;
+        fees.push(SwapFee { venue: Venue::Desk, asset: Asset::Rsm, amount: fee, amount_usd: fee * desk, burned: true });
+        fills.push(desk_fill(left, left * desk, desk));
+    }
+    leg(Asset::Rsm, Asset::Usd, amount_rsm, amount_rsm * best_price, fills)
+}
+
+/// USD → RSM: asks below the desk price, then the desk
+fn buy_rsm(market: &SwapMarket<'_>, amount_usd: f64, fees: &mut Vec<SwapFee>) -> SwapLeg {
+    let desk = market.desk_price_usd;
+    let better: Vec<&PriceLevel> = market.depth.asks.iter().take_while(|level| level.price_usd < desk).collect();
+    let best_price = better.first().map_or(desk, |level| level.price_usd);
+
+    let mut left = amount_usd;
+    let mut fills: Vec<SwapFill> = walk(&better, Side::Buy, &mut left).into_iter().collect();
+    if let Some(fill) = fills.first_mut().filter(|_| market.taker_fee > 0.0) {
+        let fee = fill.amount_out * market.taker_fee;
+        fill.amount_out -= fee;
+        fees.push(SwapFee { venue: Venue::OrderBook, asset: Asset::Rsm, amount: fee, amount_usd: fee * fill.average_price_usd, burned: false });
+    }
+    if left > 0.0 {
+        fills.push(desk_fill(left, left / desk, desk));
+    }
+    leg(Asset::Usd, Asset::Rsm, amount_usd, amount_usd / best_price, fills)
+}
+
+/// Take book `levels` best first as a `side` order would until `left`
+/// (RSM when selling, USD when buying) runs out
+fn walk(levels: &[&PriceLevel], side: Side, left: &mut f64) -> Option<SwapFill> {
+    let (mut rsm, mut usd, mut taken) = (0.0, 0.0, 0);
+    for level in levels {
+        if *left <= 0.0 {
+            break;
+        }
+        let available = match side {
+            Side::Sell => level.amount_rsm,
+            Side::Buy => level.amount_rsm * level.price_usd,
+        };
+        let used = available.min(*left);
+        *left = if available >= *left { 0.0 } else { *left - available };
+        let level_rsm = if side == Side::Sell { used } else { used / level.price_usd };
+        rsm += level_rsm;
+        usd += level_rsm * level.price_usd;
+        taken += 1;
+    }
+    (taken > 0).then(|| {
+        let (amount_in, amount_out) = if side == Side::Sell { (rsm, usd) } else { (usd, rsm) };
+        SwapFill { venue: Venue::OrderBook, amount_in, amount_out, average_price_usd: usd / rsm, levels: taken }
+    })
+}
+
+fn desk_fill(amount_in: f64, amount_out: f64, price_usd: f64) -> SwapFill {
+    SwapFill { venue: Venue::Desk, amount_in, amount_out, average_price_usd: price_usd, levels: 0 }
+}
+
+fn reference_price(market: &SwapMarket<'_>, asset: Asset) -> Result<f64, String> {
+    asset.reference_pair()
+        .and_then(|pair| market.reference_prices.get(&pair).copied())
+        .ok_or_else(|| format!("No {}/USD reference price", asset.name()))
+}
+
+fn to_usd(market: &SwapMarket<'_>, asset: Asset, amount: f64) -> Result<SwapLeg, String> {
+    let price = reference_price(market, asset)?;
+    let fill = SwapFill { venue: Venue::Reference, amount_in: amount, amount_out: amount * price, average_price_usd: price, levels: 0 };
+    Ok(leg(asset, Asset::Usd, amount, amount * price, vec![fill]))
+}
+
+fn from_usd(market: &SwapMarket<'_>, asset: Asset, amount_usd: f64) -> Result<SwapLeg, String> {
+    let price = reference_price(market, asset)?;
+    let fill = SwapFill { venue: Venue::Reference, amount_in: amount_usd, amount_out: amount_usd / price, average_price_usd: price, levels: 0 };
+    Ok(leg(Asset::Usd, asset, amount_usd, amount_usd / price, vec![fill]))
+}
+
+fn leg(from: Asset, to: Asset, amount_in: f64, spot_out: f64, fills: Vec<SwapFill>) -> SwapLeg {
+    SwapLeg { from, to, amount_in, amount_out: fills.iter().map(|fill| fill.amount_out).sum(), spot_out, fills }
+}
@@ -0,0 +1,366 @@
+//! Lightning Swarm V1 — the nodes Lightning archives broadcast to
+//!
+//! A Lightning archive is one keysend to each swarm node, sent to all of
+//! them at once. Nodes come from `LN_SWARM_PUBKEYS` (pubkeys only),
+//! `LN_SWARM_NODES` (`pubkey[@host:port][/capacity_sat]`, comma-separated)
+//! and registrations at runtime. A node has the endpoint its peers connect
+//! to and the capacity of the channels that reach it: a node whose capacity
+//! cannot take one keysend is left out of broadcasts.
+//!
+//! Each node counts its keysends (sent, landed, failed, fees, the last
+//! failure). Every outcome is also reported to Mission Control, whose
+//! probability for the pair decides whether the node is tried at all.
+//!
+//! Health: every `SWARM_HEALTH_CHECK_SECS` the server dials each node's
+//! endpoint (TCP, within `SWARM_PROBE_TIMEOUT_SECS`). A node that cannot be
+//! dialed is `unreachable`, reported to Mission Control as a failure, and
+//! skipped until it answers again. One whose last
+//! `SWARM_MAX_CONSECUTIVE_FAILURES` keysends failed, or that lands under
+//! half its keysends, is `degraded`.
+
+use std::time::Duration;
+
+use serde::{Serialize, Deserialize};
+
+use crate::lightning::{PaymentStatus, PaymentUpdate};
+
+pub const SWARM_HEALTH_CHECK_SECS: u64 = 60;
+pub const SWARM_PROBE_TIMEOUT_SECS: u64 = 5;
+/// Keysend failures in a row that make a node degraded
+pub const SWARM_MAX_CONSECUTIVE_FAILURES: u32 = 3;
+/// Keysends before a node's success rate can make it degraded
+pub const SWARM_MIN_KEYSENDS: u64 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeHealth {
+    /// Never dialed, no keysend landed yet
+    Unknown,
+    Healthy,
+    Degraded,
+    Unreachable,
+}
+
+/// A node to add to the swarm, or new details for one already in it
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NodeRegistration {
+    pub pubkey: String,
+    /// `host:port` its peers connect to
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    /// Sat the channels reaching it can carry
+    #[serde(default)]
+    pub capacity_sat: Option<i64>,
+    #[serde(default)]
+    pub alias: Option<String>,
+}
+
+impl NodeRegistration {
+    /// `pubkey[@host:port][/capacity_sat]`
+    pub fn parse(entry: &str) -> Result<Self, String> {
+        let (rest, capacity) = match entry.trim().split_once('/') {
+            Some((rest, capacity)) => (rest, Some(capacity.parse().map_err(|_| format!("Invalid swarm node capacity: {}", capacity))?)),
+            None => (entry.trim(), None),
+        };
+        let (pubkey, endpoint) = match rest.split_once('@') {
+            Some((pubkey, endpoint)) => (pubkey, Some(endpoint.to_string())),
+            None => (rest, None),
+        };
+        let registration = Self { pubkey: pubkey.to_string(), endpoint, capacity_sat: capacity, alias: None };
+        registration.validate()?;
+        Ok(registration)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let pubkey = hex::decode(&self.pubkey).map_err(|_| format!("Invalid node pubkey {}", self.pubkey))?;
+        if pubkey.len() != 33 || !matches!(pubkey[0], 2 | 3) {
+            return Err(format!("Invalid node pubkey {} (expected 33 compressed bytes)", self.pubkey));
+        }
+        if let Some(endpoint) = &self.endpoint {
+            let port = endpoint.rsplit_once(':').filter(|(host, _)| !host.is_empty()).and_then(|(_, port)| port.parse::<u16>().ok());
+            if port.is_none() {
+                return Err(format!("Invalid node endpoint {} (expected host:port)", endpoint));
+            }
+        }
+        if self.capacity_sat.is_some_and(|capacity| capacity < 0) {
+            return Err("A node's capacity cannot be negative".into());
+        }
+        Ok(())
+    }
+}
+
+/// A node's keysends so far
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NodeMetrics {
+    pub keysends: u64,
+    pub succeeded: u64,
+    pub failed: u64,
+    pub fees_msat: i64,
+    pub consecutive_failures: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_success_at: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_failure_at: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_failure_reason: Option<String>,
+}
+
+impl NodeMetrics {
+    /// Share of settled keysends that landed
+    pub fn success_rate(&self) -> Option<f64> {
+        let settled = self.succeeded + self.failed;
+        (settled > 0).then(|| self.succeeded as f64 / settled as f64)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SwarmNode {
+    pub pubkey: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capacity_sat: Option<i64>,
+    pub registered_at: i64,
+    pub health: NodeHealth,
+    /// Whether the endpoint answered when last dialed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reachable: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_checked_at: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub probe_error: Option<String>,
+    pub metrics: NodeMetrics,
+}
+
+impl SwarmNode {
+    pub fn new(registration: NodeRegistration, now: i64) -> Self {
+        Self {
+            pubkey: registration.pubkey,
+            alias: registration.alias,
+            endpoint: registration.endpoint,
+            capacity_sat: registration.capacity_sat,
+            registered_at: now,
+            health: NodeHealth::Unknown,
+            reachable: None,
+            last_checked_at: None,
+            probe_error: None,
+            metrics: NodeMetrics::default(),
+        }
+    }
+
+    /// Whether a keysend of `amount_sat` fits the node's capacity
+    pub fn can_carry(&self, amount_sat: i64) -> bool {
+        self.capacity_sat.is_none_or(|capacity| capacity >= amount_sat)
+    }
+
+    /// Whether broadcasts include the node
+    pub fn in_broadcast(&self, amount_sat: i64) -> bool {
+        self.health != NodeHealth::Unreachable && self.can_carry(amount_sat)
+    }
+
+    /// Count a keysend's outcome
+    pub fn record(&mut self, update: &PaymentUpdate, now: i64) {
+        let metrics = &mut self.metrics;
+        metrics.keysends += 1;
+        match update.status {
+            PaymentStatus::Succeeded => {
+                metrics.succeeded += 1;
+                metrics.fees_msat += update.fee_msat;
+                metrics.consecutive_failures = 0;
+                metrics.last_success_at = Some(now);
+            }
+            PaymentStatus::Failed => {
+                metrics.failed += 1;
+                metrics.consecutive_failures += 1;
+                metrics.last_failure_at = Some(now);
+                metrics.last_failure_reason = update.failure_reason.clone();
+            }
+            PaymentStatus::InFlight => {}
+        }
+        self.assess();
+    }
+
+    /// Take the result of dialing the endpoint
+    pub fn probed(&mut self, result: Result<(), String>, now: i64) {
+        self.reachable = Some(result.is_ok());
+        self.probe_error = result.err();
+        self.last_checked_at = Some(now);
+        self.assess();
+    }
+
+    fn assess(&mut self) {
+        let metrics = &self.metrics;
+        let failing = metrics.consecutive_failures >= SWARM_MAX_CONSECUTIVE_FAILURES
+            || (metrics.keysends >= SWARM_MIN_KEYSENDS && metrics.success_rate().is_some_and(|rate| rate < 0.5));
+        self.health = if self.reachable == Some(false) {
+            NodeHealth::Unreachable
+        } else if failing {
+            NodeHealth::Degraded
+        } else if self.reachable == Some(true) || metrics.succeeded > 0 {
+            NodeHealth::Healthy
+        } else {
+            NodeHealth::Unknown
+        };
+    }
+}
+
+/// A swarm node with Mission Control's probability for it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwarmNodeReport {
+    #[serde(flatten)]
+    pub node: SwarmNode,
+    /// Learned probability a keysend from this node reaches it
+    pub probability: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwarmStatus {
+    pub nodes: Vec<SwarmNodeReport>,
+    pub healthy: usize,
+    pub degraded: usize,
+    pub unreachable: usize,
+    pub unknown: usize,
+}
+
+/// The registered nodes, in registration order
+#[derive(Debug, Clone, Default)]
+pub struct Swarm {
+    nodes: Vec<SwarmNode>,
+}
+
+impl Swarm {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Nodes known only by pubkey, unchecked
+    pub fn from_pubkeys<I: IntoIterator<Item = String>>(pubkeys: I, now: i64) -> Self {
+        let mut swarm = Self::new();
+        for pubkey in pubkeys {
+            swarm.add_pubkey(&pubkey, now);
+        }
+        swarm
+    }
+
+    /// `LN_SWARM_PUBKEYS` and `LN_SWARM_NODES`, plus `own_pubkey`
+    pub fn from_env(own_pubkey: &str, now: i64) -> Result<Self, String> {
+        let var = |name: &str| std::env::var(name).unwrap_or_default();
+        let pubkeys = var("LN_SWARM_PUBKEYS");
+        let mut swarm = Self::from_pubkeys(pubkeys.split(',').map(str::trim).filter(|s| !s.is_empty()).map(str::to_string), now);
+        for entry in var("LN_SWARM_NODES").split(',').filter(|s| !s.trim().is_empty()) {
+            swarm.register(NodeRegistration::parse(entry).map_err(|e| format!("LN_SWARM_NODES: {}", e))?, now)?;
+        }
+        swarm.add_pubkey(own_pubkey, now);
+        Ok(swarm)
+    }
+
+    fn add_pubkey(&mut self, pubkey: &str, now: i64) {
+        if self.get(pubkey).is_none() {
+            self.nodes.push(SwarmNode::new(NodeRegistration { pubkey: pubkey.to_string(), ..NodeRegistration::default() }, now));
+        }
+    }
+
+    /// Add a node, or update the endpoint, capacity and alias of one
+    /// already in the swarm (its metrics stay)
+    pub fn register(&mut self, registration: NodeRegistration, now: i64) -> Result<&SwarmNode, String> {
+        registration.validate()?;
+        let index = match self.nodes.iter().position(|node| node.pubkey == registration.pubkey) {
+            Some(index) => {
+                let node = &mut self.nodes[index];
+                if node.endpoint != registration.endpoint {
+                    node.reachable = None;
+                    node.probe_error = None;
+                }
+                node.endpoint = registration.endpoint;
+                node.capacity_sat = registration.capacity_sat;
+                node.alias = registration.alias.or(node.alias.take());
+                node.assess();
+                index
+            }
+            None => {
+                self.nodes.push(SwarmNode::new(registration, now));
+                self.nodes.len() - 1
+            }
+        };
+        Ok(&self.nodes[index])
+    }
+
+    pub fn remove(&mut self, pubkey: &str) -> Option<SwarmNode> {
+        let index = self.nodes.iter().position(|node| node.pubkey == pubkey)?;
+        Some(self.nodes.remove(index))
+    }
+
+    pub fn get(&self, pubkey: &str) -> Option<&SwarmNode> {
+        self.nodes.iter().find(|node| node.pubkey == pubkey)
+    }
+
+    pub fn get_mut(&mut self, pubkey: &str) -> Option<&mut SwarmNode> {
+        self.nodes.iter_mut().find(|node| node.pubkey == pubkey)
+    }
+
+    pub fn nodes(&self) -> &[SwarmNode] {
+        &self.nodes
+    }
+
+    pub fn pubkeys(&self) -> Vec<String> {
+        self.nodes.iter().map(|node| node.pubkey.clone()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Count a keysend's outcome on the node it went to
+    pub fn record(&mut self, pubkey: &str, update: &PaymentUpdate, now: i64) {
+        if let Some(node) = self.get_mut(pubkey) {
+            node.record(update, now);
+        }
+    }
+
+    /// `(pubkey, endpoint)` of every node with an endpoint to dial
+    pub fn endpoints(&self) -> Vec<(String, String)> {
+        self.nodes.iter().filter_map(|node| Some((node.pubkey.clone(), node.endpoint.clone()?))).collect()
+    }
+
+    /// Every node with `probability(pubkey)`, and how many are in each state
+    pub fn status(&self, probability: impl Fn(&str) -> f64) -> SwarmStatus {
+        let count = |health: NodeHealth| self.nodes.iter().filter(|node| node.health == health).count();
+        SwarmStatus {
+            nodes: self.nodes.iter().map(|node| SwarmNodeReport { node: node.clone(), probability: probability(&node.pubkey) }).collect(),
+            healthy: count(NodeHealth::Healthy),
+            degraded: count(NodeHealth::Degraded),
+            unreachable: count(NodeHealth::Unreachable),
+            unknown: count(NodeHealth::Unknown),
+        }
+    }
+}
+
+/// Dial `endpoint` (`host:port`) within `SWARM_PROBE_TIMEOUT_SECS`
+pub async fn probe(endpoint: &str) -> Result<(), String> {
+    match tokio::time::timeout(Duration::from_secs(SWARM_PROBE_TIMEOUT_SECS), tokio::net::TcpStream::connect(endpoint)).await {
+        Ok(Ok(_)) => Ok(()),
+        Ok(Err(e)) => Err(format!("{}: {}", endpoint, e)),
+        Err(_) => Err(format!("{}: no answer within {}s", endpoint, SWARM_PROBE_TIMEOUT_SECS)),
+    }
+}
+
+/// Dial every endpoint at once; results by pubkey
+pub async fn probe_all(endpoints: Vec<(String, String)>) -> Vec<(String, Result<(), String>)> {
+    let mut probes = tokio::task::JoinSet::new();
+    for (pubkey, endpoint) in endpoints {
+        probes.spawn(async move { (pubkey, probe(&endpoint).await) });
+    }
+    let mut results = Vec::new();
+    while let Some(result) = probes.join_next().await {
+        if let Ok(result) = result {
+            results.push(result);
+        }
+    }
+    results
+}
@@ -0,0 +1,164 @@
+//! TLS V1 — rustls for the REST and gRPC listeners
+//!
+//! `TLS_CERT_PATH` and `TLS_KEY_PATH` (PEM files) switch the REST API, and
+//! the gRPC API when it runs, to TLS. `TLS_CLIENT_CA_PATH` adds mutual TLS:
+//! clients may present a certificate signed by that CA, `/api/admin/` routes
+//! then require one on top of an admin token, and the gRPC API — which has no
+//! tokens — refuses clients without one. Other REST routes still serve
+//! clients without a certificate.
+
+use std::fs::File;
+use std::future::Future;
+use std::io::{self, BufReader};
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Arc;
+
+use axum::{middleware::AddExtension, Extension};
+use axum_server::accept::Accept;
+use axum_server::tls_rustls::{RustlsAcceptor, RustlsConfig};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::WebPkiClientVerifier;
+use rustls::{RootCertStore, ServerConfig};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_rustls::server::TlsStream;
+use tower_layer::Layer;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsSettings {
+    pub cert_path: PathBuf,
+    pub key_path: PathBuf,
+    /// CA that signs client certificates (mutual TLS when set)
+    pub client_ca_path: Option<PathBuf>,
+}
+
+impl TlsSettings {
+    /// TLS settings from the environment, `None` for plain TCP
+    pub fn from_env() -> Result<Option<Self>, String> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty()).map(PathBuf::from);
+        Self::from_paths(var("TLS_CERT_PATH"), var("TLS_KEY_PATH"), var("TLS_CLIENT_CA_PATH"))
+            .map_err(|_| "TLS needs both TLS_CERT_PATH and TLS_KEY_PATH (and TLS_CLIENT_CA_PATH only with them)".into())
+    }
+
+    /// TLS settings from optional paths (`serve --tls-cert ...`), `None` for
+    /// plain TCP
+    pub fn from_paths(cert_path: Option<PathBuf>, key_path: Option<PathBuf>, client_ca_path: Option<PathBuf>) -> Result<Option<Self>, String> {
+        match (cert_path, key_path) {
+            (Some(cert_path), Some(key_path)) => Ok(Some(Self { cert_path, key_path, client_ca_path })),
+            (None, None) if client_ca_path.is_none() => Ok(None),
+            _ => Err("TLS needs both a certificate and a key (and a client CA only with them)".into()),
+        }
+    }
+
+    pub fn mutual(&self) -> bool {
+        self.client_ca_path.is_some()
+    }
+
+    /// rustls server config; with a client CA, client certificates are
+    /// verified when presented but not required at the handshake
+    pub fn server_config(&self) -> Result<ServerConfig, String> {
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let builder = ServerConfig::builder_with_provider(Arc::clone(&provider))
+            .with_safe_default_protocol_versions()
+            .map_err(|e| format!("TLS protocol versions: {}", e))?;
+        let builder = match &self.client_ca_path {
+            Some(path) => {
+                let mut roots = RootCertStore::empty();
+                for cert in load_certs(path)? {
+                    roots.add(cert).map_err(|e| format!("Bad client CA in {}: {}", path.display(), e))?;
+                }
+                let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider)
+                    .allow_unauthenticated()
+                    .build()
+                    .map_err(|e| format!("Client certificate verifier: {}", e))?;
+                builder.with_client_cert_verifier(verifier)
+            }
+            None => builder.with_no_client_auth(),
+        };
+        let mut config = builder.with_single_cert(load_certs(&self.cert_path)?, load_key(&self.key_path)?)
+            .map_err(|e| format!("TLS certificate {} does not match its key: {}", self.cert_path.display(), e))?;
+        config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+        Ok(config)
+    }
+
+    /// Acceptor for the REST listener
+    pub fn acceptor(&self) -> Result<ClientCertAcceptor, String> {
+        let config = RustlsConfig::from_config(Arc::new(self.server_config()?));
+        Ok(ClientCertAcceptor { inner: RustlsAcceptor::new(config), mutual: self.mutual() })
+    }
+
+    /// tonic config for the gRPC listener; client certificates are required
+    /// when a client CA is set
+    #[cfg(feature = "grpc")]
+    pub fn grpc_config(&self) -> Result<tonic::transport::ServerTlsConfig, String> {
+        use tonic::transport::{Certificate, Identity, ServerTlsConfig};
+
+        let read = |path: &Path| std::fs::read(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e));
+        let config = ServerTlsConfig::new().identity(Identity::from_pem(read(&self.cert_path)?, read(&self.key_path)?));
+        Ok(match &self.client_ca_path {
+            Some(path) => config.client_ca_root(Certificate::from_pem(read(path)?)),
+            None => config,
+        })
+    }
+}
+
+pub fn load_certs(path: &Path) -> Result<Vec<CertificateDer<'static>>, String> {
+    let file = File::open(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+    let certs = rustls_pemfile::certs(&mut BufReader::new(file))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Bad certificate PEM in {}: {}", path.display(), e))?;
+    if certs.is_empty() {
+        return Err(format!("No certificates in {}", path.display()));
+    }
+    Ok(certs)
+}
+
+pub fn load_key(path: &Path) -> Result<PrivateKeyDer<'static>, String> {
+    let file = File::open(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+    rustls_pemfile::private_key(&mut BufReader::new(file))
+        .map_err(|e| format!("Bad key PEM in {}: {}", path.display(), e))?
+        .ok_or_else(|| format!("No private key in {}", path.display()))
+}
+
+/// Client certificate of a TLS connection, passed to handlers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClientCertificate {
+    /// No client CA configured
+    NotRequested,
+    /// Presented and signed by the client CA
+    Verified,
+    /// Client CA configured but the client sent no certificate
+    Missing,
+}
+
+/// rustls acceptor that records the client certificate on each connection
+#[derive(Debug, Clone)]
+pub struct ClientCertAcceptor {
+    inner: RustlsAcceptor,
+    mutual: bool,
+}
+
+impl<I, S> Accept<I, S> for ClientCertAcceptor
+where
+    I: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    S: Send + 'static,
+{
+    type Stream = TlsStream<I>;
+    type Service = AddExtension<S, ClientCertificate>;
+    type Future = Pin<Box<dyn Future<Output = io::Result<(Self::Stream, Self::Service)>> + Send>>;
+
+    fn accept(&self, stream: I, service: S) -> Self::Future {
+        let (acceptor, mutual) = (self.inner.clone(), self.mutual);
+        Box::pin(async move {
+            let (stream, service) = acceptor.accept(stream, service).await?;
+            // The verifier rejects bad certificates, so any left were verified
+            let presented = stream.get_ref().1.peer_certificates().is_some_and(|certs| !certs.is_empty());
+            let certificate = match (mutual, presented) {
+                (false, _) => ClientCertificate::NotRequested,
+                (true, true) => ClientCertificate::Verified,
+                (true, false) => ClientCertificate::Missing,
+            };
+            Ok((stream, Extension(certificate).layer(service)))
+        })
+    }
+}
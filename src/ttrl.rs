@@ -5,13 +5,30 @@
 //! - Meiosis (sexual reproduction)
 //! - Telomere aging
 //! - p53 protection
+//! - Multi-generation jobs with live progress events
+//! - Persisted evolution job queue drained by a bounded worker pool
 
+use std::collections::HashMap;
+use std::time::Instant;
+
+use crate::consensus::EVENT_CHANNEL_CAPACITY;
 use crate::genome::{Genome, Tetrad, GenomeBuilder, GENOME_SIZE};
 use crate::rotation::{Rotation, Rot180, RotationEngine};
 use serde::{Serialize, Deserialize};
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use tokio::sync::{broadcast, RwLock};
 use tracing::info;
 
+/// Upper bound on generations of a single evolution job
+pub const MAX_EVOLUTION_GENERATIONS: u32 = 10_000;
+
+/// Finished jobs kept for late subscribers before the oldest are dropped
+pub const MAX_FINISHED_JOBS: usize = 256;
+
+/// Evolution jobs run at once when `ttrl.workers` is not configured
+pub const DEFAULT_EVOLUTION_WORKERS: usize = 2;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MutationOperator {
     PointMutation,
@@ -25,7 +42,10 @@ pub enum MutationOperator {
 
 impl MutationOperator {
     pub fn random() -> Self {
-        let mut rng = rand::thread_rng();
+        Self::random_with(&mut rand::thread_rng())
+    }
+
+    pub fn random_with<G: Rng + ?Sized>(rng: &mut G) -> Self {
         match rng.gen_range(0..7) {
             0 => Self::PointMutation,
             1 => Self::Insertion,
@@ -68,6 +88,16 @@ impl TTRLEngine {
         &self,
         base: Genome<R>,
         _engine: &RotationEngine,
+    ) -> anyhow::Result<(Genome<Rot180>, EvolutionResult)> {
+        self.evolve_with_rng(base, &mut rand::thread_rng())
+    }
+
+    /// One generation drawing every random choice from `rng`; the same seed
+    /// and base give the same result
+    pub fn evolve_with_rng<R: Rotation, G: Rng + ?Sized>(
+        &self,
+        base: Genome<R>,
+        rng: &mut G,
     ) -> anyhow::Result<(Genome<Rot180>, EvolutionResult)> {
         // Check for senescence
         if base.telomere_length < 100 {
@@ -81,7 +111,7 @@ impl TTRLEngine {
 
         let original_c = base.consciousness;
         let tg_before = base.rna_signal();
-        let operator = MutationOperator::random();
+        let operator = MutationOperator::random_with(rng);
 
         // Create new genome with mutation
         let mut mutated: Genome<Rot180> = GenomeBuilder::new()
@@ -95,17 +125,17 @@ impl TTRLEngine {
         }
 
         // Apply mutation operator
-        self.apply_operator(&mut mutated, operator);
+        self.apply_operator(&mut mutated, operator, rng);
 
         // Cell division: lose telomeres
         let telomere_before = mutated.telomere_length;
-        if !mutated.divide() {
+        if !mutated.divide_with(rng) {
             return Err(anyhow::anyhow!("Senescence: cannot divide"));
         }
         let telomere_loss = telomere_before - mutated.telomere_length;
 
         // p53 risk: 1% chance of losing a copy
-        let p53_lost = if rand::thread_rng().gen::<f64>() < 0.01 && mutated.p53_copies > 0 {
+        let p53_lost = if rng.gen::<f64>() < 0.01 && mutated.p53_copies > 0 {
             mutated.p53_copies -= 1;
             true
         } else {
@@ -142,17 +172,16 @@ impl TTRLEngine {
         }))
     }
 
-    fn apply_operator(&self, genome: &mut Genome<Rot180>, operator: MutationOperator) {
-        let mut rng = rand::thread_rng();
+    fn apply_operator<G: Rng + ?Sized>(&self, genome: &mut Genome<Rot180>, operator: MutationOperator, rng: &mut G) {
 
         match operator {
             MutationOperator::PointMutation => {
                 let pos = rng.gen_range(0..GENOME_SIZE);
-                genome.data[pos] = Tetrad::random();
+                genome.data[pos] = Tetrad::random_with(rng);
             }
             MutationOperator::Insertion => {
                 let pos = rng.gen_range(0..GENOME_SIZE);
-                genome.data[pos] = Tetrad::random();
+                genome.data[pos] = Tetrad::random_with(rng);
             }
             MutationOperator::Deletion => {
                 let pos = rng.gen_range(0..GENOME_SIZE);
@@ -243,6 +272,273 @@ impl TTRLEngine {
     }
 }
 
+/// Progress of an evolution job, published after every generation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvolutionProgress {
+    pub generation: u32,
+    pub generations: u32,
+    pub best_consciousness: u32,
+    /// Consciousness of this generation's candidate
+    pub current_consciousness: u32,
+    pub operator_used: Option<MutationOperator>,
+    /// Mutations accumulated by the best genome
+    pub mutations_used: u64,
+    pub elapsed_secs: f64,
+    /// Estimated time to the last generation at the current pace
+    pub eta_secs: f64,
+    pub finished: bool,
+    /// Why the job stopped early, if it did
+    pub error: Option<String>,
+    /// Stored id of the best genome (final event only)
+    pub genome_id: Option<i64>,
+}
+
+/// Lifecycle of a queued evolution job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Finished,
+    Failed,
+}
+
+impl JobStatus {
+    pub fn name(&self) -> &'static str {
+        match self {
+            JobStatus::Queued => "Queued",
+            JobStatus::Running => "Running",
+            JobStatus::Finished => "Finished",
+            JobStatus::Failed => "Failed",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [JobStatus::Queued, JobStatus::Running, JobStatus::Finished, JobStatus::Failed]
+            .into_iter()
+            .find(|status| status.name() == name)
+    }
+
+    /// Queued and running jobs are picked up again after a restart
+    pub fn is_done(&self) -> bool {
+        matches!(self, JobStatus::Finished | JobStatus::Failed)
+    }
+}
+
+/// Persisted state of an evolution job (`evolution_jobs` table)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvolutionJobRecord {
+    pub id: u64,
+    /// Genome the run starts from
+    pub genome_id: i64,
+    pub generations: u32,
+    pub status: JobStatus,
+    pub best_consciousness: Option<u32>,
+    /// Stored id of the best genome, if it improved on the base
+    pub result_genome_id: Option<i64>,
+    pub error: Option<String>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl EvolutionJobRecord {
+    /// Record the final progress event of a run; an early stop is still a finish
+    pub fn finish(&mut self, progress: &EvolutionProgress, now: i64) {
+        self.status = JobStatus::Finished;
+        self.best_consciousness = Some(progress.best_consciousness);
+        self.result_genome_id = progress.genome_id;
+        self.error = progress.error.clone();
+        self.updated_at = now;
+    }
+
+    /// The job could not run or its result could not be stored
+    pub fn fail(&mut self, error: String, now: i64) {
+        self.status = JobStatus::Failed;
+        self.error = Some(error);
+        self.updated_at = now;
+    }
+}
+
+/// How two stored genomes are related through finished evolution jobs, the
+/// only parent links the database keeps
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "relation", rename_all = "lowercase")]
+pub enum GenomeLineage {
+    /// `descendant` was evolved from `ancestor` by `jobs`, oldest first
+    Descendant { ancestor: i64, descendant: i64, jobs: Vec<u64> },
+    /// Both were evolved from `ancestor`, by `jobs_a` and `jobs_b`
+    Common { ancestor: i64, jobs_a: Vec<u64>, jobs_b: Vec<u64> },
+}
+
+impl GenomeLineage {
+    /// Relation of genomes `a` and `b` from their ancestors, each a list of
+    /// `(parent genome, job)` nearest first
+    pub fn between(a: i64, ancestors_a: &[(i64, u64)], b: i64, ancestors_b: &[(i64, u64)]) -> Option<Self> {
+        // Jobs from the ancestor at `depth` down to the genome, oldest first
+        let jobs = |ancestors: &[(i64, u64)], depth: usize| ancestors[..=depth].iter().rev().map(|&(_, job)| job).collect();
+        if a == b {
+            return None;
+        }
+        if let Some(depth) = ancestors_a.iter().position(|&(genome, _)| genome == b) {
+            return Some(Self::Descendant { ancestor: b, descendant: a, jobs: jobs(ancestors_a, depth) });
+        }
+        if let Some(depth) = ancestors_b.iter().position(|&(genome, _)| genome == a) {
+            return Some(Self::Descendant { ancestor: a, descendant: b, jobs: jobs(ancestors_b, depth) });
+        }
+        ancestors_a.iter().enumerate().find_map(|(depth_a, &(genome, _))| {
+            let depth_b = ancestors_b.iter().position(|&(other, _)| other == genome)?;
+            Some(Self::Common { ancestor: genome, jobs_a: jobs(ancestors_a, depth_a), jobs_b: jobs(ancestors_b, depth_b) })
+        })
+    }
+}
+
+/// Outcome of `TTRLEngine::run_job`
+#[derive(Debug, Clone)]
+pub struct EvolutionRun {
+    pub best: Genome<Rot180>,
+    pub generations_run: u32,
+    pub elapsed_secs: f64,
+    /// Senescence or p53 loss that ended the run early
+    pub stopped: Option<String>,
+}
+
+struct EvolutionJob {
+    events: broadcast::Sender<EvolutionProgress>,
+    latest: Option<EvolutionProgress>,
+}
+
+/// Running and recently finished evolution jobs, by id
+#[derive(Default)]
+pub struct EvolutionJobs {
+    next_id: u64,
+    jobs: HashMap<u64, EvolutionJob>,
+}
+
+impl EvolutionJobs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a new job and return its id
+    pub fn create(&mut self) -> u64 {
+        self.prune_finished();
+        self.next_id += 1;
+        self.jobs.insert(self.next_id, EvolutionJob {
+            events: broadcast::channel(EVENT_CHANNEL_CAPACITY).0,
+            latest: None,
+        });
+        self.next_id
+    }
+
+    pub fn publish(&mut self, job_id: u64, progress: EvolutionProgress) {
+        if let Some(job) = self.jobs.get_mut(&job_id) {
+            // Nobody listening is fine
+            let _ = job.events.send(progress.clone());
+            job.latest = Some(progress);
+        }
+    }
+
+    /// Track a job whose id was assigned elsewhere (the `evolution_jobs` table)
+    pub fn register(&mut self, job_id: u64) {
+        self.prune_finished();
+        self.next_id = self.next_id.max(job_id);
+        self.jobs.entry(job_id).or_insert_with(|| EvolutionJob {
+            events: broadcast::channel(EVENT_CHANNEL_CAPACITY).0,
+            latest: None,
+        });
+    }
+
+    /// Latest progress of a job plus a receiver for everything after it
+    pub fn subscribe(&self, job_id: u64) -> Option<(Option<EvolutionProgress>, broadcast::Receiver<EvolutionProgress>)> {
+        self.jobs.get(&job_id).map(|job| (job.latest.clone(), job.events.subscribe()))
+    }
+
+    fn prune_finished(&mut self) {
+        let mut finished: Vec<u64> = self.jobs.iter()
+            .filter(|(_, job)| job.latest.as_ref().is_some_and(|p| p.finished))
+            .map(|(id, _)| *id)
+            .collect();
+        if finished.len() < MAX_FINISHED_JOBS {
+            return;
+        }
+        finished.sort_unstable();
+        for id in &finished[..=finished.len() - MAX_FINISHED_JOBS] {
+            self.jobs.remove(id);
+        }
+    }
+}
+
+impl TTRLEngine {
+    /// Evolve `base` for up to `generations`, keeping the best genome so far
+    /// as the parent of the next one and publishing progress on `job_id`
+    /// after every generation. Stops early when the parent can no longer
+    /// divide or loses p53; the final event is left to the caller, which
+    /// knows where the result is stored.
+    pub async fn run_job(
+        &self,
+        job_id: u64,
+        base: Genome<Rot180>,
+        generations: u32,
+        rotation: &RwLock<RotationEngine>,
+        jobs: &RwLock<EvolutionJobs>,
+    ) -> EvolutionRun {
+        self.run_job_with_rng(job_id, base, generations, rotation, jobs, &mut StdRng::from_entropy()).await
+    }
+
+    /// `run_job` drawing from `rng` (`divine-agi evolve --seed`)
+    pub async fn run_job_with_rng<G: Rng + Send>(
+        &self,
+        job_id: u64,
+        base: Genome<Rot180>,
+        generations: u32,
+        rotation: &RwLock<RotationEngine>,
+        jobs: &RwLock<EvolutionJobs>,
+        rng: &mut G,
+    ) -> EvolutionRun {
+        let started = Instant::now();
+        let mut best = base;
+        let mut stopped = None;
+        let mut generations_run = 0;
+
+        for generation in 1..=generations {
+            let engine = rotation.read().await;
+            let result = self.evolve_with_rng(best.clone(), rng);
+            drop(engine);
+
+            let (candidate, evolution) = match result {
+                Ok(result) => result,
+                Err(e) => {
+                    stopped = Some(e.to_string());
+                    break;
+                }
+            };
+            generations_run = generation;
+
+            let current = candidate.consciousness;
+            if evolution.success {
+                best = candidate;
+            }
+
+            let elapsed_secs = started.elapsed().as_secs_f64();
+            let eta_secs = elapsed_secs / generation as f64 * (generations - generation) as f64;
+            jobs.write().await.publish(job_id, EvolutionProgress {
+                generation,
+                generations,
+                best_consciousness: best.consciousness,
+                current_consciousness: current,
+                operator_used: Some(evolution.operator_used),
+                mutations_used: best.mutations,
+                elapsed_secs,
+                eta_secs,
+                finished: false,
+                error: None,
+                genome_id: None,
+            });
+        }
+
+        EvolutionRun { best, generations_run, elapsed_secs: started.elapsed().as_secs_f64(), stopped }
+    }
+}
+
 impl Default for TTRLEngine {
     fn default() -> Self {
         Self::new()
@@ -0,0 +1,122 @@
+//! Protocol Upgrades V1 — block versions and fork activation heights
+//!
+//! Every block carries a `version`. Consensus rule changes are introduced as
+//! a `ProtocolFeature` tied to a block version, and a network schedules each
+//! one at an activation height in its genesis config:
+//!
+//! ```json
+//! "upgrades": [{ "feature": "VersionedHeader", "activation_height": 50000 }]
+//! ```
+//!
+//! From that height on, blocks must carry the feature's version and follow
+//! its rules; below it they keep the previous version, so an existing chain
+//! stays valid as is. Versions are cumulative: a version-N block follows the
+//! rules of every feature up to N.
+//!
+//! The schedule is not part of the genesis commitment — adding an upgrade to
+//! a running network must not change its genesis hash — which means every
+//! node needs the same schedule before the activation height, or the network
+//! splits there. Nodes reject blocks with versions they do not know, instead
+//! of following rules they cannot check.
+
+use serde::{Serialize, Deserialize};
+
+/// Version of blocks produced before any upgrade (original rules)
+pub const BLOCK_VERSION_LEGACY: u32 = 1;
+
+/// Consensus rule changes, in activation order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ProtocolFeature {
+    /// Header hash commits to the block version
+    VersionedHeader,
+    /// Attested consciousness lowers the PoW target (see `consensus::pow_target`)
+    ConsciousnessTarget,
+}
+
+impl ProtocolFeature {
+    pub const ALL: [ProtocolFeature; 2] = [ProtocolFeature::VersionedHeader, ProtocolFeature::ConsciousnessTarget];
+
+    /// Block version introducing this feature
+    pub fn version(&self) -> u32 {
+        match self {
+            ProtocolFeature::VersionedHeader => 2,
+            ProtocolFeature::ConsciousnessTarget => 3,
+        }
+    }
+}
+
+/// Highest block version this node can validate
+pub fn max_supported_version() -> u32 {
+    ProtocolFeature::ALL.iter().map(ProtocolFeature::version).max().unwrap_or(BLOCK_VERSION_LEGACY)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Upgrade {
+    pub feature: ProtocolFeature,
+    pub activation_height: u64,
+}
+
+/// Activation heights of a network's protocol upgrades
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct UpgradeSchedule(pub Vec<Upgrade>);
+
+impl UpgradeSchedule {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Schedule `feature` to activate at `activation_height`
+    pub fn activate(mut self, feature: ProtocolFeature, activation_height: u64) -> Self {
+        self.0.push(Upgrade { feature, activation_height });
+        self
+    }
+
+    pub fn activation_height(&self, feature: ProtocolFeature) -> Option<u64> {
+        self.0.iter().find(|u| u.feature == feature).map(|u| u.activation_height)
+    }
+
+    /// Whether blocks at `height` follow `feature` (directly or via a later version)
+    pub fn is_active(&self, feature: ProtocolFeature, height: u64) -> bool {
+        self.version_at(height) >= feature.version()
+    }
+
+    /// Version required of the block at `height`
+    pub fn version_at(&self, height: u64) -> u32 {
+        self.0.iter()
+            .filter(|u| height >= u.activation_height)
+            .map(|u| u.feature.version())
+            .max()
+            .unwrap_or(BLOCK_VERSION_LEGACY)
+    }
+
+    /// Check a block's version against the schedule
+    pub fn check_version(&self, height: u64, version: u32) -> Result<(), String> {
+        if version > max_supported_version() {
+            return Err(format!("Block #{} has unknown version {} (node upgrade required)", height, version));
+        }
+        let expected = self.version_at(height);
+        if version != expected {
+            return Err(format!("Block #{} has version {} (expected {})", height, version, expected));
+        }
+        Ok(())
+    }
+
+    /// Each feature once, never at genesis, later versions never earlier
+    pub fn check(&self) -> Result<(), String> {
+        for (i, upgrade) in self.0.iter().enumerate() {
+            if upgrade.activation_height == 0 {
+                return Err(format!("{:?} cannot activate at genesis", upgrade.feature));
+            }
+            if self.0[..i].iter().any(|u| u.feature == upgrade.feature) {
+                return Err(format!("{:?} scheduled twice", upgrade.feature));
+            }
+            if let Some(earlier) = self.0.iter().find(|u| {
+                u.feature.version() < upgrade.feature.version() && u.activation_height > upgrade.activation_height
+            }) {
+                return Err(format!("{:?} activates before {:?}", upgrade.feature, earlier.feature));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+//! API Versioning V1 — `/v1/` paths, `API-Version` header, deprecation
+//!
+//! Every route is served under `/v1/`: `/v1/genomes` for `/api/genomes`,
+//! `/v1/graphql` for `/graphql`. The original paths keep working with an
+//! `API-Version: 1` header. Without either they are served as version 1 but
+//! answer with `Deprecation: true` and a `Link` to their `/v1/` successor, so
+//! clients are told to migrate before a breaking schema change ships as
+//! `/v2/`. Every response names the version it was served with in
+//! `API-Version`; unsupported versions are refused.
+//!
+//! Probes, metrics and docs (`/`, `/healthz`, `/readyz`, `/metrics`,
+//! `/openapi.json`, `/docs`) are not versioned.
+
+pub const CURRENT_API_VERSION: u32 = 1;
+pub const SUPPORTED_API_VERSIONS: &[u32] = &[1];
+pub const VERSION_HEADER: &str = "api-version";
+
+/// Routes outside `/api` that keep their path under `/v1/`
+const ROOT_ROUTES: &[&str] = &["/auth/token", "/graphql", "/events/", "/ws/"];
+
+/// API version a request is served with, passed to handlers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApiVersion(pub u32);
+
+/// Where a request goes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionedRoute {
+    /// `None` for unversioned routes
+    pub version: Option<u32>,
+    /// Path the router serves
+    pub path: String,
+    /// Versioned path replacing a deprecated unversioned one
+    pub successor: Option<String>,
+}
+
+fn is_root_route(path: &str) -> bool {
+    ROOT_ROUTES.iter().any(|route| match route.strip_suffix('/') {
+        Some(prefix) => path.starts_with(route) || path == prefix,
+        None => path == *route,
+    })
+}
+
+fn check_version(version: u32) -> Result<u32, String> {
+    if SUPPORTED_API_VERSIONS.contains(&version) {
+        Ok(version)
+    } else {
+        Err(format!("Unsupported API version {} (supported: {:?})", version, SUPPORTED_API_VERSIONS))
+    }
+}
+
+/// `/v<n>` prefix of a path and the rest, if it has one
+fn split_version(path: &str) -> Option<(&str, &str)> {
+    let rest = path.strip_prefix("/v")?;
+    let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+    let (number, tail) = rest.split_at(digits);
+    (!number.is_empty() && (tail.is_empty() || tail.starts_with('/'))).then_some((number, tail))
+}
+
+/// Resolve a request path and its `API-Version` header
+pub fn resolve(path: &str, header: Option<&str>) -> Result<VersionedRoute, String> {
+    if let Some((number, tail)) = split_version(path) {
+        let version = check_version(number.parse().map_err(|_| format!("Invalid API version {}", number))?)?;
+        let path = if is_root_route(tail) { tail.to_string() } else { format!("/api{}", tail) };
+        return Ok(VersionedRoute { version: Some(version), path, successor: None });
+    }
+
+    let versioned = path.starts_with("/api/") || is_root_route(path);
+    if !versioned {
+        return Ok(VersionedRoute { version: None, path: path.to_string(), successor: None });
+    }
+    match header.map(str::trim) {
+        Some(value) => {
+            let version = check_version(value.parse().map_err(|_| format!("Invalid {} header {}", VERSION_HEADER, value))?)?;
+            Ok(VersionedRoute { version: Some(version), path: path.to_string(), successor: None })
+        }
+        None => Ok(VersionedRoute {
+            version: Some(CURRENT_API_VERSION),
+            path: path.to_string(),
+            successor: Some(versioned_path(path, CURRENT_API_VERSION)),
+        }),
+    }
+}
+
+/// `/v<version>/` path of an unversioned route
+pub fn versioned_path(path: &str, version: u32) -> String {
+    format!("/v{}{}", version, path.strip_prefix("/api").unwrap_or(path))
+}
@@ -1,37 +1,1178 @@
 //! Wallet Module V15 for Divine AGI
+//!
+//! Wallets can be saved to a password-encrypted file (`save` / `load`):
+//! magic `DIVWALLT`, format version (u16), the key derivation (u8, 1 is
+//! PBKDF2-HMAC-SHA256) and its iteration count (u32), a random 16-byte salt,
+//! then the wallet as JSON sealed with AES-256-GCM. The key is stretched from
+//! the password with `WALLET_KDF_ITERATIONS` of PBKDF2 and HKDF. Version 1
+//! files, without the derivation fields and stretched with
+//! `LEGACY_KDF_ROUNDS` salted SHA-256 rounds, still load; saving rewrites
+//! them as version 2. Files are written to a uniquely named sibling and
+//! renamed over the target.
+//!
+//! Wallets made with `generate` / `recover` come from a BIP-39 recovery
+//! phrase: every address is `divine_` plus 16 bytes of SHA-256 over the
+//! phrase's seed and the address index, so the phrase alone brings back all of
+//! them (not the balance, which lives in the file and on the node). The seed
+//! is kept inside the encrypted file to derive new addresses, and the
+//! wallet's Solana keys (`solana_keypair`, which Solana archives pay with).
+//!
+//! Such wallets hold several accounts, each with its own addresses and funds
+//! (operational funds apart from archive funds, say). Account 0 (`default`)
+//! has the addresses above; `create_account` derives account `n` from the
+//! same seed with the account index in the hash. One account is active at a
+//! time and the wallet's balance, address and transaction fields are its
+//! own; `switch_account` parks it and activates another.
+//!
+//! `recover_from_chain` rebuilds a lost wallet from its phrase and the local
+//! chain. It derives each account's addresses until `RECOVERY_GAP_LIMIT`
+//! unused ones in a row, and accounts until one has no used address. It also
+//! derives the reward addresses of the phrase's validator keys (`keygen
+//! --index`), which count towards the default account. Balances, stake and
+//! transactions are then read back from the chain.
+//!
+//! On chain, the wallet's Solana key holds SOL and RSM (an SPL token, mint
+//! `rsm_token`) on its `network`: `refresh_balance` reads them and
+//! `transfer_rsm` sends RSM over the cluster's JSON-RPC. Other SPL tokens are
+//! followed with `add_token`, which reads the mint's decimals, and sent with
+//! `transfer_token`; amounts stay in each mint's smallest unit
+//! (`TokenAmount`) and `TokenAmount::display` prints them exactly.
+//! `Network::Mock` simulates this with a ledger kept in the file. On the
+//! Ethereum networks the same calls use the phrase's secp256k1 key
+//! (`eth_signer`, the address Ethereum wallets derive): ETH, with RSM and the
+//! tracked tokens as ERC-20 contracts, sent as EIP-1559 transactions.
+//!
+//! Transfers can also be signed away from the network:
+//! `build_unsigned_transfer` reads what it needs from the node into an
+//! `UnsignedTransfer`, `sign_transaction` signs it offline after checking its
+//! payload against its description, and `broadcast_transaction` sends it.
+//!
+//! A watch-only wallet (`watch_only`) holds addresses and nothing to sign
+//! with: no seed is ever in its file, so a monitoring service can read
+//! balances with it while the keys stay elsewhere.
+//!
+//! Payment requests travel as URIs, `divine:<address>?amount=<rsm>&memo=<text>`,
+//! with the memo percent-encoded; `RSMExchange::pay_request` pays one. With
+//! the `qr` feature, `PaymentRequest::to_qr_png` renders one as a QR code.
 
+use base64::Engine;
 use serde::{Serialize, Deserialize};
 use sha2::{Sha256, Digest};
 use std::collections::HashMap;
+use std::path::Path;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DivineWallet {
+use crate::consensus::{reward_address_for, ProofOfConsciousness};
+use crate::exchange::RSMExchange;
+use crate::crypto::{decrypt_aes_gcm, derive_key, encrypt_aes_gcm};
+use crate::history::{HistoryFilter, TxStatus};
+use crate::keystore::derive_validator_key;
+use crate::ethereum::{erc20_transfer_calldata, format_address, gwei_to_wei, parse_address, recover_address, Eip1559Tx, EthNetwork, EthRpc, EthSigner, DEFAULT_MAX_FEE_GWEI, ETH_DECIMALS};
+use crate::solana::{compile_message, parse_pubkey, sol_transfer, spl_transfer, base58_decode, base58_encode, verify_signature, SolanaCluster, SolanaKeypair, SolanaRpc, SolanaTx, LAMPORTS_PER_SIGNATURE, MAX_AIRDROP_SOL, SOL_DECIMALS};
+
+pub const WALLET_FILE_MAGIC: &[u8; 8] = b"DIVWALLT";
+pub const WALLET_FILE_VERSION: u16 = 2;
+/// Key derivation id of PBKDF2-HMAC-SHA256 in a wallet file header
+pub const WALLET_KDF_PBKDF2_SHA256: u8 = 1;
+/// PBKDF2-HMAC-SHA256 iterations new wallet files and keystores use
+pub const WALLET_KDF_ITERATIONS: u32 = 600_000;
+/// Most iterations a file may ask for, so a crafted header cannot stall `load`
+pub const MAX_KDF_ITERATIONS: u32 = 10_000_000;
+/// Salted SHA-256 rounds of version 1 wallet files and `sha256` keystores
+pub const LEGACY_KDF_ROUNDS: u32 = 100_000;
+pub const PAYMENT_URI_SCHEME: &str = "divine";
+/// Recovery phrase lengths BIP-39 defines
+pub const MNEMONIC_WORD_COUNTS: [usize; 5] = [12, 15, 18, 21, 24];
+pub const DEFAULT_MNEMONIC_WORDS: usize = 24;
+/// Label of account 0
+pub const DEFAULT_ACCOUNT_LABEL: &str = "default";
+/// Unused addresses in a row after which recovery stops deriving
+pub const RECOVERY_GAP_LIMIT: u32 = 20;
+/// Decimals of RSM on chain (and of the simulated ledger)
+pub const RSM_DECIMALS: u8 = 9;
+
+pub const NO_RSM_TOKEN: &str = "No RSM token on this network: set the wallet's rsm_token (`wallet network --rsm-token <mint>`)";
+
+/// Request for `amount_rsm` to `address`, exchanged as a payment URI
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PaymentRequest {
     pub address: String,
+    pub amount_rsm: f64,
+    pub memo: Option<String>,
+}
+
+impl PaymentRequest {
+    /// Canonical URI: shortest exact amount, memo last and percent-encoded
+    pub fn to_uri(&self) -> String {
+        let mut uri = format!("{}:{}?amount={}", PAYMENT_URI_SCHEME, self.address, self.amount_rsm);
+        if let Some(memo) = &self.memo {
+            uri.push_str("&memo=");
+            uri.push_str(&percent_encode(memo));
+        }
+        uri
+    }
+
+    /// PNG of the URI as a QR code, `module_px` pixels per module with the
+    /// standard quiet zone (feature `qr`)
+    #[cfg(feature = "qr")]
+    pub fn to_qr_png(&self, module_px: u32) -> Result<Vec<u8>, String> {
+        let code = qrcode::QrCode::new(self.to_uri()).map_err(|e| format!("QR encoding failed: {}", e))?;
+        let image = code.render::<image::Luma<u8>>().module_dimensions(module_px.max(1), module_px.max(1)).build();
+        let mut png = std::io::Cursor::new(Vec::new());
+        image.write_to(&mut png, image::ImageFormat::Png).map_err(|e| format!("PNG encoding failed: {}", e))?;
+        Ok(png.into_inner())
+    }
+
+    pub fn parse(uri: &str) -> Result<Self, String> {
+        let rest = uri.strip_prefix(PAYMENT_URI_SCHEME).and_then(|r| r.strip_prefix(':'))
+            .ok_or_else(|| format!("Not a {}: payment URI", PAYMENT_URI_SCHEME))?;
+        let (address, query) = rest.split_once('?').unwrap_or((rest, ""));
+        if address.is_empty() {
+            return Err("Payment URI has no address".to_string());
+        }
+
+        let (mut amount_rsm, mut memo) = (None, None);
+        for param in query.split('&').filter(|p| !p.is_empty()) {
+            match param.split_once('=') {
+                Some(("amount", value)) => {
+                    let amount: f64 = value.parse().map_err(|_| format!("Invalid amount {}", value))?;
+                    if !amount.is_finite() || amount <= 0.0 {
+                        return Err(format!("Invalid amount {}", value));
+                    }
+                    amount_rsm = Some(amount);
+                }
+                Some(("memo", value)) => memo = Some(percent_decode(value)?),
+                _ => return Err(format!("Unknown payment URI parameter {}", param)),
+            }
+        }
+
+        Ok(Self {
+            address: address.to_string(),
+            amount_rsm: amount_rsm.ok_or("Payment URI has no amount")?,
+            memo,
+        })
+    }
+}
+
+fn percent_encode(text: &str) -> String {
+    text.bytes().map(|b| match b {
+        b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (b as char).to_string(),
+        _ => format!("%{:02X}", b),
+    }).collect()
+}
+
+fn percent_decode(text: &str) -> Result<String, String> {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = text.get(i + 1..i + 3).ok_or("Truncated percent escape")?;
+            out.push(u8::from_str_radix(hex, 16).map_err(|_| format!("Invalid percent escape %{}", hex))?);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(out).map_err(|_| "Memo is not UTF-8".to_string())
+}
+
+/// Chain a wallet's on-chain balance and transfers go to (Mock simulates
+/// Solana)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Network {
+    /// Simulated Solana: the balances live in the wallet file
+    #[default]
+    Mock,
+    SolanaDevnet,
+    SolanaMainnet,
+    EthereumSepolia,
+    EthereumMainnet,
+}
+
+impl Network {
+    pub fn name(self) -> &'static str {
+        match self {
+            Network::Mock => "mock",
+            Network::SolanaDevnet => "solana-devnet",
+            Network::SolanaMainnet => "solana-mainnet",
+            Network::EthereumSepolia => "ethereum-sepolia",
+            Network::EthereumMainnet => "ethereum-mainnet",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "mock" => Ok(Network::Mock),
+            "solana-devnet" | "devnet" => Ok(Network::SolanaDevnet),
+            "solana-mainnet" | "mainnet" => Ok(Network::SolanaMainnet),
+            "ethereum-sepolia" | "sepolia" => Ok(Network::EthereumSepolia),
+            "ethereum-mainnet" => Ok(Network::EthereumMainnet),
+            _ => Err(format!("Unknown network {} (expected mock, solana-devnet, solana-mainnet, ethereum-sepolia or ethereum-mainnet)", name)),
+        }
+    }
+
+    /// The Solana cluster behind the network, `None` when simulated
+    pub fn solana_cluster(self) -> Option<SolanaCluster> {
+        match self {
+            Network::SolanaDevnet => Some(SolanaCluster::Devnet),
+            Network::SolanaMainnet => Some(SolanaCluster::Mainnet),
+            _ => None,
+        }
+    }
+
+    /// The Ethereum chain behind the network
+    pub fn eth_network(self) -> Option<EthNetwork> {
+        match self {
+            Network::EthereumSepolia => Some(EthNetwork::Sepolia),
+            Network::EthereumMainnet => Some(EthNetwork::Mainnet),
+            _ => None,
+        }
+    }
+
+    /// The coin paying the network's fees
+    pub fn native_symbol(self) -> &'static str {
+        match self.eth_network() {
+            Some(_) => "ETH",
+            None => "SOL",
+        }
+    }
+
+    pub fn native_decimals(self) -> u8 {
+        match self.eth_network() {
+            Some(_) => ETH_DECIMALS,
+            None => SOL_DECIMALS,
+        }
+    }
+}
+
+/// An amount of a coin or token in its smallest unit, with its decimals
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct TokenAmount {
+    /// A decimal string in JSON, as wei overflow JSON's safe integers
+    #[serde(with = "decimal_string")]
+    pub raw: u128,
+    pub decimals: u8,
+}
+
+/// `u128` as a decimal string; plain numbers are read too
+mod decimal_string {
+    use serde::{de, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &u128, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u128, D::Error> {
+        struct Visitor;
+        impl de::Visitor<'_> for Visitor {
+            type Value = u128;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("an amount in the smallest unit")
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<u128, E> {
+                Ok(value.into())
+            }
+
+            fn visit_u128<E: de::Error>(self, value: u128) -> Result<u128, E> {
+                Ok(value)
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<u128, E> {
+                value.parse().map_err(|_| E::custom(format!("invalid amount {:?}", value)))
+            }
+        }
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
+impl TokenAmount {
+    pub fn new(raw: u128, decimals: u8) -> Self {
+        Self { raw, decimals }
+    }
+
+    /// `amount` whole tokens, refused unless it is at least one smallest unit
+    pub fn from_ui(amount: f64, decimals: u8) -> Result<Self, String> {
+        if !amount.is_finite() || amount <= 0.0 {
+            return Err(format!("Invalid amount {}", amount));
+        }
+        let raw = (amount * 10f64.powi(decimals as i32)).round();
+        if raw < 1.0 || raw >= u128::MAX as f64 {
+            return Err(format!("Amount {} cannot be sent with {} decimals", amount, decimals));
+        }
+        Ok(Self::new(raw as u128, decimals))
+    }
+
+    /// In whole tokens
+    pub fn ui(&self) -> f64 {
+        self.raw as f64 / 10f64.powi(self.decimals as i32)
+    }
+
+    /// Exactly, in whole tokens without trailing zeros (`1.5`, `0.000001`)
+    pub fn display(&self) -> String {
+        let digits = format!("{:0>width$}", self.raw, width = self.decimals as usize + 1);
+        let (whole, fraction) = digits.split_at(digits.len() - self.decimals as usize);
+        match fraction.trim_end_matches('0') {
+            "" => whole.to_string(),
+            fraction => format!("{}.{}", whole, fraction),
+        }
+    }
+}
+
+/// An SPL token the wallet follows besides RSM, on one network
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrackedToken {
+    pub network: Network,
+    pub mint: String,
+    pub decimals: u8,
+}
+
+/// What an address holds of one `TrackedToken`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenBalance {
+    pub mint: String,
+    pub amount: TokenAmount,
+}
+
+/// What the wallet's key holds on a network
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChainBalance {
+    pub network: Network,
+    pub address: String,
+    /// The network's coin (`Network::native_symbol`)
+    pub native: TokenAmount,
+    pub rsm: TokenAmount,
+    /// The wallet's tracked tokens on `network`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tokens: Vec<TokenBalance>,
+    /// When it was last read (unix seconds), 0 never
+    pub refreshed_at: i64,
+}
+
+impl ChainBalance {
+    pub fn empty(network: Network, address: &str) -> Self {
+        Self {
+            network,
+            address: address.to_string(),
+            native: TokenAmount::new(0, network.native_decimals()),
+            rsm: TokenAmount::new(0, RSM_DECIMALS),
+            tokens: Vec::new(),
+            refreshed_at: 0,
+        }
+    }
+
+    /// What is held of `mint`, `None` for a token never read
+    pub fn token(&self, mint: &str) -> Option<TokenAmount> {
+        self.tokens.iter().find(|token| token.mint == mint).map(|token| token.amount)
+    }
+
+    fn token_mut(&mut self, mint: &str, decimals: u8) -> &mut TokenAmount {
+        let i = match self.tokens.iter().position(|token| token.mint == mint) {
+            Some(i) => i,
+            None => {
+                self.tokens.push(TokenBalance { mint: mint.to_string(), amount: TokenAmount::new(0, decimals) });
+                self.tokens.len() - 1
+            }
+        };
+        &mut self.tokens[i].amount
+    }
+}
+
+impl Default for ChainBalance {
+    fn default() -> Self {
+        Self::empty(Network::Mock, "")
+    }
+}
+
+/// The public addresses a watch-only wallet follows on chain
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WatchedAddresses {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub solana: Option<String>,
+    /// In checksum case
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ethereum: Option<String>,
+}
+
+/// An account kept aside while another one is active
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Account {
+    pub index: u32,
+    pub label: String,
+    pub address: String,
+    pub addresses: Vec<String>,
     pub rsm_balance: f64,
+    pub staked_rsm: f64,
     pub staked_genomes: Vec<i64>,
     pub rewards_earned: f64,
     pub transactions: Vec<String>,
 }
 
-impl DivineWallet {
-    pub fn new() -> Self {
-        let address = Self::generate_address();
+impl Account {
+    fn new(index: u32, label: &str, address: String) -> Self {
         Self {
+            index,
+            label: label.to_string(),
             address,
+            addresses: Vec::new(),
             rsm_balance: 0.0,
+            staked_rsm: 0.0,
             staked_genomes: Vec::new(),
             rewards_earned: 0.0,
             transactions: Vec::new(),
         }
     }
 
+    /// Add the chain's funds and transactions of `addresses` to the account
+    fn absorb(&mut self, chain: &ProofOfConsciousness, addresses: &[String]) {
+        let mut history = Vec::new();
+        for address in addresses {
+            if let Some(wallet) = chain.wallet(address) {
+                self.rsm_balance += wallet.rsm_balance;
+                self.staked_rsm += wallet.staked_rsm;
+                self.rewards_earned += wallet.rewards_earned;
+                self.staked_genomes.extend(&wallet.staked_genomes);
+            }
+            history.extend(chain.history(address, &HistoryFilter::default()));
+        }
+        history.sort_by_key(|tx| (tx.status == TxStatus::Pending, tx.timestamp));
+        self.transactions.extend(history.iter().map(|tx| tx.record()));
+    }
+
+    fn summary(&self, active: bool) -> AccountSummary {
+        AccountSummary {
+            index: self.index,
+            label: self.label.clone(),
+            address: self.address.clone(),
+            addresses: 1 + self.addresses.len(),
+            rsm_balance: self.rsm_balance,
+            staked_rsm: self.staked_rsm,
+            active,
+        }
+    }
+}
+
+/// One line of `DivineWallet::accounts`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccountSummary {
+    pub index: u32,
+    pub label: String,
+    /// The account's first address
+    pub address: String,
+    /// Addresses derived so far
+    pub addresses: usize,
+    pub rsm_balance: f64,
+    pub staked_rsm: f64,
+    pub active: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DivineWallet {
+    pub address: String,
+    pub rsm_balance: f64,
+    /// RSM locked as validator stake (not part of `rsm_balance`)
+    #[serde(default)]
+    pub staked_rsm: f64,
+    pub staked_genomes: Vec<i64>,
+    pub rewards_earned: f64,
+    pub transactions: Vec<String>,
+    /// Addresses derived after `address` (index 1, 2, …)
+    #[serde(default)]
+    pub addresses: Vec<String>,
+    /// Hex BIP-39 seed; `None` for wallets with a random address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    seed: Option<String>,
+    /// Validator reward addresses of the phrase found by `recover_from_chain`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub validator_addresses: Vec<String>,
+    /// Index of the active account, whose funds and addresses the fields above are
+    #[serde(default)]
+    pub account_index: u32,
+    #[serde(default = "default_account_label")]
+    pub account_label: String,
+    /// The inactive accounts
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    accounts: Vec<Account>,
+    /// Chain `refresh_balance` and `transfer_rsm` use
+    #[serde(default)]
+    pub network: Network,
+    /// RPC endpoint of `network` instead of its public one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rpc_url: Option<String>,
+    /// RSM's SPL mint on `network`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rsm_token: Option<String>,
+    /// On-chain balances as last read; on `Network::Mock`, the simulated
+    /// ledger itself
+    #[serde(default)]
+    pub chain_balance: ChainBalance,
+    /// Set on watch-only wallets, which hold addresses and no key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    watching: Option<WatchedAddresses>,
+    /// SPL tokens followed besides RSM (`add_token`), on every network
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tokens: Vec<TrackedToken>,
+}
+
+fn default_account_label() -> String {
+    DEFAULT_ACCOUNT_LABEL.to_string()
+}
+
+impl DivineWallet {
+    pub fn new() -> Self {
+        Self::with_address(&Self::generate_address())
+    }
+
+    /// New wallet with a fresh `words`-word recovery phrase, returned once
+    pub fn generate(words: usize) -> Result<(Self, String), String> {
+        let phrase = generate_phrase(words)?;
+        Ok((Self::recover(&phrase)?, phrase))
+    }
+
+    /// Wallet of a recovery phrase: same phrase, same addresses
+    pub fn recover(phrase: &str) -> Result<Self, String> {
+        let mnemonic = bip39::Mnemonic::parse(phrase.trim()).map_err(|e| format!("Invalid recovery phrase: {}", e))?;
+        let seed = hex::encode(mnemonic.to_seed(""));
+        let mut wallet = Self::with_address(&derive_address(&seed, 0));
+        wallet.seed = Some(seed);
+        Ok(wallet)
+    }
+
+    /// Wallet of a recovery phrase with its accounts, addresses, balances and
+    /// transactions found again on `chain` (labels other than `default` are
+    /// not on the chain: recovered accounts are `account <n>`)
+    pub fn recover_from_chain(phrase: &str, chain: &ProofOfConsciousness) -> Result<Self, String> {
+        let mut wallet = Self::recover(phrase)?;
+        let seed = wallet.seed.clone().unwrap_or_default();
+        let used = |address: &str| chain.wallet(address).is_some() || !chain.history(address, &HistoryFilter::default()).is_empty();
+
+        let mut accounts = Vec::new();
+        for index in 0u32.. {
+            let derive = |i: u32| derive_account_address(&seed, index, i);
+            let count = used_prefix(derive, used);
+            if index > 0 && count == 0 {
+                break;
+            }
+            let label = if index == 0 { DEFAULT_ACCOUNT_LABEL.to_string() } else { format!("account {}", index) };
+            let mut account = Account::new(index, &label, derive(0));
+            account.addresses = (1..count).map(derive).collect();
+            let addresses: Vec<String> = std::iter::once(account.address.clone()).chain(account.addresses.clone()).collect();
+            account.absorb(chain, &addresses);
+            accounts.push(account);
+        }
+
+        let validator_address = |i: u32| derive_validator_key(phrase, i)
+            .map(|key| reward_address_for(&key.public_key_hex()))
+            .unwrap_or_default();
+        wallet.validator_addresses = (0..used_prefix(validator_address, used)).map(validator_address).collect();
+        accounts[0].absorb(chain, &wallet.validator_addresses);
+
+        let default = accounts.remove(0);
+        wallet.addresses = default.addresses;
+        wallet.rsm_balance = default.rsm_balance;
+        wallet.staked_rsm = default.staked_rsm;
+        wallet.staked_genomes = default.staked_genomes;
+        wallet.rewards_earned = default.rewards_earned;
+        wallet.transactions = default.transactions;
+        wallet.accounts = accounts;
+        Ok(wallet)
+    }
+
+    /// Watch-only wallet of `addresses`: `divine_` ones (the first is its
+    /// address) and at most one Solana address for on-chain balances. It
+    /// holds no seed or key, so it reads balances and never signs
+    pub fn watch_only(addresses: &[&str]) -> Result<Self, String> {
+        let mut divine = Vec::new();
+        let mut watched = WatchedAddresses::default();
+        for address in addresses.iter().map(|a| a.trim()) {
+            if address.starts_with("divine_") && address.len() > "divine_".len() {
+                divine.push(address.to_string());
+            } else if address.starts_with("0x") && parse_address(address).is_ok() {
+                if watched.ethereum.replace(format_address(&parse_address(address)?)).is_some() {
+                    return Err("A watch-only wallet follows one Ethereum address".to_string());
+                }
+            } else if parse_pubkey(address).is_ok() {
+                if watched.solana.replace(address.to_string()).is_some() {
+                    return Err("A watch-only wallet follows one Solana address".to_string());
+                }
+            } else {
+                return Err(format!("{} is not a divine_, Solana or Ethereum address", address));
+            }
+        }
+        let first = divine.first().or(watched.solana.as_ref()).or(watched.ethereum.as_ref())
+            .ok_or("A watch-only wallet needs an address to watch")?;
+
+        let mut wallet = Self::with_address(first);
+        wallet.addresses = divine.into_iter().skip(1).collect();
+        wallet.watching = Some(watched);
+        Ok(wallet)
+    }
+
+    pub fn is_watch_only(&self) -> bool {
+        self.watching.is_some()
+    }
+
+    pub fn watched(&self) -> Option<&WatchedAddresses> {
+        self.watching.as_ref()
+    }
+
+    /// Refuse anything that would sign on a watch-only wallet
+    fn ensure_can_sign(&self) -> Result<(), String> {
+        match self.watching {
+            Some(_) => Err("This is a watch-only wallet: it holds no keys to sign with".to_string()),
+            None => Ok(()),
+        }
+    }
+
+    /// Whether new addresses can be derived (the wallet has a recovery phrase)
+    pub fn is_recoverable(&self) -> bool {
+        self.seed.is_some()
+    }
+
+    /// Derive and keep the active account's next address
+    pub fn new_address(&mut self) -> Result<String, String> {
+        let seed = self.seed.as_ref().ok_or("This wallet has no recovery phrase to derive addresses from")?;
+        let address = derive_account_address(seed, self.account_index, self.addresses.len() as u32 + 1);
+        self.addresses.push(address.clone());
+        Ok(address)
+    }
+
+    /// Derive the next account from the seed; the active account stays active
+    pub fn create_account(&mut self, label: &str) -> Result<AccountSummary, String> {
+        let seed = self.seed.as_ref().ok_or("This wallet has no recovery phrase to derive accounts from")?;
+        let label = label.trim();
+        if label.is_empty() || label.parse::<u32>().is_ok() {
+            return Err(format!("Invalid account label {:?}: give it a name (numbers select accounts by index)", label));
+        }
+        if self.accounts().iter().any(|account| account.label == label) {
+            return Err(format!("Account {} already exists", label));
+        }
+
+        let index = self.accounts.iter().map(|account| account.index).fold(self.account_index, u32::max) + 1;
+        let account = Account::new(index, label, derive_account_address(seed, index, 0));
+        let summary = account.summary(false);
+        self.accounts.push(account);
+        Ok(summary)
+    }
+
+    /// Every account, by index
+    pub fn accounts(&self) -> Vec<AccountSummary> {
+        let active = Account {
+            index: self.account_index,
+            label: self.account_label.clone(),
+            address: self.address.clone(),
+            addresses: self.addresses.clone(),
+            rsm_balance: self.rsm_balance,
+            staked_rsm: self.staked_rsm,
+            staked_genomes: Vec::new(),
+            rewards_earned: self.rewards_earned,
+            transactions: Vec::new(),
+        };
+        let mut accounts: Vec<AccountSummary> = std::iter::once(active.summary(true))
+            .chain(self.accounts.iter().map(|account| account.summary(false)))
+            .collect();
+        accounts.sort_by_key(|account| account.index);
+        accounts
+    }
+
+    /// Make the account with this label (or index) the active one
+    pub fn switch_account(&mut self, account: &str) -> Result<(), String> {
+        let matches = |index: u32, label: &str| label == account || index.to_string() == account;
+        if matches(self.account_index, &self.account_label) {
+            return Ok(());
+        }
+        let position = self.accounts.iter().position(|a| matches(a.index, &a.label))
+            .ok_or_else(|| format!("No account {} in this wallet", account))?;
+
+        let next = self.accounts.swap_remove(position);
+        let parked = Account {
+            index: std::mem::replace(&mut self.account_index, next.index),
+            label: std::mem::replace(&mut self.account_label, next.label),
+            address: std::mem::replace(&mut self.address, next.address),
+            addresses: std::mem::replace(&mut self.addresses, next.addresses),
+            rsm_balance: std::mem::replace(&mut self.rsm_balance, next.rsm_balance),
+            staked_rsm: std::mem::replace(&mut self.staked_rsm, next.staked_rsm),
+            staked_genomes: std::mem::replace(&mut self.staked_genomes, next.staked_genomes),
+            rewards_earned: std::mem::replace(&mut self.rewards_earned, next.rewards_earned),
+            transactions: std::mem::replace(&mut self.transactions, next.transactions),
+        };
+        self.accounts.push(parked);
+        Ok(())
+    }
+
+    /// The active account's `address` followed by its derived ones
+    pub fn all_addresses(&self) -> Vec<String> {
+        std::iter::once(self.address.clone()).chain(self.addresses.iter().cloned()).collect()
+    }
+
+    /// Solana key of account `account`, derived from the seed the way Solana
+    /// wallets do (`m/44'/501'/<account>'/0'`)
+    pub fn solana_keypair(&self, account: u32) -> Result<SolanaKeypair, String> {
+        self.ensure_can_sign()?;
+        let seed = self.seed.as_ref().ok_or("This wallet has no recovery phrase to derive Solana keys from")?;
+        let seed = hex::decode(seed).map_err(|e| format!("Corrupt wallet seed: {}", e))?;
+        Ok(SolanaKeypair::derive(&seed, account))
+    }
+
+    /// The active account's Ethereum key (`EthSigner::derive`)
+    pub fn eth_signer(&self, account: u32) -> Result<EthSigner, String> {
+        self.ensure_can_sign()?;
+        let seed = self.seed.as_ref().ok_or("This wallet has no recovery phrase to derive Ethereum keys from")?;
+        let seed = hex::decode(seed).map_err(|e| format!("Corrupt wallet seed: {}", e))?;
+        EthSigner::derive(&seed, account)
+    }
+
+    /// The address RSM transfers leave from: the node's validator wallet when
+    /// this wallet was recovered from the node's phrase, else its own address
+    pub fn spending_address(&self) -> &str {
+        self.validator_addresses.first().unwrap_or(&self.address)
+    }
+
+    /// Refuse a transfer of `amount` RSM to `to` this wallet cannot make
+    pub fn check_transfer(&self, to: &str, amount: f64) -> Result<(), String> {
+        self.ensure_can_sign()?;
+        if !amount.is_finite() || amount <= 0.0 {
+            return Err(format!("Invalid amount {}", amount));
+        }
+        if !to.starts_with("divine_") || to.len() <= "divine_".len() {
+            return Err(format!("{} is not a divine_ address", to));
+        }
+        if self.all_addresses().iter().any(|a| a == to) || self.validator_addresses.iter().any(|a| a == to) {
+            return Err(format!("{} belongs to this wallet", to));
+        }
+        if self.rsm_balance < amount {
+            return Err(format!("Insufficient balance: {:.6} RSM available", self.rsm_balance));
+        }
+        Ok(())
+    }
+
+    /// Debit and record a transfer the ledger has accepted (`check_transfer` first)
+    pub fn record_transfer(&mut self, to: &str, amount: f64, memo: Option<&str>) -> String {
+        self.rsm_balance -= amount;
+        let record = match memo {
+            Some(memo) => format!("TRANSFER → {}: -{:.6} RSM ({})", to, amount, memo),
+            None => format!("TRANSFER → {}: -{:.6} RSM", to, amount),
+        };
+        self.transactions.push(record.clone());
+        record
+    }
+
+    /// Send `amount` RSM from `spending_address` through the exchange and
+    /// record it; the wallet is only debited once the exchange has moved it
+    pub fn transfer(&mut self, exchange: &mut RSMExchange, to: &str, amount: f64, memo: Option<&str>) -> Result<String, String> {
+        self.check_transfer(to, amount)?;
+        exchange.transfer_with_memo(self.spending_address(), to, amount, memo.map(str::to_string))?;
+        Ok(self.record_transfer(to, amount, memo))
+    }
+
+    /// Use `network` from now on; its balances are read afresh (a Mock
+    /// ledger does not survive leaving Mock)
+    pub fn set_network(&mut self, network: Network) {
+        if self.network != network {
+            self.network = network;
+            self.chain_balance = ChainBalance::empty(network, "");
+        }
+    }
+
+    /// The active account's address on `network`: its Solana or Ethereum
+    /// key (the watched address of a watch-only wallet), or on Mock the
+    /// wallet address when there is neither
+    pub fn chain_address(&self) -> Result<String, String> {
+        let watched = |address: &Option<String>, chain: &str| {
+            address.clone().ok_or_else(|| format!("This watch-only wallet follows no {} address", chain))
+        };
+        match (&self.watching, self.network) {
+            (Some(watching), Network::Mock) => Ok(watching.solana.clone().unwrap_or_else(|| self.address.clone())),
+            (Some(watching), network) if network.eth_network().is_some() => watched(&watching.ethereum, "Ethereum"),
+            (Some(watching), _) => watched(&watching.solana, "Solana"),
+            (None, Network::Mock) if self.seed.is_none() => Ok(self.address.clone()),
+            (None, network) if network.eth_network().is_some() => Ok(format_address(&self.eth_signer(self.account_index)?.address)),
+            (None, _) => Ok(self.solana_keypair(self.account_index)?.address()),
+        }
+    }
+
+    /// The node of `network`
+    fn node(&self) -> Node {
+        match (self.network.solana_cluster(), self.network.eth_network()) {
+            (Some(cluster), _) => Node::Solana(SolanaRpc::new(self.rpc_url.as_deref().unwrap_or(cluster.default_rpc_url()))),
+            (_, Some(network)) => Node::Ethereum(EthRpc::new(self.rpc_url.as_deref().unwrap_or(network.default_rpc_url())), network),
+            (None, None) => Node::Mock,
+        }
+    }
+
+    /// `text` as a token id of `network`: an SPL mint, or an ERC-20 contract
+    /// in checksum case
+    fn token_id(&self, text: &str) -> Result<String, String> {
+        match self.network.eth_network() {
+            Some(_) => Ok(format_address(&parse_address(text)?)),
+            None => parse_pubkey(text).map(|_| text.to_string()),
+        }
+    }
+
+    /// The simulated ledger of `address`, started empty for another address
+    fn mock_ledger(&mut self, address: &str) -> &mut ChainBalance {
+        if self.chain_balance.network != Network::Mock || self.chain_balance.address != address {
+            self.chain_balance = ChainBalance::empty(Network::Mock, address);
+        }
+        &mut self.chain_balance
+    }
+
+    /// The tokens followed on `network`
+    pub fn tokens(&self) -> Vec<&TrackedToken> {
+        self.tokens.iter().filter(|token| token.network == self.network).collect()
+    }
+
+    fn tracked_token(&self, mint: &str) -> Option<&TrackedToken> {
+        let mint = self.token_id(mint).ok()?;
+        self.tokens.iter().find(|token| token.network == self.network && token.mint == mint)
+    }
+
+    /// Follow token `mint` on `network` besides RSM (an SPL mint, or an ERC-20
+    /// contract on Ethereum), with the decimals the token reports (RSM's on
+    /// Mock, where mints are simulated)
+    pub async fn add_token(&mut self, mint: &str) -> Result<TrackedToken, String> {
+        let mint = self.token_id(mint)?;
+        if self.rsm_token.as_deref().is_some_and(|rsm| rsm.eq_ignore_ascii_case(&mint)) {
+            return Err(format!("{} is the RSM token", mint));
+        }
+        if let Some(token) = self.tracked_token(&mint) {
+            return Ok(token.clone());
+        }
+        let decimals = match self.node() {
+            Node::Mock => RSM_DECIMALS,
+            Node::Solana(rpc) => rpc.mint_decimals(&mint).await?,
+            Node::Ethereum(rpc, _) => rpc.erc20_decimals(&parse_address(&mint)?).await?,
+        };
+        let token = TrackedToken { network: self.network, mint, decimals };
+        self.tokens.push(token.clone());
+        Ok(token)
+    }
+
+    /// Read what the wallet's key holds on `network` into `chain_balance`:
+    /// the network's coin, RSM with `rsm_token` set, and the tracked tokens
+    pub async fn refresh_balance(&mut self) -> Result<ChainBalance, String> {
+        let address = self.chain_address()?;
+        let tokens: Vec<TrackedToken> = self.tokens().into_iter().cloned().collect();
+        let node = self.node();
+        if let Node::Mock = node {
+            let ledger = self.mock_ledger(&address);
+            for token in &tokens {
+                ledger.token_mut(&token.mint, token.decimals);
+            }
+            ledger.refreshed_at = chrono::Utc::now().timestamp();
+            return Ok(ledger.clone());
+        }
+
+        let native = TokenAmount::new(node.balance(&address).await?, self.network.native_decimals());
+        let rsm = match &self.rsm_token {
+            Some(mint) => node.token_balance(&address, mint).await?,
+            None => TokenAmount::new(0, RSM_DECIMALS),
+        };
+        let mut balances = Vec::with_capacity(tokens.len());
+        for token in tokens {
+            let amount = node.token_balance(&address, &token.mint).await?;
+            balances.push(TokenBalance { mint: token.mint, amount });
+        }
+        let refreshed_at = chrono::Utc::now().timestamp();
+        self.chain_balance = ChainBalance { network: self.network, address, native, rsm, tokens: balances, refreshed_at };
+        Ok(self.chain_balance.clone())
+    }
+
+    /// `chain_balance`'s RSM, e.g. `12.5 RSM`
+    pub fn rsm_display(&self) -> String {
+        format!("{} RSM", self.chain_balance.rsm.display())
+    }
+
+    /// `chain_balance`'s coin, e.g. `0.25 SOL`
+    pub fn native_display(&self) -> String {
+        format!("{} {}", self.chain_balance.native.display(), self.network.native_symbol())
+    }
+
+    /// `chain_balance`'s `mint`, e.g. `3 EPjF…Dt1v`; `None` for a token not
+    /// followed on `network`
+    pub fn token_display(&self, mint: &str) -> Option<String> {
+        let token = self.tracked_token(mint)?;
+        let amount = self.chain_balance.token(&token.mint).unwrap_or(TokenAmount::new(0, token.decimals));
+        Some(format!("{} {}", amount.display(), token_label(&token.mint)))
+    }
+
+    /// Send `amount` RSM to address `to` on `network` (a transfer of
+    /// `rsm_token`, signed with the wallet's key there) and record it;
+    /// returns the transaction signature (hash on Ethereum)
+    pub async fn transfer_rsm(&mut self, to: &str, amount: f64) -> Result<String, String> {
+        self.send(Asset::Rsm, to, amount).await
+    }
+
+    /// `transfer_rsm` for a tracked token (`add_token`)
+    pub async fn transfer_token(&mut self, mint: &str, to: &str, amount: f64) -> Result<String, String> {
+        let asset = self.token_asset(mint)?;
+        self.send(asset, to, amount).await
+    }
+
+    /// `transfer_rsm` for the network's coin (SOL or ETH)
+    pub async fn transfer_native(&mut self, to: &str, amount: f64) -> Result<String, String> {
+        self.send(Asset::Native, to, amount).await
+    }
+
+    /// `Asset::Token` of a tracked token
+    pub fn token_asset(&self, mint: &str) -> Result<Asset, String> {
+        match self.tracked_token(mint) {
+            Some(token) => Ok(Asset::Token(token.mint.clone())),
+            None => Err(format!("{} is not a token this wallet follows on {} (add it first)", mint, self.network.name())),
+        }
+    }
+
+    /// Send `amount` of `asset` to `to` and record it: built, signed and
+    /// broadcast in one go, or moved within the Mock ledger
+    async fn send(&mut self, asset: Asset, to: &str, amount: f64) -> Result<String, String> {
+        self.ensure_can_sign()?;
+        if self.network != Network::Mock {
+            let unsigned = self.build_unsigned_transfer(asset, to, amount).await?;
+            let signed = self.sign_transaction(&unsigned)?;
+            return self.broadcast_transaction(&signed).await;
+        }
+
+        let address = self.chain_address()?;
+        if to == address {
+            return Err(format!("{} is this wallet's own address", to));
+        }
+        parse_pubkey(to)?;
+        let decimals = match &asset {
+            Asset::Token(mint) => self.tracked_token(mint).map_or(RSM_DECIMALS, |token| token.decimals),
+            _ => RSM_DECIMALS,
+        };
+        let symbol = asset.symbol(Network::Mock);
+        let ledger = self.mock_ledger(&address);
+        let held = match &asset {
+            Asset::Native => &mut ledger.native,
+            Asset::Rsm => &mut ledger.rsm,
+            Asset::Token(mint) => ledger.token_mut(mint, decimals),
+        };
+        let sent = TokenAmount::from_ui(amount, held.decimals)?;
+        if held.raw < sent.raw {
+            return Err(format!("Insufficient balance: {} {} on {}", held.display(), symbol, Network::Mock.name()));
+        }
+        held.raw -= sent.raw;
+        let signature = simulated_signature();
+        self.transactions.push(transfer_record(Network::Mock, &asset, to, sent, &signature));
+        Ok(signature)
+    }
+
+    /// A transfer of `amount` of `asset` from the active account to `to`,
+    /// ready to be signed elsewhere (`sign_transaction`, e.g. on an
+    /// air-gapped machine holding the phrase) and sent with
+    /// `broadcast_transaction`. Needs no key, so watch-only wallets build
+    /// them too; balances, the blockhash or nonce and the fees are read here.
+    pub async fn build_unsigned_transfer(&self, asset: Asset, to: &str, amount: f64) -> Result<UnsignedTransfer, String> {
+        let address = self.chain_address()?;
+        if to.eq_ignore_ascii_case(&address) {
+            return Err(format!("{} is this wallet's own address", to));
+        }
+        let mint = match &asset {
+            Asset::Native => None,
+            Asset::Rsm => Some(self.rsm_token.clone().ok_or(NO_RSM_TOKEN)?),
+            Asset::Token(mint) => Some(mint.clone()),
+        };
+        let insufficient = |held: TokenAmount| format!("Insufficient balance: {} {} on {}", held.display(), asset.symbol(self.network), self.network.name());
+
+        let (amount, payload) = match self.node() {
+            Node::Mock => return Err("Transfers on mock are simulated, not signed: send them directly".to_string()),
+            Node::Solana(rpc) => {
+                let (payer, recipient) = (parse_pubkey(&address)?, parse_pubkey(to)?);
+                let (held, decimals) = match &mint {
+                    None => (rpc.balance(&address).await?, SOL_DECIMALS),
+                    Some(mint) => rpc.token_balance(&address, mint).await?,
+                };
+                let sent = TokenAmount::from_ui(amount, decimals)?;
+                let raw = u64::try_from(sent.raw).map_err(|_| format!("Amount {} is more than any Solana account holds", amount))?;
+                // SOL sent pays its own fee too
+                let cost = raw.saturating_add(if mint.is_none() { LAMPORTS_PER_SIGNATURE } else { 0 });
+                if held < cost {
+                    return Err(insufficient(TokenAmount::new(held.into(), decimals)));
+                }
+
+                let (blockhash, last_valid_block_height) = rpc.latest_blockhash().await?;
+                let instructions = match &mint {
+                    None => vec![sol_transfer(&payer, &recipient, raw)],
+                    Some(mint) => spl_transfer(&payer, &parse_pubkey(mint)?, &recipient, raw, decimals),
+                };
+                let message = compile_message(&payer, &blockhash, &instructions)?;
+                let message = base64::engine::general_purpose::STANDARD.encode(message);
+                (sent, UnsignedPayload::Solana { message, last_valid_block_height })
+            }
+            Node::Ethereum(rpc, network) => {
+                let (from, recipient) = (parse_address(&address)?, parse_address(to)?);
+                let token = mint.as_deref().map(parse_address).transpose()?;
+                let (held, decimals) = match &token {
+                    None => (rpc.balance(&from).await?, ETH_DECIMALS),
+                    Some(token) => (rpc.erc20_balance(token, &from).await?, rpc.erc20_decimals(token).await?),
+                };
+                let sent = TokenAmount::from_ui(amount, decimals)?;
+                if held < sent.raw {
+                    return Err(insufficient(TokenAmount::new(held, decimals)));
+                }
+
+                let (target, value, data) = match &token {
+                    None => (recipient, sent.raw, Vec::new()),
+                    Some(token) => (*token, 0, erc20_transfer_calldata(&recipient, sent.raw)),
+                };
+                let tx = rpc.prepare_tx(network.chain_id(), &from, &target, value, data, gwei_to_wei(DEFAULT_MAX_FEE_GWEI)).await?;
+                let gas_cost = tx.gas_limit as u128 * tx.max_fee_per_gas;
+                let eth = match &token {
+                    None => held,
+                    Some(_) => rpc.balance(&from).await?,
+                };
+                if eth < value + gas_cost {
+                    return Err(format!("Insufficient ETH for gas: {} ETH held, up to {} needed",
+                        TokenAmount::new(eth, ETH_DECIMALS).display(), TokenAmount::new(value + gas_cost, ETH_DECIMALS).display()));
+                }
+                (sent, UnsignedPayload::Ethereum { tx })
+            }
+        };
+        Ok(UnsignedTransfer {
+            network: self.network,
+            account: self.account_index,
+            signer: address,
+            asset,
+            token: mint,
+            to: to.to_string(),
+            amount,
+            payload,
+        })
+    }
+
+    /// Sign `unsigned` with the key of its account; offline, so it also
+    /// runs where the phrase never meets a network. Refused unless the
+    /// payload matches its description (`UnsignedTransfer::verify`) and this
+    /// wallet's key is the transfer's signer.
+    pub fn sign_transaction(&self, unsigned: &UnsignedTransfer) -> Result<SignedTransfer, String> {
+        unsigned.verify()?;
+        let wrong_key = |key: &str| format!("Account {}'s key is {}, not the transfer's signer {}", unsigned.account, key, unsigned.signer);
+        let signature = match &unsigned.payload {
+            UnsignedPayload::Solana { message, .. } => {
+                let keypair = self.solana_keypair(unsigned.account)?;
+                if keypair.address() != unsigned.signer {
+                    return Err(wrong_key(&keypair.address()));
+                }
+                let message = base64::engine::general_purpose::STANDARD.decode(message).map_err(|e| format!("Corrupt transfer message: {}", e))?;
+                SolanaTx::sign(&keypair, message).signature_base58()
+            }
+            UnsignedPayload::Ethereum { tx } => {
+                let signer = self.eth_signer(unsigned.account)?;
+                if !format_address(&signer.address).eq_ignore_ascii_case(&unsigned.signer) {
+                    return Err(wrong_key(&format_address(&signer.address)));
+                }
+                format!("0x{}", hex::encode(tx.sign(&signer).signature))
+            }
+        };
+        Ok(SignedTransfer { transfer: unsigned.clone(), signature })
+    }
+
+    /// Send `signed` to the node of `network` and record it; returns the
+    /// transaction's signature (hash on Ethereum). The signature is checked
+    /// against the transfer's signer first, and a Solana transfer whose
+    /// blockhash expired is refused (build and sign it again). Needs no key.
+    pub async fn broadcast_transaction(&mut self, signed: &SignedTransfer) -> Result<String, String> {
+        let transfer = &signed.transfer;
+        if transfer.network != self.network {
+            return Err(format!("The transfer is for {}, this wallet is on {}", transfer.network.name(), self.network.name()));
+        }
+        transfer.verify()?;
+        let forged = || "The signature does not match the transfer and its signer".to_string();
+        let id = match (self.node(), &transfer.payload) {
+            (Node::Solana(rpc), UnsignedPayload::Solana { message, last_valid_block_height }) => {
+                let message = base64::engine::general_purpose::STANDARD.decode(message).map_err(|e| format!("Corrupt transfer message: {}", e))?;
+                let signature: [u8; 64] = base58_decode(&signed.signature).ok().and_then(|s| s.try_into().ok()).ok_or_else(forged)?;
+                if !verify_signature(&parse_pubkey(&transfer.signer)?, &message, &signature) {
+                    return Err(forged());
+                }
+                let height = rpc.block_height().await?;
+                if height > *last_valid_block_height {
+                    return Err(format!("The transfer's blockhash expired at block height {} (now {}): build and sign it again", last_valid_block_height, height));
+                }
+                let tx = SolanaTx { message, signature };
+                let id = rpc.send_transaction(&tx.wire()).await?;
+                if id != tx.signature_base58() {
+                    return Err(format!("sendTransaction returned {}, not the signed {}", id, tx.signature_base58()));
+                }
+                id
+            }
+            (Node::Ethereum(rpc, _), UnsignedPayload::Ethereum { tx }) => {
+                let signature: [u8; 65] = hex::decode(signed.signature.trim_start_matches("0x")).ok()
+                    .and_then(|s| s.try_into().ok()).ok_or_else(forged)?;
+                if recover_address(tx.signing_hash(), &signature) != Ok(parse_address(&transfer.signer)?) {
+                    return Err(forged());
+                }
+                let sent = tx.with_signature(signature);
+                let id = rpc.send_raw_transaction(&sent.raw).await?;
+                let expected = format!("0x{}", hex::encode(sent.hash));
+                if !id.eq_ignore_ascii_case(&expected) {
+                    return Err(format!("eth_sendRawTransaction returned {}, not the signed {}", id, expected));
+                }
+                expected
+            }
+            _ => return Err(format!("The transfer is not a {} transaction", self.network.name())),
+        };
+
+        if self.chain_balance.network == self.network && self.chain_balance.address == transfer.signer {
+            let held = match &transfer.asset {
+                Asset::Native => &mut self.chain_balance.native,
+                Asset::Rsm => &mut self.chain_balance.rsm,
+                Asset::Token(mint) => self.chain_balance.token_mut(mint, transfer.amount.decimals),
+            };
+            *held = TokenAmount::new(held.raw.saturating_sub(transfer.amount.raw), transfer.amount.decimals);
+        }
+        self.transactions.push(transfer_record(self.network, &transfer.asset, &transfer.to, transfer.amount, &id));
+        Ok(id)
+    }
+
+    /// Fund the wallet's address with `amount_sol` test SOL: from the devnet
+    /// faucet, or minted into the Mock ledger (mainnet has no faucet);
+    /// returns the airdrop's signature
+    pub async fn request_airdrop(&mut self, amount_sol: f64) -> Result<String, String> {
+        if amount_sol > MAX_AIRDROP_SOL {
+            return Err(format!("Airdrops are at most {} SOL at once", MAX_AIRDROP_SOL));
+        }
+        let amount = TokenAmount::from_ui(amount_sol, SOL_DECIMALS)?;
+        let address = self.chain_address()?;
+        let signature = match self.network {
+            Network::Mock => {
+                self.mock_ledger(&address).native.raw += amount.raw;
+                simulated_signature()
+            }
+            Network::SolanaDevnet => match self.node() {
+                Node::Solana(rpc) => rpc.request_airdrop(&address, amount.raw as u64).await?,
+                _ => unreachable!("solana-devnet has a Solana node"),
+            },
+            network => return Err(format!("Airdrops only exist on solana-devnet and mock, not {}", network.name())),
+        };
+        self.transactions.push(format!("AIRDROP on {}: +{} SOL ({})", self.network.name(), amount.ui(), signature));
+        Ok(signature)
+    }
+
     pub fn with_address(address: &str) -> Self {
         Self {
             address: address.to_string(),
             rsm_balance: 0.0,
+            staked_rsm: 0.0,
             staked_genomes: Vec::new(),
             rewards_earned: 0.0,
             transactions: Vec::new(),
+            addresses: Vec::new(),
+            seed: None,
+            validator_addresses: Vec::new(),
+            account_index: 0,
+            account_label: default_account_label(),
+            accounts: Vec::new(),
+            network: Network::Mock,
+            rpc_url: None,
+            rsm_token: None,
+            chain_balance: ChainBalance::default(),
+            watching: None,
+            tokens: Vec::new(),
         }
     }
 
@@ -58,6 +1199,35 @@ impl DivineWallet {
         }
     }
 
+    /// Move RSM from the spendable balance into validator stake
+    pub fn lock_stake(&mut self, amount: f64) -> bool {
+        if self.rsm_balance >= amount {
+            self.rsm_balance -= amount;
+            self.staked_rsm += amount;
+            self.transactions.push(format!("STAKE LOCK: -{:.6} RSM", amount));
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn unlock_stake(&mut self, amount: f64) {
+        self.staked_rsm -= amount;
+        self.rsm_balance += amount;
+        self.transactions.push(format!("STAKE UNLOCK: +{:.6} RSM", amount));
+    }
+
+    /// Burn part of the locked stake (slashing)
+    pub fn slash_stake(&mut self, amount: f64) {
+        self.staked_rsm -= amount;
+        self.transactions.push(format!("STAKE SLASHED: -{:.6} RSM", amount));
+    }
+
+    /// Spendable plus staked RSM
+    pub fn total_rsm(&self) -> f64 {
+        self.rsm_balance + self.staked_rsm
+    }
+
     pub fn stake_genome(&mut self, genome_id: i64) {
         if !self.staked_genomes.contains(&genome_id) {
             self.staked_genomes.push(genome_id);
@@ -75,6 +1245,332 @@ impl DivineWallet {
         self.rewards_earned += amount;
         self.transactions.push(format!("REWARD: +{:.6} RSM", amount));
     }
+
+    pub fn add_block_reward(&mut self, amount: f64, block_index: u64) {
+        self.rsm_balance += amount;
+        self.rewards_earned += amount;
+        self.transactions.push(format!("BLOCK REWARD #{}: +{:.6} RSM", block_index, amount));
+    }
+
+    pub fn pay_fee(&mut self, amount: f64) -> bool {
+        if self.rsm_balance >= amount {
+            self.rsm_balance -= amount;
+            self.transactions.push(format!("FEE: -{:.6} RSM", amount));
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn collect_fees(&mut self, amount: f64, block_index: u64) {
+        self.rsm_balance += amount;
+        self.transactions.push(format!("FEES #{}: +{:.6} RSM", block_index, amount));
+    }
+
+    /// Ask for `amount` RSM to this wallet
+    pub fn payment_request(&self, amount: f64, memo: Option<&str>) -> PaymentRequest {
+        PaymentRequest {
+            address: self.address.clone(),
+            amount_rsm: amount,
+            memo: memo.map(str::to_string),
+        }
+    }
+
+    /// Write the wallet to `path`, encrypted with `password`
+    pub fn save(&self, path: impl AsRef<Path>, password: &str) -> anyhow::Result<()> {
+        let salt: [u8; 16] = rand::random();
+        let plaintext = serde_json::to_vec(self)?;
+        let key = wallet_key(&pbkdf2_password(password, &salt, WALLET_KDF_ITERATIONS).map_err(anyhow::Error::msg)?);
+        let sealed = encrypt_aes_gcm(&key, &plaintext).map_err(anyhow::Error::msg)?;
+
+        let mut out = Vec::with_capacity(WALLET_FILE_MAGIC.len() + 7 + salt.len() + sealed.len());
+        out.extend_from_slice(WALLET_FILE_MAGIC);
+        out.extend_from_slice(&WALLET_FILE_VERSION.to_le_bytes());
+        out.push(WALLET_KDF_PBKDF2_SHA256);
+        out.extend_from_slice(&WALLET_KDF_ITERATIONS.to_le_bytes());
+        out.extend_from_slice(&salt);
+        out.extend(sealed);
+
+        write_atomically(path.as_ref(), &out)?;
+        Ok(())
+    }
+
+    /// Read a wallet written by `save`, or by a version 1 node
+    pub fn load(path: impl AsRef<Path>, password: &str) -> anyhow::Result<Self> {
+        let data = std::fs::read(path)?;
+        if data.len() < WALLET_FILE_MAGIC.len() + 2 || &data[..8] != WALLET_FILE_MAGIC {
+            anyhow::bail!("Not a Divine wallet file");
+        }
+        let version = u16::from_le_bytes([data[8], data[9]]);
+        let (stretched, sealed) = match version {
+            1 => {
+                let salt = data.get(10..26).ok_or_else(|| anyhow::anyhow!("Not a Divine wallet file"))?;
+                (legacy_stretch_password(password, salt), &data[26..])
+            }
+            2 => {
+                let header = data.get(10..31).ok_or_else(|| anyhow::anyhow!("Not a Divine wallet file"))?;
+                if header[0] != WALLET_KDF_PBKDF2_SHA256 {
+                    anyhow::bail!("Unsupported wallet key derivation {}", header[0]);
+                }
+                let iterations = u32::from_le_bytes([header[1], header[2], header[3], header[4]]);
+                (pbkdf2_password(password, &header[5..], iterations).map_err(anyhow::Error::msg)?, &data[31..])
+            }
+            _ => anyhow::bail!("Unsupported wallet file version {}", version),
+        };
+
+        let plaintext = decrypt_aes_gcm(&wallet_key(&stretched), sealed)
+            .map_err(|_| anyhow::anyhow!("Wrong password or corrupted wallet file"))?;
+        Ok(serde_json::from_slice(&plaintext)?)
+    }
+}
+
+/// What a transfer sends: the network's coin, RSM or a tracked token
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Asset {
+    Native,
+    Rsm,
+    /// By mint (or ERC-20 contract)
+    Token(String),
+}
+
+impl Asset {
+    pub fn symbol(&self, network: Network) -> String {
+        match self {
+            Asset::Native => network.native_symbol().to_string(),
+            Asset::Rsm => "RSM".to_string(),
+            Asset::Token(mint) => token_label(mint),
+        }
+    }
+}
+
+/// What the signer of an `UnsignedTransfer` signs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum UnsignedPayload {
+    /// A Solana message (base64), sendable until the cluster passes
+    /// `last_valid_block_height`
+    Solana { message: String, last_valid_block_height: u64 },
+    /// An EIP-1559 transaction, at its nonce and fees
+    Ethereum { tx: Eip1559Tx },
+}
+
+/// A transfer built by `build_unsigned_transfer`, moved as JSON to
+/// wherever the key is
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnsignedTransfer {
+    pub network: Network,
+    /// Wallet account whose key signs
+    pub account: u32,
+    /// Its address on `network`
+    pub signer: String,
+    pub asset: Asset,
+    /// The mint (ERC-20 contract) sent, `None` for the network's coin
+    pub token: Option<String>,
+    pub to: String,
+    pub amount: TokenAmount,
+    pub payload: UnsignedPayload,
+}
+
+impl UnsignedTransfer {
+    /// `1.5 RSM → <to> on solana-devnet`, to check before signing
+    pub fn describe(&self) -> String {
+        format!("{} {} → {} on {}", self.amount.display(), self.asset.symbol(self.network), self.to, self.network.name())
+    }
+
+    /// Check that the payload sends exactly what the fields above describe,
+    /// paid by `signer`, so what is reviewed is what gets signed
+    pub fn verify(&self) -> Result<(), String> {
+        let mismatch = || format!("The transaction does not send {}", self.describe());
+        match &self.payload {
+            UnsignedPayload::Solana { message, .. } => {
+                let message = base64::engine::general_purpose::STANDARD.decode(message).map_err(|e| format!("Corrupt transfer message: {}", e))?;
+                // Header (3 bytes) and key count (1), the keys, then the blockhash
+                let keys = *message.get(3).ok_or_else(mismatch)? as usize;
+                let blockhash: [u8; 32] = message.get(4 + 32 * keys..4 + 32 * (keys + 1))
+                    .and_then(|hash| hash.try_into().ok()).ok_or_else(mismatch)?;
+                let (payer, recipient) = (parse_pubkey(&self.signer)?, parse_pubkey(&self.to)?);
+                let raw = u64::try_from(self.amount.raw).map_err(|_| mismatch())?;
+                let instructions = match &self.token {
+                    None => vec![sol_transfer(&payer, &recipient, raw)],
+                    Some(mint) => spl_transfer(&payer, &parse_pubkey(mint)?, &recipient, raw, self.amount.decimals),
+                };
+                match compile_message(&payer, &blockhash, &instructions)? == message {
+                    true => Ok(()),
+                    false => Err(mismatch()),
+                }
+            }
+            UnsignedPayload::Ethereum { tx } => {
+                let recipient = parse_address(&self.to)?;
+                let expected = match &self.token {
+                    None => (recipient, self.amount.raw, Vec::new()),
+                    Some(token) => (parse_address(token)?, 0, erc20_transfer_calldata(&recipient, self.amount.raw)),
+                };
+                if self.network.eth_network().map(|network| network.chain_id()) != Some(tx.chain_id) {
+                    return Err(format!("The transaction is for chain {}, not {}", tx.chain_id, self.network.name()));
+                }
+                match (tx.to, tx.value, &tx.data) == (expected.0, expected.1, &expected.2) {
+                    true => Ok(()),
+                    false => Err(mismatch()),
+                }
+            }
+        }
+    }
+}
+
+/// An `UnsignedTransfer` and its signer's signature: base58 on Solana,
+/// `0x` hex `r ‖ s ‖ y_parity` on Ethereum
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedTransfer {
+    pub transfer: UnsignedTransfer,
+    pub signature: String,
+}
+
+/// The history entry of a transfer
+fn transfer_record(network: Network, asset: &Asset, to: &str, amount: TokenAmount, id: &str) -> String {
+    let (kind, unit) = match asset {
+        Asset::Rsm => ("RSM".to_string(), "RSM".to_string()),
+        Asset::Token(mint) => ("TOKEN".to_string(), mint.clone()),
+        Asset::Native => (network.native_symbol().to_string(), network.native_symbol().to_string()),
+    };
+    format!("{} TRANSFER → {} on {}: -{} {} ({})", kind, to, network.name(), amount.display(), unit, id)
+}
+
+/// The node a wallet's network is read and sent through
+enum Node {
+    Mock,
+    Solana(SolanaRpc),
+    Ethereum(EthRpc, EthNetwork),
+}
+
+impl Node {
+    /// The coin `address` holds, in its smallest unit
+    async fn balance(&self, address: &str) -> Result<u128, String> {
+        match self {
+            Node::Mock => Err(MOCK_HAS_NO_NODE.to_string()),
+            Node::Solana(rpc) => Ok(rpc.balance(address).await?.into()),
+            Node::Ethereum(rpc, _) => rpc.balance(&parse_address(address)?).await,
+        }
+    }
+
+    async fn token_balance(&self, owner: &str, mint: &str) -> Result<TokenAmount, String> {
+        match self {
+            Node::Mock => Err(MOCK_HAS_NO_NODE.to_string()),
+            Node::Solana(rpc) => {
+                let (raw, decimals) = rpc.token_balance(owner, mint).await?;
+                Ok(TokenAmount::new(raw.into(), decimals))
+            }
+            Node::Ethereum(rpc, _) => {
+                let token = parse_address(mint)?;
+                let raw = rpc.erc20_balance(&token, &parse_address(owner)?).await?;
+                Ok(TokenAmount::new(raw, rpc.erc20_decimals(&token).await?))
+            }
+        }
+    }
+}
+
+const MOCK_HAS_NO_NODE: &str = "The mock network has no node: its ledger is in the wallet file";
+
+/// A mint shortened for display: `EPjF…Dt1v`
+fn token_label(mint: &str) -> String {
+    match mint.len() > 8 {
+        true => format!("{}…{}", &mint[..4], &mint[mint.len() - 4..]),
+        false => mint.to_string(),
+    }
+}
+
+/// A random signature for what the Mock network "sends"
+fn simulated_signature() -> String {
+    let mut signature = [0u8; 64];
+    rand::Rng::fill(&mut rand::thread_rng(), &mut signature[..]);
+    base58_encode(&signature)
+}
+
+/// Fresh BIP-39 recovery phrase of `words` words
+pub fn generate_phrase(words: usize) -> Result<String, String> {
+    if !MNEMONIC_WORD_COUNTS.contains(&words) {
+        return Err(format!("Recovery phrases have 12, 15, 18, 21 or 24 words, not {}", words));
+    }
+    Ok(bip39::Mnemonic::generate(words).map_err(|e| e.to_string())?.to_string())
+}
+
+/// How many addresses of a derivation to keep: up to the last used one,
+/// looking `RECOVERY_GAP_LIMIT` past it
+fn used_prefix(derive: impl Fn(u32) -> String, used: impl Fn(&str) -> bool) -> u32 {
+    let (mut kept, mut index) = (0, 0);
+    while index < kept + RECOVERY_GAP_LIMIT {
+        if used(&derive(index)) {
+            kept = index + 1;
+        }
+        index += 1;
+    }
+    kept
+}
+
+/// Address `index` of account `account`; account 0 keeps the original
+/// derivation so its addresses predate accounts
+fn derive_account_address(seed: &str, account: u32, index: u32) -> String {
+    if account == 0 {
+        return derive_address(seed, index);
+    }
+    let hash = Sha256::new()
+        .chain_update(b"DIVINE_ACCOUNT_V1")
+        .chain_update(seed)
+        .chain_update(account.to_le_bytes())
+        .chain_update(index.to_le_bytes())
+        .finalize();
+    format!("divine_{}", hex::encode(&hash[..16]))
+}
+
+fn derive_address(seed: &str, index: u32) -> String {
+    let hash = Sha256::new()
+        .chain_update(b"DIVINE_ADDRESS_V1")
+        .chain_update(seed)
+        .chain_update(index.to_le_bytes())
+        .finalize();
+    format!("divine_{}", hex::encode(&hash[..16]))
+}
+
+/// `password` stretched with PBKDF2-HMAC-SHA256 over `iterations`
+/// (1 to `MAX_KDF_ITERATIONS`)
+pub(crate) fn pbkdf2_password(password: &str, salt: &[u8], iterations: u32) -> Result<[u8; 32], String> {
+    let rounds = std::num::NonZeroU32::new(iterations)
+        .filter(|rounds| rounds.get() <= MAX_KDF_ITERATIONS)
+        .ok_or_else(|| format!("Key derivation iterations must be 1 to {}, got {}", MAX_KDF_ITERATIONS, iterations))?;
+    let mut stretched = [0u8; 32];
+    ring::pbkdf2::derive(ring::pbkdf2::PBKDF2_HMAC_SHA256, rounds, salt, password.as_bytes(), &mut stretched);
+    Ok(stretched)
+}
+
+/// `password` stretched over `LEGACY_KDF_ROUNDS` salted SHA-256 rounds, as
+/// version 1 wallet files were; only read, never written
+pub(crate) fn legacy_stretch_password(password: &str, salt: &[u8]) -> [u8; 32] {
+    let mut digest: [u8; 32] = Sha256::new().chain_update(salt).chain_update(password).finalize().into();
+    for _ in 0..LEGACY_KDF_ROUNDS {
+        digest = Sha256::new().chain_update(digest).chain_update(password).finalize().into();
+    }
+    digest
+}
+
+fn wallet_key(stretched: &[u8; 32]) -> [u8; 32] {
+    derive_key(stretched, b"DIVINE_WALLET_V1")
+}
+
+/// Write `bytes` to a fresh sibling of `path` (`.<name>.tmp-<random>`) and
+/// rename it over `path`, so a crash never leaves half a file and
+/// concurrent writers never share a temp file
+pub(crate) fn write_atomically(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let name = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+    let tmp = path.with_file_name(format!(".{}.tmp-{}", name, hex::encode(rand::random::<[u8; 8]>())));
+    let written = std::fs::OpenOptions::new().write(true).create_new(true).open(&tmp)
+        .and_then(|mut file| file.write_all(bytes).and_then(|_| file.sync_all()))
+        .and_then(|_| std::fs::rename(&tmp, path));
+    if written.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+    written
 }
 
 impl Default for DivineWallet {
@@ -83,7 +1579,7 @@ impl Default for DivineWallet {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct WalletManager {
     wallets: HashMap<String, DivineWallet>,
 }
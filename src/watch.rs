@@ -0,0 +1,159 @@
+//! Watch V1 — live tail of new blocks, genomes and archives
+//!
+//! Inserts into `consensus_blocks`, `divine_genomes_v15` and `chain_archives`
+//! fire a Postgres `NOTIFY` (triggers created by `init_tables`) carrying the new
+//! row as JSON, so `divine-agi watch` sees what every server and CLI sharing
+//! the database writes. Rows too big for a notification (8000 bytes) are not
+//! sent.
+//!
+//! `--filter` expressions are `<column><op><value>` with `op` one of `=`,
+//! `!=`, `>`, `>=`, `<`, `<=` or `~` (contains), e.g. `consciousness>=5000`
+//! or `layer=bitcoin`; numbers compare numerically, text case-insensitively.
+//! Every filter must match.
+
+use clap::ValueEnum;
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WatchTopic {
+    Blocks,
+    Genomes,
+    Archives,
+}
+
+impl WatchTopic {
+    pub const ALL: [WatchTopic; 3] = [Self::Blocks, Self::Genomes, Self::Archives];
+
+    pub fn table(self) -> &'static str {
+        match self {
+            Self::Blocks => "consensus_blocks",
+            Self::Genomes => "divine_genomes_v15",
+            Self::Archives => "chain_archives",
+        }
+    }
+
+    /// `NOTIFY` channel the table's inserts are sent on
+    pub fn channel(self) -> &'static str {
+        match self {
+            Self::Blocks => "divine_watch_blocks",
+            Self::Genomes => "divine_watch_genomes",
+            Self::Archives => "divine_watch_archives",
+        }
+    }
+
+    /// Columns left out of notifications (binary or unbounded)
+    pub fn omitted_columns(self) -> &'static [&'static str] {
+        match self {
+            Self::Blocks => &["stake_ops", "signature"],
+            Self::Genomes => &["hash"],
+            Self::Archives => &[],
+        }
+    }
+
+    /// One line for a notified row
+    pub fn describe(self, row: &Value) -> String {
+        let text = |key: &str| match &row[key] {
+            Value::String(s) => s.clone(),
+            Value::Null => "-".into(),
+            other => other.to_string(),
+        };
+        let short = |key: &str| text(key).chars().take(16).collect::<String>();
+        match self {
+            Self::Blocks => format!(
+                "⛓️  block #{} {}… difficulty {} | attested consciousness {} | reward {} RSM | producer {}…",
+                text("block_index"), short("hash"), text("difficulty"), text("attested_consciousness"),
+                text("reward_rsm"), short("producer"),
+            ),
+            Self::Genomes => format!(
+                "🧬 genome #{} {} | consciousness {} | p53 {} | telomere {} | T/G {}",
+                text("id"), text("dna"), text("consciousness"), text("p53_copies"), text("telomere_length"), text("tg_ratio"),
+            ),
+            Self::Archives => format!(
+                "📦 archive #{} genome #{} on {} | tx {}",
+                text("id"), text("genome_id"), text("layer"), text("tx_hash"),
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FilterOp {
+    Eq,
+    Ne,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    Contains,
+}
+
+/// One `--filter` expression
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WatchFilter {
+    pub field: String,
+    pub op: FilterOp,
+    pub value: String,
+}
+
+impl WatchFilter {
+    pub fn parse(expr: &str) -> Result<Self, String> {
+        // Two-character operators first, so `>=` is not read as `>`
+        const OPS: [(&str, FilterOp); 7] = [
+            ("!=", FilterOp::Ne), (">=", FilterOp::Gte), ("<=", FilterOp::Lte),
+            ("=", FilterOp::Eq), (">", FilterOp::Gt), ("<", FilterOp::Lt), ("~", FilterOp::Contains),
+        ];
+        let (at, token, op) = OPS.iter()
+            .filter_map(|(token, op)| expr.find(token).map(|at| (at, *token, *op)))
+            .min_by_key(|(at, token, _)| (*at, std::cmp::Reverse(token.len())))
+            .ok_or_else(|| format!("Invalid filter {} (expected <column><op><value>, op one of = != > >= < <= ~)", expr))?;
+        let field = expr[..at].trim();
+        let value = expr[at + token.len()..].trim();
+        if field.is_empty() || value.is_empty() {
+            return Err(format!("Invalid filter {} (expected <column><op><value>)", expr));
+        }
+        Ok(Self { field: field.to_string(), op, value: value.to_string() })
+    }
+
+    /// Whether `row` matches; a missing column never does
+    pub fn matches(&self, row: &Value) -> bool {
+        let actual = match &row[self.field.as_str()] {
+            Value::Null => return false,
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        match (actual.parse::<f64>(), self.value.parse::<f64>()) {
+            (Ok(actual), Ok(wanted)) if self.op != FilterOp::Contains => match self.op {
+                FilterOp::Eq => actual == wanted,
+                FilterOp::Ne => actual != wanted,
+                FilterOp::Gt => actual > wanted,
+                FilterOp::Gte => actual >= wanted,
+                FilterOp::Lt => actual < wanted,
+                _ => actual <= wanted,
+            },
+            _ => {
+                let (actual, wanted) = (actual.to_lowercase(), self.value.to_lowercase());
+                match self.op {
+                    FilterOp::Eq => actual == wanted,
+                    FilterOp::Ne => actual != wanted,
+                    FilterOp::Contains => actual.contains(&wanted),
+                    // Ordering needs numbers
+                    _ => false,
+                }
+            }
+        }
+    }
+}
+
+/// Whether `row` passes every filter
+pub fn matches_all(filters: &[WatchFilter], row: &Value) -> bool {
+    filters.iter().all(|filter| filter.matches(row))
+}
+
+/// A notified row, as printed by `--output json|yaml`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WatchEvent {
+    pub topic: WatchTopic,
+    pub row: Value,
+}
@@ -0,0 +1,476 @@
+//! Webhooks V1 — signed event callbacks with retries
+//!
+//! API clients register a URL for events with `POST /api/webhooks` and get
+//! the webhook's secret once. Each event is POSTed to it as JSON
+//! `{"id", "event", "created_at", "data"}` with headers
+//!
+//! - `X-Divine-Event`: `block.added`, `archive.confirmed` or `evolution.finished`
+//! - `X-Divine-Delivery`: delivery id (the same on every retry)
+//! - `X-Divine-Signature`: `t=<unix>,v1=<hex HMAC-SHA256(secret, "<t>.<body>")>`
+//!
+//! A 2xx answer delivers it. Anything else is retried after each of
+//! `WEBHOOK_RETRY_DELAYS_SECS`, then marked failed. Every delivery and its
+//! last attempt are kept in the delivery log (`GET /api/webhooks/:id/deliveries`);
+//! pending retries resume after a restart.
+//!
+//! Webhook URLs must reach public addresses: at registration the host is
+//! resolved and refused if any address is loopback, private, link-local,
+//! shared (CGNAT), multicast or unspecified, and deliveries resolve through
+//! `PublicResolver`, which drops such addresses, so a name re-pointed at an
+//! internal service after registration gets nothing. Hosts listed in
+//! `WEBHOOK_ALLOWED_HOSTS` (comma-separated names or IPs) are exempt, for
+//! receivers on the node's own network.
+
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use serde::{Serialize, Deserialize};
+use sha2::Sha256;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+use crate::consensus::ConsensusBlock;
+use crate::database::DivineDatabase;
+use crate::multi_chain::ChainArchiveEntry;
+use crate::ttrl::EvolutionJobRecord;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Wait before each retry; a delivery gets `len + 1` attempts
+pub const WEBHOOK_RETRY_DELAYS_SECS: [i64; 4] = [10, 60, 300, 1800];
+pub const WEBHOOK_TIMEOUT_SECS: u64 = 10;
+pub const MAX_WEBHOOKS_PER_KEY: usize = 20;
+/// Signatures older than this are rejected by `verify_signature`
+pub const SIGNATURE_TOLERANCE_SECS: i64 = 300;
+
+pub const EVENT_HEADER: &str = "x-divine-event";
+pub const DELIVERY_HEADER: &str = "x-divine-delivery";
+pub const SIGNATURE_HEADER: &str = "x-divine-signature";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WebhookEventKind {
+    #[serde(rename = "block.added")]
+    BlockAdded,
+    #[serde(rename = "archive.confirmed")]
+    ArchiveConfirmed,
+    #[serde(rename = "evolution.finished")]
+    EvolutionFinished,
+}
+
+impl WebhookEventKind {
+    pub const ALL: [WebhookEventKind; 3] = [Self::BlockAdded, Self::ArchiveConfirmed, Self::EvolutionFinished];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::BlockAdded => "block.added",
+            Self::ArchiveConfirmed => "archive.confirmed",
+            Self::EvolutionFinished => "evolution.finished",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+}
+
+/// Something a webhook can be told about
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WebhookEvent {
+    pub kind: WebhookEventKind,
+    pub data: serde_json::Value,
+    pub created_at: i64,
+}
+
+impl WebhookEvent {
+    fn new(kind: WebhookEventKind, data: serde_json::Value) -> Self {
+        Self { kind, data, created_at: chrono::Utc::now().timestamp() }
+    }
+
+    pub fn block_added(block: &ConsensusBlock) -> Self {
+        Self::new(WebhookEventKind::BlockAdded, serde_json::json!({
+            "index": block.index,
+            "hash": block.hash,
+            "previous_hash": block.previous_hash,
+            "timestamp": block.timestamp,
+            "producer": block.producer,
+            "genome_ids": block.entries.iter().map(|e| e.genome_id).collect::<Vec<_>>(),
+        }))
+    }
+
+    pub fn archive_confirmed(entry: &ChainArchiveEntry) -> Self {
+        Self::new(WebhookEventKind::ArchiveConfirmed, serde_json::json!({
+            "id": entry.id,
+            "genome_id": entry.genome_id,
+            "dna_hash": entry.dna_hash,
+            "layer": entry.layer,
+            "tx_hash": entry.tx_hash,
+            "timestamp": entry.timestamp,
+        }))
+    }
+
+    pub fn evolution_finished(job: &EvolutionJobRecord) -> Self {
+        Self::new(WebhookEventKind::EvolutionFinished, serde_json::to_value(job).unwrap_or_default())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Webhook {
+    pub id: i64,
+    pub url: String,
+    pub events: Vec<WebhookEventKind>,
+    /// Shown once, when the webhook is created
+    #[serde(skip_serializing)]
+    pub secret: String,
+    /// API key that registered it
+    pub key_id: i64,
+    pub created_at: i64,
+}
+
+impl Webhook {
+    pub fn wants(&self, kind: WebhookEventKind) -> bool {
+        self.events.contains(&kind)
+    }
+}
+
+/// Whether `ip` is routable on the public internet: not loopback, private,
+/// link-local, shared (100.64.0.0/10), multicast, broadcast or unspecified,
+/// nor an IPv6 unique-local or IPv4-mapped form of those
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            let [a, b, ..] = v4.octets();
+            !(v4.is_loopback() || v4.is_private() || v4.is_link_local() || v4.is_unspecified()
+                || v4.is_broadcast() || v4.is_multicast() || a == 0 || (a == 100 && (64..128).contains(&b)))
+        }
+        IpAddr::V6(v6) => {
+            if let Some(v4) = v6.to_ipv4_mapped() {
+                return is_public_ip(IpAddr::V4(v4));
+            }
+            let first = v6.segments()[0];
+            !(v6.is_loopback() || v6.is_unspecified() || v6.is_multicast()
+                || (first & 0xfe00) == 0xfc00   // unique local fc00::/7
+                || (first & 0xffc0) == 0xfe80)  // link-local fe80::/10
+        }
+    }
+}
+
+/// Hosts webhooks may reach on non-public addresses
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WebhookTargets {
+    /// Lowercase host names or IPs, as in the URL (IPv6 without brackets)
+    pub allowed_hosts: Vec<String>,
+}
+
+impl WebhookTargets {
+    /// `WEBHOOK_ALLOWED_HOSTS`, comma-separated; empty when unset
+    pub fn from_env() -> Self {
+        let allowed = std::env::var("WEBHOOK_ALLOWED_HOSTS").unwrap_or_default();
+        Self {
+            allowed_hosts: allowed.split(',')
+                .map(|host| host.trim().trim_start_matches('[').trim_end_matches(']').to_ascii_lowercase())
+                .filter(|host| !host.is_empty())
+                .collect(),
+        }
+    }
+
+    pub fn allows(&self, host: &str) -> bool {
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        self.allowed_hosts.iter().any(|allowed| allowed.eq_ignore_ascii_case(host))
+    }
+}
+
+/// Absolute http(s) URL with a host whose every address is public, unless
+/// `targets` allows the host
+pub async fn validate_url(url: &str, targets: &WebhookTargets) -> Result<(), String> {
+    let parsed = reqwest::Url::parse(url).map_err(|e| format!("Invalid webhook URL {}: {}", url, e))?;
+    let host = match parsed.host_str() {
+        Some(host) if !host.is_empty() && matches!(parsed.scheme(), "http" | "https") => host,
+        _ => return Err(format!("Webhook URL {} must be http(s) with a host", url)),
+    };
+    if targets.allows(host) {
+        return Ok(());
+    }
+    let port = parsed.port_or_known_default().unwrap_or(80);
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.trim_start_matches('[').trim_end_matches(']'), port)).await
+        .map_err(|e| format!("Webhook host {} does not resolve: {}", host, e))?
+        .collect();
+    match addrs.iter().find(|addr| !is_public_ip(addr.ip())) {
+        Some(addr) => Err(format!("Webhook host {} resolves to non-public address {}; add it to WEBHOOK_ALLOWED_HOSTS to allow it", host, addr.ip())),
+        None if addrs.is_empty() => Err(format!("Webhook host {} does not resolve", host)),
+        None => Ok(()),
+    }
+}
+
+/// DNS for deliveries: only public addresses, unless the host is allowed
+pub struct PublicResolver {
+    pub targets: WebhookTargets,
+}
+
+impl reqwest::dns::Resolve for PublicResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        let allowed = self.targets.allows(name.as_str());
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((name.as_str(), 0)).await?
+                .filter(|addr| allowed || is_public_ip(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(format!("{} has no public address", name.as_str()).into());
+            }
+            Ok(Box::new(addrs.into_iter()) as reqwest::dns::Addrs)
+        })
+    }
+}
+
+pub fn generate_secret() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    format!("whsec_{}", hex::encode(bytes))
+}
+
+/// `X-Divine-Signature` value for `body` sent at `timestamp`
+pub fn sign(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(format!("{}.", timestamp).as_bytes());
+    mac.update(body);
+    format!("t={},v1={}", timestamp, hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Check a received `X-Divine-Signature` (what receivers should do)
+pub fn verify_signature(secret: &str, header: &str, body: &[u8], now: i64) -> bool {
+    let mut timestamp = None;
+    let mut signature = None;
+    for part in header.split(',') {
+        match part.trim().split_once('=') {
+            Some(("t", t)) => timestamp = t.parse::<i64>().ok(),
+            Some(("v1", v)) => signature = hex::decode(v).ok(),
+            _ => {}
+        }
+    }
+    let (Some(timestamp), Some(signature)) = (timestamp, signature) else {
+        return false;
+    };
+    if (now - timestamp).abs() > SIGNATURE_TOLERANCE_SECS {
+        return false;
+    }
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(format!("{}.", timestamp).as_bytes());
+    mac.update(body);
+    mac.verify_slice(&signature).is_ok()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeliveryStatus {
+    Pending,
+    Delivered,
+    Failed,
+}
+
+impl DeliveryStatus {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Delivered => "delivered",
+            Self::Failed => "failed",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Self::Pending, Self::Delivered, Self::Failed].into_iter().find(|s| s.name() == name)
+    }
+}
+
+/// One event sent to one webhook, with its latest attempt
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub webhook_id: i64,
+    pub event: WebhookEventKind,
+    /// JSON body, signed as sent
+    pub payload: String,
+    pub status: DeliveryStatus,
+    pub attempts: u32,
+    /// HTTP status of the last attempt
+    pub response_status: Option<u16>,
+    pub error: Option<String>,
+    /// When the next retry is due (pending deliveries)
+    pub next_attempt_at: Option<i64>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl WebhookDelivery {
+    /// Body of delivery `id` for `event`
+    pub fn payload(id: i64, event: &WebhookEvent) -> String {
+        serde_json::json!({
+            "id": id,
+            "event": event.kind.name(),
+            "created_at": event.created_at,
+            "data": event.data,
+        }).to_string()
+    }
+
+    /// Record an attempt answered with an HTTP status, or failed with an
+    /// error; returns the wait before the next retry, if any
+    pub fn record_attempt(&mut self, outcome: Result<u16, String>, now: i64) -> Option<i64> {
+        self.attempts += 1;
+        self.updated_at = now;
+        let (response_status, error) = match outcome {
+            Ok(status) if (200..300).contains(&status) => {
+                self.status = DeliveryStatus::Delivered;
+                self.response_status = Some(status);
+                self.error = None;
+                self.next_attempt_at = None;
+                return None;
+            }
+            Ok(status) => (Some(status), format!("Endpoint answered {}", status)),
+            Err(e) => (None, e),
+        };
+        self.response_status = response_status;
+        self.error = Some(error);
+
+        match WEBHOOK_RETRY_DELAYS_SECS.get(self.attempts as usize - 1) {
+            Some(&delay) => {
+                self.status = DeliveryStatus::Pending;
+                self.next_attempt_at = Some(now + delay);
+                Some(delay)
+            }
+            None => {
+                self.status = DeliveryStatus::Failed;
+                self.next_attempt_at = None;
+                None
+            }
+        }
+    }
+}
+
+/// Registered webhooks and the HTTP client delivering to them
+pub struct WebhookDispatcher {
+    database: Arc<DivineDatabase>,
+    client: reqwest::Client,
+    targets: WebhookTargets,
+    webhooks: RwLock<Vec<Webhook>>,
+}
+
+impl WebhookDispatcher {
+    /// Dispatcher for the webhooks stored in `database`
+    pub async fn load(database: Arc<DivineDatabase>) -> anyhow::Result<Arc<Self>> {
+        let webhooks = database.load_webhooks().await?;
+        info!("🪝 {} webhook(s) registered", webhooks.len());
+        let targets = WebhookTargets::from_env();
+        if !targets.allowed_hosts.is_empty() {
+            info!("🪝 Webhooks may reach non-public {}", targets.allowed_hosts.join(", "));
+        }
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(WEBHOOK_TIMEOUT_SECS))
+            .redirect(reqwest::redirect::Policy::none())
+            .dns_resolver(Arc::new(PublicResolver { targets: targets.clone() }))
+            .user_agent(format!("divine-agi/{}", crate::VERSION))
+            .build()?;
+        Ok(Arc::new(Self { database, client, targets, webhooks: RwLock::new(webhooks) }))
+    }
+
+    pub async fn webhooks(&self) -> Vec<Webhook> {
+        self.webhooks.read().await.clone()
+    }
+
+    pub async fn get(&self, id: i64) -> Option<Webhook> {
+        self.webhooks.read().await.iter().find(|w| w.id == id).cloned()
+    }
+
+    pub async fn register(&self, url: String, mut events: Vec<WebhookEventKind>, key_id: i64) -> Result<Webhook, String> {
+        validate_url(&url, &self.targets).await?;
+        events.sort_by_key(|kind| kind.name());
+        events.dedup();
+        if events.is_empty() {
+            return Err("Subscribe to at least one event".into());
+        }
+        if self.webhooks.read().await.iter().filter(|w| w.key_id == key_id).count() >= MAX_WEBHOOKS_PER_KEY {
+            return Err(format!("An API key can register at most {} webhooks", MAX_WEBHOOKS_PER_KEY));
+        }
+        let mut webhook = Webhook { id: 0, url, events, secret: generate_secret(), key_id, created_at: chrono::Utc::now().timestamp() };
+        webhook.id = self.database.store_webhook(&webhook).await.map_err(|e| e.to_string())?;
+        self.webhooks.write().await.push(webhook.clone());
+        Ok(webhook)
+    }
+
+    pub async fn remove(&self, id: i64) -> Result<bool, String> {
+        let removed = self.database.delete_webhook(id).await.map_err(|e| e.to_string())?;
+        self.webhooks.write().await.retain(|w| w.id != id);
+        Ok(removed)
+    }
+
+    /// Queue `event` for every webhook subscribed to it
+    pub async fn emit(self: &Arc<Self>, event: WebhookEvent) {
+        let targets: Vec<Webhook> = self.webhooks.read().await.iter().filter(|w| w.wants(event.kind)).cloned().collect();
+        for webhook in targets {
+            match self.database.create_webhook_delivery(webhook.id, &event).await {
+                Ok(delivery) => {
+                    tokio::spawn(Arc::clone(self).deliver(webhook, delivery));
+                }
+                Err(e) => warn!("🪝 {} for webhook #{} not queued: {}", event.kind.name(), webhook.id, e),
+            }
+        }
+    }
+
+    /// Restart retries left pending by a previous run
+    pub async fn resume(self: &Arc<Self>) -> anyhow::Result<()> {
+        for delivery in self.database.pending_webhook_deliveries().await? {
+            if let Some(webhook) = self.get(delivery.webhook_id).await {
+                tokio::spawn(Arc::clone(self).deliver(webhook, delivery));
+            }
+        }
+        Ok(())
+    }
+
+    async fn deliver(self: Arc<Self>, webhook: Webhook, mut delivery: WebhookDelivery) {
+        if let Some(due) = delivery.next_attempt_at {
+            let wait = due - chrono::Utc::now().timestamp();
+            if wait > 0 {
+                tokio::time::sleep(Duration::from_secs(wait as u64)).await;
+            }
+        }
+        loop {
+            // Removed webhooks get no more attempts
+            if self.get(webhook.id).await.is_none() {
+                return;
+            }
+            let outcome = self.attempt(&webhook, &delivery).await;
+            let retry = delivery.record_attempt(outcome, chrono::Utc::now().timestamp());
+            if let Err(e) = self.database.update_webhook_delivery(&delivery).await {
+                warn!("🪝 Delivery #{} state not persisted: {}", delivery.id, e);
+            }
+            match retry {
+                Some(delay) => tokio::time::sleep(Duration::from_secs(delay as u64)).await,
+                None => break,
+            }
+        }
+        if delivery.status == DeliveryStatus::Failed {
+            warn!("🪝 Delivery #{} to webhook #{} failed after {} attempts: {}",
+                  delivery.id, webhook.id, delivery.attempts, delivery.error.as_deref().unwrap_or(""));
+        }
+    }
+
+    async fn attempt(&self, webhook: &Webhook, delivery: &WebhookDelivery) -> Result<u16, String> {
+        // IP-literal hosts skip the resolver
+        let host = reqwest::Url::parse(&webhook.url).ok().and_then(|url| url.host_str().map(str::to_string)).unwrap_or_default();
+        if let Ok(ip) = host.trim_start_matches('[').trim_end_matches(']').parse::<IpAddr>() {
+            if !is_public_ip(ip) && !self.targets.allows(&host) {
+                return Err(format!("{} is not a public address", ip));
+            }
+        }
+        let signature = sign(&webhook.secret, chrono::Utc::now().timestamp(), delivery.payload.as_bytes());
+        self.client.post(&webhook.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, delivery.event.name())
+            .header(DELIVERY_HEADER, delivery.id.to_string())
+            .header(SIGNATURE_HEADER, signature)
+            .body(delivery.payload.clone())
+            .send()
+            .await
+            .map(|response| response.status().as_u16())
+            .map_err(|e| e.to_string())
+    }
+}
@@ -0,0 +1,147 @@
+//! API Tests for Divine AGI V16 (OpenAPI spec, health probes, versioning)
+
+use axum::{body::Body, extract::Request, http::StatusCode, routing::get, Extension, Router};
+use divine_agi::api::{self, ApiDoc, ComponentStatus, HealthReport};
+use divine_agi::versioning::{self, ApiVersion};
+use tower::ServiceExt;
+use utoipa::OpenApi;
+
+// ═══════════════════════════════════════════════════════════════
+// OPENAPI TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_openapi_spec_covers_routes() {
+    let spec = serde_json::to_value(ApiDoc::openapi()).expect("spec serializes");
+
+    assert!(spec["openapi"].as_str().unwrap().starts_with("3."));
+    let paths = spec["paths"].as_object().unwrap();
+    for path in ["/api/genomes", "/api/genomes/{id}", "/api/genome/evolve", "/api/poc/mine", "/api/wallet/stake", "/api/wallet/unstake", "/auth/token", "/graphql"] {
+        assert!(paths.contains_key(path), "missing {}", path);
+    }
+
+    // Axum `:id` segments are written as OpenAPI `{id}` parameters
+    assert!(paths.keys().all(|p| !p.contains(':')), "axum path syntax leaked into spec");
+    assert_eq!(paths["/api/genomes/{id}"]["get"]["parameters"][0]["name"], "id");
+
+    println!("✅ OpenAPI spec lists {} paths", paths.len());
+}
+
+#[test]
+fn test_openapi_marks_protected_routes() {
+    let spec = serde_json::to_value(ApiDoc::openapi()).expect("spec serializes");
+
+    assert_eq!(spec["components"]["securitySchemes"]["bearer"]["scheme"], "bearer");
+
+    let mine = &spec["paths"]["/api/poc/mine"]["post"];
+    assert!(mine["security"][0].get("bearer").is_some(), "mining requires a token");
+    assert!(mine["responses"].get("401").is_some());
+
+    // The node wallet is protected even for reads
+    assert!(spec["paths"]["/api/wallet/balance"]["get"]["security"][0].get("bearer").is_some());
+
+    assert!(spec["paths"]["/api/admin/audit"]["get"]["security"][0].get("bearer").is_some());
+
+    let list = &spec["paths"]["/api/genomes"]["get"];
+    assert!(list.get("security").is_none(), "reads are open");
+    assert!(list["responses"].get("429").is_some());
+
+    println!("✅ Protected routes carry the bearer scheme");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// HEALTH PROBE TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_readiness_needs_every_component() {
+    let ready = HealthReport::new(vec![
+        ComponentStatus::up("database", "reachable"),
+        ComponentStatus::up("chain", "height 3"),
+        ComponentStatus::up("wallet", "unlocked"),
+    ]);
+    assert!(ready.is_healthy());
+    assert_eq!(ready.status_code(), StatusCode::OK);
+
+    let locked = HealthReport::new(vec![
+        ComponentStatus::up("database", "reachable"),
+        ComponentStatus::up("chain", "height 3"),
+        ComponentStatus::down("wallet", "no validator signing key"),
+    ]);
+    assert_eq!(locked.status, "unavailable");
+    assert_eq!(locked.status_code(), StatusCode::SERVICE_UNAVAILABLE);
+
+    let body = serde_json::to_value(&locked).unwrap();
+    assert_eq!(body["components"][2]["name"], "wallet");
+    assert_eq!(body["components"][2]["healthy"], false);
+
+    let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+    assert!(spec["paths"]["/readyz"]["get"]["responses"].get("503").is_some());
+    assert!(spec["paths"]["/healthz"]["get"].is_object());
+
+    println!("✅ Readiness reports each component and fails with 503");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// VERSIONING TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_versioned_paths_resolve_to_routes() {
+    let route = versioning::resolve("/v1/genomes/7", None).unwrap();
+    assert_eq!((route.version, route.path.as_str(), route.successor), (Some(1), "/api/genomes/7", None));
+    // Routes outside /api keep their path
+    assert_eq!(versioning::resolve("/v1/auth/token", None).unwrap().path, "/auth/token");
+    assert_eq!(versioning::resolve("/v1/ws/evolution/3", None).unwrap().path, "/ws/evolution/3");
+    assert_eq!(versioning::resolve("/v1/auth/login", None).unwrap().path, "/api/auth/login");
+
+    // Unversioned calls are deprecated unless they name a version
+    let legacy = versioning::resolve("/api/poc/status", None).unwrap();
+    assert_eq!(legacy.successor.as_deref(), Some("/v1/poc/status"));
+    assert_eq!(versioning::resolve("/graphql", None).unwrap().successor.as_deref(), Some("/v1/graphql"));
+    assert_eq!(versioning::resolve("/api/poc/status", Some("1")).unwrap().successor, None);
+    assert_eq!(versioning::versioned_path("/api/genomes", 2), "/v2/genomes");
+
+    // Probes and docs are not versioned
+    for path in ["/healthz", "/readyz", "/metrics", "/openapi.json", "/docs/index.html", "/"] {
+        assert_eq!(versioning::resolve(path, None).unwrap().version, None, "{}", path);
+    }
+
+    assert!(versioning::resolve("/v2/genomes", None).is_err());
+    assert!(versioning::resolve("/api/genomes", Some("2")).is_err());
+    assert!(versioning::resolve("/api/genomes", Some("latest")).is_err());
+
+    println!("✅ Versioning: /v1 paths resolved, unversioned calls deprecated");
+}
+
+#[tokio::test]
+async fn test_versioning_layer_rewrites_and_signals_deprecation() {
+    let app = api::with_versioning(Router::new().route(
+        "/api/genomes/:id",
+        get(|Extension(ApiVersion(version)): Extension<ApiVersion>| async move { format!("v{}", version) }),
+    ));
+    let call = |uri: &str, header: Option<&str>| {
+        let mut request = Request::builder().uri(uri);
+        if let Some(version) = header {
+            request = request.header(versioning::VERSION_HEADER, version);
+        }
+        app.clone().oneshot(request.body(Body::empty()).unwrap())
+    };
+
+    let versioned = call("/v1/genomes/7?fields=all", None).await.unwrap();
+    assert_eq!(versioned.status(), StatusCode::OK);
+    assert_eq!(versioned.headers()["api-version"], "1");
+    assert!(versioned.headers().get("deprecation").is_none());
+
+    let legacy = call("/api/genomes/7", None).await.unwrap();
+    assert_eq!(legacy.status(), StatusCode::OK);
+    assert_eq!(legacy.headers()["deprecation"], "true");
+    assert_eq!(legacy.headers()["link"], "</v1/genomes/7>; rel=\"successor-version\"");
+
+    let pinned = call("/api/genomes/7", Some("1")).await.unwrap();
+    assert!(pinned.headers().get("deprecation").is_none());
+
+    assert_eq!(call("/v2/genomes/7", None).await.unwrap().status(), StatusCode::BAD_REQUEST);
+
+    println!("✅ Versioning layer: /v1 served, legacy paths flagged, v2 refused");
+}
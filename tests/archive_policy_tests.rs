@@ -0,0 +1,99 @@
+//! Archive Policy Tests for Divine AGI V16 (default thresholds, custom routing)
+
+use std::collections::HashMap;
+
+use divine_agi::archive_policy::*;
+use divine_agi::genome::GenomeBuilder;
+use divine_agi::multi_chain::{ArchiveBudget, BlockchainLayer, MultiChainArchiver, MC_LAYER_SOURCE};
+use divine_agi::oracle::Pair;
+
+/// Routes every archive to the cheapest layer in USD, never Lightning
+struct CheapestPolicy;
+
+impl ArchivePolicy for CheapestPolicy {
+    fn name(&self) -> &str {
+        "cheapest"
+    }
+
+    fn rank(&self, request: &ArchiveRequest) -> Vec<(BlockchainLayer, f64)> {
+        let mut ranking: Vec<(BlockchainLayer, f64)> = request.layers.iter()
+            .filter(|option| option.layer != BlockchainLayer::Lightning)
+            .map(|option| (option.layer, -option.cost.usd.unwrap_or(f64::MAX)))
+            .collect();
+        ranking.sort_by(|a, b| b.1.total_cmp(&a.1));
+        ranking
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════
+// THRESHOLD POLICY TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_threshold_preferences() {
+    assert_eq!(ThresholdPolicy::preferences(700, 1.6)[0], BlockchainLayer::Lightning);
+    assert_eq!(ThresholdPolicy::preferences(950, 0.5)[0], BlockchainLayer::Bitcoin);
+    assert_eq!(ThresholdPolicy::preferences(500, 1.6)[0], BlockchainLayer::Solana);
+    assert_eq!(ThresholdPolicy::preferences(950, 1.0)[0], BlockchainLayer::Ethereum);
+    let preferences = ThresholdPolicy::preferences(950, 0.5);
+    assert_eq!(preferences, vec![BlockchainLayer::Bitcoin, BlockchainLayer::Lightning, BlockchainLayer::Solana, BlockchainLayer::Ethereum]);
+
+    println!("✅ Policy: T/G and consciousness thresholds pick the genome's own layer");
+}
+
+#[tokio::test]
+async fn test_default_policy_ranks_from_the_request() {
+    let mut archiver = MultiChainArchiver::new();
+    assert_eq!(archiver.policy.name(), "threshold");
+    let genome = GenomeBuilder::random().build_storage();
+    let request = archiver.archive_request(&genome);
+    assert_eq!(request.layers.iter().map(|option| option.layer).collect::<Vec<_>>(), BlockchainLayer::ALL.to_vec());
+    assert_eq!((request.consciousness, request.tg_ratio), (genome.consciousness, genome.rna_signal()));
+    assert!(request.layers.iter().all(|option| option.within_budget() && option.cost.payload_bytes > 0));
+
+    let own = ThresholdPolicy::preferences(request.consciousness, request.tg_ratio)[0];
+    let ranking = ThresholdPolicy::default().rank(&request);
+    assert_eq!((ranking[0].0, ranking[0].1), (own, LAYER_PREFERENCE_WEIGHTS[0] * request.layer(own).unwrap().probability));
+    assert_eq!(archiver.layer_ranking(&genome), ranking);
+
+    // Learned failures move the genome on, as the request carries them
+    for _ in 0..2 {
+        archiver.mission_control.report_failure(MC_LAYER_SOURCE, own.key(), "rpc down");
+    }
+    let request = archiver.archive_request(&genome);
+    assert_ne!(ThresholdPolicy::default().select(&request), Some(own));
+    assert_eq!(Some(archiver.select_layer(&genome)), ThresholdPolicy::default().select(&request));
+
+    println!("✅ Policy: the default ranks preference × learned probability");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// CUSTOM POLICY TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_custom_policy_routes_archives() {
+    let mut archiver = MultiChainArchiver::new();
+    archiver.policy = Box::new(CheapestPolicy);
+    archiver.reference_prices = HashMap::from([(Pair::BtcUsd, 60_000.0), (Pair::EthUsd, 3_000.0), (Pair::SolUsd, 150.0)]);
+    let mut genome = GenomeBuilder::random().build_storage();
+    genome.db_id = Some(41);
+
+    let request = archiver.archive_request(&genome);
+    let cheapest = request.layers.iter()
+        .filter(|option| option.layer != BlockchainLayer::Lightning)
+        .min_by(|a, b| a.cost.usd.unwrap().total_cmp(&b.cost.usd.unwrap()))
+        .unwrap();
+    assert_eq!(archiver.select_layer(&genome), cheapest.layer);
+    assert!(archiver.layer_ranking(&genome).iter().all(|(layer, _)| *layer != BlockchainLayer::Lightning));
+    assert_eq!(archiver.archive(&genome).await.unwrap().layer, cheapest.layer);
+
+    // Over budget everywhere: the policy's top is still where archiving starts
+    archiver.budget = ArchiveBudget { per_genome_usd: Some(cheapest.cost.usd.unwrap() / 2.0), daily_usd: None };
+    let request = archiver.archive_request(&genome);
+    assert!(request.layers.iter().filter(|option| option.cost.usd.is_some()).all(|option| !option.within_budget()));
+    assert_eq!(archiver.select_layer(&genome), cheapest.layer);
+    assert!(archiver.archive(&genome).await.unwrap_err().starts_with("Every layer failed"));
+
+    println!("✅ Policy: a deployment's own policy routes archives and failover");
+}
@@ -0,0 +1,422 @@
+//! Archive Tests for Divine AGI V16 (multi-chain batches)
+
+use std::collections::HashMap;
+
+use divine_agi::genome::{hash_genome_dna, GenomeBuilder};
+use divine_agi::oracle::Pair;
+use divine_agi::swarm::Swarm;
+use divine_agi::multi_chain::{decode_op_return, embedded_hash, op_return_script, ArchiveBatchItem, ArchiveBatches, ArchiveCost, ArchiveItemStatus, BlockchainLayer, ChainArchiveEntry, MissionControl, MissionControlPair, MultiChainArchiver, SimulatedFailures, BITCOIN_FEE_RATE_SAT_VB, BITCOIN_OP_RETURN_MAX_BYTES, BITCOIN_TX_BASE_VBYTES, MAX_FINISHED_BATCHES, MC_APRIORI_PROBABILITY, MC_LAYER_SOURCE, MC_MAX_PROBABILITY, MC_MIN_PROBABILITY, DEFAULT_ARCHIVE_ATTEMPTS, DEFAULT_RETRY_DELAY_MS, MAX_RETRY_DELAY_MS, LayerFailure, RetryPolicy, ArchiveBudget};
+
+// ═══════════════════════════════════════════════════════════════
+// ARCHIVE BATCH TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_batch_tracks_each_genome() {
+    let mut archiver = MultiChainArchiver::new();
+    let mut batches = ArchiveBatches::new();
+
+    let mut genomes = Vec::new();
+    for id in 1..=3 {
+        let mut genome = GenomeBuilder::random().build_storage();
+        genome.db_id = Some(id);
+        genomes.push(genome);
+    }
+    let mut items: Vec<ArchiveBatchItem> = genomes.iter()
+        .map(|g| ArchiveBatchItem::pending(g.db_id.unwrap(), archiver.select_layer(g)))
+        .collect();
+    items.push(ArchiveBatchItem::failed(99, "Genome not found".into()));
+
+    let batch = batches.create(items);
+    assert_eq!((batch.archived, batch.failed, batch.finished), (0, 1, false));
+
+    for genome in &genomes {
+        let planned = batches.get(batch.id).unwrap().items.iter().find(|i| i.genome_id == genome.db_id.unwrap()).unwrap().layer;
+        let result = archiver.archive_to(genome, planned.unwrap()).await;
+        if let Ok(entry) = &result {
+            assert_eq!(Some(entry.layer), planned, "layer assigned up front is the one used");
+        }
+        batches.complete(batch.id, genome.db_id.unwrap(), result.as_ref().map_err(Clone::clone));
+    }
+
+    let done = batches.get(batch.id).unwrap().clone();
+    assert!(done.finished);
+    assert_eq!(done.archived + done.failed, 4);
+    for item in done.items.iter().filter(|i| i.status == ArchiveItemStatus::Archived) {
+        assert!(item.tx_hash.is_some());
+    }
+
+    // Completing again changes nothing
+    batches.complete(batch.id, 1, Err("late".into()));
+    assert_eq!(batches.get(batch.id).unwrap().archived + batches.get(batch.id).unwrap().failed, 4);
+
+    println!("✅ Archive batch: {} archived, {} failed", done.archived, done.failed);
+}
+
+#[test]
+fn test_finished_batches_pruned() {
+    let mut batches = ArchiveBatches::new();
+
+    // Batches of unknown genomes finish immediately
+    let first = batches.create(vec![ArchiveBatchItem::failed(1, "missing".into())]);
+    assert!(first.finished);
+    for _ in 1..MAX_FINISHED_BATCHES {
+        batches.create(vec![ArchiveBatchItem::failed(1, "missing".into())]);
+    }
+    assert!(batches.get(first.id).is_some());
+
+    let newest = batches.create(vec![ArchiveBatchItem::failed(1, "missing".into())]);
+    assert!(batches.get(first.id).is_none(), "oldest finished batch dropped");
+    assert!(batches.get(newest.id).is_some());
+
+    println!("✅ Finished archive batches capped at {}", MAX_FINISHED_BATCHES);
+}
+
+// ═══════════════════════════════════════════════════════════════
+// ARCHIVE LOOKUP & VERIFICATION TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_archive_entries_stats_and_verification() {
+    let mut archiver = MultiChainArchiver::new();
+    archiver.simulated_failures = SimulatedFailures::Never;
+    let mut genome = GenomeBuilder::random().build_storage();
+    genome.db_id = Some(5);
+    let mut other = GenomeBuilder::random().build_storage();
+    other.db_id = Some(6);
+
+    let mut archived = Vec::new();
+    for target in [&genome, &other, &genome] {
+        archived.push(archiver.archive(target).await.unwrap());
+    }
+    let ids: Vec<u64> = archiver.archives.iter().map(|e| e.id).collect();
+    assert_eq!(ids, (1..=archiver.archives.len() as u64).collect::<Vec<_>>(), "ids are log positions");
+    assert_eq!(archiver.entry(0).map(|e| e.id), None);
+
+    let mine = archiver.entries_for_genome(5);
+    assert_eq!(mine.len(), 2);
+    assert!(mine.iter().all(|e| e.genome_id == 5) && mine[0].id < mine[1].id);
+
+    let stats = archiver.layer_stats();
+    assert_eq!(stats.len(), BlockchainLayer::ALL.len() + BlockchainLayer::STORAGE.len());
+    assert_eq!(stats.iter().map(|s| s.archives).sum::<usize>(), archiver.archives.len());
+    let used = stats.iter().find(|s| s.layer == archived[0].layer).unwrap();
+    assert!(used.genomes >= 1 && used.last_archived_at.is_some() && used.avg_consciousness > 0.0);
+    assert!(stats.iter().filter(|s| s.archives == 0).all(|s| s.last_archived_at.is_none() && s.avg_tg_ratio == 0.0));
+
+    let (id, archived_hash) = (mine[0].id, mine[0].dna_hash.clone());
+    let dna = genome.to_dna_string();
+    let check = archiver.verify(id, Some(&dna)).await.unwrap();
+    assert!(check.verified && check.dna_hash_valid && check.tx_reference_valid && check.chain_hash_matches);
+    assert_eq!((check.chain_hash.as_deref(), check.current_dna_matches), (Some(archived_hash.as_str()), Some(true)));
+    assert_eq!((check.genome_id, check.genome_unchanged), (5, Some(true)));
+    // Edited or deleted genomes are reported, the entry stays valid
+    assert_eq!(archiver.verify(id, Some("ACGT")).await.unwrap().genome_unchanged, Some(false));
+    assert_eq!(archiver.verify(id, None).await.unwrap().genome_unchanged, None);
+
+    // Tampered records fail
+    archiver.archives[id as usize - 1].dna_hash = "00".repeat(32);
+    archiver.archives[id as usize - 1].tx_hash = Some("0xnot-a-tx".into());
+    let tampered = archiver.verify(id, Some(&dna)).await.unwrap();
+    assert!(!tampered.verified && !tampered.dna_hash_valid && !tampered.tx_reference_valid);
+    assert!(!tampered.chain_hash_matches && tampered.chain_hash.is_none() && tampered.chain_error.unwrap().contains("not found"));
+    assert!(archiver.verify(99, None).await.is_err());
+
+    println!("✅ Archive entries: looked up by genome, counted per layer, verified");
+}
+
+#[tokio::test]
+async fn test_archives_read_back_from_chain() {
+    let mut archiver = MultiChainArchiver::new();
+    archiver.simulated_failures = SimulatedFailures::Never;
+    let mut genome = GenomeBuilder::random().build_storage();
+    genome.db_id = Some(8);
+    let dna_hash = hash_genome_dna(&genome.to_dna_string());
+
+    // Every layer embeds the hash in its own way; simulated ones read back the same
+    for layer in BlockchainLayer::ALL {
+        let entry = archiver.archive_to(&genome, layer).await.unwrap();
+        let tx = entry.tx_hash.clone().unwrap();
+        assert_eq!(archiver.restore_hash_from_chain(&tx, layer).await, Ok(dna_hash), "{:?}", layer);
+
+        let check = archiver.verify(entry.id, Some("ACGT")).await.unwrap();
+        assert!(check.verified && check.chain_hash_matches, "{:?}", layer);
+        assert_eq!(check.current_dna_matches, Some(false), "the genome changed since");
+    }
+    assert!(!archiver.mock_chain.is_empty());
+
+    // Read as the wrong layer, or unknown
+    let bitcoin = archiver.archives.iter().find(|e| e.layer == BlockchainLayer::Bitcoin).and_then(|e| e.tx_hash.clone()).unwrap();
+    assert!(archiver.restore_hash_from_chain(&bitcoin, BlockchainLayer::Solana).await.unwrap_err().contains("carries no genome archive"));
+    assert!(archiver.restore_hash_from_chain(&format!("0x{}", "ab".repeat(32)), BlockchainLayer::Ethereum).await.unwrap_err().contains("not found"));
+
+    // Keysends of one archive must agree
+    archiver.mock_chain.record("ln_aa", vec![1; 32]);
+    archiver.mock_chain.record("ln_bb", vec![2; 32]);
+    assert!(archiver.restore_hash_from_chain("ln_aa,ln_bb", BlockchainLayer::Lightning).await.unwrap_err().contains("different"));
+    assert_eq!(archiver.restore_hash_from_chain("ln_aa", BlockchainLayer::Lightning).await, Ok([1; 32]));
+
+    let script = op_return_script(dna_hash);
+    assert_eq!((script[0], script[1] as usize, script.len()), (0x6a, 38, 40));
+    assert!(script.len() <= BITCOIN_OP_RETURN_MAX_BYTES);
+    assert_eq!(decode_op_return(&script), Some(dna_hash));
+    assert_eq!(decode_op_return(&script[..39]), None);
+    assert_eq!(embedded_hash(BlockchainLayer::Bitcoin, &script), Some(dna_hash));
+
+    println!("✅ Archive read-back: every layer's transaction gives back the genome hash");
+}
+
+#[test]
+fn test_tx_reference_formats() {
+    let hex32 = "ab".repeat(16);
+    let hex64 = "cd".repeat(32);
+    assert!(BlockchainLayer::Lightning.valid_tx_reference(&format!("ln_{}", hex32)));
+    assert!(BlockchainLayer::Lightning.valid_tx_reference(&format!("ln_{},ln_{}", hex32, hex32)));
+    assert!(!BlockchainLayer::Lightning.valid_tx_reference(&format!("0x{}", hex64)));
+    for layer in [BlockchainLayer::Solana, BlockchainLayer::Ethereum, BlockchainLayer::Bitcoin] {
+        assert!(layer.valid_tx_reference(&format!("0x{}", hex64)));
+        assert!(!layer.valid_tx_reference(&format!("0x{}", hex32)));
+        assert!(!layer.valid_tx_reference(&format!("ln_{}", hex32)));
+    }
+
+    println!("✅ Tx references checked per layer");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// LAYER OVERRIDE & COST TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_forced_layer_and_names() {
+    let mut archiver = MultiChainArchiver::new();
+    let mut genome = GenomeBuilder::random().build_storage();
+    genome.db_id = Some(42);
+    let forced = if archiver.select_layer(&genome) == BlockchainLayer::Bitcoin { BlockchainLayer::Solana } else { BlockchainLayer::Bitcoin };
+
+    let entry = archiver.archive_to(&genome, forced).await.unwrap();
+    assert_eq!((entry.layer, entry.genome_id), (forced, 42));
+    assert!(forced.valid_tx_reference(entry.tx_hash.as_deref().unwrap()));
+
+    assert_eq!(BlockchainLayer::from_name("bitcoin"), Ok(BlockchainLayer::Bitcoin));
+    assert_eq!(BlockchainLayer::from_name(" Lightning "), Ok(BlockchainLayer::Lightning));
+    assert_eq!(BlockchainLayer::from_name("ETH"), Ok(BlockchainLayer::Ethereum));
+    assert!(BlockchainLayer::from_name("dogecoin").unwrap_err().contains("expected lightning"));
+
+    println!("✅ Archive: layer forced by name");
+}
+
+#[test]
+fn test_archive_cost_estimates() {
+    let archiver = MultiChainArchiver::new();
+    let genome = GenomeBuilder::random().build_storage();
+
+    // Past the OP_RETURN limit the transaction stops growing
+    let small = archiver.estimate_cost(BlockchainLayer::Bitcoin, 40);
+    let big = archiver.estimate_cost(BlockchainLayer::Bitcoin, 1_000);
+    assert_eq!(small, ArchiveCost { layer: BlockchainLayer::Bitcoin, payload_bytes: 40, amount: (BITCOIN_TX_BASE_VBYTES + 40) as f64 * BITCOIN_FEE_RATE_SAT_VB, unit: "sat".into(), usd: None, rsm: None });
+    assert_eq!(big.amount, (BITCOIN_TX_BASE_VBYTES + BITCOIN_OP_RETURN_MAX_BYTES) as f64 * BITCOIN_FEE_RATE_SAT_VB);
+
+    // Calldata is paid per byte
+    let eth = archiver.estimate_cost(BlockchainLayer::Ethereum, 100);
+    assert!(archiver.estimate_cost(BlockchainLayer::Ethereum, 200).amount > eth.amount);
+    assert_eq!(eth.unit, "gwei");
+    assert_eq!(archiver.estimate_cost(BlockchainLayer::Solana, 100).unit, "lamports");
+    assert!(archiver.estimate_cost(BlockchainLayer::Lightning, 100).amount > 0.0);
+
+    // The keysend record carries more than the DNA
+    let dna = genome.to_dna_string().len();
+    assert_eq!(archiver.payload_bytes(&genome, BlockchainLayer::Bitcoin), dna);
+    assert!(archiver.payload_bytes(&genome, BlockchainLayer::Lightning) > dna);
+
+    println!("✅ Archive: costs estimated per layer");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// MISSION CONTROL LEARNING TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_mission_control_learns_from_outcomes() {
+    let half_life = 100;
+    let mut pair = MissionControlPair::new("02aaaa", "03bbbb");
+    assert_eq!(pair.probability, MC_APRIORI_PROBABILITY);
+
+    // The apriori counts as two observations, so one failure is no verdict
+    pair.report_failure("no route", half_life, 0);
+    assert!((pair.probability - 1.0 / 3.0).abs() < 1e-9);
+    pair.report_failure("no route", half_life, 0);
+    assert!((pair.probability - 0.25).abs() < 1e-9);
+    assert_eq!((pair.failure_count, pair.last_failure_reason.as_deref()), (2, Some("no route")));
+
+    // Outcomes decay with the half-life, back toward the apriori
+    assert!((pair.probability_at(half_life, half_life) - 1.0 / 3.0).abs() < 1e-9, "two failures weigh one");
+    assert!((pair.probability_at(half_life, 100 * half_life) - MC_APRIORI_PROBABILITY).abs() < 1e-6);
+    pair.report_success(half_life, half_life);
+    assert!((pair.probability - 2.0 / 4.0).abs() < 1e-9, "(1 + 1) / (2 + 1 + 1)");
+
+    // Bounded however lopsided the history
+    for _ in 0..1_000 {
+        pair.report_success(half_life, half_life);
+    }
+    assert_eq!(pair.probability, MC_MAX_PROBABILITY);
+    let mut hopeless = MissionControlPair::new("02aaaa", "03cccc");
+    for _ in 0..1_000 {
+        hopeless.report_failure("timeout", half_life, 0);
+    }
+    assert_eq!(hopeless.probability, MC_MIN_PROBABILITY);
+
+    // Names shorter than the log prefix are fine; unknown pairs are the apriori
+    let mut mc = MissionControl::new();
+    mc.report_failure(MC_LAYER_SOURCE, "sol", "rpc down");
+    assert!(mc.probability(MC_LAYER_SOURCE, "sol") < MC_APRIORI_PROBABILITY);
+    assert_eq!(mc.probability(MC_LAYER_SOURCE, "btc"), MC_APRIORI_PROBABILITY);
+
+    println!("✅ Mission Control: bounded, apriori-weighted probabilities learned from outcomes");
+}
+
+#[tokio::test]
+async fn test_select_layer_consults_learned_probabilities() {
+    let mut archiver = MultiChainArchiver::new();
+    let genome = GenomeBuilder::random().build_storage();
+    let preferences = MultiChainArchiver::layer_preferences(&genome);
+    assert_eq!(preferences.len(), BlockchainLayer::ALL.len());
+    assert_eq!(archiver.select_layer(&genome), preferences[0], "nothing learned: the genome's own layer");
+
+    // One failure is tolerated, two move the genome to its first fallback
+    let own = preferences[0].key();
+    archiver.mission_control.report_failure(MC_LAYER_SOURCE, own, "rpc down");
+    assert_eq!(archiver.select_layer(&genome), preferences[0]);
+    archiver.mission_control.report_failure(MC_LAYER_SOURCE, own, "rpc down");
+    assert_eq!(archiver.select_layer(&genome), preferences[1]);
+    assert!(archiver.layer_probability(preferences[0]) < archiver.layer_probability(preferences[1]));
+
+    // Successes win it back
+    for _ in 0..3 {
+        archiver.mission_control.report_success(MC_LAYER_SOURCE, own);
+    }
+    assert_eq!(archiver.select_layer(&genome), preferences[0]);
+
+    // Archives report their own outcomes per layer
+    archiver.mission_control = MissionControl::new();
+    archiver.archive_to(&genome, BlockchainLayer::Solana).await.unwrap();
+    let stats = archiver.mission_control_stats();
+    let solana = stats.layers.iter().find(|l| l.layer == BlockchainLayer::Solana).unwrap();
+    assert_eq!((solana.successes, solana.failures), (1, 0));
+    assert!(solana.probability > MC_APRIORI_PROBABILITY);
+    assert_eq!(stats.layers.len(), BlockchainLayer::ALL.len() + BlockchainLayer::STORAGE.len());
+
+    println!("✅ Mission Control: layer selection weighs learned success probabilities");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// RETRY & FAILOVER TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_retry_backoff_doubles_and_caps() {
+    let policy = RetryPolicy::default();
+    assert_eq!((policy.attempts, policy.base_delay_ms), (DEFAULT_ARCHIVE_ATTEMPTS, DEFAULT_RETRY_DELAY_MS));
+    let delays: Vec<u64> = (1..=6).map(|retry| policy.delay(retry).as_millis() as u64).collect();
+    assert_eq!(delays, [500, 1_000, 2_000, 4_000, 8_000, MAX_RETRY_DELAY_MS]);
+    assert_eq!(policy.delay(200).as_millis() as u64, MAX_RETRY_DELAY_MS, "no overflow however many retries");
+    assert!(RetryPolicy { attempts: 0, ..policy }.validate().is_err());
+    assert!(RetryPolicy { attempts: 1, base_delay_ms: 0 }.delay(3).is_zero());
+
+    println!("✅ Archive: retry backoff doubles up to its cap");
+}
+
+#[tokio::test]
+async fn test_failed_layers_retried_then_failed_over() {
+    let mut archiver = MultiChainArchiver::new();
+    archiver.retry = RetryPolicy { attempts: 2, base_delay_ms: 0 };
+    // The only swarm node is known to be unreachable, so every keysend is skipped
+    let (own, dead) = (archiver.own_pubkey.clone(), "02deaddeaddeaddead".to_string());
+    archiver.swarm = Swarm::from_pubkeys([dead.clone()], 0);
+    for _ in 0..10 {
+        archiver.mission_control.report_failure(&own, &dead, "no route");
+    }
+    let mut genome = GenomeBuilder::random().build_storage();
+    genome.db_id = Some(12);
+
+    // A forced layer retries but stays put
+    assert_eq!(archiver.archive_to(&genome, BlockchainLayer::Lightning).await.unwrap_err(), "All keysend failed");
+    let stats = archiver.mission_control_stats();
+    let lightning = stats.layers.iter().find(|l| l.layer == BlockchainLayer::Lightning).unwrap();
+    assert_eq!((lightning.failures, lightning.last_failure_reason.as_deref()), (2, Some("All keysend failed")), "every attempt reported");
+    assert!(archiver.archives.is_empty());
+
+    // Otherwise it moves on to the next layer in the genome's ranking
+    let next = archiver.layer_ranking(&genome).into_iter().map(|(layer, _)| layer).find(|layer| *layer != BlockchainLayer::Lightning).unwrap();
+    let entry = archiver.archive_from(&genome, BlockchainLayer::Lightning).await.unwrap();
+    assert_eq!((entry.layer, entry.attempts), (next, 1));
+    assert_eq!(entry.fallbacks, vec![LayerFailure { layer: BlockchainLayer::Lightning, attempts: 2, error: "All keysend failed".into() }]);
+    assert!(entry.layer.valid_tx_reference(entry.tx_hash.as_deref().unwrap()));
+    assert_eq!(archiver.archives.len(), 1);
+
+    // Learned: the genome's own layer is no longer selected for it
+    assert_ne!(archiver.select_layer(&genome), BlockchainLayer::Lightning);
+    assert!(archiver.layer_probability(BlockchainLayer::Lightning) < MC_APRIORI_PROBABILITY);
+
+    // The chain serializes only when there was one; older entries read as one attempt
+    let json = serde_json::to_value(&entry).unwrap();
+    assert_eq!(json["fallbacks"][0]["layer"], "Lightning");
+    let plain = archiver.archive_to(&genome, BlockchainLayer::Ethereum).await.unwrap();
+    let mut old = serde_json::to_value(&plain).unwrap();
+    assert!(old.get("fallbacks").is_none());
+    old.as_object_mut().unwrap().remove("attempts");
+    assert_eq!(serde_json::from_value::<ChainArchiveEntry>(old).unwrap().attempts, 1);
+
+    println!("✅ Archive: failed layers retried, then failed over with the chain recorded");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// BUDGET TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_budget_keeps_archives_affordable() {
+    let mut archiver = MultiChainArchiver::new();
+    archiver.swarm = Swarm::from_pubkeys((0..10).map(|i| format!("02{:016x}", i)), 0);
+    archiver.reference_prices = HashMap::from([(Pair::BtcUsd, 60_000.0), (Pair::EthUsd, 3_000.0), (Pair::SolUsd, 150.0), (Pair::RsmUsd, 2.0)]);
+    let mut genome = GenomeBuilder::random().build_storage();
+    genome.db_id = Some(21);
+    let usd = |archiver: &MultiChainArchiver, layer| archiver.estimate_cost(layer, archiver.payload_bytes(&genome, layer)).usd.unwrap();
+
+    let solana = archiver.estimate_cost(BlockchainLayer::Solana, 100);
+    assert!((solana.rsm.unwrap() - solana.usd.unwrap() / 2.0).abs() < 1e-12, "USD → RSM at the RSM/USD quote");
+    let mut by_cost: Vec<(BlockchainLayer, f64)> = BlockchainLayer::ALL.into_iter().map(|layer| (layer, usd(&archiver, layer))).collect();
+    by_cost.sort_by(|a, b| a.1.total_cmp(&b.1));
+    assert_eq!(by_cost[0].0, BlockchainLayer::Solana, "a memo is the cheapest archive");
+    assert_eq!(by_cost[3].0, BlockchainLayer::Ethereum);
+
+    // A per-genome cap below every other layer leaves only Solana
+    archiver.budget = ArchiveBudget { per_genome_usd: Some((by_cost[0].1 + by_cost[1].1) / 2.0), daily_usd: None };
+    assert_eq!(archiver.select_layer(&genome), BlockchainLayer::Solana);
+    assert!(archiver.archive_to(&genome, BlockchainLayer::Bitcoin).await.unwrap_err().contains("per-genome budget"), "a forced layer fails");
+    assert!(archiver.archives.is_empty());
+    let entry = archiver.archive_from(&genome, BlockchainLayer::Bitcoin).await.unwrap();
+    assert_eq!((entry.layer, entry.cost_usd), (BlockchainLayer::Solana, Some(by_cost[0].1)));
+    assert_eq!(entry.fallbacks[0].layer, BlockchainLayer::Bitcoin);
+    assert!(entry.fallbacks.iter().all(|f| f.attempts == 0 && f.error.contains("per-genome budget")), "passed over untried");
+
+    // A daily cap with room for one more memo
+    let now = chrono::Utc::now().timestamp();
+    archiver.budget = ArchiveBudget { per_genome_usd: None, daily_usd: Some(by_cost[0].1 * 2.5) };
+    assert_eq!(archiver.archive(&genome).await.unwrap().layer, BlockchainLayer::Solana);
+    let err = archiver.archive(&genome).await.unwrap_err();
+    assert!(err.starts_with("Every layer failed") && err.contains("left of today's"), "{}", err);
+    let status = archiver.budget_status(now);
+    assert!((status.spent_today_usd - 2.0 * by_cost[0].1).abs() < 1e-12);
+    assert!((status.remaining_today_usd.unwrap() - by_cost[0].1 / 2.0).abs() < 1e-12);
+    assert_eq!(status.day_start % 86_400, 0);
+    assert_eq!(archiver.spend.on(now + 86_400), 0.0, "a new day, a new budget");
+
+    // Unpriced layers are not held to the budget
+    archiver.reference_prices.clear();
+    assert_eq!(archiver.check_budget(&genome, BlockchainLayer::Bitcoin, now), Ok(None));
+    assert!(ArchiveBudget { per_genome_usd: Some(0.0), daily_usd: None }.validate().is_err());
+    assert!(ArchiveBudget { per_genome_usd: None, daily_usd: Some(f64::NAN) }.validate().is_err());
+    assert!(ArchiveBudget::default().validate().is_ok());
+
+    println!("✅ Archive: layer selection and failover stay within the fee budget");
+}
@@ -0,0 +1,99 @@
+//! Auction Tests for Divine AGI V16 (bidding rules, anti-sniping, closing)
+
+use divine_agi::auction::{check_duration, escrow_address, Auction, AuctionStatus, Outbid, MAX_DURATION_SECS, MIN_DURATION_SECS, SNIPE_WINDOW_SECS};
+
+const START: i64 = 1_700_000_000;
+
+fn auction(duration_secs: i64) -> Auction {
+    Auction {
+        id: 7,
+        genome_id: 42,
+        seller: "divine_seller".into(),
+        reserve_rsm: 100.0,
+        high_bid_rsm: None,
+        high_bidder: None,
+        bids: 0,
+        status: AuctionStatus::Open,
+        ends_at: START + duration_secs,
+        created_at: START,
+        updated_at: START,
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════
+// BIDDING TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_bids_meet_reserve_then_raise() {
+    let mut a = auction(3600);
+    assert_eq!(a.min_bid(), 100.0, "the reserve opens the bidding");
+    assert!(a.bid("divine_alice", 99.0, START + 10).is_err());
+    assert!(a.bid("divine_seller", 150.0, START + 10).is_err(), "no bidding on your own genome");
+    assert_eq!(a.bid("divine_alice", 100.0, START + 10).unwrap(), None);
+
+    assert!((a.min_bid() - 105.0).abs() < 1e-9);
+    assert!(a.bid("divine_bob", 104.0, START + 20).is_err(), "5% raise needed");
+    assert!(a.bid("divine_alice", 120.0, START + 20).is_err(), "already the high bidder");
+    let outbid = a.bid("divine_bob", 105.0, START + 20).unwrap();
+    assert_eq!(outbid, Some(Outbid { bidder: "divine_alice".into(), amount_rsm: 100.0 }), "alice is refunded");
+    assert_eq!((a.high_bidder.as_deref(), a.high_bid_rsm, a.bids), (Some("divine_bob"), Some(105.0), 2));
+    assert_eq!(a.ends_at, START + 3600, "early bids leave the end alone");
+
+    assert!(a.bid("divine_carol", f64::NAN, START + 30).is_err());
+    assert!(a.bid("divine_carol", 500.0, START + 3600).is_err(), "over once the end is reached");
+    assert_eq!(escrow_address(a.id), "auction_escrow_7");
+
+    println!("✅ Auctions: reserve, 5% raises and outbid refunds");
+}
+
+#[test]
+fn test_late_bids_extend_the_auction() {
+    let mut a = auction(600);
+    let end = START + 600;
+
+    // A bid 1 minute before the end pushes the end to 5 minutes after it
+    a.bid("divine_alice", 100.0, end - 60).unwrap();
+    assert_eq!(a.ends_at, end - 60 + SNIPE_WINDOW_SECS);
+
+    // ...and so on for every last-minute bid
+    let end = a.ends_at;
+    a.bid("divine_bob", 110.0, end - 1).unwrap();
+    assert_eq!(a.ends_at, end - 1 + SNIPE_WINDOW_SECS);
+    assert!(!a.is_over(end + 1), "the sniper's bid leaves time to answer");
+
+    // A bid right at the window's edge changes nothing
+    let end = a.ends_at;
+    a.bid("divine_alice", 200.0, end - SNIPE_WINDOW_SECS).unwrap();
+    assert_eq!(a.ends_at, end);
+
+    println!("✅ Auctions: anti-sniping extensions");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// CLOSING TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_closing_settles_or_leaves_unsold() {
+    let mut unsold = auction(600);
+    assert!(unsold.close(START + 599).is_err(), "still running");
+    unsold.close(START + 600).unwrap();
+    assert_eq!(unsold.status, AuctionStatus::Unsold);
+    assert!(unsold.close(START + 700).is_err(), "closed once");
+    assert!(unsold.bid("divine_alice", 100.0, START + 700).is_err());
+
+    let mut sold = auction(600);
+    sold.bid("divine_alice", 100.0, START + 10).unwrap();
+    sold.close(START + 600).unwrap();
+    assert_eq!((sold.status, sold.updated_at), (AuctionStatus::Settled, START + 600));
+
+    assert!(check_duration(MIN_DURATION_SECS).is_ok() && check_duration(MAX_DURATION_SECS).is_ok());
+    assert!(check_duration(MIN_DURATION_SECS - 1).is_err() && check_duration(MAX_DURATION_SECS + 1).is_err());
+    for status in [AuctionStatus::Open, AuctionStatus::Settled, AuctionStatus::Unsold, AuctionStatus::Cancelled] {
+        assert_eq!(AuctionStatus::from_name(status.name()), Some(status));
+        assert_eq!(serde_json::to_value(status).unwrap(), status.name());
+    }
+
+    println!("✅ Auctions: settled with a bid, unsold without");
+}
@@ -0,0 +1,181 @@
+//! Auth Tests for Divine AGI V16 (API keys, JWTs, roles)
+
+use axum::http::Method;
+use divine_agi::api::required_role;
+use divine_agi::auth::{AdminAuditEntry, ApiKeyRecord, AuthManager, Role, JWT_TTL_SECS};
+
+fn key_record() -> ApiKeyRecord {
+    ApiKeyRecord { id: 7, name: "explorer".into(), created_at: 0, revoked: false, role: Role::Operator }
+}
+
+// ═══════════════════════════════════════════════════════════════
+// API KEY TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_api_keys_are_random_and_hashed() {
+    let a = AuthManager::generate_api_key();
+    let b = AuthManager::generate_api_key();
+    assert_ne!(a, b);
+    assert!(a.starts_with("dak_"));
+
+    let hash = AuthManager::hash_api_key(&a);
+    assert_eq!(hash.len(), 64);
+    assert_eq!(hash, AuthManager::hash_api_key(&a));
+    assert!(!hash.contains(&a[4..]));
+
+    println!("✅ API key hash: {}…", &hash[..16]);
+}
+
+// ═══════════════════════════════════════════════════════════════
+// JWT TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_jwt_roundtrip() {
+    let auth = AuthManager::with_jwt_secret(b"test secret");
+    let (token, claims) = auth.issue_jwt(&key_record());
+    assert_eq!(token.split('.').count(), 3);
+    assert_eq!(claims.exp - claims.iat, JWT_TTL_SECS);
+
+    let verified = auth.verify_jwt(&token).unwrap();
+    assert_eq!(verified, claims);
+    assert_eq!(verified.sub, "explorer");
+    assert_eq!(verified.key_id, 7);
+
+    println!("✅ JWT verified for key #{} until {}", verified.key_id, verified.exp);
+}
+
+#[test]
+fn test_jwt_rejects_forgeries() {
+    let auth = AuthManager::with_jwt_secret(b"test secret");
+    let (token, _) = auth.issue_jwt(&key_record());
+
+    let other = AuthManager::with_jwt_secret(b"other secret");
+    assert!(other.verify_jwt(&token).is_err(), "signed with another secret");
+
+    let mut parts: Vec<&str> = token.split('.').collect();
+    let (forged, _) = other.issue_jwt(&ApiKeyRecord { id: 1, name: "admin".into(), created_at: 0, revoked: false, role: Role::Admin });
+    let forged_payload = forged.split('.').nth(1).unwrap().to_string();
+    parts[1] = &forged_payload;
+    assert!(auth.verify_jwt(&parts.join(".")).is_err(), "payload swapped");
+
+    assert!(auth.verify_jwt("not-a-token").is_err());
+    assert!(auth.verify_jwt(&format!("{}.extra", token)).is_err());
+
+    println!("✅ Forged JWTs rejected");
+}
+
+#[test]
+fn test_admin_keys_get_admin_tokens() {
+    let auth = AuthManager::with_jwt_secret(b"test secret");
+    let (_, claims) = auth.issue_jwt(&key_record());
+    assert!(!claims.has_role(Role::Admin));
+
+    let admin = ApiKeyRecord { id: 8, name: "operator".into(), created_at: 0, revoked: false, role: Role::Admin };
+    let (token, claims) = auth.issue_jwt(&admin);
+    assert!(claims.has_role(Role::Admin));
+    assert!(auth.verify_jwt(&token).unwrap().has_role(Role::Admin), "admin scope is part of the signed claims");
+
+    let entry = AdminAuditEntry::new(&claims, "mining.pause", serde_json::json!({ "paused": true }));
+    assert_eq!((entry.caller.as_str(), entry.key_id, entry.action.as_str()), ("operator", 8, "mining.pause"));
+    assert_eq!(entry.detail, r#"{"paused":true}"#);
+
+    println!("✅ Admin scope carried from API key to JWT and audit entry");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// ROLE TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_roles_cover_the_ones_below() {
+    let auth = AuthManager::with_jwt_secret(b"test secret");
+    let reader = ApiKeyRecord { role: Role::Reader, ..key_record() };
+    let (token, claims) = auth.issue_jwt(&reader);
+    assert_eq!(auth.verify_jwt(&token).unwrap().role, Role::Reader);
+    assert!(claims.has_role(Role::Reader) && !claims.has_role(Role::Operator));
+    let (_, admin) = auth.issue_jwt(&ApiKeyRecord { role: Role::Admin, ..key_record() });
+    assert!(Role::ALL.iter().all(|role| admin.has_role(*role)));
+
+    for role in Role::ALL {
+        assert_eq!(Role::from_name(role.name()), Ok(role));
+        assert_eq!(serde_json::to_value(role).unwrap(), role.name());
+    }
+    assert!(Role::from_name("root").is_err());
+
+    // Tokens from before roles existed keep working as operator tokens
+    let legacy: divine_agi::auth::JwtClaims = serde_json::from_str(r#"{"sub":"bot","key_id":3,"iat":0,"exp":1}"#).unwrap();
+    assert_eq!(legacy.role, Role::Operator);
+
+    println!("✅ Roles: reader < operator < admin, carried in the signed claims");
+}
+
+#[test]
+fn test_routes_require_roles_by_group() {
+    // Genome queries and query-only POSTs are open
+    assert_eq!(required_role(&Method::GET, "/api/genomes"), None);
+    assert_eq!(required_role(&Method::POST, "/graphql"), None);
+    assert_eq!(required_role(&Method::POST, "/api/auth/login"), None);
+    // The node wallet and webhooks need a reader token to be read
+    assert_eq!(required_role(&Method::GET, "/api/wallet/balance"), Some(Role::Reader));
+    assert_eq!(required_role(&Method::GET, "/api/webhooks/3/deliveries"), Some(Role::Reader));
+    // Writes: transfers, genomes, orders
+    assert_eq!(required_role(&Method::POST, "/api/wallet/transfer"), Some(Role::Operator));
+    assert_eq!(required_role(&Method::POST, "/api/wallet/stake"), Some(Role::Operator));
+    assert_eq!(required_role(&Method::POST, "/api/genome/create"), Some(Role::Operator));
+    assert_eq!(required_role(&Method::DELETE, "/api/rsm/orders/4"), Some(Role::Operator));
+    // Consensus administration and key roles, reads included
+    assert_eq!(required_role(&Method::GET, "/api/admin/consensus"), Some(Role::Admin));
+    assert_eq!(required_role(&Method::GET, "/api/admin/keys"), Some(Role::Admin));
+    assert_eq!(required_role(&Method::POST, "/api/admin/keys/2/role"), Some(Role::Admin));
+
+    println!("✅ Route groups: open reads, reader, operator and admin routes");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// KEY STATUS TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_claims_follow_the_keys_current_role() {
+    let auth = AuthManager::with_jwt_secret(b"test secret");
+    let (token, claims) = auth.issue_jwt(&ApiKeyRecord { role: Role::Admin, ..key_record() });
+    assert_eq!(auth.verifier().verify_jwt(&token).unwrap(), claims, "the verifier shares the secret");
+
+    // Demoted after the token was issued: the token is demoted with it
+    let demoted = ApiKeyRecord { role: Role::Reader, ..key_record() };
+    let refreshed = claims.clone().refresh(Some(&demoted)).unwrap();
+    assert!(refreshed.has_role(Role::Reader) && !refreshed.has_role(Role::Operator));
+
+    // Revoked (or deleted) keys lose every token at once
+    assert!(claims.clone().refresh(None).unwrap_err().contains("revoked"));
+    assert!(claims.clone().refresh(Some(&ApiKeyRecord { revoked: true, ..key_record() })).is_err());
+    assert!(claims.refresh(Some(&ApiKeyRecord { id: 99, ..key_record() })).is_err(), "another key's record");
+
+    println!("✅ Tokens take their key's current role and die with it");
+}
+
+#[cfg(feature = "grpc")]
+#[test]
+fn test_grpc_interceptor_verifies_tokens() {
+    use divine_agi::auth::JwtClaims;
+    use divine_agi::grpc::authenticate;
+
+    let auth = AuthManager::with_jwt_secret(b"test secret");
+    let (token, claims) = auth.issue_jwt(&key_record());
+
+    let mut request = tonic::Request::new(());
+    request.metadata_mut().insert("authorization", format!("Bearer {}", token).parse().unwrap());
+    let request = authenticate(&auth, request).unwrap();
+    assert_eq!(request.extensions().get::<JwtClaims>(), Some(&claims));
+
+    // Anonymous calls pass on without claims; bad tokens stop at the interceptor
+    assert!(authenticate(&auth, tonic::Request::new(())).unwrap().extensions().get::<JwtClaims>().is_none());
+    let other = AuthManager::with_jwt_secret(b"other secret");
+    let mut forged = tonic::Request::new(());
+    forged.metadata_mut().insert("authorization", format!("Bearer {}", other.issue_jwt(&key_record()).0).parse().unwrap());
+    assert_eq!(authenticate(&auth, forged).unwrap_err().code(), tonic::Code::Unauthenticated);
+
+    println!("✅ gRPC interceptor verifies the REST JWT");
+}
@@ -0,0 +1,109 @@
+//! Bench Tests for Divine AGI V16 (statistics, calibration, baselines)
+
+use std::time::Duration;
+
+use divine_agi::bench::{book_ops, format_duration, hashing_benches, measure, order_book_load, seeded_book, BenchConfig, BenchReport, BenchStats, BookOp, BenchSuite,
+                        BOOK_DEPTH_ORDERS, SAMPLE_TARGET};
+use divine_agi::order_book::OrderBook;
+
+fn quick() -> BenchConfig {
+    BenchConfig::new(5, 5).unwrap()
+}
+
+// ═══════════════════════════════════════════════════════════════
+// STATISTICS TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_sample_statistics() {
+    let samples = [120.0, 100.0, 110.0, 130.0, 140.0, 100.0, 110.0, 120.0, 130.0, 500.0];
+    let stats = BenchStats::from_samples("demo", &samples, 4);
+    assert_eq!((stats.samples, stats.iterations), (10, 40));
+    assert_eq!((stats.min_ns, stats.max_ns), (100.0, 500.0));
+    assert_eq!(stats.mean_ns, 156.0);
+    assert_eq!(stats.median_ns, 120.0, "even count: middle two averaged");
+    assert_eq!(stats.p95_ns, 500.0, "nearest rank");
+    assert!((stats.stddev_ns - 121.58).abs() < 0.01, "sample stddev {}", stats.stddev_ns);
+    assert!((stats.ops_per_sec - 1e9 / 156.0).abs() < 1e-6);
+
+    assert_eq!(BenchStats::from_samples("odd", &[3.0, 1.0, 2.0], 1).median_ns, 2.0);
+
+    // Batches fill a sample; slow work runs once per sample
+    assert_eq!(BenchConfig::batch_size(1000, Duration::from_millis(1)), 10_000);
+    assert_eq!(BenchConfig::batch_size(2, SAMPLE_TARGET * 10), 1);
+    assert!(BenchConfig::new(100, 1).is_err());
+
+    assert_eq!(format_duration(950.0), "950 ns");
+    assert_eq!(format_duration(2_500.0), "2.50 µs");
+    assert_eq!(format_duration(762_490.0), "762.49 µs");
+    assert_eq!(format_duration(3.2e9), "3.20 s");
+
+    println!("✅ Bench: mean, median, p95 and spread");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// RUN TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_measure_and_suites() {
+    let mut calls = 0u64;
+    let stats = measure(&quick(), "count", || calls += 1);
+    assert_eq!(stats.samples, 5);
+    assert!(calls > stats.iterations, "warmup calls are not timed");
+    assert!(stats.min_ns <= stats.median_ns && stats.median_ns <= stats.max_ns);
+
+    let results = hashing_benches(&quick());
+    assert_eq!(results.iter().map(|r| r.name.as_str()).collect::<Vec<_>>(),
+               ["hashing.dna_sha256", "hashing.genome_rehash", "hashing.hyper_signature", "hashing.block_header"]);
+    assert!(results.iter().all(|r| r.mean_ns > 0.0));
+
+    println!("✅ Bench: warmup, samples and the hashing suite");
+}
+
+#[test]
+fn test_order_book_load_replays_the_same() {
+    let ops = book_ops(5_000, 7);
+    assert_eq!(ops, book_ops(5_000, 7), "seeded");
+    let limits = ops.iter().filter(|op| matches!(op, BookOp::Limit { .. })).count();
+    assert!((2_700..3_300).contains(&limits), "about 60% limit orders: {}", limits);
+
+    let run = |mut book: OrderBook| {
+        let report = order_book_load(&mut book, &ops);
+        (report.trades, report.resting)
+    };
+    let (trades, resting) = run(OrderBook::new());
+    assert!(trades > 0 && resting > 0);
+    assert_eq!(run(OrderBook::new()), (trades, resting));
+    assert!(run(seeded_book()).1 > BOOK_DEPTH_ORDERS, "the seeded depth mostly stays");
+
+    assert_eq!(<BenchSuite as clap::ValueEnum>::from_str("order-book", false), Ok(BenchSuite::OrderBook));
+    assert_eq!(serde_json::to_value(BenchSuite::OrderBook).unwrap(), "order-book");
+
+    println!("✅ Bench: order book load replays deterministically");
+}
+
+#[test]
+fn test_baseline_comparison() {
+    let stats = |name: &str, mean: f64, stddev: f64| BenchStats {
+        name: name.into(), samples: 30, iterations: 300, mean_ns: mean, median_ns: mean, p95_ns: mean,
+        min_ns: mean, max_ns: mean, stddev_ns: stddev, ops_per_sec: 1e9 / mean,
+    };
+    let baseline = BenchReport::new(&quick(), vec![stats("a", 100.0, 2.0), stats("b", 100.0, 40.0), stats("gone", 5.0, 0.0)]);
+    let current = BenchReport::new(&quick(), vec![stats("a", 150.0, 2.0), stats("b", 110.0, 40.0), stats("new", 1.0, 0.0)]);
+
+    let changes = current.compare(&baseline);
+    assert_eq!(changes.iter().map(|c| c.name.as_str()).collect::<Vec<_>>(), ["a", "b"], "only benchmarks in both");
+    assert_eq!((changes[0].change_pct, changes[0].significant), (50.0, true));
+    assert!((changes[1].change_pct - 10.0).abs() < 1e-9);
+    assert!(!changes[1].significant, "noisy difference is not a regression");
+
+    // A saved report reads back as a baseline
+    let mut saved = current.clone();
+    saved.changes = changes;
+    let json = serde_json::to_string(&saved).unwrap();
+    assert_eq!(serde_json::from_str::<BenchReport>(&json).unwrap(), saved);
+    assert!(!serde_json::to_string(&baseline).unwrap().contains("changes"));
+
+    println!("✅ Bench: reports compared against a baseline");
+}
@@ -0,0 +1,59 @@
+//! Response Cache Tests for Divine AGI V16 (ETags, TTL, invalidation)
+
+use std::time::{Duration, Instant};
+
+use axum::body::Bytes;
+use divine_agi::response_cache::{etag, if_none_match, ResponseCache};
+
+// ═══════════════════════════════════════════════════════════════
+// ETAG TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_etags_and_conditional_matches() {
+    let tag = etag(br#"{"success":true}"#);
+    assert!(tag.starts_with('"') && tag.ends_with('"') && tag.len() == 34);
+    assert_eq!(tag, etag(br#"{"success":true}"#), "same body, same tag");
+    assert_ne!(tag, etag(br#"{"success":false}"#));
+
+    assert!(if_none_match(&tag, &tag));
+    assert!(if_none_match(&format!("\"stale\", {}", tag), &tag));
+    assert!(if_none_match(&format!("W/{}", tag), &tag), "weak comparison for GET");
+    assert!(if_none_match("*", &tag));
+    assert!(!if_none_match("\"stale\"", &tag));
+    assert!(!if_none_match("", &tag));
+
+    println!("✅ ETags: stable per body, If-None-Match lists and wildcards matched");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// CACHE TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_cache_expires_and_clears() {
+    let mut cache = ResponseCache::new(Duration::from_millis(1000));
+    let now = Instant::now();
+    let body = Bytes::from_static(b"{\"genome_count\":42}");
+
+    let tag = cache.insert_at("/api/status".into(), body.clone(), Some("application/json".into()), now);
+    assert_eq!(tag, etag(&body));
+    let hit = cache.get_at("/api/status", now + Duration::from_millis(999)).expect("fresh");
+    assert_eq!((&hit.body, hit.content_type.as_deref(), &hit.etag), (&body, Some("application/json"), &tag));
+    assert!(cache.get_at("/api/status", now + Duration::from_millis(1000)).is_none(), "expired after the TTL");
+    // Keys include the query string
+    assert!(cache.get_at("/api/status?fields=all", now).is_none());
+
+    cache.insert_at("/api/genomes?limit=5".into(), body.clone(), None, now);
+    assert_eq!(cache.len(), 2);
+    cache.clear();
+    assert!(cache.is_empty());
+
+    // TTL 0: nothing stored, tags still computed
+    let mut off = ResponseCache::new(Duration::ZERO);
+    assert!(!off.enabled());
+    assert_eq!(off.insert_at("/api/status".into(), body.clone(), None, now), tag);
+    assert!(off.get_at("/api/status", now).is_none());
+
+    println!("✅ Response cache: served within the TTL, emptied by writes");
+}
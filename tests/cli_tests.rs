@@ -0,0 +1,458 @@
+//! CLI Tests for Divine AGI V16 (argument parsing, genome subcommands)
+
+use clap::Parser;
+use divine_agi::api::CrisprEdit;
+use divine_agi::bench::{BenchSuite, DEFAULT_SAMPLES, DEFAULT_WARMUP_MS};
+use divine_agi::cli::{comparison_table, confirmed, diff_positions, labeled_comparison_table, ChainCommand, Cli, Commands, ConfigCommand, DbCommand, DbExportFormat, GenomeArg, GenomeCommand, GenomeExportFormat, OutputFormat, WalletCommand};
+use divine_agi::genome::{Genome, GenomeBuilder, GENOME_SIZE};
+use divine_agi::genome_import::ImportParser;
+use divine_agi::multi_chain::{BlockchainLayer, MAX_ARCHIVE_BATCH};
+use divine_agi::rotation::{Rot180, RotationEngine};
+use divine_agi::tls::TlsSettings;
+use divine_agi::ttrl::{EvolutionJobs, TTRLEngine};
+use divine_agi::watch::WatchTopic;
+use rand::SeedableRng;
+use tokio::sync::RwLock;
+
+const DNA: &str = "TAATAAGGGTGCGAGTTATGGGTAAAT";
+
+fn genome() -> Genome<Rot180> {
+    GenomeBuilder::from_dna(DNA).unwrap().build_storage()
+}
+
+// ═══════════════════════════════════════════════════════════════
+// PARSING TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_genome_subcommands_parse() {
+    let cli = Cli::try_parse_from(["divine-agi", "genome", "new", "--seed", "7", "--whale"]).unwrap();
+    assert!(matches!(cli.command, Commands::Genome { command: GenomeCommand::New { seed: Some(7), whale: true, save: false, .. } }));
+
+    let cli = Cli::try_parse_from(["divine-agi", "genome", "rotate", "42", "--to", "270"]).unwrap();
+    assert!(matches!(cli.command, Commands::Genome { command: GenomeCommand::Rotate { ref genome, ref to, .. } } if genome == "42" && to == "270"));
+
+    let cli = Cli::try_parse_from(["divine-agi", "genome", "export", "1", DNA, "--format", "json"]).unwrap();
+    let Commands::Genome { command: GenomeCommand::Export { genomes, format, out } } = cli.command else { panic!("export") };
+    assert_eq!((genomes.len(), format, out), (2, GenomeExportFormat::Json, None));
+
+    // Conflicting sources, angles off the quarter turns, nothing to export
+    assert!(Cli::try_parse_from(["divine-agi", "genome", "new", "--dna", DNA, "--seed", "1"]).is_err());
+    assert!(Cli::try_parse_from(["divine-agi", "genome", "rotate", "42", "--to", "45"]).is_err());
+    assert!(Cli::try_parse_from(["divine-agi", "genome", "export"]).is_err());
+
+    assert!(matches!(GenomeArg::parse("42"), Ok(GenomeArg::Stored(42))));
+    assert!(matches!(GenomeArg::parse(DNA), Ok(GenomeArg::Dna(g)) if g.to_dna_string() == DNA));
+    assert!(GenomeArg::parse("ACGT").unwrap_err().contains("27"));
+
+    println!("✅ CLI: genome subcommands and <GENOME> arguments parsed");
+}
+
+#[test]
+fn test_evolve_arguments_parse() {
+    let cli = Cli::try_parse_from(["divine-agi", "evolve", "--genome-id", "42", "--budget", "5000", "--seed", "7"]).unwrap();
+    assert!(matches!(cli.command, Commands::Evolve { genome_id: 42, budget: Some(5000), seed: Some(7) }));
+    // The old flag still works; the budget defaults to `ttrl.budget`
+    let cli = Cli::try_parse_from(["divine-agi", "evolve", "--id", "3"]).unwrap();
+    assert!(matches!(cli.command, Commands::Evolve { genome_id: 3, budget: None, seed: None }));
+    assert!(Cli::try_parse_from(["divine-agi", "evolve", "--budget", "10"]).is_err());
+
+    println!("✅ CLI: evolve --genome-id --budget --seed parsed");
+}
+
+#[test]
+fn test_wallet_subcommands_parse() {
+    let cli = Cli::try_parse_from(["divine-agi", "wallet", "transfer", "--to", "divine_x", "-a", "2.5", "--yes", "-f", "my.wallet"]).unwrap();
+    let Commands::Wallet { file, command: WalletCommand::Transfer { to, amount, memo, api, token, yes } } = cli.command else { panic!("transfer") };
+    assert_eq!((file.unwrap().to_str(), to.as_str(), amount, memo, yes), (Some("my.wallet"), "divine_x", 2.5, None, true));
+    assert_eq!((api, token), (None, None));
+    let cli = Cli::try_parse_from(["divine-agi", "wallet", "transfer", "--to", "divine_x", "-a", "1", "--api", "http://node:8080", "--token", "jwt"]).unwrap();
+    let Commands::Wallet { command: WalletCommand::Transfer { api, token, .. }, .. } = cli.command else { panic!("transfer --api") };
+    assert_eq!((api.as_deref(), token.as_deref()), (Some("http://node:8080"), Some("jwt")));
+
+    let cli = Cli::try_parse_from(["divine-agi", "wallet", "create"]).unwrap();
+    assert!(matches!(cli.command, Commands::Wallet { file: None, command: WalletCommand::Create { words: 24, force: false } }));
+    assert!(matches!(Cli::try_parse_from(["divine-agi", "wallet", "addresses", "--new"]).unwrap().command,
+        Commands::Wallet { command: WalletCommand::Addresses { new: true }, .. }));
+    assert!(Cli::try_parse_from(["divine-agi", "wallet", "transfer", "--to", "divine_x"]).is_err());
+    assert!(matches!(Cli::try_parse_from(["divine-agi", "wallet", "balance", "--chain"]).unwrap().command,
+        Commands::Wallet { command: WalletCommand::Balance { chain: true }, .. }));
+    let cli = Cli::try_parse_from(["divine-agi", "wallet", "network", "solana-devnet", "--rsm-token", "Mint111"]).unwrap();
+    let Commands::Wallet { command: WalletCommand::Network { name, rpc_url, rsm_token }, .. } = cli.command else { panic!("network") };
+    assert_eq!((name.as_deref(), rpc_url, rsm_token.as_deref()), (Some("solana-devnet"), None, Some("Mint111")));
+    let cli = Cli::try_parse_from(["divine-agi", "wallet", "send", "--to", "Dest111", "-a", "3", "--token", "Mint222"]).unwrap();
+    let Commands::Wallet { command: WalletCommand::Send { token, yes: false, .. }, .. } = cli.command else { panic!("send") };
+    assert_eq!(token.as_deref(), Some("Mint222"));
+    assert!(matches!(Cli::try_parse_from(["divine-agi", "wallet", "send", "--to", "0xabc", "-a", "1", "--native"]).unwrap().command,
+        Commands::Wallet { command: WalletCommand::Send { native: true, token: None, .. }, .. }));
+    assert!(Cli::try_parse_from(["divine-agi", "wallet", "send", "--to", "0xabc", "-a", "1", "--native", "--token", "Mint222"]).is_err());
+    let cli = Cli::try_parse_from(["divine-agi", "wallet", "send", "--to", "Dest111", "-a", "1", "--build", "tx.json"]).unwrap();
+    let Commands::Wallet { command: WalletCommand::Send { build, .. }, .. } = cli.command else { panic!("send --build") };
+    assert_eq!(build, Some(std::path::PathBuf::from("tx.json")));
+    assert!(matches!(Cli::try_parse_from(["divine-agi", "wallet", "sign", "tx.json", "--out", "signed.json"]).unwrap().command,
+        Commands::Wallet { command: WalletCommand::Sign { yes: false, .. }, .. }));
+    assert!(Cli::try_parse_from(["divine-agi", "wallet", "sign", "tx.json"]).is_err(), "needs --out");
+    assert!(matches!(Cli::try_parse_from(["divine-agi", "wallet", "broadcast", "signed.json"]).unwrap().command,
+        Commands::Wallet { command: WalletCommand::Broadcast { .. }, .. }));
+    assert!(matches!(Cli::try_parse_from(["divine-agi", "wallet", "tokens"]).unwrap().command,
+        Commands::Wallet { command: WalletCommand::Tokens { add: None }, .. }));
+
+    for yes in ["y", "Y", "yes\n", " YES "] {
+        assert!(confirmed(yes), "{:?}", yes);
+    }
+    for no in ["", "\n", "n", "no", "yep", "sure"] {
+        assert!(!confirmed(no), "{:?}", no);
+    }
+
+    println!("✅ CLI: wallet subcommands parsed, only yes confirms");
+}
+
+#[test]
+fn test_chain_subcommands_parse() {
+    let parse = |args: &[&str]| Cli::try_parse_from(["divine-agi", "chain"].iter().chain(args)).map(|cli| cli.command);
+    assert!(matches!(parse(&["show", "--height", "12"]), Ok(Commands::Chain { command: ChainCommand::Show { height: Some(12) } })));
+    assert!(matches!(parse(&["show"]), Ok(Commands::Chain { command: ChainCommand::Show { height: None } })));
+    assert!(matches!(parse(&["mine", "--genome-id", "42"]), Ok(Commands::Chain { command: ChainCommand::Mine { genome_id: 42 } })));
+    assert!(matches!(parse(&["export", "-o", "c.bin", "--snapshot"]), Ok(Commands::Chain { command: ChainCommand::Export { snapshot: true, .. } })));
+    assert!(matches!(parse(&["import", "c.bin"]), Ok(Commands::Chain { command: ChainCommand::Import { .. } })));
+    assert!(matches!(parse(&["validate", "--file", "c.bin"]), Ok(Commands::Chain { command: ChainCommand::Validate { file: Some(_) } })));
+    assert!(matches!(parse(&["stats"]), Ok(Commands::Chain { command: ChainCommand::Stats })));
+    assert!(parse(&["mine"]).is_err());
+    assert!(parse(&["export"]).is_err());
+
+    println!("✅ CLI: chain subcommands parsed");
+}
+
+#[test]
+fn test_db_subcommands_parse() {
+    let parse = |args: &[&str]| Cli::try_parse_from(["divine-agi", "db"].iter().chain(args)).map(|cli| cli.command);
+    let Ok(Commands::Db { command: DbCommand::Export { format, table, out } }) =
+        parse(&["export", "--format", "jsonl", "-t", "divine_genomes_v15", "-t", "chain_archives"]) else { panic!("export") };
+    assert_eq!((format, table.len(), out), (DbExportFormat::Jsonl, 2, None));
+    assert!(matches!(parse(&["migrate"]), Ok(Commands::Db { command: DbCommand::Migrate })));
+    assert!(matches!(parse(&["import", "dump.jsonl"]), Ok(Commands::Db { command: DbCommand::Import { .. } })));
+    assert!(matches!(parse(&["restore", "b.jsonl", "--yes"]), Ok(Commands::Db { command: DbCommand::Restore { yes: true, .. } })));
+    assert!(parse(&["export", "--format", "csv"]).is_err());
+    assert!(parse(&["backup"]).is_err(), "backups need --out");
+
+    println!("✅ CLI: db subcommands parsed");
+}
+
+#[test]
+fn test_archive_arguments_parse() {
+    let parse = |args: &[&str]| Cli::try_parse_from(["divine-agi", "archive"].iter().chain(args)).map(|cli| cli.command);
+    let Ok(Commands::Archive { genome_id, layer, limit, .. }) =
+        parse(&["--genome-id", "42", "--layer", "Bitcoin", "--dry-run"]) else { panic!("archive") };
+    assert_eq!((genome_id, layer, limit), (Some(42), Some(BlockchainLayer::Bitcoin), MAX_ARCHIVE_BATCH));
+    assert!(matches!(parse(&["--id", "7"]), Ok(Commands::Archive { genome_id: Some(7), layer: None, .. })));
+    let Ok(Commands::Archive { genome_id, min_consciousness, min_p53, limit, .. }) =
+        parse(&["--min-consciousness", "500", "--min-p53", "20", "-l", "10"]) else { panic!("batch") };
+    assert_eq!((genome_id, min_consciousness, min_p53, limit), (None, Some(500), Some(20), 10));
+    assert!(parse(&[]).is_err(), "a genome or a filter is needed");
+    assert!(parse(&["-i", "42", "--min-p53", "20"]).is_err());
+    assert!(parse(&["-i", "42", "--layer", "dogecoin"]).is_err());
+
+    println!("✅ CLI: archive arguments parsed");
+}
+
+#[test]
+fn test_output_format() {
+    // Global: before or after the subcommand
+    for args in [&["divine-agi", "--output", "yaml", "chain", "stats"][..], &["divine-agi", "chain", "stats", "--output", "yaml"]] {
+        assert_eq!(Cli::try_parse_from(args).unwrap().output, Some(OutputFormat::Yaml));
+    }
+    assert_eq!(Cli::try_parse_from(["divine-agi", "status"]).unwrap().output, None);
+    assert!(Cli::try_parse_from(["divine-agi", "--output", "xml", "status"]).is_err());
+
+    // Tables on terminals, JSON when piped, unless asked
+    assert_eq!(OutputFormat::resolve(None, true), OutputFormat::Table);
+    assert_eq!(OutputFormat::resolve(None, false), OutputFormat::Json);
+    assert_eq!(OutputFormat::resolve(Some(OutputFormat::Table), false), OutputFormat::Table);
+
+    let value = serde_json::json!({ "height": 3, "tip": "ab" });
+    let json = OutputFormat::Json.render(&value).unwrap();
+    assert_eq!(serde_json::from_str::<serde_json::Value>(&json).unwrap(), value);
+    assert_eq!(OutputFormat::Yaml.render(&value).unwrap(), "height: 3\ntip: ab\n");
+    assert!(OutputFormat::Table.render(&value).is_err());
+
+    println!("✅ CLI: --output formats");
+}
+
+#[test]
+fn test_dry_run_flag_parse() {
+    let cli = Cli::try_parse_from(["divine-agi", "db", "import", "dump.jsonl", "--dry-run"]).unwrap();
+    assert!(cli.dry_run, "global flag after the subcommand");
+    let cli = Cli::try_parse_from(["divine-agi", "--dry-run", "wallet", "transfer", "--to", "divine_x", "-a", "1"]).unwrap();
+    assert!(cli.dry_run && !cli.command.lacks_dry_run());
+    assert!(!Cli::try_parse_from(["divine-agi", "genome", "show", "1"]).unwrap().dry_run);
+
+    // Commands that write without a preview refuse the flag; reads run as usual
+    let lacks = |args: &[&str]| Cli::try_parse_from(["divine-agi"].iter().chain(args)).unwrap().command.lacks_dry_run();
+    for args in [&["evolve", "-i", "1"][..], &["genome", "new", "--save"], &["chain", "mine", "--genome-id", "1"], &["config", "set", "api_url", "x"]] {
+        assert!(lacks(args), "{:?} has no preview", args);
+    }
+    for args in [&["archive", "-i", "1"][..], &["genome", "crispr", "1", "delete", "3"], &["db", "restore", "b.jsonl"], &["genome", "new"], &["chain", "stats"]] {
+        assert!(!lacks(args), "{:?} can run with --dry-run", args);
+    }
+
+    println!("✅ CLI: --dry-run parsed for every command");
+}
+
+#[test]
+fn test_crispr_subcommands_parse() {
+    let edit = |args: &[&str]| match Cli::try_parse_from(["divine-agi", "genome", "crispr", "42"].iter().chain(args)).map(|cli| cli.command) {
+        Ok(Commands::Genome { command: GenomeCommand::Crispr { genome, edit } }) => { assert_eq!(genome, "42"); Ok(edit.edit()) }
+        Ok(_) => panic!("crispr"),
+        Err(e) => Err(e),
+    };
+    assert_eq!(edit(&["splice", "3", "G"]).unwrap(), CrisprEdit::Splice { position: 3, new_base: 'G' });
+    assert_eq!(edit(&["join", "0", "26"]).unwrap(), CrisprEdit::Join { pos1: 0, pos2: 26 });
+    assert_eq!(edit(&["delete", "5"]).unwrap(), CrisprEdit::Delete { position: 5 });
+    assert!(edit(&["splice", "3"]).is_err());
+
+    let mut edited = genome();
+    CrisprEdit::Splice { position: 3, new_base: 'G' }.apply(&mut edited).unwrap();
+    assert_eq!(edited.to_dna_string().chars().nth(3), Some('G'));
+    assert_eq!(CrisprEdit::Join { pos1: 0, pos2: 27 }.apply(&mut edited).unwrap_err(), "Positions must be 0-26");
+    assert_eq!(CrisprEdit::Splice { position: 1, new_base: 'X' }.apply(&mut edited).unwrap_err(), "Invalid base");
+
+    println!("✅ CLI: CRISPR edits parsed and applied");
+}
+
+#[test]
+fn test_config_and_dashboard_api_parse() {
+    let cli = Cli::try_parse_from(["divine-agi", "config", "set", "ttrl.budget", "500"]).unwrap();
+    assert!(matches!(cli.command, Commands::Config { command: ConfigCommand::Set { key, value } } if key == "ttrl.budget" && value == "500"));
+    assert!(matches!(Cli::try_parse_from(["divine-agi", "config", "show"]).unwrap().command, Commands::Config { command: ConfigCommand::Show }));
+    assert!(Cli::try_parse_from(["divine-agi", "config", "set", "api_url"]).is_err());
+
+    // `--api` alone reads the configured `api_url`
+    let api = |args: &[&str]| match Cli::try_parse_from(args).unwrap().command {
+        Commands::Dashboard { api, .. } => api,
+        _ => unreachable!(),
+    };
+    assert_eq!(api(&["divine-agi", "dashboard"]), None);
+    assert_eq!(api(&["divine-agi", "dashboard", "--api"]), Some(None));
+    assert_eq!(api(&["divine-agi", "dashboard", "--api", "http://node:8080"]), Some(Some("http://node:8080".into())));
+    assert!(Cli::try_parse_from(["divine-agi", "dashboard", "--api", "--wallet", "w.wallet"]).is_err());
+
+    println!("✅ CLI: config and dashboard --api parsed");
+}
+
+#[test]
+fn test_watch_arguments_parse() {
+    let cli = Cli::try_parse_from(["divine-agi", "watch", "genomes", "archives", "-f", "consciousness>=5000", "--filter", "p53_copies=40"]).unwrap();
+    let Commands::Watch { topics, filter } = cli.command else { panic!("expected watch") };
+    assert_eq!(topics, vec![WatchTopic::Genomes, WatchTopic::Archives]);
+    assert_eq!(filter.iter().map(|f| f.field.as_str()).collect::<Vec<_>>(), ["consciousness", "p53_copies"]);
+
+    assert!(Cli::try_parse_from(["divine-agi", "watch"]).is_err(), "a topic is required");
+    assert!(Cli::try_parse_from(["divine-agi", "watch", "trades"]).is_err());
+    assert!(Cli::try_parse_from(["divine-agi", "watch", "blocks", "-f", "difficulty"]).is_err());
+
+    println!("✅ CLI: watch topics and filters parsed");
+}
+
+#[test]
+fn test_keygen_arguments_parse() {
+    let cli = Cli::try_parse_from(["divine-agi", "keygen", "--words", "12", "--out", "node.json", "--index", "2", "-y"]).unwrap();
+    assert!(matches!(cli.command, Commands::Keygen { words: 12, index: 2, recover: false, force: false, yes: true, ref out } if out.to_str() == Some("node.json")));
+
+    let cli = Cli::try_parse_from(["divine-agi", "keygen"]).unwrap();
+    assert!(matches!(cli.command, Commands::Keygen { words: 24, index: 0, ref out, .. } if out.to_str() == Some("keystore.json")));
+    assert!(Cli::try_parse_from(["divine-agi", "keygen", "--recover", "--words", "12"]).is_err(), "a recovered phrase has its own length");
+
+    println!("✅ CLI: keygen options parsed");
+}
+
+#[test]
+fn test_bench_arguments_parse() {
+    let cli = Cli::try_parse_from(["divine-agi", "bench", "rotation", "db", "-s", "50", "--baseline", "v15.json"]).unwrap();
+    let Commands::Bench { suites, samples, warmup_ms, baseline } = cli.command else { panic!("expected bench") };
+    assert_eq!((suites, samples, warmup_ms), (vec![BenchSuite::Rotation, BenchSuite::Db], 50, DEFAULT_WARMUP_MS));
+    assert_eq!(baseline, Some("v15.json".into()));
+
+    let Commands::Bench { suites, samples, .. } = Cli::try_parse_from(["divine-agi", "bench"]).unwrap().command else { panic!() };
+    assert!(suites.is_empty(), "no suite runs them all");
+    assert_eq!(samples, DEFAULT_SAMPLES);
+    assert!(Cli::try_parse_from(["divine-agi", "bench", "network"]).is_err());
+
+    println!("✅ CLI: bench suites and options parsed");
+}
+
+#[test]
+fn test_serve_arguments_parse() {
+    let cli = Cli::try_parse_from(["divine-agi", "serve", "--bind", "127.0.0.1:9000", "--tls-cert", "s.pem", "--tls-key", "s.key",
+                                   "--workers", "8", "--enable-admin", "--database-url", "postgresql://db/divine"]).unwrap();
+    let Commands::Serve { bind, workers, enable_admin, tls_cert, tls_key, tls_client_ca, database_url, .. } = cli.command else { panic!("expected serve") };
+    assert_eq!((bind.to_string().as_str(), workers, enable_admin), ("127.0.0.1:9000", Some(8), true));
+    assert_eq!(database_url.as_deref(), Some("postgresql://db/divine"));
+    let tls = TlsSettings::from_paths(tls_cert, tls_key, tls_client_ca).unwrap().unwrap();
+    assert!(!tls.mutual());
+
+    let Commands::Serve { bind, workers, enable_admin, grpc_port, rotation_interval, .. } = Cli::try_parse_from(["divine-agi", "serve"]).unwrap().command else { panic!() };
+    assert_eq!((bind.port(), workers, enable_admin, grpc_port, rotation_interval), (8080, None, false, None, 30), "admin routes are opt-in");
+    assert!(Cli::try_parse_from(["divine-agi", "serve", "--tls-cert", "s.pem"]).is_err(), "a certificate needs its key");
+    assert!(Cli::try_parse_from(["divine-agi", "serve", "--bind", "8080"]).is_err());
+    assert!(TlsSettings::from_paths(None, None, Some("ca.pem".into())).is_err());
+
+    println!("✅ CLI: serve flags parsed");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// GENOME COMMAND TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_seeded_genomes_and_mutations_repeat() {
+    let a = GenomeBuilder::seeded(7).build_storage();
+    assert_eq!(a.to_dna_string(), GenomeBuilder::seeded(7).build_storage().to_dna_string());
+    assert_ne!(a.to_dna_string(), GenomeBuilder::seeded(8).build_storage().to_dna_string());
+
+    let mutate = |seed| {
+        let mut g = genome();
+        let positions = g.mutate_with(3, &mut rand::rngs::StdRng::seed_from_u64(seed));
+        (g, positions)
+    };
+    let (mutated, positions) = mutate(1);
+    assert_eq!(mutate(1).0.to_dna_string(), mutated.to_dna_string());
+    assert_eq!(positions.len(), 3);
+    assert_eq!(mutated.mutations, 3);
+    assert_ne!(mutated.hash, genome().hash);
+    // A single mutation always changes its base
+    let mut single = genome();
+    let position = single.mutate_with(1, &mut rand::rngs::StdRng::seed_from_u64(9))[0];
+    assert_ne!(single.data[position], genome().data[position]);
+    assert_eq!(single.data.iter().zip(genome().data.iter()).filter(|(a, b)| a != b).count(), 1);
+
+    println!("✅ Genome CLI: seeded genomes and mutations are reproducible");
+}
+
+#[test]
+fn test_rotation_cube_and_export() {
+    let g = genome();
+    let quarter = g.rotated(90).unwrap();
+    assert_ne!(quarter.to_dna_string(), DNA);
+    // The centre column stays, four quarter turns are the identity
+    for z in 0..3 {
+        assert_eq!(quarter.data[4 + z * 9], g.data[4 + z * 9]);
+    }
+    let full = quarter.rotated(90).unwrap().rotated(90).unwrap().rotated(90).unwrap();
+    assert_eq!(full.to_dna_string(), DNA);
+    assert_eq!(g.rotated(180).unwrap().to_dna_string(), quarter.rotated(90).unwrap().to_dna_string());
+    assert_eq!(g.rotated(0).unwrap().to_dna_string(), DNA);
+    assert!(g.rotated(45).is_err() && g.rotated(360).is_err());
+
+    let cube = g.render_cube();
+    let lines: Vec<&str> = cube.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[1], "T A A   T G C   T G G");
+    assert_eq!(cube.chars().filter(|c| "ACGT".contains(*c)).count(), GENOME_SIZE);
+
+    // FASTA exports import back
+    let fasta = g.to_fasta("genome_5");
+    assert!(fasta.starts_with(">genome_5 consciousness="));
+    let mut parser = ImportParser::new("export.fasta", None, false);
+    let mut records = parser.feed(fasta.as_bytes());
+    records.extend(parser.finish());
+    let record = records.pop().unwrap().unwrap();
+    assert_eq!((record.name.as_deref(), record.dna.as_str()), (Some("genome_5"), DNA));
+
+    let metrics = g.metrics();
+    assert_eq!(metrics.consciousness, g.consciousness);
+    assert_eq!((metrics.t_count, metrics.g_count), g.tg_counts());
+    assert_eq!(metrics.suggested_rotation, g.suggested_rotation());
+
+    println!("✅ Genome CLI: quarter-turn rotations, cube view, FASTA round-trip");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// EVOLVE COMMAND TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_seeded_evolution_repeats() {
+    let ttrl = TTRLEngine::new();
+    let rotation = RwLock::new(RotationEngine::new());
+    let evolve = |seed| {
+        let (ttrl, rotation) = (&ttrl, &rotation);
+        async move {
+            let jobs = RwLock::new(EvolutionJobs::new());
+            jobs.write().await.register(1);
+            let (_, mut events) = jobs.read().await.subscribe(1).unwrap();
+            let run = ttrl.run_job_with_rng(1, genome(), 20, rotation, &jobs, &mut rand::rngs::StdRng::seed_from_u64(seed)).await;
+            let mut seen = 0;
+            while let Ok(event) = events.try_recv() {
+                seen += 1;
+                assert_eq!(event.generation, seen);
+            }
+            assert_eq!(seen, run.generations_run);
+            run
+        }
+    };
+
+    let first = evolve(7).await;
+    let again = evolve(7).await;
+    assert!(first.generations_run > 0);
+    assert_eq!(first.generations_run, again.generations_run);
+    assert_eq!(first.best.to_dna_string(), again.best.to_dna_string());
+    assert_eq!((first.best.consciousness, first.best.mutations), (again.best.consciousness, again.best.mutations));
+    assert!(first.best.consciousness >= genome().consciousness);
+
+    let single = |seed| ttrl.evolve_with_rng(genome(), &mut rand::rngs::StdRng::seed_from_u64(seed)).unwrap();
+    assert_eq!(single(1).0.to_dna_string(), single(1).0.to_dna_string());
+    assert_eq!(single(1).1.operator_used, single(1).1.operator_used);
+
+    println!("✅ Evolve CLI: the same seed evolves the same genome");
+}
+
+#[test]
+fn test_comparison_table() {
+    let before = genome();
+    let mut after = genome();
+    after.mutate_with(2, &mut rand::rngs::StdRng::seed_from_u64(3));
+
+    let table = comparison_table(&before, &after);
+    let lines: Vec<&str> = table.lines().collect();
+    assert!(lines[0].contains("Before") && lines[0].contains("After") && lines[0].contains("Δ"));
+    let mutations = lines.iter().find(|line| line.trim_start().starts_with("Mutations")).unwrap();
+    assert!(mutations.ends_with("+2"), "{}", mutations);
+    let telomeres = lines.iter().find(|line| line.trim_start().starts_with("Telomeres")).unwrap();
+    assert!(telomeres.ends_with('·'), "unchanged rows show a dot: {}", telomeres);
+    let dna = lines.last().unwrap();
+    assert!(dna.contains(DNA) && dna.contains(&after.to_dna_string()));
+
+    println!("✅ Evolve CLI: before/after table shows the deltas");
+}
+
+#[test]
+fn test_genome_diff_view() {
+    let a = genome();
+    let mut b = genome();
+    for position in [0, 13, 26] {
+        let base = if DNA.as_bytes()[position] == b'A' { 'C' } else { 'A' };
+        b.crispr_splice(position, divine_agi::genome::Tetrad::from_char(base).unwrap());
+    }
+
+    let diff = a.diff(&b);
+    assert_eq!(diff.changed, [0, 13, 26]);
+    assert!((diff.identity - 24.0 / 27.0).abs() < 1e-9);
+    assert_eq!(diff.consciousness.delta, b.consciousness as f64 - a.consciousness as f64);
+    assert_eq!((diff.p53_copies.delta, diff.dna_a.as_str()), (0.0, DNA));
+    assert!(a.diff(&a).changed.is_empty());
+
+    let view = diff_positions(&diff, "#1", "#2", false);
+    let lines: Vec<&str> = view.lines().collect();
+    assert_eq!(lines.len(), 5);
+    assert!(lines[2].ends_with(DNA) && lines[3].ends_with(&b.to_dna_string()));
+    assert_eq!(lines[4].trim_start(), "^            ^            ^");
+    assert!(!view.contains('\x1b'));
+    let colored = diff_positions(&diff, "#1", "#2", true);
+    assert_eq!(colored.matches("\x1b[1;31m").count(), 3, "changed bases of A in red");
+
+    assert!(labeled_comparison_table(&a, &b, "#1", "#2").lines().next().unwrap().contains("#1"));
+    assert!(matches!(Cli::try_parse_from(["divine-agi", "genome", "diff", "1", DNA]).unwrap().command,
+                     Commands::Genome { command: GenomeCommand::Diff { .. } }));
+
+    println!("✅ CLI: genome diff marks the changed positions");
+}
@@ -0,0 +1,105 @@
+//! Config Tests for Divine AGI V16 (config file, overrides, set)
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use divine_agi::config::{ConfigFile, DivineConfig, DEFAULT_API_URL, DEFAULT_TTRL_BUDGET, DEFAULT_WALLET_FILE};
+use divine_agi::ttrl::DEFAULT_EVOLUTION_WORKERS;
+use divine_agi::DEFAULT_DATABASE_URL;
+
+fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+    let vars: HashMap<String, String> = vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+    move |name| vars.get(name).cloned()
+}
+
+const FILE: &str = r#"
+database_url = "postgresql://postgres@db.internal:5432/divine_agi"
+api_url = "https://node.example.com"
+network = "genesis/testnet.json"
+
+[ttrl]
+budget = 500
+"#;
+
+// ═══════════════════════════════════════════════════════════════
+// FILE TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_config_file_set_and_round_trip() {
+    let mut file = ConfigFile::parse(FILE).unwrap();
+    assert_eq!(file.ttrl.budget, Some(500));
+    assert_eq!(file.get("network").unwrap().as_deref(), Some("genesis/testnet.json"));
+    assert_eq!(file.get("wallet_file").unwrap(), None);
+
+    file.set("wallet_file", "/data/node.wallet").unwrap();
+    file.set("ttrl.workers", "4").unwrap();
+    file.set("api_url", "https://other.example.com/").unwrap();
+    file.set("network", "").unwrap();
+    assert_eq!(file.get("api_url").unwrap().as_deref(), Some("https://other.example.com"), "trailing slash dropped");
+    assert_eq!(file.network, None, "empty value removes the key");
+    assert_eq!(ConfigFile::parse(&file.to_toml()).unwrap(), file);
+
+    assert!(file.set("ttrl.budget", "0").is_err());
+    assert!(file.set("ttrl.budget", "20000").is_err(), "above MAX_EVOLUTION_GENERATIONS");
+    assert!(file.set("ttrl.workers", "many").is_err());
+    assert!(file.set("colour", "blue").unwrap_err().contains("Unknown config key colour"));
+    assert!(ConfigFile::parse("colour = \"blue\"").is_err(), "typos are not ignored");
+
+    // Saved under a directory that does not exist yet
+    let dir = std::env::temp_dir().join(format!("divine-config-{}", std::process::id()));
+    let path = dir.join(".divine").join("config.toml");
+    assert_eq!(ConfigFile::load(&path).unwrap(), ConfigFile::default(), "missing file is empty");
+    file.save(&path).unwrap();
+    assert_eq!(ConfigFile::load(&path).unwrap(), file);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    println!("✅ Config: file parsed, set and saved");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// OVERRIDE TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_config_defaults_and_overrides() {
+    let defaults = DivineConfig::resolve(&ConfigFile::default(), env(&[])).unwrap();
+    assert_eq!(defaults, DivineConfig {
+        database_url: DEFAULT_DATABASE_URL.into(),
+        api_url: DEFAULT_API_URL.into(),
+        wallet_file: None,
+        network: None,
+        ttrl_budget: DEFAULT_TTRL_BUDGET,
+        ttrl_workers: DEFAULT_EVOLUTION_WORKERS,
+    });
+    assert_eq!(defaults.wallet_path(None), PathBuf::from(DEFAULT_WALLET_FILE));
+    assert_eq!(defaults.wallet_path(Some("mine.wallet".into())), PathBuf::from("mine.wallet"));
+
+    let file = ConfigFile::parse(FILE).unwrap();
+    let config = DivineConfig::resolve(&file, env(&[])).unwrap();
+    assert_eq!((config.api_url.as_str(), config.ttrl_budget), ("https://node.example.com", 500));
+    assert_eq!(config.network, Some(PathBuf::from("genesis/testnet.json")));
+
+    // DIVINE_* beats the old variable, which beats the file
+    let config = DivineConfig::resolve(&file, env(&[
+        ("DATABASE_URL", "postgresql://railway/db"),
+        ("DIVINE_TTRL_BUDGET", "50"),
+        ("WALLET_FILE", "/data/old.wallet"),
+        ("DIVINE_WALLET_FILE", "/data/new.wallet"),
+        ("EVOLUTION_WORKERS", "8"),
+    ])).unwrap();
+    assert_eq!(config.database_url, "postgresql://railway/db");
+    assert_eq!((config.ttrl_budget, config.ttrl_workers), (50, 8));
+    assert_eq!(config.wallet_file, Some(PathBuf::from("/data/new.wallet")));
+
+    let entries = DivineConfig::entries(&file, env(&[("DIVINE_NETWORK", "genesis/mainnet.json")])).unwrap();
+    let source = |key: &str| entries.iter().find(|e| e.key == key).map(|e| (e.value.clone(), e.source.clone())).unwrap();
+    assert_eq!(source("network"), (Some("genesis/mainnet.json".into()), "DIVINE_NETWORK".into()));
+    assert_eq!(source("ttrl.budget"), (Some("500".into()), "file".into()));
+    assert_eq!(source("wallet_file"), (Some(DEFAULT_WALLET_FILE.into()), "default".into()));
+
+    let err = DivineConfig::resolve(&file, env(&[("DIVINE_TTRL_WORKERS", "0")])).unwrap_err();
+    assert_eq!(err, "Invalid DIVINE_TTRL_WORKERS 0");
+
+    println!("✅ Config: DIVINE_* over old variables over the file over defaults");
+}
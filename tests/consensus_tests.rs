@@ -0,0 +1,1291 @@
+//! Consensus Tests for Divine AGI V16 (Kernel V4)
+
+use divine_agi::consensus::{
+    ProofOfConsciousness, ConsensusBlock, INITIAL_POC_THRESHOLD,
+    DEFAULT_DIFFICULTY, MIN_DIFFICULTY, MAX_RETARGET_STEP, ValidatorKey, BlockOutcome,
+    BLOCK_REWARD_RSM, OrphanPool, MAX_ORPHAN_BLOCKS, ORPHAN_TIMEOUT_SECS, ChainEvent, MAX_REORG_DEPTH,
+    MergeReport,
+};
+use divine_agi::genome::{Genome, GenomeBuilder};
+use divine_agi::rotation::Rot180;
+
+/// Random whale genome lifted above the current PoC threshold
+fn conscious_genome(threshold: u32) -> Genome<Rot180> {
+    let mut genome = GenomeBuilder::random().whale_mode().build_storage();
+    genome.consciousness = threshold + 100;
+    genome.db_id = Some(1);
+    genome
+}
+
+// ═══════════════════════════════════════════════════════════════
+// CHAIN TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_genesis_is_deterministic() {
+    let a = ConsensusBlock::genesis();
+    let b = ConsensusBlock::genesis();
+    assert_eq!(a, b);
+    assert_eq!(a.index, 0);
+    assert_eq!(a.hash, a.calculate_hash());
+
+    println!("✅ Genesis test passed: {}", &a.hash[..16]);
+}
+
+#[test]
+fn test_validate_mines_linked_blocks() {
+    let mut poc = ProofOfConsciousness::new();
+
+    for _ in 0..3 {
+        let genome = conscious_genome(poc.min_consciousness);
+        assert!(poc.validate(&genome).is_some());
+    }
+
+    assert_eq!(poc.chain.len(), 4);
+    assert_eq!(poc.current_block_height, 3);
+    assert_eq!(poc.min_consciousness, INITIAL_POC_THRESHOLD + 3);
+    assert!(poc.validate_chain());
+
+    let latest = poc.latest_block();
+    assert!(latest.meets_difficulty());
+    assert_eq!(latest.previous_hash, poc.chain[2].hash);
+
+    println!("✅ Chain mining test passed: height {}", poc.current_block_height);
+}
+
+#[test]
+fn test_paused_node_does_not_mine() {
+    let mut poc = ProofOfConsciousness::new();
+    poc.mining_paused = true;
+
+    let genome = conscious_genome(poc.min_consciousness);
+    poc.submit(&genome).unwrap();
+    assert!(poc.mine_block().is_none(), "paused");
+    assert_eq!(poc.mempool.len(), 1, "attestations wait in the mempool");
+
+    poc.mining_paused = false;
+    assert!(poc.mine_block().is_some());
+    assert_eq!(poc.current_block_height, 1);
+
+    println!("✅ Paused node keeps its mempool until resumed");
+}
+
+#[test]
+fn test_low_consciousness_rejected() {
+    let mut poc = ProofOfConsciousness::new();
+    let mut genome = GenomeBuilder::random().build_storage();
+    genome.consciousness = INITIAL_POC_THRESHOLD - 1;
+
+    assert!(poc.validate(&genome).is_none());
+    assert_eq!(poc.chain.len(), 1);
+}
+
+#[test]
+fn test_add_block_rejects_tampering() {
+    let mut poc = ProofOfConsciousness::new();
+    let genome = conscious_genome(poc.min_consciousness);
+    poc.validate(&genome).unwrap();
+
+    let mut other = ProofOfConsciousness::new();
+    other.validators.register(&poc.validator_public_key().unwrap());
+
+    // Wrong parent
+    let mut forged = poc.latest_block().clone();
+    forged.previous_hash = "f".repeat(64);
+    assert!(other.add_block(forged).is_err());
+
+    // Tampered reward
+    let mut forged = poc.latest_block().clone();
+    forged.entries[0].proof.reward_rsm *= 1000.0;
+    assert!(other.add_block(forged).is_err());
+
+    // Honest copy is accepted
+    assert!(other.add_block(poc.latest_block().clone()).is_ok());
+    assert!(other.validate_chain());
+}
+
+#[test]
+fn test_tampered_chain_fails_validation() {
+    let mut poc = ProofOfConsciousness::new();
+    let genome = conscious_genome(poc.min_consciousness);
+    poc.validate(&genome).unwrap();
+
+    poc.chain[1].entries[0].proof.consciousness += 1;
+    assert!(!poc.validate_chain());
+}
+
+#[test]
+fn test_parallel_validation_matches_serial() {
+    let mut poc = ProofOfConsciousness::new();
+    for id in 0..6 {
+        mine_one(&mut poc, id);
+    }
+    assert!(poc.validate_chain());
+    assert!(poc.validate_chain_parallel());
+
+    poc.chain[4].entries[0].proof.consciousness += 1;
+    assert!(!poc.validate_chain());
+    assert!(!poc.validate_chain_parallel());
+}
+
+#[test]
+fn test_incremental_validation_checks_new_blocks() {
+    let mut poc = ProofOfConsciousness::new();
+    mine_one(&mut poc, 1);
+    mine_one(&mut poc, 2);
+    assert!(poc.validate_new_blocks());
+
+    mine_one(&mut poc, 3);
+    assert!(poc.validate_new_blocks());
+
+    mine_one(&mut poc, 4);
+    poc.chain[4].nonce += 1;
+    assert!(!poc.validate_new_blocks());
+
+    println!("✅ Incremental validation test passed: height {}", poc.current_block_height);
+}
+
+// ═══════════════════════════════════════════════════════════════
+// MEMPOOL TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_mempool_batches_genomes_into_one_block() {
+    let mut poc = ProofOfConsciousness::new();
+    poc.max_block_entries = 4;
+
+    for id in 0..6 {
+        let mut genome = conscious_genome(poc.min_consciousness);
+        genome.db_id = Some(id);
+        poc.submit(&genome).unwrap();
+    }
+    assert_eq!(poc.mempool.len(), 6);
+
+    let block = poc.mine_block().unwrap().clone();
+    assert_eq!(block.entries.len(), 4);
+    assert_eq!(block.merkle_root, ConsensusBlock::compute_merkle_root(&block.entries));
+    assert_eq!(poc.mempool.len(), 2);
+
+    // Leftovers were proven for block #1 and are re-proven for #2
+    let block = poc.mine_block().unwrap().clone();
+    assert!(block.entries.iter().all(|e| e.proof.block_height == 2 && e.proof.verify(0)));
+    assert!(poc.mempool.is_empty());
+    assert!(poc.mine_block().is_none());
+    assert_eq!(poc.proofs_validated, 6);
+    assert!(poc.validate_chain());
+
+    println!("✅ Mempool test passed: 6 genomes in {} blocks", poc.current_block_height);
+}
+
+#[test]
+fn test_duplicate_genome_rejected() {
+    let mut poc = ProofOfConsciousness::new();
+    let genome = conscious_genome(poc.min_consciousness);
+
+    poc.submit(&genome).unwrap();
+    assert!(poc.submit(&genome).is_err());
+
+    poc.mine_block().unwrap();
+    assert!(poc.submit(&genome).is_err(), "already on chain");
+}
+
+#[test]
+fn test_received_block_clears_mempool() {
+    let mut miner = ProofOfConsciousness::new();
+    let mut peer = ProofOfConsciousness::new();
+    peer.validators.register(&miner.validator_public_key().unwrap());
+    let genome = conscious_genome(miner.min_consciousness);
+
+    miner.submit(&genome).unwrap();
+    peer.submit(&genome).unwrap();
+
+    let block = miner.mine_block().unwrap().clone();
+    peer.add_block(block).unwrap();
+    assert!(peer.mempool.is_empty());
+}
+
+#[test]
+fn test_block_reattesting_genomes_rejected() {
+    let mut miner = ProofOfConsciousness::new();
+    let mut peer = ProofOfConsciousness::new();
+    peer.validators.register(&miner.validator_public_key().unwrap());
+    let key = ValidatorKey::generate();
+    peer.validators.register(&key.public_key_hex());
+    let first = mine_one(&mut miner, 1);
+    assert_eq!(peer.add_block(first.clone()).unwrap(), BlockOutcome::Extended);
+
+    let (coinbase, difficulty) = (peer.coinbase("divine_attacker"), peer.difficulty);
+    let signed = |entries| {
+        let mut block = ConsensusBlock::mine(&first, entries, difficulty, coinbase.clone());
+        block.sign(&key);
+        block
+    };
+    let mut fresh = miner.clone();
+    fresh.submit(&conscious_genome(fresh.min_consciousness)).unwrap();
+    let new_entry = fresh.mempool.entries()[0].clone();
+    let mut repeated = first.entries[0].clone();
+    repeated.proof = repeated.proof.at_height(2);
+
+    let err = peer.add_block(signed(vec![repeated.clone(), repeated.clone()])).unwrap_err();
+    assert!(err.contains("twice"), "{}", err);
+    let err = peer.add_block(signed(vec![new_entry.clone(), new_entry.clone()])).unwrap_err();
+    assert!(err.contains("twice"), "{}", err);
+    let err = peer.add_block(signed(vec![repeated.clone()])).unwrap_err();
+    assert!(err.contains("already consecrated"), "{}", err);
+
+    let mut stale = new_entry.clone();
+    stale.proof = stale.proof.at_height(7);
+    let err = peer.add_block(signed(vec![stale])).unwrap_err();
+    assert!(err.contains("made for block #7"), "{}", err);
+    assert_eq!((peer.current_block_height, peer.total_rewards_distributed), (1, first.total_reward() + first.coinbase.amount_rsm));
+
+    // Spliced in past add_block, a re-attestation still fails validation
+    let mut spliced = peer.clone();
+    spliced.chain.push(signed(vec![repeated]));
+    assert!(!spliced.validate_chain());
+
+    assert_eq!(peer.add_block(signed(vec![new_entry])).unwrap(), BlockOutcome::Extended);
+    assert!(peer.validate_chain());
+
+    println!("✅ Blocks may attest each genome once, at their own height");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// DIFFICULTY RETARGETING TESTS
+// ═══════════════════════════════════════════════════════════════
+
+/// Mine `count` single-genome blocks spaced `spacing` seconds apart
+fn mine_spaced(poc: &mut ProofOfConsciousness, count: i64, spacing: i64) {
+    let key = ValidatorKey::generate();
+    poc.validators.register(&key.public_key_hex());
+
+    for _ in 0..count {
+        let mut genome = conscious_genome(poc.min_consciousness);
+        genome.db_id = Some(poc.current_block_height as i64 + 1);
+        poc.submit(&genome).unwrap();
+
+        let entries = poc.mempool.take(poc.max_block_entries);
+        let timestamp = poc.latest_block().timestamp + spacing;
+        let coinbase = poc.coinbase("divine_spaced_miner");
+        let mut block = ConsensusBlock::mine_at(poc.latest_block(), entries, poc.difficulty, coinbase, timestamp);
+        block.sign(&key);
+        poc.add_block(block).unwrap();
+    }
+}
+
+#[test]
+fn test_fast_blocks_raise_difficulty() {
+    let mut poc = ProofOfConsciousness::new();
+    poc.retarget_interval = 3;
+
+    mine_spaced(&mut poc, 5, 1);
+    assert_eq!(poc.difficulty, DEFAULT_DIFFICULTY, "no retarget before 2 full windows");
+
+    mine_spaced(&mut poc, 1, 1);
+    assert_eq!(poc.difficulty, DEFAULT_DIFFICULTY + MAX_RETARGET_STEP as u32);
+    assert!(poc.validate_chain());
+
+    println!("✅ Retarget up: {} → {} bits", DEFAULT_DIFFICULTY, poc.difficulty);
+}
+
+#[test]
+fn test_slow_blocks_lower_difficulty() {
+    let mut poc = ProofOfConsciousness::new();
+    poc.retarget_interval = 3;
+
+    // 4x the 60s target → 4x less work (two bits)
+    mine_spaced(&mut poc, 6, 240);
+    assert_eq!(poc.difficulty, DEFAULT_DIFFICULTY - 2);
+
+    mine_spaced(&mut poc, 3, 600);
+    assert_eq!(poc.difficulty, MIN_DIFFICULTY);
+    assert!(poc.validate_chain());
+}
+
+#[test]
+fn test_wrong_difficulty_rejected() {
+    let mut poc = ProofOfConsciousness::new();
+    let genome = conscious_genome(poc.min_consciousness);
+    poc.submit(&genome).unwrap();
+
+    let entries = poc.mempool.take(poc.max_block_entries);
+    let coinbase = poc.coinbase("divine_easy_miner");
+    let mut easy = ConsensusBlock::mine(poc.latest_block(), entries, MIN_DIFFICULTY, coinbase);
+    easy.sign(&ValidatorKey::generate());
+    assert!(poc.add_block(easy).is_err());
+    assert_eq!(poc.chain.len(), 1);
+}
+
+// ═══════════════════════════════════════════════════════════════
+// MERKLE INCLUSION PROOF TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_inclusion_proof_for_every_entry() {
+    let mut poc = ProofOfConsciousness::new();
+    let mut hashes = Vec::new();
+
+    // 5 entries → odd levels exercise last-node duplication
+    for id in 0..5 {
+        let mut genome = conscious_genome(poc.min_consciousness);
+        genome.db_id = Some(id);
+        hashes.push(poc.submit(&genome).unwrap().genome_hash);
+    }
+    poc.mine_block().unwrap();
+
+    for hash in &hashes {
+        let proof = poc.generate_inclusion_proof(hash).unwrap();
+        assert_eq!(proof.block_index, 1);
+        assert!(proof.verify());
+        assert!(poc.verify_inclusion_proof(&proof));
+    }
+    assert!(poc.generate_inclusion_proof(&[7u8; 32]).is_none());
+
+    println!("✅ Inclusion proofs verified for {} genomes", hashes.len());
+}
+
+#[test]
+fn test_forged_inclusion_proof_rejected() {
+    let mut poc = ProofOfConsciousness::new();
+    for id in 0..3 {
+        let mut genome = conscious_genome(poc.min_consciousness);
+        genome.db_id = Some(id);
+        poc.submit(&genome).unwrap();
+    }
+    poc.mine_block().unwrap();
+
+    let hash = poc.latest_block().entries[0].proof.genome_hash;
+    let proof = poc.generate_inclusion_proof(&hash).unwrap();
+
+    let mut forged = proof.clone();
+    forged.attestation.proof.reward_rsm *= 10.0;
+    assert!(!forged.verify());
+
+    let mut forged = proof.clone();
+    forged.path[0].sibling = [0u8; 32];
+    assert!(!poc.verify_inclusion_proof(&forged));
+
+    // Consistent proof against a root the chain never committed
+    let mut forged = proof;
+    forged.block_index = 0;
+    assert!(!poc.verify_inclusion_proof(&forged));
+}
+
+// ═══════════════════════════════════════════════════════════════
+// BLOCK SIGNING TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_mined_blocks_are_signed() {
+    let mut poc = ProofOfConsciousness::new();
+    let genome = conscious_genome(poc.min_consciousness);
+    poc.validate(&genome).unwrap();
+
+    let block = poc.latest_block();
+    assert_eq!(Some(block.producer.clone()), poc.validator_public_key());
+    assert!(block.verify_signature());
+    assert!(poc.validate_chain());
+}
+
+#[test]
+fn test_unregistered_producer_rejected() {
+    let mut miner = ProofOfConsciousness::new();
+    let mut peer = ProofOfConsciousness::new();
+    miner.validate(&conscious_genome(miner.min_consciousness)).unwrap();
+    let block = miner.latest_block().clone();
+
+    assert!(peer.add_block(block.clone()).is_err(), "miner not in validator set");
+
+    // Unsigned / re-signed copies fail even once the miner is registered
+    peer.validators.register(&miner.validator_public_key().unwrap());
+    let mut unsigned = block.clone();
+    unsigned.signature.clear();
+    assert!(peer.add_block(unsigned).is_err());
+
+    let mut stolen = block.clone();
+    stolen.signature = hex::encode(ValidatorKey::generate().sign(block.hash.as_bytes()));
+    assert!(peer.add_block(stolen).is_err());
+
+    assert!(peer.add_block(block).is_ok());
+}
+
+#[test]
+fn test_forged_history_fails_validation() {
+    let mut poc = ProofOfConsciousness::new();
+    poc.validate(&conscious_genome(poc.min_consciousness)).unwrap();
+
+    // Attacker re-mines block #1 with a key the chain never registered
+    let attacker = ValidatorKey::generate();
+    let original = &poc.chain[1];
+    let mut forged = ConsensusBlock::mine(&poc.chain[0], original.entries.clone(), original.difficulty, original.coinbase.clone());
+    forged.sign(&attacker);
+    poc.chain[1] = forged;
+    assert!(!poc.validate_chain());
+}
+
+// ═══════════════════════════════════════════════════════════════
+// FORK CHOICE TESTS
+// ═══════════════════════════════════════════════════════════════
+
+/// Two engines sharing genesis that trust each other's producers
+fn competing_miners() -> (ProofOfConsciousness, ProofOfConsciousness) {
+    let mut a = ProofOfConsciousness::new();
+    let mut b = ProofOfConsciousness::new();
+    a.validators.register(&b.validator_public_key().unwrap());
+    b.validators.register(&a.validator_public_key().unwrap());
+    (a, b)
+}
+
+fn mine_one(poc: &mut ProofOfConsciousness, genome_id: i64) -> ConsensusBlock {
+    let mut genome = conscious_genome(poc.min_consciousness);
+    genome.db_id = Some(genome_id);
+    poc.validate(&genome).unwrap();
+    poc.latest_block().clone()
+}
+
+#[test]
+fn test_lighter_fork_stored_as_side_chain() {
+    let (mut a, mut b) = competing_miners();
+    mine_one(&mut a, 1);
+    mine_one(&mut a, 2);
+    let fork = mine_one(&mut b, 3);
+
+    assert_eq!(a.add_block(fork).unwrap(), BlockOutcome::SideChain);
+    assert_eq!(a.current_block_height, 2);
+    assert_eq!(a.side_chain_len(), 1);
+    assert!(a.validate_chain());
+}
+
+#[test]
+fn test_heavier_fork_triggers_reorg() {
+    let (mut a, mut b) = competing_miners();
+    let orphaned = mine_one(&mut a, 1);
+    let pending_genome = orphaned.entries[0].proof.genome_hash;
+
+    let b1 = mine_one(&mut b, 2);
+    let b2 = mine_one(&mut b, 3);
+
+    assert_eq!(a.add_block(b1).unwrap(), BlockOutcome::SideChain, "equal work keeps first-seen tip");
+    let outcome = a.add_block(b2.clone()).unwrap();
+    assert_eq!(outcome, BlockOutcome::Reorg { fork_index: 0, disconnected: 1, connected: 2 });
+
+    assert_eq!(a.latest_block().hash, b2.hash);
+    assert_eq!(a.current_block_height, 2);
+    assert_eq!(a.proofs_validated, 2);
+    assert_eq!(a.min_consciousness, b.min_consciousness);
+    assert!(a.validate_chain());
+
+    // Disconnected attestation goes back to the mempool; old block kept as side chain
+    assert!(a.mempool.contains(&pending_genome));
+    assert_eq!(a.side_chain_len(), 1);
+
+    println!("✅ Reorg test passed: tip {}", &a.latest_block().hash[..16]);
+}
+
+#[test]
+fn test_orphans_connected_when_parent_arrives() {
+    let (mut a, mut b) = competing_miners();
+    let blocks: Vec<ConsensusBlock> = (1..=3).map(|id| mine_one(&mut a, id)).collect();
+
+    assert_eq!(b.add_block(blocks[2].clone()).unwrap(), BlockOutcome::Orphan);
+    assert_eq!(b.add_block(blocks[1].clone()).unwrap(), BlockOutcome::Orphan);
+    assert!(b.add_block(blocks[1].clone()).is_err(), "already held");
+    assert_eq!(b.orphans.len(), 2);
+
+    assert_eq!(b.add_block(blocks[0].clone()).unwrap(), BlockOutcome::Extended);
+    assert_eq!(b.current_block_height, 3);
+    assert!(b.orphans.is_empty());
+    assert_eq!(b.latest_block().hash, a.latest_block().hash);
+    assert!(b.validate_chain());
+
+    println!("✅ Orphan pool test passed: height {}", b.current_block_height);
+}
+
+#[test]
+fn test_orphan_pool_evicts_expired_and_oldest() {
+    let mut poc = ProofOfConsciousness::new();
+    let block = mine_one(&mut poc, 1);
+
+    let mut pool = OrphanPool::new();
+    for i in 0..=MAX_ORPHAN_BLOCKS as i64 {
+        let mut orphan = block.clone();
+        orphan.hash = format!("orphan-{}", i);
+        pool.insert(orphan, i);
+    }
+    assert_eq!(pool.len(), MAX_ORPHAN_BLOCKS);
+    assert!(!pool.contains("orphan-0"));
+
+    assert_eq!(pool.evict_expired(ORPHAN_TIMEOUT_SECS + 50), 50);
+    assert_eq!(pool.take_children(&block.previous_hash).len(), MAX_ORPHAN_BLOCKS - 50);
+    assert!(pool.is_empty());
+}
+
+#[test]
+fn test_invalid_side_block_rejected() {
+    let (mut a, mut b) = competing_miners();
+    mine_one(&mut a, 1);
+    let mut fork = mine_one(&mut b, 2);
+    fork.entries[0].proof.reward_rsm *= 1000.0;
+
+    assert!(a.add_block(fork).is_err());
+    assert_eq!(a.side_chain_len(), 0);
+}
+
+// ═══════════════════════════════════════════════════════════════
+// EXPORT / IMPORT TESTS
+// ═══════════════════════════════════════════════════════════════
+
+fn temp_chain_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("divine_{}_{}.chain", name, hex::encode(rand::random::<[u8; 4]>())))
+}
+
+#[test]
+fn test_export_import_roundtrip() {
+    let mut poc = ProofOfConsciousness::new();
+    for id in 0..3 {
+        mine_one(&mut poc, id);
+    }
+
+    let path = temp_chain_path("roundtrip");
+    poc.export(&path).unwrap();
+    let imported = ProofOfConsciousness::import(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(imported.chain, poc.chain);
+    assert_eq!(imported.current_block_height, 3);
+    assert_eq!(imported.min_consciousness, poc.min_consciousness);
+    assert_eq!(imported.proofs_validated, poc.proofs_validated);
+    assert!(imported.validate_chain());
+
+    println!("✅ Export/import test passed: {} blocks", imported.chain.len());
+}
+
+#[test]
+fn test_import_rejects_corrupted_file() {
+    let mut poc = ProofOfConsciousness::new();
+    mine_one(&mut poc, 1);
+
+    let path = temp_chain_path("corrupt");
+    poc.export(&path).unwrap();
+    let mut bytes = std::fs::read(&path).unwrap();
+
+    // Flipped byte → checksum mismatch
+    let middle = bytes.len() / 2;
+    bytes[middle] ^= 0xff;
+    std::fs::write(&path, &bytes).unwrap();
+    assert!(ProofOfConsciousness::import(&path).is_err());
+
+    std::fs::write(&path, b"not a chain file").unwrap();
+    assert!(ProofOfConsciousness::import(&path).is_err());
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn test_import_revalidates_blocks() {
+    use divine_agi::chain_file::ChainFile;
+
+    let mut poc = ProofOfConsciousness::new();
+    mine_one(&mut poc, 1);
+
+    let path = temp_chain_path("forged");
+    poc.export(&path).unwrap();
+
+    // Well-formed file (valid checksum) carrying a tampered block
+    let mut file = ChainFile::decode(&std::fs::read(&path).unwrap()).unwrap();
+    file.blocks[1].entries[0].proof.reward_rsm *= 1000.0;
+    std::fs::write(&path, file.encode()).unwrap();
+    assert!(ProofOfConsciousness::import(&path).is_err());
+
+    // Producer dropped from the validator set
+    file = ChainFile::decode(&std::fs::read(&path).unwrap()).unwrap();
+    file.blocks = poc.chain.clone();
+    file.validators.clear();
+    std::fs::write(&path, file.encode()).unwrap();
+    assert!(ProofOfConsciousness::import(&path).is_err());
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn test_merge_imported_chain_by_fork_choice() {
+    let (mut a, mut b) = competing_miners();
+    let shared = mine_one(&mut a, 1);
+    b.add_block(shared).unwrap();
+    mine_one(&mut a, 2);
+    mine_one(&mut a, 3);
+
+    // Behind: the missing blocks extend the tip
+    let report = b.merge_blocks(a.chain.clone()).unwrap();
+    assert_eq!(report, MergeReport { known: 1, extended: 2, ..MergeReport::default() });
+    assert_eq!(b.latest_block().hash, a.latest_block().hash);
+    assert!(b.validate_chain());
+    // Importing again changes nothing
+    assert_eq!(b.merge_blocks(a.chain.clone()).unwrap(), MergeReport { known: 3, ..MergeReport::default() });
+
+    // A heavier competing chain takes over; its lighter prefix waits on a side chain
+    let (mut c, mut d) = competing_miners();
+    mine_one(&mut c, 1);
+    for id in 10..13 {
+        mine_one(&mut d, id);
+    }
+    let report = c.merge_blocks(d.chain.clone()).unwrap();
+    assert_eq!((report.side_chain, report.reorgs, report.extended), (1, 1, 1));
+    assert_eq!(c.latest_block().hash, d.latest_block().hash);
+
+    // Unknown producers are refused, foreign networks entirely
+    let mut stranger = ProofOfConsciousness::new();
+    mine_one(&mut stranger, 20);
+    let report = a.merge_blocks(stranger.chain.clone()).unwrap();
+    assert_eq!(report.rejected.len(), 1);
+    assert_eq!(report.rejected[0].0, 1);
+    let mut foreign = stranger.chain.clone();
+    foreign[0].timestamp += 1;
+    assert!(a.merge_blocks(foreign).unwrap_err().contains("foreign genesis"));
+
+    println!("✅ Chain merge: behind nodes catch up, heavier imports reorg");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// GENESIS CONFIG TESTS
+// ═══════════════════════════════════════════════════════════════
+
+fn write_genesis(json: &str) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!("divine_genesis_{}.json", hex::encode(rand::random::<[u8; 4]>())));
+    std::fs::write(&path, json).unwrap();
+    path
+}
+
+#[test]
+fn test_genesis_config_defines_network() {
+    let path = write_genesis(r#"{
+        "chain_id": "divine-testnet",
+        "initial_difficulty": 6,
+        "min_consciousness": 3000,
+        "allocations": [{ "address": "divine_founder", "amount_rsm": 1000.0 }]
+    }"#);
+    let mut testnet = ProofOfConsciousness::from_genesis_config(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(testnet.genesis.chain_id, "divine-testnet");
+    assert_eq!(testnet.genesis.premined_rsm(), 1000.0);
+    assert_eq!(testnet.difficulty, 6);
+    assert_eq!(testnet.min_consciousness, 3000);
+    assert_ne!(testnet.chain[0], ConsensusBlock::genesis());
+
+    let block = mine_one(&mut testnet, 1);
+    assert_eq!(block.difficulty, 6);
+    assert!(testnet.validate_chain());
+
+    // Dev-network blocks do not connect to the testnet genesis
+    let mut devnet = ProofOfConsciousness::new();
+    testnet.validators.register(&devnet.validator_public_key().unwrap());
+    let mut genome = conscious_genome(3000);
+    genome.db_id = Some(2);
+    devnet.validate(&genome).unwrap();
+    assert!(testnet.add_block(devnet.latest_block().clone()).is_err());
+}
+
+#[test]
+fn test_invalid_genesis_config_rejected() {
+    let path = write_genesis(r#"{ "chain_id": "bad", "initial_difficulty": 500 }"#);
+    assert!(ProofOfConsciousness::from_genesis_config(&path).is_err());
+
+    std::fs::write(&path, r#"{ "chain_id": "bad", "allocations": [{ "address": "x", "amount_rsm": -1.0 }] }"#).unwrap();
+    assert!(ProofOfConsciousness::from_genesis_config(&path).is_err());
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn test_export_preserves_genesis_config() {
+    use divine_agi::genesis::GenesisConfig;
+
+    let genesis = GenesisConfig { chain_id: "divine-export".into(), initial_difficulty: 5, ..GenesisConfig::default() };
+    let mut poc = ProofOfConsciousness::from_genesis(genesis.clone());
+    mine_one(&mut poc, 1);
+
+    let path = temp_chain_path("genesis");
+    poc.export(&path).unwrap();
+    let imported = ProofOfConsciousness::import(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(imported.genesis, genesis);
+    assert_eq!(imported.chain, poc.chain);
+}
+
+// ═══════════════════════════════════════════════════════════════
+// BLOCK REWARD TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_block_reward_credited_to_producer_wallet() {
+    let mut poc = ProofOfConsciousness::new();
+    poc.reward_address = Some("divine_miner".to_string());
+    mine_one(&mut poc, 1);
+    mine_one(&mut poc, 2);
+
+    let block = poc.latest_block();
+    assert_eq!(block.coinbase.address, "divine_miner");
+    assert_eq!(block.coinbase.amount_rsm, BLOCK_REWARD_RSM);
+
+    let wallet = poc.wallet("divine_miner").unwrap();
+    assert_eq!(wallet.rsm_balance, 2.0 * BLOCK_REWARD_RSM);
+    assert_eq!(wallet.rewards_earned, 2.0 * BLOCK_REWARD_RSM);
+    assert!(wallet.transactions.iter().any(|t| t.starts_with("BLOCK REWARD #2")));
+}
+
+#[test]
+fn test_reward_halving_schedule() {
+    use divine_agi::genesis::GenesisConfig;
+
+    let genesis = GenesisConfig { block_reward_rsm: 40.0, reward_halving_interval: 2, ..GenesisConfig::default() };
+    assert_eq!(genesis.block_subsidy(0), 0.0);
+    assert_eq!(genesis.block_subsidy(1), 40.0);
+    assert_eq!(genesis.block_subsidy(2), 40.0);
+    assert_eq!(genesis.block_subsidy(3), 20.0);
+    assert_eq!(genesis.block_subsidy(5), 10.0);
+    assert_eq!(genesis.block_subsidy(1_000), 0.0);
+
+    let mut poc = ProofOfConsciousness::from_genesis(genesis);
+    poc.reward_address = Some("divine_halving".to_string());
+    for id in 0..4 {
+        mine_one(&mut poc, id);
+    }
+    assert_eq!(poc.wallet("divine_halving").unwrap().rsm_balance, 120.0);
+}
+
+#[test]
+fn test_inflated_coinbase_rejected() {
+    let mut miner = ProofOfConsciousness::new();
+    let mut peer = ProofOfConsciousness::new();
+
+    let mut genome = conscious_genome(miner.min_consciousness);
+    genome.db_id = Some(1);
+    miner.submit(&genome).unwrap();
+    let entries = miner.mempool.take(miner.max_block_entries);
+    let mut coinbase = miner.coinbase("divine_greedy");
+    coinbase.amount_rsm *= 100.0;
+
+    let key = ValidatorKey::generate();
+    peer.validators.register(&key.public_key_hex());
+    let mut block = ConsensusBlock::mine(miner.latest_block(), entries, miner.difficulty, coinbase);
+    block.sign(&key);
+    assert!(peer.add_block(block).is_err());
+    assert!(peer.wallet("divine_greedy").is_none());
+}
+
+#[test]
+fn test_genesis_allocations_fund_wallets() {
+    use divine_agi::genesis::{GenesisConfig, GenesisAllocation};
+
+    let genesis = GenesisConfig {
+        allocations: vec![GenesisAllocation { address: "divine_founder".into(), amount_rsm: 1_000.0 }],
+        ..GenesisConfig::default()
+    };
+    let mut poc = ProofOfConsciousness::from_genesis(genesis);
+    assert_eq!(poc.wallet("divine_founder").unwrap().rsm_balance, 1_000.0);
+
+    mine_one(&mut poc, 1);
+    poc.reset();
+    assert_eq!(poc.wallet("divine_founder").unwrap().rsm_balance, 1_000.0);
+    assert_eq!(poc.wallets.total_supply_in_wallets(), 1_000.0);
+}
+
+// ═══════════════════════════════════════════════════════════════
+// CHAIN EVENT TESTS
+// ═══════════════════════════════════════════════════════════════
+
+fn drain_events(rx: &mut tokio::sync::broadcast::Receiver<ChainEvent>) -> Vec<ChainEvent> {
+    std::iter::from_fn(|| rx.try_recv().ok()).collect()
+}
+
+#[test]
+fn test_subscribers_receive_block_and_difficulty_events() {
+    let mut poc = ProofOfConsciousness::new();
+    poc.retarget_interval = 3;
+    let mut rx = poc.subscribe();
+
+    mine_spaced(&mut poc, 6, 1);
+    let events = drain_events(&mut rx);
+
+    let added: Vec<u64> = events.iter().filter_map(|e| match e {
+        ChainEvent::BlockAdded(block) => Some(block.index),
+        _ => None,
+    }).collect();
+    assert_eq!(added, vec![1, 2, 3, 4, 5, 6]);
+    assert!(events.contains(&ChainEvent::DifficultyChanged {
+        height: 6, from: DEFAULT_DIFFICULTY, to: DEFAULT_DIFFICULTY + MAX_RETARGET_STEP as u32,
+    }));
+}
+
+#[test]
+fn test_reorg_event_followed_by_connected_blocks() {
+    let (mut a, mut b) = competing_miners();
+    mine_one(&mut a, 1);
+    let fork: Vec<ConsensusBlock> = (2..=3).map(|id| mine_one(&mut b, id)).collect();
+
+    let mut rx = a.subscribe();
+    for block in &fork {
+        a.add_block(block.clone()).unwrap();
+    }
+
+    let events = drain_events(&mut rx);
+    assert_eq!(events.len(), 3, "{:?}", events);
+    assert_eq!(events[0], ChainEvent::Reorg { fork_index: 0, disconnected: 1, connected: 2, new_tip: fork[1].hash.clone() });
+    assert_eq!(events[1], ChainEvent::BlockAdded(fork[0].clone()));
+    assert_eq!(events[2], ChainEvent::BlockAdded(fork[1].clone()));
+}
+
+#[test]
+fn test_blocks_finalized_past_reorg_depth() {
+    let mut poc = ProofOfConsciousness::new();
+    mine_spaced(&mut poc, MAX_REORG_DEPTH as i64, 60);
+    assert_eq!(poc.finalized_height(), 0);
+
+    let mut rx = poc.subscribe();
+    mine_spaced(&mut poc, 2, 60);
+    let finalized: Vec<u64> = drain_events(&mut rx).into_iter().filter_map(|e| match e {
+        ChainEvent::Finalized { index, hash } => {
+            assert_eq!(hash, poc.chain[index as usize].hash);
+            Some(index)
+        }
+        _ => None,
+    }).collect();
+    assert_eq!(finalized, vec![1, 2]);
+    assert_eq!(poc.finalized_height(), 2);
+}
+
+// ═══════════════════════════════════════════════════════════════
+// PRUNING / SNAPSHOT TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_prune_keeps_headers_and_state() {
+    let mut poc = ProofOfConsciousness::new();
+    let genome = conscious_genome(poc.min_consciousness);
+    poc.submit(&genome).unwrap();
+    mine_spaced(&mut poc, MAX_REORG_DEPTH as i64 + 10, 60);
+
+    let consciousness: u64 = poc.chain[1..=10].iter().map(|b| b.total_consciousness()).sum();
+    let (proofs, supply) = (poc.proofs_validated, poc.wallets.total_supply_in_wallets());
+
+    assert!(poc.prune(MAX_REORG_DEPTH - 1).is_err());
+    assert_eq!(poc.prune(MAX_REORG_DEPTH).unwrap(), 10);
+    assert_eq!(poc.prune(MAX_REORG_DEPTH).unwrap(), 0);
+    assert_eq!(poc.pruned_height(), 10);
+    assert!(poc.chain[1..=10].iter().all(|b| b.entries.is_empty()));
+
+    let snapshot = poc.snapshot.as_ref().unwrap();
+    assert_eq!(snapshot.total_consciousness, consciousness);
+    assert_eq!(snapshot.block_hash, poc.chain[10].hash);
+    assert_eq!((poc.proofs_validated, poc.wallets.total_supply_in_wallets()), (proofs, supply));
+
+    // Pruned attestations still count as consecrated
+    assert!(poc.submit(&genome).is_err());
+    assert!(poc.validate_chain());
+    assert!(poc.export(temp_chain_path("pruned")).is_err());
+
+    mine_spaced(&mut poc, 1, 60);
+    assert!(poc.validate_chain());
+
+    println!("✅ Pruning test passed: {} blocks pruned", poc.pruned_height());
+}
+
+#[test]
+fn test_reorg_replays_from_snapshot() {
+    let mut a = ProofOfConsciousness::new();
+    mine_spaced(&mut a, MAX_REORG_DEPTH as i64 + 5, 60);
+    let mut b = a.clone();
+    a.prune(MAX_REORG_DEPTH).unwrap();
+
+    mine_spaced(&mut a, 1, 60);
+    mine_spaced(&mut b, 2, 60);
+    for key in b.validators.keys() {
+        a.validators.register(&key);
+    }
+
+    let height = b.current_block_height as usize;
+    a.add_block(b.chain[height - 1].clone()).unwrap();
+    assert!(matches!(a.add_block(b.chain[height].clone()).unwrap(), BlockOutcome::Reorg { .. }));
+    assert_eq!(a.latest_block().hash, b.latest_block().hash);
+    assert_eq!(a.wallets.total_supply_in_wallets(), b.wallets.total_supply_in_wallets());
+    assert!(a.validate_chain());
+}
+
+#[test]
+fn test_snapshot_export_import_bootstrap() {
+    let mut poc = ProofOfConsciousness::new();
+    poc.prune_depth = Some(MAX_REORG_DEPTH);
+    mine_spaced(&mut poc, MAX_REORG_DEPTH as i64 + 60, 60);
+    assert_eq!(poc.pruned_height(), 50, "pruned automatically in batches");
+
+    let path = temp_chain_path("snapshot");
+    poc.export_snapshot(&path).unwrap();
+    let mut node = ProofOfConsciousness::import_snapshot(&path).unwrap();
+    assert_eq!(node.current_block_height, 50);
+
+    for key in poc.validators.keys() {
+        node.validators.register(&key);
+    }
+    for block in &poc.chain[51..] {
+        node.add_block(block.clone()).unwrap();
+    }
+    assert_eq!(node.latest_block().hash, poc.latest_block().hash);
+    assert_eq!(node.total_rewards_distributed, poc.total_rewards_distributed);
+    assert_eq!(node.wallets.total_supply_in_wallets(), poc.wallets.total_supply_in_wallets());
+
+    // A tampered snapshot header is caught
+    let mut json: serde_json::Value = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+    json["blocks"][7]["nonce"] = serde_json::json!(1);
+    std::fs::write(&path, serde_json::to_vec(&json).unwrap()).unwrap();
+    assert!(ProofOfConsciousness::import_snapshot(&path).is_err());
+}
+
+// ═══════════════════════════════════════════════════════════════
+// FEE MARKET TESTS
+// ═══════════════════════════════════════════════════════════════
+
+/// Network with a 1 RSM minimum fee and a payer funded at genesis
+fn fee_market() -> (ProofOfConsciousness, ValidatorKey) {
+    use divine_agi::consensus::reward_address_for;
+    use divine_agi::genesis::{GenesisConfig, GenesisAllocation};
+
+    let key = ValidatorKey::generate();
+    let genesis = GenesisConfig {
+        min_fee_rsm: 1.0,
+        allocations: vec![GenesisAllocation { address: reward_address_for(&key.public_key_hex()), amount_rsm: 100.0 }],
+        ..GenesisConfig::default()
+    };
+    let mut poc = ProofOfConsciousness::from_genesis(genesis).with_signer(key.clone());
+    poc.reward_address = Some("divine_fee_miner".to_string());
+    (poc, key)
+}
+
+#[test]
+fn test_fee_paid_to_producer() {
+    use divine_agi::consensus::reward_address_for;
+
+    let (mut poc, key) = fee_market();
+    let mut peer = ProofOfConsciousness::from_genesis(poc.genesis.clone());
+    peer.validators.register(&key.public_key_hex());
+
+    let block = mine_one(&mut poc, 1);
+    assert_eq!(block.total_fees(), 1.0);
+    assert!(block.entries[0].verify_fee());
+
+    let payer = reward_address_for(&key.public_key_hex());
+    assert_eq!(poc.wallet(&payer).unwrap().rsm_balance, 99.0);
+    let miner = poc.wallet("divine_fee_miner").unwrap();
+    assert_eq!(miner.rsm_balance, BLOCK_REWARD_RSM + 1.0);
+    assert!(miner.transactions.iter().any(|t| t.starts_with("FEES #1")));
+    assert_eq!(poc.total_rewards_distributed, block.total_reward() + BLOCK_REWARD_RSM);
+
+    // Peers apply the same transfer, and the chain file keeps the fee
+    peer.add_block(block).unwrap();
+    assert_eq!(peer.wallet(&payer).unwrap().rsm_balance, 99.0);
+
+    let path = temp_chain_path("fees");
+    poc.export(&path).unwrap();
+    let imported = ProofOfConsciousness::import(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+    assert_eq!(imported.chain, poc.chain);
+    assert_eq!(imported.wallet("divine_fee_miner").unwrap().rsm_balance, BLOCK_REWARD_RSM + 1.0);
+
+    println!("✅ Fee test passed: {} RSM paid to the producer", imported.chain[1].total_fees());
+}
+
+#[test]
+fn test_fee_rises_with_mempool_pressure() {
+    let (mut poc, _) = fee_market();
+    poc.max_block_entries = 2;
+
+    let mut fees = Vec::new();
+    for _ in 0..5 {
+        fees.push(poc.required_fee());
+        poc.submit(&conscious_genome(poc.min_consciousness)).unwrap();
+    }
+    assert_eq!(fees, vec![1.0, 1.0, 2.0, 2.0, 4.0]);
+    assert_eq!(poc.fee_quote().required_fee_rsm, 4.0);
+    assert!(poc.submit_with_fee(&conscious_genome(poc.min_consciousness), 0.5).is_err());
+
+    // Highest fees are mined first
+    let rich = conscious_genome(poc.min_consciousness);
+    poc.submit_with_fee(&rich, 10.0).unwrap();
+    let block = poc.mine_block().unwrap().clone();
+    assert_eq!(block.entries[0].proof.genome_hash, rich.hash);
+    assert_eq!(block.entries[1].fee_rsm, 4.0);
+}
+
+#[test]
+fn test_invalid_fees_rejected() {
+    let (mut poc, key) = fee_market();
+    let mut peer = ProofOfConsciousness::from_genesis(poc.genesis.clone());
+    peer.validators.register(&key.public_key_hex());
+
+    // Payer cannot promise more than it holds
+    assert!(poc.submit_with_fee(&conscious_genome(poc.min_consciousness), 101.0).is_err());
+
+    poc.submit(&conscious_genome(poc.min_consciousness)).unwrap();
+    let entries = poc.mempool.take(poc.max_block_entries);
+    let mine = |entries| {
+        let mut block = ConsensusBlock::mine(poc.latest_block(), entries, poc.difficulty, poc.coinbase("divine_fee_miner"));
+        block.sign(&key);
+        block
+    };
+
+    let mut forged = entries.clone();
+    forged[0].fee_rsm = 50.0;
+    assert!(peer.add_block(mine(forged)).is_err(), "fee not signed by the payer");
+
+    let mut free = entries.clone();
+    free[0].fee_rsm = 0.0;
+    free[0].payer.clear();
+    free[0].payer_signature.clear();
+    assert!(peer.add_block(mine(free)).is_err(), "below the genesis minimum");
+
+    let broke = ValidatorKey::generate();
+    let unfunded = vec![entries[0].clone().with_fee(&broke, 1.0)];
+    assert!(peer.add_block(mine(unfunded)).is_err(), "payer has no balance");
+
+    peer.add_block(mine(entries)).unwrap();
+}
+
+// ═══════════════════════════════════════════════════════════════
+// METRICS TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_chain_metrics() {
+    let mut poc = ProofOfConsciousness::new();
+    assert_eq!(poc.metrics().window_blocks, 0);
+    assert_eq!(poc.metrics().hash_rate, 0.0);
+
+    mine_spaced(&mut poc, 5, 60);
+    poc.submit(&conscious_genome(poc.min_consciousness)).unwrap();
+
+    let consciousness: Vec<u64> = poc.chain[1..].iter().map(|b| b.total_consciousness()).collect();
+    let metrics = poc.metrics();
+    assert_eq!(metrics.window_blocks, 5);
+    assert_eq!(metrics.avg_block_interval_secs, 60.0);
+    assert_eq!(metrics.hash_rate, (1u64 << DEFAULT_DIFFICULTY) as f64 / 60.0);
+    assert_eq!(metrics.avg_consciousness_per_block, consciousness.iter().sum::<u64>() as f64 / 5.0);
+    assert_eq!(metrics.median_consciousness_per_block, consciousness[2] as f64);
+    assert_eq!((metrics.mempool_depth, metrics.reorg_count), (1, 0));
+
+    let text = metrics.to_prometheus();
+    assert!(text.contains("# TYPE divine_chain_reorgs_total counter"));
+    assert!(text.contains("\ndivine_chain_height 5\n"));
+
+    println!("✅ Metrics test passed: {:.2} H/s", metrics.hash_rate);
+}
+
+#[test]
+fn test_metrics_count_reorgs() {
+    let (mut a, mut b) = competing_miners();
+    mine_one(&mut a, 1);
+    let b1 = mine_one(&mut b, 2);
+    let b2 = mine_one(&mut b, 3);
+
+    a.add_block(b1).unwrap();
+    a.add_block(b2).unwrap();
+    assert_eq!(a.metrics().reorg_count, 1);
+    assert_eq!(b.metrics().reorg_count, 0);
+}
+
+// ═══════════════════════════════════════════════════════════════
+// PROTOCOL UPGRADE TESTS
+// ═══════════════════════════════════════════════════════════════
+
+fn upgraded_genesis(height: u64) -> divine_agi::genesis::GenesisConfig {
+    use divine_agi::upgrades::{ProtocolFeature, UpgradeSchedule};
+
+    divine_agi::genesis::GenesisConfig {
+        upgrades: UpgradeSchedule::new().activate(ProtocolFeature::VersionedHeader, height),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_upgrade_activates_at_height() {
+    let mut poc = ProofOfConsciousness::from_genesis(upgraded_genesis(3));
+    let mut upgraded_peer = ProofOfConsciousness::from_genesis(upgraded_genesis(3));
+    let mut legacy_peer = ProofOfConsciousness::new();
+    assert_eq!(poc.latest_block(), legacy_peer.latest_block(), "schedule is not part of the genesis hash");
+
+    for id in 1..=4 {
+        mine_one(&mut poc, id);
+    }
+    let versions: Vec<u32> = poc.chain.iter().map(|b| b.version).collect();
+    assert_eq!(versions, vec![1, 1, 1, 2, 2]);
+    assert!(poc.validate_chain());
+
+    let key = poc.validator_public_key().unwrap();
+    upgraded_peer.validators.register(&key);
+    legacy_peer.validators.register(&key);
+    for block in &poc.chain[1..] {
+        upgraded_peer.add_block(block.clone()).unwrap();
+    }
+    legacy_peer.add_block(poc.chain[1].clone()).unwrap();
+    legacy_peer.add_block(poc.chain[2].clone()).unwrap();
+    assert!(legacy_peer.add_block(poc.chain[3].clone()).is_err(), "nodes without the schedule split at the activation height");
+
+    let path = temp_chain_path("upgrade");
+    poc.export(&path).unwrap();
+    let imported = ProofOfConsciousness::import(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+    assert_eq!(imported.chain, poc.chain);
+
+    println!("✅ Upgrade test passed: version {} from block #3", poc.latest_block().version);
+}
+
+#[test]
+fn test_block_version_rules() {
+    let mut poc = ProofOfConsciousness::from_genesis(upgraded_genesis(2));
+    let key = ValidatorKey::generate();
+    poc.validators.register(&key.public_key_hex());
+
+    let block_at = |poc: &mut ProofOfConsciousness, version: u32| {
+        poc.submit(&conscious_genome(poc.min_consciousness)).unwrap();
+        let entries = poc.mempool.take(poc.max_block_entries);
+        let mut block = ConsensusBlock::mine_versioned(
+            poc.latest_block(), entries, Vec::new(), poc.difficulty, poc.coinbase("divine_versioned"), 0, version,
+        );
+        block.sign(&key);
+        block
+    };
+
+    let early = block_at(&mut poc, 2);
+    assert!(poc.add_block(early).is_err(), "version 2 before its activation height");
+    let legacy = block_at(&mut poc, 1);
+    poc.add_block(legacy).unwrap();
+
+    let stale = block_at(&mut poc, 1);
+    assert!(poc.add_block(stale).is_err(), "legacy version after activation");
+    let future = block_at(&mut poc, 99);
+    assert!(poc.add_block(future).unwrap_err().contains("upgrade required"));
+
+    let block = block_at(&mut poc, 2);
+    let mut header = block.header();
+    header.version = 1;
+    assert_ne!(header.calculate_hash(), block.hash, "version 2 headers commit to the version");
+    poc.add_block(block).unwrap();
+}
+
+#[test]
+fn test_upgrade_schedule_checks() {
+    use divine_agi::upgrades::{ProtocolFeature, UpgradeSchedule};
+
+    let schedule = UpgradeSchedule::new().activate(ProtocolFeature::VersionedHeader, 10);
+    assert_eq!((schedule.version_at(9), schedule.version_at(10)), (1, 2));
+    assert!(schedule.is_active(ProtocolFeature::VersionedHeader, 10));
+    assert!(schedule.check().is_ok());
+
+    assert!(UpgradeSchedule::new().activate(ProtocolFeature::VersionedHeader, 0).check().is_err());
+    let twice = schedule.clone().activate(ProtocolFeature::VersionedHeader, 20);
+    assert!(twice.check().is_err());
+
+    let json = r#"{ "chain_id": "divine-upgrade", "upgrades": [{ "feature": "VersionedHeader", "activation_height": 10 }] }"#;
+    let genesis: divine_agi::genesis::GenesisConfig = serde_json::from_str(json).unwrap();
+    assert_eq!(genesis.upgrades, schedule);
+}
+
+// ═══════════════════════════════════════════════════════════════
+// CONSCIOUSNESS TARGET TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_consciousness_lowers_pow_target() {
+    use divine_agi::consensus::{pow_target, CONSCIOUSNESS_TARGET_UNIT, MAX_CONSCIOUSNESS_DISCOUNT};
+
+    let base = pow_target(8, 0);
+    let ratio = |c: u64| pow_target(8, c) as f64 / base as f64;
+    assert!(base <= 1u128 << 120 && base > (1u128 << 120) - CONSCIOUSNESS_TARGET_UNIT as u128);
+    assert_eq!(pow_target(9, 0) as f64 / base as f64, 0.5, "one more bit halves the target");
+
+    // Proportional: every unit of consciousness adds one base target
+    assert_eq!(ratio(CONSCIOUSNESS_TARGET_UNIT), 2.0);
+    assert_eq!(ratio(3 * CONSCIOUSNESS_TARGET_UNIT), 4.0);
+    assert_eq!(ratio(CONSCIOUSNESS_TARGET_UNIT / 2), 1.5);
+    assert!(ratio(1_500) > ratio(1_000));
+
+    // Capped, so high consciousness never makes PoW free
+    let cap = MAX_CONSCIOUSNESS_DISCOUNT as f64;
+    assert_eq!(ratio(u64::MAX), cap);
+    assert_eq!(ratio(CONSCIOUSNESS_TARGET_UNIT * MAX_CONSCIOUSNESS_DISCOUNT * 10), cap);
+    assert!(pow_target(64, u64::MAX) > 0);
+}
+
+#[test]
+fn test_pow_target_saturates_at_low_difficulty() {
+    use divine_agi::consensus::{meets_target, pow_target, CONSCIOUSNESS_TARGET_UNIT, MAX_CONSCIOUSNESS_DISCOUNT, MIN_DIFFICULTY};
+    use divine_agi::upgrades::ProtocolFeature;
+
+    // 2^124 × 256 does not fit in 128 bits: the target saturates instead
+    let cap = MAX_CONSCIOUSNESS_DISCOUNT * CONSCIOUSNESS_TARGET_UNIT;
+    assert_eq!(pow_target(MIN_DIFFICULTY, cap), u128::MAX);
+    assert_eq!(pow_target(MIN_DIFFICULTY, 200_000), u128::MAX);
+    assert!(pow_target(0, 0) > pow_target(MIN_DIFFICULTY, 0), "no shift overflow below the floor either");
+    let unit = CONSCIOUSNESS_TARGET_UNIT as u128;
+    assert_eq!(pow_target(MIN_DIFFICULTY, 0), (1u128 << 124) / unit * unit);
+    assert!(pow_target(9, cap) > pow_target(10, cap));
+
+    // A peer block at the floor is judged, not a panic
+    let version = ProtocolFeature::ConsciousnessTarget.version();
+    assert!(meets_target(&"7".repeat(64), MIN_DIFFICULTY, version, cap));
+}
+
+#[test]
+fn test_consciousness_target_activation() {
+    use divine_agi::consensus::{leading_zero_bits, CONSCIOUSNESS_TARGET_UNIT};
+    use divine_agi::light::LightChain;
+    use divine_agi::upgrades::{ProtocolFeature, UpgradeSchedule};
+
+    let genesis = divine_agi::genesis::GenesisConfig {
+        upgrades: UpgradeSchedule::new().activate(ProtocolFeature::ConsciousnessTarget, 2),
+        ..Default::default()
+    };
+    let mut poc = ProofOfConsciousness::from_genesis(genesis.clone());
+    let mut peer = ProofOfConsciousness::from_genesis(genesis.clone());
+    for id in 1..=3 {
+        mine_one(&mut poc, id);
+    }
+
+    assert_eq!((poc.chain[1].version, poc.chain[1].attested_consciousness), (1, 0));
+    for block in &poc.chain[2..] {
+        assert_eq!(block.version, 3);
+        assert_eq!(block.attested_consciousness, block.total_consciousness());
+    }
+    assert!(poc.validate_chain());
+
+    peer.validators.register(&poc.validator_public_key().unwrap());
+    let mut forged = poc.chain[2].clone();
+    forged.attested_consciousness *= 2;
+    assert!(peer.add_block(poc.chain[1].clone()).is_ok());
+    assert!(peer.add_block(forged).is_err(), "inflated commitment changes the hash");
+    for block in &poc.chain[2..] {
+        peer.add_block(block.clone()).unwrap();
+    }
+
+    // Light clients check the discounted target from headers alone
+    let mut light = LightChain::new(genesis, poc.validators.clone());
+    assert_eq!(light.add_headers(poc.headers_from(1, 10)).unwrap(), 3);
+
+    // With a large discount, valid hashes routinely fall short of the bit difficulty
+    let mut header = poc.chain[3].header();
+    header.difficulty = 16;
+    header.attested_consciousness = 255 * CONSCIOUSNESS_TARGET_UNIT;
+    let mut short = 0;
+    for _ in 0..3 {
+        loop {
+            header.nonce += 1;
+            header.hash = header.calculate_hash();
+            if header.meets_difficulty() {
+                break;
+            }
+        }
+        if leading_zero_bits(&header.hash) < 16 {
+            short += 1;
+        }
+    }
+    assert!(short > 0);
+
+    println!("✅ Consciousness target test passed: {} discounted block(s)", poc.chain.len() - 2);
+}
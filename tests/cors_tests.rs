@@ -0,0 +1,85 @@
+//! CORS Tests for Divine AGI V16 (origin lists, preflights, credentials)
+
+use axum::{body::Body, extract::Request, http::{Method, StatusCode}, routing::get, Router};
+use divine_agi::cors::CorsSettings;
+use tower::ServiceExt;
+
+fn dashboards() -> CorsSettings {
+    CorsSettings {
+        origins: Some(vec!["https://dash.example.com".into(), "https://*.divine.dev".into()]),
+        methods: Some(vec![Method::GET, Method::POST]),
+        headers: None,
+        credentials: true,
+        max_age_secs: 60,
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════
+// SETTINGS TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_cors_origins_and_validation() {
+    let settings = dashboards();
+    assert!(settings.validate().is_ok());
+    assert!(settings.allows_origin("https://dash.example.com"));
+    assert!(settings.allows_origin("https://ops.divine.dev"));
+    assert!(settings.allows_origin("https://a.b.divine.dev"));
+    // Exact scheme and host; the wildcard needs a subdomain
+    assert!(!settings.allows_origin("http://dash.example.com"));
+    assert!(!settings.allows_origin("https://dash.example.com.evil.io"));
+    assert!(!settings.allows_origin("https://divine.dev"));
+    assert!(!settings.allows_origin("https://evildivine.dev"));
+    assert!(!settings.allows_origin("http://ops.divine.dev"));
+
+    // Default: every origin, no credentials (the previous behavior)
+    let open = CorsSettings::default();
+    assert!(open.validate().is_ok() && open.allows_origin("https://anything.io"));
+
+    // Credentials cannot be offered to every origin
+    assert!(CorsSettings { credentials: true, ..CorsSettings::default() }.validate().unwrap_err().contains("CORS_ALLOWED_ORIGINS"));
+    for bad in ["dash.example.com", "https://dash.example.com/app", "https://*.*.divine.dev"] {
+        assert!(CorsSettings { origins: Some(vec![bad.into()]), ..CorsSettings::default() }.validate().is_err(), "{}", bad);
+    }
+
+    println!("✅ CORS origins: exact and wildcard matches, unsafe settings refused");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// LAYER TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_cors_layer_answers_preflights() {
+    let app = Router::new().route("/api/genomes", get(|| async { "[]" })).layer(dashboards().layer().unwrap());
+    let call = |method: Method, origin: &str| {
+        let request = Request::builder().method(method).uri("/api/genomes")
+            .header("origin", origin)
+            .header("access-control-request-method", "POST")
+            .header("access-control-request-headers", "authorization,content-type");
+        app.clone().oneshot(request.body(Body::empty()).unwrap())
+    };
+
+    let preflight = call(Method::OPTIONS, "https://ops.divine.dev").await.unwrap();
+    assert_eq!(preflight.status(), StatusCode::OK);
+    let headers = preflight.headers();
+    assert_eq!(headers["access-control-allow-origin"], "https://ops.divine.dev");
+    assert_eq!(headers["access-control-allow-credentials"], "true");
+    assert_eq!(headers["access-control-allow-methods"], "GET,POST");
+    assert_eq!(headers["access-control-allow-headers"], "authorization,content-type");
+    assert_eq!(headers["access-control-max-age"], "60");
+
+    let read = call(Method::GET, "https://dash.example.com").await.unwrap();
+    assert_eq!(read.headers()["access-control-allow-origin"], "https://dash.example.com");
+    assert!(read.headers()["access-control-expose-headers"].to_str().unwrap().contains("api-version"));
+
+    // Other origins get no CORS headers, so browsers block the response
+    let foreign = call(Method::GET, "https://evil.io").await.unwrap();
+    assert!(foreign.headers().get("access-control-allow-origin").is_none());
+
+    let open = Router::new().route("/", get(|| async { "ok" })).layer(CorsSettings::default().layer().unwrap());
+    let any = open.oneshot(Request::builder().uri("/").header("origin", "https://evil.io").body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(any.headers()["access-control-allow-origin"], "*");
+
+    println!("✅ CORS layer: preflights answered for listed origins only");
+}
@@ -0,0 +1,120 @@
+//! Dashboard Tests for Divine AGI V16 (snapshot rows and panels)
+
+use divine_agi::consensus::ProofOfConsciousness;
+use divine_agi::dashboard::{progress_bar, render, DashboardSnapshot, JobRow, LayerRow, WalletRow, RECENT_BLOCKS};
+use divine_agi::multi_chain::BlockchainLayer;
+use divine_agi::ttrl::{EvolutionJobRecord, EvolutionProgress, JobStatus};
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+
+fn job(id: u64, status: JobStatus, best: Option<u32>) -> EvolutionJobRecord {
+    EvolutionJobRecord {
+        id,
+        genome_id: 42,
+        generations: 200,
+        status,
+        best_consciousness: best,
+        result_genome_id: None,
+        error: None,
+        created_at: 0,
+        updated_at: 0,
+    }
+}
+
+fn snapshot() -> DashboardSnapshot {
+    let poc = ProofOfConsciousness::new();
+    let mut chain = Vec::new();
+    for index in 0..12 {
+        let mut block = poc.latest_block().clone();
+        block.index = index;
+        chain.push(block);
+    }
+    DashboardSnapshot {
+        height: 11,
+        tip: "ab".repeat(32),
+        blocks: DashboardSnapshot::recent_blocks(&chain),
+        jobs: vec![JobRow::new(&job(3, JobStatus::Finished, Some(4321)), None)],
+        genome_count: 1234,
+        tables: vec![("divine_genomes_v15".into(), 1234), ("webhooks".into(), 0)],
+        wallet: Some(WalletRow { address: "DIVINE_wallet".into(), balance_rsm: 12.5, staked_rsm: 2.0 }),
+        layers: LayerRow::from_counts(&[("Bitcoin".into(), 7)]),
+        taken_at: 1_700_000_000,
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════
+// SNAPSHOT TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_snapshot_rows() {
+    let snapshot = snapshot();
+    assert_eq!(snapshot.blocks.len(), RECENT_BLOCKS);
+    assert_eq!((snapshot.blocks[0].index, snapshot.blocks[RECENT_BLOCKS - 1].index), (11, 4), "newest first");
+
+    // Live progress wins over the stored record
+    let progress = EvolutionProgress {
+        generation: 50,
+        generations: 200,
+        best_consciousness: 900,
+        current_consciousness: 880,
+        operator_used: None,
+        mutations_used: 3,
+        elapsed_secs: 1.0,
+        eta_secs: 3.0,
+        finished: false,
+        error: None,
+        genome_id: None,
+    };
+    let running = JobRow::new(&job(4, JobStatus::Running, None), Some(&progress));
+    assert_eq!((running.generation, running.best_consciousness, running.ratio()), (50, Some(900), 0.25));
+    let finished = JobRow::new(&job(3, JobStatus::Finished, Some(4321)), None);
+    assert_eq!((finished.generation, finished.ratio()), (200, 1.0));
+    assert_eq!(JobRow::new(&job(5, JobStatus::Queued, None), None).ratio(), 0.0);
+
+    // Every layer is listed, named as stored
+    let layers = LayerRow::from_counts(&[("Bitcoin".into(), 7), ("lightning".into(), 2), ("Dogecoin".into(), 9)]);
+    assert_eq!(layers.len(), BlockchainLayer::ALL.len());
+    let count = |layer| layers.iter().find(|row| row.layer == layer).unwrap().archives;
+    assert_eq!((count(BlockchainLayer::Bitcoin), count(BlockchainLayer::Lightning), count(BlockchainLayer::Solana)), (7, 2, 0));
+
+    assert_eq!(progress_bar(0.5, 8), "████░░░░");
+    assert_eq!(progress_bar(2.0, 4), "████");
+
+    // The API sends the same snapshot
+    let json = serde_json::to_string(&snapshot).unwrap();
+    assert_eq!(serde_json::from_str::<DashboardSnapshot>(&json).unwrap(), snapshot);
+
+    println!("✅ Dashboard: blocks, jobs and layers summarized");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// RENDER TESTS
+// ═══════════════════════════════════════════════════════════════
+
+fn screen(snapshot: Option<&DashboardSnapshot>) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    terminal.draw(|frame| render(frame, snapshot, "local database · updated")).unwrap();
+    let buffer = terminal.backend().buffer();
+    buffer.content().chunks(buffer.area.width as usize)
+        .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn test_render_panels() {
+    let text = screen(Some(&snapshot()));
+    for expected in ["Chain · height 11", "#11", "genesis", "Database", "1234", "TTRL jobs", "Finished", "200/200", "4321",
+                     "Wallet", "12.5000 RSM", "Archives per layer", "Bitcoin", "local database · updated"] {
+        assert!(text.contains(expected), "missing {:?} in\n{}", expected, text);
+    }
+    assert!(!text.contains("webhooks"), "empty tables are left out");
+
+    let mut without_wallet = snapshot();
+    without_wallet.wallet = None;
+    assert!(screen(Some(&without_wallet)).contains("No wallet"));
+    assert!(screen(None).contains("Loading"));
+
+    println!("✅ Dashboard: panels drawn");
+}
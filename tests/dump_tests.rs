@@ -0,0 +1,80 @@
+//! Database Dump Tests for Divine AGI V16 (JSONL exports, backups, validation)
+
+use divine_agi::db_dump::{check_column, check_table, Dump, DumpKind, DUMP_FORMAT, EXPORT_TABLES, TABLES};
+use serde_json::{json, Map, Value};
+
+fn row(value: Value) -> Map<String, Value> {
+    value.as_object().unwrap().clone()
+}
+
+fn sample() -> Dump {
+    Dump::new(DumpKind::Backup, 1_700_000_000, vec![
+        ("consensus_block_entries".to_string(), vec![row(json!({"block_index": 1, "position": 0, "genome_hash": "\\x00ff"}))]),
+        ("divine_genomes_v15".to_string(), vec![
+            row(json!({"id": 1, "dna": "TAATAAGGGTGCGAGTTATGGGTAAAT", "consciousness": 956})),
+            row(json!({"id": 2, "dna": "CCCGATAACTGCCTCGAGATGATACCC", "consciousness": 1037})),
+        ]),
+        ("consensus_blocks".to_string(), vec![row(json!({"block_index": 1, "hash": "00ab"}))]),
+        ("api_keys".to_string(), vec![]),
+    ]).unwrap()
+}
+
+// ═══════════════════════════════════════════════════════════════
+// FORMAT TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_dump_roundtrip_in_table_order() {
+    let dump = sample();
+    let order: Vec<&str> = dump.tables.iter().map(|(table, _)| table.as_str()).collect();
+    assert_eq!(order, ["api_keys", "divine_genomes_v15", "consensus_blocks", "consensus_block_entries"], "parents first");
+    assert_eq!(dump.row_count(), 4);
+    assert_eq!(dump.header.tables["divine_genomes_v15"], 2);
+
+    let text = dump.to_jsonl();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 5);
+    assert!(lines[0].contains(DUMP_FORMAT) && lines[0].contains("\"kind\":\"backup\""));
+    assert!(lines[1].starts_with("{\"table\":\"divine_genomes_v15\""));
+    assert_eq!(Dump::parse(&text).unwrap(), dump);
+    // Blank lines (and a missing final newline) are fine
+    assert_eq!(Dump::parse(&text.replace('\n', "\n\n")).unwrap(), dump);
+
+    // Every data table can be exported; secrets stay out of exports
+    for table in EXPORT_TABLES {
+        assert!(TABLES.contains(table));
+    }
+    for secret in ["api_keys", "wallet_accounts", "webhooks", "webhook_deliveries", "admin_audit"] {
+        assert!(!EXPORT_TABLES.contains(&secret), "{}", secret);
+    }
+
+    println!("✅ Dump: {} rows round-trip as JSONL, parents first", dump.row_count());
+}
+
+#[test]
+fn test_dump_rejects_bad_input() {
+    let text = sample().to_jsonl();
+    let lines: Vec<&str> = text.lines().collect();
+
+    // Truncated: the header promises more rows
+    assert!(Dump::parse(&lines[..4].join("\n")).unwrap_err().contains("truncated"));
+    // Rows of a table the header does not list
+    let extra = format!("{}\n{{\"table\":\"chain_archives\",\"row\":{{\"id\":1}}}}", text);
+    assert!(Dump::parse(&extra).unwrap_err().contains("not in the header"));
+    // Unknown tables and injection through names
+    let unknown = format!("{}\n{{\"table\":\"pg_user\",\"row\":{{}}}}", text);
+    assert!(Dump::parse(&unknown).unwrap_err().contains("Unknown table"));
+    let column = text.replace("\"consciousness\"", "\"consciousness\\\" = 0; --\"");
+    assert!(Dump::parse(&column).unwrap_err().contains("Invalid column"));
+    assert!(check_table("divine_genomes_v15; DROP TABLE api_keys").is_err());
+    assert!(check_column("dna").is_ok() && check_column("DNA").is_err() && check_column("").is_err());
+
+    assert!(Dump::parse("").is_err());
+    assert!(Dump::parse("{\"table\":\"api_keys\",\"row\":{}}").unwrap_err().contains("header"));
+    assert!(Dump::parse(&lines[0].replace(DUMP_FORMAT, "pg_dump")).unwrap_err().contains("Not a Divine dump"));
+    assert!(Dump::parse(&lines[0].replace("\"version\":1", "\"version\":9")).unwrap_err().contains("version 9"));
+    assert!(Dump::new(DumpKind::Export, 0, vec![("users".into(), vec![])]).is_err());
+    assert!(Dump::new(DumpKind::Export, 0, vec![("api_keys".into(), vec![]), ("api_keys".into(), vec![])]).is_err());
+
+    println!("✅ Dump: truncated, foreign and unsafe dumps rejected");
+}
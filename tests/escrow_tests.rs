@@ -0,0 +1,162 @@
+//! Settlement Escrow Tests for Divine AGI V16 (finality release, timeout refund, validation)
+
+use divine_agi::consensus::{ConsensusBlock, ProofOfConsciousness, ValidatorKey, MAX_REORG_DEPTH};
+use divine_agi::escrow::{escrow_address, EscrowAnchor, EscrowStatus, EscrowTerms, MAX_ESCROW_TIMEOUT_SECS, MIN_ESCROW_TIMEOUT_SECS};
+use divine_agi::exchange::RSMExchange;
+use divine_agi::genome::{Genome, GenomeBuilder};
+use divine_agi::rotation::Rot180;
+
+const NOW: i64 = 1_700_000_000;
+const HOUR: i64 = 3600;
+
+fn terms(payee: &str, amount_rsm: f64, anchor: EscrowAnchor, timeout_secs: i64) -> EscrowTerms {
+    EscrowTerms { payee: payee.into(), amount_rsm, anchor, timeout_secs, memo: None }
+}
+
+fn conscious_genome(poc: &ProofOfConsciousness) -> Genome<Rot180> {
+    let mut genome = GenomeBuilder::random().whale_mode().build_storage();
+    genome.consciousness = poc.min_consciousness + 100;
+    genome.db_id = Some(poc.current_block_height as i64 + 1);
+    genome
+}
+
+/// Mine `count` blocks, the first attesting `first` when given
+fn mine(poc: &mut ProofOfConsciousness, key: &ValidatorKey, count: usize, first: Option<&Genome<Rot180>>) {
+    for i in 0..count {
+        let genome = match first {
+            Some(genome) if i == 0 => genome.clone(),
+            _ => conscious_genome(poc),
+        };
+        poc.submit(&genome).unwrap();
+        let entries = poc.mempool.take(poc.max_block_entries);
+        let timestamp = poc.latest_block().timestamp + 60;
+        let coinbase = poc.coinbase("divine_escrow_miner");
+        let mut block = ConsensusBlock::mine_at(poc.latest_block(), entries, poc.difficulty, coinbase, timestamp);
+        block.sign(key);
+        poc.add_block(block).unwrap();
+    }
+}
+
+fn setup() -> (ProofOfConsciousness, ValidatorKey, RSMExchange) {
+    let mut poc = ProofOfConsciousness::new();
+    let key = ValidatorKey::generate();
+    poc.validators.register(&key.public_key_hex());
+    let mut exchange = RSMExchange::new();
+    exchange.set_mark_price(1.0).unwrap();
+    exchange.buy_rsm("divine_payer", 100.0, 0);
+    (poc, key, exchange)
+}
+
+// ═══════════════════════════════════════════════════════════════
+// RELEASE TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_escrow_released_once_its_block_is_final() {
+    let (mut poc, key, mut exchange) = setup();
+    let anchor = EscrowAnchor::next_block(&poc);
+    assert_eq!(anchor, EscrowAnchor::Block { index: 1 });
+    let escrow = exchange.open_escrow("divine_payer", EscrowTerms { memo: Some("genome #7".into()), ..terms("divine_payee", 40.0, anchor, 24 * HOUR) }, NOW).unwrap();
+    assert_eq!((escrow.id, escrow.status, escrow.expires_at), (1, EscrowStatus::Held, NOW + 24 * HOUR));
+    assert_eq!(exchange.get_balance("divine_payer"), 60.0);
+    assert_eq!(exchange.get_balance(&escrow_address(1)), 40.0);
+
+    // Mined, but still reorgable: held
+    mine(&mut poc, &key, 1, None);
+    assert!(exchange.resolve_escrows(&poc, NOW + 60).is_empty());
+    mine(&mut poc, &key, MAX_REORG_DEPTH as usize - 1, None);
+    assert_eq!(poc.finalized_height(), 0);
+    assert!(exchange.resolve_escrows(&poc, NOW + HOUR).is_empty());
+
+    // One more block buries it past the reorg depth
+    mine(&mut poc, &key, 1, None);
+    let resolved = exchange.resolve_escrows(&poc, NOW + 2 * HOUR);
+    assert_eq!(resolved.len(), 1);
+    let released = &resolved[0];
+    assert_eq!(released.status, EscrowStatus::Released);
+    assert_eq!(released.block.as_ref().map(|b| (b.index, b.hash.as_str())), Some((1, poc.chain[1].hash.as_str())));
+    assert_eq!(released.transactions.len(), 2, "deposit and release");
+    assert_eq!(exchange.get_balance("divine_payee"), 40.0);
+    assert_eq!(exchange.get_balance(&escrow_address(1)), 0.0);
+
+    assert!(exchange.resolve_escrows(&poc, NOW + 48 * HOUR).is_empty(), "released once, never refunded");
+    assert_eq!(exchange.list_escrows(Some(EscrowStatus::Released), 10).len(), 1);
+    assert!(exchange.list_escrows(Some(EscrowStatus::Held), 10).is_empty());
+
+    println!("✅ Escrow: released once its block is final");
+}
+
+#[test]
+fn test_genome_sale_waits_for_its_attestation() {
+    let (mut poc, key, mut exchange) = setup();
+    let genome = conscious_genome(&poc);
+    let anchor = EscrowAnchor::Attestation { genome_hash: hex::encode(genome.hash) };
+    exchange.open_escrow("divine_payer", terms("divine_seller", 25.0, anchor, 24 * HOUR), NOW).unwrap();
+
+    // Other blocks finalizing does not matter until the genome is attested
+    mine(&mut poc, &key, 5, None);
+    mine(&mut poc, &key, 1, Some(&genome));
+    mine(&mut poc, &key, MAX_REORG_DEPTH as usize - 1, None);
+    assert!(poc.finalized_height() >= 1 && poc.finalized_height() < 6);
+    assert!(exchange.resolve_escrows(&poc, NOW + HOUR).is_empty());
+
+    mine(&mut poc, &key, 1, None);
+    let released = exchange.resolve_escrows(&poc, NOW + HOUR);
+    assert_eq!(released[0].block.as_ref().map(|b| b.index), Some(6), "the attesting block");
+    assert_eq!(exchange.get_balance("divine_seller"), 25.0);
+
+    println!("✅ Escrow: genome sales pay out once the attestation is final");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// REFUND & VALIDATION TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_unfinalized_escrow_refunded_on_timeout() {
+    let (mut poc, key, mut exchange) = setup();
+    let never = EscrowAnchor::Attestation { genome_hash: hex::encode([7u8; 32]) };
+    exchange.open_escrow("divine_payer", terms("divine_payee", 30.0, never, HOUR), NOW).unwrap();
+    let next = EscrowAnchor::next_block(&poc);
+    exchange.open_escrow("divine_payer", terms("divine_payee", 10.0, next, HOUR), NOW).unwrap();
+    mine(&mut poc, &key, 3, None);
+
+    assert!(exchange.resolve_escrows(&poc, NOW + HOUR - 1).is_empty(), "not expired yet");
+    let refunded = exchange.resolve_escrows(&poc, NOW + HOUR);
+    assert_eq!(refunded.iter().map(|e| (e.id, e.status)).collect::<Vec<_>>(), [(1, EscrowStatus::Refunded), (2, EscrowStatus::Refunded)]);
+    assert!(refunded.iter().all(|e| e.block.is_none()));
+    assert_eq!(exchange.get_balance("divine_payer"), 100.0);
+    assert_eq!(exchange.get_balance("divine_payee"), 0.0);
+    assert_eq!(exchange.list_escrows(None, 1)[0].id, 2, "newest first");
+
+    println!("✅ Escrow: refunded when the block is not final in time");
+}
+
+#[test]
+fn test_bad_escrows_refused() {
+    let (poc, _, mut exchange) = setup();
+    let next = EscrowAnchor::next_block(&poc);
+    let open = |exchange: &mut RSMExchange, payee: &str, amount: f64, anchor: EscrowAnchor, timeout: i64| {
+        exchange.open_escrow("divine_payer", terms(payee, amount, anchor, timeout), NOW)
+    };
+
+    assert!(open(&mut exchange, "divine_payer", 1.0, next.clone(), HOUR).is_err(), "to itself");
+    assert!(open(&mut exchange, "two words", 1.0, next.clone(), HOUR).is_err());
+    for amount in [0.0, -1.0, f64::NAN] {
+        assert!(open(&mut exchange, "divine_payee", amount, next.clone(), HOUR).is_err(), "{}", amount);
+    }
+    assert!(open(&mut exchange, "divine_payee", 101.0, next.clone(), HOUR).unwrap_err().contains("Insufficient"));
+    assert!(open(&mut exchange, "divine_payee", 1.0, next.clone(), MIN_ESCROW_TIMEOUT_SECS - 1).is_err());
+    assert!(open(&mut exchange, "divine_payee", 1.0, next.clone(), MAX_ESCROW_TIMEOUT_SECS + 1).is_err());
+    assert!(open(&mut exchange, "divine_payee", 1.0, EscrowAnchor::Attestation { genome_hash: "abc".into() }, HOUR).is_err());
+    assert!(exchange.escrows.is_empty());
+    assert_eq!(exchange.get_balance("divine_payer"), 100.0, "nothing held");
+
+    for status in [EscrowStatus::Held, EscrowStatus::Released, EscrowStatus::Refunded] {
+        assert_eq!(EscrowStatus::from_name(status.name()), Some(status));
+        assert_eq!(serde_json::to_value(status).unwrap(), status.name());
+    }
+    assert_eq!(serde_json::to_value(&next).unwrap(), serde_json::json!({ "block": { "index": 1 } }));
+
+    println!("✅ Escrow: bad payees, amounts, timeouts and anchors refused");
+}
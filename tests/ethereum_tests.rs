@@ -0,0 +1,377 @@
+//! Ethereum Archiving Tests for Divine AGI V16 (RLP, EIP-1559 signing, fees, nonces, confirmations, wallet keys and transfers)
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use axum::{extract::State, routing::post, Json, Router};
+use serde_json::{json, Value};
+
+use divine_agi::ethereum::*;
+use divine_agi::genome::{hash_genome_dna, GenomeBuilder};
+use divine_agi::multi_chain::{BlockchainLayer, MultiChainArchiver};
+use divine_agi::wallet::{Asset, DivineWallet, Network, SignedTransfer, TokenAmount};
+
+/// Hardhat's first dev account
+const DEV_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+const DEV_ADDRESS: &str = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+const TEST_PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+fn config() -> EthConfig {
+    EthConfig {
+        rpc_url: "https://sepolia.example".into(),
+        network: EthNetwork::Sepolia,
+        mode: EthArchiveMode::Calldata,
+        registry: None,
+        confirmations: 3,
+        max_fee_gwei: DEFAULT_MAX_FEE_GWEI,
+        priority_fee_gwei: None,
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════
+// ENCODING & SIGNING TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_keccak_addresses_and_rlp() {
+    assert_eq!(hex::encode(keccak256(b"")), "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470");
+    assert_eq!(function_selector("transfer(address,uint256)"), [0xa9, 0x05, 0x9c, 0xbb]);
+
+    let signer = EthSigner::from_hex(DEV_KEY).unwrap();
+    assert_eq!(format_address(&signer.address), DEV_ADDRESS, "EIP-55 checksum");
+    assert_eq!(parse_address(DEV_ADDRESS), Ok(signer.address));
+    assert!(parse_address("0x1234").is_err());
+    assert!(!format!("{:?}", signer).contains("ac0974"), "Debug never shows the key");
+    assert!(EthSigner::from_hex("0x00").is_err());
+
+    assert_eq!(rlp_bytes(b"dog"), hex::decode("83646f67").unwrap());
+    assert_eq!(rlp_list(&[rlp_bytes(b"cat"), rlp_bytes(b"dog")]), hex::decode("c88363617483646f67").unwrap());
+    assert_eq!(rlp_bytes(b""), [0x80]);
+    assert_eq!(rlp_bytes(&[0x7f]), [0x7f]);
+    assert_eq!(rlp_uint(0), [0x80]);
+    assert_eq!(rlp_uint(1024), [0x82, 0x04, 0x00]);
+    assert_eq!(rlp_list(&[]), [0xc0]);
+    let long = [b'a'; 56];
+    assert_eq!(rlp_bytes(&long)[..2], [0xb8, 56]);
+
+    println!("✅ Ethereum: keccak, checksum addresses and RLP match the spec vectors");
+}
+
+#[test]
+fn test_eip1559_transactions_signed_by_the_archiver() {
+    let signer = EthSigner::from_hex(DEV_KEY).unwrap();
+    let dna_hash = hash_genome_dna("ATCGATCGATCGATCGATCGATCGATC");
+    let tx = Eip1559Tx {
+        chain_id: EthNetwork::Sepolia.chain_id(),
+        nonce: 7,
+        max_priority_fee_per_gas: gwei_to_wei(2.0),
+        max_fee_per_gas: gwei_to_wei(62.0),
+        gas_limit: 25_000,
+        to: signer.address,
+        value: 0,
+        data: archive_calldata(EthArchiveMode::Calldata, dna_hash),
+    };
+
+    let signed = tx.sign(&signer);
+    assert_eq!(signed.raw[0], 0x02, "typed transaction");
+    assert_eq!(signed.hash, keccak256(&signed.raw));
+    assert_eq!(recover_address(tx.signing_hash(), &signed.signature), Ok(signer.address));
+    assert!(signed.signature[64] <= 1, "y parity, not a legacy v");
+    assert_eq!(tx.sign(&signer), signed, "deterministic (RFC 6979)");
+    assert_ne!(Eip1559Tx { nonce: 8, ..tx.clone() }.sign(&signer).hash, signed.hash);
+
+    // The hash reads back out of either kind of calldata
+    assert_eq!(tx.data.len(), CALLDATA_TAG.len() + 32);
+    assert_eq!(decode_archive_calldata(&tx.data), Some(dna_hash));
+    let event = archive_calldata(EthArchiveMode::Event, dna_hash);
+    assert_eq!(event[..4], function_selector(ARCHIVE_FUNCTION));
+    assert_eq!(decode_archive_calldata(&event), Some(dna_hash));
+    assert_eq!(decode_archive_calldata(b"DIVINE"), None);
+
+    println!("✅ Ethereum: EIP-1559 archives signed locally and readable back");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// FEE, NONCE & CONFIRMATION TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_fees_capped_and_nonces_counted() {
+    let fees = Eip1559Fees::new(gwei_to_wei(30.0), gwei_to_wei(2.0), gwei_to_wei(100.0)).unwrap();
+    assert_eq!((fees.max_fee_per_gas, fees.max_priority_fee_per_gas), (gwei_to_wei(62.0), gwei_to_wei(2.0)));
+    assert_eq!(Eip1559Fees::new(gwei_to_wei(30.0), gwei_to_wei(2.0), gwei_to_wei(40.0)).unwrap().max_fee_per_gas, gwei_to_wei(40.0));
+    assert!(Eip1559Fees::new(gwei_to_wei(30.0), gwei_to_wei(2.0), gwei_to_wei(31.0)).unwrap_err().contains("Insufficient fee"));
+    assert_eq!(with_gas_margin(21_000), 25_200);
+
+    let mut nonces = NonceManager::default();
+    assert_eq!(nonces.next(), None, "read from the node first");
+    nonces.sent(5);
+    nonces.sent(6);
+    assert_eq!(nonces.next(), Some(7), "counted locally while the node catches up");
+    nonces.sent(3);
+    assert_eq!(nonces.next(), Some(7));
+    nonces.reset();
+    assert_eq!(nonces.next(), None);
+    assert!(is_nonce_error("eth_sendRawTransaction: nonce too low: next nonce 9, tx nonce 7"));
+    assert!(is_nonce_error("already known"));
+    assert!(!is_nonce_error("insufficient funds for gas * price + value"));
+
+    println!("✅ Ethereum: EIP-1559 fees capped, nonces counted locally");
+}
+
+#[test]
+fn test_receipts_confirm_archives() {
+    let mut tx: EthArchiveTx = serde_json::from_value(serde_json::json!({
+        "tx_hash": "0xabc", "genome_id": 4, "dna_hash": "00", "network": "sepolia", "mode": "calldata",
+        "from": DEV_ADDRESS, "to": DEV_ADDRESS, "nonce": 0, "gas_limit": 25_200, "max_fee_gwei": 62.0,
+        "priority_fee_gwei": 2.0, "status": "pending", "confirmations": 0, "sent_at": 0, "updated_at": 0
+    })).unwrap();
+    let mined = Receipt { block_number: 100, success: true, gas_used: 21_608, effective_gas_price: gwei_to_wei(31.0) };
+
+    tx.apply_receipt(Some(mined), 100, 3, 10);
+    assert_eq!((tx.status, tx.confirmations, tx.block_number), (EthTxStatus::Included, 1, Some(100)));
+    assert!((tx.fee_gwei.unwrap() - 21_608.0 * 31.0).abs() < 1e-6);
+    assert!(!tx.is_settled());
+
+    tx.apply_receipt(Some(mined), 102, 3, 20);
+    assert_eq!((tx.status, tx.confirmations, tx.updated_at), (EthTxStatus::Confirmed, 3, 20));
+    assert!(tx.is_settled());
+
+    // Reorged out: back to the mempool
+    let mut reorged = tx.clone();
+    reorged.apply_receipt(None, 103, 3, 30);
+    assert_eq!((reorged.status, reorged.block_number, reorged.confirmations), (EthTxStatus::Pending, None, 0));
+
+    tx.apply_receipt(Some(Receipt { success: false, ..mined }), 100, 3, 40);
+    assert_eq!(tx.status, EthTxStatus::Reverted);
+
+    assert_eq!(parse_quantity(&serde_json::json!("0x1a")), Ok(26));
+    assert_eq!(parse_quantity(&serde_json::json!("0x")), Ok(0));
+    assert!(parse_quantity(&serde_json::json!(26)).is_err());
+
+    println!("✅ Ethereum: receipts move archives to included, confirmed or reverted");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// CONFIGURATION TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_networks_configured_and_simulated_by_default() {
+    assert_eq!((EthNetwork::Mainnet.chain_id(), EthNetwork::Sepolia.chain_id()), (1, 11_155_111));
+    assert_eq!(EthNetwork::from_name(" Sepolia "), Ok(EthNetwork::Sepolia));
+    assert!(EthNetwork::from_name("goerli").is_err());
+    assert_eq!(EthArchiveMode::from_name("EVENT"), Ok(EthArchiveMode::Event));
+    assert!(EthNetwork::Mainnet.default_confirmations() > EthNetwork::Sepolia.default_confirmations());
+
+    assert!(config().validate().is_ok());
+    assert!(EthConfig { rpc_url: "ws://node".into(), ..config() }.validate().is_err());
+    assert!(EthConfig { mode: EthArchiveMode::Event, ..config() }.validate().unwrap_err().contains("ETH_REGISTRY_ADDRESS"));
+    assert!(EthConfig { confirmations: 0, ..config() }.validate().is_err());
+    assert!(EthConfig { max_fee_gwei: f64::NAN, ..config() }.validate().is_err());
+    assert!(EthConfig { priority_fee_gwei: Some(150.0), ..config() }.validate().is_err());
+
+    let layer = EthereumLayer::new(config(), EthSigner::from_hex(DEV_KEY).unwrap());
+    assert_eq!(layer.recipient(), layer.address(), "no registry: archives go to the archiver itself");
+    let registry = parse_address("0x5FbDB2315678afecb367f032d93F642f64180aa3").unwrap();
+    assert_eq!(EthereumLayer::new(EthConfig { registry: Some(registry), ..config() }, EthSigner::from_hex(DEV_KEY).unwrap()).recipient(), registry);
+
+    // Without ETH_RPC_URL the layer is simulated
+    let mut archiver = MultiChainArchiver::new();
+    assert!(archiver.ethereum.is_none());
+    let entry = archiver.archive_to(&GenomeBuilder::random().build_storage(), BlockchainLayer::Ethereum).await.unwrap();
+    assert!(BlockchainLayer::Ethereum.valid_tx_reference(entry.tx_hash.as_deref().unwrap()));
+    assert!(archiver.ethereum_txs(10).unwrap_err().contains("ETH_RPC_URL"));
+
+    println!("✅ Ethereum: Sepolia/mainnet settings validated, simulated without them");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// WALLET TESTS
+// ═══════════════════════════════════════════════════════════════
+
+/// What the local JSON-RPC node answers, and the transactions sent to it
+#[derive(Default)]
+struct Node {
+    wei: u128,
+    /// Held and decimals by lowercase token contract
+    tokens: HashMap<String, (u128, u8)>,
+    sent: Vec<Vec<u8>>,
+}
+
+const BASE_FEE: u128 = 10_000_000_000;
+const PRIORITY_FEE: u128 = 1_000_000_000;
+const NONCE: u64 = 4;
+const TOKEN_GAS: u64 = 50_000;
+
+async fn rpc(State(node): State<Arc<Mutex<Node>>>, Json(request): Json<Value>) -> Json<Value> {
+    let mut node = node.lock().unwrap();
+    let quantity = |n: u128| json!(format!("0x{:x}", n));
+    let params = &request["params"];
+    let result = match request["method"].as_str().unwrap_or_default() {
+        "eth_chainId" => quantity(EthNetwork::Sepolia.chain_id().into()),
+        "eth_getBalance" => quantity(node.wei),
+        "eth_getBlockByNumber" => json!({ "number": "0x10", "baseFeePerGas": format!("0x{:x}", BASE_FEE) }),
+        "eth_maxPriorityFeePerGas" => quantity(PRIORITY_FEE),
+        "eth_getTransactionCount" => quantity(NONCE.into()),
+        "eth_estimateGas" => quantity(TOKEN_GAS.into()),
+        "eth_call" => {
+            let (balance, decimals) = node.tokens.get(&params[0]["to"].as_str().unwrap().to_lowercase()).copied().unwrap_or_default();
+            let data = hex::decode(params[0]["data"].as_str().unwrap().trim_start_matches("0x")).unwrap();
+            let value = match data[..4] == function_selector(ERC20_DECIMALS) {
+                true => decimals.into(),
+                false => balance,
+            };
+            json!(format!("0x{:064x}", value))
+        }
+        "eth_sendRawTransaction" => {
+            let raw = hex::decode(params[0].as_str().unwrap().trim_start_matches("0x")).unwrap();
+            let hash = format!("0x{}", hex::encode(keccak256(&raw)));
+            node.sent.push(raw);
+            json!(hash)
+        }
+        _ => return Json(json!({ "jsonrpc": "2.0", "id": 1, "error": { "code": -32601, "message": "Method not found" } })),
+    };
+    Json(json!({ "jsonrpc": "2.0", "id": 1, "result": result }))
+}
+
+/// A local Sepolia node; returns its URL
+async fn ethereum_node(node: Arc<Mutex<Node>>) -> String {
+    let app = Router::new().route("/", post(rpc)).with_state(node);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{}", addr)
+}
+
+#[test]
+fn test_wallet_ethereum_keys() {
+    // BIP-32 test vector 1
+    let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+    let key = |path: &[u32]| hex::encode(bip32_secp256k1(&seed, path).unwrap().secret_bytes());
+    assert_eq!(key(&[]), "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35");
+    assert_eq!(key(&[BIP32_HARDENED]), "edb2e14f9ee77d26dd93b4ecede8d16ed408ce149b6cd80b0715a2d911a0afea");
+    assert_eq!(key(&[BIP32_HARDENED, 1]), "3c6cb8d0f6a264c91ea8b5030fadaa8e538b020f0a387421a12de9319dc93368");
+    assert_eq!(key(&[BIP32_HARDENED, 1, 2 | BIP32_HARDENED]), "cbce0d719ecf7431d88e6a89fa1483e02e35092af60c042b1df2ff59fa424dca");
+
+    // The address Ethereum wallets show for the same phrase
+    let wallet = DivineWallet::recover(TEST_PHRASE).unwrap();
+    let signer = wallet.eth_signer(0).unwrap();
+    assert_eq!(format_address(&signer.address), "0x9858EfFD232B4033E47d90003D41EC34EcaEda94");
+    assert_ne!(wallet.eth_signer(1).unwrap().address, signer.address);
+    assert!(DivineWallet::new().eth_signer(0).unwrap_err().contains("no recovery phrase"));
+
+    let to = parse_address(DEV_ADDRESS).unwrap();
+    let calldata = erc20_transfer_calldata(&to, 1_500_000);
+    assert_eq!(calldata.len(), 4 + 64);
+    assert_eq!((&calldata[..4], &calldata[16..36]), (&[0xa9, 0x05, 0x9c, 0xbb][..], &to[..]));
+    assert_eq!(decode_uint(&calldata[36..]), Ok(1_500_000));
+    assert!(decode_uint(&[0xff; 32]).is_err());
+
+    println!("✅ Ethereum: wallet keys derived on m/44'/60'/0'/0/n");
+}
+
+#[tokio::test]
+async fn test_wallet_reads_and_spends_sepolia_balances() {
+    let token = "0x1c7d4b196cb0c7b01d743fbc6116a902379c7238";
+    let node = Arc::new(Mutex::new(Node {
+        wei: 2 * 10u128.pow(18),
+        tokens: HashMap::from([(token.to_string(), (25_000_000, 6))]),
+        sent: Vec::new(),
+    }));
+    let mut wallet = DivineWallet::recover(TEST_PHRASE).unwrap();
+    assert_eq!(Network::from_name("sepolia"), Ok(Network::EthereumSepolia));
+    wallet.set_network(Network::EthereumSepolia);
+    wallet.rpc_url = Some(ethereum_node(node.clone()).await);
+    assert_eq!(wallet.chain_address().unwrap(), "0x9858EfFD232B4033E47d90003D41EC34EcaEda94");
+    wallet.rsm_token = Some(token.to_string());
+
+    let balance = wallet.refresh_balance().await.unwrap();
+    assert_eq!((balance.native, balance.rsm), (TokenAmount::new(2 * 10u128.pow(18), 18), TokenAmount::new(25_000_000, 6)));
+    assert_eq!((wallet.native_display(), wallet.rsm_display()), ("2 ETH".to_string(), "25 RSM".to_string()));
+    let whale = TokenAmount::new(20 * 10u128.pow(18), 18);
+    assert_eq!(serde_json::to_value(whale).unwrap(), json!({ "raw": "20000000000000000000", "decimals": 18 }), "above u64, as a string");
+    assert_eq!(serde_json::from_value::<TokenAmount>(json!({ "raw": 5, "decimals": 9 })).unwrap(), TokenAmount::new(5, 9));
+
+    let signer = wallet.eth_signer(0).unwrap();
+    let to = parse_address(DEV_ADDRESS).unwrap();
+    let hash = wallet.transfer_rsm(DEV_ADDRESS, 1.5).await.unwrap();
+    let contract = parse_address(token).unwrap();
+    let data = erc20_transfer_calldata(&to, 1_500_000);
+    let expected = Eip1559Tx { gas_limit: with_gas_margin(TOKEN_GAS), to: contract, data, ..transfer_fees() }.sign(&signer);
+    assert_eq!(node.lock().unwrap().sent.pop().unwrap(), expected.raw, "an ERC-20 transfer at the node's fees and nonce");
+    assert_eq!(hash, format!("0x{}", hex::encode(expected.hash)));
+    assert_eq!(wallet.rsm_display(), "23.5 RSM");
+    assert!(wallet.transactions.last().unwrap().contains(&hash));
+
+    let hash = wallet.transfer_native(DEV_ADDRESS, 0.5).await.unwrap();
+    let raw = node.lock().unwrap().sent.pop().unwrap();
+    let plain = Eip1559Tx { gas_limit: ETH_TRANSFER_GAS, to, value: 5 * 10u128.pow(17), data: Vec::new(), ..transfer_fees() }.sign(&signer);
+    assert_eq!((raw, hash), (plain.raw.clone(), format!("0x{}", hex::encode(plain.hash))));
+    assert_eq!(wallet.native_display(), "1.5 ETH");
+
+    assert!(wallet.transfer_native(DEV_ADDRESS, 2.0).await.unwrap_err().contains("Insufficient"));
+    assert!(wallet.transfer_rsm(DEV_ADDRESS, 30.0).await.unwrap_err().contains("Insufficient"));
+    assert!(wallet.transfer_rsm("HAgk14JpMQLgt6rVgv7cBQFJWFto5Dqxi472uT3DKpqk", 1.0).await.is_err(), "not an Ethereum address");
+    assert!(wallet.transfer_rsm("0x9858effd232b4033e47d90003d41ec34ecaeda94", 1.0).await.unwrap_err().contains("own address"));
+    assert!(wallet.request_airdrop(1.0).await.is_err());
+    assert!(node.lock().unwrap().sent.is_empty());
+
+    // ERC-20 tokens besides RSM, and watch-only Ethereum addresses
+    assert!(wallet.add_token(&format_address(&contract)).await.unwrap_err().contains("RSM"), "in any case");
+    node.lock().unwrap().tokens.insert(DEV_ADDRESS.to_lowercase(), (7, 0));
+    assert_eq!(wallet.add_token(&DEV_ADDRESS.to_lowercase()).await.unwrap().decimals, 0);
+    wallet.refresh_balance().await.unwrap();
+    assert_eq!(wallet.token_display(DEV_ADDRESS).unwrap(), "7 0xf3…2266");
+
+    let mut watching = DivineWallet::watch_only(&["0x9858effd232b4033e47d90003d41ec34ecaeda94"]).unwrap();
+    assert_eq!(watching.watched().unwrap().ethereum.as_deref(), Some("0x9858EfFD232B4033E47d90003D41EC34EcaEda94"));
+    watching.set_network(Network::EthereumSepolia);
+    watching.rpc_url = wallet.rpc_url.clone();
+    assert_eq!(watching.refresh_balance().await.unwrap().native.display(), "2");
+    assert!(watching.transfer_native(DEV_ADDRESS, 0.1).await.unwrap_err().contains("watch-only"));
+
+    println!("✅ Ethereum: wallet balances read and ETH/ERC-20 sent over JSON-RPC");
+}
+
+#[tokio::test]
+async fn test_transfers_signed_offline() {
+    let node = Arc::new(Mutex::new(Node { wei: 10u128.pow(18), ..Node::default() }));
+    let mut online = DivineWallet::watch_only(&["0x9858EfFD232B4033E47d90003D41EC34EcaEda94"]).unwrap();
+    online.set_network(Network::EthereumSepolia);
+    online.rpc_url = Some(ethereum_node(node.clone()).await);
+
+    let unsigned = online.build_unsigned_transfer(Asset::Native, DEV_ADDRESS, 0.25).await.unwrap();
+    let offline = DivineWallet::recover(TEST_PHRASE).unwrap();
+    let signed = offline.sign_transaction(&unsigned).unwrap();
+    let mut tampered = unsigned.clone();
+    tampered.amount.raw /= 10;
+    assert!(offline.sign_transaction(&tampered).unwrap_err().contains("does not send"), "the tx sends more than described");
+
+    let signed: SignedTransfer = serde_json::from_str(&serde_json::to_string(&signed).unwrap()).unwrap();
+    let hash = online.broadcast_transaction(&signed).await.unwrap();
+    let expected = Eip1559Tx { gas_limit: ETH_TRANSFER_GAS, to: parse_address(DEV_ADDRESS).unwrap(), value: 25 * 10u128.pow(16), ..transfer_fees() }
+        .sign(&offline.eth_signer(0).unwrap());
+    assert_eq!((node.lock().unwrap().sent.pop().unwrap(), hash), (expected.raw, format!("0x{}", hex::encode(expected.hash))));
+
+    let mut forged = signed.clone();
+    forged.signature = format!("0x{}", hex::encode(EthSigner::from_hex(DEV_KEY).unwrap().sign([0; 32])));
+    assert!(online.broadcast_transaction(&forged).await.unwrap_err().contains("signature"));
+    assert!(node.lock().unwrap().sent.is_empty());
+
+    println!("✅ Ethereum: transfers built online, signed offline, broadcast separately");
+}
+
+/// The chain, nonce and fees the local node gives every transfer
+fn transfer_fees() -> Eip1559Tx {
+    Eip1559Tx {
+        chain_id: EthNetwork::Sepolia.chain_id(),
+        nonce: NONCE,
+        max_priority_fee_per_gas: PRIORITY_FEE,
+        max_fee_per_gas: 2 * BASE_FEE + PRIORITY_FEE,
+        gas_limit: 0,
+        to: [0; 20],
+        value: 0,
+        data: Vec::new(),
+    }
+}
@@ -0,0 +1,159 @@
+//! Evolution Job Tests for Divine AGI V16 (multi-generation runs, progress events, job queue)
+
+use divine_agi::genome::GenomeBuilder;
+use divine_agi::rotation::RotationEngine;
+use divine_agi::ttrl::{EvolutionJobRecord, EvolutionJobs, EvolutionProgress, GenomeLineage, JobStatus, TTRLEngine, MAX_FINISHED_JOBS};
+use tokio::sync::RwLock;
+
+// ═══════════════════════════════════════════════════════════════
+// PROGRESS EVENT TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_run_job_publishes_every_generation() {
+    let engine = TTRLEngine::new();
+    let rotation = RwLock::new(RotationEngine::new());
+    let jobs = RwLock::new(EvolutionJobs::new());
+    let base = GenomeBuilder::random().elephant_mode().build_storage();
+    let start = base.consciousness;
+
+    let job_id = jobs.write().await.create();
+    let (latest, mut events) = jobs.read().await.subscribe(job_id).unwrap();
+    assert!(latest.is_none());
+
+    let run = engine.run_job(job_id, base, 20, &rotation, &jobs).await;
+    assert!(run.best.consciousness >= start, "best genome never regresses");
+
+    let mut previous_best = start;
+    for generation in 1..=run.generations_run {
+        let progress = events.try_recv().unwrap();
+        assert_eq!(progress.generation, generation);
+        assert_eq!(progress.generations, 20);
+        assert!(progress.best_consciousness >= previous_best);
+        assert!(progress.operator_used.is_some());
+        assert!(!progress.finished);
+        previous_best = progress.best_consciousness;
+    }
+    if run.generations_run == 20 {
+        assert_eq!(jobs.read().await.subscribe(job_id).unwrap().0.unwrap().eta_secs, 0.0);
+    }
+    assert_eq!(previous_best, run.best.consciousness);
+
+    println!("✅ Evolution job: {} → {} in {} generations", start, run.best.consciousness, run.generations_run);
+}
+
+#[tokio::test]
+async fn test_unknown_job_and_finished_pruning() {
+    let engine = TTRLEngine::new();
+    let rotation = RwLock::new(RotationEngine::new());
+    let jobs = RwLock::new(EvolutionJobs::new());
+    assert!(jobs.read().await.subscribe(42).is_none());
+
+    let mut first = 0;
+    for i in 0..MAX_FINISHED_JOBS {
+        let job_id = jobs.write().await.create();
+        if i == 0 {
+            first = job_id;
+        }
+        let run = engine.run_job(job_id, GenomeBuilder::random().build_storage(), 1, &rotation, &jobs).await;
+        let mut last = jobs.read().await.subscribe(job_id).unwrap().0.unwrap_or_else(|| panic!("job {} published nothing ({:?})", job_id, run.stopped));
+        last.finished = true;
+        jobs.write().await.publish(job_id, last);
+    }
+    assert!(jobs.read().await.subscribe(first).is_some());
+
+    let newest = jobs.write().await.create();
+    assert!(jobs.read().await.subscribe(first).is_none(), "oldest finished job dropped");
+    assert!(jobs.read().await.subscribe(first + 1).is_some());
+    assert!(jobs.read().await.subscribe(newest).is_some());
+
+    println!("✅ Finished evolution jobs capped at {}", MAX_FINISHED_JOBS);
+}
+
+// ═══════════════════════════════════════════════════════════════
+// JOB QUEUE TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_job_status_round_trip() {
+    for status in [JobStatus::Queued, JobStatus::Running, JobStatus::Finished, JobStatus::Failed] {
+        assert_eq!(JobStatus::from_name(status.name()), Some(status));
+    }
+    assert_eq!(JobStatus::from_name("Paused"), None);
+    assert!(!JobStatus::Queued.is_done() && !JobStatus::Running.is_done(), "resumed after a restart");
+    assert!(JobStatus::Finished.is_done() && JobStatus::Failed.is_done());
+
+    let mut job = EvolutionJobRecord {
+        id: 7, genome_id: 3, generations: 50, status: JobStatus::Running,
+        best_consciousness: None, result_genome_id: None, error: None, created_at: 100, updated_at: 100,
+    };
+    let last = EvolutionProgress {
+        generation: 12, generations: 50, best_consciousness: 640, current_consciousness: 640,
+        operator_used: None, mutations_used: 12, elapsed_secs: 0.5, eta_secs: 0.0,
+        finished: true, error: Some("Senescence".into()), genome_id: Some(9),
+    };
+    job.finish(&last, 160);
+    assert_eq!(job.status, JobStatus::Finished, "an early stop still finishes");
+    assert_eq!((job.best_consciousness, job.result_genome_id, job.updated_at), (Some(640), Some(9), 160));
+
+    job.fail("disk full".into(), 170);
+    assert_eq!(job.status, JobStatus::Failed);
+    assert_eq!(job.error.as_deref(), Some("disk full"));
+
+    println!("✅ Job status persisted by name, finish/fail recorded");
+}
+
+#[tokio::test]
+async fn test_registered_ids_do_not_collide() {
+    let jobs = RwLock::new(EvolutionJobs::new());
+
+    // Ids from the job table are tracked as given
+    jobs.write().await.register(41);
+    let (latest, _) = jobs.read().await.subscribe(41).unwrap();
+    assert!(latest.is_none(), "queued jobs have no progress yet");
+
+    // Registering twice keeps the existing channel
+    let (_, mut events) = jobs.read().await.subscribe(41).unwrap();
+    jobs.write().await.register(41);
+    let progress = EvolutionProgress {
+        generation: 1, generations: 2, best_consciousness: 1, current_consciousness: 1,
+        operator_used: None, mutations_used: 0, elapsed_secs: 0.0, eta_secs: 0.0,
+        finished: false, error: None, genome_id: None,
+    };
+    jobs.write().await.publish(41, progress.clone());
+    assert_eq!(events.try_recv().unwrap(), progress);
+
+    assert_eq!(jobs.write().await.create(), 42, "local ids continue after registered ones");
+
+    println!("✅ Registered job ids tracked without collisions");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// LINEAGE TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_lineage_through_jobs() {
+    // 1 ─job 10→ 2 ─job 11→ 3,  1 ─job 12→ 4,  5 unrelated
+    let ancestors = |id: i64| -> Vec<(i64, u64)> {
+        match id {
+            2 => vec![(1, 10)],
+            3 => vec![(2, 11), (1, 10)],
+            4 => vec![(1, 12)],
+            _ => vec![],
+        }
+    };
+    let between = |a: i64, b: i64| GenomeLineage::between(a, &ancestors(a), b, &ancestors(b));
+
+    assert_eq!(between(3, 1), Some(GenomeLineage::Descendant { ancestor: 1, descendant: 3, jobs: vec![10, 11] }));
+    assert_eq!(between(1, 3), between(3, 1), "either order");
+    assert_eq!(between(2, 3), Some(GenomeLineage::Descendant { ancestor: 2, descendant: 3, jobs: vec![11] }));
+    assert_eq!(between(3, 4), Some(GenomeLineage::Common { ancestor: 1, jobs_a: vec![10, 11], jobs_b: vec![12] }));
+    assert_eq!(between(3, 5), None);
+    assert_eq!(between(3, 3), None);
+
+    let json = serde_json::to_value(between(3, 4).unwrap()).unwrap();
+    assert_eq!(json["relation"], "common");
+
+    println!("✅ Lineage found through evolution jobs");
+}
@@ -0,0 +1,196 @@
+//! Exchange Fee Tests for Divine AGI V16 (tiers, maker/taker charges, holds, distribution, reports)
+
+use divine_agi::exchange::{RSMExchange, TransactionType};
+use divine_agi::fees::{export_reports, FeeAccounts, FeeReport, FeeSchedule, FEE_ACCRUAL_WALLET, FEE_REPORT_CSV_HEADER, TREASURY_WALLET};
+use divine_agi::history::ExportFormat;
+use divine_agi::order_book::Side;
+use divine_agi::swap::{Asset, Venue};
+
+const NOW: i64 = 1_700_000_000;
+const DAY: i64 = 24 * 3600;
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+/// Standard fees, a $1 mark and 100 RSM for the seller
+fn setup() -> RSMExchange {
+    let mut exchange = RSMExchange::new();
+    exchange.set_mark_price(1.0).unwrap();
+    exchange.set_fee_schedule(FeeSchedule::standard()).unwrap();
+    exchange.buy_rsm("divine_seller", 100.0, 0);
+    exchange
+}
+
+// ═══════════════════════════════════════════════════════════════
+// SCHEDULE TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_schedules_validated_and_tiered() {
+    let standard = FeeSchedule::standard();
+    assert!(standard.validate().is_ok() && FeeSchedule::default().validate().is_ok());
+    assert_eq!(FeeSchedule::default().tiers[0].taker_fee, 0.0, "a new exchange charges nothing");
+    assert_eq!(standard.treasury_wallet, TREASURY_WALLET);
+    assert_eq!([0.0, 99_999.0, 100_000.0, 5e6, 1e9].map(|volume| standard.tier(volume)), [0, 0, 1, 2, 4]);
+
+    let json = serde_json::to_string(&standard).unwrap();
+    assert_eq!(FeeSchedule::parse(&json).unwrap(), standard);
+    let broken = |edit: fn(&mut FeeSchedule)| {
+        let mut schedule = FeeSchedule::standard();
+        edit(&mut schedule);
+        FeeSchedule::parse(&serde_json::to_string(&schedule).unwrap())
+    };
+    assert!(broken(|s| s.tiers[0].min_volume_usd = 10.0).is_err(), "first tier from 0");
+    assert!(broken(|s| s.tiers[2].min_volume_usd = 50_000.0).is_err(), "tiers rise");
+    assert!(broken(|s| s.tiers[1].maker_fee = 0.01).unwrap_err().contains("maker fee above"));
+    assert!(broken(|s| s.tiers[1].taker_fee = 0.02).is_err(), "over MAX_FEE");
+    assert!(broken(|s| s.tiers[1].maker_fee = -0.001).is_err());
+    assert!(broken(|s| s.split.burn = 0.5).unwrap_err().contains("add up to 1"));
+    assert!(broken(|s| s.treasury_wallet = "two words".into()).is_err());
+    assert!(broken(|s| s.distribution_secs = 5).is_err());
+    assert!(FeeSchedule::parse(r#"{"tiers": []}"#).is_err());
+
+    println!("✅ Fees: schedules validated, tiers picked by volume");
+}
+
+#[test]
+fn test_volume_tiers_roll_over_30_days() {
+    let mut accounts = FeeAccounts::default();
+    accounts.schedule = FeeSchedule::standard();
+    accounts.record_fill("divine_maker", "divine_taker", 60_000.0, 0.0, 0.0, NOW - 29 * DAY);
+    accounts.record_fill("divine_maker", "divine_other", 60_000.0, 0.0, 0.0, NOW);
+
+    assert_eq!(accounts.volume_30d("divine_maker", NOW), 120_000.0, "maker and taker volume both count");
+    assert_eq!(accounts.rates("divine_maker", NOW).taker_fee, 0.0016);
+    assert_eq!(accounts.rates("divine_taker", NOW).taker_fee, 0.0020);
+    assert_eq!(accounts.rates("divine_nobody", NOW).maker_fee, 0.0010);
+
+    // The older fill drops out of the window
+    assert_eq!(accounts.volume_30d("divine_maker", NOW + 2 * DAY), 60_000.0);
+    assert_eq!(accounts.rates("divine_maker", NOW + 2 * DAY).taker_fee, 0.0020);
+    assert_eq!(accounts.maker_volume_usd["divine_maker"], 120_000.0);
+
+    println!("✅ Fees: 30-day volume moves wallets between tiers");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// CHARGING TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_fills_charge_maker_and_taker() {
+    let mut exchange = setup();
+
+    // The sell order holds its RSM and the taker fee on it
+    exchange.place_order("divine_seller", Side::Sell, 1.0, 10.0).unwrap();
+    assert!(close(exchange.get_balance("divine_seller"), 100.0 - 10.0 - 10.0 * 0.002));
+
+    let placement = exchange.place_market_order("divine_buyer", Side::Buy, 10.0).unwrap();
+    assert!(close(exchange.get_balance("divine_buyer"), 10.0 * (1.0 - 0.002)), "taker fee withheld");
+    assert!(close(exchange.get_balance("divine_seller"), 100.0 - 10.0 - 10.0 * 0.001), "maker fee only; the rest of the hold is back");
+    assert!(close(exchange.get_balance(FEE_ACCRUAL_WALLET), 0.03));
+    assert!(exchange.fees.orders.is_empty(), "filled orders hold nothing");
+
+    let kinds: Vec<_> = placement.transactions.iter().map(|tx| (tx.tx_type, tx.from_address.as_str())).collect();
+    assert_eq!(kinds, [(TransactionType::Trade, "divine_seller"), (TransactionType::Fee, "divine_seller"), (TransactionType::Fee, "divine_buyer")]);
+    assert_eq!(placement.transactions[1].memo.as_deref(), Some("maker fee, trade #1"));
+    assert!(close(exchange.fees.maker_fees_rsm, 0.01) && close(exchange.fees.taker_fees_rsm, 0.02));
+
+    // A cancelled order gets its whole hold back
+    let order = exchange.place_order("divine_seller", Side::Sell, 2.0, 20.0).unwrap().order;
+    exchange.cancel_order(order.id).unwrap();
+    assert!(close(exchange.get_balance("divine_seller"), 100.0 - 10.0 - 10.0 * 0.001));
+    assert!(exchange.place_order("divine_seller", Side::Sell, 2.0, exchange.get_balance("divine_seller")).unwrap_err().contains("Insufficient"),
+            "the fee hold needs RSM too");
+
+    println!("✅ Fees: makers and takers pay in RSM, holds released");
+}
+
+#[test]
+fn test_swap_previews_include_the_taker_fee() {
+    let mut exchange = setup();
+    exchange.set_mark_price(100.0).unwrap();
+    exchange.place_order("divine_seller", Side::Sell, 95.0, 10.0).unwrap();
+
+    let quote = exchange.simulate_swap(Asset::Usd, Asset::Rsm, 950.0).unwrap();
+    assert!(close(quote.amount_out, 10.0 * (1.0 - 0.002)));
+    assert!(close(quote.price_impact, 0.0), "fees are not impact");
+    assert_eq!((quote.fees[0].venue, quote.fees[0].asset, quote.fees[0].burned), (Venue::OrderBook, Asset::Rsm, false));
+
+    let placement = exchange.place_market_order("divine_buyer", Side::Buy, 10.0).unwrap();
+    assert_eq!(placement.trades.len(), 1);
+    assert!(close(exchange.get_balance("divine_buyer"), quote.amount_out), "the preview matches the fill");
+
+    println!("✅ Fees: swap previews withhold the book's taker fee");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// DISTRIBUTION TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_distribution_burns_pays_treasury_and_makers() {
+    let mut exchange = setup();
+    exchange.buy_rsm("divine_maker_b", 100.0, 0);
+    exchange.place_order("divine_seller", Side::Sell, 1.0, 30.0).unwrap();
+    exchange.place_order("divine_maker_b", Side::Sell, 1.0, 10.0).unwrap();
+    exchange.place_market_order("divine_buyer", Side::Buy, 40.0).unwrap();
+
+    let accrued = exchange.get_balance(FEE_ACCRUAL_WALLET);
+    assert!(close(accrued, 40.0 * 0.001 + 40.0 * 0.002));
+    let burns = exchange.total_burns;
+    let report = exchange.distribute_fees(NOW).unwrap();
+
+    assert_eq!(report.id, 1);
+    assert!(close(report.distributed_rsm, accrued));
+    assert!(close(report.burned_rsm, accrued * 0.3));
+    assert_eq!((exchange.total_burns, report.burn_id), (burns + 1, Some(burns + 1)));
+    let rewards: Vec<_> = report.lp_rewards.iter().map(|r| (r.wallet.as_str(), r.maker_volume_usd)).collect();
+    assert_eq!(rewards, [("divine_maker_b", 10.0), ("divine_seller", 30.0)], "pro rata to maker volume");
+    assert!(close(report.lp_rewards[1].amount_rsm, accrued * 0.3 * 0.75));
+    assert!(close(report.treasury_rsm, accrued * 0.4));
+    assert!(close(exchange.get_balance(TREASURY_WALLET), accrued * 0.4));
+    assert!(close(exchange.get_balance(FEE_ACCRUAL_WALLET), 0.0));
+    assert_eq!(report.transactions.len(), 3);
+    assert!(report.transactions.iter().all(|tx| tx.from_address == FEE_ACCRUAL_WALLET && tx.memo.as_deref() == Some("fee distribution #1")));
+
+    // A new period starts empty
+    assert!(exchange.fees.maker_volume_usd.is_empty() && exchange.fees.maker_fees_rsm == 0.0);
+    assert_eq!(exchange.fees.period_start, NOW);
+    assert!(exchange.distribute_fees(NOW + 60).unwrap_err().contains("No fees"));
+
+    // Without maker volume the LP share goes to the treasury
+    exchange.transfer("divine_buyer", FEE_ACCRUAL_WALLET, 1.0).unwrap();
+    let second = exchange.distribute_fees(NOW + DAY).unwrap();
+    assert!(second.lp_rewards.is_empty() && close(second.treasury_rsm, 0.7));
+    assert_eq!((second.id, second.period_start), (2, NOW));
+
+    println!("✅ Fees: distributions burn, reward makers and fund the treasury");
+}
+
+#[test]
+fn test_reports_export_as_csv_and_json() {
+    let mut exchange = setup();
+    exchange.place_order("divine_seller", Side::Sell, 1.0, 10.0).unwrap();
+    exchange.place_market_order("divine_buyer", Side::Buy, 10.0).unwrap();
+    exchange.distribute_fees(NOW).unwrap();
+    let reports = exchange.fees.recent_reports(10);
+
+    let csv = export_reports(&reports, ExportFormat::Csv);
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], FEE_REPORT_CSV_HEADER);
+    assert_eq!(lines.len(), 4, "burn, treasury and one LP reward");
+    assert!(lines[1].starts_with(&format!("1,{},{},burn,,", exchange.fees.reports[0].period_start, NOW)));
+    assert!(lines[2].contains(",treasury,DIVINE_TREASURY,"));
+    let reward = &reports[0].lp_rewards[0];
+    assert!(lines[3].ends_with(&format!(",lp_reward,divine_seller,{},10,{}", reward.amount_rsm, reward.tx_hash)));
+
+    let json = export_reports(&reports, ExportFormat::Json);
+    let parsed: Vec<FeeReport> = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.iter().map(|r| (r.id, r.treasury_tx_hash.clone(), r.lp_rewards.len())).collect::<Vec<_>>(),
+               reports.iter().map(|r| (r.id, r.treasury_tx_hash.clone(), r.lp_rewards.len())).collect::<Vec<_>>());
+    assert!(close(parsed[0].treasury_rsm, reports[0].treasury_rsm));
+
+    println!("✅ Fees: distribution reports export as CSV and JSON");
+}
@@ -0,0 +1,99 @@
+//! Import Tests for Divine AGI V16 (FASTA and JSONL genome uploads)
+
+use divine_agi::genome_import::{ImportFormat, ImportParser, ImportRecord, ImportReport, MAX_IMPORT_RECORDS, MAX_REPORTED_ERRORS};
+
+const DNA: &str = "ATGCATGCATGCATGCATGCATGCATG";
+
+/// Feed `data` in `chunk`-byte pieces and collect every record
+fn parse(format: Option<ImportFormat>, data: &str, chunk: usize) -> (Vec<ImportRecord>, Vec<(usize, String)>) {
+    let mut parser = ImportParser::new("upload", format, false);
+    let mut parsed = Vec::new();
+    for piece in data.as_bytes().chunks(chunk) {
+        parsed.extend(parser.feed(piece));
+    }
+    parsed.extend(parser.finish());
+
+    let mut records = Vec::new();
+    let mut errors = Vec::new();
+    for result in parsed {
+        match result {
+            Ok(record) => records.push(record),
+            Err(e) => errors.push((e.line, e.error)),
+        }
+    }
+    (records, errors)
+}
+
+// ═══════════════════════════════════════════════════════════════
+// PARSER TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_fasta_records_survive_chunk_boundaries() {
+    let fasta = format!(">first sample one\n{}\n{}\n\n>second\r\n{}\r\n>third\n", &DNA[..13], &DNA[13..], DNA);
+    for chunk in [1, 3, 7, 4096] {
+        let (records, errors) = parse(None, &fasta, chunk);
+        assert!(errors.is_empty(), "chunk {}: {:?}", chunk, errors);
+        assert_eq!(records.len(), 3, "chunk {}", chunk);
+        assert_eq!((records[0].line, records[0].name.as_deref(), records[0].dna.as_str()), (1, Some("first"), DNA));
+        assert_eq!((records[1].line, records[1].dna.as_str()), (5, DNA));
+        // An empty record is still reported, and fails validation
+        assert_eq!(records[2].dna, "");
+        assert!(records[2].build().is_err());
+    }
+    assert!(parse(Some(ImportFormat::Fasta), &fasta, 64).0[..2].iter().all(|r| r.build().is_ok()));
+
+    let (_, errors) = parse(Some(ImportFormat::Fasta), &format!("{}\n>a\n{}\n", DNA, DNA), 64);
+    assert_eq!(errors, vec![(1, "Sequence before the first > header".to_string())]);
+
+    println!("✅ FASTA import: multi-line records parsed across chunk sizes");
+}
+
+#[test]
+fn test_jsonl_records_are_validated_per_line() {
+    let jsonl = format!(
+        "{{\"dna\": \"{}\", \"name\": \"a\"}}\n{{\"dna\": \"{}\", \"whale\": true}}\nnot json\n{{\"dna\": \"ATGN\"}}\n",
+        DNA, DNA
+    );
+    let (records, errors) = parse(None, &jsonl, 5);
+    assert_eq!(records.len(), 3);
+    assert!(!records[0].whale && records[1].whale);
+    assert_eq!(records[1].build().unwrap().p53_copies, 40);
+    assert!(records[2].build().unwrap_err().contains("Invalid DNA"));
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].0, 3);
+
+    assert_eq!(ImportFormat::detect(Some("set.FASTA"), None), Some(ImportFormat::Fasta));
+    assert_eq!(ImportFormat::detect(Some("set.jsonl"), Some("text/plain")), Some(ImportFormat::Jsonl));
+    assert_eq!(ImportFormat::detect(None, Some("application/x-ndjson")), Some(ImportFormat::Jsonl));
+    assert_eq!(ImportFormat::detect(Some("set.txt"), None), None);
+    let (_, errors) = parse(None, "ACGT\n", 64);
+    assert!(errors[0].1.contains("Neither FASTA"));
+
+    println!("✅ JSONL import: valid rows kept, bad rows reported by line");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// REPORT TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_import_report_counts_and_caps() {
+    let mut report = ImportReport::default();
+    report.imported(vec![4, 5, 6]);
+    for line in 0..(MAX_REPORTED_ERRORS + 10) {
+        let mut parser = ImportParser::new("bad.jsonl", Some(ImportFormat::Jsonl), false);
+        let mut error = parser.feed(b"x\n").remove(0).unwrap_err();
+        error.line = line + 1;
+        report.reject(error);
+    }
+    assert_eq!((report.records, report.imported, report.rejected), (3 + MAX_REPORTED_ERRORS + 10, 3, MAX_REPORTED_ERRORS + 10));
+    assert_eq!(report.errors.len(), MAX_REPORTED_ERRORS);
+    assert_eq!(report.errors[0].file, "bad.jsonl");
+    assert_eq!(report.genome_ids, vec![4, 5, 6]);
+
+    assert!(!report.full(0));
+    assert!(report.full(MAX_IMPORT_RECORDS - report.records));
+
+    println!("✅ Import report: {} imported, {} rejected, {} listed", report.imported, report.rejected, report.errors.len());
+}
@@ -0,0 +1,93 @@
+//! Keystore Tests for Divine AGI V16 (key derivation, sealing, files)
+
+use divine_agi::consensus::reward_address_for;
+use divine_agi::keystore::{derive_validator_key, Keystore, KEYSTORE_VERSION};
+use divine_agi::wallet::{generate_phrase, WALLET_KDF_ITERATIONS};
+
+const PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+// ═══════════════════════════════════════════════════════════════
+// DERIVATION TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_keys_derive_from_phrase_and_index() {
+    let key = derive_validator_key(PHRASE, 0).unwrap();
+    assert_eq!(key.public_key_hex(), derive_validator_key(&format!("  {}\n", PHRASE), 0).unwrap().public_key_hex());
+    assert_eq!(key.public_key_hex().len(), 66, "compressed secp256k1 key");
+
+    // Rotating moves to the next index: a different key from the same phrase
+    let next = derive_validator_key(PHRASE, 1).unwrap();
+    assert_ne!(key.public_key_hex(), next.public_key_hex());
+
+    let other = derive_validator_key(&generate_phrase(24).unwrap(), 0).unwrap();
+    assert_ne!(key.public_key_hex(), other.public_key_hex());
+
+    assert!(derive_validator_key("abandon abandon abandon", 0).is_err());
+    assert!(generate_phrase(13).is_err());
+    assert_eq!(generate_phrase(12).unwrap().split_whitespace().count(), 12);
+
+    println!("✅ Keystore: keys derived from phrase and index");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// SEALING TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_keystore_seal_and_unseal() {
+    let key = derive_validator_key(PHRASE, 2).unwrap();
+    let keystore = Keystore::seal(&key, 2, "correct horse").unwrap();
+    assert_eq!((keystore.version, keystore.index), (KEYSTORE_VERSION, 2));
+    assert_eq!(keystore.public_key, key.public_key_hex());
+    assert_eq!(keystore.address, reward_address_for(&key.public_key_hex()));
+    assert!(!keystore.ciphertext.contains(&key.secret_key_hex()), "the secret is not stored in the clear");
+
+    let opened = keystore.unseal("correct horse").unwrap();
+    assert_eq!(opened.secret_key_hex(), key.secret_key_hex());
+    assert_eq!(keystore.unseal("wrong").unwrap_err(), "Wrong password or corrupted keystore");
+    assert!(Keystore::seal(&key, 2, "").is_err());
+
+    // A swapped public key is caught
+    let mut swapped = keystore.clone();
+    swapped.public_key = derive_validator_key(PHRASE, 3).unwrap().public_key_hex();
+    assert!(swapped.unseal("correct horse").unwrap_err().contains("does not match"));
+
+    let mut future = keystore.clone();
+    future.version = 2;
+    assert!(future.unseal("correct horse").unwrap_err().contains("Unsupported keystore version"));
+
+    // PBKDF2 and its rounds come from the file; a bad count is refused
+    assert_eq!((keystore.kdf.as_str(), keystore.kdf_rounds), ("pbkdf2-sha256", WALLET_KDF_ITERATIONS));
+    let mut fewer = keystore.clone();
+    fewer.kdf_rounds = 1;
+    assert_eq!(fewer.unseal("correct horse").unwrap_err(), "Wrong password or corrupted keystore");
+    fewer.kdf_rounds = 0;
+    assert!(fewer.unseal("correct horse").unwrap_err().contains("iterations"));
+    let mut unknown = keystore.clone();
+    unknown.kdf = "scrypt".into();
+    assert!(unknown.unseal("correct horse").unwrap_err().contains("Unsupported keystore encryption"));
+
+    // Two seals of one key use different salts
+    assert_ne!(Keystore::seal(&key, 2, "correct horse").unwrap().salt, keystore.salt);
+
+    println!("✅ Keystore: sealed and opened with the password");
+}
+
+#[test]
+fn test_keystore_file_round_trip() {
+    let path = std::env::temp_dir().join(format!("divine-keystore-{}.json", std::process::id()));
+    let key = derive_validator_key(PHRASE, 0).unwrap();
+    let keystore = Keystore::seal(&key, 0, "pw").unwrap();
+    keystore.save(&path).unwrap();
+
+    let loaded = Keystore::load(&path).unwrap();
+    assert_eq!(loaded, keystore);
+    assert_eq!(loaded.unseal("pw").unwrap().public_key_hex(), key.public_key_hex());
+
+    std::fs::write(&path, "{\"not\": \"a keystore\"}").unwrap();
+    assert!(Keystore::load(&path).unwrap_err().to_string().contains("not a keystore"));
+    std::fs::remove_file(&path).unwrap();
+
+    println!("✅ Keystore: saved and loaded as JSON");
+}
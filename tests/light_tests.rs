@@ -0,0 +1,84 @@
+//! Light Client Tests for Divine AGI V16
+
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+
+use divine_agi::consensus::{ProofOfConsciousness, ValidatorSet};
+use divine_agi::genesis::GenesisConfig;
+use divine_agi::genome::GenomeBuilder;
+use divine_agi::light::LightChain;
+use divine_agi::network::P2PNode;
+
+fn full_node(blocks: i64) -> (ProofOfConsciousness, Vec<[u8; 32]>) {
+    let mut poc = ProofOfConsciousness::new();
+    let mut hashes = Vec::new();
+    for id in 0..blocks {
+        let mut genome = GenomeBuilder::random().whale_mode().build_storage();
+        genome.consciousness = poc.min_consciousness + 100;
+        genome.db_id = Some(id);
+        hashes.push(poc.validate(&genome).unwrap().genome_hash);
+    }
+    (poc, hashes)
+}
+
+fn light_for(poc: &ProofOfConsciousness) -> LightChain {
+    let mut validators = ValidatorSet::new();
+    validators.register(&poc.validator_public_key().unwrap());
+    LightChain::new(GenesisConfig::default(), validators)
+}
+
+#[test]
+fn test_light_chain_verifies_headers_and_proofs() {
+    let (poc, hashes) = full_node(4);
+    let mut light = light_for(&poc);
+
+    assert_eq!(light.add_headers(poc.headers_from(0, 100)).unwrap(), 4);
+    assert_eq!(light.height(), 4);
+    assert_eq!(light.tip().hash, poc.latest_block().hash);
+
+    for hash in &hashes {
+        let proof = poc.generate_inclusion_proof(hash).unwrap();
+        assert!(light.verify_inclusion_proof(&proof));
+    }
+
+    let mut forged = poc.generate_inclusion_proof(&hashes[0]).unwrap();
+    forged.attestation.proof.consciousness += 1;
+    assert!(!light.verify_inclusion_proof(&forged));
+
+    println!("✅ Light chain test passed: height {}", light.height());
+}
+
+#[test]
+fn test_light_chain_rejects_bad_headers() {
+    let (poc, _) = full_node(2);
+    let headers = poc.headers_from(1, 10);
+
+    // Tampered coinbase breaks the header hash
+    let mut light = light_for(&poc);
+    let mut tampered = headers[0].clone();
+    tampered.coinbase.amount_rsm *= 2.0;
+    assert!(light.add_header(tampered).is_err());
+
+    // Gap in the chain
+    assert!(light.add_header(headers[1].clone()).is_err());
+
+    // Unknown producer
+    let mut stranger = LightChain::new(GenesisConfig::default(), ValidatorSet::new());
+    assert!(stranger.add_header(headers[0].clone()).is_err());
+    assert_eq!(stranger.height(), 0);
+}
+
+#[tokio::test]
+async fn test_light_chain_syncs_from_peer() {
+    let (poc, _) = full_node(3);
+    let mut light = light_for(&poc);
+
+    let addr = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().to_string();
+    let node = Arc::new(P2PNode::new(&addr, Arc::new(RwLock::new(poc)), None));
+    Arc::clone(&node).start().await.unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    assert_eq!(light.sync_from(&addr).await.unwrap(), 3);
+    assert_eq!(light.height(), 3);
+}
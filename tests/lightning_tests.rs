@@ -0,0 +1,150 @@
+//! Lightning Archiving Tests for Divine AGI V16 (keysend records, payment tracking, channel health)
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use sha2::{Digest, Sha256};
+
+use divine_agi::genome::{hash_genome_dna, GenomeBuilder};
+use divine_agi::lightning::{parse_payment_hash, ArchiveRecords, ChannelHealth, ChannelStatus, KeysendPayload, LndConfig, NodeStatus, PaymentStatus, ARCHIVE_RECORD_RECORD, GENOME_HASH_RECORD, KEYSEND_PREIMAGE_RECORD};
+use divine_agi::multi_chain::{BlockchainLayer, MultiChainArchiver, SimulatedFailures};
+use divine_agi::swarm::Swarm;
+
+// ═══════════════════════════════════════════════════════════════
+// KEYSEND RECORD TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_keysend_carries_the_genome_hash() {
+    let dna_hash = hash_genome_dna("ATCGATCGATCGATCGATCGATCGATC");
+    let payload = KeysendPayload::with_preimage([7u8; 32], dna_hash, "DIVINE_GENOME|v15|id:5");
+
+    let payment_hash: [u8; 32] = Sha256::digest([7u8; 32]).into();
+    assert_eq!(payload.payment_hash, payment_hash, "keysend pays the hash of its preimage");
+    assert_eq!(payload.records[&KEYSEND_PREIMAGE_RECORD], vec![7u8; 32]);
+    assert_eq!(payload.records[&GENOME_HASH_RECORD], dna_hash.to_vec());
+    for record in [GENOME_HASH_RECORD, ARCHIVE_RECORD_RECORD] {
+        assert!(record >= 1 << 16 && record % 2 == 1, "custom and safe to ignore");
+    }
+    assert_ne!(KeysendPayload::new(dna_hash, "x").preimage, KeysendPayload::new(dna_hash, "x").preimage);
+
+    // Read back the way a receiving node sees it
+    let archive = ArchiveRecords::decode(&payload.records).unwrap();
+    assert_eq!(archive.genome_hash, hex::encode(dna_hash));
+    assert_eq!(archive.record.as_deref(), Some("DIVINE_GENOME|v15|id:5"));
+    assert!(ArchiveRecords::decode(&HashMap::new()).is_err());
+    assert!(ArchiveRecords::decode(&HashMap::from([(GENOME_HASH_RECORD, vec![1, 2, 3])])).unwrap_err().contains("3 bytes"));
+    assert_eq!(ArchiveRecords::decode(&HashMap::from([(GENOME_HASH_RECORD, vec![0; 32])])).unwrap().record, None);
+
+    let reference = payload.tx_reference();
+    assert!(BlockchainLayer::Lightning.valid_tx_reference(&reference));
+    assert_eq!(parse_payment_hash(&reference), Ok(payment_hash));
+    assert_eq!(parse_payment_hash(&hex::encode(payment_hash)), Ok(payment_hash));
+    assert!(parse_payment_hash("ln_abcd").is_err());
+
+    println!("✅ Lightning: keysends carry the genome hash in a custom TLV record");
+}
+
+#[test]
+fn test_lnd_config_validated() {
+    let config = LndConfig {
+        url: "https://localhost:10009".into(),
+        macaroon_path: PathBuf::from("admin.macaroon"),
+        tls_cert_path: PathBuf::from("tls.cert"),
+        tls_domain: "localhost".into(),
+        amount_sat: 1,
+        fee_limit_sat: 10,
+        timeout_secs: 60,
+    };
+    assert!(config.validate().is_ok());
+    assert!(LndConfig { url: "http://localhost:10009".into(), ..config.clone() }.validate().is_err(), "lnd serves TLS only");
+    assert!(LndConfig { amount_sat: 0, ..config.clone() }.validate().is_err());
+    assert!(LndConfig { fee_limit_sat: -1, ..config.clone() }.validate().is_err());
+    assert!(LndConfig { timeout_secs: 0, ..config }.validate().is_err());
+
+    println!("✅ Lightning: LND settings validated");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// TRACKING & HEALTH TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_simulated_keysends_tracked() {
+    let mut archiver = MultiChainArchiver::new();
+    archiver.swarm = Swarm::from_pubkeys(["02aaaaaaaaaaaaaaaa".into(), "03bbbbbbbbbbbbbbbb".into()], 0);
+    let mut genome = GenomeBuilder::random().build_storage();
+    genome.db_id = Some(9);
+
+    // The same seed fails the same keysends
+    let mut twin = MultiChainArchiver::new();
+    twin.swarm = archiver.swarm.clone();
+    archiver.simulated_failures = SimulatedFailures::seeded(7);
+    twin.simulated_failures = SimulatedFailures::seeded(7);
+    let landed = archiver.archive_to(&genome, BlockchainLayer::Lightning).await.is_ok();
+    assert_eq!(landed, twin.archive_to(&genome, BlockchainLayer::Lightning).await.is_ok());
+    let outcomes = |archiver: &MultiChainArchiver| archiver.payments.iter().map(|p| p.status).collect::<Vec<_>>();
+    assert_eq!(outcomes(&archiver), outcomes(&twin));
+
+    archiver.simulated_failures = SimulatedFailures::Never;
+    let entry = archiver.archive_to(&genome, BlockchainLayer::Lightning).await.unwrap();
+    assert!(!archiver.uses_lnd());
+
+    // Every keysend is tracked; the archive references the ones that landed
+    let tx_hash = entry.tx_hash.unwrap();
+    let latest = archiver.recent_payments(2);
+    let landed: Vec<String> = latest.iter().rev()
+        .filter(|p| p.status == PaymentStatus::Succeeded)
+        .map(|p| format!("ln_{}", p.payment_hash))
+        .collect();
+    assert_eq!(tx_hash, landed.join(","));
+    assert!(archiver.payments.iter().all(|p| p.simulated && p.genome_id == 9 && p.dna_hash == entry.dna_hash && p.amount_sat == 1));
+    assert!(archiver.payments.iter().filter(|p| p.status == PaymentStatus::Failed).all(|p| p.failure_reason.is_some()));
+
+    let first = tx_hash.split(',').next().unwrap();
+    let tracked = archiver.track_payment(first).await.unwrap();
+    assert_eq!((tracked.status, format!("ln_{}", tracked.payment_hash)), (PaymentStatus::Succeeded, first.to_string()));
+    assert!(archiver.track_payment(&hex::encode([9u8; 32])).await.unwrap_err().contains("not found"));
+    assert!(archiver.track_payment("nope").await.is_err());
+
+    // Invoices and channels live in LND
+    assert!(archiver.lookup_invoice(first).await.unwrap_err().contains("needs LND"));
+    assert!(archiver.channel_health().await.unwrap_err().contains("needs LND"));
+
+    println!("✅ Lightning: simulated keysends tracked per node");
+}
+
+#[test]
+fn test_channel_health_flags_problems() {
+    let node = NodeStatus {
+        pubkey: "02aaaa".into(),
+        alias: "divine".into(),
+        version: "0.18.3-beta".into(),
+        block_height: 850_000,
+        synced_to_chain: true,
+        synced_to_graph: true,
+        peers: 3,
+        pending_channels: 0,
+    };
+    let channel = |active: bool, local_balance_sat: i64| ChannelStatus {
+        chan_id: 1,
+        remote_pubkey: "03bbbb".into(),
+        active,
+        capacity_sat: 100_000,
+        local_balance_sat,
+        remote_balance_sat: 100_000 - local_balance_sat,
+    };
+
+    let health = ChannelHealth::assess(node.clone(), vec![channel(true, 40), channel(false, 90_000)], 22);
+    assert!(health.healthy && health.problems.is_empty());
+    assert_eq!((health.active_channels, health.inactive_channels, health.outbound_sat), (1, 1, 40), "inactive channels spend nothing");
+
+    let poor = ChannelHealth::assess(node.clone(), vec![channel(true, 10), channel(false, 90_000)], 22);
+    assert!(!poor.healthy && poor.problems[0].contains("10 sat outbound"));
+
+    let lagging = ChannelHealth::assess(NodeStatus { synced_to_chain: false, synced_to_graph: false, ..node }, vec![channel(false, 50)], 22);
+    assert_eq!(lagging.problems.len(), 3);
+    assert!(lagging.problems[2].contains("No active channels"));
+
+    println!("✅ Lightning: channel health flags sync and liquidity problems");
+}
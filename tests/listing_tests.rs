@@ -0,0 +1,97 @@
+//! Listing Tests for Divine AGI V16 (filters, sorting, cursors)
+
+use std::collections::HashMap;
+
+use divine_agi::exchange::{BurnReason, RSMExchange};
+use divine_agi::listing::{self, Cursor, FilterOp, ListQuery, BURN_FIELDS, GENOME_FIELDS};
+
+fn params(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+}
+
+// ═══════════════════════════════════════════════════════════════
+// QUERY PARSING TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_list_query_parsing() {
+    let query = ListQuery::parse(&params(&[
+        ("filter[consciousness_gte]", "700"),
+        ("filter[p53_copies_eq]", "40"),
+        ("sort", "-created_at"),
+        ("limit", "50"),
+    ]), GENOME_FIELDS).unwrap();
+    assert_eq!(query.filters.len(), 2);
+    assert_eq!((query.filters[0].field.as_str(), query.filters[0].op, query.filters[0].value), ("consciousness", FilterOp::Gte, 700.0));
+    assert_eq!((query.sort.field.as_str(), query.sort.descending, query.limit), ("created_at", true, 50));
+
+    let defaults = ListQuery::parse(&HashMap::new(), GENOME_FIELDS).unwrap();
+    assert_eq!((defaults.sort.field.as_str(), defaults.sort.descending), ("id", true));
+
+    // Only listed fields reach the SQL
+    assert!(ListQuery::parse(&params(&[("sort", "dna; DROP TABLE x")]), GENOME_FIELDS).is_err());
+    assert!(ListQuery::parse(&params(&[("filter[dna_eq]", "1")]), GENOME_FIELDS).is_err());
+    assert!(ListQuery::parse(&params(&[("filter[consciousness]", "1")]), GENOME_FIELDS).is_err());
+    assert!(ListQuery::parse(&params(&[("filter[consciousness_gt]", "high")]), GENOME_FIELDS).is_err());
+    assert!(ListQuery::parse(&params(&[("limit", "0")]), GENOME_FIELDS).is_err());
+    assert!(ListQuery::parse(&params(&[("cursor", "not-a-cursor")]), GENOME_FIELDS).is_err());
+    assert!(ListQuery::parse(&params(&[("page", "2")]), GENOME_FIELDS).is_err());
+
+    // Old genome parameters still work; offsets are replaced by cursors
+    let legacy = listing::parse_genome_query(&params(&[("min_consciousness", "500"), ("min_p53", "20")])).unwrap();
+    assert_eq!(legacy.filters.len(), 2);
+    assert!(listing::parse_genome_query(&params(&[("offset", "20")])).is_err());
+
+    let cursor = Cursor { value: 812.0, id: 42 };
+    assert_eq!(Cursor::decode(&cursor.encode()).unwrap(), cursor);
+
+    println!("✅ List queries: filters, sort and cursor parsed");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// PAGINATION TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_cursor_pages_cover_every_match_once() {
+    let mut exchange = RSMExchange::new();
+    for i in 0..25 {
+        // Equal amounts make the id tie-breaker matter
+        exchange.burn((i % 5) as f64 + 1.0, BurnReason::ManualBurn, Some(i), 0, 0);
+    }
+
+    let mut seen = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let mut query = params(&[("filter[amount_rsm_gte]", "2"), ("sort", "-amount_rsm"), ("limit", "7")]);
+        if let Some(cursor) = cursor {
+            query.insert("cursor".into(), cursor);
+        }
+        let query = ListQuery::parse(&query, BURN_FIELDS).unwrap();
+        let rows = exchange.burn_events.iter().map(|b| (b.id as i64, b)).collect();
+        let page = listing::paginate(rows, &query);
+        assert_eq!(page.total_estimate, 20);
+        assert!(page.data.len() <= 7);
+        seen.extend(page.data.iter().map(|b| (b.amount_rsm, b.id)));
+        cursor = page.next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+
+    assert_eq!(seen.len(), 20);
+    let mut sorted = seen.clone();
+    sorted.sort_by(|a, b| b.0.total_cmp(&a.0).then(b.1.cmp(&a.1)));
+    assert_eq!(seen, sorted, "pages continue in sort order without repeats");
+
+    // New rows do not shift a page that is already being read
+    let first = listing::paginate(exchange.burn_events.iter().map(|b| (b.id as i64, b)).collect(),
+        &ListQuery::parse(&params(&[("limit", "10")]), BURN_FIELDS).unwrap());
+    let (cursor, last_id) = (first.next_cursor.unwrap(), first.data.last().unwrap().id);
+    exchange.burn(9.0, BurnReason::ManualBurn, None, 0, 0);
+    let second = listing::paginate(exchange.burn_events.iter().map(|b| (b.id as i64, b)).collect(),
+        &ListQuery::parse(&params(&[("limit", "10"), ("cursor", cursor.as_str())]), BURN_FIELDS).unwrap());
+    assert_eq!(second.data[0].id + 1, last_id);
+
+    println!("✅ Cursor pages: {} burns in order across pages", seen.len());
+}
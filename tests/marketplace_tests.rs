@@ -0,0 +1,89 @@
+//! Marketplace Tests for Divine AGI V16 (listing validation, browse queries)
+
+use std::collections::HashMap;
+
+use divine_agi::listing::{paginate, FilterOp};
+use divine_agi::marketplace::{check_price, check_wallet, listing_column, parse_listing_query, Listing, ListingStatus, LISTING_FIELDS, MAX_WALLET_LEN};
+
+fn listing(id: i64, price_rsm: f64, consciousness: u32, tg_ratio: f64) -> Listing {
+    Listing {
+        id,
+        genome_id: 100 + id,
+        seller: "divine_seller".into(),
+        price_rsm,
+        status: ListingStatus::Active,
+        buyer: None,
+        dna: "TAATAAGGGTGCGAGTTATGGGTAAAT".into(),
+        consciousness,
+        tg_ratio,
+        created_at: 1_700_000_000 + id,
+        updated_at: 1_700_000_000 + id,
+    }
+}
+
+fn params(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+}
+
+// ═══════════════════════════════════════════════════════════════
+// VALIDATION TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_listing_values_checked() {
+    assert!(check_price(12.5).is_ok());
+    for price in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+        assert!(check_price(price).is_err(), "{}", price);
+    }
+
+    assert!(check_wallet("divine_3f9a").is_ok());
+    for wallet in ["", "two words", "tab\there"] {
+        assert!(check_wallet(wallet).is_err(), "{:?}", wallet);
+    }
+    assert!(check_wallet(&"x".repeat(MAX_WALLET_LEN)).is_ok());
+    assert!(check_wallet(&"x".repeat(MAX_WALLET_LEN + 1)).is_err());
+
+    for status in [ListingStatus::Active, ListingStatus::Sold, ListingStatus::Cancelled] {
+        assert_eq!(ListingStatus::from_name(status.name()), Some(status));
+        assert_eq!(serde_json::to_value(status).unwrap(), status.name());
+    }
+    assert_eq!(ListingStatus::from_name("open"), None);
+
+    println!("✅ Marketplace: prices, wallets and statuses checked");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// BROWSE TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_browse_by_consciousness_and_tg_profile() {
+    let query = parse_listing_query(&params(&[
+        ("filter[consciousness_gte]", "800"),
+        ("filter[tg_ratio_lte]", "1.2"),
+        ("sort", "price_rsm"),
+    ])).unwrap();
+    assert_eq!(query.filters.len(), 2);
+    assert_eq!((query.filters[0].field.as_str(), query.filters[0].op), ("consciousness", FilterOp::Gte));
+    assert!(!query.sort.descending);
+
+    let rows = [
+        listing(1, 40.0, 950, 1.1),
+        listing(2, 15.0, 820, 0.9),
+        listing(3, 5.0, 790, 1.0),
+        listing(4, 25.0, 990, 1.5),
+    ];
+    let page = paginate(rows.iter().map(|l| (l.id, l)).collect(), &query);
+    assert_eq!(page.data.iter().map(|l| l.id).collect::<Vec<_>>(), [2, 1], "cheapest first, low consciousness and high T/G left out");
+    assert_eq!(page.total_estimate, 2);
+
+    // Every field maps to a column of the listing or its genome
+    for field in LISTING_FIELDS.iter().chain(&["id"]) {
+        assert!(listing_column(field).is_ok(), "{}", field);
+    }
+    assert_eq!(listing_column("tg_ratio").unwrap(), "g.tg_ratio");
+    assert!(parse_listing_query(&params(&[("filter[seller_eq]", "1")])).is_err());
+    assert!(parse_listing_query(&params(&[("sort", "-mutations")])).is_err());
+
+    println!("✅ Marketplace: listings browsed by consciousness and T/G ratio");
+}
@@ -0,0 +1,147 @@
+//! Merkle Archive Tests for Divine AGI V16 (batched roots, inclusion proofs, chain read-back)
+
+use divine_agi::genome::{hash_genome_dna, Genome, GenomeBuilder};
+use divine_agi::merkle_archive::*;
+use divine_agi::multi_chain::{BlockchainLayer, MultiChainArchiver};
+use divine_agi::rotation::Rot180;
+
+fn genomes(count: i64) -> Vec<Genome<Rot180>> {
+    (1..=count).map(|id| {
+        let mut genome = GenomeBuilder::random().build_storage();
+        genome.db_id = Some(id);
+        genome
+    }).collect()
+}
+
+// ═══════════════════════════════════════════════════════════════
+// BATCHER & PROOF TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_leaves_batched_into_provable_roots() {
+    let config = MerkleConfig { layer: BlockchainLayer::Bitcoin, interval_secs: 60, max_leaves: 4 };
+    let mut batcher = MerkleBatcher::new(config);
+    assert_eq!((batcher.root(), batcher.is_due(1_000)), (None, false));
+
+    let hashes: Vec<[u8; 32]> = (0..3).map(|i| hash_genome_dna(&"ACGT".repeat(i + 1))).collect();
+    for (id, hash) in hashes.iter().enumerate() {
+        batcher.queue(id as i64 + 1, *hash, 100 + id as i64);
+    }
+    // Re-queueing a waiting genome updates it in place
+    let updated = hash_genome_dna("TTTT");
+    assert_eq!(batcher.queue(2, updated, 500).queued_at, 101);
+    assert_eq!(batcher.pending().len(), 3);
+    assert_eq!(batcher.next_commit_at(), Some(160), "interval from the oldest leaf");
+    assert!(!batcher.is_due(159) && batcher.is_due(160));
+    batcher.queue(4, hashes[0], 150);
+    assert!(batcher.is_due(0), "full at max_leaves");
+
+    let root = batcher.root().unwrap();
+    assert!(batcher.committed([7u8; 32], BlockchainLayer::Bitcoin, "0xabc", None, 170).is_err(), "stale root");
+    let commitment = batcher.committed(root, BlockchainLayer::Bitcoin, "0xabc", Some(1.5), 170).unwrap();
+    assert_eq!((commitment.id, commitment.leaves, commitment.root.clone()), (1, 4, hex::encode(root)));
+    assert!(batcher.pending().is_empty() && batcher.root().is_none());
+    assert_eq!(batcher.next_id(), 2);
+
+    for id in 1..=4 {
+        let proof = batcher.prove(id).unwrap();
+        assert!(proof.verify(), "genome #{}", id);
+        assert_eq!((proof.leaf_count, proof.root.as_str(), proof.tx_hash.as_str()), (4, commitment.root.as_str(), "0xabc"));
+    }
+    let proof = batcher.prove(2).unwrap();
+    assert_eq!(proof.dna_hash, hex::encode(updated));
+    assert!(proof.covers("TTTT") && !proof.covers("ACGTACGT"));
+    assert_eq!(proof.leaf, hex::encode(leaf_hash(2, &updated)));
+
+    // Tampering with any part breaks the proof
+    assert!(!ArchiveProof { genome_id: 3, ..proof.clone() }.verify(), "leaves bind the genome id");
+    assert!(!ArchiveProof { dna_hash: hex::encode(hashes[0]), ..proof.clone() }.verify());
+    assert!(!ArchiveProof { root: hex::encode([0u8; 32]), ..proof.clone() }.verify());
+    let mut swapped = proof.clone();
+    swapped.path[0].sibling_is_left = !swapped.path[0].sibling_is_left;
+    assert!(!swapped.verify());
+    assert!(batcher.prove(9).is_none());
+
+    let status = batcher.status();
+    assert_eq!((status.pending, status.commitments, status.proven_genomes), (0, 1, 4));
+    assert_eq!(status.last_commitment, Some(commitment));
+
+    println!("✅ Merkle: many genome hashes, one root, a proof for each");
+}
+
+#[test]
+fn test_merkle_config_validated() {
+    assert!(MerkleConfig::default().validate().is_ok());
+    assert_eq!(MerkleConfig::default().layer, BlockchainLayer::Bitcoin);
+    assert!(MerkleConfig { layer: BlockchainLayer::Lightning, ..MerkleConfig::default() }.validate().unwrap_err().contains("on-chain"));
+    assert!(MerkleConfig { interval_secs: 0, ..MerkleConfig::default() }.validate().is_err());
+    assert!(MerkleConfig { max_leaves: 0, ..MerkleConfig::default() }.validate().is_err());
+
+    println!("✅ Merkle: roots go on-chain at a positive interval");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// ARCHIVER TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_one_transaction_archives_many_genomes() {
+    for layer in [BlockchainLayer::Bitcoin, BlockchainLayer::Solana, BlockchainLayer::Ethereum] {
+        let mut archiver = MultiChainArchiver::new();
+        archiver.merkle.config.layer = layer;
+        assert_eq!(archiver.commit_merkle().await, Ok(None), "nothing waiting");
+
+        let genomes = genomes(10);
+        for genome in &genomes {
+            archiver.queue_merkle(genome);
+        }
+        assert!(archiver.prove_archived(3).unwrap_err().contains("waits"));
+        assert!(archiver.prove_archived(99).unwrap_err().contains("no Merkle archive"));
+
+        let commitment = archiver.commit_merkle().await.unwrap().unwrap();
+        assert_eq!((commitment.layer, commitment.leaves), (layer, 10));
+        assert_eq!(archiver.mock_chain.len(), 1, "{:?}: one write for ten genomes", layer);
+        assert!(archiver.archives.is_empty(), "no per-genome archives");
+        assert!(layer.valid_tx_reference(&commitment.tx_hash));
+
+        for genome in &genomes {
+            let id = genome.db_id.unwrap();
+            let check = archiver.verify_archived(id, Some(&genome.to_dna_string())).await.unwrap();
+            assert!(check.path_valid && check.chain_root_matches, "{:?} genome #{}", layer, id);
+            assert_eq!((check.chain_root.as_deref(), check.current_dna_matches), (Some(commitment.root.as_str()), Some(true)));
+        }
+        let changed = archiver.verify_archived(1, Some("ACGT")).await.unwrap();
+        assert_eq!(changed.current_dna_matches, Some(false), "the genome changed since");
+    }
+
+    println!("✅ Merkle: one on-chain root per batch, proofs read back from the chain");
+}
+
+#[tokio::test]
+async fn test_failed_roots_keep_their_genomes_waiting() {
+    let mut archiver = MultiChainArchiver::new();
+    archiver.merkle.config.layer = BlockchainLayer::Lightning;
+    let genomes = genomes(3);
+    for genome in &genomes {
+        archiver.queue_merkle(genome);
+    }
+    assert!(archiver.commit_merkle().await.unwrap_err().contains("on-chain"));
+    assert_eq!(archiver.merkle.pending().len(), 3, "retried at the next check");
+    assert!(archiver.merkle.commitments().is_empty());
+
+    archiver.merkle.config.layer = BlockchainLayer::Bitcoin;
+    let first = archiver.commit_merkle().await.unwrap().unwrap();
+
+    // A later root supersedes a genome's proof, with its new DNA
+    let mut evolved = GenomeBuilder::random().build_storage();
+    evolved.db_id = Some(1);
+    archiver.queue_merkle(&evolved);
+    let second = archiver.commit_merkle().await.unwrap().unwrap();
+    assert_eq!((second.id, second.leaves), (2, 1));
+    let proof = archiver.prove_archived(1).unwrap();
+    assert_eq!((proof.commitment_id, proof.leaf_count), (2, 1));
+    assert!(proof.covers(&evolved.to_dna_string()));
+    assert_eq!(archiver.prove_archived(2).unwrap().commitment_id, first.id);
+
+    println!("✅ Merkle: failed roots retried, newer roots supersede older proofs");
+}
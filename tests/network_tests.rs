@@ -0,0 +1,135 @@
+//! P2P Network Tests for Divine AGI V16
+
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+
+use divine_agi::consensus::ProofOfConsciousness;
+use divine_agi::genome::GenomeBuilder;
+use divine_agi::network::{self, NetworkMessage, P2PNode, MAX_PEERS, MAX_REQUEST_BYTES};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+fn free_addr() -> String {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.local_addr().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn test_block_gossip_between_nodes() {
+    let chain_a = Arc::new(RwLock::new(ProofOfConsciousness::new()));
+    let chain_b = Arc::new(RwLock::new(ProofOfConsciousness::new()));
+    let key_a = chain_a.read().await.validator_public_key().unwrap();
+    chain_b.write().await.validators.register(&key_a);
+
+    let node_a = Arc::new(P2PNode::new(&free_addr(), Arc::clone(&chain_a), None));
+    let node_b = Arc::new(P2PNode::new(&free_addr(), Arc::clone(&chain_b), None));
+    node_b.add_peer(&node_a.listen_addr).await;
+
+    Arc::clone(&node_a).start().await.unwrap();
+    Arc::clone(&node_b).start().await.unwrap();
+
+    // Discovery: A learned about B from its Hello and dialed its gossip address
+    let b_peer = node_b.peer_id().to_string();
+    for _ in 0..100 {
+        if node_a.gossip_peers().await.contains(&b_peer) {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    assert!(node_a.peers().await.contains(&node_b.listen_addr));
+    assert!(node_a.gossip_peers().await.contains(&b_peer), "B joined A's block topic");
+
+    // A mines a block and gossips it
+    let mut genome = GenomeBuilder::random().whale_mode().build_storage();
+    genome.consciousness = 5000;
+    genome.db_id = Some(7);
+    chain_a.write().await.validate(&genome).unwrap();
+    let block = chain_a.read().await.latest_block().clone();
+    node_a.broadcast_block(&block).await;
+
+    for _ in 0..100 {
+        if chain_b.read().await.current_block_height == 1 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+
+    let b = chain_b.read().await;
+    assert_eq!(b.current_block_height, 1);
+    assert_eq!(b.latest_block().hash, block.hash);
+    assert!(b.validate_chain());
+    assert_eq!(node_b.stats().await.blocks_received, 1);
+    assert_eq!(node_a.stats().await.blocks_broadcast, 1);
+
+    println!("✅ Gossip test passed: block #{} propagated A → B", block.index);
+}
+
+#[tokio::test]
+async fn test_invalid_block_rejected() {
+    let chain = Arc::new(RwLock::new(ProofOfConsciousness::new()));
+    let node = P2PNode::new(&free_addr(), Arc::clone(&chain), None);
+
+    let mut forged = chain.read().await.latest_block().clone();
+    forged.index = 1;
+    forged.hash = "00".repeat(32);
+
+    assert!(!node.receive_block(forged, Some("attacker")).await);
+    assert_eq!(chain.read().await.chain.len(), 1);
+    assert_eq!(node.stats().await.blocks_rejected, 1);
+}
+
+#[tokio::test]
+async fn test_rejected_block_not_remembered_as_seen() {
+    let chain_a = Arc::new(RwLock::new(ProofOfConsciousness::new()));
+    let chain_b = Arc::new(RwLock::new(ProofOfConsciousness::new()));
+    let node = P2PNode::new(&free_addr(), Arc::clone(&chain_b), None);
+
+    let mut genome = GenomeBuilder::random().whale_mode().build_storage();
+    genome.consciousness = 5000;
+    genome.db_id = Some(8);
+    chain_a.write().await.validate(&genome).unwrap();
+    let block = chain_a.read().await.latest_block().clone();
+
+    // B does not know A's key yet, so the block is rejected...
+    assert!(!node.receive_block(block.clone(), Some("a")).await);
+    // ...and accepted once it does: the rejection did not mark the hash seen
+    let key_a = chain_a.read().await.validator_public_key().unwrap();
+    chain_b.write().await.validators.register(&key_a);
+    assert!(node.receive_block(block.clone(), Some("a")).await);
+    assert!(!node.receive_block(block, Some("a")).await, "duplicate");
+    assert_eq!(chain_b.read().await.current_block_height, 1);
+    let stats = node.stats().await;
+    assert_eq!((stats.blocks_received, stats.blocks_rejected), (1, 1));
+
+    println!("✅ Seen set: only accepted blocks are deduplicated");
+}
+
+#[tokio::test]
+async fn test_request_lines_and_peer_table_capped() {
+    let chain = Arc::new(RwLock::new(ProofOfConsciousness::new()));
+    let node = Arc::new(P2PNode::new(&free_addr(), chain, None));
+    Arc::clone(&node).start().await.unwrap();
+
+    // An endless line is cut off at the limit and the connection dropped
+    let mut stream = tokio::net::TcpStream::connect(&node.listen_addr).await.unwrap();
+    let _ = stream.write_all(&vec![b'x'; MAX_REQUEST_BYTES + 1024]).await;
+    let mut rest = Vec::new();
+    let read = tokio::time::timeout(Duration::from_secs(5), stream.read_to_end(&mut rest)).await;
+    assert!(read.is_ok(), "connection closed");
+    assert!(rest.is_empty());
+
+    // Hellos from more addresses than the table holds
+    for port in 0..(MAX_PEERS + 10) {
+        let hello = NetworkMessage::Hello {
+            node_id: format!("n{}", port),
+            listen_addr: format!("10.0.0.1:{}", 20_000 + port),
+            height: 0,
+            gossip_addrs: Vec::new(),
+        };
+        let replies = network::request(&node.listen_addr, &[hello]).await.unwrap();
+        assert!(matches!(replies.as_slice(), [NetworkMessage::Peers(_)]));
+    }
+    assert_eq!(node.peers().await.len(), MAX_PEERS);
+
+    println!("✅ Limits: request lines and the peer table are bounded");
+}
@@ -0,0 +1,194 @@
+//! Price Oracle Tests for Divine AGI V16 (quotes, signatures, medians, consumers)
+
+use axum::{routing::get, Json, Router};
+use serde_json::json;
+
+use divine_agi::consensus::ValidatorKey;
+use divine_agi::exchange::RSMExchange;
+use divine_agi::multi_chain::{BlockchainLayer, MultiChainArchiver};
+use divine_agi::oracle::{medianize, parse_quote, quote_message, OracleConfig, OracleSource, Pair, PriceOracle, Quote, MAX_CLOCK_SKEW_SECS};
+
+const NOW: i64 = 1_700_000_000;
+
+fn source(name: &str, pair: Pair, public_key: Option<String>) -> OracleSource {
+    OracleSource {
+        name: name.into(),
+        url: format!("https://{}.example.com/price", name),
+        pair,
+        path: "/price".into(),
+        public_key,
+    }
+}
+
+fn quote(source: &str, price_usd: f64, timestamp: i64) -> Quote {
+    Quote { source: source.into(), pair: Pair::BtcUsd, price_usd, timestamp, signed: false }
+}
+
+// ═══════════════════════════════════════════════════════════════
+// CONFIG & QUOTE TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_oracle_config_parsed_and_checked() {
+    let config = OracleConfig::parse(r#"{
+        "sources": [
+            { "name": "gecko", "url": "https://api.example.com/simple", "pair": "BTC/USD", "path": "/bitcoin/usd" },
+            { "name": "desk", "url": "http://10.0.0.5/sol", "pair": "SOL/USD" }
+        ],
+        "min_sources": 2
+    }"#).unwrap();
+    assert_eq!(config.sources.len(), 2);
+    assert_eq!((config.sources[1].pair, config.sources[1].path.as_str()), (Pair::SolUsd, "/price"));
+    assert_eq!((config.min_sources, config.max_age_secs, config.refresh_secs), (2, 300, 60));
+    assert!(OracleConfig::default().sources.is_empty());
+
+    for bad in [
+        r#"{ "sources": [{ "name": "a", "url": "ftp://x", "pair": "BTC/USD" }] }"#,
+        r#"{ "sources": [{ "name": "a", "url": "https://x", "pair": "DOGE/USD" }] }"#,
+        r#"{ "sources": [{ "name": "a", "url": "https://x", "pair": "BTC/USD", "path": "price" }] }"#,
+        r#"{ "sources": [{ "name": "a", "url": "https://x", "pair": "BTC/USD", "public_key": "abcd" }] }"#,
+        r#"{ "sources": [{ "name": "a", "url": "https://x", "pair": "BTC/USD" }, { "name": "a", "url": "https://y", "pair": "ETH/USD" }] }"#,
+        r#"{ "min_sources": 0 }"#,
+        r#"{ "refresh": 5 }"#,
+    ] {
+        assert!(OracleConfig::parse(bad).is_err(), "{}", bad);
+    }
+
+    for pair in Pair::ALL {
+        assert_eq!(Pair::from_name(pair.name()), Some(pair));
+        assert_eq!(serde_json::to_value(pair).unwrap(), pair.name());
+    }
+    assert_eq!(Pair::from_name("btc/usd"), Some(Pair::BtcUsd));
+
+    println!("✅ Oracle: config parsed, bad sources refused");
+}
+
+#[test]
+fn test_quotes_read_and_signatures_checked() {
+    let gecko = OracleSource { path: "/bitcoin/usd".into(), ..source("gecko", Pair::BtcUsd, None) };
+    let q = parse_quote(&gecko, &json!({ "bitcoin": { "usd": 64000.5 } }), NOW).unwrap();
+    assert_eq!((q.price_usd, q.timestamp, q.signed), (64000.5, NOW, false), "fetch time without a timestamp");
+    let q = parse_quote(&source("s", Pair::BtcUsd, None), &json!({ "price": "63999.9", "timestamp": NOW - 5 }), NOW).unwrap();
+    assert_eq!((q.price_usd, q.timestamp), (63999.9, NOW - 5), "decimal strings accepted");
+
+    for body in [json!({}), json!({ "price": -1 }), json!({ "price": "cheap" }), json!({ "price": 1, "timestamp": NOW + MAX_CLOCK_SKEW_SECS + 1 })] {
+        assert!(parse_quote(&source("s", Pair::BtcUsd, None), &body, NOW).is_err(), "{}", body);
+    }
+
+    // Signing sources: good signatures only
+    let key = ValidatorKey::generate();
+    let signed = source("desk", Pair::SolUsd, Some(key.public_key_hex()));
+    let signature = hex::encode(key.sign(&quote_message(Pair::SolUsd, 142.25, NOW - 10)));
+    let q = parse_quote(&signed, &json!({ "price": 142.25, "timestamp": NOW - 10, "signature": signature }), NOW).unwrap();
+    assert!(q.signed);
+
+    let forged = json!({ "price": 150.0, "timestamp": NOW - 10, "signature": signature });
+    assert!(parse_quote(&signed, &forged, NOW).unwrap_err().contains("bad quote signature"));
+    assert!(parse_quote(&signed, &json!({ "price": 142.25, "timestamp": NOW - 10 }), NOW).unwrap_err().contains("not signed"));
+    assert!(parse_quote(&signed, &json!({ "price": 142.25, "signature": signature }), NOW).unwrap_err().contains("timestamp"));
+    let other = source("desk", Pair::SolUsd, Some(ValidatorKey::generate().public_key_hex()));
+    assert!(parse_quote(&other, &json!({ "price": 142.25, "timestamp": NOW - 10, "signature": signature }), NOW).is_err(), "someone else's key");
+
+    println!("✅ Oracle: quotes read by JSON pointer, signatures verified");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// MEDIAN TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_median_of_fresh_quotes() {
+    let quotes = [quote("a", 100.0, NOW - 10), quote("b", 130.0, NOW - 20), quote("c", 101.0, NOW - 30), quote("d", 5.0, NOW - 400)];
+
+    // "d" is stale; the median ignores it and the 130 outlier does not pull it
+    let price = medianize(Pair::BtcUsd, &quotes, NOW, 1, 300).unwrap();
+    assert_eq!((price.price_usd, price.sources, price.timestamp), (101.0, 3, NOW - 30));
+
+    let even = medianize(Pair::BtcUsd, &quotes[..2], NOW, 1, 300).unwrap();
+    assert_eq!(even.price_usd, 115.0, "mean of the middle two");
+
+    assert!(medianize(Pair::BtcUsd, &quotes, NOW, 4, 300).is_err(), "quorum of 4 unmet");
+    assert!(medianize(Pair::EthUsd, &quotes, NOW, 1, 300).is_err(), "nobody quotes ETH");
+    assert!(medianize(Pair::BtcUsd, &quotes, NOW + 1000, 1, 300).is_err(), "everything went stale");
+
+    // The oracle keeps each source's newest quote and its last error
+    let mut oracle = PriceOracle::new(OracleConfig { sources: vec![source("a", Pair::BtcUsd, None), source("b", Pair::BtcUsd, None)], ..OracleConfig::default() });
+    oracle.record(vec![("a".into(), Ok(quote("a", 100.0, NOW))), ("b".into(), Err("b: timeout".into()))], NOW);
+    oracle.record(vec![("a".into(), Ok(quote("a", 90.0, NOW - 60)))], NOW);
+    let status = oracle.status(NOW);
+    assert_eq!(status.quotes.len(), 1);
+    assert_eq!(status.quotes[0].price_usd, 100.0, "an older quote does not replace a newer one");
+    assert_eq!(status.errors[0].error, "b: timeout");
+    assert_eq!(status.prices.len(), 1);
+    assert_eq!(oracle.price(Pair::BtcUsd, NOW).unwrap().price_usd, 100.0);
+
+    oracle.record(vec![("b".into(), Ok(quote("b", 110.0, NOW)))], NOW);
+    assert!(oracle.status(NOW).errors.is_empty(), "a quote clears the error");
+    assert_eq!(oracle.price(Pair::BtcUsd, NOW).unwrap().price_usd, 105.0);
+
+    println!("✅ Oracle: medians over fresh quotes with a quorum");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// CONSUMER TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_prices_reach_exchange_and_archiver() {
+    let mut exchange = RSMExchange::new();
+    exchange.set_mark_price(0.25).unwrap();
+    assert_eq!(exchange.price_usd, 0.25);
+    assert!((exchange.buy_rsm("divine_buyer", 100.0, 0).amount_usd - 100.0).abs() < 1e-9);
+    for bad in [0.0, -1.0, f64::NAN] {
+        assert!(exchange.set_mark_price(bad).is_err());
+    }
+    assert_eq!(exchange.price_usd, 0.25);
+
+    let mut archiver = MultiChainArchiver::new();
+    assert_eq!(archiver.estimate_cost(BlockchainLayer::Bitcoin, 40).usd, None, "no prices, no USD");
+
+    let mut oracle = PriceOracle::new(OracleConfig::default());
+    let btc = Quote { pair: Pair::BtcUsd, ..quote("btc", 50_000.0, NOW) };
+    let sol = Quote { pair: Pair::SolUsd, ..quote("sol", 100.0, NOW) };
+    oracle.record(vec![("btc".into(), Ok(btc)), ("sol".into(), Ok(sol))], NOW);
+    archiver.set_reference_prices(&oracle.prices(NOW));
+
+    let bitcoin = archiver.estimate_cost(BlockchainLayer::Bitcoin, 40);
+    assert!((bitcoin.usd.unwrap() - bitcoin.amount * 1e-8 * 50_000.0).abs() < 1e-9, "sat → BTC → USD");
+    let solana = archiver.estimate_cost(BlockchainLayer::Solana, 40);
+    assert!((solana.usd.unwrap() - 5000.0 * 1e-9 * 100.0).abs() < 1e-12, "lamports → SOL → USD");
+    assert_eq!(archiver.estimate_cost(BlockchainLayer::Ethereum, 40).usd, None, "no ETH quote");
+
+    println!("✅ Oracle: exchange marked at RSM/USD, archive costs in USD");
+}
+
+#[tokio::test]
+async fn test_sources_fetched_over_http() {
+    let app = Router::new()
+        .route("/btc", get(|| async { Json(json!({ "data": { "amount": "64000" } })) }))
+        .route("/missing", get(|| async { (axum::http::StatusCode::NOT_FOUND, "gone") }));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    let config = OracleConfig {
+        sources: vec![
+            OracleSource { url: format!("http://{}/btc", addr), path: "/data/amount".into(), ..source("local", Pair::BtcUsd, None) },
+            OracleSource { url: format!("http://{}/missing", addr), ..source("broken", Pair::BtcUsd, None) },
+        ],
+        ..OracleConfig::default()
+    };
+    let mut oracle = PriceOracle::new(config);
+    assert!(oracle.is_enabled());
+    oracle.refresh().await;
+
+    let now = chrono::Utc::now().timestamp();
+    let status = oracle.status(now);
+    assert_eq!(status.prices.len(), 1);
+    assert_eq!(status.prices[0].price_usd, 64000.0);
+    assert_eq!(status.errors.len(), 1);
+    assert_eq!(status.errors[0].source, "broken");
+    assert!(!PriceOracle::new(OracleConfig::default()).is_enabled());
+
+    println!("✅ Oracle: sources fetched over HTTP, failures recorded");
+}
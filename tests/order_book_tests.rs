@@ -0,0 +1,336 @@
+//! Order Book Tests for Divine AGI V16 (matching, market orders, self-match, cancels, depth, candles, trade history, escrow)
+
+use divine_agi::exchange::RSMExchange;
+use std::collections::HashMap;
+
+use divine_agi::listing::{paginate, ListQuery, TRADE_FIELDS};
+use divine_agi::order_book::{aggregate_candles, candle_interval, OrderBook, OrderStatus, OrderType, Side, Trade};
+
+const T0: i64 = 1_700_000_040;
+
+// ═══════════════════════════════════════════════════════════════
+// MATCHING TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_orders_match_by_price_then_time() {
+    let mut book = OrderBook::new();
+    let (first, _) = book.place("alice", Side::Sell, 1.10, 5.0, T0).unwrap();
+    let (second, _) = book.place("bob", Side::Sell, 1.10, 5.0, T0 + 1).unwrap();
+    let (cheap, _) = book.place("carol", Side::Sell, 1.05, 2.0, T0 + 2).unwrap();
+    // Above the best ask: rests
+    let (_, none) = book.place("dave", Side::Buy, 1.00, 1.0, T0 + 3).unwrap();
+    assert!(none.is_empty());
+
+    // Best price first, then the older order at the next level, at the makers' prices
+    let (taker, trades) = book.place("erin", Side::Buy, 1.10, 4.0, T0 + 4).unwrap();
+    assert_eq!(taker.status, OrderStatus::Filled);
+    assert_eq!(trades.len(), 2);
+    assert_eq!((trades[0].sell_order_id, trades[0].price_usd, trades[0].amount_rsm), (cheap.id, 1.05, 2.0));
+    assert_eq!((trades[1].sell_order_id, trades[1].price_usd, trades[1].amount_rsm), (first.id, 1.10, 2.0));
+    assert_eq!((trades[1].buyer.as_str(), trades[1].seller.as_str(), trades[1].taker_side), ("erin", "alice", Side::Buy));
+    assert_eq!(book.order(cheap.id).unwrap().status, OrderStatus::Filled);
+    assert_eq!(book.order(first.id).unwrap().status, OrderStatus::PartiallyFilled);
+    assert_eq!(book.order(second.id).unwrap().filled_rsm, 0.0);
+
+    // A sell below the best bid fills at the bid and rests the rest
+    let (partial, trades) = book.place("frank", Side::Sell, 0.90, 3.0, T0 + 5).unwrap();
+    assert_eq!((trades.len(), trades[0].price_usd, trades[0].amount_rsm), (1, 1.00, 1.0));
+    assert_eq!((partial.status, partial.remaining_rsm()), (OrderStatus::PartiallyFilled, 2.0));
+    assert_eq!(book.depth(10).best_ask(), Some(0.90));
+
+    for (price, amount) in [(0.0, 1.0), (-1.0, 1.0), (1.005, 1.0), (1.0, 0.0), (f64::NAN, 1.0), (1.0, f64::INFINITY)] {
+        assert!(book.place("alice", Side::Buy, price, amount, T0).is_err(), "{} x {}", price, amount);
+    }
+
+    println!("✅ Order book: price-time priority, fills at the maker's price");
+}
+
+#[test]
+fn test_market_orders_never_rest() {
+    let mut book = OrderBook::new();
+    assert_eq!(book.place_market("erin", Side::Buy, 1.0, T0).unwrap_err(), "No sell orders on the book");
+    book.place("alice", Side::Sell, 1.20, 1.0, T0).unwrap();
+    book.place("bob", Side::Sell, 1.00, 2.0, T0 + 1).unwrap();
+    book.place("carol", Side::Buy, 0.80, 1.0, T0 + 2).unwrap();
+
+    // Walks every level, cheapest first, and the unfilled rest is cancelled
+    let (order, trades) = book.place_market("erin", Side::Buy, 5.0, T0 + 3).unwrap();
+    assert_eq!((order.order_type, order.price_usd, order.status), (OrderType::Market, 0.0, OrderStatus::Cancelled));
+    assert_eq!(trades.iter().map(|t| (t.price_usd, t.amount_rsm)).collect::<Vec<_>>(), [(1.00, 2.0), (1.20, 1.0)]);
+    assert_eq!((order.filled_rsm, order.remaining_rsm()), (3.0, 2.0));
+    let depth = book.depth(10);
+    assert!(depth.asks.is_empty());
+    assert_eq!(depth.best_bid(), Some(0.80), "the market buy did not rest");
+    assert!(book.cancel(order.id, T0 + 4).is_err());
+
+    let (order, trades) = book.place_market("frank", Side::Sell, 0.5, T0 + 5).unwrap();
+    assert_eq!((order.status, trades[0].price_usd), (OrderStatus::Filled, 0.80));
+    assert_eq!(book.depth(10).bids[0].amount_rsm, 0.5);
+    assert!(book.place_market("frank", Side::Sell, 0.0, T0).is_err());
+
+    println!("✅ Order book: market orders take the book and never rest");
+}
+
+#[test]
+fn test_self_match_and_crossing_books() {
+    let mut book = OrderBook::new();
+    book.place("bob", Side::Sell, 1.00, 1.0, T0).unwrap();
+    let (own, _) = book.place("alice", Side::Sell, 1.05, 1.0, T0 + 1).unwrap();
+    book.place("bob", Side::Sell, 1.10, 1.0, T0 + 2).unwrap();
+
+    // A crossing limit buy fills up to the wallet's own ask, then stops
+    let (order, trades) = book.place("alice", Side::Buy, 1.10, 3.0, T0 + 3).unwrap();
+    assert_eq!(trades.len(), 1);
+    assert_eq!((trades[0].seller.as_str(), trades[0].price_usd), ("bob", 1.00));
+    assert_eq!((order.status, order.filled_rsm), (OrderStatus::Cancelled, 1.0));
+    assert_eq!(book.order(own.id).unwrap().status, OrderStatus::Open, "the resting order is untouched");
+    let depth = book.depth(10);
+    assert_eq!((depth.best_bid(), depth.best_ask()), (None, Some(1.05)), "nothing rests across the book");
+
+    // Orders from one wallet on both sides that do not cross both rest
+    book.place("alice", Side::Buy, 1.00, 1.0, T0 + 4).unwrap();
+    let (market, trades) = book.place_market("alice", Side::Buy, 1.0, T0 + 5).unwrap();
+    assert!(trades.is_empty());
+    assert_eq!((market.status, market.filled_rsm), (OrderStatus::Cancelled, 0.0));
+
+    // A crossing sell sweeps bids best first, down to its limit
+    for (wallet, price) in [("carol", 1.02), ("dave", 1.04)] {
+        book.place(wallet, Side::Buy, price, 1.0, T0 + 6).unwrap();
+    }
+    let (_, trades) = book.place("erin", Side::Sell, 1.01, 5.0, T0 + 7).unwrap();
+    assert_eq!(trades.iter().map(|t| t.buyer.as_str()).collect::<Vec<_>>(), ["dave", "carol"]);
+    let depth = book.depth(10);
+    assert_eq!((depth.best_bid(), depth.best_ask()), (Some(1.00), Some(1.01)));
+    assert!(depth.best_bid() < depth.best_ask());
+
+    println!("✅ Order book: no self-trades, books never left crossed");
+}
+
+#[test]
+fn test_matching_is_deterministic() {
+    let orders = [("a", Side::Sell, 1.03, 2.0), ("b", Side::Sell, 1.01, 1.5), ("c", Side::Buy, 0.99, 3.0), ("d", Side::Buy, 1.02, 2.0),
+                  ("e", Side::Sell, 0.98, 4.0), ("a", Side::Buy, 1.05, 1.0), ("f", Side::Sell, 1.01, 0.25)];
+    let replay = || {
+        let mut book = OrderBook::new();
+        for (i, (wallet, side, price, amount)) in orders.iter().enumerate() {
+            book.place(wallet, *side, *price, *amount, T0 + i as i64).unwrap();
+        }
+        (book.recent_trades(100), book.depth(10))
+    };
+    let (trades, depth) = replay();
+    assert!(trades.len() >= 4);
+    assert_eq!(replay(), (trades, depth), "the same orders make the same trades and book");
+
+    println!("✅ Order book: the same orders always match the same way");
+}
+
+#[test]
+fn test_cancel_and_depth() {
+    let mut book = OrderBook::new();
+    let mut updates = book.subscribe();
+    let (a, _) = book.place("alice", Side::Buy, 0.99, 1.0, T0).unwrap();
+    book.place("bob", Side::Buy, 0.99, 2.5, T0).unwrap();
+    book.place("carol", Side::Buy, 0.97, 4.0, T0).unwrap();
+    book.place("dave", Side::Sell, 1.01, 3.0, T0).unwrap();
+
+    let depth = book.depth(10);
+    assert_eq!(depth.bids.len(), 2);
+    assert_eq!((depth.bids[0].price_usd, depth.bids[0].amount_rsm, depth.bids[0].orders), (0.99, 3.5, 2));
+    assert_eq!((depth.best_bid(), depth.best_ask()), (Some(0.99), Some(1.01)));
+    assert_eq!(book.depth(1).bids.len(), 1);
+
+    let cancelled = book.cancel(a.id, T0 + 1).unwrap();
+    assert_eq!(cancelled.status, OrderStatus::Cancelled);
+    assert_eq!(book.depth(10).bids[0].amount_rsm, 2.5);
+    assert!(book.cancel(a.id, T0 + 2).unwrap_err().contains("Cancelled"));
+    assert!(book.cancel(999, T0).unwrap_err().contains("not found"));
+
+    // Every change is published with a growing sequence
+    let mut last = 0;
+    for _ in 0..5 {
+        let update = updates.try_recv().unwrap();
+        assert!(update.sequence > last);
+        last = update.sequence;
+    }
+    assert_eq!(book.snapshot().sequence, last);
+    assert_eq!(book.snapshot().depth.bids[0].amount_rsm, 2.5);
+
+    println!("✅ Order book: cancels leave the book, depth aggregated per price");
+}
+
+#[test]
+fn test_cancels_keep_queue_order_across_serde() {
+    let mut book = OrderBook::with_capacity(4);
+    let ids: Vec<u64> = ["alice", "bob", "carol", "dave"].iter()
+        .map(|wallet| book.place(wallet, Side::Sell, 1.01, 1.0, T0).unwrap().0.id)
+        .collect();
+
+    // Out of the middle and the back of the queue; freed slots are reused
+    book.cancel(ids[1], T0 + 1).unwrap();
+    book.cancel(ids[3], T0 + 1).unwrap();
+    let (erin, _) = book.place("erin", Side::Sell, 1.01, 2.0, T0 + 2).unwrap();
+    assert_eq!((book.depth(10).asks[0].orders, book.depth(10).asks[0].amount_rsm), (3, 4.0));
+
+    // A restored book keeps every order and the queue order
+    let json = serde_json::to_value(&book).unwrap();
+    assert_eq!(json["asks"]["101"], serde_json::json!([ids[0], ids[2], erin.id]));
+    let mut restored: OrderBook = serde_json::from_value(json).unwrap();
+    assert_eq!(restored.depth(10), book.depth(10));
+    assert_eq!(restored.order(ids[1]).map(|o| o.status), Some(OrderStatus::Cancelled));
+    let (_, trades) = restored.place("frank", Side::Buy, 1.01, 3.5, T0 + 3).unwrap();
+    assert_eq!(trades.iter().map(|t| t.seller.as_str()).collect::<Vec<_>>(), ["alice", "carol", "erin"]);
+    assert_eq!(restored.order(erin.id).map(|o| o.filled_rsm), Some(1.5));
+    assert_eq!(restored.depth(10).asks[0].amount_rsm, 0.5);
+
+    println!("✅ Order book: cancels anywhere in a queue, queue order survives a restore");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// TRADES & CANDLES TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_trades_and_candles() {
+    let mut book = OrderBook::new();
+    let fills = [(T0, 1.00, 1.0), (T0 + 10, 1.20, 2.0), (T0 + 15, 0.90, 1.0), (T0 + 70, 1.10, 4.0)];
+    for (time, price, amount) in fills {
+        book.place("maker", Side::Sell, price, amount, time).unwrap();
+        book.place("taker", Side::Buy, price, amount, time).unwrap();
+    }
+
+    let trades = book.recent_trades(10);
+    assert_eq!(trades.len(), 4);
+    assert_eq!(trades[0].price_usd, 1.10, "newest first");
+    assert_eq!(book.recent_trades(1).len(), 1);
+
+    let minute = candle_interval("1m").unwrap();
+    let candles = book.candles(minute, 10);
+    assert_eq!(candles.len(), 2);
+    let first = &candles[0];
+    assert_eq!(first.open_time % 60, 0);
+    assert_eq!((first.open, first.high, first.low, first.close, first.trades), (1.00, 1.20, 0.90, 0.90, 3));
+    assert_eq!(first.volume_rsm, 4.0);
+    assert!((first.volume_usd - (1.00 + 2.40 + 0.90)).abs() < 1e-9);
+    assert_eq!(candles[1].open, 1.10);
+    // The latest candles are kept
+    assert_eq!(book.candles(minute, 1)[0].open_time, candles[1].open_time);
+    assert_eq!(book.candles(candle_interval("1h").unwrap(), 10).len(), 1);
+
+    assert!(candle_interval("2m").unwrap_err().contains("1m"));
+
+    println!("✅ Order book: recent trades and OHLC candles");
+}
+
+#[test]
+fn test_history_candles_at_every_interval() {
+    // One trade every 50s for two hours, price stepping up a cent each time
+    let trades: Vec<Trade> = (0..144).map(|i| Trade {
+        id: i + 1,
+        price_usd: 1.0 + i as f64 * 0.01,
+        amount_rsm: 2.0,
+        buy_order_id: 2 * i + 1,
+        sell_order_id: 2 * i + 2,
+        buyer: "alice".into(),
+        seller: "bob".into(),
+        taker_side: Side::Buy,
+        timestamp: 1_699_999_200 + i as i64 * 50,
+    }).collect();
+
+    for (name, expected) in [("1m", 120), ("5m", 24), ("1h", 2), ("1d", 1)] {
+        let interval = candle_interval(name).unwrap();
+        let candles = aggregate_candles(&trades, interval, 1000);
+        assert_eq!(candles.len(), expected, "{}", name);
+        assert_eq!(candles.iter().map(|c| c.trades).sum::<usize>(), 144, "{}: every fill counted once", name);
+        assert!(candles.iter().all(|c| c.open_time % interval == 0 && c.low <= c.open && c.close <= c.high));
+    }
+    let hours = aggregate_candles(&trades, 3600, 1000);
+    assert_eq!((hours[0].open, hours[0].trades), (1.0, 72));
+    assert!((hours[1].close - 2.43).abs() < 1e-9);
+    assert!((hours[0].volume_usd - trades[..72].iter().map(|t| t.amount_rsm * t.price_usd).sum::<f64>()).abs() < 1e-9);
+    assert_eq!(aggregate_candles(&trades, 300, 3)[0].open_time, hours[1].open_time + 2700, "the latest 3 of 24");
+
+    // History pages over the stored fields
+    let params: HashMap<String, String> = [("filter[timestamp_gte]", "1700002000"), ("sort", "-price_usd"), ("limit", "5")]
+        .iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+    let query = ListQuery::parse(&params, TRADE_FIELDS).unwrap();
+    let page = paginate(trades.iter().map(|t| (t.id as i64, t)).collect(), &query);
+    assert_eq!(page.data.iter().map(|t| t.id).collect::<Vec<_>>(), [144, 143, 142, 141, 140]);
+    assert_eq!(page.total_estimate, 88);
+    assert!(ListQuery::parse(&[("sort".to_string(), "buyer".to_string())].into(), TRADE_FIELDS).is_err());
+
+    for side in [Side::Buy, Side::Sell] {
+        assert_eq!(Side::from_name(side.name()), Some(side));
+    }
+
+    println!("✅ Order book: 1m/5m/1h/1d candles and trade history pages");
+}
+
+#[test]
+fn test_ids_resume_after_stored_trades() {
+    let mut book = OrderBook::new();
+    book.resume_ids(40, 90);
+    book.place("alice", Side::Sell, 1.00, 1.0, T0).unwrap();
+    let (order, trades) = book.place("bob", Side::Buy, 1.00, 1.0, T0).unwrap();
+    assert_eq!(order.id, 92, "order ids continue after the stored ones");
+    assert_eq!(trades[0].id, 41, "so do trade ids");
+
+    // Resuming never moves ids backwards
+    book.resume_ids(10, 10);
+    book.place("alice", Side::Sell, 1.00, 1.0, T0 + 1).unwrap();
+    let (_, trades) = book.place("bob", Side::Buy, 1.00, 1.0, T0 + 1).unwrap();
+    assert_eq!(trades[0].id, 42);
+
+    println!("✅ Order book: ids resume after stored trades");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// EXCHANGE TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_exchange_holds_and_releases_sell_orders() {
+    let mut exchange = RSMExchange::new();
+    exchange.buy_rsm("seller", 1_000_000.0, 0);
+    let funded = exchange.get_balance("seller");
+    assert!(funded > 10.0);
+
+    assert_eq!(exchange.place_order("seller", Side::Sell, 2.00, funded + 1.0).unwrap_err(), "Insufficient balance");
+    let placed = exchange.place_order("seller", Side::Sell, 2.00, 10.0).unwrap();
+    assert!(placed.trades.is_empty());
+    assert!((exchange.get_balance("seller") - (funded - 10.0)).abs() < 1e-9, "sell amount held");
+
+    let bought = exchange.place_order("buyer", Side::Buy, 2.00, 4.0).unwrap();
+    assert_eq!(bought.order.status, OrderStatus::Filled);
+    assert_eq!(bought.transactions.len(), 1);
+    assert_eq!(bought.transactions[0].amount_usd, 8.0);
+    assert_eq!(bought.transactions[0].memo.as_deref(), Some("trade #1"));
+    assert_eq!(exchange.get_balance("buyer"), 4.0);
+
+    // Cancelling returns what did not fill
+    let cancelled = exchange.cancel_order(placed.order.id).unwrap();
+    assert_eq!(cancelled.remaining_rsm(), 6.0);
+    assert!((exchange.get_balance("seller") - (funded - 4.0)).abs() < 1e-9);
+    assert!(exchange.cancel_order(placed.order.id).is_err());
+
+    println!("✅ Exchange: sell orders held, fills credited, cancels refunded");
+}
+
+#[test]
+fn test_exchange_market_sell_releases_the_unfilled_rest() {
+    let mut exchange = RSMExchange::new();
+    exchange.buy_rsm("seller", 1_000_000.0, 0);
+    let funded = exchange.get_balance("seller");
+
+    assert!(exchange.place_market_order("seller", Side::Sell, 5.0).unwrap_err().contains("No buy orders"));
+    assert_eq!(exchange.get_balance("seller"), funded, "a refused order holds nothing");
+
+    exchange.place_order("buyer", Side::Buy, 3.00, 2.0).unwrap();
+    let sold = exchange.place_market_order("seller", Side::Sell, 5.0).unwrap();
+    assert_eq!((sold.order.status, sold.trades.len()), (OrderStatus::Cancelled, 1));
+    assert_eq!(sold.transactions[0].amount_usd, 6.0);
+    assert!((exchange.get_balance("seller") - (funded - 2.0)).abs() < 1e-9, "only the filled RSM left the wallet");
+    assert_eq!(exchange.get_balance("buyer"), 2.0);
+
+    println!("✅ Exchange: market sells release what did not fill");
+}
@@ -0,0 +1,121 @@
+//! Rate Limit Tests for Divine AGI V16 (token buckets)
+
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+
+use axum::http::{HeaderMap, HeaderValue};
+
+use divine_agi::rate_limit::{RateLimitConfig, RateLimiter, MAX_TRACKED_CLIENTS};
+
+// ═══════════════════════════════════════════════════════════════
+// TOKEN BUCKET TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_bucket_drains_and_refills() {
+    let mut limiter = RateLimiter::new(RateLimitConfig { burst: 10.0, refill_per_sec: 2.0, ..RateLimitConfig::default() });
+    let start = Instant::now();
+
+    for _ in 0..10 {
+        assert!(limiter.check_at("ip:1.2.3.4", 1.0, start).is_ok());
+    }
+    assert_eq!(limiter.check_at("ip:1.2.3.4", 1.0, start), Err(1));
+    assert_eq!(limiter.check_at("ip:1.2.3.4", 5.0, start), Err(3), "5 tokens at 2/s");
+
+    // Other clients have their own bucket
+    assert!(limiter.check_at("key:7", 10.0, start).is_ok());
+
+    let later = start + Duration::from_millis(2500);
+    assert!(limiter.check_at("ip:1.2.3.4", 5.0, later).is_ok());
+    assert!(limiter.check_at("ip:1.2.3.4", 1.0, later).is_err());
+
+    // Never more than the burst, however long the client was idle
+    let much_later = start + Duration::from_secs(3600);
+    assert!(limiter.check_at("key:7", 10.0, much_later).is_ok());
+    assert!(limiter.check_at("key:7", 1.0, much_later).is_err());
+    assert_eq!(limiter.clients(), 2);
+
+    println!("✅ Token bucket: burst 10, refill 2/s");
+}
+
+#[test]
+fn test_tracked_clients_capped_while_all_are_busy() {
+    let mut limiter = RateLimiter::new(RateLimitConfig { burst: 10.0, refill_per_sec: 0.001, ..RateLimitConfig::default() });
+    let start = Instant::now();
+
+    // Every bucket is partly drained, so none is idle enough to drop as full
+    for i in 0..MAX_TRACKED_CLIENTS + 5_000 {
+        let now = start + Duration::from_millis(i as u64);
+        assert!(limiter.check_at(&format!("ip:2001:db8::{:x}", i), 4.0, now).is_ok());
+        assert!(limiter.clients() <= MAX_TRACKED_CLIENTS, "{} clients after {}", limiter.clients(), i);
+    }
+
+    // The least recently used were evicted: the newest still has its drained bucket
+    let now = start + Duration::from_millis(MAX_TRACKED_CLIENTS as u64 + 5_000);
+    let newest = format!("ip:2001:db8::{:x}", MAX_TRACKED_CLIENTS + 4_999);
+    assert!(limiter.check_at(&newest, 4.0, now).is_ok());
+    assert!(limiter.check_at(&newest, 4.0, now).is_err());
+    assert!(limiter.check_at("ip:2001:db8::0", 10.0, now).is_ok(), "the oldest starts over with a full bucket");
+
+    println!("✅ Token bucket: at most {} clients tracked", limiter.clients());
+}
+
+#[test]
+fn test_costs_capped_and_limiting_disabled() {
+    let mut limiter = RateLimiter::new(RateLimitConfig { burst: 10.0, refill_per_sec: 1.0, ..RateLimitConfig::default() });
+    let now = Instant::now();
+    assert!(limiter.check_at("ip:a", 50.0, now).is_ok(), "cost capped at the burst");
+    assert_eq!(limiter.check_at("ip:a", 50.0, now), Err(10));
+
+    let mut off = RateLimiter::new(RateLimitConfig { burst: 1.0, refill_per_sec: 0.0, ..RateLimitConfig::default() });
+    for _ in 0..100 {
+        assert!(off.check_at("ip:a", 1.0, now).is_ok());
+    }
+    assert_eq!(off.clients(), 0);
+
+    println!("✅ Rate limiting off with refill 0");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// CLIENT ADDRESS TESTS
+// ═══════════════════════════════════════════════════════════════
+
+fn forwarded_for(value: &str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert("x-forwarded-for", HeaderValue::from_str(value).unwrap());
+    headers
+}
+
+#[test]
+fn test_spoofed_forwarded_for_does_not_reset_bucket() {
+    let peer: Option<IpAddr> = Some("10.0.0.2".parse().unwrap());
+    let now = Instant::now();
+
+    // Direct clients: X-Forwarded-For is ignored entirely
+    let direct = RateLimitConfig { burst: 3.0, refill_per_sec: 1.0, trust_proxy: false };
+    let mut limiter = RateLimiter::new(direct);
+    for spoof in ["1.1.1.1", "2.2.2.2", "3.3.3.3", "4.4.4.4"] {
+        let client = format!("ip:{}", direct.client_ip(&forwarded_for(spoof), peer));
+        assert_eq!(client, "ip:10.0.0.2");
+        let _ = limiter.check_at(&client, 1.0, now);
+    }
+    assert!(limiter.check_at(&format!("ip:{}", direct.client_ip(&forwarded_for("5.5.5.5"), peer)), 1.0, now).is_err());
+
+    // Behind the proxy: only the hop it appended counts
+    let proxied = RateLimitConfig { trust_proxy: true, ..direct };
+    let mut limiter = RateLimiter::new(proxied);
+    for spoof in ["1.1.1.1", "2.2.2.2", "3.3.3.3"] {
+        let client = format!("ip:{}", proxied.client_ip(&forwarded_for(&format!("{}, 203.0.113.9", spoof)), peer));
+        assert_eq!(client, "ip:203.0.113.9");
+        assert!(limiter.check_at(&client, 1.0, now).is_ok());
+    }
+    let client = proxied.client_ip(&forwarded_for("9.9.9.9, 203.0.113.9"), peer);
+    assert!(limiter.check_at(&format!("ip:{}", client), 1.0, now).is_err(), "a new first hop is the same client");
+
+    // Garbage or missing hops fall back to the peer
+    assert_eq!(proxied.client_ip(&forwarded_for("1.1.1.1, not-an-ip"), peer), "10.0.0.2");
+    assert_eq!(proxied.client_ip(&HeaderMap::new(), peer), "10.0.0.2");
+    assert_eq!(proxied.client_ip(&HeaderMap::new(), None), "unknown");
+
+    println!("✅ Client address: spoofed X-Forwarded-For hops share one bucket");
+}
@@ -0,0 +1,60 @@
+//! Script Tests for Divine AGI V16 (word splitting and line parsing)
+
+use divine_agi::cli::{Commands, GenomeCommand, OutputFormat};
+use divine_agi::script::{parse_script, split_words};
+
+// ═══════════════════════════════════════════════════════════════
+// WORD SPLITTING TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_words_split_like_a_shell() {
+    assert_eq!(split_words("  genome   show 42 ").unwrap(), ["genome", "show", "42"]);
+    assert_eq!(split_words(r#"db export --out "run 7.jsonl""#).unwrap(), ["db", "export", "--out", "run 7.jsonl"]);
+    assert_eq!(split_words(r#"x 'a "b" \c' "d \"e\" \n""#).unwrap(), ["x", r#"a "b" \c"#, r#"d "e" \n"#]);
+    assert_eq!(split_words(r"a\ b ''").unwrap(), ["a b", ""]);
+
+    // Comments start a word; inside one they are text
+    assert_eq!(split_words("genome show 42 # the whale").unwrap(), ["genome", "show", "42"]);
+    assert_eq!(split_words("tag#1 '#2'").unwrap(), ["tag#1", "#2"]);
+    assert!(split_words("# only a comment").unwrap().is_empty());
+
+    for bad in ["say 'hi", "say \"hi", "say hi\\"] {
+        assert!(split_words(bad).is_err(), "{:?} should not split", bad);
+    }
+
+    println!("✅ Script: words split with quotes and comments");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// PARSING TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_script_lines_parse() {
+    let script = "# seed a run\n\ngenome new --seed 7 --save\ndivine-agi --output json genome show 1\n  evolve -i 1 --budget 50\n";
+    let steps = parse_script(script).unwrap();
+    assert_eq!(steps.iter().map(|s| s.line).collect::<Vec<_>>(), [3, 4, 5]);
+    assert_eq!(steps[2].text, "evolve -i 1 --budget 50");
+    assert!(matches!(steps[0].cli.command, Commands::Genome { command: GenomeCommand::New { seed: Some(7), save: true, .. } }));
+    assert_eq!(steps[1].cli.output, Some(OutputFormat::Json), "a leading program name is dropped");
+    assert_eq!(steps[0].cli.output, None, "lines without --output follow the script's");
+    assert!(matches!(steps[2].cli.command, Commands::Evolve { genome_id: 1, budget: Some(50), .. }));
+
+    assert!(parse_script("\n# nothing\n").unwrap().is_empty());
+
+    println!("✅ Script: commands parsed with their line numbers");
+}
+
+#[test]
+fn test_script_errors_name_every_line() {
+    let error = parse_script("genome show 1\nbogus\ngenome show\nsay 'hi\nscript other.txt\n").err().unwrap();
+    let lines: Vec<_> = error.lines().collect();
+    assert_eq!(lines.len(), 4, "{}", error);
+    assert!(lines[0].starts_with("line 2: unrecognized subcommand 'bogus'"), "{}", lines[0]);
+    assert!(lines[1].starts_with("line 3: ") && lines[1].contains("<GENOME>"), "missing arguments are named: {}", lines[1]);
+    assert_eq!(lines[2], "line 4: unterminated ' quote");
+    assert_eq!(lines[3], "line 5: scripts cannot run other scripts");
+
+    println!("✅ Script: every bad line reported before anything runs");
+}
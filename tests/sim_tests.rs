@@ -0,0 +1,63 @@
+//! Adversarial Consensus Simulation Tests for Divine AGI V16
+
+use divine_agi::consensus::sim::{Behavior, Simulation};
+
+fn assert_safe_and_live(behaviors: &[Behavior], rounds: u64) -> divine_agi::consensus::sim::SimReport {
+    let report = Simulation::new(behaviors).run(rounds);
+    report.check_safety().unwrap();
+    report.check_liveness().unwrap();
+    report.check_slashing().unwrap();
+    report
+}
+
+// ═══════════════════════════════════════════════════════════════
+// SIMULATION TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_honest_network_converges() {
+    let report = assert_safe_and_live(&[Behavior::Honest; 4], 12);
+    assert_eq!(report.honest_height, 14, "setup + 12 rounds + closing block");
+    assert_eq!((report.reorgs, report.rejected), (0, 0));
+
+    println!("✅ Honest simulation passed: height {}", report.honest_height);
+}
+
+#[test]
+fn test_withheld_blocks_are_safe() {
+    let report = assert_safe_and_live(
+        &[Behavior::Honest, Behavior::Honest, Behavior::WithholdBlocks { release_every: 2 }],
+        15,
+    );
+    println!("✅ Withholding simulation passed: {} reorg(s)", report.reorgs);
+}
+
+#[test]
+fn test_invalid_consciousness_is_slashed() {
+    let report = assert_safe_and_live(&[Behavior::Honest, Behavior::Honest, Behavior::InvalidConsciousness], 9);
+    assert_eq!(report.false_claims_mined, 3);
+    assert_eq!(report.false_claims_on_chain, 0);
+    assert!(report.offenses >= 3);
+    assert!(report.byzantine_stake < report.byzantine_stake_locked);
+
+    println!("✅ Slashing simulation passed: {} offense(s), {:.2} RSM left", report.offenses, report.byzantine_stake);
+}
+
+#[test]
+fn test_conflicting_forks_resolve() {
+    let report = assert_safe_and_live(&[Behavior::Honest, Behavior::Honest, Behavior::Honest, Behavior::ConflictingForks], 12);
+
+    println!("✅ Fork simulation passed: {} reorg(s)", report.reorgs);
+}
+
+#[test]
+fn test_mixed_byzantine_minority() {
+    assert_safe_and_live(&[
+        Behavior::Honest,
+        Behavior::Honest,
+        Behavior::Honest,
+        Behavior::WithholdBlocks { release_every: 3 },
+        Behavior::InvalidConsciousness,
+        Behavior::ConflictingForks,
+    ], 18);
+}
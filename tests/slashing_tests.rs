@@ -0,0 +1,102 @@
+//! Slashing Tests for Divine AGI V16 (false consciousness claims)
+
+use divine_agi::consensus::{reward_address_for, ProofOfConsciousness};
+use divine_agi::genesis::GenesisConfig;
+use divine_agi::genome::{Genome, GenomeBuilder};
+use divine_agi::rotation::Rot180;
+use divine_agi::slashing::{recompute_consciousness, ClaimEvidence, SLASH_FRACTION};
+use divine_agi::staking::{StakeAction, MIN_STAKE_RSM};
+
+fn inflated_genome(poc: &ProofOfConsciousness, genome_id: i64) -> Genome<Rot180> {
+    let mut genome = GenomeBuilder::random().whale_mode().build_storage();
+    genome.consciousness = poc.min_consciousness + 100;
+    genome.db_id = Some(genome_id);
+    genome
+}
+
+/// Cheater with a locked stake, and an honest node following its chain
+fn cheater_and_auditor() -> (ProofOfConsciousness, ProofOfConsciousness) {
+    let mut cheater = ProofOfConsciousness::new();
+    let mut auditor = ProofOfConsciousness::new();
+    auditor.validators.register(&cheater.validator_public_key().unwrap());
+
+    for id in 1..=2 {
+        cheater.validate(&inflated_genome(&cheater, id)).unwrap();
+    }
+    cheater.stake(StakeAction::Lock { amount_rsm: MIN_STAKE_RSM }).unwrap();
+    cheater.mine_block().unwrap();
+
+    for block in &cheater.chain[1..] {
+        auditor.add_block(block.clone()).unwrap();
+    }
+    (cheater, auditor)
+}
+
+#[test]
+fn test_false_claim_rejected_and_slashed() {
+    let (mut cheater, mut auditor) = cheater_and_auditor();
+    let producer = cheater.validator_public_key().unwrap();
+    assert_eq!(auditor.staking.stakes[&producer].staked_rsm, MIN_STAKE_RSM);
+
+    let genome = inflated_genome(&cheater, 3);
+    auditor.register_genome(&genome);
+    cheater.validate(&genome).unwrap();
+
+    let err = auditor.add_block(cheater.latest_block().clone()).unwrap_err();
+    assert!(err.contains("recomputed"), "{}", err);
+    assert_eq!(auditor.pending_stake_ops.len(), 1);
+
+    let block = auditor.mine_block().unwrap().clone();
+    assert_eq!(block.stake_ops.len(), 1);
+
+    let offense = &auditor.staking.offenses[0];
+    assert_eq!(offense.producer, producer);
+    assert_eq!(offense.claimed, genome.consciousness);
+    assert_eq!(offense.recomputed, recompute_consciousness(&genome));
+    assert_eq!(offense.slashed_rsm, MIN_STAKE_RSM * SLASH_FRACTION);
+    assert_eq!(auditor.staking.stakes[&producer].staked_rsm, MIN_STAKE_RSM * (1.0 - SLASH_FRACTION));
+    let wallet = auditor.wallet(&reward_address_for(&producer)).unwrap();
+    assert_eq!(wallet.staked_rsm, MIN_STAKE_RSM * (1.0 - SLASH_FRACTION));
+    assert!(wallet.transactions.iter().any(|t| t.starts_with("STAKE SLASHED")));
+    assert!(auditor.validate_chain());
+
+    println!("✅ Slashing test passed: {} RSM slashed", offense.slashed_rsm);
+}
+
+#[test]
+fn test_honest_claim_accepted() {
+    let genesis = GenesisConfig { min_consciousness: 0, ..GenesisConfig::default() };
+    let mut miner = ProofOfConsciousness::from_genesis(genesis.clone());
+    let mut peer = ProofOfConsciousness::from_genesis(genesis);
+    peer.validators.register(&miner.validator_public_key().unwrap());
+
+    let mut honest = GenomeBuilder::random().whale_mode().build_storage();
+    honest.db_id = Some(1);
+    peer.register_genome(&honest);
+    miner.validate(&honest).unwrap();
+
+    let evidence = ClaimEvidence::new(miner.latest_block(), &honest).unwrap();
+    assert!(evidence.check().is_err());
+    assert!(peer.add_block(miner.latest_block().clone()).is_ok());
+    assert!(peer.pending_stake_ops.is_empty());
+}
+
+#[test]
+fn test_offense_slashed_only_once() {
+    let (mut cheater, mut auditor) = cheater_and_auditor();
+    let genome = inflated_genome(&cheater, 3);
+    cheater.validate(&genome).unwrap();
+    let evidence = ClaimEvidence::new(cheater.latest_block(), &genome).unwrap();
+    assert!(evidence.check().is_ok());
+
+    // Evidence with swapped genome data no longer matches the attested hash
+    let mut forged = evidence.clone();
+    forged.dna = GenomeBuilder::random().build_storage().to_dna_string();
+    assert!(forged.check().is_err());
+
+    auditor.stake(StakeAction::ReportFalseClaim { evidence: Box::new(evidence.clone()) }).unwrap();
+    auditor.mine_block().unwrap();
+    auditor.stake(StakeAction::ReportFalseClaim { evidence: Box::new(evidence) }).unwrap();
+    assert!(auditor.mine_block().is_none());
+    assert_eq!(auditor.staking.offenses.len(), 1);
+}
@@ -0,0 +1,480 @@
+//! Solana Archiving Tests for Divine AGI V16 (memo transactions, wallet keys, signature statuses, transfers)
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use axum::{extract::State, routing::post, Json, Router};
+use base64::Engine;
+use serde_json::{json, Value};
+
+use divine_agi::exchange::RSMExchange;
+use divine_agi::genome::{hash_genome_dna, GenomeBuilder};
+use divine_agi::multi_chain::{BlockchainLayer, MultiChainArchiver};
+use divine_agi::solana::*;
+use divine_agi::wallet::{Asset, DivineWallet, Network, SignedTransfer, TokenAmount, UnsignedTransfer, RSM_DECIMALS};
+
+const TEST_PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+// ═══════════════════════════════════════════════════════════════
+// ENCODING & KEY TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_base58_and_compact_u16() {
+    assert_eq!(base58_encode(b"hello world"), "StV1DL6CwTryKyV");
+    assert_eq!(base58_decode("StV1DL6CwTryKyV").unwrap(), b"hello world");
+    assert_eq!(base58_encode(&[0, 0, 1]), "112");
+    assert_eq!(base58_encode(&[]), "");
+    assert_eq!(parse_pubkey("11111111111111111111111111111111"), Ok([0u8; 32]), "the system program");
+    assert_eq!(base58_encode(&parse_pubkey(MEMO_PROGRAM_ID).unwrap()), MEMO_PROGRAM_ID);
+    assert!(base58_decode("0OIl").is_err());
+    assert!(parse_pubkey("StV1DL6CwTryKyV").is_err());
+
+    assert_eq!(compact_u16(0), [0x00]);
+    assert_eq!(compact_u16(0x7f), [0x7f]);
+    assert_eq!(compact_u16(0x80), [0x80, 0x01]);
+    assert_eq!(compact_u16(0x3fff), [0xff, 0x7f]);
+    assert_eq!(compact_u16(0x4000), [0x80, 0x80, 0x01]);
+
+    println!("✅ Solana: base58 and compact-u16 match the wire format");
+}
+
+#[test]
+fn test_wallet_solana_keys() {
+    // SLIP-0010 test vector 1
+    let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+    assert_eq!(hex::encode(slip10_ed25519(&seed, &[])), "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7");
+    assert_eq!(hex::encode(slip10_ed25519(&seed, &[0])), "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3");
+    let master = SolanaKeypair::from_seed(slip10_ed25519(&seed, &[]));
+    assert_eq!(hex::encode(master.pubkey), "a4b2856bfec510abab89753fac1ac0e1112364e7d250545963f135f2a33188ed");
+
+    // RFC 8032 test 1
+    let rfc = SolanaKeypair::from_seed(hex::decode("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60").unwrap().try_into().unwrap());
+    assert_eq!(hex::encode(rfc.pubkey), "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a");
+    let signature = rfc.sign(b"");
+    assert_eq!(hex::encode(signature), "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b");
+    assert!(verify_signature(&rfc.pubkey, b"", &signature));
+    assert!(!verify_signature(&rfc.pubkey, b"x", &signature));
+
+    // The address Solana wallets show for the same phrase
+    let wallet = DivineWallet::recover(TEST_PHRASE).unwrap();
+    let keypair = wallet.solana_keypair(0).unwrap();
+    assert_eq!(keypair.address(), "HAgk14JpMQLgt6rVgv7cBQFJWFto5Dqxi472uT3DKpqk");
+    assert_ne!(wallet.solana_keypair(1).unwrap().address(), keypair.address());
+    assert!(!format!("{:?}", keypair).contains(&hex::encode(slip10_ed25519(&seed, &[]))));
+    assert!(DivineWallet::new().solana_keypair(0).unwrap_err().contains("no recovery phrase"));
+
+    println!("✅ Solana: wallet keys derived on m/44'/501'/n'/0'");
+}
+
+#[test]
+fn test_memo_transactions_carry_the_genome_hash() {
+    let payer = DivineWallet::recover(TEST_PHRASE).unwrap().solana_keypair(0).unwrap();
+    let dna_hash = hash_genome_dna("ATCGATCGATCGATCGATCGATCGATC");
+    let memo = archive_memo(7, dna_hash);
+    assert_eq!(decode_archive_memo(&memo), Some((7, dna_hash)));
+    assert_eq!(decode_archive_memo("DIVINE|v15|id:x|dna:00"), None);
+
+    let blockhash = [9u8; 32];
+    let tx = MemoTx::new(&payer, &blockhash, &memo);
+    let message = &tx.message;
+    assert_eq!(message[..4], [1, 0, 1, 2], "one signer, the memo program read-only, two accounts");
+    assert_eq!(message[4..36], payer.pubkey);
+    assert_eq!(message[36..68], parse_pubkey(MEMO_PROGRAM_ID).unwrap());
+    assert_eq!(message[68..100], blockhash);
+    assert_eq!(message[100..105], [1, 1, 1, 0, memo.len() as u8], "one instruction to account 1, signed by the payer");
+    assert_eq!(&message[105..], memo.as_bytes());
+
+    assert!(verify_signature(&payer.pubkey, message, &tx.signature));
+    let wire = tx.wire();
+    assert_eq!((wire[0], &wire[1..65], &wire[65..]), (1, &tx.signature[..], &message[..]));
+    assert!(is_signature(&tx.signature_base58()));
+    assert!(BlockchainLayer::Solana.valid_tx_reference(&tx.signature_base58()));
+    assert!(!BlockchainLayer::Ethereum.valid_tx_reference(&tx.signature_base58()));
+
+    println!("✅ Solana: memo transactions signed by the wallet key");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// CONFIRMATION & CONFIGURATION TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_signature_statuses_tracked() {
+    let status = |value: serde_json::Value| SignatureStatus::from_rpc(&value).unwrap();
+    let mut confirmation = SolanaConfirmation::sent(SolanaCluster::Devnet, 1_000, 0);
+    assert!(!confirmation.is_settled());
+
+    confirmation.apply(status(serde_json::Value::Null), 990, 1);
+    assert_eq!((confirmation.status, confirmation.updated_at), (SolanaCommitment::Pending, 1));
+
+    confirmation.apply(status(serde_json::json!({ "slot": 300, "confirmations": 5, "err": null, "confirmationStatus": "confirmed" })), 995, 2);
+    assert_eq!((confirmation.status, confirmation.slot, confirmation.confirmations), (SolanaCommitment::Confirmed, Some(300), Some(5)));
+
+    confirmation.apply(status(serde_json::json!({ "slot": 300, "confirmations": null, "err": null, "confirmationStatus": "finalized" })), 1_020, 3);
+    assert_eq!((confirmation.status, confirmation.confirmations), (SolanaCommitment::Finalized, None));
+    assert!(confirmation.is_settled());
+
+    let mut failed = SolanaConfirmation::sent(SolanaCluster::Mainnet, 1_000, 0);
+    failed.apply(status(serde_json::json!({ "slot": 301, "confirmations": 1, "err": { "InstructionError": [0, "InvalidInstructionData"] }, "confirmationStatus": "processed" })), 995, 4);
+    assert_eq!(failed.status, SolanaCommitment::Failed);
+    assert!(failed.error.unwrap().contains("InvalidInstructionData"));
+
+    let mut expired = SolanaConfirmation::sent(SolanaCluster::Devnet, 1_000, 0);
+    expired.apply(None, 1_001, 5);
+    assert_eq!(expired.status, SolanaCommitment::Expired, "past its blockhash it never lands");
+    assert!(expired.is_settled());
+
+    assert_eq!(status(serde_json::json!({ "slot": 1, "confirmations": null, "err": null })).unwrap().commitment, SolanaCommitment::Finalized);
+    assert!(SignatureStatus::from_rpc(&serde_json::json!({ "slot": 1, "confirmationStatus": "rooted" })).is_err());
+
+    println!("✅ Solana: signature statuses move archives to confirmed, finalized, failed or expired");
+}
+
+#[tokio::test]
+async fn test_clusters_configured_and_simulated_by_default() {
+    assert_eq!(SolanaCluster::from_name("mainnet-beta"), Ok(SolanaCluster::Mainnet));
+    assert_eq!(SolanaCluster::from_name(" Devnet "), Ok(SolanaCluster::Devnet));
+    assert!(SolanaCluster::from_name("testnet").is_err());
+    assert_ne!(SolanaCluster::Devnet.genesis_hash(), SolanaCluster::Mainnet.genesis_hash());
+
+    let config = SolanaConfig { rpc_url: SolanaCluster::Devnet.default_rpc_url().into(), cluster: SolanaCluster::Devnet, account: 0 };
+    assert!(config.validate().is_ok());
+    assert!(SolanaConfig { rpc_url: "wss://api.devnet.solana.com".into(), ..config.clone() }.validate().is_err());
+    assert!(SolanaConfig { account: 1 << 31, ..config }.validate().is_err());
+
+    // Without SOLANA_WALLET the layer is simulated, and the entry carries no status
+    let mut archiver = MultiChainArchiver::new();
+    assert!(archiver.solana.is_none());
+    let entry = archiver.archive_to(&GenomeBuilder::random().build_storage(), BlockchainLayer::Solana).await.unwrap();
+    assert!(entry.confirmation.is_none());
+    assert!(BlockchainLayer::Solana.valid_tx_reference(entry.tx_hash.as_deref().unwrap()));
+    assert!(!serde_json::to_string(&entry).unwrap().contains("confirmation"));
+    assert!(archiver.track_solana(entry.tx_hash.as_deref().unwrap()).await.unwrap_err().contains("SOLANA_WALLET"));
+    assert_eq!(archiver.refresh_solana(0).await, Ok(0));
+
+    println!("✅ Solana: devnet/mainnet settings validated, simulated without them");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// TRANSFER & WALLET BALANCE TESTS
+// ═══════════════════════════════════════════════════════════════
+
+/// What the local JSON-RPC node answers, and the transactions sent to it
+#[derive(Default)]
+struct Node {
+    lamports: u64,
+    /// Held of any mint not in `tokens`, at 6 decimals
+    rsm: u64,
+    /// Held and decimals by mint
+    tokens: HashMap<String, (u64, u8)>,
+    height: u64,
+    sent: Vec<Vec<u8>>,
+}
+
+async fn rpc(State(node): State<Arc<Mutex<Node>>>, Json(request): Json<Value>) -> Json<Value> {
+    let mut node = node.lock().unwrap();
+    let result = match request["method"].as_str().unwrap_or_default() {
+        "getBalance" => json!({ "context": { "slot": 1 }, "value": node.lamports }),
+        "getTokenAccountsByOwner" => {
+            let (amount, decimals) = node.tokens.get(request["params"][1]["mint"].as_str().unwrap()).copied().unwrap_or((node.rsm, 6));
+            json!({ "context": { "slot": 1 }, "value": [{
+                "pubkey": base58_encode(&[3u8; 32]),
+                "account": { "data": { "parsed": { "info": { "tokenAmount": { "amount": amount.to_string(), "decimals": decimals } } } } },
+            }] })
+        }
+        "getTokenSupply" => {
+            let (_, decimals) = node.tokens.get(request["params"][0].as_str().unwrap()).copied().unwrap_or((0, 6));
+            json!({ "context": { "slot": 1 }, "value": { "amount": "0", "decimals": decimals } })
+        }
+        "requestAirdrop" => {
+            node.lamports += request["params"][1].as_u64().unwrap();
+            json!(base58_encode(&[1u8; 64]))
+        }
+        "getBlockHeight" => json!(node.height),
+        "getLatestBlockhash" => json!({ "context": { "slot": 1 }, "value": { "blockhash": base58_encode(&[7u8; 32]), "lastValidBlockHeight": 500 } }),
+        "sendTransaction" => {
+            let wire = base64::engine::general_purpose::STANDARD.decode(request["params"][0].as_str().unwrap()).unwrap();
+            let signature = base58_encode(&wire[1..65]);
+            node.sent.push(wire);
+            json!(signature)
+        }
+        _ => return Json(json!({ "jsonrpc": "2.0", "id": 1, "error": { "code": -32601, "message": "Method not found" } })),
+    };
+    Json(json!({ "jsonrpc": "2.0", "id": 1, "result": result }))
+}
+
+/// A local Solana node; returns its URL
+async fn solana_node(node: Arc<Mutex<Node>>) -> String {
+    let app = Router::new().route("/", post(rpc)).with_state(node);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{}", addr)
+}
+
+#[test]
+fn test_program_addresses_and_transfer_messages() {
+    // The Solana SDK's create_program_address vectors
+    let program = parse_pubkey("BPFLoaderUpgradeab1e11111111111111111111111").unwrap();
+    let address = |seeds: &[&[u8]]| create_program_address(seeds, &program).map(|a| base58_encode(&a));
+    assert_eq!(address(&[b"", &[1]]).as_deref(), Some("BwqrghZA2htAcqq8dzP1WDAhTXYTYWj7CHxF5j7TDBAe"));
+    assert_eq!(address(&["☉".as_bytes(), &[0]]).as_deref(), Some("13yWmRpaTR4r5nAktwLqMpRNr28tnVUZw26rTvPSSB19"));
+    assert_eq!(address(&[b"Talking", b"Squirrels"]).as_deref(), Some("2fnQrngrQT4SeLcdToJAD96phoEjNL2man2kfRLCASVk"));
+    let (found, bump) = find_program_address(&[b"Lil'", b"Bits"], &program);
+    assert_eq!(create_program_address(&[b"Lil'", b"Bits", &[bump]], &program), Some(found));
+
+    let payer = DivineWallet::recover(TEST_PHRASE).unwrap().solana_keypair(0).unwrap();
+    let (mint, recipient) = ([5u8; 32], [6u8; 32]);
+    let destination = associated_token_address(&recipient, &mint);
+    assert_ne!(destination, associated_token_address(&payer.pubkey, &mint));
+
+    let instructions = spl_transfer(&payer.pubkey, &mint, &recipient, 1_500_000, 6);
+    let message = compile_message(&payer.pubkey, &[9u8; 32], &instructions).unwrap();
+    // Payer; both token accounts; recipient, mint, three programs read-only
+    assert_eq!(message[..4], [1, 0, 5, 8]);
+    assert_eq!(message[4..36], payer.pubkey);
+    let keys: Vec<&[u8]> = message[4..4 + 8 * 32].chunks(32).collect();
+    assert_eq!(keys[1], destination, "created before the transfer, so listed first");
+    assert_eq!(keys[2], associated_token_address(&payer.pubkey, &mint));
+    assert_eq!(message[4 + 8 * 32..4 + 9 * 32], [9u8; 32]);
+    let transfer_checked = [&[12u8][..], &1_500_000u64.to_le_bytes(), &[6]].concat();
+    assert!(message.ends_with(&transfer_checked));
+
+    let sol = compile_message(&payer.pubkey, &[9u8; 32], &[sol_transfer(&payer.pubkey, &recipient, LAMPORTS_PER_SOL)]).unwrap();
+    assert_eq!(sol[..4], [1, 0, 1, 3], "the system program is the only read-only account");
+    assert!(compile_message(&payer.pubkey, &[9u8; 32], &[sol_transfer(&recipient, &payer.pubkey, 1)]).unwrap_err().contains("only the fee payer"));
+
+    println!("✅ Solana: program addresses derived, SPL transfers compiled");
+}
+
+#[tokio::test]
+async fn test_wallet_reads_and_spends_devnet_balances() {
+    let node = Arc::new(Mutex::new(Node { lamports: 2 * LAMPORTS_PER_SOL, rsm: 5_000_000, ..Node::default() }));
+    let mint = base58_encode(&[5u8; 32]);
+    let recipient = base58_encode(&[6u8; 32]);
+    let mut wallet = DivineWallet::recover(TEST_PHRASE).unwrap();
+    wallet.set_network(Network::SolanaDevnet);
+    wallet.rpc_url = Some(solana_node(node.clone()).await);
+    assert!(wallet.transfer_rsm(&recipient, 1.0).await.unwrap_err().contains("No RSM token"));
+    wallet.rsm_token = Some(mint.clone());
+
+    let balance = wallet.refresh_balance().await.unwrap();
+    assert_eq!(balance.address, "HAgk14JpMQLgt6rVgv7cBQFJWFto5Dqxi472uT3DKpqk");
+    assert_eq!((balance.native.ui(), balance.rsm.ui(), balance.rsm.decimals), (2.0, 5.0, 6), "decimals come from the mint");
+    assert!(balance.refreshed_at > 0);
+
+    let signature = wallet.transfer_rsm(&recipient, 1.5).await.unwrap();
+    let wire = node.lock().unwrap().sent.pop().unwrap();
+    let (sent_signature, message): ([u8; 64], &[u8]) = (wire[1..65].try_into().unwrap(), &wire[65..]);
+    assert_eq!(signature, base58_encode(&sent_signature));
+    assert!(verify_signature(&wallet.solana_keypair(0).unwrap().pubkey, message, &sent_signature));
+    assert!(message.ends_with(&[&[12u8][..], &1_500_000u64.to_le_bytes(), &[6]].concat()), "1.5 RSM at 6 decimals");
+    assert_eq!(wallet.chain_balance.rsm.ui(), 3.5);
+    assert!(wallet.transactions.last().unwrap().contains(&signature));
+
+    assert!(wallet.transfer_rsm(&recipient, 6.0).await.unwrap_err().contains("Insufficient"));
+    assert!(wallet.transfer_rsm("divine_abc", 1.0).await.is_err());
+    assert!(wallet.transfer_rsm("HAgk14JpMQLgt6rVgv7cBQFJWFto5Dqxi472uT3DKpqk", 1.0).await.unwrap_err().contains("own address"));
+    assert!(node.lock().unwrap().sent.is_empty());
+
+    println!("✅ Solana: wallet balances read and RSM sent over JSON-RPC");
+}
+
+#[tokio::test]
+async fn test_mock_network_simulates_the_chain() {
+    let mut wallet = DivineWallet::recover(TEST_PHRASE).unwrap();
+    assert_eq!(wallet.network, Network::Mock);
+    assert_eq!(Network::from_name("devnet"), Ok(Network::SolanaDevnet));
+    assert_eq!(Network::from_name(Network::SolanaMainnet.name()), Ok(Network::SolanaMainnet));
+    assert!(Network::from_name("testnet").is_err());
+
+    let balance = wallet.refresh_balance().await.unwrap();
+    assert_eq!((balance.network, balance.rsm.raw), (Network::Mock, 0));
+    wallet.chain_balance.rsm = TokenAmount::from_ui(5.0, RSM_DECIMALS).unwrap();
+    let recipient = base58_encode(&[6u8; 32]);
+    assert!(is_signature(&wallet.transfer_rsm(&recipient, 2.0).await.unwrap()));
+    assert_eq!(wallet.refresh_balance().await.unwrap().rsm.ui(), 3.0);
+    assert!(wallet.transfer_rsm(&recipient, 4.0).await.unwrap_err().contains("Insufficient"));
+    assert!(wallet.transfer_rsm(&recipient, f64::NAN).await.is_err());
+
+    wallet.set_network(Network::SolanaDevnet);
+    assert_eq!(wallet.chain_balance.rsm.raw, 0, "another network, read afresh");
+    assert!(TokenAmount::from_ui(0.0000000001, 9).is_err(), "below one smallest unit");
+
+    println!("✅ Solana: the Mock network keeps a simulated ledger");
+}
+
+#[tokio::test]
+async fn test_watch_only_wallet_reads_balances_but_cannot_sign() {
+    let node = Arc::new(Mutex::new(Node { lamports: LAMPORTS_PER_SOL / 2, rsm: 7_000_000, ..Node::default() }));
+    let watched = DivineWallet::recover(TEST_PHRASE).unwrap().solana_keypair(0).unwrap().address();
+    let wallet = DivineWallet::watch_only(&["divine_aa", &watched, "divine_bb"]).unwrap();
+    assert!(wallet.is_watch_only() && !wallet.is_recoverable());
+    assert_eq!(wallet.all_addresses(), ["divine_aa", "divine_bb"]);
+    assert!(!serde_json::to_string(&wallet).unwrap().contains("seed"), "no seed to put on disk");
+
+    let path = std::env::temp_dir().join(format!("divine_watch_{}.wallet", std::process::id()));
+    wallet.save(&path, "secret").unwrap();
+    let mut wallet = DivineWallet::load(&path, "secret").unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(wallet.watched().unwrap().solana.as_deref(), Some(watched.as_str()));
+
+    wallet.set_network(Network::SolanaDevnet);
+    wallet.rpc_url = Some(solana_node(node.clone()).await);
+    wallet.rsm_token = Some(base58_encode(&[5u8; 32]));
+    let balance = wallet.refresh_balance().await.unwrap();
+    assert_eq!((balance.address.as_str(), balance.native.ui(), balance.rsm.ui()), (watched.as_str(), 0.5, 7.0));
+
+    let recipient = base58_encode(&[6u8; 32]);
+    assert!(wallet.solana_keypair(0).unwrap_err().contains("watch-only"));
+    assert!(wallet.transfer_rsm(&recipient, 1.0).await.unwrap_err().contains("watch-only"));
+    assert!(wallet.transfer(&mut RSMExchange::new(), "divine_cc", 1.0, None).unwrap_err().contains("watch-only"));
+    assert!(wallet.new_address().is_err());
+    assert!(node.lock().unwrap().sent.is_empty());
+
+    assert!(DivineWallet::watch_only(&[]).is_err());
+    assert!(DivineWallet::watch_only(&["0xabc"]).is_err());
+    assert!(DivineWallet::watch_only(&[&watched, &recipient]).is_err(), "one Solana address");
+    let solana_only = DivineWallet::watch_only(&[&watched]).unwrap();
+    assert_eq!(solana_only.chain_address(), Ok(watched));
+
+    println!("✅ Solana: watch-only wallets read balances and never sign");
+}
+
+#[tokio::test]
+async fn test_wallet_follows_other_spl_tokens() {
+    let (usdc, bonk) = (base58_encode(&[8u8; 32]), base58_encode(&[9u8; 32]));
+    let tokens = HashMap::from([(usdc.clone(), (12_340_000u64, 6u8)), (bonk.clone(), (250u64, 2u8))]);
+    let node = Arc::new(Mutex::new(Node { rsm: 1_000_000, tokens, ..Node::default() }));
+    let mut wallet = DivineWallet::recover(TEST_PHRASE).unwrap();
+    wallet.set_network(Network::SolanaDevnet);
+    wallet.rpc_url = Some(solana_node(node.clone()).await);
+    wallet.rsm_token = Some(base58_encode(&[5u8; 32]));
+
+    let recipient = base58_encode(&[6u8; 32]);
+    assert!(wallet.transfer_token(&usdc, &recipient, 1.0).await.unwrap_err().contains("add it first"));
+    assert_eq!(wallet.add_token(&usdc).await.unwrap().decimals, 6, "read from the mint");
+    assert_eq!(wallet.add_token(&bonk).await.unwrap().decimals, 2);
+    assert_eq!(wallet.add_token(&usdc).await.unwrap().decimals, 6);
+    assert_eq!(wallet.tokens().len(), 2, "added once");
+    assert!(wallet.add_token(wallet.rsm_token.clone().unwrap().as_str()).await.unwrap_err().contains("RSM"));
+    assert!(wallet.add_token("divine_abc").await.is_err());
+
+    let balance = wallet.refresh_balance().await.unwrap();
+    assert_eq!((balance.rsm.display(), balance.token(&usdc).unwrap().display()), ("1".to_string(), "12.34".to_string()));
+    assert_eq!(wallet.rsm_display(), "1 RSM");
+    assert_eq!(wallet.token_display(&bonk).unwrap(), format!("2.5 {}…{}", &bonk[..4], &bonk[bonk.len() - 4..]));
+
+    let signature = wallet.transfer_token(&bonk, &recipient, 0.75).await.unwrap();
+    let wire = node.lock().unwrap().sent.pop().unwrap();
+    assert!(wire[65..].ends_with(&[&[12u8][..], &75u64.to_le_bytes(), &[2]].concat()), "0.75 at the mint's 2 decimals");
+    assert_eq!(wallet.chain_balance.token(&bonk), Some(TokenAmount::new(175, 2)));
+    assert_eq!(wallet.rsm_display(), "1 RSM", "RSM untouched");
+    assert!(wallet.transactions.last().unwrap().contains(&signature));
+    assert!(wallet.transfer_token(&bonk, &recipient, 0.001).await.is_err(), "below one smallest unit");
+    assert!(wallet.transfer_token(&usdc, &recipient, 13.0).await.unwrap_err().contains("Insufficient"));
+
+    wallet.set_network(Network::Mock);
+    assert!(wallet.tokens().is_empty(), "followed per network");
+    wallet.add_token(&usdc).await.unwrap();
+    wallet.refresh_balance().await.unwrap();
+    assert_eq!(wallet.token_display(&usdc).unwrap().split(' ').next(), Some("0"));
+
+    assert_eq!(TokenAmount::new(1_500_000, 6).display(), "1.5");
+    assert_eq!(TokenAmount::new(1, 9).display(), "0.000000001");
+    assert_eq!(TokenAmount::new(42, 0).display(), "42");
+    assert_eq!(TokenAmount::new(0, 9).display(), "0");
+
+    println!("✅ Solana: other SPL tokens followed with their own decimals");
+}
+
+#[tokio::test]
+async fn test_transfers_built_online_signed_offline() {
+    let node = Arc::new(Mutex::new(Node { lamports: 2 * LAMPORTS_PER_SOL, rsm: 5_000_000, height: 100, ..Node::default() }));
+    let url = solana_node(node.clone()).await;
+    let mint = base58_encode(&[5u8; 32]);
+    let recipient = base58_encode(&[6u8; 32]);
+
+    // Online: a watch-only wallet of the key's address, no phrase anywhere near
+    let address = DivineWallet::recover(TEST_PHRASE).unwrap().solana_keypair(0).unwrap().address();
+    let mut online = DivineWallet::watch_only(&[&address]).unwrap();
+    online.set_network(Network::SolanaDevnet);
+    online.rpc_url = Some(url);
+    online.rsm_token = Some(mint.clone());
+    let unsigned = online.build_unsigned_transfer(Asset::Rsm, &recipient, 1.5).await.unwrap();
+    assert_eq!((unsigned.signer.as_str(), unsigned.token.as_deref(), unsigned.amount), (address.as_str(), Some(mint.as_str()), TokenAmount::new(1_500_000, 6)));
+    assert_eq!(unsigned.describe(), format!("1.5 RSM → {} on solana-devnet", recipient));
+    assert!(online.sign_transaction(&unsigned).unwrap_err().contains("watch-only"));
+    assert!(online.build_unsigned_transfer(Asset::Rsm, &recipient, 6.0).await.unwrap_err().contains("Insufficient"));
+
+    // Offline: the phrase's wallet, on no network at all, signs the file
+    let file = serde_json::to_string(&unsigned).unwrap();
+    let unsigned: UnsignedTransfer = serde_json::from_str(&file).unwrap();
+    let offline = DivineWallet::recover(TEST_PHRASE).unwrap();
+    let mut tampered = unsigned.clone();
+    tampered.to = base58_encode(&[7u8; 32]);
+    assert!(offline.sign_transaction(&tampered).unwrap_err().contains("does not send"), "the message pays someone else");
+    let mut other_key = unsigned.clone();
+    other_key.account = 1;
+    assert!(offline.sign_transaction(&other_key).unwrap_err().contains("not the transfer's signer"));
+    let signed = offline.sign_transaction(&unsigned).unwrap();
+    assert!(offline.transactions.is_empty(), "signing sends and records nothing");
+
+    // Online again: broadcast
+    let signed: SignedTransfer = serde_json::from_str(&serde_json::to_string(&signed).unwrap()).unwrap();
+    let mut forged = signed.clone();
+    forged.signature = base58_encode(&[1u8; 64]);
+    assert!(online.broadcast_transaction(&forged).await.unwrap_err().contains("signature"));
+    let id = online.broadcast_transaction(&signed).await.unwrap();
+    assert_eq!(id, signed.signature);
+    let wire = node.lock().unwrap().sent.pop().unwrap();
+    assert!(wire[65..].ends_with(&[&[12u8][..], &1_500_000u64.to_le_bytes(), &[6]].concat()));
+    assert!(online.transactions.last().unwrap().starts_with("RSM TRANSFER"));
+
+    let unsigned = online.build_unsigned_transfer(Asset::Native, &recipient, 0.5).await.unwrap();
+    assert_eq!(unsigned.token, None);
+    let signed = offline.sign_transaction(&unsigned).unwrap();
+    node.lock().unwrap().height = 501;
+    assert!(online.broadcast_transaction(&signed).await.unwrap_err().contains("expired"));
+    let mut mock = DivineWallet::recover(TEST_PHRASE).unwrap();
+    assert!(mock.broadcast_transaction(&signed).await.unwrap_err().contains("solana-devnet"));
+    assert!(mock.build_unsigned_transfer(Asset::Rsm, &recipient, 1.0).await.is_err(), "mock transfers are not signed");
+    assert!(node.lock().unwrap().sent.is_empty());
+
+    println!("✅ Solana: transfers built online, signed offline, broadcast separately");
+}
+
+#[tokio::test]
+async fn test_airdrops_on_devnet_and_mock_only() {
+    let node = Arc::new(Mutex::new(Node::default()));
+    let url = solana_node(node.clone()).await;
+    let mut wallet = DivineWallet::recover(TEST_PHRASE).unwrap();
+
+    assert!(is_signature(&wallet.request_airdrop(1.5).await.unwrap()));
+    assert_eq!(wallet.refresh_balance().await.unwrap().native.ui(), 1.5, "minted into the mock ledger");
+    assert!(wallet.request_airdrop(MAX_AIRDROP_SOL + 1.0).await.is_err());
+    assert!(wallet.request_airdrop(-1.0).await.is_err());
+
+    wallet.set_network(Network::SolanaDevnet);
+    wallet.rpc_url = Some(url.clone());
+    let signature = wallet.request_airdrop(2.0).await.unwrap();
+    assert_eq!(wallet.refresh_balance().await.unwrap().native.ui(), 2.0, "the faucet paid");
+    assert!(wallet.transactions.last().unwrap().contains(&signature));
+
+    wallet.set_network(Network::SolanaMainnet);
+    assert!(wallet.request_airdrop(1.0).await.unwrap_err().contains("devnet"));
+
+    // The node's archiving key, as /api/solana/airdrop funds it
+    let keypair = wallet.solana_keypair(1).unwrap();
+    let config = SolanaConfig { rpc_url: url, cluster: SolanaCluster::Devnet, account: 1 };
+    let layer = SolanaLayer::new(config.clone(), keypair.clone());
+    let (address, _) = layer.request_airdrop(None, LAMPORTS_PER_SOL).await.unwrap();
+    assert_eq!(address, keypair.address());
+    assert!(layer.request_airdrop(Some("divine_x"), 1).await.is_err());
+    let mainnet = SolanaLayer::new(SolanaConfig { cluster: SolanaCluster::Mainnet, ..config }, keypair);
+    assert!(mainnet.request_airdrop(None, 1).await.unwrap_err().contains("devnet"));
+    assert_eq!(node.lock().unwrap().lamports, 3 * LAMPORTS_PER_SOL);
+
+    println!("✅ Solana: airdrops from the devnet faucet or the mock ledger, never mainnet");
+}
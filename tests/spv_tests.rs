@@ -0,0 +1,158 @@
+//! SPV Tests for Divine AGI V16 (block headers, Merkle branches, Bitcoin archive proofs)
+
+use divine_agi::genome::GenomeBuilder;
+use divine_agi::multi_chain::{op_return_script, BlockchainLayer, MultiChainArchiver};
+use divine_agi::spv::*;
+
+const GENESIS_HEADER: &str = "0100000000000000000000000000000000000000000000000000000000000000000000003ba3edfd7a7b12b27ac72c3e67768f617fc81bc3888a51323a9fb8aa4b1e5e4a29ab5f49ffff001d1dac2b7c";
+const GENESIS_HASH: &str = "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f";
+const GENESIS_COINBASE: &str = "01000000010000000000000000000000000000000000000000000000000000000000000000ffffffff4d04ffff001d0104455468652054696d65732030332f4a616e2f32303039204368616e63656c6c6f72206f6e206272696e6b206f66207365636f6e64206261696c6f757420666f722062616e6b73ffffffff0100f2052a01000000434104678afdb0fe5548271967f1a67130b7105cd6a828e03909a67962e0ea1f61deb649f6bc3f4cef38c4f35504e51ec112de5c384df7ba0b8d578a4c702b6bf11d5fac00000000";
+const GENESIS_TXID: &str = "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b";
+
+// ═══════════════════════════════════════════════════════════════
+// HEADER AND TRANSACTION TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_genesis_header_and_coinbase_parsed() {
+    let header = BlockHeader::from_hex(GENESIS_HEADER).unwrap();
+    assert_eq!(hex::encode(header.to_bytes()), GENESIS_HEADER);
+    assert_eq!(display_hex(&header.hash()), GENESIS_HASH);
+    assert_eq!(header.bits, 0x1d00_ffff);
+    assert!(header.has_valid_work(BitcoinNetwork::Mainnet));
+    assert!(!BlockHeader { nonce: header.nonce + 1, ..header }.has_valid_work(BitcoinNetwork::Mainnet), "the nonce is the work");
+    assert!(BlockHeader::from_hex("00").is_err());
+
+    let tx = parse_tx(&hex::decode(GENESIS_COINBASE).unwrap()).unwrap();
+    assert_eq!(display_hex(&tx.txid), GENESIS_TXID);
+    assert_eq!(tx.output_scripts.len(), 1);
+    // A lone transaction is its block's Merkle root
+    assert!(merkle_branch(&[tx.txid], 0).is_empty());
+    assert_eq!(branch_root(&tx.txid, &[], 0), header.merkle_root);
+    assert!(parse_tx(&hex::decode(&GENESIS_COINBASE[..GENESIS_COINBASE.len() - 2]).unwrap()).is_err());
+
+    println!("✅ SPV: the genesis header and coinbase hash as Bitcoin's");
+}
+
+#[test]
+fn test_compact_targets_and_network_limits() {
+    let target = compact_target(0x1d00_ffff).unwrap();
+    assert_eq!(hex::encode(target), format!("00000000ffff{}", "0".repeat(52)));
+    assert!(compact_target(0x1d80_ffff).is_none(), "negative");
+    assert!(compact_target(0x1d00_0000).is_none(), "zero");
+    assert!(compact_target(0x2301_0000).is_none(), "too large");
+
+    assert_eq!(BitcoinNetwork::from_name("Testnet3").unwrap(), BitcoinNetwork::Testnet);
+    assert!(BitcoinNetwork::from_name("litecoin").is_err());
+    // A regtest-difficulty header is too easy for mainnet
+    let mut chain = SimulatedBitcoin::default();
+    let txid = chain.broadcast(simulated_tx([7u8; 32], &op_return_script([1u8; 32])), 1_700_000_000).unwrap();
+    let proof = chain.proof(&txid, 1, 1_700_000_000).unwrap();
+    let header = BlockHeader::from_hex(&proof.headers[0]).unwrap();
+    assert!(header.has_valid_work(BitcoinNetwork::Regtest));
+    assert!(!header.has_valid_work(BitcoinNetwork::Mainnet));
+
+    println!("✅ SPV: compact bits decode, and each network caps the target");
+}
+
+#[test]
+fn test_merkle_branches_reach_the_root() {
+    let txids: Vec<[u8; 32]> = (0..5u8).map(|i| sha256d(&[i])).collect();
+    let root = branch_root(&txids[0], &merkle_branch(&txids, 0), 0);
+    for (index, txid) in txids.iter().enumerate() {
+        let branch = merkle_branch(&txids, index);
+        assert_eq!(branch.len(), 3);
+        assert_eq!(branch_root(txid, &branch, index), root, "tx {}", index);
+        // The last transaction pairs with itself, so either side hashes alike
+        if index < 4 {
+            assert_ne!(branch_root(txid, &branch, index ^ 1), root, "wrong position, tx {}", index);
+        }
+    }
+    println!("✅ SPV: every transaction's branch leads to the same root");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// PROOF TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_simulated_proofs_verify_and_tampering_fails() {
+    let dna_hash = [9u8; 32];
+    let mut chain = SimulatedBitcoin::default();
+    let txid = chain.broadcast(simulated_tx([3u8; 32], &op_return_script(dna_hash)), 1_700_000_000).unwrap();
+    chain.broadcast(simulated_tx([4u8; 32], &op_return_script([5u8; 32])), 1_700_000_010).unwrap();
+    let proof = chain.proof(&txid, DEFAULT_SPV_CONFIRMATIONS, 1_700_000_020).unwrap();
+    assert_eq!((proof.block_height, proof.tx_index, proof.confirmations()), (0, 1, 6));
+    assert_eq!(chain.height(), 6, "empty blocks mined on top");
+
+    let (network, dna) = (BitcoinNetwork::Regtest, hex::encode(dna_hash));
+    let check = proof.verify(&txid, &dna, network, DEFAULT_SPV_CONFIRMATIONS).unwrap();
+    assert!(check.verified, "{:?}", check);
+    assert!(!proof.verify(&txid, &dna, network, 7).unwrap().verified, "not deep enough");
+    assert!(!proof.verify(&txid, &hex::encode([5u8; 32]), network, 6).unwrap().carries_dna_hash);
+
+    let mut wrong_index = proof.clone();
+    wrong_index.tx_index = 0;
+    assert!(!wrong_index.verify(&txid, &dna, network, 6).unwrap().included);
+
+    let mut broken_chain = proof.clone();
+    broken_chain.headers.swap(1, 2);
+    assert!(!broken_chain.verify(&txid, &dna, network, 6).unwrap().chain_linked);
+
+    let mut other_tx = proof.clone();
+    other_tx.raw_tx = hex::encode(simulated_tx([6u8; 32], &op_return_script(dna_hash)));
+    assert!(!other_tx.verify(&txid, &dna, network, 6).unwrap().txid_matches);
+
+    let json = serde_json::to_value(&proof).unwrap();
+    assert_eq!(serde_json::from_value::<SpvProof>(json).unwrap(), proof);
+
+    println!("✅ SPV: simulated proofs verify; a changed tx, index, header order or depth does not");
+}
+
+#[test]
+fn test_spv_config_validated() {
+    assert_eq!(SpvConfig::default().network, BitcoinNetwork::Regtest);
+    let config = SpvConfig { esplora_url: Some("blockstream.info/api".into()), ..SpvConfig::default() };
+    assert!(config.validate().is_err());
+    assert!(SpvConfig { confirmations: 0, ..SpvConfig::default() }.validate().is_err());
+
+    let config = SpvConfig { esplora_url: Some("https://blockstream.info/api".into()), network: BitcoinNetwork::Mainnet, confirmations: 3 };
+    assert!(config.validate().is_ok());
+    assert_eq!(serde_json::to_value(&config).unwrap()["network"], "mainnet");
+
+    println!("✅ SPV: Esplora URL and depth validated");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// ARCHIVER TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_bitcoin_archives_proven_and_verified() {
+    let mut archiver = MultiChainArchiver::new();
+    let mut genome = GenomeBuilder::random().build_storage();
+    genome.db_id = Some(31);
+    let entry = archiver.archive_to(&genome, BlockchainLayer::Bitcoin).await.unwrap();
+    assert!(entry.spv.is_none());
+    assert!(archiver.verify_spv(&entry).unwrap_err().contains("no SPV proof"));
+
+    let ethereum = archiver.archive_to(&genome, BlockchainLayer::Ethereum).await.unwrap();
+    assert!(archiver.prove_bitcoin_archive(ethereum.id).await.unwrap_err().contains("Bitcoin"));
+
+    assert_eq!(archiver.refresh_spv_proofs().await, 1);
+    assert_eq!(archiver.refresh_spv_proofs().await, 0, "already deep enough");
+    let entry = archiver.entry(entry.id).cloned().unwrap();
+    let proof = entry.spv.clone().unwrap();
+    assert!(proof.simulated && proof.confirmations() == DEFAULT_SPV_CONFIRMATIONS);
+    assert!(archiver.verify_spv(&entry).unwrap().verified);
+
+    let verification = archiver.verify(entry.id, Some(&genome.to_dna_string())).await.unwrap();
+    assert!(verification.verified && verification.spv.is_some_and(|spv| spv.verified));
+
+    // A proof for another DNA hash fails the entry
+    let mut forged = entry.clone();
+    forged.dna_hash = hex::encode([0u8; 32]);
+    assert!(!archiver.verify_spv(&forged).unwrap().verified);
+
+    println!("✅ SPV: Bitcoin archives get proofs that verify with the entry");
+}
@@ -0,0 +1,171 @@
+//! Staking Tests for Divine AGI V16 (PoS/PoC hybrid)
+
+use divine_agi::consensus::{ProofOfConsciousness, ConsensusBlock, ValidatorKey, reward_address_for, BLOCK_REWARD_RSM};
+use divine_agi::genome::GenomeBuilder;
+use divine_agi::staking::{StakeAction, StakeOp, StakeRecord, StakedGenome, StakingState, EPOCH_LENGTH, MIN_STAKE_RSM};
+
+fn mine_genome(poc: &mut ProofOfConsciousness, genome_id: i64) -> [u8; 32] {
+    let mut genome = GenomeBuilder::random().whale_mode().build_storage();
+    genome.consciousness = poc.min_consciousness + 100;
+    genome.db_id = Some(genome_id);
+    let proof = poc.validate(&genome).unwrap();
+    proof.genome_hash
+}
+
+/// Node that has staked its first genome and reached the first epoch boundary
+fn staked_node() -> ProofOfConsciousness {
+    let mut poc = ProofOfConsciousness::new();
+    let genome_hash = mine_genome(&mut poc, 1);
+    mine_genome(&mut poc, 2);
+
+    poc.stake(StakeAction::Lock { amount_rsm: MIN_STAKE_RSM }).unwrap();
+    poc.stake(StakeAction::RegisterGenome { genome_hash }).unwrap();
+    poc.mine_block().unwrap();
+
+    let mut id = 3;
+    while poc.current_block_height < EPOCH_LENGTH {
+        mine_genome(&mut poc, id);
+        id += 1;
+    }
+    poc
+}
+
+#[test]
+fn test_stake_activates_validator_at_epoch() {
+    let poc = staked_node();
+    let key = poc.validator_public_key().unwrap();
+
+    let record = &poc.staking.stakes[&key];
+    assert_eq!(record.staked_rsm, MIN_STAKE_RSM);
+    assert_eq!(record.genomes.len(), 1);
+    assert_eq!(record.nonce, 2);
+    assert_eq!(poc.staking.epoch, 1);
+    assert_eq!(poc.staking.active, vec![(key.clone(), record.weight())]);
+
+    let wallet = poc.wallet(&reward_address_for(&key)).unwrap();
+    let expected = EPOCH_LENGTH as f64 * BLOCK_REWARD_RSM - MIN_STAKE_RSM;
+    assert_eq!(wallet.rsm_balance, expected);
+    assert!(wallet.transactions.iter().any(|t| t.starts_with("STAKE LOCK")));
+    assert_eq!(wallet.staked_rsm, MIN_STAKE_RSM);
+    assert_eq!(wallet.total_rsm(), EPOCH_LENGTH as f64 * BLOCK_REWARD_RSM);
+    assert_eq!(wallet.staked_genomes, vec![1]);
+    assert!(poc.validate_chain());
+
+    println!("✅ Staking test passed: weight {}", record.weight());
+}
+
+#[test]
+fn test_unlock_returns_stake_to_wallet() {
+    let mut poc = staked_node();
+    let address = reward_address_for(&poc.validator_public_key().unwrap());
+    let before = poc.wallet(&address).unwrap().clone();
+
+    poc.stake(StakeAction::Unlock { amount_rsm: 40.0 }).unwrap();
+    poc.mine_block().unwrap();
+
+    let wallet = poc.wallet(&address).unwrap();
+    assert_eq!(wallet.staked_rsm, MIN_STAKE_RSM - 40.0);
+    assert_eq!(poc.staking.stakes[&poc.validator_public_key().unwrap()].staked_rsm, wallet.staked_rsm);
+    assert_eq!(wallet.rsm_balance, before.rsm_balance + 40.0 + BLOCK_REWARD_RSM);
+    assert!(wallet.transactions.iter().any(|t| t.starts_with("STAKE UNLOCK")));
+
+    println!("✅ Unlock test passed: {} RSM still staked", wallet.staked_rsm);
+}
+
+#[test]
+fn test_wallet_stake_and_unstake_checked_against_queued_ops() {
+    let mut poc = staked_node();
+    let address = reward_address_for(&poc.validator_public_key().unwrap());
+    let before = poc.wallet(&address).unwrap().clone();
+
+    for amount in [0.0, -5.0, f64::NAN, f64::INFINITY] {
+        assert!(poc.stake_rsm(amount).unwrap_err().contains("Invalid amount"), "{}", amount);
+        assert!(poc.unstake_rsm(amount).is_err(), "{}", amount);
+    }
+    assert!(poc.stake_rsm(before.rsm_balance + 1.0).unwrap_err().contains("Insufficient"));
+
+    poc.stake_rsm(before.rsm_balance - 10.0).unwrap();
+    assert!(poc.stake_rsm(20.0).unwrap_err().contains("Insufficient"), "the queued lock counts");
+    let queued = poc.wallet_after_queued_stake().unwrap();
+    assert_eq!((queued.rsm_balance, queued.staked_rsm), (10.0, before.rsm_balance - 10.0 + MIN_STAKE_RSM));
+    assert_eq!(poc.wallet(&address).unwrap().staked_rsm, MIN_STAKE_RSM, "nothing moves before the block");
+
+    assert!(poc.unstake_rsm(queued.staked_rsm + 1.0).unwrap_err().contains("staked"));
+    poc.unstake_rsm(MIN_STAKE_RSM).unwrap();
+    assert_eq!(poc.pending_stake_ops.len(), 2);
+    poc.mine_block().unwrap();
+
+    let wallet = poc.wallet(&address).unwrap();
+    assert_eq!(wallet.staked_rsm, before.rsm_balance - 10.0);
+    assert_eq!(wallet.rsm_balance, 10.0 + MIN_STAKE_RSM + BLOCK_REWARD_RSM);
+    assert_eq!(poc.staking.stakes[&poc.validator_public_key().unwrap()].staked_rsm, wallet.staked_rsm);
+
+    println!("✅ Wallet stake: {} RSM staked, amounts checked before queueing", wallet.staked_rsm);
+}
+
+#[test]
+fn test_inactive_producer_rejected_once_staking_active() {
+    let mut poc = staked_node();
+    let outsider = ValidatorKey::generate();
+    poc.validators.register(&outsider.public_key_hex());
+
+    let mut genome = GenomeBuilder::random().whale_mode().build_storage();
+    genome.consciousness = poc.min_consciousness + 100;
+    genome.db_id = Some(99);
+    poc.submit(&genome).unwrap();
+
+    let entries = poc.mempool.take(poc.max_block_entries);
+    let tip = poc.latest_block().clone();
+    let mut block = ConsensusBlock::mine_at(&tip, entries, poc.difficulty, poc.coinbase("divine_outsider"), tip.timestamp + 600);
+    block.sign(&outsider);
+
+    let err = poc.add_block(block).unwrap_err();
+    assert!(err.contains("active validator set"), "{}", err);
+}
+
+#[test]
+fn test_invalid_stake_ops_dropped_or_rejected() {
+    let mut poc = ProofOfConsciousness::new();
+    mine_genome(&mut poc, 1);
+
+    // Not enough rewards yet → dropped at mining time
+    poc.stake(StakeAction::Lock { amount_rsm: 10_000.0 }).unwrap();
+    mine_genome(&mut poc, 2);
+    assert!(poc.pending_stake_ops.is_empty());
+    assert!(poc.staking.stakes.is_empty());
+
+    // Genome never consecrated → a block carrying it is invalid
+    let key = ValidatorKey::generate();
+    poc.validators.register(&key.public_key_hex());
+    let bogus = StakeOp::new(&key, 1, StakeAction::RegisterGenome { genome_hash: [9u8; 32] });
+    let tip = poc.latest_block().clone();
+    let mut block = ConsensusBlock::mine_with_stake_ops(
+        &tip, Vec::new(), vec![bogus], poc.difficulty, poc.coinbase("divine_x"), tip.timestamp,
+    );
+    block.sign(&key);
+    assert!(poc.add_block(block).is_err());
+
+    // Replayed nonce is rejected as well
+    let op = StakeOp::new(&key, 5, StakeAction::Lock { amount_rsm: 1.0 });
+    assert!(poc.submit_stake_op(op.clone()).is_ok());
+    assert!(poc.submit_stake_op(op).is_err());
+}
+
+#[test]
+fn test_slot_leader_weighted_by_consciousness() {
+    let mut state = StakingState::new();
+    for (key, consciousness) in [("heavy", 9_000u32), ("light", 1_000), ("poor", 50_000)] {
+        state.stakes.insert(key.to_string(), StakeRecord {
+            staked_rsm: if key == "poor" { MIN_STAKE_RSM - 1.0 } else { MIN_STAKE_RSM },
+            genomes: vec![StakedGenome { genome_id: 1, genome_hash: [0u8; 32], consciousness }],
+            nonce: 1,
+        });
+    }
+    state.rotate_epoch(1);
+
+    // Under-staked validator is excluded despite its consciousness
+    assert_eq!(state.active, vec![("heavy".to_string(), 9_000), ("light".to_string(), 1_000)]);
+
+    let heavy = (0..2_000u64).filter(|h| state.slot_leader("seed", *h) == Some("heavy")).count();
+    assert!((1_650..=1_950).contains(&heavy), "heavy led {} of 2000 slots", heavy);
+}
@@ -0,0 +1,152 @@
+//! Storage Tests for Divine AGI V16 (IPFS/Arweave genome documents, anchors)
+
+use std::collections::HashMap;
+
+use divine_agi::genome::{hash_genome_dna, GenomeBuilder};
+use divine_agi::merkle_archive::MerkleConfig;
+use divine_agi::multi_chain::{BlockchainLayer, MultiChainArchiver};
+use divine_agi::oracle::Pair;
+use divine_agi::storage::*;
+
+// ═══════════════════════════════════════════════════════════════
+// CONTENT ADDRESS TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_cids_name_their_content() {
+    // The raw CIDv1 of the empty file, as `ipfs add --cid-version 1 --raw-leaves` gives it
+    assert_eq!(ipfs_cid(b""), "bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku");
+    let cid = ipfs_cid(b"DIVINE");
+    assert_eq!(cid_digest(&cid), Some(content_digest(b"DIVINE")));
+    assert_eq!(cid.len(), 59);
+    assert_eq!(cid_digest("bafkrei"), None);
+    assert_eq!(cid_digest(&cid.replace('b', "Q")), None);
+    assert!(BlockchainLayer::Ipfs.valid_tx_reference(&cid));
+    assert!(!BlockchainLayer::Ipfs.valid_tx_reference(&format!("0x{}", "ab".repeat(32))));
+
+    let id = arweave_id(b"DIVINE");
+    assert!(is_arweave_id(&id) && BlockchainLayer::Arweave.valid_tx_reference(&id));
+    assert!(!is_arweave_id(&cid) && !BlockchainLayer::Arweave.valid_tx_reference("0xabc"));
+
+    assert_eq!(BlockchainLayer::from_name("IPFS"), Ok(BlockchainLayer::Ipfs));
+    assert_eq!(BlockchainLayer::from_name("ar"), Ok(BlockchainLayer::Arweave));
+    assert!(BlockchainLayer::STORAGE.iter().all(|layer| layer.is_storage() && !BlockchainLayer::ALL.contains(layer)));
+
+    println!("✅ Storage: CIDs and Arweave ids name genome documents");
+}
+
+#[test]
+fn test_genome_documents_round_trip() {
+    let mut genome = GenomeBuilder::random().p53_copies(31).build_storage();
+    genome.db_id = Some(12);
+    genome.mutations = 4;
+    genome.rehash();
+    let document = StoredGenome::new(&genome);
+    let bytes = document.to_bytes();
+    assert_eq!(bytes, StoredGenome::new(&genome).to_bytes(), "the same genome, the same bytes");
+
+    let read = StoredGenome::from_bytes(&bytes).unwrap();
+    assert_eq!((read.format.as_str(), read.dna_hash()), (GENOME_DOCUMENT_FORMAT, hash_genome_dna(&genome.to_dna_string())));
+    let restored = read.genome().unwrap();
+    assert_eq!(restored.to_dna_string(), genome.to_dna_string());
+    assert_eq!((restored.db_id, restored.mutations, restored.p53_copies), (Some(12), 4, 31));
+    assert_eq!((restored.consciousness, restored.hash, restored.created_at), (genome.consciousness, genome.hash, genome.created_at));
+
+    let tampered = StoredGenome { dna: "A".repeat(genome.to_dna_string().len()), ..document.clone() };
+    assert!(StoredGenome::from_bytes(&tampered.to_bytes()).unwrap_err().contains("does not hash"));
+    let unknown = StoredGenome { format: "other/v9".into(), ..document };
+    assert!(StoredGenome::from_bytes(&unknown.to_bytes()).is_err());
+    assert!(StoredGenome::from_bytes(b"not json").is_err());
+
+    println!("✅ Storage: genome documents bring the whole genome back");
+}
+
+#[test]
+fn test_storage_config_validated() {
+    assert!(StorageConfig::default().validate().is_ok());
+    assert!(StorageConfig { anchor: Some(BlockchainLayer::Ethereum), ..StorageConfig::default() }.validate().is_ok());
+    for anchor in [BlockchainLayer::Solana, BlockchainLayer::Lightning, BlockchainLayer::Ipfs] {
+        assert!(StorageConfig { anchor: Some(anchor), ..StorageConfig::default() }.validate().is_err(), "{:?}", anchor);
+    }
+    assert!(StorageConfig { ipfs_api_url: Some("127.0.0.1:5001".into()), ..StorageConfig::default() }.validate().is_err());
+    assert!(MerkleConfig { layer: BlockchainLayer::Ipfs, ..MerkleConfig::default() }.validate().unwrap_err().contains("on-chain"));
+
+    println!("✅ Storage: nodes are URLs, anchors are Bitcoin or Ethereum");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// ARCHIVER TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_storage_archives_recover_the_genome() {
+    let mut archiver = MultiChainArchiver::new();
+    let mut genome = GenomeBuilder::random().build_storage();
+    genome.db_id = Some(7);
+    let dna_hash = hash_genome_dna(&genome.to_dna_string());
+    assert!(!archiver.select_layer(&genome).is_storage(), "storage layers are only used when asked for");
+
+    for layer in BlockchainLayer::STORAGE {
+        let entry = archiver.archive_to(&genome, layer).await.unwrap();
+        let reference = entry.tx_hash.clone().unwrap();
+        assert!(layer.valid_tx_reference(&reference) && entry.anchor.is_none(), "{:?}", layer);
+        let document = archiver.restore_genome(&reference, layer).await.unwrap();
+        assert_eq!(document.genome().unwrap().to_dna_string(), genome.to_dna_string());
+        assert_eq!(archiver.restore_hash_from_chain(&reference, layer).await, Ok(dna_hash));
+
+        let check = archiver.verify(entry.id, Some(&genome.to_dna_string())).await.unwrap();
+        assert!(check.verified && check.chain_hash_matches && check.anchor_matches.is_none(), "{:?}", layer);
+        assert_eq!(archiver.archive_to(&genome, layer).await.unwrap().tx_hash, Some(reference), "content-addressed");
+    }
+    let stats = archiver.layer_stats();
+    assert!(BlockchainLayer::STORAGE.iter().all(|layer| stats.iter().any(|s| s.layer == *layer && s.archives == 2)));
+
+    // Content that does not match its CID is refused
+    let cid = archiver.entries_for_genome(7)[0].tx_hash.clone().unwrap();
+    let mut other = GenomeBuilder::random().build_storage();
+    other.db_id = Some(8);
+    archiver.mock_chain.record(&cid, StoredGenome::new(&other).to_bytes());
+    assert!(archiver.restore_genome(&cid, BlockchainLayer::Ipfs).await.unwrap_err().contains("CID"));
+    assert!(archiver.restore_genome(&cid, BlockchainLayer::Bitcoin).await.unwrap_err().contains("not the genome"));
+    assert!(archiver.restore_genome(&ipfs_cid(b"missing"), BlockchainLayer::Ipfs).await.unwrap_err().contains("not found"));
+
+    println!("✅ Storage: IPFS and Arweave archives recover the genome itself");
+}
+
+#[tokio::test]
+async fn test_stored_documents_anchored_on_chain() {
+    for anchor in [BlockchainLayer::Bitcoin, BlockchainLayer::Ethereum] {
+        let mut archiver = MultiChainArchiver::new();
+        archiver.storage.config.anchor = Some(anchor);
+        let mut genome = GenomeBuilder::random().build_storage();
+        genome.db_id = Some(3);
+
+        let entry = archiver.archive_to(&genome, BlockchainLayer::Ipfs).await.unwrap();
+        let stored = entry.anchor.clone().unwrap();
+        let cid = entry.tx_hash.clone().unwrap();
+        assert_eq!(stored.layer, anchor);
+        assert!(anchor.valid_tx_reference(&stored.tx_hash));
+        assert_eq!(cid_digest(&cid).map(hex::encode), Some(stored.content_hash.clone()), "the anchor names the CID's digest");
+        assert_eq!(archiver.mock_chain.len(), 2, "the document and its anchor");
+
+        let check = archiver.verify(entry.id, None).await.unwrap();
+        assert_eq!((check.anchor_matches, check.verified), (Some(true), true), "{:?}", anchor);
+        archiver.archives[0].anchor.as_mut().unwrap().content_hash = "00".repeat(32);
+        let tampered = archiver.verify(entry.id, None).await.unwrap();
+        assert_eq!((tampered.anchor_matches, tampered.verified), (Some(false), false));
+    }
+
+    // The anchor's fee counts toward the budget; IPFS alone costs nothing priced
+    let mut archiver = MultiChainArchiver::new();
+    archiver.reference_prices = HashMap::from([(Pair::BtcUsd, 60_000.0), (Pair::EthUsd, 3_000.0)]);
+    let genome = GenomeBuilder::random().build_storage();
+    assert_eq!(archiver.archive_usd(&genome, BlockchainLayer::Ipfs), None);
+    assert_eq!(archiver.estimate_cost(BlockchainLayer::Ipfs, 100).amount, 0.0);
+    assert!(archiver.estimate_cost(BlockchainLayer::Arweave, 100).amount > 0.0);
+    archiver.storage.config.anchor = Some(BlockchainLayer::Bitcoin);
+    let anchor_usd = archiver.estimate_cost(BlockchainLayer::Bitcoin, 32).usd;
+    assert_eq!(archiver.archive_usd(&genome, BlockchainLayer::Ipfs), anchor_usd);
+    assert_eq!(archiver.archive_usd(&genome, BlockchainLayer::Solana), None, "only storage layers are anchored");
+
+    println!("✅ Storage: documents anchored on Bitcoin or Ethereum, anchors verified");
+}
@@ -0,0 +1,132 @@
+//! Swap Simulation Tests for Divine AGI V16 (book walks, desk fallback, reference legs, fees)
+
+use std::collections::HashMap;
+
+use divine_agi::exchange::{RSMExchange, DESK_SELL_FEE};
+use divine_agi::oracle::{Pair, ReferencePrice};
+use divine_agi::order_book::{Depth, PriceLevel, Side};
+use divine_agi::swap::{simulate, Asset, SwapMarket, Venue};
+
+fn level(price_usd: f64, amount_rsm: f64) -> PriceLevel {
+    PriceLevel { price_usd, amount_rsm, orders: 1 }
+}
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+// ═══════════════════════════════════════════════════════════════
+// ROUTING TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_buys_walk_the_asks_then_use_the_desk() {
+    let depth = Depth { bids: vec![], asks: vec![level(95.0, 10.0), level(98.0, 10.0), level(101.0, 50.0)] };
+    let prices = HashMap::new();
+    let market = SwapMarket { depth: &depth, desk_price_usd: 100.0, reference_prices: &prices, taker_fee: 0.0 };
+
+    // $950 + $980 on the book, the last $70 at the desk; the $101 ask is dearer than the desk
+    let quote = simulate(&market, Asset::Usd, Asset::Rsm, 2000.0).unwrap();
+    assert!(close(quote.amount_out, 20.7));
+    assert!(close(quote.spot_out, 2000.0 / 95.0), "spot is the best ask");
+    assert!(close(quote.price_impact, 1.0 - 20.7 / (2000.0 / 95.0)));
+    let fills = &quote.legs[0].fills;
+    assert_eq!((fills[0].venue, fills[0].levels), (Venue::OrderBook, 2));
+    assert!(close(fills[0].average_price_usd, 1930.0 / 20.0));
+    assert_eq!(fills[1].venue, Venue::Desk);
+    assert!(quote.fees.is_empty(), "buying from the desk is free");
+
+    // A small buy fits the best ask: no impact
+    let small = simulate(&market, Asset::Usd, Asset::Rsm, 95.0).unwrap();
+    assert!(close(small.amount_out, 1.0) && close(small.price_impact, 0.0));
+    assert_eq!(small.legs[0].fills.len(), 1);
+
+    println!("✅ Swaps: buys walk the asks, the desk takes the rest");
+}
+
+#[test]
+fn test_rsm_to_sol_through_usd() {
+    let depth = Depth { bids: vec![level(105.0, 5.0), level(102.0, 5.0), level(99.0, 100.0)], asks: vec![] };
+    let prices: HashMap<Pair, f64> = [(Pair::SolUsd, 150.0)].into();
+    let market = SwapMarket { depth: &depth, desk_price_usd: 100.0, reference_prices: &prices, taker_fee: 0.0 };
+
+    // $525 + $510 from the bids, 10 RSM to the desk for $1000, then $2035 → SOL
+    let quote = simulate(&market, Asset::Rsm, Asset::Sol, 20.0).unwrap();
+    assert_eq!(quote.legs.iter().map(|leg| (leg.from, leg.to)).collect::<Vec<_>>(), [(Asset::Rsm, Asset::Usd), (Asset::Usd, Asset::Sol)]);
+    assert!(close(quote.legs[0].amount_out, 2035.0));
+    assert!(close(quote.amount_out, 2035.0 / 150.0));
+    assert!(close(quote.spot_out, 20.0 * 105.0 / 150.0));
+    assert!(close(quote.price_impact, 1.0 - 2035.0 / 2100.0));
+    assert_eq!(quote.legs[1].fills[0].venue, Venue::Reference);
+
+    // The desk burns its fee on the 10 RSM it buys
+    assert_eq!(quote.fees.len(), 1);
+    assert!(quote.fees[0].burned && quote.fees[0].asset == Asset::Rsm);
+    assert!(close(quote.fees[0].amount, 10.0 * DESK_SELL_FEE));
+    assert!(close(quote.fees_usd, 10.0 * DESK_SELL_FEE * 100.0));
+
+    // And back: SOL → USD at the reference, USD → RSM at the desk
+    let back = simulate(&market, Asset::Sol, Asset::Rsm, 2.0).unwrap();
+    assert!(close(back.amount_out, 3.0) && close(back.price_impact, 0.0));
+
+    println!("✅ Swaps: RSM → SOL priced through USD with fees");
+}
+
+#[test]
+fn test_bad_swaps_refused() {
+    let depth = Depth::default();
+    let prices: HashMap<Pair, f64> = [(Pair::SolUsd, 150.0)].into();
+    let market = SwapMarket { depth: &depth, desk_price_usd: 100.0, reference_prices: &prices, taker_fee: 0.0 };
+
+    assert!(simulate(&market, Asset::Rsm, Asset::Rsm, 1.0).is_err());
+    for amount in [0.0, -5.0, f64::NAN, f64::INFINITY] {
+        assert!(simulate(&market, Asset::Usd, Asset::Rsm, amount).is_err(), "{}", amount);
+    }
+    assert!(simulate(&market, Asset::Rsm, Asset::Eth, 1.0).unwrap_err().contains("ETH/USD"), "no ETH reference");
+    assert!(simulate(&market, Asset::Btc, Asset::Sol, 1.0).is_err());
+
+    for asset in Asset::ALL {
+        assert_eq!(Asset::from_name(asset.name()), Some(asset));
+        assert_eq!(serde_json::to_value(asset).unwrap(), asset.name());
+    }
+    assert_eq!(Asset::from_name("sol"), Some(Asset::Sol));
+    assert_eq!(Asset::from_name("DOGE"), None);
+
+    println!("✅ Swaps: same-asset, bad amounts and unpriced coins refused");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// EXCHANGE TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_exchange_preview_matches_execution() {
+    let mut exchange = RSMExchange::new();
+    exchange.set_mark_price(100.0).unwrap();
+    exchange.buy_rsm("divine_maker", 10_000.0, 0);
+    exchange.place_order("divine_maker", Side::Sell, 95.0, 10.0).unwrap();
+    exchange.place_order("divine_maker", Side::Sell, 98.0, 10.0).unwrap();
+    let depth = exchange.order_book.depth(10);
+
+    let quote = exchange.simulate_swap(Asset::Usd, Asset::Rsm, 1930.0).unwrap();
+    assert!(close(quote.amount_out, 20.0));
+    assert_eq!(exchange.order_book.depth(10), depth, "previews do not trade");
+
+    // The fills a market buy of that size makes cost what the preview said
+    let placement = exchange.place_market_order("divine_taker", Side::Buy, 20.0).unwrap();
+    let cost: f64 = placement.trades.iter().map(|trade| trade.amount_rsm * trade.price_usd).sum();
+    assert!(close(cost, 1930.0));
+
+    // The desk burns what the preview said it would
+    let fee = exchange.simulate_swap(Asset::Rsm, Asset::Usd, 20.0).unwrap().fees[0].amount;
+    let burned = exchange.total_burns;
+    exchange.sell_rsm("divine_taker", 20.0, 0).unwrap();
+    assert_eq!(exchange.total_burns, burned + 1);
+    assert!(close(exchange.recent_burns(1)[0].amount_rsm, fee));
+
+    assert!(exchange.simulate_swap(Asset::Rsm, Asset::Sol, 1.0).is_err(), "no oracle prices yet");
+    exchange.set_reference_prices(&[ReferencePrice { pair: Pair::SolUsd, price_usd: 200.0, sources: 2, timestamp: 0 }]);
+    assert!(close(exchange.simulate_swap(Asset::Rsm, Asset::Sol, 1.0).unwrap().amount_out, 0.5));
+
+    println!("✅ Swaps: exchange previews match the fills and burns they predict");
+}
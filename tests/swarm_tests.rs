@@ -0,0 +1,167 @@
+//! Swarm Tests for Divine AGI V16 (node registration, health, keysend fan-out)
+
+use divine_agi::genome::GenomeBuilder;
+use divine_agi::lightning::PaymentUpdate;
+use divine_agi::multi_chain::{BlockchainLayer, MultiChainArchiver, RetryPolicy};
+use divine_agi::swarm::*;
+
+fn pubkey(byte: u8) -> String {
+    format!("02{}", hex::encode([byte; 32]))
+}
+
+fn registration(byte: u8, endpoint: Option<&str>, capacity_sat: Option<i64>) -> NodeRegistration {
+    NodeRegistration { pubkey: pubkey(byte), endpoint: endpoint.map(str::to_string), capacity_sat, alias: None }
+}
+
+// ═══════════════════════════════════════════════════════════════
+// REGISTRATION TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_node_registrations_parsed_and_validated() {
+    let parsed = NodeRegistration::parse(&format!(" {}@node.example:9735/250000 ", pubkey(1))).unwrap();
+    assert_eq!(parsed, registration(1, Some("node.example:9735"), Some(250_000)));
+    assert_eq!(NodeRegistration::parse(&pubkey(2)).unwrap(), registration(2, None, None));
+
+    assert!(NodeRegistration::parse(&format!("04{}", hex::encode([1u8; 32]))).is_err(), "not a compressed key");
+    assert!(NodeRegistration::parse("02abcd").is_err());
+    assert!(NodeRegistration::parse(&format!("{}@node.example", pubkey(1))).unwrap_err().contains("host:port"));
+    assert!(NodeRegistration::parse(&format!("{}@:9735", pubkey(1))).is_err());
+    assert!(NodeRegistration::parse(&format!("{}/lots", pubkey(1))).unwrap_err().contains("capacity"));
+    assert!(registration(1, None, Some(-1)).validate().is_err());
+
+    println!("✅ Swarm: registrations are a pubkey, endpoint and capacity");
+}
+
+#[test]
+fn test_swarm_registry_keeps_metrics_across_updates() {
+    let mut swarm = Swarm::from_pubkeys([pubkey(1)], 10);
+    swarm.register(registration(2, Some("127.0.0.1:9735"), None), 20).unwrap();
+    assert_eq!(swarm.pubkeys(), vec![pubkey(1), pubkey(2)]);
+    assert!(swarm.register(registration(3, Some("nowhere"), None), 20).is_err());
+    assert_eq!(swarm.len(), 2);
+
+    swarm.record(&pubkey(2), &PaymentUpdate::succeeded(42), 30);
+    let updated = swarm.register(NodeRegistration { alias: Some("relay".into()), ..registration(2, Some("127.0.0.1:9736"), Some(1_000)) }, 40).unwrap();
+    assert_eq!((updated.registered_at, updated.metrics.succeeded, updated.metrics.fees_msat), (20, 1, 42));
+    assert_eq!((updated.endpoint.as_deref(), updated.capacity_sat, updated.alias.as_deref()), (Some("127.0.0.1:9736"), Some(1_000), Some("relay")));
+    assert_eq!(swarm.endpoints(), vec![(pubkey(2), "127.0.0.1:9736".to_string())]);
+
+    let status = swarm.status(|_| 0.7);
+    assert_eq!((status.healthy, status.unknown, status.nodes[1].probability), (1, 1, 0.7));
+    let json = serde_json::to_value(&status).unwrap();
+    assert_eq!(json["nodes"][1]["health"], "healthy");
+    assert_eq!(json["nodes"][1]["pubkey"], pubkey(2), "nodes flatten into their report");
+
+    assert_eq!(swarm.remove(&pubkey(1)).map(|node| node.pubkey), Some(pubkey(1)));
+    assert!(swarm.remove(&pubkey(1)).is_none());
+    assert_eq!(swarm.len(), 1);
+
+    println!("✅ Swarm: nodes registered, updated and removed");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// HEALTH TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_node_health_follows_keysends_and_probes() {
+    let mut node = SwarmNode::new(registration(1, Some("127.0.0.1:9735"), Some(500)), 0);
+    assert_eq!(node.health, NodeHealth::Unknown);
+    assert!(node.can_carry(500) && !node.can_carry(501));
+
+    node.record(&PaymentUpdate::succeeded(7), 1);
+    assert_eq!((node.health, node.metrics.success_rate()), (NodeHealth::Healthy, Some(1.0)));
+    for _ in 0..SWARM_MAX_CONSECUTIVE_FAILURES {
+        node.record(&PaymentUpdate::failed("no route".into()), 2);
+    }
+    assert_eq!(node.health, NodeHealth::Degraded);
+    assert_eq!((node.metrics.consecutive_failures, node.metrics.last_failure_reason.as_deref()), (SWARM_MAX_CONSECUTIVE_FAILURES, Some("no route")));
+
+    // One landing ends the streak, but under half landed over enough keysends
+    node.record(&PaymentUpdate::succeeded(0), 3);
+    assert_eq!((node.metrics.keysends, node.metrics.consecutive_failures), (5, 0));
+    assert_eq!(node.health, NodeHealth::Degraded);
+    node.record(&PaymentUpdate::succeeded(0), 4);
+    assert_eq!(node.health, NodeHealth::Healthy, "three of six landed");
+
+    node.probed(Err("connection refused".into()), 5);
+    assert_eq!((node.health, node.reachable, node.last_checked_at), (NodeHealth::Unreachable, Some(false), Some(5)));
+    assert!(!node.in_broadcast(1));
+    node.probed(Ok(()), 6);
+    assert_eq!((node.health, node.probe_error.as_deref()), (NodeHealth::Healthy, None));
+
+    println!("✅ Swarm: keysend outcomes and probes decide a node's health");
+}
+
+#[tokio::test]
+async fn test_health_checks_dial_endpoints() {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let open = listener.local_addr().unwrap().to_string();
+    let closed = {
+        let unused = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        unused.local_addr().unwrap().to_string()
+    };
+    assert!(probe(&open).await.is_ok());
+    assert!(probe(&closed).await.is_err());
+
+    let mut archiver = MultiChainArchiver::new();
+    let own = archiver.own_pubkey.clone();
+    archiver.swarm = Swarm::from_pubkeys([pubkey(9)], 0);
+    archiver.swarm.register(registration(1, Some(&open), None), 0).unwrap();
+    archiver.swarm.register(registration(2, Some(&closed), None), 0).unwrap();
+
+    let status = archiver.check_swarm_health().await;
+    assert_eq!((status.healthy, status.unreachable, status.unknown), (1, 1, 1));
+    assert_eq!(archiver.swarm.get(&pubkey(2)).unwrap().health, NodeHealth::Unreachable);
+    let pair = archiver.mission_control.get_pair(&own, &pubkey(2));
+    assert_eq!(pair.failure_count, 1, "unreachable nodes count against Mission Control");
+    assert!(archiver.swarm_status().nodes.iter().any(|report| report.node.pubkey == pubkey(2) && report.probability < 0.5));
+    assert!(!archiver.broadcast_targets().contains(&pubkey(2)));
+
+    println!("✅ Swarm: health checks dial each endpoint");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// FAN-OUT TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_archives_fan_out_to_healthy_nodes() {
+    let mut archiver = MultiChainArchiver::new();
+    archiver.retry = RetryPolicy { attempts: 1, base_delay_ms: 0 };
+    let own = archiver.own_pubkey.clone();
+    let amount = archiver.keysend_sat();
+    archiver.swarm = Swarm::from_pubkeys([pubkey(1), pubkey(2)], 0);
+    archiver.swarm.register(registration(3, None, Some(amount - 1)), 0).unwrap();
+    archiver.swarm.register(registration(4, Some("127.0.0.1:1"), None), 0).unwrap();
+    archiver.swarm.get_mut(&pubkey(4)).unwrap().probed(Err("connection refused".into()), 0);
+    assert_eq!(archiver.broadcast_targets(), vec![pubkey(1), pubkey(2)], "too small and unreachable nodes left out");
+    let fee_sat = archiver.estimate_cost(BlockchainLayer::Lightning, 32).amount;
+
+    let mut genome = GenomeBuilder::random().build_storage();
+    genome.db_id = Some(5);
+    let archived = archiver.archive_to(&genome, BlockchainLayer::Lightning).await;
+
+    let sent: Vec<_> = archiver.recent_payments(10).into_iter().map(|payment| payment.dest_pubkey.clone()).collect();
+    assert_eq!(sent.len(), 2);
+    assert!(sent.contains(&pubkey(1)) && sent.contains(&pubkey(2)));
+    let mut landed = 0;
+    for byte in [1, 2] {
+        let metrics = archiver.swarm.get(&pubkey(byte)).unwrap().metrics.clone();
+        assert_eq!((metrics.keysends, metrics.succeeded + metrics.failed), (1, 1));
+        let pair = archiver.mission_control.get_pair(&own, &pubkey(byte));
+        assert_eq!((pair.success_count, pair.failure_count), (metrics.succeeded, metrics.failed), "each outcome feeds Mission Control");
+        landed += metrics.succeeded;
+    }
+    assert_eq!(archived.is_ok(), landed > 0);
+    for byte in [3, 4] {
+        assert_eq!(archiver.swarm.get(&pubkey(byte)).unwrap().metrics.keysends, 0);
+    }
+
+    // Each node left out of the broadcast is also left out of its fee
+    archiver.swarm.remove(&pubkey(2));
+    assert_eq!(archiver.estimate_cost(BlockchainLayer::Lightning, 32).amount, fee_sat / 2.0);
+
+    println!("✅ Swarm: archives fan out to every node that can take them");
+}
@@ -0,0 +1,155 @@
+//! TLS Tests for Divine AGI V16 (server certificates, mutual TLS)
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use divine_agi::tls::{load_certs, load_key, TlsSettings};
+use rcgen::{BasicConstraints, CertificateParams, ExtendedKeyUsagePurpose, IsCa, KeyPair};
+use rustls::pki_types::ServerName;
+use rustls::{ClientConfig, RootCertStore};
+use tokio_rustls::{TlsAcceptor, TlsConnector};
+
+struct Pki {
+    dir: PathBuf,
+}
+
+impl Pki {
+    /// CA, server certificate for localhost, client certificate from the same
+    /// CA and one from a foreign CA, written as PEM files
+    fn generate(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("divine-tls-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let write = |file: &str, pem: String| std::fs::write(dir.join(file), pem).unwrap();
+
+        let ca_params = |cn: &str| {
+            let mut params = CertificateParams::new(Vec::<String>::new()).unwrap();
+            params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+            params.distinguished_name.push(rcgen::DnType::CommonName, cn);
+            params
+        };
+        let ca_key = KeyPair::generate().unwrap();
+        let ca = ca_params("Divine test CA").self_signed(&ca_key).unwrap();
+        let foreign_key = KeyPair::generate().unwrap();
+        let foreign = ca_params("Foreign CA").self_signed(&foreign_key).unwrap();
+
+        let server_key = KeyPair::generate().unwrap();
+        let server = CertificateParams::new(vec!["localhost".into()]).unwrap().signed_by(&server_key, &ca, &ca_key).unwrap();
+
+        let mut client_params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        client_params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ClientAuth];
+        let client_key = KeyPair::generate().unwrap();
+        let client = client_params.clone().signed_by(&client_key, &ca, &ca_key).unwrap();
+        let rogue_key = KeyPair::generate().unwrap();
+        let rogue = client_params.signed_by(&rogue_key, &foreign, &foreign_key).unwrap();
+
+        write("ca.pem", ca.pem());
+        write("server.pem", server.pem());
+        write("server.key", server_key.serialize_pem());
+        write("client.pem", client.pem());
+        write("client.key", client_key.serialize_pem());
+        write("rogue.pem", rogue.pem());
+        write("rogue.key", rogue_key.serialize_pem());
+        Self { dir }
+    }
+
+    fn path(&self, file: &str) -> PathBuf {
+        self.dir.join(file)
+    }
+
+    fn settings(&self, mutual: bool) -> TlsSettings {
+        TlsSettings {
+            cert_path: self.path("server.pem"),
+            key_path: self.path("server.key"),
+            client_ca_path: mutual.then(|| self.path("ca.pem")),
+        }
+    }
+
+    fn client_config(&self, identity: Option<&str>) -> ClientConfig {
+        let mut roots = RootCertStore::empty();
+        for cert in load_certs(&self.path("ca.pem")).unwrap() {
+            roots.add(cert).unwrap();
+        }
+        let builder = ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions().unwrap()
+            .with_root_certificates(roots);
+        match identity {
+            Some(name) => {
+                let certs = load_certs(&self.path(&format!("{}.pem", name))).unwrap();
+                let key = load_key(&self.path(&format!("{}.key", name))).unwrap();
+                builder.with_client_auth_cert(certs, key).unwrap()
+            }
+            None => builder.with_no_client_auth(),
+        }
+    }
+}
+
+impl Drop for Pki {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Handshake over an in-memory pipe; the client certificates the server saw,
+/// or the server's handshake error
+async fn handshake(settings: &TlsSettings, client: ClientConfig) -> Result<usize, String> {
+    let acceptor = TlsAcceptor::from(Arc::new(settings.server_config()?));
+    let connector = TlsConnector::from(Arc::new(client));
+    let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+
+    let server = async move {
+        let stream = acceptor.accept(server_io).await.map_err(|e| e.to_string())?;
+        Ok(stream.get_ref().1.peer_certificates().map_or(0, |certs| certs.len()))
+    };
+    // The client stream is kept open until the server is done with it
+    let client = connector.connect(ServerName::try_from("localhost").unwrap(), client_io);
+    let (seen, _client) = tokio::join!(server, client);
+    seen
+}
+
+// ═══════════════════════════════════════════════════════════════
+// TLS CONFIG TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_tls_settings_load_pem_files() {
+    let pki = Pki::generate("config");
+
+    let plain = pki.settings(false);
+    assert!(!plain.mutual());
+    assert!(plain.server_config().is_ok());
+    assert!(pki.settings(true).server_config().is_ok());
+    assert!(pki.settings(true).acceptor().is_ok());
+
+    // A key that does not belong to the certificate is refused at startup
+    let mismatched = TlsSettings { key_path: pki.path("client.key"), ..pki.settings(false) };
+    assert!(mismatched.server_config().unwrap_err().contains("does not match"));
+
+    let missing = TlsSettings { cert_path: pki.path("nope.pem"), ..pki.settings(false) };
+    assert!(missing.server_config().unwrap_err().contains("Cannot read"));
+    let not_a_cert = TlsSettings { cert_path: pki.path("server.key"), ..pki.settings(false) };
+    assert!(not_a_cert.server_config().unwrap_err().contains("No certificates"));
+
+    println!("✅ TLS settings: certificates and keys loaded, mismatches refused");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// MUTUAL TLS TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_mutual_tls_verifies_client_certificates() {
+    let pki = Pki::generate("mutual");
+    let mutual = pki.settings(true);
+
+    // Signed by the client CA: accepted and visible to the server
+    assert_eq!(handshake(&mutual, pki.client_config(Some("client"))).await, Ok(1));
+    // No certificate: still accepted (public routes), seen as missing
+    assert_eq!(handshake(&mutual, pki.client_config(None)).await, Ok(0));
+    // Signed by another CA: the handshake fails
+    assert!(handshake(&mutual, pki.client_config(Some("rogue"))).await.is_err());
+
+    // Without a client CA no certificate is requested
+    assert_eq!(handshake(&pki.settings(false), pki.client_config(Some("client"))).await, Ok(0));
+
+    println!("✅ Mutual TLS: CA-signed client accepted, foreign client refused");
+}
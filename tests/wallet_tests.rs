@@ -0,0 +1,668 @@
+//! Wallet Tests for Divine AGI V16 (persistence, history, payment requests, node wallet)
+
+use divine_agi::consensus::{reward_address_for, ConsensusBlock, ProofOfConsciousness, ValidatorKey, BLOCK_REWARD_RSM, MAX_REORG_DEPTH};
+use divine_agi::genesis::{GenesisAllocation, GenesisConfig};
+use divine_agi::genome::GenomeBuilder;
+use divine_agi::history::{Direction, ExportFormat, ExportRow, HistoryFilter, TxKind, TxStatus, EXPORT_CSV_HEADER};
+use divine_agi::keystore::derive_validator_key;
+use divine_agi::exchange::{RSMExchange, SpendingPolicy, WalletEvent};
+use divine_agi::crypto::{derive_key, encrypt_aes_gcm};
+use divine_agi::wallet::{DivineWallet, PaymentRequest, DEFAULT_ACCOUNT_LABEL, LEGACY_KDF_ROUNDS, MNEMONIC_WORD_COUNTS, WALLET_KDF_ITERATIONS, WALLET_KDF_PBKDF2_SHA256};
+use sha2::{Digest, Sha256};
+
+fn wallet_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("divine_{}_{}.wallet", name, std::process::id()))
+}
+
+// ═══════════════════════════════════════════════════════════════
+// ENCRYPTED PERSISTENCE TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_wallet_save_load_roundtrip() {
+    let mut wallet = DivineWallet::new();
+    wallet.deposit(120.0);
+    wallet.add_block_reward(50.0, 7);
+    assert!(wallet.withdraw(20.0));
+    wallet.stake_genome(42);
+
+    let path = wallet_path("roundtrip");
+    wallet.save(&path, "correct horse").unwrap();
+
+    let raw = std::fs::read(&path).unwrap();
+    assert!(raw.starts_with(b"DIVWALLT"));
+    assert!(!String::from_utf8_lossy(&raw).contains(&wallet.address), "wallet file is not encrypted");
+
+    let loaded = DivineWallet::load(&path, "correct horse").unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded.address, wallet.address);
+    assert_eq!(loaded.rsm_balance, 150.0);
+    assert_eq!(loaded.rewards_earned, 50.0);
+    assert_eq!(loaded.staked_genomes, vec![42]);
+    assert_eq!(loaded.transactions, wallet.transactions);
+
+    println!("✅ Wallet survives save/load: {} | {} RSM | {} txs",
+             loaded.address, loaded.rsm_balance, loaded.transactions.len());
+}
+
+#[test]
+fn test_wallet_load_rejects_wrong_password() {
+    let mut wallet = DivineWallet::new();
+    wallet.deposit(1.0);
+
+    let path = wallet_path("wrong_password");
+    wallet.save(&path, "secret").unwrap();
+
+    let err = DivineWallet::load(&path, "not the secret").unwrap_err();
+    assert!(err.to_string().contains("Wrong password"), "{}", err);
+
+    // A flipped ciphertext byte fails authentication the same way
+    let mut raw = std::fs::read(&path).unwrap();
+    let last = raw.len() - 1;
+    raw[last] ^= 1;
+    std::fs::write(&path, &raw).unwrap();
+    assert!(DivineWallet::load(&path, "secret").is_err());
+
+    std::fs::remove_file(&path).unwrap();
+    println!("✅ Wrong password and tampered file rejected");
+}
+
+#[test]
+fn test_wallet_file_names_its_key_derivation() {
+    let mut wallet = DivineWallet::new();
+    wallet.deposit(3.0);
+    let path = wallet_path("kdf");
+    wallet.save(&path, "secret").unwrap();
+
+    // Version 2: PBKDF2-HMAC-SHA256 and its iterations follow the version
+    let raw = std::fs::read(&path).unwrap();
+    assert_eq!(u16::from_le_bytes([raw[8], raw[9]]), 2);
+    assert_eq!(raw[10], WALLET_KDF_PBKDF2_SHA256);
+    assert_eq!(u32::from_le_bytes(raw[11..15].try_into().unwrap()), WALLET_KDF_ITERATIONS);
+
+    // The temp file was renamed over the target, not left beside it
+    let name = path.file_name().unwrap().to_string_lossy().into_owned();
+    let leftovers = std::fs::read_dir(path.parent().unwrap()).unwrap()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(&format!(".{}.tmp-", name)))
+        .count();
+    assert_eq!(leftovers, 0);
+
+    // An unknown derivation, or iterations out of range, are refused
+    let mut unknown = raw.clone();
+    unknown[10] = 9;
+    std::fs::write(&path, &unknown).unwrap();
+    assert!(DivineWallet::load(&path, "secret").unwrap_err().to_string().contains("key derivation"));
+    let mut zero = raw.clone();
+    zero[11..15].copy_from_slice(&0u32.to_le_bytes());
+    std::fs::write(&path, &zero).unwrap();
+    assert!(DivineWallet::load(&path, "secret").unwrap_err().to_string().contains("iterations"));
+
+    // Version 1 files, salted SHA-256 rounds and no derivation fields, still load
+    let salt = [4u8; 16];
+    let mut stretched: [u8; 32] = Sha256::new().chain_update(salt).chain_update("secret").finalize().into();
+    for _ in 0..LEGACY_KDF_ROUNDS {
+        stretched = Sha256::new().chain_update(stretched).chain_update("secret").finalize().into();
+    }
+    let sealed = encrypt_aes_gcm(&derive_key(&stretched, b"DIVINE_WALLET_V1"), &serde_json::to_vec(&wallet).unwrap()).unwrap();
+    let legacy = [&b"DIVWALLT"[..], &1u16.to_le_bytes(), &salt, &sealed].concat();
+    std::fs::write(&path, &legacy).unwrap();
+    let loaded = DivineWallet::load(&path, "secret").unwrap();
+    assert_eq!((loaded.address.clone(), loaded.rsm_balance), (wallet.address.clone(), 3.0));
+    assert!(DivineWallet::load(&path, "wrong").is_err());
+
+    // Saving upgrades it
+    loaded.save(&path, "secret").unwrap();
+    assert_eq!(std::fs::read(&path).unwrap()[8], 2);
+    assert_eq!(DivineWallet::load(&path, "secret").unwrap().address, wallet.address);
+
+    std::fs::remove_file(&path).unwrap();
+    println!("✅ Wallet file: PBKDF2 named in the header, version 1 files still open");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// RECOVERY PHRASE TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_recovery_phrase_restores_addresses() {
+    let (mut wallet, phrase) = DivineWallet::generate(24).unwrap();
+    assert_eq!(phrase.split_whitespace().count(), 24);
+    assert!(wallet.is_recoverable());
+    let second = wallet.new_address().unwrap();
+    let third = wallet.new_address().unwrap();
+    assert_eq!(wallet.all_addresses().len(), 3);
+    assert_ne!(second, third);
+
+    // The phrase alone gives back the same addresses, in order
+    let mut recovered = DivineWallet::recover(&format!("  {}\n", phrase)).unwrap();
+    assert_eq!(recovered.address, wallet.address);
+    assert_eq!(recovered.new_address().unwrap(), second);
+    assert_eq!(recovered.new_address().unwrap(), third);
+
+    // The seed survives the encrypted file
+    let path = wallet_path("recoverable");
+    wallet.save(&path, "secret").unwrap();
+    let mut loaded = DivineWallet::load(&path, "secret").unwrap();
+    assert_eq!(loaded.all_addresses(), wallet.all_addresses());
+    assert_eq!(loaded.new_address().unwrap(), recovered.new_address().unwrap());
+    std::fs::remove_file(&path).unwrap();
+
+    for words in MNEMONIC_WORD_COUNTS {
+        assert_eq!(DivineWallet::generate(words).unwrap().1.split_whitespace().count(), words);
+    }
+    assert!(DivineWallet::generate(13).is_err());
+    // Valid words, wrong checksum word
+    assert!(DivineWallet::recover(&["abandon"; 12].join(" ")).is_err());
+    assert!(DivineWallet::recover(&format!("{} about", ["abandon"; 11].join(" "))).is_ok());
+    assert!(DivineWallet::recover("not a phrase").unwrap_err().contains("Invalid recovery phrase"));
+    assert!(DivineWallet::new().new_address().is_err(), "random wallets cannot derive");
+
+    println!("✅ Recovery phrase: {} restores {} addresses", wallet.address, wallet.all_addresses().len());
+}
+
+#[test]
+fn test_accounts_derived_listed_and_switched() {
+    let (mut wallet, phrase) = DivineWallet::generate(12).unwrap();
+    let default_address = wallet.address.clone();
+    wallet.deposit(10.0);
+
+    let archive = wallet.create_account("archive").unwrap();
+    assert_eq!((archive.index, archive.active), (1, false));
+    assert_ne!(archive.address, default_address);
+    assert!(wallet.create_account("archive").unwrap_err().contains("already exists"));
+    assert!(wallet.create_account("7").is_err() && wallet.create_account(" ").is_err());
+    wallet.create_account("ops").unwrap();
+    let labels: Vec<(u32, String, bool)> = wallet.accounts().into_iter().map(|a| (a.index, a.label, a.active)).collect();
+    assert_eq!(labels, vec![(0, DEFAULT_ACCOUNT_LABEL.into(), true), (1, "archive".into(), false), (2, "ops".into(), false)]);
+
+    // Switching swaps addresses and funds; the parked account keeps its own
+    wallet.switch_account("archive").unwrap();
+    assert_eq!((wallet.address.as_str(), wallet.account_index, wallet.rsm_balance), (archive.address.as_str(), 1, 0.0));
+    let archive_second = wallet.new_address().unwrap();
+    wallet.deposit(3.0);
+    assert!(wallet.switch_account("missing").is_err());
+    wallet.switch_account("0").unwrap();
+    assert_eq!((wallet.address.as_str(), wallet.rsm_balance), (default_address.as_str(), 10.0));
+    assert_eq!(wallet.accounts()[1].rsm_balance, 3.0);
+    assert_eq!(wallet.accounts()[1].addresses, 2);
+
+    // Accounts and the active one survive the file; the phrase derives the same ones
+    let path = wallet_path("accounts");
+    wallet.save(&path, "secret").unwrap();
+    let mut loaded = DivineWallet::load(&path, "secret").unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.accounts(), wallet.accounts());
+    let mut recovered = DivineWallet::recover(&phrase).unwrap();
+    assert_eq!(recovered.create_account("archive").unwrap().address, archive.address);
+    recovered.switch_account("archive").unwrap();
+    assert_eq!(recovered.new_address().unwrap(), archive_second);
+    loaded.switch_account("ops").unwrap();
+    assert_ne!(loaded.solana_keypair(loaded.account_index).unwrap().address(), wallet.solana_keypair(0).unwrap().address());
+
+    assert!(DivineWallet::new().create_account("archive").is_err(), "random wallets cannot derive");
+
+    println!("✅ Accounts: {} derived from one phrase, funds kept apart", wallet.accounts().len());
+}
+
+#[test]
+fn test_recovery_scans_the_chain() {
+    let (mut reference, phrase) = DivineWallet::generate(12).unwrap();
+    for _ in 0..3 {
+        reference.new_address().unwrap();
+    }
+    let (primary, fourth) = (reference.address.clone(), reference.addresses[2].clone());
+    let savings = reference.create_account("savings").unwrap().address;
+    let key = derive_validator_key(&phrase, 0).unwrap();
+    let validator = reward_address_for(&key.public_key_hex());
+
+    // Funds on the fourth address (a gap before it), account 1 and the validator key
+    let genesis = GenesisConfig {
+        min_fee_rsm: 1.0,
+        allocations: vec![
+            GenesisAllocation { address: fourth.clone(), amount_rsm: 100.0 },
+            GenesisAllocation { address: savings, amount_rsm: 50.0 },
+            GenesisAllocation { address: validator.clone(), amount_rsm: 1_000.0 },
+        ],
+        ..GenesisConfig::default()
+    };
+    let mut poc = ProofOfConsciousness::from_genesis(genesis).with_signer(key);
+    poc.reward_address = Some(primary.clone());
+    submit_genome(&mut poc);
+    poc.mine_block().unwrap();
+    submit_genome(&mut poc);
+
+    let wallet = DivineWallet::recover_from_chain(&phrase, &poc).unwrap();
+    assert_eq!(wallet.all_addresses(), reference.all_addresses().into_iter().take(4).collect::<Vec<_>>());
+    assert_eq!(wallet.validator_addresses, vec![validator.clone()]);
+    let balance: f64 = [&primary, &fourth, &validator].iter().map(|a| poc.wallet(a).map_or(0.0, |w| w.rsm_balance)).sum();
+    assert_eq!(wallet.rsm_balance, balance);
+    assert!(wallet.transactions.contains(&"ALLOCATION #0: +100.000000 RSM".to_string()), "{:?}", wallet.transactions);
+    assert!(wallet.transactions.iter().any(|t| t.starts_with("BLOCK REWARD #1: +")));
+    assert!(wallet.transactions.contains(&"FEE #1: -1.000000 RSM".to_string()));
+    assert_eq!(wallet.transactions.last().unwrap(), "FEE (pending): -1.000000 RSM", "pending entries last");
+
+    // Account 1 had a deposit, account 2 nothing: discovery stops there
+    let accounts = wallet.accounts();
+    assert_eq!(accounts.iter().map(|a| a.label.as_str()).collect::<Vec<_>>(), vec![DEFAULT_ACCOUNT_LABEL, "account 1"]);
+    assert_eq!((accounts[1].addresses, accounts[1].rsm_balance), (1, 50.0));
+
+    // An empty chain gives back the bare phrase wallet
+    let fresh = DivineWallet::recover_from_chain(&phrase, &ProofOfConsciousness::new()).unwrap();
+    assert_eq!((fresh.all_addresses(), fresh.accounts().len()), (vec![primary], 1));
+    assert!(fresh.validator_addresses.is_empty() && fresh.transactions.is_empty());
+
+    println!("✅ Recovery scan: {} RSM and {} entries found again", wallet.rsm_balance, wallet.transactions.len());
+}
+
+#[test]
+fn test_wallet_transfer_checks_and_records() {
+    let (mut wallet, _) = DivineWallet::generate(12).unwrap();
+    wallet.deposit(10.0);
+    let own = wallet.new_address().unwrap();
+    let mut exchange = RSMExchange::new();
+    exchange.balances.insert(wallet.spending_address().to_string(), 10.0);
+
+    let record = wallet.transfer(&mut exchange, "divine_friend", 4.0, Some("rent")).unwrap();
+    assert_eq!(record, "TRANSFER → divine_friend: -4.000000 RSM (rent)");
+    assert_eq!(wallet.rsm_balance, 6.0);
+    assert_eq!(wallet.transactions.last(), Some(&record));
+    assert_eq!(exchange.get_balance("divine_friend"), 4.0, "the recipient is credited");
+    assert_eq!(exchange.get_balance(&wallet.address), 6.0);
+
+    assert!(wallet.transfer(&mut exchange, "divine_friend", 6.5, None).unwrap_err().contains("Insufficient"));
+    assert!(wallet.transfer(&mut exchange, &own, 1.0, None).unwrap_err().contains("belongs to this wallet"));
+    assert!(wallet.transfer(&mut exchange, "bc1qfriend", 1.0, None).is_err());
+    for amount in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+        assert!(wallet.transfer(&mut exchange, "divine_friend", amount, None).is_err(), "{}", amount);
+    }
+
+    // A transfer the ledger refuses leaves the wallet file alone
+    exchange.set_policy(&wallet.address, SpendingPolicy { daily_limit_rsm: Some(5.0), ..Default::default() });
+    assert!(wallet.transfer(&mut exchange, "divine_friend", 2.0, None).is_err());
+    assert_eq!(wallet.rsm_balance, 6.0, "failed transfers change nothing");
+    assert_eq!(wallet.transactions.len(), 2);
+    assert_eq!(exchange.get_balance("divine_friend"), 4.0);
+
+    println!("✅ Wallet transfer: {}", record);
+}
+
+// ═══════════════════════════════════════════════════════════════
+// TRANSACTION HISTORY TESTS
+// ═══════════════════════════════════════════════════════════════
+
+/// 1 RSM fee network whose node key pays fees from a premined wallet
+fn fee_node() -> (ProofOfConsciousness, ValidatorKey, String) {
+    let key = ValidatorKey::generate();
+    let payer = reward_address_for(&key.public_key_hex());
+    let genesis = GenesisConfig {
+        min_fee_rsm: 1.0,
+        allocations: vec![GenesisAllocation { address: payer.clone(), amount_rsm: 1_000.0 }],
+        ..GenesisConfig::default()
+    };
+    let mut poc = ProofOfConsciousness::from_genesis(genesis).with_signer(key.clone());
+    poc.reward_address = Some("divine_history_miner".to_string());
+    (poc, key, payer)
+}
+
+fn submit_genome(poc: &mut ProofOfConsciousness) {
+    let mut genome = GenomeBuilder::random().whale_mode().build_storage();
+    genome.consciousness = poc.min_consciousness + 100;
+    genome.db_id = Some(1);
+    poc.submit(&genome).unwrap();
+}
+
+#[test]
+fn test_history_lists_chain_transactions() {
+    let (mut poc, _, payer) = fee_node();
+    submit_genome(&mut poc);
+    poc.mine_block().unwrap();
+    submit_genome(&mut poc);
+
+    let history = poc.history(&payer, &HistoryFilter::default());
+    let kinds: Vec<(TxKind, TxStatus)> = history.iter().map(|tx| (tx.kind, tx.status)).collect();
+    assert_eq!(kinds, vec![
+        (TxKind::Allocation, TxStatus::Finalized),
+        (TxKind::FeePaid, TxStatus::Confirmed),
+        (TxKind::FeePaid, TxStatus::Pending),
+    ]);
+    assert_eq!(history[1].block_index, Some(1));
+    assert_eq!(history[1].timestamp, poc.chain[1].timestamp);
+    assert_eq!(history[1].counterparty.as_deref(), Some("divine_history_miner"));
+
+    let miner = poc.history("divine_history_miner", &HistoryFilter::default());
+    assert_eq!(miner.len(), 2);
+    assert_eq!((miner[0].kind, miner[0].amount_rsm), (TxKind::FeeCollected, 1.0));
+    assert_eq!(miner[0].counterparty.as_deref(), Some(payer.as_str()));
+    assert_eq!((miner[1].kind, miner[1].amount_rsm), (TxKind::BlockReward, BLOCK_REWARD_RSM));
+
+    // The mempool fee is mined and becomes confirmed
+    poc.mine_block().unwrap();
+    let history = poc.history(&payer, &HistoryFilter::default());
+    assert_eq!(history[2].status, TxStatus::Confirmed);
+    assert_eq!(history[2].block_index, Some(2));
+
+    println!("✅ History: {} payer and {} miner transactions", history.len(), miner.len());
+}
+
+#[test]
+fn test_history_filters_and_finality() {
+    let (mut poc, key, payer) = fee_node();
+    // One minute apart, so difficulty stays put
+    while poc.current_block_height < MAX_REORG_DEPTH + 1 {
+        submit_genome(&mut poc);
+        let entries = poc.mempool.take(poc.max_block_entries);
+        let timestamp = poc.latest_block().timestamp + 60;
+        let coinbase = poc.coinbase("divine_history_miner");
+        let mut block = ConsensusBlock::mine_at(poc.latest_block(), entries, poc.difficulty, coinbase, timestamp);
+        block.sign(&key);
+        poc.add_block(block).unwrap();
+    }
+
+    let all = poc.history(&payer, &HistoryFilter::default());
+    assert_eq!(all.len(), MAX_REORG_DEPTH as usize + 2);
+
+    let outgoing = poc.history(&payer, &HistoryFilter { direction: Some(Direction::Out), ..Default::default() });
+    assert_eq!(outgoing.len(), MAX_REORG_DEPTH as usize + 1);
+
+    let large = poc.history(&payer, &HistoryFilter { min_amount_rsm: Some(500.0), ..Default::default() });
+    assert_eq!(large.len(), 1);
+    assert_eq!(large[0].kind, TxKind::Allocation);
+
+    let finalized = poc.history(&payer, &HistoryFilter { status: Some(TxStatus::Finalized), ..Default::default() });
+    assert_eq!(finalized.len(), 2, "genesis allocation and block #1 are final");
+    assert_eq!(finalized[1].block_index, Some(poc.finalized_height()));
+
+    let to_miner = HistoryFilter { counterparty: Some("divine_history_miner".into()), ..Default::default() };
+    assert_eq!(poc.history(&payer, &to_miner).len(), MAX_REORG_DEPTH as usize + 1);
+    assert!(poc.history(&payer, &HistoryFilter { counterparty: Some("divine_nobody".into()), ..Default::default() }).is_empty());
+
+    let latest = poc.latest_block().timestamp;
+    let recent = poc.history(&payer, &HistoryFilter { from_timestamp: Some(latest), ..Default::default() });
+    assert!(!recent.is_empty() && recent.iter().all(|tx| tx.timestamp >= latest));
+    assert!(poc.history(&payer, &HistoryFilter { to_timestamp: Some(0), ..Default::default() }).is_empty());
+
+    println!("✅ History filters: {} total, {} finalized", all.len(), finalized.len());
+}
+
+#[test]
+fn test_history_export_running_balances() {
+    let (mut poc, _, payer) = fee_node();
+    for _ in 0..2 {
+        submit_genome(&mut poc);
+        poc.mine_block().unwrap();
+    }
+    submit_genome(&mut poc);
+
+    let csv = poc.export_history(&payer, ExportFormat::Csv, &HistoryFilter::default());
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], EXPORT_CSV_HEADER);
+    assert_eq!(lines.len(), 4, "allocation and two fees; the pending fee is not exported");
+    assert!(lines[1].contains(",Allocation,In,1000,,Finalized,1000"), "{}", lines[1]);
+    assert!(lines[3].ends_with(&format!("FeePaid,Out,1,divine_history_miner,Confirmed,{}", 998)), "{}", lines[3]);
+    assert!(lines[3].contains(&poc.chain[2].hash));
+
+    let json = poc.export_history(&payer, ExportFormat::Json, &HistoryFilter::default());
+    let rows: Vec<ExportRow> = serde_json::from_str(&json).unwrap();
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows.last().unwrap().balance_rsm, poc.wallet(&payer).unwrap().rsm_balance);
+
+    // A range keeps the balances carried from before it
+    let since_block_2 = HistoryFilter { from_timestamp: Some(poc.chain[2].timestamp), ..Default::default() };
+    let tail = poc.export_rows(&payer, &since_block_2);
+    assert!(tail.iter().all(|r| r.tx.kind == TxKind::FeePaid));
+    assert_eq!(tail.last().unwrap().balance_rsm, 998.0);
+
+    println!("✅ History export: {} CSV rows", lines.len() - 1);
+}
+
+// ═══════════════════════════════════════════════════════════════
+// PAYMENT REQUEST TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_payment_uri_roundtrip() {
+    let wallet = DivineWallet::with_address("divine_merchant");
+    let request = wallet.payment_request(2.5, Some("archive fee #7 & más"));
+
+    let uri = request.to_uri();
+    assert_eq!(uri, "divine:divine_merchant?amount=2.5&memo=archive%20fee%20%237%20%26%20m%C3%A1s");
+    assert_eq!(PaymentRequest::parse(&uri).unwrap(), request);
+
+    let plain = wallet.payment_request(100.0, None);
+    assert_eq!(plain.to_uri(), "divine:divine_merchant?amount=100");
+    assert_eq!(PaymentRequest::parse(&plain.to_uri()).unwrap(), plain);
+
+    for bad in [
+        "bitcoin:divine_merchant?amount=1",
+        "divine:?amount=1",
+        "divine:divine_merchant",
+        "divine:divine_merchant?amount=-1",
+        "divine:divine_merchant?amount=NaN",
+        "divine:divine_merchant?amount=1&memo=%G0",
+        "divine:divine_merchant?amount=1&label=x",
+    ] {
+        assert!(PaymentRequest::parse(bad).is_err(), "{} should not parse", bad);
+    }
+
+    println!("✅ Payment URI: {}", uri);
+}
+
+#[cfg(feature = "qr")]
+#[test]
+fn test_payment_qr_code_roundtrip() {
+    let request = DivineWallet::with_address("divine_merchant").payment_request(2.5, Some("archive fee #7 & más"));
+    let png = request.to_qr_png(4).unwrap();
+    assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+
+    // Scan the image back: the same URI, the same request
+    let image = image::load_from_memory(&png).unwrap().to_luma8();
+    let mut prepared = rqrr::PreparedImage::prepare(image);
+    let grids = prepared.detect_grids();
+    assert_eq!(grids.len(), 1);
+    let (_, uri) = grids[0].decode().unwrap();
+    assert_eq!(uri, request.to_uri());
+    assert_eq!(PaymentRequest::parse(&uri).unwrap(), request);
+
+    println!("✅ Payment QR code: {} bytes of PNG scan back to {}", png.len(), uri);
+}
+
+#[test]
+fn test_exchange_pays_payment_request() {
+    let mut exchange = RSMExchange::new();
+    exchange.balances.insert("divine_payer".to_string(), 10.0);
+
+    let uri = DivineWallet::with_address("divine_merchant").payment_request(4.0, Some("order-42")).to_uri();
+    let request = PaymentRequest::parse(&uri).unwrap();
+
+    let tx = exchange.pay_request("divine_payer", &request).unwrap();
+    assert_eq!(tx.to_address, "divine_merchant");
+    assert_eq!(tx.amount_rsm, 4.0);
+    assert_eq!(tx.memo.as_deref(), Some("order-42"));
+    assert_eq!(exchange.balances["divine_payer"], 6.0);
+    assert_eq!(exchange.balances["divine_merchant"], 4.0);
+
+    let too_much = DivineWallet::with_address("divine_merchant").payment_request(7.0, None);
+    assert_eq!(exchange.pay_request("divine_payer", &too_much).unwrap_err(), "Insufficient balance");
+
+    println!("✅ Payment request paid: {} RSM, memo {:?}", tx.amount_rsm, tx.memo);
+}
+
+// ═══════════════════════════════════════════════════════════════
+// SPENDING POLICY TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_spending_policy_limits_and_allow_list() {
+    let mut exchange = RSMExchange::new();
+    exchange.balances.insert("divine_pipeline".to_string(), 100.0);
+    exchange.set_policy("divine_pipeline", SpendingPolicy {
+        daily_limit_rsm: Some(10.0),
+        allowed_recipients: Some(vec!["divine_archive".to_string()]),
+        confirm_above_rsm: None,
+    });
+
+    let err = exchange.transfer_checked("divine_pipeline", "divine_stranger", 1.0).unwrap_err();
+    assert!(err.contains("not allow-listed"), "{}", err);
+
+    exchange.transfer_checked("divine_pipeline", "divine_archive", 6.0).unwrap();
+    exchange.transfer_checked("divine_pipeline", "divine_archive", 4.0).unwrap();
+    assert_eq!(exchange.spent_last_day("divine_pipeline"), 10.0);
+
+    let err = exchange.transfer_checked("divine_pipeline", "divine_archive", 0.5).unwrap_err();
+    assert!(err.contains("Daily limit"), "{}", err);
+    assert_eq!(exchange.balances["divine_pipeline"], 90.0);
+
+    // Other wallets are not affected
+    exchange.balances.insert("divine_free".to_string(), 5.0);
+    assert!(exchange.transfer("divine_free", "divine_stranger", 5.0).is_some());
+
+    println!("✅ Spending policy: {} RSM sent today", exchange.spent_last_day("divine_pipeline"));
+}
+
+#[test]
+fn test_transfer_rejects_negative_and_nan_amounts() {
+    let mut exchange = RSMExchange::new();
+    exchange.balances.insert("divine_pipeline".to_string(), 100.0);
+    exchange.balances.insert("divine_archive".to_string(), 50.0);
+    exchange.set_policy("divine_pipeline", SpendingPolicy {
+        daily_limit_rsm: Some(10.0),
+        confirm_above_rsm: Some(5.0),
+        ..Default::default()
+    });
+    exchange.transfer_checked("divine_pipeline", "divine_archive", 5.0).unwrap();
+
+    // A negative amount would pull RSM from the recipient and lower the day's spend
+    for amount in [-20.0, f64::NAN, f64::INFINITY, f64::NEG_INFINITY, 0.0] {
+        let err = exchange.transfer_checked("divine_pipeline", "divine_archive", amount).unwrap_err();
+        assert!(err.contains("Invalid amount"), "{}: {}", amount, err);
+    }
+    assert_eq!((exchange.balances["divine_pipeline"], exchange.balances["divine_archive"]), (95.0, 55.0));
+    assert_eq!(exchange.spent_last_day("divine_pipeline"), 5.0);
+    assert_eq!(exchange.transactions.len(), 1);
+
+    // The daily limit still holds afterwards
+    exchange.transfer_checked("divine_pipeline", "divine_archive", 5.0).unwrap();
+    assert!(exchange.transfer_checked("divine_pipeline", "divine_archive", 1.0).unwrap_err().contains("Daily limit"));
+
+    println!("✅ Spending policy: negative, NaN and infinite amounts refused");
+}
+
+#[test]
+fn test_spending_policy_confirmation_callback() {
+    let mut exchange = RSMExchange::new();
+    exchange.balances.insert("divine_treasury".to_string(), 1_000.0);
+    exchange.set_policy("divine_treasury", SpendingPolicy {
+        confirm_above_rsm: Some(100.0),
+        ..Default::default()
+    });
+
+    // Small transfers pass, large ones need a callback
+    exchange.transfer_checked("divine_treasury", "divine_ops", 100.0).unwrap();
+    let err = exchange.transfer_checked("divine_treasury", "divine_ops", 200.0).unwrap_err();
+    assert!(err.contains("need confirmation"), "{}", err);
+
+    exchange.set_confirmation("divine_treasury", |proposal| {
+        proposal.to == "divine_ops" && proposal.amount_rsm <= 500.0
+    });
+    exchange.transfer_checked("divine_treasury", "divine_ops", 200.0).unwrap();
+    assert!(exchange.transfer_checked("divine_treasury", "divine_ops", 600.0).is_err());
+    assert!(exchange.transfer_checked("divine_treasury", "divine_thief", 200.0).is_err());
+    assert_eq!(exchange.balances["divine_treasury"], 700.0);
+
+    println!("✅ Confirmation callback approved {} RSM", 200.0);
+}
+
+// ═══════════════════════════════════════════════════════════════
+// WALLET EVENT TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_wallet_events_on_transfer() {
+    let mut exchange = RSMExchange::new();
+    exchange.balances.insert("divine_archiver".to_string(), 10.0);
+    exchange.set_low_balance_alert("divine_archiver", 5.0);
+    let mut events = exchange.subscribe();
+
+    let tx = exchange.transfer_checked("divine_archiver", "divine_layer", 4.0).unwrap();
+    let received: Vec<WalletEvent> = std::iter::from_fn(|| events.try_recv().ok()).collect();
+    assert_eq!(received, vec![
+        WalletEvent::BalanceChanged { wallet: "divine_archiver".into(), previous_rsm: 10.0, balance_rsm: 6.0 },
+        WalletEvent::BalanceChanged { wallet: "divine_layer".into(), previous_rsm: 0.0, balance_rsm: 4.0 },
+        WalletEvent::TransferConfirmed(tx),
+    ]);
+
+    // Crossing the threshold alerts once, further spending does not
+    exchange.transfer_checked("divine_archiver", "divine_layer", 2.0).unwrap();
+    exchange.transfer_checked("divine_archiver", "divine_layer", 1.0).unwrap();
+    let alerts: Vec<WalletEvent> = std::iter::from_fn(|| events.try_recv().ok())
+        .filter(|e| matches!(e, WalletEvent::LowBalance { .. }))
+        .collect();
+    assert_eq!(alerts, vec![
+        WalletEvent::LowBalance { wallet: "divine_archiver".into(), balance_rsm: 4.0, threshold_rsm: 5.0 },
+    ]);
+
+    // Rejected transfers emit nothing
+    assert!(exchange.transfer_checked("divine_archiver", "divine_layer", 100.0).is_err());
+    assert!(events.try_recv().is_err());
+
+    println!("✅ Wallet events: low balance alert at {} RSM", 4.0);
+}
+
+// ═══════════════════════════════════════════════════════════════
+// NODE WALLET TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_node_addresses_and_signed_transfer() {
+    let mut poc = ProofOfConsciousness::new();
+    let key = poc.validator_public_key().unwrap();
+    let addresses = poc.node_addresses();
+    assert_eq!(addresses.len(), 1);
+    assert_eq!((addresses[0].role.as_str(), addresses[0].address.clone()), ("validator", reward_address_for(&key)));
+
+    poc.reward_address = Some("DIVINE_rewards".into());
+    let addresses = poc.node_addresses();
+    assert_eq!(addresses[1].role, "rewards");
+    poc.reward_address = Some(reward_address_for(&key));
+    assert_eq!(poc.node_addresses().len(), 1, "reward address equal to the validator wallet listed once");
+
+    // Transfers out of the node wallet still go through its policy
+    let from = &addresses[0].address;
+    let mut exchange = RSMExchange::new();
+    exchange.balances.insert(from.clone(), 10.0);
+    exchange.set_policy(from, SpendingPolicy { daily_limit_rsm: Some(1.0), ..Default::default() });
+    assert!(exchange.transfer_with_memo(from, "DIVINE_ops", 2.0, None).is_err());
+    let tx = exchange.transfer_with_memo(from, "DIVINE_ops", 0.5, Some("payroll".into())).unwrap();
+
+    let (public_key, signature) = poc.sign_as_validator(tx.hash.as_bytes()).unwrap();
+    assert_eq!(public_key, key);
+    assert!(divine_agi::crypto::verify_signature(
+        &hex::decode(&public_key).unwrap(), tx.hash.as_bytes(), &hex::decode(&signature).unwrap(),
+    ));
+
+    println!("✅ Node wallet {} transfer signed by its validator key", from);
+}
+
+#[test]
+fn test_node_transfer_moves_the_exchange_ledger() {
+    // /api/wallet/transfer debits the exchange; the chain balance it reports
+    // next to it only moves with fees and stake ops
+    let (poc, _, from) = fee_node();
+    let chain_before = poc.wallet(&from).unwrap().rsm_balance;
+    assert_eq!(chain_before, 1_000.0);
+
+    let mut exchange = RSMExchange::new();
+    assert!(exchange.transfer_with_memo(&from, "DIVINE_ops", 1.0, None).is_err(), "chain RSM is not spendable on the exchange");
+
+    exchange.balances.insert(from.clone(), 10.0);
+    let tx = exchange.transfer_with_memo(&from, "DIVINE_ops", 4.0, None).unwrap();
+    assert_eq!(tx.amount_rsm, 4.0);
+    assert_eq!(exchange.get_balance(&from), 6.0);
+    assert_eq!(exchange.get_balance("DIVINE_ops"), 4.0);
+    assert_eq!(poc.wallet(&from).unwrap().rsm_balance, chain_before);
+    assert!(poc.wallet("DIVINE_ops").is_none());
+
+    println!("✅ Node transfer moved exchange RSM, chain balance {} untouched", chain_before);
+}
@@ -0,0 +1,81 @@
+//! Watch Tests for Divine AGI V16 (filters and event lines)
+
+use divine_agi::watch::{matches_all, FilterOp, WatchEvent, WatchFilter, WatchTopic};
+use serde_json::json;
+
+fn genome_row() -> serde_json::Value {
+    json!({ "id": 42, "dna": "TAATAAGGGTGCGAGTTATGGGTAAAT", "consciousness": 5120, "p53_copies": 40,
+            "telomere_length": 15000, "tg_ratio": 1.25, "created_at": 1_700_000_000 })
+}
+
+// ═══════════════════════════════════════════════════════════════
+// FILTER TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_filter_parse() {
+    let filter = WatchFilter::parse("consciousness >= 5000").unwrap();
+    assert_eq!(filter, WatchFilter { field: "consciousness".into(), op: FilterOp::Gte, value: "5000".into() });
+    assert_eq!(WatchFilter::parse("layer!=Bitcoin").unwrap().op, FilterOp::Ne);
+    assert_eq!(WatchFilter::parse("p53_copies<=20").unwrap().op, FilterOp::Lte);
+    assert_eq!(WatchFilter::parse("producer~02ab").unwrap().op, FilterOp::Contains);
+    // The first operator splits; the rest belongs to the value
+    assert_eq!(WatchFilter::parse("tx_hash=ln_a=b").unwrap().value, "ln_a=b");
+
+    for bad in ["consciousness", ">=5000", "layer=", ""] {
+        assert!(WatchFilter::parse(bad).is_err(), "{:?} should not parse", bad);
+    }
+
+    println!("✅ Watch: filter expressions parsed");
+}
+
+#[test]
+fn test_filter_matches() {
+    let row = genome_row();
+    let passes = |expr: &str| WatchFilter::parse(expr).unwrap().matches(&row);
+    assert!(passes("consciousness>=5000") && passes("consciousness>5119") && passes("consciousness=5120"));
+    assert!(!passes("consciousness<5000") && !passes("consciousness!=5120"));
+    assert!(passes("tg_ratio<1.5"), "decimals compare numerically");
+    assert!(passes("dna~gggtgc"), "text is case-insensitive");
+    assert!(!passes("dna>AAAA"), "ordering needs numbers");
+    assert!(!passes("layer=bitcoin"), "a missing column never matches");
+
+    let archive = json!({ "id": 7, "genome_id": 42, "layer": "Bitcoin", "tx_hash": null });
+    assert!(WatchFilter::parse("layer=bitcoin").unwrap().matches(&archive));
+    assert!(!WatchFilter::parse("tx_hash~ln").unwrap().matches(&archive));
+
+    let filters = [WatchFilter::parse("consciousness>=5000").unwrap(), WatchFilter::parse("p53_copies=40").unwrap()];
+    assert!(matches_all(&filters, &row));
+    assert!(!matches_all(&filters, &json!({ "consciousness": 6000, "p53_copies": 20 })));
+    assert!(matches_all(&[], &row));
+
+    println!("✅ Watch: filters matched against rows");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// EVENT TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_event_lines() {
+    let line = WatchTopic::Genomes.describe(&genome_row());
+    assert!(line.contains("genome #42") && line.contains("consciousness 5120") && line.contains("p53 40"), "{}", line);
+
+    let block = json!({ "block_index": 12, "hash": "00000abcdef0123456789abcdef", "difficulty": 4,
+                        "attested_consciousness": 5120, "reward_rsm": 50.0, "producer": "02deadbeefcafebabe00ff" });
+    let line = WatchTopic::Blocks.describe(&block);
+    assert!(line.contains("block #12 00000abcdef01234…") && line.contains("difficulty 4"), "{}", line);
+
+    let line = WatchTopic::Archives.describe(&json!({ "id": 7, "genome_id": 42, "layer": "Bitcoin", "tx_hash": null }));
+    assert!(line.contains("archive #7 genome #42 on Bitcoin | tx -"), "{}", line);
+
+    // Every topic has its own table and channel
+    let channels: std::collections::HashSet<_> = WatchTopic::ALL.iter().map(|t| t.channel()).collect();
+    assert_eq!(channels.len(), WatchTopic::ALL.len());
+    assert!(WatchTopic::Genomes.omitted_columns().contains(&"hash"));
+
+    let event = WatchEvent { topic: WatchTopic::Archives, row: json!({ "id": 7 }) };
+    assert_eq!(serde_json::to_string(&event).unwrap(), r#"{"topic":"archives","row":{"id":7}}"#);
+
+    println!("✅ Watch: event lines and JSON");
+}
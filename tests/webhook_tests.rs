@@ -0,0 +1,157 @@
+//! Webhook Tests for Divine AGI V16 (signatures, retries, event matching)
+
+use std::net::IpAddr;
+
+use divine_agi::webhooks::{
+    generate_secret, is_public_ip, sign, validate_url, verify_signature, DeliveryStatus, Webhook, WebhookDelivery, WebhookEvent,
+    WebhookEventKind, WebhookTargets, SIGNATURE_TOLERANCE_SECS, WEBHOOK_RETRY_DELAYS_SECS,
+};
+
+fn delivery() -> WebhookDelivery {
+    let event = WebhookEvent { kind: WebhookEventKind::BlockAdded, data: serde_json::json!({ "index": 7 }), created_at: 1_700_000_000 };
+    WebhookDelivery {
+        id: 3,
+        webhook_id: 1,
+        event: event.kind,
+        payload: WebhookDelivery::payload(3, &event),
+        status: DeliveryStatus::Pending,
+        attempts: 0,
+        response_status: None,
+        error: None,
+        next_attempt_at: None,
+        created_at: 1_700_000_000,
+        updated_at: 1_700_000_000,
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════
+// SIGNATURE TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn test_signatures_verify_and_reject_tampering() {
+    let secret = generate_secret();
+    assert!(secret.starts_with("whsec_") && secret.len() == 6 + 64);
+    assert_ne!(secret, generate_secret());
+
+    let body = delivery().payload.into_bytes();
+    let now = 1_700_000_100;
+    let header = sign(&secret, now, &body);
+    assert!(header.starts_with(&format!("t={},v1=", now)));
+    assert!(verify_signature(&secret, &header, &body, now));
+    assert!(verify_signature(&secret, &header, &body, now + SIGNATURE_TOLERANCE_SECS));
+
+    // Wrong secret, altered body, replayed too late, or malformed header
+    assert!(!verify_signature(&generate_secret(), &header, &body, now));
+    assert!(!verify_signature(&secret, &header, b"{\"id\":4}", now));
+    assert!(!verify_signature(&secret, &header, &body, now + SIGNATURE_TOLERANCE_SECS + 1));
+    assert!(!verify_signature(&secret, "v1=00", &body, now));
+    // The timestamp is signed too
+    let forged = header.replacen(&now.to_string(), &(now + 1).to_string(), 1);
+    assert!(!verify_signature(&secret, &forged, &body, now));
+
+    println!("✅ Webhook signatures: HMAC over timestamp and body, tampering rejected");
+}
+
+#[tokio::test]
+async fn test_webhook_urls_and_events() {
+    let targets = WebhookTargets::default();
+    assert!(validate_url("https://93.184.216.34/divine", &targets).await.is_ok());
+    assert!(validate_url("ftp://example.com/hook", &targets).await.is_err());
+    assert!(validate_url("/relative/path", &targets).await.is_err());
+
+    for kind in WebhookEventKind::ALL {
+        assert_eq!(WebhookEventKind::from_name(kind.name()), Some(kind));
+        assert_eq!(serde_json::to_value(kind).unwrap(), kind.name());
+    }
+    assert_eq!(WebhookEventKind::from_name("block.removed"), None);
+
+    let webhook = Webhook {
+        id: 1,
+        url: "https://hooks.example.com".into(),
+        events: vec![WebhookEventKind::ArchiveConfirmed],
+        secret: generate_secret(),
+        key_id: 9,
+        created_at: 0,
+    };
+    assert!(webhook.wants(WebhookEventKind::ArchiveConfirmed));
+    assert!(!webhook.wants(WebhookEventKind::BlockAdded));
+    // The secret never appears in listings
+    assert!(serde_json::to_value(&webhook).unwrap().get("secret").is_none());
+
+    let payload: serde_json::Value = serde_json::from_str(&delivery().payload).unwrap();
+    assert_eq!(payload["id"], 3);
+    assert_eq!(payload["event"], "block.added");
+    assert_eq!(payload["data"]["index"], 7);
+
+    println!("✅ Webhook URLs validated, events matched by name");
+}
+
+// ═══════════════════════════════════════════════════════════════
+// RETRY TESTS
+// ═══════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_internal_targets_refused_unless_allowed() {
+    let targets = WebhookTargets::default();
+    for url in [
+        "http://10.0.0.5:8080/hook",
+        "http://127.0.0.1/hook",
+        "http://169.254.169.254/latest/meta-data",
+        "http://[::1]:9000/hook",
+        "http://[::ffff:192.168.1.1]/hook",
+        "http://0.0.0.0/hook",
+    ] {
+        let err = validate_url(url, &targets).await.unwrap_err();
+        assert!(err.contains("non-public"), "{}: {}", url, err);
+    }
+    // Names are checked by what they resolve to
+    assert!(validate_url("http://localhost:8080/hook", &targets).await.unwrap_err().contains("non-public"));
+
+    for ip in ["100.64.0.1", "172.16.4.4", "192.168.0.1", "fc00::1", "fe80::1", "224.0.0.1"] {
+        assert!(!is_public_ip(ip.parse::<IpAddr>().unwrap()), "{}", ip);
+    }
+    for ip in ["93.184.216.34", "100.128.0.1", "172.32.0.1", "2606:4700::1111"] {
+        assert!(is_public_ip(ip.parse::<IpAddr>().unwrap()), "{}", ip);
+    }
+
+    // The allow-list opens exactly the hosts it names
+    let targets = WebhookTargets { allowed_hosts: vec!["10.0.0.5".into(), "localhost".into(), "::1".into()] };
+    assert!(validate_url("http://10.0.0.5:8080/hook", &targets).await.is_ok());
+    assert!(validate_url("http://LOCALHOST:8080/hook", &targets).await.is_ok());
+    assert!(validate_url("http://[::1]:9000/hook", &targets).await.is_ok());
+    assert!(validate_url("http://10.0.0.6:8080/hook", &targets).await.is_err());
+
+    println!("✅ Webhooks: private, loopback and link-local targets refused unless allowed");
+}
+
+#[test]
+fn test_deliveries_retry_then_fail() {
+    let now = 1_700_000_000;
+    let mut failing = delivery();
+    for (attempt, delay) in WEBHOOK_RETRY_DELAYS_SECS.iter().enumerate() {
+        let outcome = if attempt % 2 == 0 { Ok(503) } else { Err("connection refused".to_string()) };
+        assert_eq!(failing.record_attempt(outcome, now), Some(*delay));
+        assert_eq!(failing.status, DeliveryStatus::Pending);
+        assert_eq!(failing.next_attempt_at, Some(now + delay));
+    }
+    assert_eq!(failing.response_status, None, "last attempt got no answer");
+    assert_eq!(failing.record_attempt(Ok(500), now), None);
+    assert_eq!(failing.status, DeliveryStatus::Failed);
+    assert_eq!(failing.attempts as usize, WEBHOOK_RETRY_DELAYS_SECS.len() + 1);
+    assert_eq!(failing.response_status, Some(500));
+    assert_eq!(failing.next_attempt_at, None);
+
+    // A retry that lands clears the error
+    let mut flaky = delivery();
+    assert!(flaky.record_attempt(Err("timed out".into()), now).is_some());
+    assert_eq!(flaky.record_attempt(Ok(204), now + 10), None);
+    assert_eq!(flaky.status, DeliveryStatus::Delivered);
+    assert_eq!((flaky.attempts, flaky.response_status, flaky.error.clone()), (2, Some(204), None));
+    assert_eq!(flaky.updated_at, now + 10);
+
+    // Redirects are not followed as success
+    assert!(delivery().record_attempt(Ok(301), now).is_some());
+
+    println!("✅ Webhook retries: {} attempts, then failed", WEBHOOK_RETRY_DELAYS_SECS.len() + 1);
+}